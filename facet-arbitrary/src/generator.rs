@@ -0,0 +1,374 @@
+use core::ops::Range;
+use std::borrow::Cow;
+
+use facet_core::{Def, Facet, Field, SequenceType, Shape, Type, UserType};
+use facet_reflect::{HeapValue, ReflectError, ScalarType, Wip};
+
+use crate::{GenerateError, GenerateErrorKind, Source};
+
+/// Generates random values of any shape, pulling randomness from a [`Source`]
+///
+/// The defaults keep values small enough to be readable when a test fails: collections
+/// and strings have up to 8 elements, options are `Some` half of the time, and past a
+/// depth of 6, options are always `None`, collections are empty, and enums pick
+/// field-less variants when they have any.
+pub struct Generator<S> {
+    source: S,
+    some_probability: f64,
+    collection_len: Range<usize>,
+    string_len: Range<usize>,
+    max_depth: usize,
+    max_attempts: usize,
+    text_hook: Option<TextHook>,
+}
+
+/// A function that gets a say in every scalar a [`Generator`] generates: when it returns some
+/// text, the scalar is parsed from it instead of being generated at random.
+pub type TextHook = Box<dyn FnMut(Slot, &mut dyn Source) -> Option<String>>;
+
+/// The scalar about to be generated, as seen by a [`TextHook`]
+#[derive(Debug, Clone, Copy)]
+pub struct Slot {
+    /// The shape of the scalar
+    pub shape: &'static Shape,
+
+    /// The struct or enum variant field the scalar is for, if any. Options, lists and arrays
+    /// pass it on to their contents, so the elements of a `Vec<String>` field see it too.
+    pub field: Option<&'static Field>,
+}
+
+impl<S> core::fmt::Debug for Generator<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Generator")
+            .field("some_probability", &self.some_probability)
+            .field("collection_len", &self.collection_len)
+            .field("string_len", &self.string_len)
+            .field("max_depth", &self.max_depth)
+            .field("max_attempts", &self.max_attempts)
+            .field("text_hook", &self.text_hook.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Source> Generator<S> {
+    /// Creates a generator with the default settings
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            some_probability: 0.5,
+            collection_len: 0..9,
+            string_len: 0..9,
+            max_depth: 6,
+            max_attempts: 16,
+            text_hook: None,
+        }
+    }
+
+    /// Sets how likely an `Option` is to be `Some` (between 0 and 1)
+    pub fn some_probability(mut self, probability: f64) -> Self {
+        self.some_probability = probability;
+        self
+    }
+
+    /// Sets the range lists and maps pick their length from (arrays always have their own length)
+    pub fn collection_len(mut self, len: Range<usize>) -> Self {
+        self.collection_len = len;
+        self
+    }
+
+    /// Sets the range strings pick their length (in `char`s) from
+    pub fn string_len(mut self, len: Range<usize>) -> Self {
+        self.string_len = len;
+        self
+    }
+
+    /// Sets the depth past which the generator stops growing values
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets how many values to try before giving up on one that upholds its invariants
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Installs a [`TextHook`], replacing any previous one
+    pub fn text_hook(
+        mut self,
+        hook: impl FnMut(Slot, &mut dyn Source) -> Option<String> + 'static,
+    ) -> Self {
+        self.text_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Generates a random `T`
+    pub fn generate<'facet, T: Facet<'facet>>(&mut self) -> Result<T, GenerateError> {
+        Ok(self.generate_shape(T::SHAPE)?.materialize()?)
+    }
+
+    /// Generates a random value of the given shape
+    ///
+    /// The value is built with [`Wip`], which checks the invariants of the value and of
+    /// everything in it: values that don't uphold them are thrown away and generated again, up to the
+    /// configured number of attempts.
+    pub fn generate_shape<'facet>(
+        &mut self,
+        shape: &'static Shape,
+    ) -> Result<HeapValue<'facet>, GenerateError> {
+        for _ in 0..self.max_attempts {
+            let res = self
+                .value(Wip::alloc_shape(shape)?, None, 0)
+                .and_then(|wip| Ok(wip.build()?));
+            match res {
+                Ok(value) => return Ok(value),
+                // The value doesn't uphold its invariants, or a transparent wrapper refused
+                // the inner value (think `NonZeroU8` and `0`): roll again
+                Err(GenerateError {
+                    kind:
+                        GenerateErrorKind::Reflect(
+                            ReflectError::InvariantViolated { .. }
+                            | ReflectError::TryFromError { .. },
+                        ),
+                }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(GenerateError {
+            kind: GenerateErrorKind::TooManyAttempts {
+                shape,
+                attempts: self.max_attempts,
+            },
+        })
+    }
+
+    /// Fills the current frame with a random value
+    fn value<'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        field: Option<&'static Field>,
+        depth: usize,
+    ) -> Result<Wip<'facet>, GenerateError> {
+        let shape = wip.shape();
+        let deep = depth >= self.max_depth;
+
+        if let (Def::Scalar(_), Some(hook)) = (shape.def, self.text_hook.as_mut()) {
+            if let Some(text) = hook(Slot { shape, field }, &mut self.source) {
+                let wip = match ScalarType::try_from_shape(shape) {
+                    Some(ScalarType::String) => wip.put(text)?,
+                    Some(ScalarType::CowStr) => wip.put(Cow::<str>::Owned(text))?,
+                    _ => wip.parse(&text)?,
+                };
+                return Ok(wip);
+            }
+        }
+
+        match shape.def {
+            Def::Option(_) => {
+                if deep || !self.source.chance(self.some_probability) {
+                    return Ok(wip.put_default()?);
+                }
+                let wip = self.value(wip.push_some()?, field, depth + 1)?;
+                return Ok(wip.pop()?);
+            }
+            Def::List(_) => {
+                let len = if deep {
+                    0
+                } else {
+                    self.len(self.collection_len.clone())
+                };
+                if len == 0 {
+                    return Ok(wip.put_empty_list()?);
+                }
+                wip = wip.begin_pushback()?;
+                for _ in 0..len {
+                    wip = self.value(wip.push()?, field, depth + 1)?.pop()?;
+                }
+                return Ok(wip);
+            }
+            Def::Array(array_def) => {
+                wip = wip.begin_pushback()?;
+                for _ in 0..array_def.n {
+                    wip = self.value(wip.push()?, field, depth + 1)?.pop()?;
+                }
+                return Ok(wip);
+            }
+            Def::Map(_) => {
+                let len = if deep {
+                    0
+                } else {
+                    self.len(self.collection_len.clone())
+                };
+                if len == 0 {
+                    return Ok(wip.put_empty_map()?);
+                }
+                wip = wip.begin_map_insert()?;
+                // Colliding keys make for a shorter map, which is fine
+                for _ in 0..len {
+                    wip = self.value(wip.push_map_key()?, None, depth + 1)?;
+                    wip = self.value(wip.push_map_value()?, None, depth + 1)?.pop()?;
+                }
+                return Ok(wip);
+            }
+            Def::Scalar(_) => return self.scalar(wip),
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => {
+                for (index, field) in sd.fields.iter().enumerate() {
+                    wip = self
+                        .value(wip.field(index)?, Some(field), depth + 1)?
+                        .pop()?;
+                }
+                Ok(wip)
+            }
+            Type::User(UserType::Enum(ed)) => {
+                if ed.variants.is_empty() {
+                    return Err(unsupported(shape));
+                }
+                let unit_variants: Vec<usize> = (0..ed.variants.len())
+                    .filter(|&i| ed.variants[i].data.fields.is_empty())
+                    .collect();
+                let index = if deep && !unit_variants.is_empty() {
+                    unit_variants[self.source.below(unit_variants.len())]
+                } else {
+                    self.source.below(ed.variants.len())
+                };
+                wip = wip.variant(index)?;
+                for (i, field) in ed.variants[index].data.fields.iter().enumerate() {
+                    wip = self.value(wip.field(i)?, Some(field), depth + 1)?.pop()?;
+                }
+                Ok(wip)
+            }
+            Type::Sequence(SequenceType::Tuple(tt)) => {
+                wip = wip.begin_pushback()?;
+                for _ in 0..tt.fields.len() {
+                    wip = self.value(wip.push()?, None, depth + 1)?.pop()?;
+                }
+                Ok(wip)
+            }
+            _ if shape.inner.is_some() => self.scalar(wip),
+            _ => Err(unsupported(shape)),
+        }
+    }
+
+    /// Puts a random scalar — for transparent wrappers, one of the inner type, which
+    /// the wrapper is then converted from.
+    fn scalar<'facet>(&mut self, wip: Wip<'facet>) -> Result<Wip<'facet>, GenerateError> {
+        let shape = wip.shape();
+        let target = wip.innermost_shape();
+        let Some(scalar) = ScalarType::try_from_shape(target) else {
+            return Err(unsupported(shape));
+        };
+
+        macro_rules! bits {
+            ($ty:ty) => {
+                wip.put(self.source.next_u64() as $ty)
+            };
+        }
+        macro_rules! wide {
+            ($ty:ty) => {
+                wip.put(self.u128() as $ty)
+            };
+        }
+
+        let res = match scalar {
+            ScalarType::Unit => wip.put(()),
+            ScalarType::Bool => wip.put(self.source.chance(0.5)),
+            ScalarType::Char => wip.put(self.char()),
+            ScalarType::String => wip.put(self.string()),
+            ScalarType::CowStr => wip.put(Cow::<str>::Owned(self.string())),
+            ScalarType::U8 => bits!(u8),
+            ScalarType::U16 => bits!(u16),
+            ScalarType::U32 => bits!(u32),
+            ScalarType::U64 => bits!(u64),
+            ScalarType::USize => bits!(usize),
+            ScalarType::I8 => bits!(i8),
+            ScalarType::I16 => bits!(i16),
+            ScalarType::I32 => bits!(i32),
+            ScalarType::I64 => bits!(i64),
+            ScalarType::ISize => bits!(isize),
+            ScalarType::U128 => wide!(u128),
+            ScalarType::I128 => wide!(i128),
+            ScalarType::F32 => wip.put(self.f32()),
+            ScalarType::F64 => wip.put(self.f64()),
+            ScalarType::Ipv4Addr => {
+                wip.put(core::net::Ipv4Addr::from(self.source.next_u64() as u32))
+            }
+            ScalarType::Ipv6Addr => wip.put(core::net::Ipv6Addr::from(self.u128())),
+            ScalarType::IpAddr => {
+                let addr = if self.source.chance(0.5) {
+                    core::net::IpAddr::V4((self.source.next_u64() as u32).into())
+                } else {
+                    core::net::IpAddr::V6(self.u128().into())
+                };
+                wip.put(addr)
+            }
+            _ => return Err(unsupported(shape)),
+        };
+        Ok(res?)
+    }
+
+    fn len(&mut self, range: Range<usize>) -> usize {
+        if range.end <= range.start {
+            range.start
+        } else {
+            range.start + self.source.below(range.end - range.start)
+        }
+    }
+
+    fn u128(&mut self) -> u128 {
+        ((self.source.next_u64() as u128) << 64) | self.source.next_u64() as u128
+    }
+
+    /// Mostly printable ASCII, with the occasional arbitrary code point
+    fn char(&mut self) -> char {
+        if !self.source.chance(0.25) {
+            return char::from(b' ' + self.source.below(95) as u8);
+        }
+        loop {
+            if let Some(c) = char::from_u32(self.source.below(0x11_0000) as u32) {
+                return c;
+            }
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.len(self.string_len.clone());
+        (0..len).map(|_| self.char()).collect()
+    }
+
+    /// Finite floats only: mostly small ones, sometimes anywhere in the representable range
+    fn f64(&mut self) -> f64 {
+        if self.source.chance(0.5) {
+            return (self.source.unit() - 0.5) * 2000.0;
+        }
+        loop {
+            let f = f64::from_bits(self.source.next_u64());
+            if f.is_finite() {
+                return f;
+            }
+        }
+    }
+
+    /// Same as [`Self::f64`], for `f32`
+    fn f32(&mut self) -> f32 {
+        if self.source.chance(0.5) {
+            return ((self.source.unit() - 0.5) * 2000.0) as f32;
+        }
+        loop {
+            let f = f32::from_bits(self.source.next_u64() as u32);
+            if f.is_finite() {
+                return f;
+            }
+        }
+    }
+}
+
+fn unsupported(shape: &'static Shape) -> GenerateError {
+    GenerateError {
+        kind: GenerateErrorKind::Unsupported { shape },
+    }
+}
@@ -0,0 +1,151 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::{Facet, Shape};
+use facet_reflect::ReflectError;
+
+mod generator;
+pub use generator::*;
+
+/// Where a [`Generator`] gets its randomness from
+pub trait Source {
+    /// Returns the next 64 random bits
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a random number in `[0, 1)`
+    fn unit(&mut self) -> f64 {
+        // 53 random bits, as many as an `f64` has mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random number in `0..n` (with a slight bias for huge `n`s). `n` must not be zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns true with the given probability
+    fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+}
+
+impl<S: Source + ?Sized> Source for &mut S {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A small, fast, seedable pseudo-random generator ([SplitMix64](https://prng.di.unimi.it/splitmix64.c))
+///
+/// Not suitable for anything cryptographic — but the same seed always produces the same values,
+/// which is what you want when reproducing a failing test.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Source for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Generates a random `T` from `seed`, with the default [`Generator`] settings
+pub fn generate<'facet, T: Facet<'facet>>(seed: u64) -> Result<T, GenerateError> {
+    Generator::new(SplitMix64::new(seed)).generate()
+}
+
+#[cfg(feature = "arbitrary")]
+impl Source for arbitrary::Unstructured<'_> {
+    fn next_u64(&mut self) -> u64 {
+        // Once the input is exhausted, this returns zeroes, which steers generation
+        // towards the smallest values (empty collections, first variants…)
+        <u64 as arbitrary::Arbitrary>::arbitrary(self).unwrap_or(0)
+    }
+}
+
+/// Generates a `T` from fuzzer input — handy to implement [`arbitrary::Arbitrary`]
+/// for a type that implements `Facet`:
+///
+/// ```rust,ignore
+/// impl<'a> arbitrary::Arbitrary<'a> for MyType {
+///     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+///         facet_arbitrary::arbitrary(u)
+///     }
+/// }
+/// ```
+#[cfg(feature = "arbitrary")]
+pub fn arbitrary<'facet, T: Facet<'facet>>(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<T> {
+    Generator::new(u)
+        .generate()
+        .map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+/// An error encountered while generating a value
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateError {
+    /// Type of error
+    pub kind: GenerateErrorKind,
+}
+
+impl core::fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            GenerateErrorKind::Unsupported { shape } => {
+                write!(f, "Don't know how to generate values of type {shape}")
+            }
+            GenerateErrorKind::Reflect(e) => write!(f, "{e}"),
+            GenerateErrorKind::TooManyAttempts { shape, attempts } => write!(
+                f,
+                "Could not generate a {shape} that upholds its invariants in {attempts} attempts"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for GenerateError {}
+
+impl From<ReflectError> for GenerateError {
+    fn from(e: ReflectError) -> Self {
+        Self {
+            kind: GenerateErrorKind::Reflect(e),
+        }
+    }
+}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum GenerateErrorKind {
+    /// The shape (or one nested in it) can't be generated: borrowed data, smart pointers,
+    /// opaque types…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// Building the value failed
+    Reflect(ReflectError),
+
+    /// Every attempt produced a value that violated its invariants
+    TooManyAttempts {
+        /// The shape being generated
+        shape: &'static Shape,
+        /// How many attempts were made
+        attempts: usize,
+    },
+}
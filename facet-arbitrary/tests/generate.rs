@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_arbitrary::{GenerateErrorKind, Generator, SplitMix64, generate};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Catalog {
+    title: String,
+    products: Vec<Product>,
+    featured: Option<Product>,
+    stock: HashMap<String, u32>,
+    origin: (f32, f64),
+    checksum: [u8; 4],
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Product {
+    id: u64,
+    kind: Kind,
+    available: bool,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Kind {
+    Physical { weight: u16 },
+    Digital(String),
+    Service,
+}
+
+#[test]
+fn same_seed_same_value() {
+    facet_testhelpers::setup();
+
+    for seed in 0..32 {
+        let a: Catalog = generate(seed).unwrap();
+        let b: Catalog = generate(seed).unwrap();
+        assert_eq!(a, b);
+        assert!(a.origin.0.is_finite() && a.origin.1.is_finite());
+    }
+
+    let values: Vec<Catalog> = (0..8).map(|seed| generate(seed).unwrap()).collect();
+    assert!(values.windows(2).any(|w| w[0] != w[1]));
+}
+
+#[test]
+fn settings_are_respected() {
+    facet_testhelpers::setup();
+
+    let mut generator = Generator::new(SplitMix64::new(1))
+        .some_probability(1.0)
+        .collection_len(2..4)
+        .string_len(5..6);
+    for _ in 0..16 {
+        let catalog: Catalog = generator.generate().unwrap();
+        assert!(catalog.featured.is_some());
+        assert!((2..4).contains(&catalog.products.len()));
+        assert_eq!(catalog.title.chars().count(), 5);
+    }
+
+    let mut generator = Generator::new(SplitMix64::new(1)).some_probability(0.0);
+    let value: Vec<Option<u8>> = generator.generate().unwrap();
+    assert!(value.iter().all(Option::is_none));
+}
+
+#[test]
+fn depth_is_bounded() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Tree {
+        children: Vec<Tree>,
+    }
+
+    fn depth(tree: &Tree) -> usize {
+        1 + tree.children.iter().map(depth).max().unwrap_or(0)
+    }
+
+    let mut generator = Generator::new(SplitMix64::new(3))
+        .collection_len(1..3)
+        .max_depth(4);
+    let tree: Tree = generator.generate().unwrap();
+    assert!(depth(&tree) <= 5);
+}
+
+#[test]
+fn invariants_and_conversions_are_upheld() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    #[facet(invariants = Even::invariants)]
+    struct Even(u8);
+
+    impl Even {
+        fn invariants(&self) -> bool {
+            self.0 % 2 == 0
+        }
+    }
+
+    #[derive(Facet, Debug)]
+    #[facet(invariants = Never::invariants)]
+    struct Never(u8);
+
+    impl Never {
+        fn invariants(&self) -> bool {
+            false
+        }
+    }
+
+    for seed in 0..32 {
+        let even: Even = generate(seed).unwrap();
+        assert_eq!(even.0 % 2, 0);
+
+        let nonzero: core::num::NonZeroU8 = generate(seed).unwrap();
+        assert_ne!(nonzero.get(), 0);
+    }
+
+    let err = Generator::new(SplitMix64::new(0))
+        .max_attempts(3)
+        .generate::<Never>()
+        .unwrap_err();
+    assert!(matches!(
+        err.kind,
+        GenerateErrorKind::TooManyAttempts { attempts: 3, .. }
+    ));
+}
+
+#[test]
+fn borrowed_data_is_unsupported() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Borrowed<'a> {
+        name: &'a str,
+    }
+
+    let err = generate::<Borrowed>(0).unwrap_err();
+    assert!(matches!(err.kind, GenerateErrorKind::Unsupported { .. }));
+}
@@ -18,8 +18,20 @@ impl ArgsError {
                 format!("Error while reflecting type: {reflect_error}")
             }
             ArgsErrorKind::GenericArgsError(message) => format!("Args error: {message}"),
+            ArgsErrorKind::Help(help) => help.clone(),
         }
     }
+
+    /// Whether `--help` was asked for: the message is the help text, to be printed as-is
+    pub fn is_help(&self) -> bool {
+        matches!(self.kind, ArgsErrorKind::Help(_))
+    }
+}
+
+impl From<ReflectError> for ArgsError {
+    fn from(err: ReflectError) -> Self {
+        Self::new(ArgsErrorKind::GenericReflect(err))
+    }
 }
 
 impl core::fmt::Display for ArgsError {
@@ -43,4 +55,6 @@ pub enum ArgsErrorKind {
     GenericReflect(ReflectError),
     /// Parsing arguments error
     GenericArgsError(String),
+    /// `--help` was passed: holds the help text
+    Help(String),
 }
@@ -8,9 +8,10 @@ extern crate alloc;
 use alloc::borrow::Cow;
 
 mod error;
+mod usage;
 
-use error::{ArgsError, ArgsErrorKind};
-use facet_core::{Def, Facet, FieldAttribute, Type, UserType};
+pub use error::{ArgsError, ArgsErrorKind};
+use facet_core::{Def, Facet, Field, FieldAttribute, FieldFlags, Shape, Type, UserType};
 use facet_reflect::{ReflectError, Wip};
 
 fn parse_field<'facet>(wip: Wip<'facet>, value: &'facet str) -> Result<Wip<'facet>, ArgsError> {
@@ -40,12 +41,9 @@ fn parse_field<'facet>(wip: Wip<'facet>, value: &'facet str) -> Result<Wip<'face
                 )));
             }
         }
-    }
-    .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?
+    }?
     .pop()
-    .map_err(|e| ArgsError {
-        kind: ArgsErrorKind::GenericReflect(e),
-    })
+    .map_err(ArgsError::from)
 }
 
 fn kebab_to_snake(input: &str) -> Cow<str> {
@@ -57,165 +55,302 @@ fn kebab_to_snake(input: &str) -> Cow<str> {
     Cow::Owned(input.replace('-', "_"))
 }
 
+/// How the values of an argument end up in its field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArgKind {
+    /// `bool`: present or not, no value
+    Flag,
+    /// `Option<T>`: `None` when absent
+    Optional,
+    /// `Vec<T>` and other lists: one element per occurrence, empty when absent
+    Repeated,
+    /// Anything else: required unless it has a default; the last occurrence wins
+    Value,
+}
+
+/// An argument, as described by a struct field and its attributes
+pub(crate) struct Arg {
+    pub(crate) index: usize,
+    pub(crate) field: &'static Field,
+    /// `long = "..."`, or the field name in kebab-case
+    pub(crate) long: Cow<'static, str>,
+    /// `short = '.'`
+    pub(crate) short: Option<char>,
+    /// `positional`, otherwise the argument is named
+    pub(crate) positional: bool,
+    pub(crate) kind: ArgKind,
+}
+
+impl Arg {
+    fn new(index: usize, field: &'static Field) -> Self {
+        let long = match attribute(field, "long") {
+            Some(long) => Cow::Borrowed(long.trim_matches('"')),
+            None => Cow::Owned(field.name.replace('_', "-")),
+        };
+        let short =
+            attribute(field, "short").and_then(|short| short.trim_matches('\'').chars().next());
+        let kind = if field.shape().is_type::<bool>() {
+            ArgKind::Flag
+        } else {
+            match field.shape().def {
+                Def::Option(_) => ArgKind::Optional,
+                Def::List(_) => ArgKind::Repeated,
+                _ => ArgKind::Value,
+            }
+        };
+        Self {
+            index,
+            field,
+            long,
+            short,
+            positional: has_attribute(field, "positional"),
+            kind,
+        }
+    }
+
+    /// `doc = "..."`, or the field's doc comment
+    pub(crate) fn doc(&self) -> Option<Cow<'static, str>> {
+        if let Some(doc) = attribute(self.field, "doc") {
+            return Some(Cow::Borrowed(doc.trim_matches('"')));
+        }
+        let lines: Vec<&str> = self.field.doc.iter().map(|line| line.trim()).collect();
+        if lines.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(lines.join(" ")))
+        }
+    }
+
+    /// Whether leaving the argument out is an error
+    pub(crate) fn is_required(&self) -> bool {
+        self.kind == ArgKind::Value && !self.field.flags.contains(FieldFlags::DEFAULT)
+    }
+}
+
+/// The value of an attribute like `#[facet(short = 'v')]`, as written
+fn attribute(field: &Field, name: &str) -> Option<&'static str> {
+    field.attributes.iter().find_map(|attribute| {
+        let &FieldAttribute::Arbitrary(attribute) = attribute else {
+            return None;
+        };
+        let value = attribute.strip_prefix(name)?.trim_start();
+        Some(value.strip_prefix('=')?.trim())
+    })
+}
+
+/// Whether a field has an attribute like `#[facet(positional)]`
+fn has_attribute(field: &Field, name: &str) -> bool {
+    field
+        .attributes
+        .iter()
+        .any(|attribute| matches!(attribute, FieldAttribute::Arbitrary(a) if a.trim() == name))
+}
+
+/// The arguments of a struct, one per field
+fn args_of(shape: &'static Shape) -> Result<Vec<Arg>, ArgsError> {
+    let Type::User(UserType::Struct(st)) = shape.ty else {
+        return Err(ArgsError::new(ArgsErrorKind::GenericArgsError(
+            "Expected struct type".to_string(),
+        )));
+    };
+    Ok(st
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| Arg::new(index, field))
+        .collect())
+}
+
 /// Parses command-line arguments
+///
+/// Fields marked `#[facet(positional)]` take the arguments that don't start with a dash, in
+/// order; the others are named, `--long-name value` or `-s value`. `bool` fields are flags,
+/// `Option` fields may be left out, and `Vec` fields take every occurrence of the argument.
+/// `--help` (or `-h`, when no field uses it) returns an error holding the [`help`] text.
 pub fn from_slice<'input, 'facet, T>(s: &[&'input str]) -> Result<T, ArgsError>
+where
+    T: Facet<'facet>,
+    'input: 'facet,
+{
+    parse(&usage::program_name(T::SHAPE), s)
+}
+
+/// Parses the arguments the program was started with, like [`from_slice`]
+///
+/// The arguments are dropped once parsed, so `T` can't borrow from them: its fields have to
+/// be owned, e.g. `String` rather than `&str`. Panics if an argument isn't valid UTF-8.
+pub fn from_std_args<T>() -> Result<T, ArgsError>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    let mut args = std::env::args();
+    let program = args
+        .next()
+        .as_deref()
+        .and_then(|path| std::path::Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| usage::program_name(T::SHAPE));
+    let args: Vec<String> = args.collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    parse(&program, &args)
+}
+
+/// The `--help` text for `T`, with `program` in the usage line
+pub fn help<'facet, T: Facet<'facet>>(program: &str) -> Result<String, ArgsError> {
+    Ok(usage::render(program, T::SHAPE, &args_of(T::SHAPE)?))
+}
+
+fn parse<'input, 'facet, T>(program: &str, s: &[&'input str]) -> Result<T, ArgsError>
 where
     T: Facet<'facet>,
     'input: 'facet,
 {
     log::trace!("Entering from_slice function");
+    let args = args_of(T::SHAPE)?;
     let mut s = s;
-    let mut wip =
-        Wip::alloc::<T>().map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-    log::trace!("Allocated Poke for type T");
-    let Type::User(UserType::Struct(st)) = wip.shape().ty else {
-        return Err(ArgsError::new(ArgsErrorKind::GenericArgsError(
-            "Expected struct type".to_string(),
-        )));
-    };
+    let mut values: Vec<Vec<&'input str>> = args.iter().map(|_| Vec::new()).collect();
+    let mut only_positional = false;
 
-    while let Some(token) = s.first() {
+    while let Some(&token) = s.first() {
         log::trace!("Processing token: {}", token);
         s = &s[1..];
 
-        if let Some(key) = token.strip_prefix("--") {
-            let key = kebab_to_snake(key);
-            let field_index = match wip.field_index(&key) {
-                Some(index) => index,
-                None => {
-                    return Err(ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
-                        "Unknown argument `{key}`",
-                    ))));
-                }
-            };
-            log::trace!("Found named argument: {}", key);
+        if !only_positional && token == "--" {
+            only_positional = true;
+            continue;
+        }
 
-            let field = wip
-                .field(field_index)
-                .expect("field_index should be a valid field bound");
-
-            if field.shape().is_type::<bool>() {
-                // TODO: absence i.e "false" case is not handled
-                wip = parse_field(field, "true")?;
-            } else {
-                let value = s
-                    .first()
-                    .ok_or(ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
-                        "expected value after argument `{key}`"
-                    ))))?;
-                log::trace!("Field value: {}", value);
-                s = &s[1..];
-                wip = parse_field(field, value)?;
+        let named = if only_positional {
+            None
+        } else if let Some(key) = token.strip_prefix("--") {
+            let (key, inline) = match key.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (key, None),
+            };
+            if key == "help" && !args.iter().any(|arg| arg.long == "help") {
+                return Err(ArgsError::new(ArgsErrorKind::Help(usage::render(
+                    program,
+                    T::SHAPE,
+                    &args,
+                ))));
             }
-        } else if let Some(key) = token.strip_prefix("-") {
+            let arg = args
+                .iter()
+                .find(|arg| !arg.positional && kebab_to_snake(&arg.long) == kebab_to_snake(key))
+                .ok_or_else(|| {
+                    ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
+                        "Unknown argument `{key}`",
+                    )))
+                })?;
+            log::trace!("Found named argument: {}", key);
+            Some((arg, key, inline))
+        } else if let Some(key) = token.strip_prefix('-').filter(|key| !key.is_empty()) {
             log::trace!("Found short named argument: {}", key);
-            for (field_index, f) in st.fields.iter().enumerate() {
-                if f.attributes
-                    .iter()
-                    .any(|a| matches!(a, FieldAttribute::Arbitrary(a) if a.contains("short") && a.contains(key))
-                   )
-                {
-                    log::trace!("Found field matching short_code: {} for field {}", key, f.name);
-                    let field = wip.field(field_index).expect("field_index is in bounds");
-                    if field.shape().is_type::<bool>() {
-                        wip = parse_field(field, "true")?;
-                    } else {
-                        let value = s
-                            .first()
-                            .ok_or(ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
+            let mut chars = key.chars();
+            let short = chars.next().filter(|_| chars.next().is_none());
+            if short == Some('h') && !args.iter().any(|arg| arg.short == Some('h')) {
+                return Err(ArgsError::new(ArgsErrorKind::Help(usage::render(
+                    program,
+                    T::SHAPE,
+                    &args,
+                ))));
+            }
+            let arg = args
+                .iter()
+                .find(|arg| !arg.positional && short.is_some() && arg.short == short)
+                .ok_or_else(|| {
+                    ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
+                        "Unknown argument `{key}`",
+                    )))
+                })?;
+            Some((arg, key, None))
+        } else {
+            None
+        };
+
+        match named {
+            Some((arg, key, inline)) => {
+                let value = match (arg.kind, inline) {
+                    (_, Some(value)) => value,
+                    (ArgKind::Flag, None) => "true",
+                    (_, None) => {
+                        let value = s.first().ok_or_else(|| {
+                            ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
                                 "expected value after argument `{key}`"
-                            ))))?;
+                            )))
+                        })?;
                         log::trace!("Field value: {}", value);
                         s = &s[1..];
-                        wip = parse_field(field, value)?;
+                        value
                     }
-                    break;
-                }
+                };
+                values[arg.index].push(value);
             }
-        } else {
-            log::trace!("Encountered positional argument: {}", token);
-            for (field_index, f) in st.fields.iter().enumerate() {
-                if f.attributes
+            None => {
+                log::trace!("Encountered positional argument: {}", token);
+                let arg = args
                     .iter()
-                    .any(|a| matches!(a, FieldAttribute::Arbitrary(a) if a.contains("positional")))
-                {
-                    if wip
-                        .is_field_set(field_index)
-                        .expect("field_index is in bounds")
-                    {
-                        continue;
-                    }
-                    let field = wip.field(field_index).expect("field_index is in bounds");
-                    wip = parse_field(field, token)?;
-                    break;
-                }
+                    .find(|arg| {
+                        arg.positional
+                            && (arg.kind == ArgKind::Repeated || values[arg.index].is_empty())
+                    })
+                    .ok_or_else(|| {
+                        ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
+                            "Unexpected argument `{token}`",
+                        )))
+                    })?;
+                values[arg.index].push(token);
             }
         }
     }
 
-    // Look for uninitialized fields with DEFAULT flag
-    // Adapted from the approach in `facet-deserialize::StackRunner::pop()`
-    for (field_index, field) in st.fields.iter().enumerate() {
-        if !wip.is_field_set(field_index).expect("in bounds") {
-            log::trace!(
-                "Field {} is not initialized, checking if it has DEFAULT flag",
-                field.name
-            );
-
-            // Check if the field has the DEFAULT flag
-            if field.flags.contains(facet_core::FieldFlags::DEFAULT) {
-                log::trace!("Field {} has DEFAULT flag, applying default", field.name);
-
-                let field_wip = wip.field(field_index).expect("field_index is in bounds");
-
-                // Check if there's a custom default function
-                if let Some(default_fn) = field.vtable.default_fn {
-                    log::trace!("Using custom default function for field {}", field.name);
-                    wip = field_wip
-                        .put_from_fn(default_fn)
-                        .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-                } else {
-                    // Otherwise use the Default trait
-                    log::trace!("Using Default trait for field {}", field.name);
-                    wip = field_wip
-                        .put_default()
-                        .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-                }
-
-                // Pop back up to the struct level
-                wip = wip
-                    .pop()
-                    .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-            }
-        }
+    let mut wip = Wip::alloc::<T>()?;
+    log::trace!("Allocated Poke for type T");
+    for (arg, values) in args.iter().zip(&values) {
+        wip = fill_field(wip, arg, values, program)?;
     }
 
-    // If a boolean field is unset the value is set to `false`
-    // This behaviour means `#[facet(default = false)]` does not need to be explicitly set
-    // on each boolean field specified on a Command struct
-    for (field_index, f) in st.fields.iter().enumerate() {
-        if f.shape().is_type::<bool>() && !wip.is_field_set(field_index).expect("in bounds") {
-            let field = wip.field(field_index).expect("field_index is in bounds");
-            wip = parse_field(field, "false")?;
-        }
-    }
+    let heap_vale = wip.build()?;
+    let result = heap_vale.materialize()?;
+    Ok(result)
+}
 
-    // Add this right after getting the struct type (st)
-    log::trace!("Checking field attributes");
-    for (i, field) in st.fields.iter().enumerate() {
-        log::trace!(
-            "Field {}: {} - Attributes: {:?}",
-            i,
-            field.name,
-            field.attributes
-        );
-    }
+/// Puts the values given for an argument in its field, or what it gets when left out
+fn fill_field<'facet>(
+    wip: Wip<'facet>,
+    arg: &Arg,
+    values: &[&'facet str],
+    program: &str,
+) -> Result<Wip<'facet>, ArgsError> {
+    let field = arg.field;
+    let wip = wip.field(arg.index)?;
 
-    let heap_vale = wip
-        .build()
-        .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-    let result = heap_vale
-        .materialize()
-        .map_err(|e| ArgsError::new(ArgsErrorKind::GenericReflect(e)))?;
-    Ok(result)
+    match (arg.kind, values.last()) {
+        (ArgKind::Repeated, Some(_)) => {
+            let mut wip = wip.begin_pushback()?;
+            for value in values {
+                wip = parse_field(wip.push()?, value)?;
+            }
+            Ok(wip.pop()?)
+        }
+        (ArgKind::Optional, Some(value)) => Ok(parse_field(wip.push_some()?, value)?.pop()?),
+        (_, Some(value)) => parse_field(wip, value),
+        (_, None) if field.flags.contains(FieldFlags::DEFAULT) => {
+            log::trace!("Field {} has DEFAULT flag, applying default", field.name);
+            let wip = match field.vtable.default_fn {
+                Some(default_fn) => wip.put_from_fn(default_fn)?,
+                None => wip.put_default()?,
+            };
+            Ok(wip.pop()?)
+        }
+        // Absence of a flag means `false`, so `bool` fields don't need
+        // `#[facet(default = false)]`
+        (ArgKind::Flag, None) => parse_field(wip, "false"),
+        (ArgKind::Optional, None) => Ok(wip.put_default()?.pop()?),
+        (ArgKind::Repeated, None) => Ok(wip.put_empty_list()?.pop()?),
+        (ArgKind::Value, None) => Err(ArgsError::new(ArgsErrorKind::GenericArgsError(format!(
+            "missing argument `{}`, see `{program} --help`",
+            usage::name(arg)
+        )))),
+    }
 }
@@ -0,0 +1,110 @@
+use alloc::borrow::Cow;
+use core::fmt::Write;
+
+use facet_core::Shape;
+
+use crate::{Arg, ArgKind};
+
+/// The name of the type, in kebab-case, for when the program name isn't known
+pub(crate) fn program_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    let name = name.split('<').next().unwrap_or_default();
+    let mut kebab = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            kebab.push('-');
+        }
+        kebab.extend(c.to_lowercase());
+    }
+    kebab
+}
+
+/// How an argument is shown in usage lines and errors: `<PATH>` or `--verbose`
+pub(crate) fn name(arg: &Arg) -> String {
+    if arg.positional {
+        format!("<{}>", metavar(arg))
+    } else {
+        format!("--{}", arg.long)
+    }
+}
+
+fn metavar(arg: &Arg) -> String {
+    arg.field.name.to_uppercase()
+}
+
+/// Renders the `--help` text: the type's doc comment, a usage line, then the positional
+/// arguments and the options with their docs
+pub(crate) fn render(program: &str, shape: &'static Shape, args: &[Arg]) -> String {
+    let mut out = String::new();
+    let description: Vec<&str> = shape.doc.iter().map(|line| line.trim()).collect();
+    if !description.is_empty() {
+        out.push_str(&description.join(" "));
+        out.push_str("\n\n");
+    }
+
+    let positionals: Vec<&Arg> = args.iter().filter(|arg| arg.positional).collect();
+    let _ = write!(out, "Usage: {program} [OPTIONS]");
+    for arg in &positionals {
+        let name = metavar(arg);
+        let _ = match arg.kind {
+            ArgKind::Repeated => write!(out, " [{name}]..."),
+            _ if arg.is_required() => write!(out, " <{name}>"),
+            _ => write!(out, " [{name}]"),
+        };
+    }
+    out.push('\n');
+
+    let mut rows: Vec<(String, Option<Cow<'static, str>>)> = Vec::new();
+    if !positionals.is_empty() {
+        for arg in &positionals {
+            rows.push((format!("<{}>", metavar(arg)), arg.doc()));
+        }
+        write_section(&mut out, "Arguments", &rows);
+    }
+
+    rows.clear();
+    for arg in args.iter().filter(|arg| !arg.positional) {
+        let mut left = match arg.short {
+            Some(short) => format!("-{short}, --{}", arg.long),
+            None => format!("    --{}", arg.long),
+        };
+        match arg.kind {
+            ArgKind::Flag => {}
+            ArgKind::Repeated => {
+                let _ = write!(left, " <{}>...", metavar(arg));
+            }
+            _ => {
+                let _ = write!(left, " <{}>", metavar(arg));
+            }
+        }
+        rows.push((left, arg.doc()));
+    }
+    let help_short = !args.iter().any(|arg| arg.short == Some('h'));
+    let help_long = !args.iter().any(|arg| arg.long == "help");
+    if help_long {
+        let left = if help_short {
+            "-h, --help"
+        } else {
+            "    --help"
+        };
+        rows.push((left.to_string(), Some(Cow::Borrowed("Print help"))));
+    }
+    write_section(&mut out, "Options", &rows);
+    out
+}
+
+/// A blank line, a title, then one row per line with the docs lined up
+fn write_section(out: &mut String, title: &str, rows: &[(String, Option<Cow<'static, str>>)]) {
+    let width = rows.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
+    let _ = write!(out, "\n{title}:\n");
+    for (left, doc) in rows {
+        match doc {
+            Some(doc) => {
+                let _ = writeln!(out, "  {left:width$}  {doc}");
+            }
+            None => {
+                let _ = writeln!(out, "  {left}");
+            }
+        }
+    }
+}
@@ -180,3 +180,98 @@ fn test_error_unknown_argument() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_optional_and_repeated_arguments() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(named, short = 'o')]
+        output: Option<String>,
+        #[facet(named, short = 'I', long = "include")]
+        include_dirs: Vec<String>,
+        #[facet(positional)]
+        inputs: Vec<String>,
+    }
+
+    let args: Args = facet_args::from_slice(&["-I", "a", "main.c", "--include=b", "util.c"])?;
+    assert_eq!(args.output, None);
+    assert_eq!(args.include_dirs, vec!["a", "b"]);
+    assert_eq!(args.inputs, vec!["main.c", "util.c"]);
+
+    let args: Args = facet_args::from_slice(&["--output", "app", "--", "-weird.c"])?;
+    assert_eq!(args.output.as_deref(), Some("app"));
+    assert!(args.include_dirs.is_empty());
+    assert_eq!(args.inputs, vec!["-weird.c"]);
+
+    // The long name replaces the field name
+    let args: Result<Args, _> = facet_args::from_slice(&["--include-dirs", "a"]);
+    assert_eq!(
+        args.unwrap_err().message(),
+        "Args error: Unknown argument `include-dirs`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_error_missing_required_argument() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(positional)]
+        path: String,
+    }
+    let args: Result<Args, _> = facet_args::from_slice(&[]);
+    assert_eq!(
+        args.unwrap_err().message(),
+        "Args error: missing argument `<PATH>`, see `args --help`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_help() -> Result<()> {
+    facet_testhelpers::setup();
+
+    /// Counts the lines of files
+    #[derive(Facet, Debug)]
+    struct LineCount {
+        /// The files to read
+        #[facet(positional)]
+        files: Vec<String>,
+
+        #[facet(named, short = 'v', doc = "Print more")]
+        verbose: bool,
+
+        /// Stop after this many lines
+        #[facet(named)]
+        max_lines: Option<usize>,
+    }
+
+    let err = facet_args::from_slice::<LineCount>(&["-v", "--help"]).unwrap_err();
+    assert!(err.is_help());
+    let expected = "\
+Counts the lines of files
+
+Usage: line-count [OPTIONS] [FILES]...
+
+Arguments:
+  <FILES>  The files to read
+
+Options:
+  -v, --verbose                Print more
+      --max-lines <MAX_LINES>  Stop after this many lines
+  -h, --help                   Print help
+";
+    assert_eq!(err.message(), expected);
+    assert_eq!(
+        facet_args::help::<LineCount>("wc")?,
+        expected.replace("line-count", "wc")
+    );
+
+    Ok(())
+}
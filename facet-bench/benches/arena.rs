@@ -0,0 +1,101 @@
+use divan::{Bencher, black_box};
+use facet::Facet;
+use facet_json::Json;
+use facet_reflect::{Arena, HeapValue, Wip};
+
+#[derive(Debug, Facet)]
+struct Event {
+    id: u64,
+    kind: Option<u32>,
+    score: Option<f64>,
+    source: Option<Source>,
+}
+
+#[derive(Debug, Facet)]
+struct Source {
+    host: Option<u32>,
+    port: Option<u16>,
+}
+
+/// A batch of small, short-lived documents
+fn create_batch() -> Vec<String> {
+    (0..1000)
+        .map(|i| {
+            format!(
+                r#"{{"id":{i},"kind":{},"score":{}.5,"source":{{"host":{},"port":8080}}}}"#,
+                i % 7,
+                i % 100,
+                i * 31
+            )
+        })
+        .collect()
+}
+
+fn deserialize_batch<'a>(
+    batch: &'a [String],
+    mut alloc: impl FnMut() -> Wip<'a>,
+) -> Vec<HeapValue<'a>> {
+    batch
+        .iter()
+        .map(|doc| facet_deserialize::deserialize_wip(alloc(), doc.as_bytes(), Json).unwrap())
+        .collect()
+}
+
+#[divan::bench(name = "Deserialize - Batch (1000) - global allocator")]
+fn bench_batch_heap(bencher: Bencher) {
+    let batch = create_batch();
+
+    bencher.bench(|| {
+        let values = deserialize_batch(black_box(&batch), || Wip::alloc::<Event>().unwrap());
+        black_box(values)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Batch (1000) - arena")]
+fn bench_batch_arena(bencher: Bencher) {
+    let batch = create_batch();
+
+    bencher.bench(|| {
+        let arena = Arena::new();
+        let values = deserialize_batch(black_box(&batch), || {
+            Wip::alloc_in::<Event>(&arena).unwrap()
+        });
+        black_box(values)
+    });
+}
+
+#[derive(Debug, Facet)]
+struct Log {
+    events: Vec<Event>,
+}
+
+/// One large document, with many nested values
+fn create_log() -> String {
+    let events = create_batch().join(",");
+    format!(r#"{{"events":[{events}]}}"#)
+}
+
+#[divan::bench(name = "Deserialize - Nested log (1000) - global allocator")]
+fn bench_log_heap(bencher: Bencher) {
+    let log = create_log();
+
+    bencher.bench(|| {
+        let log: Log = facet_json::from_str(black_box(&log)).unwrap();
+        black_box(log)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Nested log (1000) - arena")]
+fn bench_log_arena(bencher: Bencher) {
+    let log = create_log();
+
+    bencher.bench(|| {
+        let arena = Arena::new();
+        let log: Log = facet_json::from_str_in(black_box(&log), &arena).unwrap();
+        black_box(log)
+    });
+}
+
+fn main() {
+    divan::main();
+}
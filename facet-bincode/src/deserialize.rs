@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use facet_core::{Def, Facet, FieldFlags, SequenceType, Type, UserType};
+use facet_reflect::{HeapValue, ScalarType, Wip};
+use log::trace;
+
+use crate::DecodeError;
+
+/// Deserializes bincode bytes into a type that implements `Facet`.
+///
+/// The whole input must be a single value: bytes left over after it are an error.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Ping {
+///     id: u16,
+///     payload: Vec<u8>,
+/// }
+///
+/// let bytes = [7, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 2];
+/// let ping: Ping = facet_bincode::from_slice(&bytes).unwrap();
+/// assert_eq!(ping, Ping { id: 7, payload: vec![1, 2] });
+/// ```
+pub fn from_slice<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<T, DecodeError> {
+    Ok(from_slice_value(Wip::alloc::<T>()?, input)?.materialize::<T>()?)
+}
+
+/// Deserializes bincode bytes into the value a `Wip` is building.
+///
+/// Strings can be borrowed from the input, into `&str` and `Cow<str>`.
+pub fn from_slice_value<'mem>(
+    wip: Wip<'mem>,
+    input: &'mem [u8],
+) -> Result<HeapValue<'mem>, DecodeError> {
+    let mut decoder = Decoder { input, offset: 0 };
+    let wip = decoder.decode_value(wip)?;
+    if decoder.offset < input.len() {
+        return Err(DecodeError::TrailingBytes(input.len() - decoder.offset));
+    }
+    Ok(wip.build()?)
+}
+
+struct Decoder<'input> {
+    input: &'input [u8],
+    offset: usize,
+}
+
+impl<'input> Decoder<'input> {
+    fn take(&mut self, len: usize) -> Result<&'input [u8], DecodeError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|&end| end <= self.input.len())
+            .ok_or(DecodeError::UnexpectedEof)?;
+        let bytes = &self.input[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn decode_u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn decode_u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    fn decode_u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    fn decode_len(&mut self) -> Result<usize, DecodeError> {
+        usize::try_from(self.decode_u64()?).map_err(|_| DecodeError::IntegerOverflow)
+    }
+
+    fn decode_str(&mut self) -> Result<&'input str, DecodeError> {
+        let len = self.decode_len()?;
+        core::str::from_utf8(self.take(len)?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    /// A `char` is its UTF-8 bytes, as many as the first one says
+    fn decode_char(&mut self) -> Result<char, DecodeError> {
+        let first = self
+            .input
+            .get(self.offset)
+            .ok_or(DecodeError::UnexpectedEof)?;
+        let len = match first.leading_ones() {
+            0 => 1,
+            len @ 2..=4 => len as usize,
+            _ => return Err(DecodeError::InvalidChar),
+        };
+        let s = core::str::from_utf8(self.take(len)?).map_err(|_| DecodeError::InvalidChar)?;
+        s.chars().next().ok_or(DecodeError::InvalidChar)
+    }
+
+    fn decode_value(&mut self, wip: Wip<'input>) -> Result<Wip<'input>, DecodeError> {
+        let shape = wip.shape();
+        trace!("Decoding {}", shape);
+
+        let wip = match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.decode_scalar(wip)?,
+            (Def::List(_), _) => {
+                let len = self.decode_len()?;
+                let mut wip = wip.begin_pushback()?;
+                for _ in 0..len {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Array(array_def), _) => {
+                let mut wip = wip.begin_pushback()?;
+                for _ in 0..array_def.n {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Map(_), _) => {
+                let len = self.decode_len()?;
+                let mut wip = wip.begin_map_insert()?;
+                for _ in 0..len {
+                    wip = self.decode_value(wip.push_map_key()?)?;
+                    wip = self.decode_value(wip.push_map_value()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Option(_), _) => match self.decode_u8()? {
+                0 => wip.put_default()?,
+                1 => self.decode_value(wip.push_some()?)?.pop()?,
+                tag => return Err(DecodeError::InvalidOptionTag(tag)),
+            },
+            (Def::Result(_), _) => {
+                let wip = match self.decode_u32()? {
+                    0 => wip.push_ok()?,
+                    1 => wip.push_err()?,
+                    index => return Err(DecodeError::NoSuchVariant { shape, index }),
+                };
+                self.decode_value(wip)?.pop()?
+            }
+            (Def::SmartPointer(_), _) => self.decode_value(wip.push_pointee()?)?.pop()?,
+            (_, Type::User(UserType::Struct(struct_type))) => {
+                let mut wip = wip;
+                for (index, field) in struct_type.fields.iter().enumerate() {
+                    if field.flags.contains(FieldFlags::SKIP_DESERIALIZING) {
+                        continue;
+                    }
+                    wip = self.decode_value(wip.field(index)?)?.pop()?;
+                }
+                wip.fill_defaults()?
+            }
+            (_, Type::User(UserType::Enum(enum_type))) => {
+                let index = self.decode_u32()?;
+                let Some(variant) = enum_type.variants.get(index as usize) else {
+                    return Err(DecodeError::NoSuchVariant { shape, index });
+                };
+                let mut wip = wip.variant(index as usize)?;
+                for (index, field) in variant.data.fields.iter().enumerate() {
+                    if field.flags.contains(FieldFlags::SKIP_DESERIALIZING) {
+                        continue;
+                    }
+                    wip = self.decode_value(wip.field(index)?)?.pop()?;
+                }
+                wip.fill_defaults()?
+            }
+            (_, Type::Sequence(SequenceType::Tuple(tuple_type))) => {
+                let mut wip = wip.begin_pushback()?;
+                for _ in tuple_type.fields {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            // `&str` is a pointer rather than a scalar
+            (_, Type::Pointer(_)) => self.decode_scalar(wip)?,
+            _ => return Err(DecodeError::UnsupportedShape(shape)),
+        };
+        Ok(wip)
+    }
+
+    fn decode_scalar(&mut self, wip: Wip<'input>) -> Result<Wip<'input>, DecodeError> {
+        let shape = wip.shape();
+        let wip = match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::Unit) => wip.put(())?,
+            Some(ScalarType::Bool) => match self.decode_u8()? {
+                0 => wip.put(false)?,
+                1 => wip.put(true)?,
+                byte => return Err(DecodeError::InvalidBool(byte)),
+            },
+            Some(ScalarType::Char) => wip.put(self.decode_char()?)?,
+            // Borrowed from the input, which outlives the value
+            Some(ScalarType::Str) => wip.put(self.decode_str()?)?,
+            Some(ScalarType::String) => wip.put(self.decode_str()?.to_string())?,
+            Some(ScalarType::CowStr) => wip.put(Cow::Borrowed(self.decode_str()?))?,
+            Some(ScalarType::F32) => wip.put(f32::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::F64) => wip.put(f64::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::U8) => wip.put(self.decode_u8()?)?,
+            Some(ScalarType::U16) => wip.put(u16::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::U32) => wip.put(self.decode_u32()?)?,
+            Some(ScalarType::U64) => wip.put(self.decode_u64()?)?,
+            Some(ScalarType::U128) => wip.put(u128::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::USize) => {
+                let value = usize::try_from(self.decode_u64()?)
+                    .map_err(|_| DecodeError::IntegerOverflow)?;
+                wip.put(value)?
+            }
+            Some(ScalarType::I8) => wip.put(i8::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::I16) => wip.put(i16::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::I32) => wip.put(i32::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::I64) => wip.put(i64::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::I128) => wip.put(i128::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::ISize) => {
+                let value = isize::try_from(i64::from_le_bytes(self.take_array()?))
+                    .map_err(|_| DecodeError::IntegerOverflow)?;
+                wip.put(value)?
+            }
+            Some(ScalarType::Duration) => {
+                let secs = self.decode_u64()?;
+                let nanos = self.decode_u32()?;
+                let duration = Duration::from_secs(secs)
+                    .checked_add(Duration::from_nanos(nanos.into()))
+                    .ok_or(DecodeError::DurationOverflow)?;
+                wip.put(duration)?
+            }
+            // Written as their `Display` output, like addresses or UUIDs
+            _ if shape.vtable.parse.is_some() => wip.parse(self.decode_str()?)?,
+            _ => return Err(DecodeError::UnsupportedShape(shape)),
+        };
+        Ok(wip)
+    }
+}
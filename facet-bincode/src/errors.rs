@@ -0,0 +1,108 @@
+use core::fmt;
+use std::io;
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors that can occur while encoding a value to bincode
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// Writing the encoded bytes failed
+    Io(io::Error),
+    /// Values of this shape can't be encoded, e.g. scalars of unknown type
+    UnsupportedShape(&'static Shape),
+    /// Reflection error
+    ReflectError(ReflectError),
+}
+
+impl From<io::Error> for EncodeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ReflectError> for EncodeError {
+    fn from(err: ReflectError) -> Self {
+        Self::ReflectError(err)
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::Io(err) => write!(f, "Failed to write bincode: {}", err),
+            EncodeError::UnsupportedShape(shape) => {
+                write!(f, "Unsupported shape for serialization: {}", shape)
+            }
+            EncodeError::ReflectError(err) => write!(f, "Reflection error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Errors that can occur while decoding bincode into a value
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended in the middle of a value
+    UnexpectedEof,
+    /// Bytes were left in the input after the value
+    TrailingBytes(usize),
+    /// A `bool` was neither 0 nor 1
+    InvalidBool(u8),
+    /// The tag of an `Option` was neither 0 (`None`) nor 1 (`Some`)
+    InvalidOptionTag(u8),
+    /// A string wasn't valid UTF-8
+    InvalidUtf8,
+    /// A `char` wasn't a single valid UTF-8 character
+    InvalidChar,
+    /// A length or integer doesn't fit in the type it's read into
+    IntegerOverflow,
+    /// A `Duration` whose nanoseconds overflow its seconds
+    DurationOverflow,
+    /// A variant index the enum doesn't have
+    NoSuchVariant {
+        /// The enum being read
+        shape: &'static Shape,
+        /// The index that was read
+        index: u32,
+    },
+    /// Values of this shape can't be decoded
+    UnsupportedShape(&'static Shape),
+    /// Reflection error
+    ReflectError(ReflectError),
+}
+
+impl From<ReflectError> for DecodeError {
+    fn from(err: ReflectError) -> Self {
+        Self::ReflectError(err)
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "Unexpected end of input"),
+            DecodeError::TrailingBytes(count) => {
+                write!(f, "{} bytes left over after the value", count)
+            }
+            DecodeError::InvalidBool(byte) => write!(f, "Invalid bool: {}", byte),
+            DecodeError::InvalidOptionTag(tag) => write!(f, "Invalid Option tag: {}", tag),
+            DecodeError::InvalidUtf8 => write!(f, "Invalid UTF-8 in string"),
+            DecodeError::InvalidChar => write!(f, "Invalid char"),
+            DecodeError::IntegerOverflow => write!(f, "Integer value too large for target type"),
+            DecodeError::DurationOverflow => write!(f, "Duration out of range"),
+            DecodeError::NoSuchVariant { shape, index } => {
+                write!(f, "No variant #{} in enum {}", index, shape)
+            }
+            DecodeError::UnsupportedShape(shape) => {
+                write!(f, "Unsupported shape for deserialization: {}", shape)
+            }
+            DecodeError::ReflectError(err) => write!(f, "Reflection error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
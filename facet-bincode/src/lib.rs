@@ -0,0 +1,12 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod errors;
+pub use errors::{DecodeError, EncodeError};
+
+mod deserialize;
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
@@ -0,0 +1,204 @@
+use std::io::Write;
+use std::time::Duration;
+
+use facet_core::{Def, Facet, FieldFlags, SequenceType, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use log::trace;
+
+use crate::EncodeError;
+
+/// Serializes any Facet type to bincode bytes
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Ping {
+///     id: u16,
+///     payload: Vec<u8>,
+/// }
+///
+/// let bytes = facet_bincode::to_vec(&Ping { id: 7, payload: vec![1, 2] }).unwrap();
+/// assert_eq!(bytes, [7, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+/// ```
+pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Result<Vec<u8>, EncodeError> {
+    peek_to_vec(Peek::new(value))
+}
+
+/// Serializes a Peek instance to bincode bytes
+pub fn peek_to_vec(peek: Peek<'_, '_>) -> Result<Vec<u8>, EncodeError> {
+    let mut buffer = Vec::new();
+    peek_to_writer(peek, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Serializes any Facet type to a writer in bincode format
+pub fn to_writer<'a, T: Facet<'a>, W: Write>(
+    value: &'a T,
+    writer: &mut W,
+) -> Result<(), EncodeError> {
+    peek_to_writer(Peek::new(value), writer)
+}
+
+/// Serializes a Peek instance to a writer in bincode format
+pub fn peek_to_writer<W: Write>(peek: Peek<'_, '_>, writer: &mut W) -> Result<(), EncodeError> {
+    Encoder { writer }.encode(peek)
+}
+
+struct Encoder<'w, W: Write> {
+    writer: &'w mut W,
+}
+
+impl<W: Write> Encoder<'_, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+
+    /// Lengths of sequences, maps and strings are always 8 bytes
+    fn write_len(&mut self, len: usize) -> Result<(), EncodeError> {
+        self.write(&(len as u64).to_le_bytes())
+    }
+
+    fn write_str(&mut self, s: &str) -> Result<(), EncodeError> {
+        self.write_len(s.len())?;
+        self.write(s.as_bytes())
+    }
+
+    fn encode(&mut self, peek: Peek<'_, '_>) -> Result<(), EncodeError> {
+        let shape = peek.shape();
+        trace!("Encoding {}", shape);
+
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.encode_scalar(peek.innermost_peek()),
+            (Def::List(_), _) | (Def::Slice(_), _) => {
+                let list = peek.into_list_like()?;
+                self.write_len(list.len())?;
+                list.iter().try_for_each(|item| self.encode(item))
+            }
+            // Their length is part of their type, so it isn't written
+            (Def::Array(_), _) => peek
+                .into_list_like()?
+                .iter()
+                .try_for_each(|item| self.encode(item)),
+            (Def::Map(_), _) => {
+                let map = peek.into_map()?;
+                self.write_len(map.len())?;
+                map.iter().try_for_each(|(key, value)| {
+                    self.encode(key)?;
+                    self.encode(value)
+                })
+            }
+            (Def::Set(_), _) => {
+                let set = peek.into_set()?;
+                self.write_len(set.len())?;
+                set.iter().try_for_each(|item| self.encode(item))
+            }
+            (Def::Option(_), _) => match peek.into_option()?.value() {
+                Some(value) => {
+                    self.write(&[1])?;
+                    self.encode(value)
+                }
+                None => self.write(&[0]),
+            },
+            // Like an enum with `Ok` and `Err` variants
+            (Def::Result(_), _) => {
+                let (index, value) = match peek.into_result()?.value() {
+                    Ok(value) => (0u32, value),
+                    Err(error) => (1u32, error),
+                };
+                self.write(&index.to_le_bytes())?;
+                self.encode(value)
+            }
+            // The pointee stands for the pointer
+            (Def::SmartPointer(_), _) => match peek.into_smart_pointer()?.borrow_inner() {
+                Some(inner) => self.encode(inner),
+                None => Err(EncodeError::UnsupportedShape(shape)),
+            },
+            (_, Type::User(UserType::Struct(_))) => {
+                let fields = peek.into_struct()?;
+                self.encode_fields(fields.fields())
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let peek_enum = peek.into_enum()?;
+                let index = peek_enum
+                    .variant_index()
+                    .map_err(|_| EncodeError::UnsupportedShape(shape))?;
+                self.write(&(index as u32).to_le_bytes())?;
+                self.encode_fields(peek_enum.fields())
+            }
+            (_, Type::Sequence(SequenceType::Tuple(_))) => peek
+                .into_tuple()?
+                .fields()
+                .try_for_each(|(_, value)| self.encode(value)),
+            (_, Type::Pointer(_)) => {
+                if let Some(s) = peek.as_str() {
+                    self.write_str(s)
+                } else if let Some(target) = peek.follow_reference() {
+                    self.encode(target)
+                } else {
+                    Err(EncodeError::UnsupportedShape(shape))
+                }
+            }
+            _ => Err(EncodeError::UnsupportedShape(shape)),
+        }
+    }
+
+    /// Writes fields one after the other, without their names. Fields that are skipped when
+    /// serializing aren't written, since there's nothing to tell they're missing.
+    fn encode_fields<'mem, 'facet>(
+        &mut self,
+        fields: impl Iterator<Item = (facet_core::Field, Peek<'mem, 'facet>)>,
+    ) -> Result<(), EncodeError> {
+        for (field, value) in fields {
+            if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+            self.encode(value)?;
+        }
+        Ok(())
+    }
+
+    fn encode_scalar(&mut self, peek: Peek<'_, '_>) -> Result<(), EncodeError> {
+        let shape = peek.shape();
+        match peek.scalar_type() {
+            Some(ScalarType::Unit) => Ok(()),
+            Some(ScalarType::Bool) => self.write(&[*peek.get::<bool>()? as u8]),
+            // As its UTF-8 bytes, without a length
+            Some(ScalarType::Char) => {
+                let mut buf = [0; 4];
+                let encoded = peek.get::<char>()?.encode_utf8(&mut buf);
+                self.write(encoded.as_bytes())
+            }
+            Some(ScalarType::Str) => self.write_str(peek.get::<&str>()?),
+            Some(ScalarType::String) => self.write_str(peek.get::<String>()?),
+            Some(ScalarType::CowStr) => {
+                self.write_str(peek.get::<std::borrow::Cow<'_, str>>()?.as_ref())
+            }
+            Some(ScalarType::F32) => self.write(&peek.get::<f32>()?.to_le_bytes()),
+            Some(ScalarType::F64) => self.write(&peek.get::<f64>()?.to_le_bytes()),
+            Some(ScalarType::U8) => self.write(&[*peek.get::<u8>()?]),
+            Some(ScalarType::U16) => self.write(&peek.get::<u16>()?.to_le_bytes()),
+            Some(ScalarType::U32) => self.write(&peek.get::<u32>()?.to_le_bytes()),
+            Some(ScalarType::U64) => self.write(&peek.get::<u64>()?.to_le_bytes()),
+            Some(ScalarType::U128) => self.write(&peek.get::<u128>()?.to_le_bytes()),
+            Some(ScalarType::USize) => self.write(&(*peek.get::<usize>()? as u64).to_le_bytes()),
+            Some(ScalarType::I8) => self.write(&peek.get::<i8>()?.to_le_bytes()),
+            Some(ScalarType::I16) => self.write(&peek.get::<i16>()?.to_le_bytes()),
+            Some(ScalarType::I32) => self.write(&peek.get::<i32>()?.to_le_bytes()),
+            Some(ScalarType::I64) => self.write(&peek.get::<i64>()?.to_le_bytes()),
+            Some(ScalarType::I128) => self.write(&peek.get::<i128>()?.to_le_bytes()),
+            Some(ScalarType::ISize) => self.write(&(*peek.get::<isize>()? as i64).to_le_bytes()),
+            // Seconds, then nanoseconds, like serde does it
+            Some(ScalarType::Duration) => {
+                let duration = peek.get::<Duration>()?;
+                self.write(&duration.as_secs().to_le_bytes())?;
+                self.write(&duration.subsec_nanos().to_le_bytes())
+            }
+            // Anything else that can display itself, like addresses or UUIDs, as a string
+            _ if shape.vtable.display.is_some() => self.write_str(&peek.to_string()),
+            _ => Err(EncodeError::UnsupportedShape(shape)),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use eyre::Result;
+use facet::Facet;
+use facet_bincode::{DecodeError, from_slice, to_vec};
+
+#[derive(Debug, PartialEq, Facet, serde::Serialize, serde::Deserialize)]
+struct Message {
+    id: u64,
+    topic: String,
+    flags: [bool; 2],
+    reply_to: Option<u16>,
+    body: Body,
+    headers: BTreeMap<String, i32>,
+    timeout: Duration,
+}
+
+#[derive(Debug, PartialEq, Facet, serde::Serialize, serde::Deserialize)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Body {
+    Empty,
+    Text(String),
+    Pair(i8, char),
+    Blob { bytes: Vec<u8>, checksum: u32 },
+}
+
+fn message() -> Message {
+    Message {
+        id: 42,
+        topic: "jobs".to_string(),
+        flags: [true, false],
+        reply_to: Some(9),
+        body: Body::Blob {
+            bytes: vec![0xde, 0xad],
+            checksum: 7,
+        },
+        headers: [("a".to_string(), -1), ("b".to_string(), 2)]
+            .into_iter()
+            .collect(),
+        timeout: Duration::new(3, 500),
+    }
+}
+
+#[test]
+fn test_scalars() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&true)?, [1]);
+    assert_eq!(to_vec(&0x1234u16)?, [0x34, 0x12]);
+    assert_eq!(to_vec(&-2i32)?, [0xfe, 0xff, 0xff, 0xff]);
+    assert_eq!(to_vec(&1usize)?, [1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(to_vec(&1.5f32)?, [0x00, 0x00, 0xc0, 0x3f]);
+    assert_eq!(to_vec(&'é')?, [0xc3, 0xa9]);
+    assert_eq!(
+        to_vec(&"hi".to_string())?,
+        [2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i']
+    );
+    assert_eq!(to_vec(&None::<u8>)?, [0]);
+    assert_eq!(to_vec(&Some(5u8))?, [1, 5]);
+    assert_eq!(to_vec(&())?, [0u8; 0]);
+
+    assert!(from_slice::<bool>(&[1])?);
+    assert_eq!(from_slice::<char>(&[0xc3, 0xa9])?, 'é');
+    assert_eq!(from_slice::<i64>(&(-7i64).to_le_bytes())?, -7);
+    assert_eq!(from_slice::<Option<u8>>(&[1, 5])?, Some(5));
+
+    Ok(())
+}
+
+#[test]
+fn test_enums_use_variant_index() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&Body::Empty)?, [0, 0, 0, 0]);
+    assert_eq!(to_vec(&Body::Pair(-1, 'x'))?, [2, 0, 0, 0, 0xff, b'x']);
+    assert_eq!(
+        from_slice::<Body>(&[2, 0, 0, 0, 0xff, b'x'])?,
+        Body::Pair(-1, 'x')
+    );
+
+    let err = from_slice::<Body>(&[4, 0, 0, 0]).unwrap_err();
+    assert!(matches!(err, DecodeError::NoSuchVariant { index: 4, .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let message = message();
+    let bytes = to_vec(&message)?;
+    assert_eq!(from_slice::<Message>(&bytes)?, message);
+
+    let tuple = (1u8, "two".to_string(), vec![3i16, 4]);
+    let bytes = to_vec(&tuple)?;
+    assert_eq!(from_slice::<(u8, String, Vec<i16>)>(&bytes)?, tuple);
+
+    Ok(())
+}
+
+#[test]
+fn test_same_bytes_as_serde_bincode() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let message = message();
+    let bytes = bincode::serialize(&message)?;
+    assert_eq!(to_vec(&message)?, bytes);
+    assert_eq!(from_slice::<Message>(&bytes)?, message);
+
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_strings() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        rest: std::borrow::Cow<'a, str>,
+    }
+
+    let bytes = to_vec(&("ab", "c"))?;
+    let borrowed: Borrowed = from_slice(&bytes)?;
+    assert_eq!(borrowed.name, "ab");
+    assert!(matches!(borrowed.rest, std::borrow::Cow::Borrowed("c")));
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed_input() {
+    facet_testhelpers::setup();
+
+    assert!(matches!(
+        from_slice::<u32>(&[1, 2]),
+        Err(DecodeError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        from_slice::<u8>(&[1, 2]),
+        Err(DecodeError::TrailingBytes(1))
+    ));
+    assert!(matches!(
+        from_slice::<bool>(&[2]),
+        Err(DecodeError::InvalidBool(2))
+    ));
+    assert!(matches!(
+        from_slice::<Option<u8>>(&[3, 0]),
+        Err(DecodeError::InvalidOptionTag(3))
+    ));
+    assert!(matches!(
+        from_slice::<String>(&[1, 0, 0, 0, 0, 0, 0, 0, 0xff]),
+        Err(DecodeError::InvalidUtf8)
+    ));
+    // A length longer than what's left
+    assert!(matches!(
+        from_slice::<Vec<u8>>(&[0xff; 8]),
+        Err(DecodeError::UnexpectedEof | DecodeError::IntegerOverflow)
+    ));
+}
@@ -0,0 +1,71 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+struct Bincode;
+
+impl Format for Bincode {
+    const NAME: &'static str = "bincode";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        facet_bincode::peek_to_vec(peek).map_err(|e| e.to_string())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        facet_bincode::from_slice(input).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Bincode>();
+}
+
+#[test]
+fn wide_integers() {
+    facet_testhelpers::setup();
+    conformance::wide_integers::<Bincode>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Bincode>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Bincode>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Bincode>();
+}
+
+#[test]
+fn options() {
+    facet_testhelpers::setup();
+    conformance::options::<Bincode>();
+}
+
+#[test]
+fn collections() {
+    facet_testhelpers::setup();
+    conformance::collections::<Bincode>();
+}
+
+#[test]
+fn structs() {
+    facet_testhelpers::setup();
+    conformance::structs::<Bincode>();
+}
+
+#[test]
+fn enums() {
+    facet_testhelpers::setup();
+    conformance::enums::<Bincode>();
+}
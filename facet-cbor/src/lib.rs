@@ -0,0 +1,6 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod serialize;
+pub use serialize::*;
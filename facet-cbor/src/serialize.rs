@@ -0,0 +1,439 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, serialize_iterative};
+use log::trace;
+use std::io::{self, Write};
+
+/// Serializes any Facet type to CBOR bytes
+pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> io::Result<Vec<u8>> {
+    peek_to_vec(Peek::new(value))
+}
+
+/// Serializes a Peek instance to CBOR bytes
+pub fn peek_to_vec(peek: Peek<'_, '_>) -> io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    peek_to_writer(peek, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Serializes any Facet type to canonical CBOR bytes, the same for equal values
+pub fn to_vec_canonical<'a, T: Facet<'a>>(value: &'a T) -> io::Result<Vec<u8>> {
+    peek_to_vec_canonical(Peek::new(value))
+}
+
+/// Serializes a Peek instance to canonical CBOR bytes, the same for equal values
+pub fn peek_to_vec_canonical(peek: Peek<'_, '_>) -> io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    peek_to_writer_canonical(peek, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Serializes any Facet type to a writer in CBOR format
+pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &'a T, writer: &mut W) -> io::Result<()> {
+    peek_to_writer(Peek::new(value), writer)
+}
+
+/// Serializes a Peek instance to a writer in CBOR format
+pub fn peek_to_writer<W: Write>(peek: Peek<'_, '_>, writer: &mut W) -> io::Result<()> {
+    let mut serializer = CborSerializer::new(writer);
+    serialize_iterative(peek, &mut serializer)
+}
+
+/// Serializes any Facet type to a writer in canonical CBOR format
+pub fn to_writer_canonical<'a, T: Facet<'a>, W: Write>(
+    value: &'a T,
+    writer: &mut W,
+) -> io::Result<()> {
+    peek_to_writer_canonical(Peek::new(value), writer)
+}
+
+/// Serializes a Peek instance to a writer in canonical CBOR format
+pub fn peek_to_writer_canonical<W: Write>(peek: Peek<'_, '_>, writer: &mut W) -> io::Result<()> {
+    let mut serializer = CborSerializer::new(writer).canonical();
+    serialize_iterative(peek, &mut serializer)
+}
+
+/// CBOR major types, already shifted into the top three bits of the initial byte
+mod major {
+    pub(crate) const UNSIGNED: u8 = 0 << 5;
+    pub(crate) const NEGATIVE: u8 = 1 << 5;
+    pub(crate) const BYTES: u8 = 2 << 5;
+    pub(crate) const TEXT: u8 = 3 << 5;
+    pub(crate) const ARRAY: u8 = 4 << 5;
+    pub(crate) const MAP: u8 = 5 << 5;
+    pub(crate) const TAG: u8 = 6 << 5;
+    pub(crate) const SIMPLE: u8 = 7 << 5;
+}
+
+const FALSE: u8 = major::SIMPLE | 20;
+const TRUE: u8 = major::SIMPLE | 21;
+const NULL: u8 = major::SIMPLE | 22;
+const HALF: u8 = major::SIMPLE | 25;
+const SINGLE: u8 = major::SIMPLE | 26;
+const DOUBLE: u8 = major::SIMPLE | 27;
+const INDEFINITE: u8 = 31;
+const BREAK: u8 = 0xff;
+
+/// Tags for integers that don't fit in 64 bits
+const POSITIVE_BIGNUM: u64 = 2;
+const NEGATIVE_BIGNUM: u64 = 3;
+
+/// A serializer for CBOR that implements the `facet_serialize::Serializer` trait.
+///
+/// Integers and lengths always use their shortest encoding. In [canonical](Self::canonical)
+/// mode, maps are buffered until they end so their entries can be sorted.
+pub struct CborSerializer<'w, W: Write> {
+    writer: &'w mut W,
+    canonical: bool,
+    /// Arrays and maps that haven't ended yet, innermost last
+    stack: Vec<Container>,
+}
+
+enum Container {
+    /// Ends with a break if it was started without a length
+    Array { indefinite: bool },
+    /// Ends with a break if it was started without a length
+    Map { indefinite: bool },
+    /// A map in canonical mode, written out once all its entries are in
+    Sorted(SortedMap),
+}
+
+#[derive(Default)]
+struct SortedMap {
+    /// Encoded keys and values
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+    /// The encoded key waiting for its value
+    key: Option<Vec<u8>>,
+    /// The key or value being written
+    buffer: Vec<u8>,
+}
+
+impl<'w, W: Write> CborSerializer<'w, W> {
+    /// Creates a new CBOR serializer writing to `writer`.
+    pub fn new(writer: &'w mut W) -> Self {
+        Self {
+            writer,
+            canonical: false,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Writes canonical CBOR (RFC 8949 §4.2.1): map entries sorted by their encoded keys,
+    /// floats in their shortest exact form and only definite lengths.
+    pub fn canonical(mut self) -> Self {
+        self.canonical = true;
+        self
+    }
+
+    /// Writes to the innermost map being sorted, or straight to the writer
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let sorted = self.stack.iter_mut().rev().find_map(|c| match c {
+            Container::Sorted(map) => Some(map),
+            _ => None,
+        });
+        match sorted {
+            Some(map) => {
+                map.buffer.extend_from_slice(bytes);
+                Ok(())
+            }
+            None => self.writer.write_all(bytes),
+        }
+    }
+
+    /// Writes the initial byte of an item, with its argument in as few bytes as possible
+    fn write_head(&mut self, major: u8, value: u64) -> io::Result<()> {
+        if value < 24 {
+            self.write(&[major | value as u8])
+        } else if value <= u8::MAX as u64 {
+            self.write(&[major | 24, value as u8])
+        } else if value <= u16::MAX as u64 {
+            let [a, b] = (value as u16).to_be_bytes();
+            self.write(&[major | 25, a, b])
+        } else if value <= u32::MAX as u64 {
+            let [a, b, c, d] = (value as u32).to_be_bytes();
+            self.write(&[major | 26, a, b, c, d])
+        } else {
+            let mut bytes = [major | 27; 9];
+            bytes[1..].copy_from_slice(&value.to_be_bytes());
+            self.write(&bytes)
+        }
+    }
+
+    /// Called after every complete item: the items of a sorted map alternate between keys
+    /// and values
+    fn item_done(&mut self) {
+        if let Some(Container::Sorted(map)) = self.stack.last_mut() {
+            let bytes = std::mem::take(&mut map.buffer);
+            match map.key.take() {
+                Some(key) => map.entries.push((key, bytes)),
+                None => map.key = Some(bytes),
+            }
+        }
+    }
+
+    /// Writes a whole item that isn't an array or a map
+    fn write_item(&mut self, major: u8, value: u64, payload: &[u8]) -> io::Result<()> {
+        self.write_head(major, value)?;
+        self.write(payload)?;
+        self.item_done();
+        Ok(())
+    }
+
+    /// Writes a whole simple value or float
+    fn write_simple(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.write(bytes)?;
+        self.item_done();
+        Ok(())
+    }
+
+    fn start_container(&mut self, major: u8, len: Option<usize>) -> io::Result<()> {
+        let map = major == major::MAP;
+        if map && self.canonical {
+            self.stack.push(Container::Sorted(SortedMap::default()));
+            return Ok(());
+        }
+
+        let indefinite = match len {
+            Some(len) => {
+                self.write_head(major, len as u64)?;
+                false
+            }
+            None if self.canonical => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "canonical CBOR needs the length of arrays upfront",
+                ));
+            }
+            None => {
+                self.write(&[major | INDEFINITE])?;
+                true
+            }
+        };
+        self.stack.push(if map {
+            Container::Map { indefinite }
+        } else {
+            Container::Array { indefinite }
+        });
+        Ok(())
+    }
+
+    fn end_container(&mut self) -> io::Result<()> {
+        match self.stack.pop() {
+            Some(Container::Array { indefinite } | Container::Map { indefinite }) => {
+                if indefinite {
+                    self.write(&[BREAK])?;
+                }
+            }
+            Some(Container::Sorted(mut map)) => {
+                // Keys are compared as bytes, so shorter keys come first
+                map.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                self.write_head(major::MAP, map.entries.len() as u64)?;
+                for (key, value) in &map.entries {
+                    self.write(key)?;
+                    self.write(value)?;
+                }
+            }
+            None => {}
+        }
+        self.item_done();
+        Ok(())
+    }
+
+    fn write_bignum(&mut self, tag: u64, value: u128) -> io::Result<()> {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+        self.write_head(major::TAG, tag)?;
+        self.write_item(major::BYTES, (bytes.len() - start) as u64, &bytes[start..])
+    }
+
+    /// Writes a double, or in canonical mode the shortest float that has the same value
+    fn write_float(&mut self, value: f64) -> io::Result<()> {
+        if self.canonical {
+            if value.is_nan() {
+                return self.write_simple(&[HALF, 0x7e, 0x00]);
+            }
+            if let Some(half) = to_f16(value) {
+                let [a, b] = half.to_be_bytes();
+                return self.write_simple(&[HALF, a, b]);
+            }
+            if value as f32 as f64 == value {
+                return self.write_single(value as f32);
+            }
+        }
+        let mut bytes = [DOUBLE; 9];
+        bytes[1..].copy_from_slice(&value.to_bits().to_be_bytes());
+        self.write_simple(&bytes)
+    }
+
+    fn write_single(&mut self, value: f32) -> io::Result<()> {
+        let mut bytes = [SINGLE; 5];
+        bytes[1..].copy_from_slice(&value.to_bits().to_be_bytes());
+        self.write_simple(&bytes)
+    }
+}
+
+/// The bits of `value` as a half-precision float, if it has one that's exactly equal
+fn to_f16(value: f64) -> Option<u16> {
+    let single = value as f32;
+    if single as f64 != value {
+        return None;
+    }
+    let bits = single.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    if single == 0.0 {
+        return Some(sign);
+    }
+    if single.is_infinite() {
+        return Some(sign | 0x7c00);
+    }
+
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = bits & 0x7f_ffff;
+    match exponent {
+        // Normal: the 13 mantissa bits that don't fit must be zero
+        -14..=15 if mantissa & 0x1fff == 0 => {
+            Some(sign | (((exponent + 15) as u16) << 10) | (mantissa >> 13) as u16)
+        }
+        // Subnormal: the value is a multiple of 2^-24
+        -24..=-15 => {
+            let full = mantissa | 0x80_0000;
+            let shift = -(exponent + 1) as u32;
+            if full & ((1 << shift) - 1) == 0 {
+                Some(sign | (full >> shift) as u16)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+impl<W: Write> Serializer for CborSerializer<'_, W> {
+    type Error = io::Error;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        trace!("Serializing u64: {}", value);
+        self.write_item(major::UNSIGNED, value, &[])
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        trace!("Serializing u128: {}", value);
+        match u64::try_from(value) {
+            Ok(value) => self.serialize_u64(value),
+            Err(_) => self.write_bignum(POSITIVE_BIGNUM, value),
+        }
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        trace!("Serializing i64: {}", value);
+        if value >= 0 {
+            self.write_item(major::UNSIGNED, value as u64, &[])
+        } else {
+            // -1 - n, which can't overflow: `!value` is the same bits
+            self.write_item(major::NEGATIVE, !value as u64, &[])
+        }
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        trace!("Serializing i128: {}", value);
+        if let Ok(value) = i64::try_from(value) {
+            return self.serialize_i64(value);
+        }
+        if value >= 0 {
+            return self.serialize_u128(value as u128);
+        }
+        let value = !value as u128;
+        match u64::try_from(value) {
+            Ok(value) => self.write_item(major::NEGATIVE, value, &[]),
+            Err(_) => self.write_bignum(NEGATIVE_BIGNUM, value),
+        }
+    }
+
+    fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        trace!("Serializing f32: {}", value);
+        if self.canonical {
+            return self.write_float(value as f64);
+        }
+        self.write_single(value)
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        trace!("Serializing f64: {}", value);
+        self.write_float(value)
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        trace!("Serializing bool: {}", value);
+        self.write_simple(&[if value { TRUE } else { FALSE }])
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        trace!("Serializing char: {}", value);
+        let mut buf = [0; 4];
+        self.serialize_str(value.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        trace!("Serializing str: {}", value);
+        self.write_item(major::TEXT, value.len() as u64, value.as_bytes())
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        trace!("Serializing bytes, length: {}", value.len());
+        self.write_item(major::BYTES, value.len() as u64, value)
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        trace!("Serializing none");
+        self.write_simple(&[NULL])
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        trace!("Serializing unit");
+        self.write_simple(&[NULL])
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'static str,
+    ) -> Result<(), Self::Error> {
+        trace!("Serializing unit variant: {}", variant_name);
+        self.serialize_str(variant_name)
+    }
+
+    fn start_object(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        trace!("Starting object, len: {:?}", len);
+        self.start_container(major::MAP, len)
+    }
+
+    fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        trace!("Serializing field name: {}", name);
+        self.serialize_str(name)
+    }
+
+    fn start_array(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        trace!("Starting array, len: {:?}", len);
+        self.start_container(major::ARRAY, len)
+    }
+
+    fn start_map(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        trace!("Starting map, len: {:?}", len);
+        self.start_container(major::MAP, len)
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        trace!("Ending object");
+        self.end_container()
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        trace!("Ending array");
+        self.end_container()
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        trace!("Ending map");
+        self.end_container()
+    }
+}
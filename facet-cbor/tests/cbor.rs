@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_cbor::{peek_to_vec_canonical, to_vec, to_vec_canonical, to_writer_canonical};
+use facet_reflect::Peek;
+
+#[derive(Facet)]
+struct Manifest {
+    version: u32,
+    name: String,
+    tags: Vec<String>,
+    parent: Option<u64>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Shape {
+    Empty,
+    Circle(f64),
+}
+
+#[test]
+fn test_integers_use_shortest_encoding() {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&0u64).unwrap(), [0x00]);
+    assert_eq!(to_vec(&23u8).unwrap(), [0x17]);
+    assert_eq!(to_vec(&24u64).unwrap(), [0x18, 0x18]);
+    assert_eq!(to_vec(&1000u64).unwrap(), [0x19, 0x03, 0xe8]);
+    assert_eq!(
+        to_vec(&1_000_000u64).unwrap(),
+        [0x1a, 0x00, 0x0f, 0x42, 0x40]
+    );
+    assert_eq!(
+        to_vec(&u64::MAX).unwrap(),
+        [0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_eq!(to_vec(&-1i32).unwrap(), [0x20]);
+    assert_eq!(to_vec(&-1000i64).unwrap(), [0x39, 0x03, 0xe7]);
+    assert_eq!(
+        to_vec(&i64::MIN).unwrap(),
+        [0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+
+    // Past 64 bits, bignums
+    assert_eq!(
+        to_vec(&(u64::MAX as u128 + 1)).unwrap(),
+        [
+            0xc2, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+    assert_eq!(
+        to_vec(&(-(u64::MAX as i128) - 2)).unwrap(),
+        [
+            0xc3, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn test_scalars() {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&true).unwrap(), [0xf5]);
+    assert_eq!(to_vec(&false).unwrap(), [0xf4]);
+    assert_eq!(to_vec(&None::<u8>).unwrap(), [0xf6]);
+    assert_eq!(to_vec(&Some(1u8)).unwrap(), [0x01]);
+    assert_eq!(
+        to_vec(&"IETF".to_string()).unwrap(),
+        [0x64, b'I', b'E', b'T', b'F']
+    );
+    assert_eq!(to_vec(&'a').unwrap(), [0x61, b'a']);
+    assert_eq!(
+        to_vec(&1.5f64).unwrap(),
+        [0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(to_vec(&1.5f32).unwrap(), [0xfa, 0x3f, 0xc0, 0x00, 0x00]);
+}
+
+#[test]
+fn test_structs_and_enums() {
+    facet_testhelpers::setup();
+
+    let manifest = Manifest {
+        version: 2,
+        name: "a".to_string(),
+        tags: vec!["x".to_string()],
+        parent: None,
+    };
+    #[rustfmt::skip]
+    let expected = [
+        0xa4,
+        0x67, b'v', b'e', b'r', b's', b'i', b'o', b'n', 0x02,
+        0x64, b'n', b'a', b'm', b'e', 0x61, b'a',
+        0x64, b't', b'a', b'g', b's', 0x81, 0x61, b'x',
+        0x66, b'p', b'a', b'r', b'e', b'n', b't', 0xf6,
+    ];
+    assert_eq!(to_vec(&manifest).unwrap(), expected);
+
+    assert_eq!(
+        to_vec(&Shape::Empty).unwrap(),
+        [0x65, b'E', b'm', b'p', b't', b'y']
+    );
+    #[rustfmt::skip]
+    let expected = [
+        0xa1,
+        0x66, b'C', b'i', b'r', b'c', b'l', b'e',
+        0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    assert_eq!(to_vec(&Shape::Circle(1.5)).unwrap(), expected);
+}
+
+#[test]
+fn test_canonical_sorts_map_keys() {
+    facet_testhelpers::setup();
+
+    let manifest = Manifest {
+        version: 2,
+        name: "a".to_string(),
+        tags: vec!["x".to_string()],
+        parent: None,
+    };
+    // Shorter keys first, then bytewise
+    #[rustfmt::skip]
+    let expected = [
+        0xa4,
+        0x64, b'n', b'a', b'm', b'e', 0x61, b'a',
+        0x64, b't', b'a', b'g', b's', 0x81, 0x61, b'x',
+        0x66, b'p', b'a', b'r', b'e', b'n', b't', 0xf6,
+        0x67, b'v', b'e', b'r', b's', b'i', b'o', b'n', 0x02,
+    ];
+    assert_eq!(to_vec_canonical(&manifest).unwrap(), expected);
+    assert_eq!(
+        peek_to_vec_canonical(Peek::new(&manifest)).unwrap(),
+        expected
+    );
+
+    let mut writer = Vec::new();
+    to_writer_canonical(&manifest, &mut writer).unwrap();
+    assert_eq!(writer, expected);
+}
+
+#[test]
+fn test_canonical_is_independent_of_insertion_order() {
+    facet_testhelpers::setup();
+
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+    for i in 0..100u32 {
+        forward.insert(i.to_string(), vec![i]);
+        backward.insert((99 - i).to_string(), vec![99 - i]);
+    }
+    assert_eq!(
+        to_vec_canonical(&forward).unwrap(),
+        to_vec_canonical(&backward).unwrap()
+    );
+
+    let mut nested = HashMap::new();
+    nested.insert(10u32, HashMap::from([("b", 1u8), ("a", 2u8)]));
+    nested.insert(1u32, HashMap::new());
+    #[rustfmt::skip]
+    let expected = [
+        0xa2,
+        0x01, 0xa0,
+        0x0a, 0xa2, 0x61, b'a', 0x02, 0x61, b'b', 0x01,
+    ];
+    assert_eq!(to_vec_canonical(&nested).unwrap(), expected);
+}
+
+#[test]
+fn test_canonical_floats_use_shortest_form() {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec_canonical(&0.0f64).unwrap(), [0xf9, 0x00, 0x00]);
+    assert_eq!(to_vec_canonical(&-0.0f64).unwrap(), [0xf9, 0x80, 0x00]);
+    assert_eq!(to_vec_canonical(&1.5f64).unwrap(), [0xf9, 0x3e, 0x00]);
+    assert_eq!(to_vec_canonical(&65504.0f64).unwrap(), [0xf9, 0x7b, 0xff]);
+    assert_eq!(
+        to_vec_canonical(&5.960464477539063e-8f64).unwrap(),
+        [0xf9, 0x00, 0x01]
+    );
+    assert_eq!(
+        to_vec_canonical(&f64::INFINITY).unwrap(),
+        [0xf9, 0x7c, 0x00]
+    );
+    assert_eq!(
+        to_vec_canonical(&f64::NEG_INFINITY).unwrap(),
+        [0xf9, 0xfc, 0x00]
+    );
+    assert_eq!(to_vec_canonical(&f64::NAN).unwrap(), [0xf9, 0x7e, 0x00]);
+    assert_eq!(to_vec_canonical(&f32::NAN).unwrap(), [0xf9, 0x7e, 0x00]);
+    assert_eq!(
+        to_vec_canonical(&100000.0f64).unwrap(),
+        [0xfa, 0x47, 0xc3, 0x50, 0x00]
+    );
+    assert_eq!(
+        to_vec_canonical(&1.1f64).unwrap(),
+        [0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]
+    );
+    // Whatever the Rust type
+    assert_eq!(
+        to_vec_canonical(&1.5f32).unwrap(),
+        to_vec_canonical(&1.5f64).unwrap()
+    );
+}
+
+#[test]
+fn test_unsupported_values_fail() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Pair {
+        Both(u8, u8),
+    }
+
+    let err = to_vec(&Pair::Both(1, 2)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string()
+            .contains("tuple variants can't be internally tagged"),
+        "{err}"
+    );
+}
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use facet_core::Facet;
+use facet_json::Value;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+// facet-cbor only serializes, so values are read back by decoding the CBOR here into a JSON
+// `Value` and deserializing that. The decoder follows RFC 8949 and knows nothing of facet,
+// so it checks what was written, not how. Not covered: wide integers, which are written as
+// bignum tags that JSON has no equivalent for
+struct Cbor;
+
+impl Format for Cbor {
+    const NAME: &'static str = "CBOR";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        facet_cbor::peek_to_vec(peek).map_err(|e| e.to_string())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        let mut decoder = Decoder { input, pos: 0 };
+        let value = decoder.item()?;
+        if decoder.pos != input.len() {
+            return Err(format!("{} trailing bytes", input.len() - decoder.pos));
+        }
+        facet_json::from_value(&value).map_err(|e| e.to_string())
+    }
+}
+
+struct Decoder<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let bytes = self
+            .input
+            .get(self.pos..self.pos + len)
+            .ok_or("unexpected end of input")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Reads an initial byte, returning its major type, additional information, and argument
+    /// (`None` if indefinite)
+    fn head(&mut self) -> Result<(u8, u8, Option<u64>), String> {
+        let initial = self.take(1)?[0];
+        let info = initial & 0x1f;
+        let argument = match info {
+            0..24 => Some(info as u64),
+            24..28 => {
+                let bytes = self.take(1 << (info - 24))?;
+                Some(bytes.iter().fold(0, |n, &b| (n << 8) | b as u64))
+            }
+            31 => None,
+            _ => return Err(format!("reserved additional information {info}")),
+        };
+        Ok((initial >> 5, info, argument))
+    }
+
+    fn at_break(&mut self) -> bool {
+        let at_break = self.input.get(self.pos) == Some(&0xff);
+        if at_break {
+            self.pos += 1;
+        }
+        at_break
+    }
+
+    fn text(&mut self, len: Option<u64>) -> Result<String, String> {
+        let len = len.ok_or("indefinite strings aren't written")?;
+        let bytes = self.take(len as usize)?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+    }
+
+    fn item(&mut self) -> Result<Value, String> {
+        let (major, info, argument) = self.head()?;
+        Ok(match (major, argument) {
+            (0, Some(n)) => Value::U64(n),
+            (1, Some(n)) => Value::I64(-1 - i64::try_from(n).map_err(|e| e.to_string())?),
+            (3, len) => Value::String(self.text(len)?),
+            (4, len) => {
+                let mut items = Vec::new();
+                while len.is_some_and(|len| items.len() < len as usize)
+                    || (len.is_none() && !self.at_break())
+                {
+                    items.push(self.item()?);
+                }
+                Value::Array(items)
+            }
+            (5, len) => {
+                let mut entries = BTreeMap::new();
+                let mut count = 0;
+                while len.is_some_and(|len| count < len) || (len.is_none() && !self.at_break()) {
+                    let key = match self.item()? {
+                        Value::String(key) => key,
+                        key => return Err(format!("map key {key:?} isn't a string")),
+                    };
+                    entries.insert(key, self.item()?);
+                    count += 1;
+                }
+                Value::Object(entries)
+            }
+            (7, Some(20)) => Value::Bool(false),
+            (7, Some(21)) => Value::Bool(true),
+            (7, Some(22)) => Value::Null,
+            (7, Some(bits)) if info == 26 => Value::F64(f32::from_bits(bits as u32) as f64),
+            (7, Some(bits)) if info == 27 => Value::F64(f64::from_bits(bits)),
+            _ => return Err(format!("major type {major} with {info} isn't written")),
+        })
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Cbor>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Cbor>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Cbor>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Cbor>();
+}
+
+#[test]
+fn options() {
+    facet_testhelpers::setup();
+    conformance::options::<Cbor>();
+}
+
+#[test]
+fn collections() {
+    facet_testhelpers::setup();
+    conformance::collections::<Cbor>();
+}
+
+#[test]
+fn structs() {
+    facet_testhelpers::setup();
+    conformance::structs::<Cbor>();
+}
+
+#[test]
+fn enums() {
+    facet_testhelpers::setup();
+    conformance::enums::<Cbor>();
+}
@@ -0,0 +1,580 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::alloc::Layout;
+use core::fmt::Write;
+
+use facet_core::{
+    BaseRepr, Def, EnumRepr, EnumType, Facet, Field, NumberBits, PointerType, ScalarAffinity,
+    Shape, Signedness, Type, UserType,
+};
+
+/// Generates a C header declaring `T` and every type it refers to
+pub fn to_header<'a, T: Facet<'a>>() -> Result<String, HeaderError> {
+    Generator::new().add::<T>().finish()
+}
+
+/// Collects C declarations for a set of `repr(C)` types
+///
+/// Structs become structs, enums without data become an integer typedef of
+/// the enum's `repr` along with constants for its discriminants, and enums with data become
+/// tagged unions. Every declaration's layout is computed the way a C compiler would and
+/// checked against the offsets and sizes Rust recorded, so a header is only generated when
+/// it matches the Rust types exactly.
+#[derive(Debug, Default)]
+pub struct Generator {
+    roots: Vec<&'static Shape>,
+}
+
+impl Generator {
+    /// Creates a generator with no declarations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the declaration of `T`, and of every type it refers to
+    pub fn add<'a, T: Facet<'a>>(self) -> Self {
+        self.add_shape(T::SHAPE)
+    }
+
+    /// Adds the declaration of `shape`, and of every type it refers to
+    pub fn add_shape(mut self, shape: &'static Shape) -> Self {
+        self.roots.push(shape);
+        self
+    }
+
+    /// Returns the header, with every type declared before the types that contain it
+    pub fn finish(self) -> Result<String, HeaderError> {
+        let mut declarations = Declarations::default();
+        for root in self.roots {
+            declarations.declare(root, &type_name(root))?;
+        }
+        // Types only pointed to don't have to come first, so they're declared last
+        let mut i = 0;
+        while let Some(&shape) = declarations.pointees.get(i) {
+            declarations.declare(shape, &type_name(shape))?;
+            i += 1;
+        }
+
+        let mut out = String::from("#pragma once\n\n#include <stdbool.h>\n#include <stdint.h>\n");
+        for declaration in declarations.declarations {
+            write!(out, "\n{declaration}").unwrap();
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Default)]
+struct Declarations {
+    declared: Vec<&'static Shape>,
+    pointees: Vec<&'static Shape>,
+    declarations: Vec<String>,
+}
+
+/// A field of a C struct, along with its layout
+struct Member {
+    field: &'static Field,
+    layout: Layout,
+    path: String,
+}
+
+impl Declarations {
+    /// Declares a struct or enum, after the types it contains, and returns its name
+    fn declare(&mut self, shape: &'static Shape, path: &str) -> Result<String, HeaderError> {
+        let name = type_name(shape);
+        if self.declared.contains(&shape) {
+            return Ok(name);
+        }
+        self.declared.push(shape);
+        let layout = sized_layout(shape, path)?;
+
+        let mut out = String::new();
+        match shape.ty {
+            Type::User(UserType::Struct(st)) => {
+                match st.repr.base {
+                    BaseRepr::C if !st.repr.packed => {}
+                    BaseRepr::C => return Err(unsupported(path, shape)),
+                    _ => return Err(not_repr_c(path, shape)),
+                }
+                let members = members(st.fields, &name)?;
+                // C structs can't be empty
+                if members.is_empty() {
+                    return Err(unsupported(path, shape));
+                }
+                let (end, align) = place(&members, 0)?;
+                check_size(path, layout, end, align)?;
+
+                write_doc(&mut out, shape.doc, "");
+                writeln!(out, "typedef struct {name} {{").unwrap();
+                self.write_members(&mut out, &members, "    ")?;
+                writeln!(out, "}} {name};").unwrap();
+            }
+            Type::User(UserType::Enum(et)) if is_fieldless(et) => {
+                let (tag, tag_layout) = tag_type(path, shape, et)?;
+                check_size(path, layout, tag_layout.size(), tag_layout.align())?;
+                write_doc(&mut out, shape.doc, "");
+                write_tag(&mut out, &name, &name, tag, et);
+            }
+            Type::User(UserType::Enum(et)) => self.write_tagged_union(&mut out, shape, et, path)?,
+            _ => return Err(unsupported(path, shape)),
+        }
+
+        self.declarations.push(out);
+        Ok(name)
+    }
+
+    /// Writes an enum with data as its tag, followed by a union of its variants
+    fn write_tagged_union(
+        &mut self,
+        out: &mut String,
+        shape: &'static Shape,
+        et: EnumType,
+        path: &str,
+    ) -> Result<(), HeaderError> {
+        let name = type_name(shape);
+        let (tag, _) = tag_type(path, shape, et)?;
+        let is_c = is_c_layout(shape, et, path)?;
+
+        let tag_name = format!("{name}_Tag");
+        write_tag(out, &name, &tag_name, tag, et);
+        writeln!(out).unwrap();
+        write_doc(out, shape.doc, "");
+        let indent = match is_c {
+            true => {
+                writeln!(
+                    out,
+                    "typedef struct {name} {{\n    {tag_name} tag;\n    union {{"
+                )
+                .unwrap();
+                "        "
+            }
+            false => {
+                writeln!(out, "typedef union {name} {{\n    {tag_name} tag;").unwrap();
+                "    "
+            }
+        };
+        for variant in et.variants {
+            let members = members(variant.data.fields, &format!("{name}.{}", variant.name))?;
+            if members.is_empty() {
+                continue;
+            }
+            write_doc(out, variant.doc, indent);
+            writeln!(out, "{indent}struct {{").unwrap();
+            let member_indent = format!("{indent}    ");
+            if !is_c {
+                writeln!(out, "{member_indent}{tag_name} tag;").unwrap();
+            }
+            self.write_members(out, &members, &member_indent)?;
+            writeln!(
+                out,
+                "{indent}}} {};",
+                c_identifier(&snake_case(variant.name))
+            )
+            .unwrap();
+        }
+        if is_c {
+            writeln!(out, "    }};").unwrap();
+        }
+        writeln!(out, "}} {name};").unwrap();
+        Ok(())
+    }
+
+    fn write_members(
+        &mut self,
+        out: &mut String,
+        members: &[Member],
+        indent: &str,
+    ) -> Result<(), HeaderError> {
+        for member in members {
+            let field = member.field;
+            let (ty, dims) = self.c_type(field.shape(), &member.path)?;
+            let name = format!("{}{dims}", c_identifier(field.name));
+            write_doc(out, field.doc, indent);
+            writeln!(out, "{indent}{};", declarator(&ty, &name)).unwrap();
+        }
+        Ok(())
+    }
+
+    /// Returns the C type of `shape`, and the array dimensions that go after the name
+    fn c_type(
+        &mut self,
+        shape: &'static Shape,
+        path: &str,
+    ) -> Result<(String, String), HeaderError> {
+        if let Def::Array(ad) = shape.def {
+            let (ty, dims) = self.c_type(ad.t(), path)?;
+            return Ok((ty, format!("[{}]{dims}", ad.n)));
+        }
+
+        let ty = match (shape.def, shape.ty) {
+            (Def::Scalar(sd), _) => match sd.affinity {
+                _ if shape.is_type::<usize>() => "uintptr_t",
+                _ if shape.is_type::<isize>() => "intptr_t",
+                ScalarAffinity::Number(na) => match na.bits {
+                    NumberBits::Integer { bits, sign } => match (bits, sign) {
+                        (8, Signedness::Unsigned) => "uint8_t",
+                        (16, Signedness::Unsigned) => "uint16_t",
+                        (32, Signedness::Unsigned) => "uint32_t",
+                        (64, Signedness::Unsigned) => "uint64_t",
+                        (8, Signedness::Signed) => "int8_t",
+                        (16, Signedness::Signed) => "int16_t",
+                        (32, Signedness::Signed) => "int32_t",
+                        (64, Signedness::Signed) => "int64_t",
+                        _ => return Err(unsupported(path, shape)),
+                    },
+                    NumberBits::Float {
+                        mantissa_bits: 23, ..
+                    } => "float",
+                    NumberBits::Float {
+                        mantissa_bits: 52, ..
+                    } => "double",
+                    _ => return Err(unsupported(path, shape)),
+                },
+                ScalarAffinity::Boolean(_) => "bool",
+                // A Rust `char` is a Unicode scalar value, not a C `char`
+                ScalarAffinity::Char(_) => "uint32_t",
+                _ => return Err(unsupported(path, shape)),
+            },
+            (_, Type::Pointer(PointerType::Raw(vpt) | PointerType::Reference(vpt)))
+                if !vpt.wide =>
+            {
+                let target = (vpt.target)();
+                let pointee = if let Some(keyword) = aggregate_keyword(target, path)? {
+                    // A pointer only needs the struct's tag, so it may be defined later
+                    self.pointees.push(target);
+                    format!("{keyword} {}", type_name(target))
+                } else {
+                    match self.c_type(target, path)? {
+                        (ty, dims) if dims.is_empty() => ty,
+                        _ => return Err(unsupported(path, shape)),
+                    }
+                };
+                return Ok((pointer(&pointee, vpt.mutable), String::new()));
+            }
+            (_, Type::User(UserType::Struct(_)) | Type::User(UserType::Enum(_))) => {
+                return Ok((self.declare(shape, path)?, String::new()));
+            }
+            _ => return Err(unsupported(path, shape)),
+        };
+        Ok((ty.to_string(), String::new()))
+    }
+}
+
+/// The fields of a struct or variant, except zero-sized ones like `PhantomData`: they take
+/// no room, and C has no way to say them
+fn members(fields: &'static [Field], parent: &str) -> Result<Vec<Member>, HeaderError> {
+    let mut members = Vec::with_capacity(fields.len());
+    for field in fields {
+        let path = format!("{parent}.{}", field.name);
+        let layout = sized_layout(field.shape(), &path)?;
+        if layout.size() > 0 {
+            members.push(Member {
+                field,
+                layout,
+                path,
+            });
+        }
+    }
+    Ok(members)
+}
+
+/// Lays out `members` one after the other from `start`, the way C does, checking that each
+/// one lands where Rust put it; returns the end of the last one and the largest alignment
+fn place(members: &[Member], start: usize) -> Result<(usize, usize), HeaderError> {
+    let (mut end, mut align) = (start, 1);
+    for member in members {
+        let offset = end.next_multiple_of(member.layout.align());
+        if offset != member.field.offset {
+            return Err(HeaderError {
+                path: member.path.clone(),
+                kind: HeaderErrorKind::OffsetMismatch {
+                    recorded: member.field.offset,
+                    computed: offset,
+                },
+            });
+        }
+        end = offset + member.layout.size();
+        align = align.max(member.layout.align());
+    }
+    Ok((end, align))
+}
+
+/// Whether an enum with data is laid out as a struct of its tag and a union of its variants'
+/// fields, like `repr(C)` enums, or as a union of structs that each start with the tag, like
+/// `repr(u8)` (and friends) ones
+fn is_c_layout(shape: &'static Shape, et: EnumType, path: &str) -> Result<bool, HeaderError> {
+    let name = type_name(shape);
+    let layout = sized_layout(shape, path)?;
+    let (_, tag) = tag_type(path, shape, et)?;
+    let mut variants = Vec::with_capacity(et.variants.len());
+    for variant in et.variants {
+        variants.push(members(
+            variant.data.fields,
+            &format!("{name}.{}", variant.name),
+        )?);
+    }
+
+    let c_layout = || {
+        let union_align = variants
+            .iter()
+            .flatten()
+            .map(|member| member.layout.align())
+            .fold(1, usize::max);
+        let union_offset = tag.size().next_multiple_of(union_align);
+        let mut union_size = 0;
+        for members in &variants {
+            let (end, align) = place(members, union_offset)?;
+            union_size = union_size.max((end - union_offset).next_multiple_of(align));
+        }
+        let align = tag.align().max(union_align);
+        check_size(path, layout, union_offset + union_size, align)
+    };
+    let primitive_layout = || {
+        let (mut size, mut align) = (tag.size(), tag.align());
+        for members in &variants {
+            let (end, members_align) = place(members, tag.size())?;
+            size = size.max(end);
+            align = align.max(members_align);
+        }
+        check_size(path, layout, size, align)
+    };
+    match (c_layout(), primitive_layout()) {
+        (Ok(()), _) => Ok(true),
+        (Err(_), Ok(())) => Ok(false),
+        (Err(e), Err(_)) => Err(e),
+    }
+}
+
+fn check_size(path: &str, layout: Layout, end: usize, align: usize) -> Result<(), HeaderError> {
+    let size = end.next_multiple_of(align);
+    match size == layout.size() {
+        true => Ok(()),
+        false => Err(HeaderError {
+            path: path.to_string(),
+            kind: HeaderErrorKind::SizeMismatch {
+                recorded: layout.size(),
+                computed: size,
+            },
+        }),
+    }
+}
+
+/// Writes the discriminants of an enum as constants, and the enum's `repr` as the tag's type
+fn write_tag(out: &mut String, name: &str, tag_name: &str, tag: &str, et: EnumType) {
+    let prefix = screaming_snake_case(name);
+    writeln!(out, "enum {tag_name} {{").unwrap();
+    for (i, variant) in et.variants.iter().enumerate() {
+        write_doc(out, variant.doc, "    ");
+        let value = variant.discriminant.unwrap_or(i as i64);
+        let variant = screaming_snake_case(variant.name);
+        writeln!(out, "    {prefix}_{variant} = {value},").unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    // C enums are `int`-sized, which isn't necessarily what Rust uses
+    writeln!(out, "typedef {tag} {tag_name};").unwrap();
+}
+
+/// The C type and layout of an enum's tag
+fn tag_type(
+    path: &str,
+    shape: &'static Shape,
+    et: EnumType,
+) -> Result<(&'static str, Layout), HeaderError> {
+    Ok(match et.enum_repr {
+        EnumRepr::U8 => ("uint8_t", Layout::new::<u8>()),
+        EnumRepr::U16 => ("uint16_t", Layout::new::<u16>()),
+        EnumRepr::U32 => ("uint32_t", Layout::new::<u32>()),
+        EnumRepr::U64 => ("uint64_t", Layout::new::<u64>()),
+        EnumRepr::USize => ("uintptr_t", Layout::new::<usize>()),
+        EnumRepr::I8 => ("int8_t", Layout::new::<i8>()),
+        EnumRepr::I16 => ("int16_t", Layout::new::<i16>()),
+        EnumRepr::I32 => ("int32_t", Layout::new::<i32>()),
+        EnumRepr::I64 => ("int64_t", Layout::new::<i64>()),
+        EnumRepr::ISize => ("intptr_t", Layout::new::<isize>()),
+        // Niche-optimized enums have no tag of their own
+        _ => return Err(not_repr_c(path, shape)),
+    })
+}
+
+fn is_fieldless(et: EnumType) -> bool {
+    et.variants
+        .iter()
+        .all(|variant| variant.data.fields.is_empty())
+}
+
+/// `struct` or `union`, if `shape` is declared as one
+fn aggregate_keyword(
+    shape: &'static Shape,
+    path: &str,
+) -> Result<Option<&'static str>, HeaderError> {
+    Ok(match (shape.def, shape.ty) {
+        (Def::Undefined, Type::User(UserType::Struct(_))) => Some("struct"),
+        (Def::Undefined, Type::User(UserType::Enum(et))) if !is_fieldless(et) => {
+            match is_c_layout(shape, et, path)? {
+                true => Some("struct"),
+                false => Some("union"),
+            }
+        }
+        _ => None,
+    })
+}
+
+fn sized_layout(shape: &'static Shape, path: &str) -> Result<Layout, HeaderError> {
+    shape
+        .layout
+        .sized_layout()
+        .map_err(|_| unsupported(path, shape))
+}
+
+/// `const uint8_t *` for `*const u8`, `uint8_t *const *` for `*const *mut u8`
+fn pointer(pointee: &str, mutable: bool) -> String {
+    match (mutable, pointee.ends_with('*')) {
+        (true, _) => format!("{pointee}*"),
+        (false, true) => format!("{pointee}const *"),
+        (false, false) => format!("const {pointee} *"),
+    }
+}
+
+/// `uint8_t *data` rather than `uint8_t * data`
+fn declarator(ty: &str, name: &str) -> String {
+    match ty.ends_with('*') {
+        true => format!("{ty}{name}"),
+        false => format!("{ty} {name}"),
+    }
+}
+
+/// Tuple struct fields are `_0`, `_1`…, and fields named after C keywords get an underscore
+fn c_identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "auto", "bool", "case", "char", "default", "double", "float", "goto", "inline", "int",
+        "long", "register", "restrict", "short", "signed", "sizeof", "switch", "typedef", "union",
+        "unsigned", "volatile",
+    ];
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// `Page<User>` is declared as `PageUser`
+fn type_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    let mut out = String::with_capacity(name.len());
+    for word in name.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    out
+}
+
+/// `InProgress` becomes `in_progress`
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// `InProgress` becomes `IN_PROGRESS`
+fn screaming_snake_case(name: &str) -> String {
+    snake_case(name).to_uppercase()
+}
+
+fn write_doc(out: &mut String, doc: &[&str], indent: &str) {
+    for line in doc {
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        match line.is_empty() {
+            true => writeln!(out, "{indent}//").unwrap(),
+            false => writeln!(out, "{indent}// {line}").unwrap(),
+        }
+    }
+}
+
+fn not_repr_c(path: &str, shape: &'static Shape) -> HeaderError {
+    HeaderError {
+        path: path.to_string(),
+        kind: HeaderErrorKind::NotReprC { shape },
+    }
+}
+
+fn unsupported(path: &str, shape: &'static Shape) -> HeaderError {
+    HeaderError {
+        path: path.to_string(),
+        kind: HeaderErrorKind::Unsupported { shape },
+    }
+}
+
+/// An error encountered while generating a C header
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderError {
+    /// The type, field or variant in question, as `Type.field`
+    pub path: String,
+    /// Type of error
+    pub kind: HeaderErrorKind,
+}
+
+impl core::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            HeaderErrorKind::NotReprC { shape } => write!(f, "{shape} isn't repr(C)")?,
+            HeaderErrorKind::Unsupported { shape } => write!(f, "{shape} has no C equivalent")?,
+            HeaderErrorKind::OffsetMismatch { recorded, computed } => write!(
+                f,
+                "Field is at offset {recorded}, but would be at {computed} in C"
+            )?,
+            HeaderErrorKind::SizeMismatch { recorded, computed } => write!(
+                f,
+                "Type is {recorded} bytes, but would be {computed} bytes in C"
+            )?,
+        }
+        write!(f, " (at {})", self.path)
+    }
+}
+
+impl core::error::Error for HeaderError {}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum HeaderErrorKind {
+    /// A type's layout is up to the Rust compiler
+    NotReprC {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// A type has no C equivalent: `String`, wide pointers, packed structs, empty structs…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// A field isn't where a C compiler would put it
+    OffsetMismatch {
+        /// The offset Rust recorded
+        recorded: usize,
+        /// The offset in the C declaration
+        computed: usize,
+    },
+
+    /// A type's size isn't what it would be in C
+    SizeMismatch {
+        /// The size Rust recorded
+        recorded: usize,
+        /// The size of the C declaration
+        computed: usize,
+    },
+}
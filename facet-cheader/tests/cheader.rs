@@ -0,0 +1,139 @@
+use facet::Facet;
+use facet_cheader::{HeaderErrorKind, to_header};
+
+/// A point on the screen
+#[derive(Facet)]
+#[repr(C)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+}
+
+/// Something to draw
+#[derive(Facet)]
+#[repr(C)]
+struct Sprite {
+    position: Point,
+    color: Color,
+    /// Frames of the animation
+    frames: *const u8,
+    scale: [f32; 2],
+    visible: bool,
+}
+
+#[test]
+fn structs_and_enums() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        to_header::<Sprite>().unwrap(),
+        r#"#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+
+// A point on the screen
+typedef struct Point {
+    int32_t x;
+    int32_t y;
+} Point;
+
+enum Color {
+    COLOR_RED = 1,
+    COLOR_GREEN = 2,
+    COLOR_BLUE = 4,
+};
+typedef uint8_t Color;
+
+// Something to draw
+typedef struct Sprite {
+    Point position;
+    Color color;
+    // Frames of the animation
+    const uint8_t *frames;
+    float scale[2];
+    bool visible;
+} Sprite;
+"#
+    );
+}
+
+#[test]
+fn enums_with_data_are_tagged_unions() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    #[repr(C, u8)]
+    #[allow(dead_code)]
+    enum Figure {
+        Circle { radius: f32 },
+        Rect(f32, f32),
+        Empty,
+    }
+
+    assert_eq!(
+        to_header::<Figure>().unwrap(),
+        r#"#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+
+enum Figure_Tag {
+    FIGURE_CIRCLE = 0,
+    FIGURE_RECT = 1,
+    FIGURE_EMPTY = 2,
+};
+typedef uint8_t Figure_Tag;
+
+typedef struct Figure {
+    Figure_Tag tag;
+    union {
+        struct {
+            float radius;
+        } circle;
+        struct {
+            float _0;
+            float _1;
+        } rect;
+    };
+} Figure;
+"#
+    );
+}
+
+#[test]
+fn layouts_are_checked() {
+    facet_testhelpers::setup();
+
+    // Without `repr(C)`, Rust is free to reorder fields to save space
+    #[derive(Facet)]
+    #[allow(dead_code)]
+    struct Reordered {
+        a: u8,
+        b: u32,
+        c: u8,
+    }
+
+    let err = to_header::<Reordered>().unwrap_err();
+    assert!(matches!(err.kind, HeaderErrorKind::OffsetMismatch { .. }));
+    assert!(err.path.starts_with("Reordered."));
+
+    #[derive(Facet)]
+    #[repr(C)]
+    struct Named {
+        name: String,
+    }
+
+    let err = to_header::<Named>().unwrap_err();
+    assert!(matches!(err.kind, HeaderErrorKind::Unsupported { .. }));
+    assert_eq!(err.path, "Named.name");
+}
@@ -0,0 +1,97 @@
+//! Turning names from schemas into Rust identifiers
+
+/// Words in `name`: `userId`, `user_id`, `user-id` and `UserID` are all `["user", "id"]`
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(core::mem::take(&mut word));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        // `userId` splits before the `I`, `HTTPServer` before the `S`
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            });
+        if boundary && !word.is_empty() {
+            words.push(core::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// `user_id` becomes `UserId`, for types and variants
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut ident: String = words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if ident == "Self" {
+        ident.push('_');
+    }
+    ident
+}
+
+/// `userId` becomes `user_id`, for fields
+pub(crate) fn field(name: &str) -> String {
+    let mut ident = words(name).join("_");
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    match ident.as_str() {
+        // These can't be raw identifiers
+        "self" | "super" | "crate" => {
+            ident.push('_');
+            ident
+        }
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{ident}"),
+        _ => ident,
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(pascal_case("user_id"), "UserId");
+        assert_eq!(pascal_case("HTTPServer"), "HttpServer");
+        assert_eq!(pascal_case("in-progress"), "InProgress");
+        assert_eq!(pascal_case("self"), "Self_");
+        assert_eq!(field("userId"), "user_id");
+        assert_eq!(field("avatarURL"), "avatar_url");
+        assert_eq!(field("type"), "r#type");
+        assert_eq!(field("self"), "self_");
+        assert_eq!(field("2fa"), "_2fa");
+    }
+}
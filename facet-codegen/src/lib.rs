@@ -0,0 +1,119 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::fmt::Write;
+
+use facet_json::Value;
+
+mod ident;
+mod model;
+mod schema;
+
+/// Generates Rust definitions for the types a JSON schema describes, the root one being
+/// called `name`
+pub fn from_json_schema(name: &str, schema: &str) -> Result<String, CodegenError> {
+    Ok(Codegen::new().json_schema(name, schema)?.finish())
+}
+
+/// Collects Rust definitions from one or more schemas
+///
+/// Objects become structs and string enums become enums, as do `oneOf`s of string constants
+/// and single-property objects — the way facet-json writes enums. Fields that aren't
+/// required are `Option`s, fields whose names aren't Rust identifiers are renamed, and nested
+/// object schemas get named after their parent and field, unless they have a `title`.
+#[derive(Debug, Default)]
+pub struct Codegen {
+    items: Vec<Option<model::Item>>,
+}
+
+impl Codegen {
+    /// Creates a generator with no definitions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the types described by a JSON schema document, as well as its `$defs`
+    pub fn json_schema(self, name: &str, schema: &str) -> Result<Self, CodegenError> {
+        let schema = facet_json::value_from_str(schema).map_err(|e| CodegenError {
+            path: String::new(),
+            kind: CodegenErrorKind::Syntax(e.to_string()),
+        })?;
+        self.json_schema_value(name, &schema)
+    }
+
+    /// Adds the types described by an already-parsed JSON schema document
+    pub fn json_schema_value(mut self, name: &str, schema: &Value) -> Result<Self, CodegenError> {
+        schema::Converter::new(schema, &mut self.items).run(name)?;
+        Ok(self)
+    }
+
+    /// Returns the definitions as a Rust module, with the imports they need
+    pub fn finish(self) -> String {
+        let items: Vec<_> = self.items.into_iter().flatten().collect();
+        let mut out = String::from("use facet::Facet;\n");
+        if items.iter().any(model::Item::uses_map) {
+            out.push_str("use std::collections::HashMap;\n");
+        }
+        for item in items {
+            write!(out, "\n{item}").unwrap();
+        }
+        out
+    }
+}
+
+/// An error encountered while generating code from a schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenError {
+    /// Path to the offending schema in the document, as a JSON Pointer
+    pub path: String,
+    /// Type of error
+    pub kind: CodegenErrorKind,
+}
+
+impl core::fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            CodegenErrorKind::Syntax(e) => write!(f, "Invalid JSON: {e}")?,
+            CodegenErrorKind::Invalid { reason } => write!(f, "Invalid schema: {reason}")?,
+            CodegenErrorKind::Unsupported { reason } => write!(f, "Unsupported: {reason}")?,
+            CodegenErrorKind::UnresolvedRef { reference } => {
+                write!(f, "Can't resolve `$ref` {reference}")?
+            }
+        }
+        if !self.path.is_empty() {
+            write!(f, " (at {})", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for CodegenError {}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum CodegenErrorKind {
+    /// The document isn't valid JSON
+    Syntax(String),
+
+    /// The document isn't a valid JSON schema
+    Invalid {
+        /// What's wrong with it
+        reason: &'static str,
+    },
+
+    /// The schema describes something that has no Rust equivalent, or that isn't supported
+    /// yet
+    Unsupported {
+        /// What it is
+        reason: &'static str,
+    },
+
+    /// A `$ref` doesn't point to anything in the document — references to other documents
+    /// aren't supported
+    UnresolvedRef {
+        /// The reference
+        reference: String,
+    },
+}
@@ -0,0 +1,168 @@
+use core::fmt::{self, Display, Formatter};
+
+/// A Rust type, as written in a field or variant
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Ty {
+    /// A primitive or `String`, written as is
+    Builtin(&'static str),
+    /// One of the generated items
+    Named(String),
+    Option(Box<Ty>),
+    Boxed(Box<Ty>),
+    Vec(Box<Ty>),
+    Array(Box<Ty>, usize),
+    Tuple(Vec<Ty>),
+    /// A map with string keys
+    Map(Box<Ty>),
+}
+
+impl Ty {
+    pub(crate) fn uses_map(&self) -> bool {
+        match self {
+            Ty::Map(_) => true,
+            Ty::Builtin(_) | Ty::Named(_) => false,
+            Ty::Option(ty) | Ty::Boxed(ty) | Ty::Vec(ty) | Ty::Array(ty, _) => ty.uses_map(),
+            Ty::Tuple(tys) => tys.iter().any(Ty::uses_map),
+        }
+    }
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Builtin(name) => write!(f, "{name}"),
+            Ty::Named(name) => write!(f, "{name}"),
+            Ty::Option(ty) => write!(f, "Option<{ty}>"),
+            Ty::Boxed(ty) => write!(f, "Box<{ty}>"),
+            Ty::Vec(ty) => write!(f, "Vec<{ty}>"),
+            Ty::Array(ty, n) => write!(f, "[{ty}; {n}]"),
+            Ty::Tuple(tys) => {
+                write!(f, "(")?;
+                for (i, ty) in tys.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{ty}")?;
+                }
+                // `(T,)` is a tuple, `(T)` isn't
+                if tys.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Ty::Map(ty) => write!(f, "HashMap<String, {ty}>"),
+        }
+    }
+}
+
+/// A generated struct or enum
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) kind: ItemKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ItemKind {
+    Struct(Vec<Field>),
+    Enum(Vec<Variant>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Field {
+    /// The Rust identifier, possibly raw (`r#type`)
+    pub(crate) ident: String,
+    /// The name in the schema, if the identifier spells it differently
+    pub(crate) rename: Option<String>,
+    pub(crate) doc: Option<String>,
+    pub(crate) ty: Ty,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Variant {
+    pub(crate) ident: String,
+    pub(crate) rename: Option<String>,
+    pub(crate) doc: Option<String>,
+    pub(crate) data: VariantData,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum VariantData {
+    Unit,
+    Newtype(Ty),
+    Struct(Vec<Field>),
+}
+
+impl Item {
+    pub(crate) fn uses_map(&self) -> bool {
+        let fields_use_map = |fields: &[Field]| fields.iter().any(|field| field.ty.uses_map());
+        match &self.kind {
+            ItemKind::Struct(fields) => fields_use_map(fields),
+            ItemKind::Enum(variants) => variants.iter().any(|variant| match &variant.data {
+                VariantData::Unit => false,
+                VariantData::Newtype(ty) => ty.uses_map(),
+                VariantData::Struct(fields) => fields_use_map(fields),
+            }),
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_doc(f, self.doc.as_deref(), "")?;
+        match &self.kind {
+            ItemKind::Struct(fields) => {
+                writeln!(f, "#[derive(Facet, Debug, Clone, PartialEq)]")?;
+                writeln!(f, "pub struct {} {{", self.name)?;
+                write_fields(f, fields, "    ", "pub ")?;
+                writeln!(f, "}}")
+            }
+            ItemKind::Enum(variants) => {
+                writeln!(f, "#[derive(Facet, Debug, Clone, PartialEq)]")?;
+                writeln!(f, "#[repr(u8)]")?;
+                writeln!(f, "pub enum {} {{", self.name)?;
+                for variant in variants {
+                    write_doc(f, variant.doc.as_deref(), "    ")?;
+                    write_rename(f, variant.rename.as_deref(), "    ")?;
+                    match &variant.data {
+                        VariantData::Unit => writeln!(f, "    {},", variant.ident)?,
+                        VariantData::Newtype(ty) => writeln!(f, "    {}({ty}),", variant.ident)?,
+                        VariantData::Struct(fields) => {
+                            writeln!(f, "    {} {{", variant.ident)?;
+                            write_fields(f, fields, "        ", "")?;
+                            writeln!(f, "    }},")?;
+                        }
+                    }
+                }
+                writeln!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_fields(f: &mut Formatter<'_>, fields: &[Field], indent: &str, vis: &str) -> fmt::Result {
+    for field in fields {
+        write_doc(f, field.doc.as_deref(), indent)?;
+        write_rename(f, field.rename.as_deref(), indent)?;
+        writeln!(f, "{indent}{vis}{}: {},", field.ident, field.ty)?;
+    }
+    Ok(())
+}
+
+fn write_rename(f: &mut Formatter<'_>, rename: Option<&str>, indent: &str) -> fmt::Result {
+    match rename {
+        Some(rename) => writeln!(f, "{indent}#[facet(rename = {rename:?})]"),
+        None => Ok(()),
+    }
+}
+
+fn write_doc(f: &mut Formatter<'_>, doc: Option<&str>, indent: &str) -> fmt::Result {
+    for line in doc.into_iter().flat_map(str::lines) {
+        match line.trim_end() {
+            "" => writeln!(f, "{indent}///")?,
+            line => writeln!(f, "{indent}/// {line}")?,
+        }
+    }
+    Ok(())
+}
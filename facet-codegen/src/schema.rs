@@ -0,0 +1,535 @@
+use facet_json::Value;
+
+use crate::model::{Field, Item, ItemKind, Ty, Variant, VariantData};
+use crate::{CodegenError, CodegenErrorKind, ident};
+
+/// Turns a JSON schema into items
+pub(crate) struct Converter<'s, 'i> {
+    root: &'s Value,
+    /// Generated items, in the order they were first referred to. Slots are reserved before
+    /// items are converted, so recursive types can refer to themselves.
+    items: &'i mut Vec<Option<Item>>,
+    /// Items being converted, whose fields need boxing when they refer to them
+    in_progress: Vec<String>,
+    /// Converted `$ref`s, and the types they stand for
+    refs: Vec<(String, Ty)>,
+    /// The name to give the next item, if it's been decided already (for `$ref` targets)
+    reserved: Option<String>,
+    /// JSON Pointer segments, unescaped, to the schema being converted
+    path: Vec<String>,
+}
+
+impl<'s, 'i> Converter<'s, 'i> {
+    pub(crate) fn new(root: &'s Value, items: &'i mut Vec<Option<Item>>) -> Self {
+        Self {
+            root,
+            items,
+            in_progress: Vec::new(),
+            refs: Vec::new(),
+            reserved: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Converts the root schema into an item called `name`, along with everything it defines
+    pub(crate) fn run(mut self, name: &str) -> Result<(), CodegenError> {
+        let name = self.unique(&ident::pascal_case(name));
+        // `"$ref": "#"` is the root itself
+        self.refs.push(("#".to_string(), Ty::Named(name.clone())));
+        self.reserved = Some(name.clone());
+        let ty = self.ty(self.root, &name)?;
+        if !matches!(ty, Ty::Named(_)) {
+            return Err(self.error(CodegenErrorKind::Unsupported {
+                reason: "the root schema must describe an object or an enum",
+            }));
+        }
+
+        for defs in ["$defs", "definitions"] {
+            let Some(Value::Object(definitions)) = self.root.get(defs) else {
+                continue;
+            };
+            for key in definitions.keys() {
+                let reference = format!("#/{defs}/{}", key.replace('~', "~0").replace('/', "~1"));
+                self.reference(&reference)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn ty(&mut self, schema: &'s Value, hint: &str) -> Result<Ty, CodegenError> {
+        let Value::Object(_) = schema else {
+            return Err(self.error(CodegenErrorKind::Unsupported {
+                reason: "schemas must be objects",
+            }));
+        };
+
+        if let Some(reference) = schema.get("$ref") {
+            let Some(reference) = reference.as_str() else {
+                return Err(self.invalid("`$ref` must be a string"));
+            };
+            return self.reference(reference);
+        }
+        if let Some(values) = schema.get("enum") {
+            return self.string_enum(schema, values, hint);
+        }
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(alternatives) = schema.get(keyword) {
+                self.path.push(keyword.to_string());
+                let res = self.alternatives(schema, alternatives, hint);
+                self.path.pop();
+                return res;
+            }
+        }
+        if let Some(Value::String(_)) = schema.get("const") {
+            return Ok(Ty::Builtin("String"));
+        }
+
+        let ty = match schema.get("type") {
+            Some(Value::String(ty)) => ty.as_str(),
+            // `["string", "null"]` is an optional string
+            Some(Value::Array(types)) => {
+                let non_null: Vec<_> = types
+                    .iter()
+                    .filter(|ty| ty.as_str() != Some("null"))
+                    .collect();
+                let nullable = non_null.len() < types.len();
+                let [ty] = non_null.as_slice() else {
+                    return Err(self.error(CodegenErrorKind::Unsupported {
+                        reason: "values that can be of several types",
+                    }));
+                };
+                let Some(ty) = ty.as_str() else {
+                    return Err(self.invalid("`type` must be a string or an array of strings"));
+                };
+                let ty = self.typed(schema, ty, hint)?;
+                return Ok(if nullable { option(ty) } else { ty });
+            }
+            Some(_) => return Err(self.invalid("`type` must be a string or an array of strings")),
+            None if schema.get("properties").is_some() => "object",
+            None => {
+                return Err(self.error(CodegenErrorKind::Unsupported {
+                    reason: "schemas without a `type`",
+                }));
+            }
+        };
+        self.typed(schema, ty, hint)
+    }
+
+    /// Converts a schema whose `type` is `ty`
+    fn typed(&mut self, schema: &'s Value, ty: &str, hint: &str) -> Result<Ty, CodegenError> {
+        Ok(match ty {
+            "string" => Ty::Builtin("String"),
+            "boolean" => Ty::Builtin("bool"),
+            "null" => Ty::Builtin("()"),
+            "integer" => Ty::Builtin(integer(schema)),
+            "number" => match schema.get("format").and_then(Value::as_str) {
+                Some("float") => Ty::Builtin("f32"),
+                _ => Ty::Builtin("f64"),
+            },
+            "array" => self.array(schema, hint)?,
+            "object" => self.object(schema, hint)?,
+            _ => return Err(self.invalid("unknown `type`")),
+        })
+    }
+
+    fn array(&mut self, schema: &'s Value, hint: &str) -> Result<Ty, CodegenError> {
+        if let Some(prefix) = schema.get("prefixItems") {
+            let Some(prefix) = prefix.as_array() else {
+                return Err(self.invalid("`prefixItems` must be an array"));
+            };
+            let mut tys = Vec::with_capacity(prefix.len());
+            for (i, item) in prefix.iter().enumerate() {
+                self.path.extend(["prefixItems".to_string(), i.to_string()]);
+                tys.push(self.ty(item, &format!("{hint}{i}"))?);
+                self.path.truncate(self.path.len() - 2);
+            }
+            return Ok(Ty::Tuple(tys));
+        }
+
+        let Some(items) = schema.get("items") else {
+            return Err(self.error(CodegenErrorKind::Unsupported {
+                reason: "arrays without `items`",
+            }));
+        };
+        self.path.push("items".to_string());
+        let item = self.ty(items, &format!("{hint}Item"))?;
+        self.path.pop();
+
+        let len = |keyword| match schema.get(keyword) {
+            Some(&Value::U64(n)) => Some(n as usize),
+            _ => None,
+        };
+        Ok(match (len("minItems"), len("maxItems")) {
+            (Some(min), Some(max)) if min == max => Ty::Array(Box::new(item), min),
+            _ => Ty::Vec(Box::new(item)),
+        })
+    }
+
+    fn object(&mut self, schema: &'s Value, hint: &str) -> Result<Ty, CodegenError> {
+        if let Some(Value::Object(_)) = schema.get("properties") {
+            let name = self.name(schema, hint);
+            let slot = self.reserve(&name);
+            let fields = self.fields(schema, &name)?;
+            return Ok(self.fill(slot, schema, name, ItemKind::Struct(fields)));
+        }
+        match schema.get("additionalProperties") {
+            Some(values @ Value::Object(_)) => {
+                self.path.push("additionalProperties".to_string());
+                let value = self.ty(values, &format!("{hint}Value"))?;
+                self.path.pop();
+                Ok(Ty::Map(Box::new(value)))
+            }
+            _ => Err(self.error(CodegenErrorKind::Unsupported {
+                reason: "objects without `properties` or `additionalProperties`",
+            })),
+        }
+    }
+
+    /// Converts the properties of an object schema into fields: required ones first, in the
+    /// order they're listed in, then optional ones.
+    fn fields(&mut self, schema: &'s Value, parent: &str) -> Result<Vec<Field>, CodegenError> {
+        let Some(Value::Object(properties)) = schema.get("properties") else {
+            return Ok(Vec::new());
+        };
+        let required: Vec<&str> = match schema.get("required") {
+            Some(Value::Array(required)) => required.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+
+        let mut keys: Vec<&String> = required
+            .iter()
+            .filter_map(|key| properties.get_key_value(*key).map(|(k, _)| k))
+            .collect();
+        keys.extend(
+            properties
+                .keys()
+                .filter(|key| !required.contains(&key.as_str())),
+        );
+
+        let mut fields = Vec::with_capacity(keys.len());
+        for key in keys {
+            let property = &properties[key];
+            self.path.extend(["properties".to_string(), key.clone()]);
+            let ty = self.ty(property, &format!("{parent}{}", ident::pascal_case(key)))?;
+            self.path.truncate(self.path.len() - 2);
+
+            let ty = if required.contains(&key.as_str()) {
+                ty
+            } else {
+                option(ty)
+            };
+            let ident = ident::field(key);
+            fields.push(Field {
+                rename: (ident.trim_start_matches("r#") != key).then(|| key.clone()),
+                ident,
+                doc: description(property),
+                ty: self.boxed(ty),
+            });
+        }
+        Ok(fields)
+    }
+
+    /// An enum of strings becomes an enum of unit variants
+    fn string_enum(
+        &mut self,
+        schema: &'s Value,
+        values: &Value,
+        hint: &str,
+    ) -> Result<Ty, CodegenError> {
+        let Some(values) = values.as_array() else {
+            return Err(self.invalid("`enum` must be an array"));
+        };
+        let mut variants = Vec::with_capacity(values.len());
+        for value in values {
+            match value {
+                Value::String(value) => variants.push(unit_variant(value)),
+                // `null` among the values makes it optional, which the caller deals with
+                Value::Null => {}
+                _ => {
+                    return Err(self.error(CodegenErrorKind::Unsupported {
+                        reason: "enums of values other than strings",
+                    }));
+                }
+            }
+        }
+        let name = self.name(schema, hint);
+        let slot = self.reserve(&name);
+        let ty = self.fill(slot, schema, name, ItemKind::Enum(variants));
+        Ok(match values.contains(&Value::Null) {
+            true => option(ty),
+            false => ty,
+        })
+    }
+
+    /// `oneOf` / `anyOf`: `null` alternatives make the value optional, and the rest become
+    /// the variants of an enum, externally tagged like facet-json writes them.
+    fn alternatives(
+        &mut self,
+        schema: &'s Value,
+        alternatives: &'s Value,
+        hint: &str,
+    ) -> Result<Ty, CodegenError> {
+        let Some(alternatives) = alternatives.as_array() else {
+            return Err(self.invalid("`oneOf` and `anyOf` must be arrays"));
+        };
+        let is_null = |alt: &Value| alt.get("type").and_then(Value::as_str) == Some("null");
+        let nullable = alternatives.iter().any(is_null);
+        let rest: Vec<(usize, &'s Value)> = alternatives
+            .iter()
+            .enumerate()
+            .filter(|(_, alt)| !is_null(alt))
+            .collect();
+
+        let ty = if let [(i, alt)] = rest.as_slice() {
+            self.path.push(i.to_string());
+            let ty = self.ty(alt, hint)?;
+            self.path.pop();
+            ty
+        } else {
+            let name = self.name(schema, hint);
+            let slot = self.reserve(&name);
+            let mut variants = Vec::with_capacity(rest.len());
+            for (i, alt) in rest {
+                self.path.push(i.to_string());
+                self.variants(alt, &name, &mut variants)?;
+                self.path.pop();
+            }
+            self.fill(slot, schema, name, ItemKind::Enum(variants))
+        };
+        Ok(if nullable { option(ty) } else { ty })
+    }
+
+    /// Converts one alternative of a `oneOf` into variants: a string constant is a unit
+    /// variant, and an object with a single required property is a variant holding its value.
+    fn variants(
+        &mut self,
+        alt: &'s Value,
+        parent: &str,
+        variants: &mut Vec<Variant>,
+    ) -> Result<(), CodegenError> {
+        if let Some(Value::String(value)) = alt.get("const") {
+            let mut variant = unit_variant(value);
+            variant.doc = description(alt);
+            variants.push(variant);
+            return Ok(());
+        }
+        match alt.get("enum") {
+            Some(Value::Array(values)) if values.iter().all(|value| value.as_str().is_some()) => {
+                variants.extend(values.iter().filter_map(Value::as_str).map(unit_variant));
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let tag = match (alt.get("properties"), alt.get("required")) {
+            (Some(Value::Object(properties)), Some(Value::Array(required)))
+                if properties.len() == 1 && required.len() == 1 =>
+            {
+                properties
+                    .iter()
+                    .next()
+                    .filter(|(key, _)| required[0].as_str() == Some(key.as_str()))
+            }
+            _ => None,
+        };
+        let Some((tag, schema)) = tag else {
+            return Err(self.error(CodegenErrorKind::Unsupported {
+                reason: "alternatives other than string constants and single-property objects",
+            }));
+        };
+
+        let ident = ident::pascal_case(tag);
+        let hint = format!("{parent}{ident}");
+        self.path.extend(["properties".to_string(), tag.clone()]);
+        let data = match schema.get("properties") {
+            // Struct variants are inlined, newtype variants refer to their type
+            Some(Value::Object(_)) if schema.get("$ref").is_none() => {
+                VariantData::Struct(self.fields(schema, &hint)?)
+            }
+            _ => {
+                let ty = self.ty(schema, &hint)?;
+                VariantData::Newtype(self.boxed(ty))
+            }
+        };
+        self.path.truncate(self.path.len() - 2);
+
+        variants.push(Variant {
+            rename: (&ident != tag).then(|| tag.clone()),
+            ident,
+            doc: description(alt).or_else(|| description(schema)),
+            data,
+        });
+        Ok(())
+    }
+
+    /// Converts the schema a `$ref` points to, once
+    fn reference(&mut self, reference: &str) -> Result<Ty, CodegenError> {
+        if let Some((_, ty)) = self.refs.iter().find(|(r, _)| r == reference) {
+            return Ok(ty.clone());
+        }
+
+        let Some(pointer) = reference.strip_prefix('#') else {
+            return Err(self.error(CodegenErrorKind::UnresolvedRef {
+                reference: reference.to_string(),
+            }));
+        };
+        let segments: Vec<String> = pointer
+            .split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        let target = segments
+            .iter()
+            .try_fold(self.root, |value, segment| match value {
+                Value::Object(map) => map.get(segment),
+                Value::Array(items) => segment.parse().ok().and_then(|i: usize| items.get(i)),
+                _ => None,
+            });
+        let Some(target) = target else {
+            return Err(self.error(CodegenErrorKind::UnresolvedRef {
+                reference: reference.to_string(),
+            }));
+        };
+
+        let hint = match target.get("title").and_then(Value::as_str) {
+            Some(title) => ident::pascal_case(title),
+            None => ident::pascal_case(segments.last().map_or("Type", String::as_str)),
+        };
+        let name = self.unique(&hint);
+        // Until it's converted, the target is assumed to be an item of that name, which is what
+        // recursive references need
+        self.refs
+            .push((reference.to_string(), Ty::Named(name.clone())));
+        self.reserved = Some(name);
+
+        let path = core::mem::replace(&mut self.path, segments);
+        let res = self.ty(target, &hint);
+        self.path = path;
+        self.reserved = None;
+
+        let ty = res?;
+        if let Some(entry) = self.refs.iter_mut().find(|(r, _)| r == reference) {
+            entry.1 = ty.clone();
+        }
+        Ok(ty)
+    }
+
+    /// The name for a new item: the one reserved for it, or its title, or the hint
+    fn name(&mut self, schema: &Value, hint: &str) -> String {
+        if let Some(name) = self.reserved.take() {
+            return name;
+        }
+        match schema.get("title").and_then(Value::as_str) {
+            Some(title) => self.unique(&ident::pascal_case(title)),
+            None => self.unique(&ident::pascal_case(hint)),
+        }
+    }
+
+    /// Makes `name` unique among the items generated so far, by appending a number
+    fn unique(&self, name: &str) -> String {
+        let taken = |name: &str| {
+            self.items.iter().flatten().any(|item| item.name == name)
+                || self.in_progress.iter().any(|n| n == name)
+                || self
+                    .refs
+                    .iter()
+                    .any(|(_, ty)| *ty == Ty::Named(name.to_string()))
+        };
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|i| format!("{name}{i}"))
+            .find(|name| !taken(name))
+            .unwrap()
+    }
+
+    fn reserve(&mut self, name: &str) -> usize {
+        self.in_progress.push(name.to_string());
+        self.items.push(None);
+        self.items.len() - 1
+    }
+
+    fn fill(&mut self, slot: usize, schema: &Value, name: String, kind: ItemKind) -> Ty {
+        self.in_progress.retain(|n| *n != name);
+        self.items[slot] = Some(Item {
+            name: name.clone(),
+            doc: description(schema),
+            kind,
+        });
+        Ty::Named(name)
+    }
+
+    /// Boxes references to items that are still being converted, that is, recursive ones —
+    /// unless they're already behind a `Vec` or a map.
+    fn boxed(&self, ty: Ty) -> Ty {
+        match ty {
+            Ty::Named(ref name) if self.in_progress.contains(name) => Ty::Boxed(Box::new(ty)),
+            Ty::Option(ty) => Ty::Option(Box::new(self.boxed(*ty))),
+            Ty::Array(ty, n) => Ty::Array(Box::new(self.boxed(*ty)), n),
+            Ty::Tuple(tys) => Ty::Tuple(tys.into_iter().map(|ty| self.boxed(ty)).collect()),
+            ty => ty,
+        }
+    }
+
+    fn invalid(&self, reason: &'static str) -> CodegenError {
+        self.error(CodegenErrorKind::Invalid { reason })
+    }
+
+    fn error(&self, kind: CodegenErrorKind) -> CodegenError {
+        CodegenError {
+            path: self
+                .path
+                .iter()
+                .map(|s| format!("/{}", s.replace('~', "~0").replace('/', "~1")))
+                .collect(),
+            kind,
+        }
+    }
+}
+
+fn option(ty: Ty) -> Ty {
+    match ty {
+        Ty::Option(_) => ty,
+        ty => Ty::Option(Box::new(ty)),
+    }
+}
+
+fn unit_variant(value: &str) -> Variant {
+    let ident = ident::pascal_case(value);
+    Variant {
+        rename: (ident != value).then(|| value.to_string()),
+        ident,
+        doc: None,
+        data: VariantData::Unit,
+    }
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Integers use their `format` (`uint32`, `int64`…) if they have one, and are 64-bit
+/// otherwise
+fn integer(schema: &Value) -> &'static str {
+    match schema.get("format").and_then(Value::as_str) {
+        Some("uint8") => "u8",
+        Some("uint16") => "u16",
+        Some("uint32") => "u32",
+        Some("uint64") => "u64",
+        Some("uint128") => "u128",
+        Some("int8") => "i8",
+        Some("int16") => "i16",
+        Some("int32") => "i32",
+        Some("int64") => "i64",
+        Some("int128") => "i128",
+        _ => match schema.get("minimum") {
+            Some(Value::U64(_)) => "u64",
+            Some(&Value::F64(min)) if min >= 0.0 => "u64",
+            _ => "i64",
+        },
+    }
+}
@@ -0,0 +1,99 @@
+use facet_codegen::{CodegenErrorKind, from_json_schema};
+
+#[test]
+fn structs_and_enums() {
+    facet_testhelpers::setup();
+
+    let schema = r##"{
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "description": "A registered user",
+        "type": "object",
+        "required": ["userId", "roles", "address"],
+        "properties": {
+            "userId": { "type": "integer", "format": "uint64", "minimum": 0 },
+            "nickname": { "description": "Shown instead of the name", "type": "string" },
+            "roles": { "type": "array", "items": { "$ref": "#/$defs/Role" } },
+            "address": {
+                "type": "object",
+                "required": ["city"],
+                "properties": { "city": { "type": "string" } }
+            },
+            "labels": { "type": "object", "additionalProperties": { "type": "string" } }
+        },
+        "$defs": {
+            "Role": {
+                "oneOf": [
+                    { "const": "Admin" },
+                    {
+                        "type": "object",
+                        "required": ["Team"],
+                        "properties": { "Team": { "type": "string" } }
+                    }
+                ]
+            }
+        }
+    }"##;
+
+    assert_eq!(
+        from_json_schema("User", schema).unwrap(),
+        r#"use facet::Facet;
+use std::collections::HashMap;
+
+/// A registered user
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct User {
+    #[facet(rename = "userId")]
+    pub user_id: u64,
+    pub roles: Vec<Role>,
+    pub address: UserAddress,
+    pub labels: Option<HashMap<String, String>>,
+    /// Shown instead of the name
+    pub nickname: Option<String>,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[repr(u8)]
+pub enum Role {
+    Admin,
+    Team(String),
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct UserAddress {
+    pub city: String,
+}
+"#
+    );
+}
+
+#[test]
+fn recursive_types_are_boxed() {
+    facet_testhelpers::setup();
+
+    let schema = r##"{
+        "type": "object",
+        "required": ["value"],
+        "properties": {
+            "value": { "type": "integer" },
+            "next": { "$ref": "#" },
+            "children": { "type": "array", "items": { "$ref": "#" } }
+        }
+    }"##;
+
+    let code = from_json_schema("Node", schema).unwrap();
+    assert!(code.contains("pub next: Option<Box<Node>>,"), "{code}");
+    assert!(code.contains("pub children: Option<Vec<Node>>,"), "{code}");
+}
+
+#[test]
+fn unsupported_schemas_are_errors() {
+    facet_testhelpers::setup();
+
+    let schema = r#"{
+        "type": "object",
+        "properties": { "any": { "type": ["string", "integer"] } }
+    }"#;
+    let err = from_json_schema("Thing", schema).unwrap_err();
+    assert_eq!(err.path, "/properties/any");
+    assert!(matches!(err.kind, CodegenErrorKind::Unsupported { .. }));
+}
@@ -0,0 +1,144 @@
+use core::write;
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeSet, btree_set},
+};
+
+use crate::{
+    Def, Facet, MarkerTraits, PtrConst, PtrMut, SetDef, SetIterVTable, SetVTable, Shape, Type,
+    UserType, VTableView, ValueVTable,
+};
+
+unsafe impl<'a, T> Facet<'a> for BTreeSet<T>
+where
+    T: Facet<'a> + core::cmp::Eq + core::cmp::Ord,
+{
+    const VTABLE: &'static ValueVTable = &const {
+        let mut builder = ValueVTable::builder::<Self>()
+            .marker_traits({
+                let arg_dependent_traits = MarkerTraits::SEND
+                    .union(MarkerTraits::SYNC)
+                    .union(MarkerTraits::EQ);
+                arg_dependent_traits
+                    .intersection(T::VTABLE.marker_traits)
+                    // only depends on `A` which we are not generic over (yet)
+                    .union(MarkerTraits::UNPIN)
+            })
+            .type_name(|f, opts| {
+                if let Some(opts) = opts.for_children() {
+                    write!(f, "BTreeSet<")?;
+                    (T::SHAPE.vtable.type_name)(f, opts)?;
+                    write!(f, ">")
+                } else {
+                    write!(f, "BTreeSet<⋯>")
+                }
+            });
+
+        if T::VTABLE.debug.is_some() {
+            builder = builder.debug(|value, f| {
+                let t_debug = <VTableView<T>>::of().debug().unwrap();
+                write!(f, "{{")?;
+                for (i, item) in value.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    (t_debug)(item, f)?;
+                }
+                write!(f, "}}")
+            })
+        }
+
+        builder = builder.default_in_place(|target| unsafe { target.put(Self::default()) });
+
+        if T::VTABLE.clone_into.is_some() {
+            builder = builder.clone_into(|src, dst| unsafe {
+                let mut new_set = BTreeSet::new();
+
+                let t_clone_into = <VTableView<T>>::of().clone_into().unwrap();
+
+                for item in src {
+                    use crate::TypedPtrUninit;
+                    use core::mem::MaybeUninit;
+
+                    let mut new_item = MaybeUninit::<T>::uninit();
+                    let uninit_item = TypedPtrUninit::new(new_item.as_mut_ptr());
+                    (t_clone_into)(item, uninit_item);
+
+                    new_set.insert(new_item.assume_init());
+                }
+
+                dst.put(new_set)
+            });
+        }
+
+        builder = builder.eq(|a, b| a == b);
+
+        if T::VTABLE.hash.is_some() {
+            builder = builder.hash(|set, hasher_this, hasher_write_fn| unsafe {
+                use crate::HasherProxy;
+                use core::hash::Hash;
+
+                let t_hash = <VTableView<T>>::of().hash().unwrap();
+                let mut hasher = HasherProxy::new(hasher_this, hasher_write_fn);
+                set.len().hash(&mut hasher);
+                for item in set {
+                    (t_hash)(item, hasher_this, hasher_write_fn);
+                }
+            });
+        }
+
+        builder.build()
+    };
+
+    const SHAPE: &'static crate::Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_params(&[crate::TypeParam {
+                name: "T",
+                shape: || T::SHAPE,
+            }])
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Set(
+                SetDef::builder()
+                    .t(|| T::SHAPE)
+                    .vtable(
+                        &const {
+                            SetVTable::builder()
+                                .init_in_place_with_capacity(|uninit, _capacity| unsafe {
+                                    uninit.put(Self::new())
+                                })
+                                .insert(|ptr, value| unsafe {
+                                    let set = ptr.as_mut::<Self>();
+                                    set.insert(value.read::<T>())
+                                })
+                                .len(|ptr| unsafe { ptr.get::<Self>().len() })
+                                .contains(|ptr, value| unsafe {
+                                    ptr.get::<Self>().contains(value.get::<T>())
+                                })
+                                .iter(|ptr| unsafe {
+                                    let iter = Box::new(ptr.get::<Self>().iter());
+                                    PtrMut::new(Box::into_raw(iter))
+                                })
+                                .iter_vtable(
+                                    SetIterVTable::builder()
+                                        .next(|iter_ptr| unsafe {
+                                            let iter = iter_ptr.as_mut::<btree_set::Iter<'_, T>>();
+                                            iter.next().map(|item| PtrConst::new(item as *const T))
+                                        })
+                                        .dealloc(|iter_ptr| unsafe {
+                                            drop(Box::from_raw(
+                                                iter_ptr
+                                                    .as_ptr::<btree_set::Iter<'_, T>>()
+                                                    .cast_mut(),
+                                            ))
+                                        })
+                                        .build(),
+                                )
+                                .build()
+                        },
+                    )
+                    .build(),
+            ))
+            .build()
+    };
+}
@@ -1,6 +1,7 @@
 mod arc;
 mod boxed;
 mod btreemap;
+mod btreeset;
 mod rc;
 mod string;
 mod vec;
@@ -20,7 +20,9 @@ where
             })
             .default_in_place(|target| unsafe { target.put(Self::default()) });
 
-        if T::SHAPE.vtable.clone_into.is_some() {
+        // These read `T::VTABLE` rather than `T::SHAPE.vtable`: the shape of a type that
+        // contains itself through a `Vec` would otherwise depend on itself
+        if T::VTABLE.clone_into.is_some() {
             builder = builder.clone_into(|src, dst| unsafe {
                 let mut new_vec = Vec::with_capacity(src.len());
 
@@ -42,7 +44,7 @@ where
             });
         }
 
-        if T::SHAPE.vtable.debug.is_some() {
+        if T::VTABLE.debug.is_some() {
             builder = builder.debug(|value, f| {
                 write!(f, "[")?;
                 for (i, item) in value.iter().enumerate() {
@@ -55,7 +57,7 @@ where
             });
         }
 
-        if T::SHAPE.vtable.eq.is_some() {
+        if T::VTABLE.eq.is_some() {
             builder = builder.eq(|a, b| {
                 if a.len() != b.len() {
                     return false;
@@ -69,7 +71,7 @@ where
             });
         }
 
-        if T::SHAPE.vtable.hash.is_some() {
+        if T::VTABLE.hash.is_some() {
             builder = builder.hash(|vec, hasher_this, hasher_write_fn| unsafe {
                 use crate::HasherProxy;
                 let t_hash = <VTableView<T>>::of().hash().unwrap_unchecked();
@@ -85,7 +87,7 @@ where
             .union(MarkerTraits::SYNC)
             .union(MarkerTraits::EQ)
             .union(MarkerTraits::UNPIN)
-            .intersection(T::SHAPE.vtable.marker_traits);
+            .intersection(T::VTABLE.marker_traits);
         builder = builder.marker_traits(traits);
 
         builder.build()
@@ -115,6 +117,10 @@ where
                                     let vec = ptr.get::<Self>();
                                     vec.len()
                                 })
+                                .get(|ptr, index| unsafe {
+                                    let vec = ptr.get::<Self>();
+                                    vec.get(index).map(|item| PtrConst::new(item as *const T))
+                                })
                                 .as_ptr(|ptr| unsafe {
                                     let vec = ptr.get::<Self>();
                                     PtrConst::new(vec.as_ptr())
@@ -50,11 +50,6 @@ unsafe impl Facet<'_> for Utf8PathBuf {
     };
 
     const SHAPE: &'static Shape = &const {
-        // Function to return inner type's shape
-        fn inner_shape() -> &'static Shape {
-            <String as Facet>::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .ty(Type::User(UserType::Opaque))
             .def(Def::Scalar(
@@ -62,7 +57,7 @@ unsafe impl Facet<'_> for Utf8PathBuf {
                     .affinity(ScalarAffinity::path().build())
                     .build(),
             ))
-            .inner(inner_shape)
+            .inner_static(<String as Facet>::SHAPE)
             .build()
     };
 }
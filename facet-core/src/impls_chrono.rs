@@ -0,0 +1,43 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::impls_datetime::{datetime_facet, split_unix_nanos};
+
+fn unix_nanos<Tz: chrono::TimeZone>(datetime: &DateTime<Tz>) -> i128 {
+    i128::from(datetime.timestamp()) * 1_000_000_000 + i128::from(datetime.timestamp_subsec_nanos())
+}
+
+fn from_unix_nanos(nanos: i128) -> Option<DateTime<Utc>> {
+    let (secs, subsec_nanos) = split_unix_nanos(nanos)?;
+    DateTime::from_timestamp(secs, subsec_nanos)
+}
+
+datetime_facet!(
+    DateTime<Utc>, "DateTime<Utc>",
+    display: |datetime, f| f.write_str(&datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+    parse: |s| DateTime::parse_from_rfc3339(s).ok().map(|datetime| datetime.to_utc()),
+    unix_nanos: |datetime| unix_nanos(datetime),
+    from_unix_nanos: |nanos| from_unix_nanos(nanos),
+);
+
+datetime_facet!(
+    DateTime<FixedOffset>, "DateTime<FixedOffset>",
+    display: |datetime, f| f.write_str(&datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+    parse: |s| DateTime::parse_from_rfc3339(s).ok(),
+    unix_nanos: |datetime| unix_nanos(datetime),
+    from_unix_nanos: |nanos| from_unix_nanos(nanos).map(|datetime| datetime.fixed_offset()),
+);
+
+// Without an offset, unix timestamps are taken to be in UTC
+datetime_facet!(
+    NaiveDateTime, "NaiveDateTime",
+    display: |datetime, f| write!(f, "{}", datetime.format("%Y-%m-%dT%H:%M:%S%.f")),
+    parse: |s| s.parse::<NaiveDateTime>().ok(),
+    unix_nanos: |datetime| unix_nanos(&datetime.and_utc()),
+    from_unix_nanos: |nanos| from_unix_nanos(nanos).map(|datetime| datetime.naive_utc()),
+);
+
+datetime_facet!(
+    NaiveDate, "NaiveDate",
+    display: |date, f| write!(f, "{}", date.format("%Y-%m-%d")),
+    parse: |s| s.parse::<NaiveDate>().ok(),
+);
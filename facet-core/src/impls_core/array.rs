@@ -132,6 +132,10 @@ where
                     .vtable(
                         &const {
                             ArrayVTable::builder()
+                                .get(|ptr, index| unsafe {
+                                    let array = ptr.get::<[T; L]>();
+                                    array.get(index).map(|item| PtrConst::new(item as *const T))
+                                })
                                 .as_ptr(|ptr| unsafe {
                                     let array = ptr.get::<[T; L]>();
                                     PtrConst::new(array.as_ptr())
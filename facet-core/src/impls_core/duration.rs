@@ -0,0 +1,229 @@
+use core::{fmt, mem, time::Duration};
+
+use crate::{
+    Def, DeserializeWith, Facet, Field, FieldFlags, PtrConst, PtrMut, PtrUninit, Repr,
+    ScalarAffinity, ScalarDef, Shape, StructKind, StructType, TryFromError, Type, UserType,
+    ValueVTable, value_vtable,
+};
+
+unsafe impl Facet<'_> for Duration {
+    const VTABLE: &'static ValueVTable = &const {
+        // Lets seconds, a string or the parts of a duration go in, so formats can put whatever
+        // they found
+        unsafe fn try_from<'dst>(
+            src_ptr: PtrConst<'_>,
+            src_shape: &'static Shape,
+            dst: PtrUninit<'dst>,
+        ) -> Result<PtrMut<'dst>, TryFromError> {
+            let duration = if src_shape == u64::SHAPE {
+                Some(Duration::from_secs(unsafe { *src_ptr.get::<u64>() }))
+            } else if src_shape == i64::SHAPE {
+                u64::try_from(unsafe { *src_ptr.get::<i64>() })
+                    .ok()
+                    .map(Duration::from_secs)
+            } else if src_shape == f64::SHAPE {
+                Duration::try_from_secs_f64(unsafe { *src_ptr.get::<f64>() }).ok()
+            } else if src_shape == DurationParts::SHAPE {
+                unsafe { src_ptr.get::<DurationParts>() }.to_duration()
+            } else {
+                #[cfg(feature = "alloc")]
+                if src_shape == alloc::string::String::SHAPE {
+                    let s = unsafe { src_ptr.get::<alloc::string::String>() };
+                    return match parse_duration(s) {
+                        Some(duration) => Ok(unsafe { dst.put(duration) }),
+                        None => Err(TryFromError::Generic("invalid duration")),
+                    };
+                }
+                return Err(TryFromError::UnsupportedSourceShape {
+                    src_shape,
+                    expected: &[u64::SHAPE, i64::SHAPE, f64::SHAPE, DurationParts::SHAPE],
+                });
+            };
+            match duration {
+                Some(duration) => Ok(unsafe { dst.put(duration) }),
+                None => Err(TryFromError::Generic("duration out of range")),
+            }
+        }
+
+        let mut vtable = value_vtable!(Duration, |f, _opts| write!(f, "Duration"));
+        vtable.display = Some(|value, f| write_human(unsafe { value.get::<Duration>() }, f));
+        vtable.parse = Some(|s, target| match parse_duration(s) {
+            Some(duration) => Ok(unsafe { target.put(duration) }),
+            None => Err(crate::ParseError::Generic("invalid duration")),
+        });
+        vtable.try_from = Some(try_from);
+        vtable
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    .affinity(ScalarAffinity::time().build())
+                    .build(),
+            ))
+            .build()
+    };
+}
+
+/// The parts of a [`Duration`], which it's serialized as by default: `{"secs":90,"nanos":0}`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DurationParts {
+    /// Whole seconds
+    pub secs: u64,
+    /// Nanoseconds past them, which may add up to more seconds. Zero if missing.
+    pub nanos: u32,
+}
+
+impl DurationParts {
+    /// Deserializes a `Duration` from its parts: a `DurationParts` proxy, converted to the
+    /// duration, failing if it overflows
+    pub const DESERIALIZE_DURATION: DeserializeWith =
+        DeserializeWith::new(Self::SHAPE, |proxy, value, message| {
+            let parts = unsafe { proxy.read::<DurationParts>() };
+            match parts.to_duration() {
+                Some(duration) => {
+                    unsafe { value.put(duration) };
+                    true
+                }
+                None => {
+                    let _ = fmt::Write::write_str(message, "duration out of range");
+                    false
+                }
+            }
+        });
+
+    /// The duration these parts add up to, if it fits
+    pub fn to_duration(&self) -> Option<Duration> {
+        Duration::from_secs(self.secs).checked_add(Duration::from_nanos(self.nanos.into()))
+    }
+}
+
+impl From<Duration> for DurationParts {
+    fn from(duration: Duration) -> Self {
+        Self {
+            secs: duration.as_secs(),
+            nanos: duration.subsec_nanos(),
+        }
+    }
+}
+
+unsafe impl Facet<'_> for DurationParts {
+    const VTABLE: &'static ValueVTable =
+        &const { value_vtable!(DurationParts, |f, _opts| write!(f, "DurationParts")) };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .ty(Type::User(UserType::Struct(
+                StructType::builder()
+                    .kind(StructKind::Struct)
+                    .repr(Repr::default())
+                    .fields(
+                        &const {
+                            [
+                                Field::builder()
+                                    .name("secs")
+                                    .shape(u64::SHAPE)
+                                    .offset(mem::offset_of!(DurationParts, secs))
+                                    .build(),
+                                Field::builder()
+                                    .name("nanos")
+                                    .shape(u32::SHAPE)
+                                    .offset(mem::offset_of!(DurationParts, nanos))
+                                    .flags(FieldFlags::DEFAULT)
+                                    .build(),
+                            ]
+                        },
+                    )
+                    .build(),
+            )))
+            .build()
+    };
+}
+
+/// Units a duration can be written in, with how many nanoseconds they are
+const UNITS: &[(&str, u128)] = &[
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// Writes a duration as the amounts of the units it's made of, largest first: `1h30m`,
+/// `1s500ms`, or `0s`
+fn write_human(duration: &Duration, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if duration.is_zero() {
+        return f.write_str("0s");
+    }
+    let mut rest = duration.as_nanos();
+    // Days and `µs` are only read
+    for &(unit, per_unit) in UNITS
+        .iter()
+        .filter(|(unit, _)| !matches!(*unit, "d" | "µs"))
+    {
+        let amount = rest / per_unit;
+        if amount > 0 {
+            write!(f, "{amount}{unit}")?;
+            rest %= per_unit;
+        }
+    }
+    Ok(())
+}
+
+/// Parses a duration written as seconds, `"90"` or `"1.5"`, or as amounts of units, like
+/// `"1h30m"` or `"2.5s"`
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if let Ok(secs) = s.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+    if s.is_empty() {
+        return None;
+    }
+
+    let mut nanos: u128 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let amount_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (amount, after) = rest.split_at(amount_len);
+        let unit_len = after
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        let &(_, per_unit) = UNITS.iter().find(|(name, _)| *name == unit.trim())?;
+        nanos = nanos.checked_add(amount_nanos(amount, per_unit)?)?;
+        rest = after;
+    }
+
+    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// How many nanoseconds an amount of a unit is, e.g. `1.5` of a second
+fn amount_nanos(amount: &str, per_unit: u128) -> Option<u128> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let mut nanos = whole.checked_mul(per_unit)?;
+
+    // Digits past the nanoseconds of the largest unit don't count
+    let fraction = &fraction[..fraction.len().min(18)];
+    if !fraction.is_empty() {
+        let numerator: u128 = fraction.parse().ok()?;
+        nanos = nanos.checked_add(numerator * per_unit / 10u128.pow(fraction.len() as u32))?;
+    }
+    Some(nanos)
+}
@@ -1,8 +1,11 @@
 mod array;
+mod duration;
+pub use duration::DurationParts;
 mod fn_ptr;
 mod ops;
 mod option;
 mod pointer;
+mod result;
 mod scalar;
 mod slice;
 mod smartptr;
@@ -93,11 +93,6 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for Option<T> {
     };
 
     const SHAPE: &'static Shape = &const {
-        // Function to return inner type's shape
-        fn inner_shape<'a, T: Facet<'a>>() -> &'static Shape {
-            T::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .type_params(&[crate::TypeParam {
                 name: "T",
@@ -182,7 +177,7 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for Option<T> {
                     )
                     .build(),
             ))
-            .inner(inner_shape::<T>)
+            .inner_static(T::SHAPE)
             .build()
     };
 }
@@ -0,0 +1,91 @@
+use crate::{
+    Def, Facet, PtrConst, ResultDef, ResultVTable, Shape, Type, UserType, VTableView, ValueVTable,
+    value_vtable,
+};
+
+unsafe impl<'a, T: Facet<'a>, E: Facet<'a>> Facet<'a> for Result<T, E> {
+    const VTABLE: &'static ValueVTable = &const {
+        let mut vtable = value_vtable!(core::result::Result<T, E>, |f, opts| {
+            write!(f, "Result")?;
+            if let Some(opts) = opts.for_children() {
+                write!(f, "<")?;
+                (T::SHAPE.vtable.type_name)(f, opts)?;
+                write!(f, ", ")?;
+                (E::SHAPE.vtable.type_name)(f, opts)?;
+                write!(f, ">")?;
+            } else {
+                write!(f, "<…>")?;
+            }
+            Ok(())
+        });
+
+        if T::SHAPE.is_debug() && E::SHAPE.is_debug() {
+            vtable.debug = Some(|this, f| {
+                let this = unsafe { this.get::<Self>() };
+                match this {
+                    Ok(value) => {
+                        write!(f, "Ok(")?;
+                        (<VTableView<T>>::of().debug().unwrap())(value, f)?;
+                    }
+                    Err(error) => {
+                        write!(f, "Err(")?;
+                        (<VTableView<E>>::of().debug().unwrap())(error, f)?;
+                    }
+                }
+                write!(f, ")")
+            });
+        }
+
+        vtable
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_params(&[
+                crate::TypeParam {
+                    name: "T",
+                    shape: || T::SHAPE,
+                },
+                crate::TypeParam {
+                    name: "E",
+                    shape: || E::SHAPE,
+                },
+            ])
+            // The layout of a `Result` isn't specified, so it's only reachable through its vtable
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Result(
+                ResultDef::builder()
+                    .t(T::SHAPE)
+                    .e(E::SHAPE)
+                    .vtable(
+                        const {
+                            &ResultVTable::builder()
+                                .is_ok(|result| unsafe { result.get::<Result<T, E>>().is_ok() })
+                                .get_ok(|result| unsafe {
+                                    result
+                                        .get::<Result<T, E>>()
+                                        .as_ref()
+                                        .ok()
+                                        .map(|t| PtrConst::new(t as *const T))
+                                })
+                                .get_err(|result| unsafe {
+                                    result
+                                        .get::<Result<T, E>>()
+                                        .as_ref()
+                                        .err()
+                                        .map(|e| PtrConst::new(e as *const E))
+                                })
+                                .init_ok(|result, value| unsafe {
+                                    result.put(Result::<T, E>::Ok(value.read::<T>()))
+                                })
+                                .init_err(|result, value| unsafe {
+                                    result.put(Result::<T, E>::Err(value.read::<E>()))
+                                })
+                                .build()
+                        },
+                    )
+                    .build(),
+            ))
+            .build()
+    };
+}
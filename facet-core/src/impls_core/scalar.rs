@@ -18,6 +18,7 @@ unsafe impl Facet<'_> for ConstTypeId {
                 repr: Repr::c(),
                 kind: StructKind::Struct,
                 fields: &const { [field_in_type!(ConstTypeId, type_id_fn)] },
+                fields_by_name: &[],
             })))
             .build()
     };
@@ -72,6 +73,7 @@ unsafe impl<'a, T: ?Sized + 'a> Facet<'a> for core::marker::PhantomData<T> {
                 repr: Repr::default(),
                 kind: StructKind::Unit,
                 fields: &[],
+                fields_by_name: &[],
             })))
             .build()
     };
@@ -231,6 +233,50 @@ macro_rules! impl_facet_for_integer {
                             }
                         }
                     }
+                    if source_shape == u128::SHAPE {
+                        let value: u128 = *unsafe { source.get::<u128>() };
+                        match <$type>::try_from(value) {
+                            Ok(converted) => {
+                                return Ok(unsafe { dest.put::<$type>(converted) });
+                            }
+                            Err(_) => {
+                                return Err(TryFromError::Generic("conversion from u128 failed"));
+                            }
+                        }
+                    }
+                    if source_shape == i128::SHAPE {
+                        let value: i128 = *unsafe { source.get::<i128>() };
+                        match <$type>::try_from(value) {
+                            Ok(converted) => {
+                                return Ok(unsafe { dest.put::<$type>(converted) });
+                            }
+                            Err(_) => {
+                                return Err(TryFromError::Generic("conversion from i128 failed"));
+                            }
+                        }
+                    }
+                    if source_shape == usize::SHAPE {
+                        let value: usize = *unsafe { source.get::<usize>() };
+                        match <$type>::try_from(value) {
+                            Ok(converted) => {
+                                return Ok(unsafe { dest.put::<$type>(converted) });
+                            }
+                            Err(_) => {
+                                return Err(TryFromError::Generic("conversion from usize failed"));
+                            }
+                        }
+                    }
+                    if source_shape == isize::SHAPE {
+                        let value: isize = *unsafe { source.get::<isize>() };
+                        match <$type>::try_from(value) {
+                            Ok(converted) => {
+                                return Ok(unsafe { dest.put::<$type>(converted) });
+                            }
+                            Err(_) => {
+                                return Err(TryFromError::Generic("conversion from isize failed"));
+                            }
+                        }
+                    }
                     if source_shape == f64::SHAPE {
                         let value: f64 = *unsafe { source.get::<f64>() };
                         let value = value as i64;
@@ -257,7 +303,14 @@ macro_rules! impl_facet_for_integer {
                     }
                     Err(TryFromError::UnsupportedSourceShape {
                         src_shape: source_shape,
-                        expected: &[Self::SHAPE, u64::SHAPE, i64::SHAPE, f64::SHAPE],
+                        expected: &[
+                            Self::SHAPE,
+                            u64::SHAPE,
+                            i64::SHAPE,
+                            u128::SHAPE,
+                            i128::SHAPE,
+                            f64::SHAPE,
+                        ],
                     })
                 });
 
@@ -337,7 +390,9 @@ macro_rules! impl_facet_for_integer {
                     Ok(src_ptr)
                 }
 
-                let mut vtable = value_vtable!($type, |f, _opts| write!(
+                // Built for `NonZero` itself rather than `$type`, so that there's no `Default`
+                // writing a zero and `parse` rejects one
+                let mut vtable = value_vtable!(NonZero<$type>, |f, _opts| write!(
                     f,
                     "NonZero<{}>",
                     stringify!($type)
@@ -369,11 +424,6 @@ macro_rules! impl_facet_for_integer {
             };
 
             const SHAPE: &'static Shape = &const {
-                // Function to return inner type's shape
-                fn inner_shape() -> &'static Shape {
-                    <$type as Facet>::SHAPE
-                }
-
                 Shape::builder_for_sized::<Self>()
                     .def(Def::Scalar(
                         ScalarDef::builder().affinity($nz_affinity).build(),
@@ -391,8 +441,9 @@ macro_rules! impl_facet_for_integer {
                                 .flags(FieldFlags::EMPTY)
                                 .build()]
                         },
+                        fields_by_name: &[],
                     })))
-                    .inner(inner_shape)
+                    .inner_static(<$type as Facet>::SHAPE)
                     .build()
             };
         }
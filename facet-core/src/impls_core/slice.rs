@@ -105,6 +105,10 @@ where
                                     let slice = ptr.get::<&[T]>();
                                     slice.len()
                                 })
+                                .get(|ptr, index| unsafe {
+                                    let slice = ptr.get::<&[T]>();
+                                    slice.get(index).map(|item| PtrConst::new(item as *const T))
+                                })
                                 .as_ptr(|ptr| unsafe {
                                     let slice = ptr.get::<&[T]>();
                                     PtrConst::new(slice.as_ptr())
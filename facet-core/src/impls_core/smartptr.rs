@@ -25,6 +25,7 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for core::ptr::NonNull<T> {
                         .flags(FieldFlags::EMPTY)
                         .build()]
                 },
+                fields_by_name: &[],
             })))
             .def(Def::SmartPointer(
                 SmartPointerDef::builder()
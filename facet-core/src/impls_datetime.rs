@@ -0,0 +1,96 @@
+/// Implements `Facet` for a datetime type of `chrono`, `time` or `jiff`: a scalar with a time
+/// affinity, displayed and parsed as text (RFC 3339 for instants). Types whose values stand for
+/// an instant also convert to and from unix timestamps.
+macro_rules! datetime_facet {
+    (
+        $type:ty, $type_name:literal,
+        display: |$value:ident, $f:ident| $display:expr,
+        parse: |$s:ident| $parse:expr
+        $(, unix_nanos: |$instant:ident| $to_nanos:expr, from_unix_nanos: |$nanos:ident| $from_nanos:expr)?
+        $(,)?
+    ) => {
+        unsafe impl $crate::Facet<'_> for $type {
+            const VTABLE: &'static $crate::ValueVTable = &const {
+                // Lets a string go in, so formats that don't parse scalars themselves can put one
+                unsafe fn try_from<'dst>(
+                    src_ptr: $crate::PtrConst<'_>,
+                    src_shape: &'static $crate::Shape,
+                    dst: $crate::PtrUninit<'dst>,
+                ) -> Result<$crate::PtrMut<'dst>, $crate::TryFromError> {
+                    if src_shape.id != <alloc::string::String as $crate::Facet>::SHAPE.id {
+                        return Err($crate::TryFromError::UnsupportedSourceShape {
+                            src_shape,
+                            expected: &[<alloc::string::String as $crate::Facet>::SHAPE],
+                        });
+                    }
+                    let $s: &str = unsafe { src_ptr.get::<alloc::string::String>() };
+                    let parsed: Option<$type> = $parse;
+                    match parsed {
+                        Some(value) => Ok(unsafe { dst.put(value) }),
+                        None => Err($crate::TryFromError::Generic(concat!(
+                            "invalid ",
+                            $type_name
+                        ))),
+                    }
+                }
+
+                let mut vtable =
+                    $crate::value_vtable!($type, |f, _opts| write!(f, $type_name));
+                vtable.display = Some(|value, $f| {
+                    let $value: &$type = unsafe { value.get::<$type>() };
+                    $display
+                });
+                vtable.parse = Some(|$s, target| {
+                    let parsed: Option<$type> = $parse;
+                    match parsed {
+                        Some(value) => Ok(unsafe { target.put(value) }),
+                        None => Err($crate::ParseError::Generic(concat!(
+                            "invalid ",
+                            $type_name
+                        ))),
+                    }
+                });
+                vtable.try_from = Some(try_from);
+                vtable
+            };
+
+            const SHAPE: &'static $crate::Shape = &const {
+                let affinity = $crate::ScalarAffinity::time();
+                $(
+                    let affinity = affinity.unix_nanos(
+                        |value| {
+                            let $instant: &$type = unsafe { value.get::<$type>() };
+                            $to_nanos
+                        },
+                        |nanos, target| {
+                            let $nanos: i128 = nanos;
+                            let value: Option<$type> = $from_nanos;
+                            match value {
+                                Some(value) => Some(unsafe { target.put(value) }),
+                                None => None,
+                            }
+                        },
+                    );
+                )?
+                $crate::Shape::builder_for_sized::<Self>()
+                    .ty($crate::Type::User($crate::UserType::Opaque))
+                    .def($crate::Def::Scalar(
+                        $crate::ScalarDef::builder()
+                            .affinity(affinity.build())
+                            .build(),
+                    ))
+                    .build()
+            };
+        }
+    };
+}
+
+pub(crate) use datetime_facet;
+
+/// Splits nanoseconds since the unix epoch into whole seconds and the nanoseconds past them,
+/// or returns `None` if the seconds don't fit an `i64`
+#[allow(dead_code)] // not every datetime crate needs it
+pub(crate) fn split_unix_nanos(nanos: i128) -> Option<(i64, u32)> {
+    let secs = i64::try_from(nanos.div_euclid(1_000_000_000)).ok()?;
+    Some((secs, nanos.rem_euclid(1_000_000_000) as u32))
+}
@@ -0,0 +1,40 @@
+use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+
+use crate::impls_datetime::datetime_facet;
+
+datetime_facet!(
+    Timestamp, "Timestamp",
+    display: |timestamp, f| write!(f, "{timestamp}"),
+    parse: |s| s.parse::<Timestamp>().ok(),
+    unix_nanos: |timestamp| timestamp.as_nanosecond(),
+    from_unix_nanos: |nanos| Timestamp::from_nanosecond(nanos).ok(),
+);
+
+// Written with its time zone, e.g. `2024-06-19T15:22:45-04:00[America/New_York]`; unix
+// timestamps are read back in UTC
+datetime_facet!(
+    Zoned, "Zoned",
+    display: |zoned, f| write!(f, "{zoned}"),
+    parse: |s| s.parse::<Zoned>().ok(),
+    unix_nanos: |zoned| zoned.timestamp().as_nanosecond(),
+    from_unix_nanos: |nanos| {
+        Timestamp::from_nanosecond(nanos)
+            .ok()
+            .map(|timestamp| timestamp.to_zoned(TimeZone::UTC))
+    },
+);
+
+// Civil datetimes aren't instants: jiff only turns them into one given a time zone
+datetime_facet!(
+    DateTime, "civil::DateTime",
+    display: |datetime, f| write!(f, "{datetime}"),
+    parse: |s| s.parse::<DateTime>().ok(),
+);
+
+datetime_facet!(
+    Date, "civil::Date",
+    display: |date, f| write!(f, "{date}"),
+    parse: |s| s.parse::<Date>().ok(),
+);
@@ -38,7 +38,7 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for OrderedFloat<T> {
             src_ptr: PtrConst<'_>,
         ) -> Result<PtrConst<'_>, TryBorrowInnerError> {
             let v = unsafe { src_ptr.get::<OrderedFloat<T>>() };
-            Ok(PtrConst::new((&v.0) as *const T as *const u8))
+            Ok(PtrConst::new(&raw const v.0))
         }
 
         let mut vtable = value_vtable!((), |f, _opts| write!(f, "OrderedFloat"));
@@ -54,10 +54,6 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for OrderedFloat<T> {
     };
 
     const SHAPE: &'static Shape = &const {
-        fn inner_shape<'a, T: Facet<'a>>() -> &'static Shape {
-            <T as Facet>::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .ty(Type::User(UserType::Struct(
                 StructType::builder()
@@ -72,7 +68,7 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for OrderedFloat<T> {
                     .affinity(ScalarAffinity::opaque().build())
                     .build(),
             ))
-            .inner(inner_shape::<T>)
+            .inner_static(T::SHAPE)
             .build()
     };
 }
@@ -114,7 +110,8 @@ unsafe impl<'a, T: Facet<'a> + ordered_float::FloatCore + Clone + core::str::Fro
             src_ptr: PtrConst<'_>,
         ) -> Result<PtrConst<'_>, TryBorrowInnerError> {
             let v = unsafe { src_ptr.get::<NotNan<T>>() };
-            Ok(PtrConst::new((&v.into_inner()) as *const T as *const u8))
+            // Pointing into the value itself, not at a copy of the float that's gone right away
+            Ok(PtrConst::new(core::ptr::from_ref::<T>(v)))
         }
 
         let mut vtable = value_vtable!((), |f, _opts| write!(f, "NotNan"));
@@ -135,10 +132,6 @@ unsafe impl<'a, T: Facet<'a> + ordered_float::FloatCore + Clone + core::str::Fro
     };
 
     const SHAPE: &'static Shape = &const {
-        fn inner_shape<'a, T: Facet<'a>>() -> &'static Shape {
-            <T as Facet>::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .ty(Type::User(UserType::Opaque))
             .def(Def::Scalar(
@@ -146,7 +139,7 @@ unsafe impl<'a, T: Facet<'a> + ordered_float::FloatCore + Clone + core::str::Fro
                     .affinity(ScalarAffinity::opaque().build())
                     .build(),
             ))
-            .inner(inner_shape::<T>)
+            .inner_static(T::SHAPE)
             .build()
     };
 }
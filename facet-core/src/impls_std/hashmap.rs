@@ -29,8 +29,8 @@ where
                     .union(MarkerTraits::EQ)
                     .union(MarkerTraits::UNPIN);
                 arg_dependent_traits
-                    .intersection(V::SHAPE.vtable.marker_traits)
-                    .intersection(K::SHAPE.vtable.marker_traits)
+                    .intersection(V::VTABLE.marker_traits)
+                    .intersection(K::VTABLE.marker_traits)
             })
             .type_name(|f, opts| {
                 if let Some(opts) = opts.for_children() {
@@ -44,7 +44,7 @@ where
                 }
             });
 
-        if K::SHAPE.vtable.debug.is_some() && V::SHAPE.vtable.debug.is_some() {
+        if K::VTABLE.debug.is_some() && V::VTABLE.debug.is_some() {
             builder = builder.debug(|value, f| {
                 let k_debug = <VTableView<K>>::of().debug().unwrap();
                 let v_debug = <VTableView<V>>::of().debug().unwrap();
@@ -63,7 +63,7 @@ where
 
         builder = builder.default_in_place(|target| unsafe { target.put(Self::default()) });
 
-        if V::SHAPE.vtable.clone_into.is_some() && K::SHAPE.vtable.clone_into.is_some() {
+        if V::VTABLE.clone_into.is_some() && K::VTABLE.clone_into.is_some() {
             builder = builder.clone_into(|src, dst| unsafe {
                 let map = src;
                 let mut new_map = HashMap::with_capacity_and_hasher(map.len(), S::default());
@@ -91,7 +91,7 @@ where
             });
         }
 
-        if V::SHAPE.vtable.eq.is_some() {
+        if V::VTABLE.eq.is_some() {
             builder = builder.eq(|a, b| {
                 let v_eq = <VTableView<V>>::of().eq().unwrap();
                 a.len() == b.len()
@@ -101,7 +101,7 @@ where
             });
         }
 
-        if V::SHAPE.vtable.hash.is_some() {
+        if V::VTABLE.hash.is_some() {
             builder = builder.hash(|map, hasher_this, hasher_write_fn| unsafe {
                 use crate::HasherProxy;
                 let v_hash = <VTableView<V>>::of().hash().unwrap();
@@ -167,7 +167,7 @@ where
                                     let map = ptr.get::<HashMap<K, V>>();
                                     let keys: VecDeque<&K> = map.keys().collect();
                                     let iter_state = Box::new(HashMapIterator { map: ptr, keys });
-                                    PtrMut::new(Box::into_raw(iter_state) as *mut u8)
+                                    PtrMut::new(Box::into_raw(iter_state))
                                 })
                                 .iter_vtable(
                                     MapIterVTable::builder()
@@ -187,8 +187,9 @@ where
                                         })
                                         .dealloc(|iter_ptr| unsafe {
                                             drop(Box::from_raw(
-                                                iter_ptr.as_ptr::<HashMapIterator<'_, K>>()
-                                                    as *mut HashMapIterator<'_, K>,
+                                                iter_ptr
+                                                    .as_ptr::<HashMapIterator<'_, K>>()
+                                                    .cast_mut(),
                                             ));
                                         })
                                         .build(),
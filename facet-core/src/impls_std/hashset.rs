@@ -0,0 +1,136 @@
+use core::hash::BuildHasher;
+use std::collections::HashSet;
+use std::collections::hash_set;
+
+use crate::ptr::{PtrConst, PtrMut};
+
+use crate::{
+    Def, Facet, MarkerTraits, SetDef, SetIterVTable, SetVTable, Shape, Type, TypeParam, UserType,
+    VTableView, ValueVTable,
+};
+
+unsafe impl<'a, T, S> Facet<'a> for HashSet<T, S>
+where
+    T: Facet<'a> + core::cmp::Eq + core::hash::Hash,
+    S: Facet<'a> + Default + BuildHasher,
+{
+    const VTABLE: &'static ValueVTable = &const {
+        let mut builder = ValueVTable::builder::<Self>()
+            .marker_traits({
+                let arg_dependent_traits = MarkerTraits::SEND
+                    .union(MarkerTraits::SYNC)
+                    .union(MarkerTraits::EQ)
+                    .union(MarkerTraits::UNPIN);
+                arg_dependent_traits.intersection(T::VTABLE.marker_traits)
+            })
+            .type_name(|f, opts| {
+                if let Some(opts) = opts.for_children() {
+                    write!(f, "HashSet<")?;
+                    (T::SHAPE.vtable.type_name)(f, opts)?;
+                    write!(f, ">")
+                } else {
+                    write!(f, "HashSet<⋯>")
+                }
+            });
+
+        if T::VTABLE.debug.is_some() {
+            builder = builder.debug(|value, f| {
+                let t_debug = <VTableView<T>>::of().debug().unwrap();
+                write!(f, "{{")?;
+                for (i, item) in value.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    (t_debug)(item, f)?;
+                }
+                write!(f, "}}")
+            });
+        }
+
+        builder = builder.default_in_place(|target| unsafe { target.put(Self::default()) });
+
+        if T::VTABLE.clone_into.is_some() {
+            builder = builder.clone_into(|src, dst| unsafe {
+                let mut new_set = HashSet::with_capacity_and_hasher(src.len(), S::default());
+
+                let t_clone_into = <VTableView<T>>::of().clone_into().unwrap();
+
+                for item in src {
+                    use crate::TypedPtrUninit;
+                    use core::mem::MaybeUninit;
+
+                    let mut new_item = MaybeUninit::<T>::uninit();
+                    let uninit_item = TypedPtrUninit::new(new_item.as_mut_ptr());
+                    (t_clone_into)(item, uninit_item);
+
+                    new_set.insert(new_item.assume_init());
+                }
+
+                dst.put(new_set)
+            });
+        }
+
+        // Equal sets can iterate in different orders, so there is no `hash` to go with `eq`
+        builder = builder.eq(|a, b| a.len() == b.len() && a.iter().all(|item| b.contains(item)));
+
+        builder.build()
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .type_params(&[
+                TypeParam {
+                    name: "T",
+                    shape: || T::SHAPE,
+                },
+                TypeParam {
+                    name: "S",
+                    shape: || S::SHAPE,
+                },
+            ])
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Set(
+                SetDef::builder()
+                    .t(|| T::SHAPE)
+                    .vtable(
+                        &const {
+                            SetVTable::builder()
+                                .init_in_place_with_capacity(|uninit, capacity| unsafe {
+                                    uninit
+                                        .put(Self::with_capacity_and_hasher(capacity, S::default()))
+                                })
+                                .insert(|ptr, value| unsafe {
+                                    let set = ptr.as_mut::<Self>();
+                                    set.insert(value.read::<T>())
+                                })
+                                .len(|ptr| unsafe { ptr.get::<Self>().len() })
+                                .contains(|ptr, value| unsafe {
+                                    ptr.get::<Self>().contains(value.get::<T>())
+                                })
+                                .iter(|ptr| unsafe {
+                                    let iter = Box::new(ptr.get::<Self>().iter());
+                                    PtrMut::new(Box::into_raw(iter))
+                                })
+                                .iter_vtable(
+                                    SetIterVTable::builder()
+                                        .next(|iter_ptr| unsafe {
+                                            let iter = iter_ptr.as_mut::<hash_set::Iter<'_, T>>();
+                                            iter.next().map(|item| PtrConst::new(item as *const T))
+                                        })
+                                        .dealloc(|iter_ptr| unsafe {
+                                            drop(Box::from_raw(
+                                                iter_ptr
+                                                    .as_ptr::<hash_set::Iter<'_, T>>()
+                                                    .cast_mut(),
+                                            ));
+                                        })
+                                        .build(),
+                                )
+                                .build()
+                        },
+                    )
+                    .build(),
+            ))
+            .build()
+    };
+}
@@ -1,2 +1,3 @@
 mod hashmap;
+mod hashset;
 mod path;
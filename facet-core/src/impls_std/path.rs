@@ -1,8 +1,35 @@
 use crate::*;
 
-unsafe impl Facet<'_> for std::path::PathBuf {
-    const VTABLE: &'static ValueVTable =
-        &const { value_vtable!((), |f, _opts| write!(f, "PathBuf")) };
+use alloc::string::String;
+use std::path::{Path, PathBuf};
+
+unsafe impl Facet<'_> for PathBuf {
+    const VTABLE: &'static ValueVTable = &const {
+        // Lets a string go in, so formats that don't parse scalars themselves can put one
+        unsafe fn try_from<'dst>(
+            src_ptr: PtrConst<'_>,
+            src_shape: &'static Shape,
+            dst: PtrUninit<'dst>,
+        ) -> Result<PtrMut<'dst>, TryFromError> {
+            if src_shape.id != <String as Facet>::SHAPE.id {
+                return Err(TryFromError::UnsupportedSourceShape {
+                    src_shape,
+                    expected: &[<String as Facet>::SHAPE],
+                });
+            }
+            let s = unsafe { src_ptr.get::<String>() };
+            Ok(unsafe { dst.put(PathBuf::from(s.clone())) })
+        }
+
+        // Paths aren't `Display`, since they may not be UTF-8: they're written lossily
+        let mut vtable = value_vtable!(PathBuf, |f, _opts| write!(f, "PathBuf"));
+        vtable.display = Some(|value, f| {
+            let path = unsafe { value.get::<PathBuf>() };
+            write!(f, "{}", path.display())
+        });
+        vtable.try_from = Some(try_from);
+        vtable
+    };
 
     const SHAPE: &'static Shape = &const {
         Shape::builder_for_sized::<Self>()
@@ -16,8 +43,16 @@ unsafe impl Facet<'_> for std::path::PathBuf {
     };
 }
 
-unsafe impl Facet<'_> for std::path::Path {
-    const VTABLE: &'static ValueVTable = &const { value_vtable!((), |f, _opts| write!(f, "Path")) };
+unsafe impl Facet<'_> for Path {
+    const VTABLE: &'static ValueVTable = &const {
+        // Intentionally &Path, since Path is a DST
+        let mut vtable = value_vtable!(&Path, |f, _opts| write!(f, "Path"));
+        vtable.display = Some(|value, f| {
+            let path = unsafe { value.get::<&Path>() };
+            write!(f, "{}", path.display())
+        });
+        vtable
+    };
 
     const SHAPE: &'static Shape = &const {
         Shape::builder_for_unsized::<Self>()
@@ -0,0 +1,38 @@
+use time::format_description::well_known::{Iso8601, Rfc3339};
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+use crate::impls_datetime::datetime_facet;
+
+fn write_formatted(
+    f: &mut core::fmt::Formatter<'_>,
+    formatted: Result<alloc::string::String, time::error::Format>,
+) -> core::fmt::Result {
+    f.write_str(&formatted.map_err(|_| core::fmt::Error)?)
+}
+
+datetime_facet!(
+    OffsetDateTime, "OffsetDateTime",
+    display: |datetime, f| write_formatted(f, datetime.format(&Rfc3339)),
+    parse: |s| OffsetDateTime::parse(s, &Rfc3339).ok(),
+    unix_nanos: |datetime| datetime.unix_timestamp_nanos(),
+    from_unix_nanos: |nanos| OffsetDateTime::from_unix_timestamp_nanos(nanos).ok(),
+);
+
+// Without an offset, unix timestamps are taken to be in UTC
+datetime_facet!(
+    PrimitiveDateTime, "PrimitiveDateTime",
+    display: |datetime, f| write_formatted(f, datetime.format(&Iso8601::DATE_TIME)),
+    parse: |s| PrimitiveDateTime::parse(s, &Iso8601::DATE_TIME).ok(),
+    unix_nanos: |datetime| datetime.assume_utc().unix_timestamp_nanos(),
+    from_unix_nanos: |nanos| {
+        OffsetDateTime::from_unix_timestamp_nanos(nanos)
+            .ok()
+            .map(|datetime| PrimitiveDateTime::new(datetime.date(), datetime.time()))
+    },
+);
+
+datetime_facet!(
+    Date, "Date",
+    display: |date, f| write_formatted(f, date.format(&Iso8601::DATE)),
+    parse: |s| Date::parse(s, &Iso8601::DATE).ok(),
+);
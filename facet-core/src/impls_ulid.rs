@@ -51,10 +51,6 @@ unsafe impl Facet<'_> for Ulid {
 
     const SHAPE: &'static Shape = &const {
         // Return the Shape of the inner type (String)
-        fn inner_shape() -> &'static Shape {
-            <String as Facet>::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .ty(Type::User(UserType::Opaque))
             .def(Def::Scalar(
@@ -62,7 +58,7 @@ unsafe impl Facet<'_> for Ulid {
                     .affinity(ScalarAffinity::ulid().build())
                     .build(),
             ))
-            .inner(inner_shape)
+            .inner_static(<String as Facet>::SHAPE)
             .build()
     };
 }
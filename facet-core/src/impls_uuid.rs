@@ -5,8 +5,7 @@ use uuid::Uuid;
 
 use crate::{
     Def, Facet, ParseError, PtrConst, PtrMut, PtrUninit, ScalarAffinity, ScalarDef, Shape,
-    TryBorrowInnerError, TryFromError, TryIntoInnerError, Type, UserType, ValueVTable,
-    value_vtable,
+    TryFromError, TryIntoInnerError, Type, UserType, ValueVTable, value_vtable,
 };
 
 unsafe impl Facet<'_> for Uuid {
@@ -41,30 +40,18 @@ unsafe impl Facet<'_> for Uuid {
             Ok(unsafe { dst.put(uuid.to_string()) })
         }
 
-        unsafe fn try_borrow_inner(
-            src_ptr: PtrConst<'_>,
-        ) -> Result<PtrConst<'_>, TryBorrowInnerError> {
-            let uuid = unsafe { src_ptr.get::<Uuid>() };
-            Ok(PtrConst::new(uuid.as_bytes().as_ptr()))
-        }
-
-        let mut vtable = value_vtable!((), |f, _opts| write!(f, "Uuid"));
+        let mut vtable = value_vtable!(Uuid, |f, _opts| write!(f, "Uuid"));
         vtable.parse = Some(|s, target| match Uuid::parse_str(s) {
             Ok(uuid) => Ok(unsafe { target.put(uuid) }),
             Err(_) => Err(ParseError::Generic("UUID parsing failed")),
         });
         vtable.try_from = Some(try_from);
         vtable.try_into_inner = Some(try_into_inner);
-        vtable.try_borrow_inner = Some(try_borrow_inner);
         vtable
     };
 
     const SHAPE: &'static Shape = &const {
         // Return the Shape of the inner type (String)
-        fn inner_shape() -> &'static Shape {
-            <String as Facet>::SHAPE
-        }
-
         Shape::builder_for_sized::<Self>()
             .ty(Type::User(UserType::Opaque))
             .def(Def::Scalar(
@@ -72,7 +59,7 @@ unsafe impl Facet<'_> for Uuid {
                     .affinity(ScalarAffinity::uuid().build())
                     .build(),
             ))
-            .inner(inner_shape)
+            .inner_static(<String as Facet>::SHAPE)
             .build()
     };
 }
@@ -0,0 +1,97 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{Facet, Type, UserType};
+
+/// The most fields a [`Lens`] can go through
+pub const MAX_LENS_DEPTH: usize = 16;
+
+/// A typed path from a `Root` value down to a `Target` nested in its fields.
+///
+/// Structs deriving `Facet` with `#[facet(lenses)]` get one lens per field, e.g.
+/// `Server::fields().port()`, and lenses compose with [`Lens::then`]. They're followed
+/// with `Peek::lens` and `Wip::lens` from `facet-reflect`, which check the shapes along
+/// the way, so a typo in a path is a compile error rather than a runtime one.
+pub struct Lens<Root, Target> {
+    indices: [usize; MAX_LENS_DEPTH],
+    len: usize,
+    _phantom: PhantomData<fn(&Root) -> &Target>,
+}
+
+impl<Root, Target> Lens<Root, Target> {
+    /// A lens onto the field at `index` of `Root`, which must be of type `Target`
+    pub const fn field(index: usize) -> Self {
+        let mut indices = [0; MAX_LENS_DEPTH];
+        indices[0] = index;
+        Self {
+            indices,
+            len: 1,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Goes further down, through a lens starting where this one ends
+    ///
+    /// # Panics
+    ///
+    /// If the path ends up deeper than [`MAX_LENS_DEPTH`] fields.
+    pub const fn then<Next>(self, next: Lens<Target, Next>) -> Lens<Root, Next> {
+        assert!(
+            self.len + next.len <= MAX_LENS_DEPTH,
+            "lens goes through too many fields"
+        );
+        let mut indices = self.indices;
+        let mut i = 0;
+        while i < next.len {
+            indices[self.len + i] = next.indices[i];
+            i += 1;
+        }
+        Lens {
+            indices,
+            len: self.len + next.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The index of each field the lens goes through, outermost first
+    pub fn indices(&self) -> &[usize] {
+        &self.indices[..self.len]
+    }
+
+    /// How many fields the lens goes through
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Always false: a lens goes through at least one field
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<Root, Target> Clone for Lens<Root, Target> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Root, Target> Copy for Lens<Root, Target> {}
+
+impl<'a, Root: Facet<'a>, Target> fmt::Debug for Lens<Root, Target> {
+    /// Shows the path as field names, e.g. `Server.backup.port`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Root::SHAPE)?;
+        let mut shape = Root::SHAPE;
+        for &index in self.indices() {
+            match shape.ty {
+                Type::User(UserType::Struct(ty)) if index < ty.fields.len() => {
+                    let field = &ty.fields[index];
+                    write!(f, ".{}", field.name)?;
+                    shape = field.shape();
+                }
+                _ => write!(f, ".{index}")?,
+            }
+        }
+        Ok(())
+    }
+}
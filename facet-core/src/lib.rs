@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::std_instead_of_core)]
+#![warn(clippy::ptr_as_ptr)]
+#![warn(clippy::ptr_cast_constness)]
 #![warn(clippy::std_instead_of_alloc)]
 #![doc = include_str!("../README.md")]
 
@@ -23,6 +25,7 @@ pub mod spez;
 
 // Definition for `core::` types
 mod impls_core;
+pub use impls_core::DurationParts;
 
 // Definition for `alloc::` types
 #[cfg(feature = "alloc")]
@@ -44,10 +47,26 @@ mod impls_uuid;
 #[cfg(feature = "ulid")]
 mod impls_ulid;
 
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+mod impls_datetime;
+
+#[cfg(feature = "chrono")]
+mod impls_chrono;
+
+#[cfg(feature = "time")]
+mod impls_time;
+
+#[cfg(feature = "jiff")]
+mod impls_jiff;
+
 // Const type Id
 mod typeid;
 pub use typeid::*;
 
+// Typed paths through fields
+mod lens;
+pub use lens::*;
+
 // Type definitions
 mod types;
 #[allow(unused_imports)] // wtf clippy? we're re-exporting?
@@ -92,6 +92,9 @@ macro_rules! value_vtable {
                 if $crate::spez::impls!($type_name: core::marker::Unpin) {
                     traits = traits.union($crate::MarkerTraits::UNPIN);
                 }
+                if !$crate::spez::impls!($type_name: core::ops::Drop) {
+                    traits = traits.union($crate::MarkerTraits::NO_DROP_IMPL);
+                }
                 builder = builder.marker_traits(traits);
             }
 
@@ -1,6 +1,10 @@
 //! Opaque pointers
 //!
 //! Type-erased pointer helpers for working with reflected values
+//!
+//! None of these go through integers: offsets are applied to the pointers themselves, so
+//! they keep their provenance — `cargo miri test` checks as much, with
+//! `-Zmiri-strict-provenance`.
 
 use core::{marker::PhantomData, ptr::NonNull};
 
@@ -39,7 +43,7 @@ impl<'mem> PtrUninit<'mem> {
     ///
     /// This is safe because it's generic over T
     pub fn new<T>(ptr: *mut T) -> Self {
-        Self(ptr as *mut u8, PhantomData)
+        Self(ptr.cast(), PhantomData)
     }
 
     /// Creates a new opaque pointer from a reference to a [`core::mem::MaybeUninit`]
@@ -48,7 +52,7 @@ impl<'mem> PtrUninit<'mem> {
     ///
     /// This is safe because it's generic over T
     pub fn from_maybe_uninit<T>(borrow: &'mem mut core::mem::MaybeUninit<T>) -> Self {
-        Self(borrow.as_mut_ptr() as *mut u8, PhantomData)
+        Self(borrow.as_mut_ptr().cast(), PhantomData)
     }
 
     /// Assumes the pointer is initialized and returns an `Opaque` pointer
@@ -69,7 +73,7 @@ impl<'mem> PtrUninit<'mem> {
     /// that can be safely written to.
     pub unsafe fn put<T>(self, value: T) -> PtrMut<'mem> {
         unsafe {
-            core::ptr::write(self.0 as *mut T, value);
+            core::ptr::write(self.0.cast::<T>(), value);
             self.assume_init()
         }
     }
@@ -107,6 +111,16 @@ impl<'mem> PtrUninit<'mem> {
             PhantomData,
         )
     }
+
+    /// How many bytes this pointer is past `origin`
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be into the same allocated object, see
+    /// [`pointer::byte_offset_from`](https://doc.rust-lang.org/std/primitive.pointer.html#method.byte_offset_from)
+    pub unsafe fn byte_offset_from(self, origin: PtrUninit<'_>) -> isize {
+        unsafe { self.0.byte_offset_from(origin.0.cast_const()) }
+    }
 }
 
 impl<'mem, T> From<TypedPtrUninit<'mem, T>> for PtrUninit<'mem> {
@@ -246,7 +260,7 @@ impl<'mem> PtrConst<'mem> {
     ///
     /// It's encouraged to take the address of something with `&raw const x`, rather than `&x`
     pub const fn new<T>(ptr: *const T) -> Self {
-        unsafe { Self(NonNull::new_unchecked(ptr as *mut u8), PhantomData) }
+        unsafe { Self(NonNull::new_unchecked(ptr.cast_mut().cast()), PhantomData) }
     }
 
     /// Gets the underlying raw pointer as a byte pointer
@@ -260,7 +274,7 @@ impl<'mem> PtrConst<'mem> {
     ///
     /// Must be called with the original type T that was used to create this pointer
     pub const unsafe fn as_ptr<T>(self) -> *const T {
-        self.0.as_ptr() as *const T
+        self.0.as_ptr().cast_const().cast()
     }
 
     /// Gets the underlying raw pointer as a const pointer of type T
@@ -270,7 +284,7 @@ impl<'mem> PtrConst<'mem> {
     /// `T` must be the _actual_ underlying type. You're downcasting with no guardrails.
     pub const unsafe fn get<'borrow: 'mem, T>(self) -> &'borrow T {
         // TODO: rename to `get`, or something else? it's technically a borrow...
-        unsafe { &*(self.0.as_ptr() as *const T) }
+        unsafe { self.0.cast::<T>().as_ref() }
     }
 
     /// Returns a pointer with the given offset added
@@ -295,6 +309,17 @@ impl<'mem> PtrConst<'mem> {
     pub const unsafe fn read<T>(self) -> T {
         unsafe { core::ptr::read(self.as_ptr()) }
     }
+
+    /// How many bytes this pointer is past `origin`, e.g. the offset of a field found
+    /// through a vtable. Apply it to another pointer with [`PtrMut::field`].
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be into the same allocated object, see
+    /// [`pointer::byte_offset_from`](https://doc.rust-lang.org/std/primitive.pointer.html#method.byte_offset_from)
+    pub unsafe fn byte_offset_from(self, origin: PtrConst<'_>) -> isize {
+        unsafe { self.0.byte_offset_from(origin.0) }
+    }
 }
 
 /// A type-erased pointer to an initialized value
@@ -312,10 +337,7 @@ impl<'mem> PtrMut<'mem> {
     ///
     /// It's encouraged to take the address of something with `&raw mut x`, rather than `&x`
     pub const fn new<T>(ptr: *mut T) -> Self {
-        Self(
-            unsafe { NonNull::new_unchecked(ptr as *mut u8) },
-            PhantomData,
-        )
+        Self(unsafe { NonNull::new_unchecked(ptr.cast()) }, PhantomData)
     }
 
     /// Gets the underlying raw pointer
@@ -334,7 +356,7 @@ impl<'mem> PtrMut<'mem> {
     ///
     /// Must be called with the original type T that was used to create this pointer
     pub const unsafe fn as_ptr<T>(self) -> *const T {
-        self.0.as_ptr() as *const T
+        self.0.as_ptr().cast_const().cast()
     }
 
     /// Gets the underlying raw pointer as a mutable pointer of type T
@@ -343,7 +365,7 @@ impl<'mem> PtrMut<'mem> {
     ///
     /// `T` must be the _actual_ underlying type. You're downcasting with no guardrails.
     pub const unsafe fn as_mut<'borrow: 'mem, T>(self) -> &'borrow mut T {
-        unsafe { &mut *(self.0.as_ptr() as *mut T) }
+        unsafe { self.0.cast::<T>().as_mut() }
     }
 
     /// Gets the underlying raw pointer as a const pointer of type T
@@ -356,7 +378,7 @@ impl<'mem> PtrMut<'mem> {
     ///
     /// Basically this is UB land. Careful.
     pub const unsafe fn get<'borrow: 'mem, T>(self) -> &'borrow T {
-        unsafe { &*(self.0.as_ptr() as *const T) }
+        unsafe { self.0.cast::<T>().as_ref() }
     }
 
     /// Make a const ptr out of this mut ptr
@@ -364,6 +386,29 @@ impl<'mem> PtrMut<'mem> {
         PtrConst(self.0, PhantomData)
     }
 
+    /// Returns a pointer with the given offset added
+    ///
+    /// # Safety
+    ///
+    /// Offset must be within the bounds of the allocated memory,
+    /// and the resulting pointer must be properly aligned.
+    pub const unsafe fn field(self, offset: usize) -> PtrMut<'mem> {
+        PtrMut(
+            unsafe { NonNull::new_unchecked(self.0.as_ptr().byte_add(offset)) },
+            PhantomData,
+        )
+    }
+
+    /// How many bytes this pointer is past `origin`
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be into the same allocated object, see
+    /// [`pointer::byte_offset_from`](https://doc.rust-lang.org/std/primitive.pointer.html#method.byte_offset_from)
+    pub unsafe fn byte_offset_from(self, origin: PtrMut<'_>) -> isize {
+        unsafe { self.0.byte_offset_from(origin.0) }
+    }
+
     /// Exposes [`core::ptr::read`]
     ///
     /// # Safety
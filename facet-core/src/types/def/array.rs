@@ -75,6 +75,13 @@ impl ArrayDefBuilder {
     }
 }
 
+/// Get pointer to the item at the given index, or `None` if it's out of bounds
+///
+/// # Safety
+///
+/// The `array` parameter must point to aligned, initialized memory of the correct type.
+pub type ArrayGetFn = unsafe fn(array: PtrConst, index: usize) -> Option<PtrConst>;
+
 /// Get pointer to the data buffer of the array.
 ///
 /// # Safety
@@ -94,6 +101,9 @@ pub type ArrayAsMutPtrFn = unsafe fn(array: PtrMut) -> PtrMut;
 #[repr(C)]
 #[non_exhaustive]
 pub struct ArrayVTable {
+    /// cf. [`ArrayGetFn`]
+    pub get: ArrayGetFn,
+
     /// cf. [`ArrayAsPtrFn`]
     pub as_ptr: ArrayAsPtrFn,
 
@@ -110,6 +120,7 @@ impl ArrayVTable {
 
 /// Builds a [`ArrayVTable`]
 pub struct ArrayVTableBuilder {
+    get_fn: Option<ArrayGetFn>,
     as_ptr_fn: Option<ArrayAsPtrFn>,
     as_mut_ptr_fn: Option<ArrayAsMutPtrFn>,
 }
@@ -119,11 +130,18 @@ impl ArrayVTableBuilder {
     #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         Self {
+            get_fn: None,
             as_ptr_fn: None,
             as_mut_ptr_fn: None,
         }
     }
 
+    /// Sets the get field
+    pub const fn get(mut self, f: ArrayGetFn) -> Self {
+        self.get_fn = Some(f);
+        self
+    }
+
     /// Sets the as_ptr field
     pub const fn as_ptr(mut self, f: ArrayAsPtrFn) -> Self {
         self.as_ptr_fn = Some(f);
@@ -143,6 +161,7 @@ impl ArrayVTableBuilder {
     /// This method will panic if any of the required fields are `None`.
     pub const fn build(self) -> ArrayVTable {
         ArrayVTable {
+            get: self.get_fn.unwrap(),
             as_ptr: self.as_ptr_fn.unwrap(),
             as_mut_ptr: self.as_mut_ptr_fn.unwrap(),
         }
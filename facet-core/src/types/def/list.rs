@@ -88,6 +88,13 @@ pub type ListPushFn = unsafe fn(list: PtrMut, item: PtrMut);
 /// The `list` parameter must point to aligned, initialized memory of the correct type.
 pub type ListLenFn = unsafe fn(list: PtrConst) -> usize;
 
+/// Get pointer to the item at the given index, or `None` if it's out of bounds
+///
+/// # Safety
+///
+/// The `list` parameter must point to aligned, initialized memory of the correct type.
+pub type ListGetFn = unsafe fn(list: PtrConst, index: usize) -> Option<PtrConst>;
+
 /// Get pointer to the data buffer of the list.
 ///
 /// # Safety
@@ -118,6 +125,9 @@ pub struct ListVTable {
     /// cf. [`ListLenFn`]
     pub len: ListLenFn,
 
+    /// cf. [`ListGetFn`]
+    pub get: ListGetFn,
+
     /// cf. [`ListAsPtrFn`]
     pub as_ptr: ListAsPtrFn,
 
@@ -137,6 +147,7 @@ pub struct ListVTableBuilder {
     init_in_place_with_capacity: Option<ListInitInPlaceWithCapacityFn>,
     push: Option<ListPushFn>,
     len: Option<ListLenFn>,
+    get: Option<ListGetFn>,
     as_ptr: Option<ListAsPtrFn>,
     as_mut_ptr: Option<ListAsMutPtrFn>,
 }
@@ -149,6 +160,7 @@ impl ListVTableBuilder {
             init_in_place_with_capacity: None,
             push: None,
             len: None,
+            get: None,
             as_ptr: None,
             as_mut_ptr: None,
         }
@@ -172,6 +184,12 @@ impl ListVTableBuilder {
         self
     }
 
+    /// Sets the get field
+    pub const fn get(mut self, f: ListGetFn) -> Self {
+        self.get = Some(f);
+        self
+    }
+
     /// Sets the as_ptr field
     pub const fn as_ptr(mut self, f: ListAsPtrFn) -> Self {
         self.as_ptr = Some(f);
@@ -194,6 +212,7 @@ impl ListVTableBuilder {
             init_in_place_with_capacity: self.init_in_place_with_capacity,
             push: self.push.unwrap(),
             len: self.len.unwrap(),
+            get: self.get.unwrap(),
             as_ptr: self.as_ptr.unwrap(),
             as_mut_ptr: self.as_mut_ptr.unwrap(),
         }
@@ -12,9 +12,15 @@ pub use list::*;
 mod map;
 pub use map::*;
 
+mod set;
+pub use set::*;
+
 mod option;
 pub use option::*;
 
+mod result;
+pub use result::*;
+
 mod smartptr;
 pub use smartptr::*;
 
@@ -46,6 +52,11 @@ pub enum Def {
     /// e.g. `Map<String, T>`
     Map(MapDef),
 
+    /// Set — unordered collection of unique values, homogeneous
+    ///
+    /// e.g. `HashSet<T>`, `BTreeSet<T>`
+    Set(SetDef),
+
     /// Ordered list of heterogenous values, variable size
     ///
     /// e.g. `Vec<T>`
@@ -66,6 +77,11 @@ pub enum Def {
     /// e.g. `Option<T>`
     Option(OptionDef),
 
+    /// Result
+    ///
+    /// e.g. `Result<T, E>`
+    Result(ResultDef),
+
     /// Smart pointers, like `Arc<T>`, `Rc<T>`, etc.
     SmartPointer(SmartPointerDef),
 }
@@ -86,6 +102,13 @@ impl Def {
             _ => Err(self),
         }
     }
+    /// Returns the `SetDef` wrapped in an `Ok` if this is a [`Def::Set`].
+    pub fn into_set(self) -> Result<SetDef, Self> {
+        match self {
+            Self::Set(def) => Ok(def),
+            _ => Err(self),
+        }
+    }
     /// Returns the `ListDef` wrapped in an `Ok` if this is a [`Def::List`].
     pub fn into_list(self) -> Result<ListDef, Self> {
         match self {
@@ -114,6 +137,13 @@ impl Def {
             _ => Err(self),
         }
     }
+    /// Returns the `ResultDef` wrapped in an `Ok` if this is a [`Def::Result`].
+    pub fn into_result(self) -> Result<ResultDef, Self> {
+        match self {
+            Self::Result(def) => Ok(def),
+            _ => Err(self),
+        }
+    }
     /// Returns the `SmartPointerDef` wrapped in an `Ok` if this is a [`Def::SmartPointer`].
     pub fn into_smart_pointer(self) -> Result<SmartPointerDef, Self> {
         match self {
@@ -1,4 +1,4 @@
-use crate::PtrConst;
+use crate::{PtrConst, PtrMut, PtrUninit};
 
 /// Definition for scalar types
 #[derive(Clone, Copy, Debug)]
@@ -755,6 +755,12 @@ pub struct TimeAffinity {
     /// store a copy of the epoch for a lack of timezone support, and None for "it's more
     /// complicated than that".
     timezone_granularity: Option<PtrConst<'static>>,
+
+    /// Converts a value to a unix timestamp, for types whose values stand for an instant
+    to_unix_nanos: Option<ToUnixNanosFn>,
+
+    /// Creates a value from a unix timestamp, for types whose values stand for an instant
+    from_unix_nanos: Option<FromUnixNanosFn>,
 }
 
 impl TimeAffinity {
@@ -762,8 +768,37 @@ impl TimeAffinity {
     pub const fn builder() -> TimeAffinityBuilder {
         TimeAffinityBuilder::new()
     }
+
+    /// Returns the function converting a value to nanoseconds since the unix epoch, if values
+    /// stand for an instant (a `NaiveDate`, say, doesn't)
+    pub const fn to_unix_nanos(&self) -> Option<ToUnixNanosFn> {
+        self.to_unix_nanos
+    }
+
+    /// Returns the function creating a value from nanoseconds since the unix epoch, if values
+    /// stand for an instant
+    pub const fn from_unix_nanos(&self) -> Option<FromUnixNanosFn> {
+        self.from_unix_nanos
+    }
 }
 
+/// Returns the number of nanoseconds between the unix epoch and the instant a value stands for
+///
+/// # Safety
+///
+/// The `value` parameter must point to aligned, initialized memory of the correct type.
+pub type ToUnixNanosFn = for<'mem> unsafe fn(value: PtrConst<'mem>) -> i128;
+
+/// Initializes `target` to the instant `nanos` nanoseconds after the unix epoch, or returns
+/// `None` if the type can't represent it
+///
+/// # Safety
+///
+/// The `target` parameter must have the correct layout and alignment, but points to
+/// uninitialized memory. If this returns `Some`, the memory is initialized.
+pub type FromUnixNanosFn =
+    for<'mem> unsafe fn(nanos: i128, target: PtrUninit<'mem>) -> Option<PtrMut<'mem>>;
+
 /// Builder for UuidAffinity
 #[repr(C)]
 pub struct TimeAffinityBuilder {
@@ -773,6 +808,8 @@ pub struct TimeAffinityBuilder {
     granularity: Option<PtrConst<'static>>,
     interval_elements: Option<&'static [PtrConst<'static>]>,
     timezone_granularity: Option<PtrConst<'static>>,
+    to_unix_nanos: Option<ToUnixNanosFn>,
+    from_unix_nanos: Option<FromUnixNanosFn>,
 }
 
 impl TimeAffinityBuilder {
@@ -786,6 +823,8 @@ impl TimeAffinityBuilder {
             granularity: None,
             interval_elements: None,
             timezone_granularity: None,
+            to_unix_nanos: None,
+            from_unix_nanos: None,
         }
     }
 
@@ -828,6 +867,18 @@ impl TimeAffinityBuilder {
         self
     }
 
+    /// Sets the functions converting values to and from nanoseconds since the unix epoch, for
+    /// types whose values stand for an instant
+    pub const fn unix_nanos(
+        mut self,
+        to_unix_nanos: ToUnixNanosFn,
+        from_unix_nanos: FromUnixNanosFn,
+    ) -> Self {
+        self.to_unix_nanos = Some(to_unix_nanos);
+        self.from_unix_nanos = Some(from_unix_nanos);
+        self
+    }
+
     /// Builds the ScalarAffinity
     pub const fn build(self) -> ScalarAffinity {
         ScalarAffinity::Time(TimeAffinity {
@@ -837,6 +888,8 @@ impl TimeAffinityBuilder {
             granularity: self.granularity,
             interval_elements: self.interval_elements,
             timezone_granularity: self.timezone_granularity,
+            to_unix_nanos: self.to_unix_nanos,
+            from_unix_nanos: self.from_unix_nanos,
         })
     }
 }
@@ -70,6 +70,13 @@ impl SliceDefBuilder {
 /// The `slice` parameter must point to aligned, initialized memory of the correct type.
 pub type SliceLenFn = unsafe fn(slice: PtrConst) -> usize;
 
+/// Get pointer to the item at the given index, or `None` if it's out of bounds
+///
+/// # Safety
+///
+/// The `slice` parameter must point to aligned, initialized memory of the correct type.
+pub type SliceGetFn = unsafe fn(slice: PtrConst, index: usize) -> Option<PtrConst>;
+
 /// Get pointer to the data buffer of the slice
 ///
 /// # Safety
@@ -92,6 +99,8 @@ pub type SliceAsMutPtrFn = unsafe fn(slice: PtrMut) -> PtrMut;
 pub struct SliceVTable {
     /// Number of items in the slice
     pub len: SliceLenFn,
+    /// Get pointer to the item at the given index
+    pub get: SliceGetFn,
     /// Get pointer to the data buffer of the slice.
     pub as_ptr: SliceAsPtrFn,
     /// Get mutable pointer to the data buffer of the slice.
@@ -110,6 +119,7 @@ pub struct SliceVTableBuilder {
     as_ptr: Option<SliceAsPtrFn>,
     as_mut_ptr: Option<SliceAsMutPtrFn>,
     len: Option<SliceLenFn>,
+    get: Option<SliceGetFn>,
 }
 
 impl SliceVTableBuilder {
@@ -118,6 +128,7 @@ impl SliceVTableBuilder {
     pub const fn new() -> Self {
         Self {
             len: None,
+            get: None,
             as_ptr: None,
             as_mut_ptr: None,
         }
@@ -129,6 +140,12 @@ impl SliceVTableBuilder {
         self
     }
 
+    /// Sets the `get` field
+    pub const fn get(mut self, f: SliceGetFn) -> Self {
+        self.get = Some(f);
+        self
+    }
+
     /// Sets the as_ptr field
     pub const fn as_ptr(mut self, f: SliceAsPtrFn) -> Self {
         self.as_ptr = Some(f);
@@ -149,6 +166,7 @@ impl SliceVTableBuilder {
     pub const fn build(self) -> SliceVTable {
         SliceVTable {
             len: self.len.unwrap(),
+            get: self.get.unwrap(),
             as_ptr: self.as_ptr.unwrap(),
             as_mut_ptr: self.as_mut_ptr.unwrap(),
         }
@@ -76,7 +76,87 @@ pub struct Shape {
     /// Same for `Utf8PathBuf`, which is parsed from and serialized to "just a string".
     ///
     /// See Wip's `innermost_shape` function (and its support in `put`).
-    pub inner: Option<fn() -> &'static Shape>,
+    pub inner: Option<ShapeRef>,
+
+    /// Whether values of this shape, or anything in them, have validation functions or
+    /// invariants to check. Worked out by [`ShapeBuilder::build`].
+    pub validators: Validators,
+}
+
+/// Whether there are validation functions or invariants to check for the values of a shape,
+/// or for anything they contain, see [`Shape::validators`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Validators {
+    /// There are none, in the shape or in anything it contains
+    Absent,
+    /// There are some, in the shape or in something it contains
+    Present,
+    /// None were found, but the shape contains shapes that can't be looked at when it's
+    /// built, like the items of a list, which need to be checked at runtime
+    Unknown,
+}
+
+impl Validators {
+    /// Combines what was found for two parts of a shape
+    const fn or(self, other: Validators) -> Validators {
+        match (self, other) {
+            (Validators::Present, _) | (_, Validators::Present) => Validators::Present,
+            (Validators::Unknown, _) | (_, Validators::Unknown) => Validators::Unknown,
+            _ => Validators::Absent,
+        }
+    }
+
+    /// What's found in the given fields: their own validation functions, and their shapes'
+    const fn of_fields(fields: &'static [Field]) -> Validators {
+        let mut found = Validators::Absent;
+        let mut i = 0;
+        while i < fields.len() {
+            if fields[i].vtable.validate.is_some() {
+                return Validators::Present;
+            }
+            found = found.or(fields[i].shape.validators);
+            i += 1;
+        }
+        found
+    }
+}
+
+/// Points to another shape — directly when possible, through a function when the
+/// shape may not be const-evaluated yet because it refers back to this one, as the
+/// pointee of `Box<Node>` can be `Node`.
+#[derive(Clone, Copy)]
+pub enum ShapeRef {
+    /// The shape itself
+    Static(&'static Shape),
+    /// A function returning the shape, for recursive types
+    Lazy(fn() -> &'static Shape),
+}
+
+impl ShapeRef {
+    /// The shape pointed to
+    #[inline]
+    pub fn get(self) -> &'static Shape {
+        match self {
+            ShapeRef::Static(shape) => shape,
+            ShapeRef::Lazy(shape_fn) => shape_fn(),
+        }
+    }
+
+    /// The shape pointed to, if it's available in const contexts
+    #[inline]
+    pub const fn as_static(self) -> Option<&'static Shape> {
+        match self {
+            ShapeRef::Static(shape) => Some(shape),
+            ShapeRef::Lazy(_) => None,
+        }
+    }
+}
+
+impl core::fmt::Debug for ShapeRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Going through `Shape`'s `Debug` would print the whole tree
+        write!(f, "{}", self.get())
+    }
 }
 
 /// Layout of the shape
@@ -127,6 +207,38 @@ pub enum ShapeAttribute {
     RenameAll(&'static str),
     /// Custom field attribute containing arbitrary text
     Arbitrary(&'static str),
+    /// Tags enum variants with their name under this key, next to their fields
+    /// (`#[facet(tag = "type")]`)
+    Tag(&'static str),
+    /// Along with [`ShapeAttribute::Tag`], puts the fields of enum variants under this key
+    /// (`#[facet(tag = "t", content = "c")]`)
+    Content(&'static str),
+    /// Leaves enum variants untagged: only their fields are serialized
+    Untagged,
+    /// Serializes the unit variants of an enum as their discriminant, a number, instead of
+    /// their name (`#[facet(serialize_as = "discriminant")]`)
+    SerializeAsDiscriminant,
+}
+
+/// How the variants of an enum are told apart when serialized
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EnumTagging {
+    /// `{"Variant": fields}`, the default
+    External,
+    /// `{"tag": "Variant", ...fields}`
+    Internal {
+        /// The key of the variant name
+        tag: &'static str,
+    },
+    /// `{"tag": "Variant", "content": fields}`
+    Adjacent {
+        /// The key of the variant name
+        tag: &'static str,
+        /// The key of the fields
+        content: &'static str,
+    },
+    /// `fields`, without the variant name
+    Untagged,
 }
 
 impl Shape {
@@ -180,6 +292,32 @@ impl Shape {
             }
         })
     }
+
+    /// See [`ShapeAttribute::SerializeAsDiscriminant`]
+    pub fn has_serialize_as_discriminant_attr(&'static self) -> bool {
+        self.attributes
+            .contains(&ShapeAttribute::SerializeAsDiscriminant)
+    }
+
+    /// How this enum's variants are tagged, from [`ShapeAttribute::Tag`],
+    /// [`ShapeAttribute::Content`] and [`ShapeAttribute::Untagged`]
+    pub fn enum_tagging(&'static self) -> EnumTagging {
+        let mut tag = None;
+        let mut content = None;
+        for attr in self.attributes {
+            match attr {
+                ShapeAttribute::Untagged => return EnumTagging::Untagged,
+                ShapeAttribute::Tag(name) => tag = Some(*name),
+                ShapeAttribute::Content(name) => content = Some(*name),
+                _ => {}
+            }
+        }
+        match (tag, content) {
+            (Some(tag), Some(content)) => EnumTagging::Adjacent { tag, content },
+            (Some(tag), None) => EnumTagging::Internal { tag },
+            _ => EnumTagging::External,
+        }
+    }
 }
 
 /// Builder for [`Shape`]
@@ -192,7 +330,7 @@ pub struct ShapeBuilder {
     type_params: &'static [TypeParam],
     doc: &'static [&'static str],
     attributes: &'static [ShapeAttribute],
-    inner: Option<fn() -> &'static Shape>,
+    inner: Option<ShapeRef>,
 }
 
 impl ShapeBuilder {
@@ -274,10 +412,18 @@ impl ShapeBuilder {
     /// like a newtype or smart pointer, and should be treated as such for serialization
     /// and deserialization.
     ///
-    /// The function `inner_fn` should return the static shape of the inner type.
+    /// The function `inner_fn` should return the static shape of the inner type. Prefer
+    /// [`Self::inner_static`] unless the inner type may refer back to this one.
     #[inline]
     pub const fn inner(mut self, inner_fn: fn() -> &'static Shape) -> Self {
-        self.inner = Some(inner_fn);
+        self.inner = Some(ShapeRef::Lazy(inner_fn));
+        self
+    }
+
+    /// Like [`Self::inner`], for an inner shape that can be referred to directly
+    #[inline]
+    pub const fn inner_static(mut self, inner: &'static Shape) -> Self {
+        self.inner = Some(ShapeRef::Static(inner));
         self
     }
 
@@ -288,16 +434,49 @@ impl ShapeBuilder {
     /// This method will panic if any of the required fields (`layout`, `vtable`, or `def`) are `None`.
     #[inline]
     pub const fn build(self) -> Shape {
+        let ty = self.ty.unwrap();
         Shape {
             id: self.id.unwrap(),
             layout: self.layout.unwrap(),
             vtable: self.vtable,
             type_params: self.type_params,
             def: self.def,
-            ty: self.ty.unwrap(),
+            ty,
             doc: self.doc,
             attributes: self.attributes,
             inner: self.inner,
+            validators: Self::validators(self.vtable, self.def, ty),
+        }
+    }
+
+    /// Looks for validation functions and invariants, through everything that values of
+    /// the shape contain and that can be looked at in const contexts
+    const fn validators(vtable: &'static ValueVTable, def: Def, ty: Type) -> Validators {
+        if vtable.validate.is_some() || vtable.invariants.is_some() {
+            return Validators::Present;
+        }
+        match def {
+            Def::Option(od) => return od.t.validators,
+            Def::Array(ad) => return ad.t.validators,
+            Def::Slice(sd) => return sd.t.validators,
+            Def::List(_) | Def::Map(_) | Def::Set(_) | Def::SmartPointer(_) => {
+                return Validators::Unknown;
+            }
+            _ => {}
+        }
+        match ty {
+            Type::User(UserType::Struct(st)) => Validators::of_fields(st.fields),
+            Type::User(UserType::Enum(et)) => {
+                let mut found = Validators::Absent;
+                let mut i = 0;
+                while i < et.variants.len() {
+                    found = found.or(Validators::of_fields(et.variants[i].data.fields));
+                    i += 1;
+                }
+                found
+            }
+            Type::Sequence(SequenceType::Tuple(tt)) => Validators::of_fields(tt.fields),
+            _ => Validators::Absent,
         }
     }
 }
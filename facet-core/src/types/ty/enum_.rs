@@ -20,6 +20,18 @@ impl EnumType {
     pub const fn builder() -> EnumDefBuilder {
         EnumDefBuilder::new()
     }
+
+    /// Returns the index of the variant called `name` (its serialized name, after renames), if any
+    pub fn variant_index(&self, name: &str) -> Option<usize> {
+        self.variants.iter().position(|v| v.name == name)
+    }
+
+    /// Returns the index of the variant whose discriminant is `discriminant`, if any
+    pub fn variant_index_for_discriminant(&self, discriminant: i64) -> Option<usize> {
+        self.variants
+            .iter()
+            .position(|v| v.discriminant == Some(discriminant))
+    }
 }
 
 /// Builder for EnumDef
@@ -1,6 +1,6 @@
-use crate::PtrConst;
+use crate::{Facet, PtrConst, PtrMut, PtrUninit};
 
-use super::{DefaultInPlaceFn, Shape};
+use super::{DefaultInPlaceFn, Shape, ValidateFn};
 use bitflags::bitflags;
 
 /// Describes a field in a struct or tuple
@@ -61,12 +61,103 @@ pub struct FieldVTable {
 
     /// Function to get the default value for this field
     pub default_fn: Option<DefaultInPlaceFn>,
+
+    /// Function to validate the value of this field
+    pub validate: Option<ValidateFn>,
+
+    /// What this field is serialized as, instead of its own value
+    pub serialize_with: Option<SerializeWith>,
+
+    /// What this field is deserialized as, instead of its own value
+    pub deserialize_with: Option<DeserializeWith>,
 }
 
 /// A function that, if present, determines whether field should be included in the serialization
 /// step.
 pub type SkipSerializingIfFn = for<'mem> unsafe fn(value: PtrConst<'mem>) -> bool;
 
+/// Function converting a value, of a field or of a shape, to the proxy value it's serialized
+/// as. If the value can't be converted, it writes why to `message` and returns false, leaving
+/// `proxy` uninitialized.
+///
+/// # Safety
+///
+/// `value` must point to an initialized value of the field's type (or of the shape), and
+/// `proxy` to memory suitable for a value of the proxy's shape.
+pub type SerializeWithFn = for<'mem, 'proxy> unsafe fn(
+    value: PtrConst<'mem>,
+    proxy: PtrUninit<'proxy>,
+    message: &mut dyn core::fmt::Write,
+) -> bool;
+
+/// Function converting the proxy value a field, or a value of a shape, was deserialized as to
+/// the value. The proxy is moved out of, whether it succeeds or not; if it fails, it writes why
+/// to `message` and returns false, leaving `value` uninitialized.
+///
+/// # Safety
+///
+/// `proxy` must point to an initialized value of the proxy's shape, and `value` to memory
+/// suitable for a value of the field's type (or of the shape).
+pub type DeserializeWithFn = for<'proxy, 'mem> unsafe fn(
+    proxy: PtrMut<'proxy>,
+    value: PtrUninit<'mem>,
+    message: &mut dyn core::fmt::Write,
+) -> bool;
+
+/// The proxy a value is serialized as, set with `#[facet(serialize_with = ...)]` on a field or
+/// `#[facet(into = ...)]` on a container
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[repr(C)]
+#[non_exhaustive]
+pub struct SerializeWith {
+    /// The shape of the proxy
+    pub shape: &'static Shape,
+
+    /// Converts the value to the proxy
+    pub convert: SerializeWithFn,
+}
+
+impl SerializeWith {
+    /// Creates a new `SerializeWith`
+    pub const fn new(shape: &'static Shape, convert: SerializeWithFn) -> Self {
+        Self { shape, convert }
+    }
+
+    /// Returns the shape of the proxy a conversion function returns
+    pub const fn proxy_shape_of<'facet, T, P: Facet<'facet>, E>(
+        _convert: fn(&T) -> Result<P, E>,
+    ) -> &'static Shape {
+        P::SHAPE
+    }
+}
+
+/// The proxy a value is deserialized as, set with `#[facet(deserialize_with = ...)]` on a
+/// field or `#[facet(from = ...)]` on a container
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[repr(C)]
+#[non_exhaustive]
+pub struct DeserializeWith {
+    /// The shape of the proxy
+    pub shape: &'static Shape,
+
+    /// Converts the proxy to the value
+    pub convert: DeserializeWithFn,
+}
+
+impl DeserializeWith {
+    /// Creates a new `DeserializeWith`
+    pub const fn new(shape: &'static Shape, convert: DeserializeWithFn) -> Self {
+        Self { shape, convert }
+    }
+
+    /// Returns the shape of the proxy a conversion function takes
+    pub const fn proxy_shape_of<'facet, P: Facet<'facet>, T, E>(
+        _convert: fn(P) -> Result<T, E>,
+    ) -> &'static Shape {
+        P::SHAPE
+    }
+}
+
 impl Field {
     /// Returns the shape of the inner type
     pub const fn shape(&self) -> &'static Shape {
@@ -97,6 +188,9 @@ pub enum FieldAttribute {
 pub struct FieldVTableBuilder {
     skip_serializing_if: Option<SkipSerializingIfFn>,
     default_fn: Option<DefaultInPlaceFn>,
+    validate: Option<ValidateFn>,
+    serialize_with: Option<SerializeWith>,
+    deserialize_with: Option<DeserializeWith>,
 }
 
 impl FieldVTableBuilder {
@@ -106,6 +200,9 @@ impl FieldVTableBuilder {
         Self {
             skip_serializing_if: None,
             default_fn: None,
+            validate: None,
+            serialize_with: None,
+            deserialize_with: None,
         }
     }
 
@@ -121,11 +218,32 @@ impl FieldVTableBuilder {
         self
     }
 
+    /// Sets the validate function for the FieldVTable
+    pub const fn validate(mut self, func: ValidateFn) -> Self {
+        self.validate = Some(func);
+        self
+    }
+
+    /// Sets what the field is serialized as for the FieldVTable
+    pub const fn serialize_with(mut self, serialize_with: SerializeWith) -> Self {
+        self.serialize_with = Some(serialize_with);
+        self
+    }
+
+    /// Sets what the field is deserialized as for the FieldVTable
+    pub const fn deserialize_with(mut self, deserialize_with: DeserializeWith) -> Self {
+        self.deserialize_with = Some(deserialize_with);
+        self
+    }
+
     /// Builds the FieldVTable
     pub const fn build(self) -> FieldVTable {
         FieldVTable {
             skip_serializing_if: self.skip_serializing_if,
             default_fn: self.default_fn,
+            validate: self.validate,
+            serialize_with: self.serialize_with,
+            deserialize_with: self.deserialize_with,
         }
     }
 }
@@ -163,6 +281,9 @@ impl FieldBuilder {
                 FieldVTable {
                     skip_serializing_if: None,
                     default_fn: None,
+                    validate: None,
+                    serialize_with: None,
+                    deserialize_with: None,
                 }
             },
         }
@@ -251,6 +372,14 @@ bitflags! {
         /// When deserializing, if this field is missing, use its default value. If
         /// `FieldVTable::default_fn` is set, use that.
         const DEFAULT = 1 << 4;
+
+        /// Flag indicating this field should be ignored during deserialization: it is
+        /// always filled with its default value, as with [`FieldFlags::DEFAULT`].
+        const SKIP_DESERIALIZING = 1 << 5;
+
+        /// Flag indicating this field is a map that deserializers can collect the keys no
+        /// other field matches into, rather than ignoring them
+        const EXTRA = 1 << 6;
     }
 }
 
@@ -13,6 +13,9 @@ pub struct StructType {
 
     /// all fields, in declaration order (not necessarily in memory order)
     pub fields: &'static [Field],
+
+    /// indices into `fields`, sorted by field name — empty if the shape doesn't provide them
+    pub fields_by_name: &'static [usize],
 }
 
 impl StructType {
@@ -20,6 +23,20 @@ impl StructType {
     pub const fn builder() -> StructBuilder {
         StructBuilder::new()
     }
+
+    /// Returns the index of the field called `name`, if any
+    ///
+    /// This is a binary search when the shape comes with `fields_by_name` (as derived ones
+    /// do), and a linear scan otherwise.
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        if self.fields_by_name.len() != self.fields.len() {
+            return self.fields.iter().position(|f| f.name == name);
+        }
+        self.fields_by_name
+            .binary_search_by(|&index| self.fields[index].name.cmp(name))
+            .ok()
+            .map(|found| self.fields_by_name[found])
+    }
 }
 
 /// Builder for StructDef
@@ -27,6 +44,7 @@ pub struct StructBuilder {
     repr: Option<Repr>,
     kind: Option<StructKind>,
     fields: &'static [Field],
+    fields_by_name: &'static [usize],
 }
 
 impl StructBuilder {
@@ -37,6 +55,7 @@ impl StructBuilder {
             repr: None,
             kind: None,
             fields: &[],
+            fields_by_name: &[],
         }
     }
     /// Sets the kind to Unit and returns self
@@ -75,12 +94,19 @@ impl StructBuilder {
         self
     }
 
+    /// Sets the indices of the fields, sorted by field name, for [`StructType::field_index`]
+    pub const fn fields_by_name(mut self, fields_by_name: &'static [usize]) -> Self {
+        self.fields_by_name = fields_by_name;
+        self
+    }
+
     /// Builds the StructDef
     pub const fn build(self) -> StructType {
         StructType {
             repr: self.repr.unwrap(),
             kind: self.kind.unwrap(),
             fields: self.fields,
+            fields_by_name: self.fields_by_name,
         }
     }
 }
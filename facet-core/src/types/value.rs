@@ -7,7 +7,7 @@ use core::{cmp::Ordering, marker::PhantomData, mem};
 
 use crate::Shape;
 
-use super::UnsizedError;
+use super::{DeserializeWith, SerializeWith, UnsizedError};
 
 //======== Type Information ========
 
@@ -70,14 +70,59 @@ impl TypeNameOpts {
 
 //======== Invariants ========
 
-/// Function to validate the invariants of a value. If it returns false, the value is considered invalid.
+/// Function to validate the invariants of a value, like `start <= end`. If they don't hold, it
+/// writes which one doesn't to `message` and returns false.
 ///
 /// # Safety
 ///
 /// The `value` parameter must point to aligned, initialized memory of the correct type.
-pub type InvariantsFn = for<'mem> unsafe fn(value: PtrConst<'mem>) -> bool;
-/// Function to validate the invariants of a value. If it returns false, the value is considered invalid.
-pub type InvariantsFnTyped<T> = fn(value: &T) -> bool;
+pub type InvariantsFn =
+    for<'mem> unsafe fn(value: PtrConst<'mem>, message: &mut dyn core::fmt::Write) -> bool;
+/// Function to validate the invariants of a value, like `start <= end`. If they don't hold, it
+/// writes which one doesn't to `message` and returns false.
+pub type InvariantsFnTyped<T> = fn(value: &T, message: &mut dyn core::fmt::Write) -> bool;
+
+/// What functions given to `#[facet(invariants = ...)]` return: either a `bool`, or a
+/// `Result<(), E>` whose error says which invariant doesn't hold
+pub trait InvariantsOutcome {
+    /// Returns whether the invariants hold, writing why not to `message` otherwise
+    fn holds(self, message: &mut dyn core::fmt::Write) -> bool;
+}
+
+impl InvariantsOutcome for bool {
+    fn holds(self, message: &mut dyn core::fmt::Write) -> bool {
+        if !self {
+            let _ = message.write_str("invariants function returned false");
+        }
+        self
+    }
+}
+
+impl<E: core::fmt::Display> InvariantsOutcome for Result<(), E> {
+    fn holds(self, message: &mut dyn core::fmt::Write) -> bool {
+        match self {
+            Ok(()) => true,
+            Err(e) => {
+                let _ = core::fmt::Write::write_fmt(message, format_args!("{e}"));
+                false
+            }
+        }
+    }
+}
+
+//======== Validation ========
+
+/// Function to validate a value, e.g. that a number is in range. If the value is invalid, it
+/// writes why to `message` and returns false.
+///
+/// # Safety
+///
+/// The `value` parameter must point to aligned, initialized memory of the correct type.
+pub type ValidateFn =
+    for<'mem> unsafe fn(value: PtrConst<'mem>, message: &mut dyn core::fmt::Write) -> bool;
+/// Function to validate a value, e.g. that a number is in range. If the value is invalid, it
+/// writes why to `message` and returns false.
+pub type ValidateFnTyped<T> = fn(value: &T, message: &mut dyn core::fmt::Write) -> bool;
 
 //======== Memory Management ========
 
@@ -429,6 +474,9 @@ bitflags! {
         const COPY = 1 << 3;
         /// Indicates that the type implements the [`Unpin`] marker trait
         const UNPIN = 1 << 4;
+        /// Indicates that the type doesn't implement [`Drop`] itself: dropping it only drops
+        /// what it holds
+        const NO_DROP_IMPL = 1 << 5;
     }
 }
 
@@ -474,6 +522,9 @@ pub struct ValueVTable {
     /// cf. [`InvariantsFn`]
     pub invariants: Option<InvariantsFn>,
 
+    /// cf. [`ValidateFn`]
+    pub validate: Option<ValidateFn>,
+
     /// cf. [`DisplayFn`]
     pub display: Option<DisplayFn>,
 
@@ -524,6 +575,13 @@ pub struct ValueVTable {
     ///
     /// This is used by transparent types to efficiently access the inner value without copying.
     pub try_borrow_inner: Option<TryBorrowInnerFn>,
+
+    /// What values of this shape are serialized as, set with `#[facet(into = ...)]`
+    pub serialize_into: Option<SerializeWith>,
+
+    /// What values of this shape are deserialized as, set with `#[facet(from = ...)]` or
+    /// `#[facet(try_from = ...)]`
+    pub deserialize_from: Option<DeserializeWith>,
 }
 
 impl ValueVTable {
@@ -596,6 +654,14 @@ impl<'a, T: crate::Facet<'a>> VTableView<T> {
         })
     }
 
+    /// cf. [`ValidateFn`]
+    #[inline(always)]
+    pub fn validate(self) -> Option<ValidateFnTyped<T>> {
+        self.0
+            .validate
+            .map(|validate| unsafe { mem::transmute::<ValidateFn, ValidateFnTyped<T>>(validate) })
+    }
+
     /// cf. [`DisplayFn`]
     #[inline(always)]
     pub fn display(self) -> Option<DisplayFnTyped<T>> {
@@ -722,10 +788,13 @@ pub struct ValueVTableBuilder<T> {
     hash: Option<HashFnTyped<T>>,
     drop_in_place: Option<DropInPlaceFn>,
     invariants: Option<InvariantsFnTyped<T>>,
+    validate: Option<ValidateFnTyped<T>>,
     parse: Option<ParseFnTyped<T>>,
     try_from: Option<TryFromFnTyped<T>>,
     try_into_inner: Option<TryIntoInnerFnTyped<T>>,
     try_borrow_inner: Option<TryBorrowInnerFnTyped<T>>,
+    serialize_into: Option<SerializeWith>,
+    deserialize_from: Option<DeserializeWith>,
     _pd: PhantomData<T>,
 }
 
@@ -746,10 +815,13 @@ impl<T> ValueVTableBuilder<T> {
             hash: None,
             drop_in_place: None,
             invariants: None,
+            validate: None,
             parse: None,
             try_from: None,
             try_into_inner: None,
             try_borrow_inner: None,
+            serialize_into: None,
+            deserialize_from: None,
             _pd: PhantomData,
         }
     }
@@ -879,6 +951,12 @@ impl<T> ValueVTableBuilder<T> {
         self
     }
 
+    /// Sets the validate function for this builder.
+    pub const fn validate(mut self, validate: ValidateFnTyped<T>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
     /// Sets the parse function for this builder.
     pub const fn parse(mut self, parse: ParseFnTyped<T>) -> Self {
         self.parse = Some(parse);
@@ -909,6 +987,19 @@ impl<T> ValueVTableBuilder<T> {
         self
     }
 
+    /// Sets what values are serialized as for this builder, cf. [`ValueVTable::serialize_into`].
+    pub const fn serialize_into(mut self, serialize_into: SerializeWith) -> Self {
+        self.serialize_into = Some(serialize_into);
+        self
+    }
+
+    /// Sets what values are deserialized as for this builder, cf.
+    /// [`ValueVTable::deserialize_from`].
+    pub const fn deserialize_from(mut self, deserialize_from: DeserializeWith) -> Self {
+        self.deserialize_from = Some(deserialize_from);
+        self
+    }
+
     /// Builds the [`ValueVTable`] from the current state of the builder.
     pub const fn build(self) -> ValueVTable {
         ValueVTable {
@@ -919,6 +1010,9 @@ impl<T> ValueVTableBuilder<T> {
                     self.invariants,
                 )
             },
+            validate: unsafe {
+                mem::transmute::<Option<ValidateFnTyped<T>>, Option<ValidateFn>>(self.validate)
+            },
             display: unsafe {
                 mem::transmute::<Option<DisplayFnTyped<T>>, Option<DisplayFn>>(self.display)
             },
@@ -959,6 +1053,8 @@ impl<T> ValueVTableBuilder<T> {
                     self.try_borrow_inner,
                 )
             },
+            serialize_into: self.serialize_into,
+            deserialize_from: self.deserialize_from,
             drop_in_place: if let Some(drop_in_place) = self.drop_in_place {
                 Some(drop_in_place)
             } else if mem::needs_drop::<T>() {
@@ -0,0 +1,55 @@
+use core::mem::offset_of;
+
+use facet_core::{Facet, PtrConst, PtrMut, PtrUninit, SequenceType, TupleType, Type};
+
+#[derive(Debug, PartialEq)]
+#[repr(C)]
+struct Pair {
+    left: u8,
+    right: u32,
+}
+
+#[test]
+fn byte_offset_round_trips() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut pair = Pair { left: 1, right: 2 };
+    let base = PtrMut::new(&raw mut pair);
+
+    // An offset found through a const pointer can be applied to a mutable one
+    let right_const = unsafe { base.as_const().field(offset_of!(Pair, right)) };
+    let offset = unsafe { right_const.byte_offset_from(base.as_const()) };
+    assert_eq!(offset, offset_of!(Pair, right) as isize);
+
+    let right = unsafe { base.field(offset as usize) };
+    assert_eq!(unsafe { right.byte_offset_from(base) }, offset);
+    unsafe { *right.as_mut::<u32>() = 42 };
+    assert_eq!(pair, Pair { left: 1, right: 42 });
+
+    Ok(())
+}
+
+#[test]
+fn uninit_fields_keep_provenance() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let Type::Sequence(SequenceType::Tuple(TupleType { fields })) = <(u8, u64)>::SHAPE.ty else {
+        panic!("tuples are tuple sequences");
+    };
+
+    let mut tuple = core::mem::MaybeUninit::<(u8, u64)>::uninit();
+    let base = PtrUninit::from_maybe_uninit(&mut tuple);
+    unsafe {
+        base.field_uninit_at(fields[0].offset).put(7u8);
+        let second = base.field_uninit_at(fields[1].offset);
+        assert_eq!(second.byte_offset_from(base), fields[1].offset as isize);
+        second.put(9u64);
+    }
+    let tuple = unsafe { tuple.assume_init() };
+    assert_eq!(tuple, (7, 9));
+
+    let read = PtrConst::new(&raw const tuple);
+    assert_eq!(unsafe { read.field(fields[1].offset).read::<u64>() }, 9);
+
+    Ok(())
+}
@@ -0,0 +1,392 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use facet_core::{Def, Facet, FieldFlags, Shape, StructKind, StructType, Type, UserType};
+use facet_reflect::{ReflectError, ScalarType, Wip};
+use log::trace;
+
+use crate::{CsvError, CsvErrorKind};
+
+impl From<ReflectError> for CsvErrorKind {
+    fn from(err: ReflectError) -> Self {
+        CsvErrorKind::ReflectError(err)
+    }
+}
+
+/// Deserializes CSV into a type that implements `Facet`.
+///
+/// A list of structs, like a `Vec<Record>`, is read from a header naming their fields and one
+/// row per struct. A single struct is read from the first row, which has its fields in order,
+/// without a header.
+///
+/// Columns that no field matches are ignored, unless the struct has
+/// `#[facet(deny_unknown_fields)]`. Empty values count as missing, except for strings: fields
+/// without one get their default, `None` for options, or fail.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct City {
+///     name: String,
+///     #[facet(rename = "pop")]
+///     population: u32,
+/// }
+///
+/// let cities: Vec<City> =
+///     facet_csv::from_str("name,pop\nLyon,522000\n\"Nantes, France\",320000\n").unwrap();
+/// assert_eq!(cities[1].name, "Nantes, France");
+///
+/// let err = facet_csv::from_str::<Vec<City>>("name,pop\nLyon,many\n").unwrap_err();
+/// assert_eq!(err.line, 2);
+/// ```
+pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input str,
+) -> Result<T, CsvError> {
+    let mut rows = Rows::new(input);
+    let wip = Wip::alloc::<T>().map_err(|err| CsvError::new(1, err.into()))?;
+    let shape = wip.shape();
+
+    let wip = if let Def::List(list_def) = shape.def {
+        let mut wip = wip
+            .begin_pushback()
+            .map_err(|err| CsvError::new(1, err.into()))?;
+        if let Some(header) = rows.next_row() {
+            let header = header?;
+            let columns = columns(list_def.t(), &header.values)
+                .map_err(|kind| CsvError::new(header.line, kind))?;
+            while let Some(row) = rows.next_row() {
+                let row = row?;
+                trace!("Reading row at line {}", row.line);
+                wip = push_record(wip, &columns, row.values)
+                    .map_err(|kind| CsvError::new(row.line, kind))?;
+            }
+        }
+        wip
+    } else {
+        let row = rows
+            .next_row()
+            .unwrap_or(Err(CsvError::new(1, CsvErrorKind::NoRecord)))?;
+        fill_record(wip, &positional_columns(shape), row.values)
+            .map_err(|kind| CsvError::new(row.line, kind))?
+    };
+
+    wip.build()
+        .and_then(|value| value.materialize::<T>())
+        .map_err(|err| CsvError::new(rows.line, err.into()))
+}
+
+/// Reads CSV with a header one record at a time, so that a bad row doesn't stop the others
+/// from being read.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Debug, Facet)]
+/// struct Reading {
+///     sensor: String,
+///     value: f64,
+/// }
+///
+/// let csv = "sensor,value\nA,1.5\nB,oops\nC,2\n";
+/// let (readings, errors): (Vec<_>, Vec<_>) =
+///     facet_csv::records_from_str::<Reading>(csv).partition(Result::is_ok);
+/// assert_eq!(readings.len(), 2);
+/// assert_eq!(errors[0].as_ref().unwrap_err().line, 3);
+/// ```
+pub fn records_from_str<'input, T: Facet<'input>>(input: &'input str) -> Records<'input, T> {
+    Records {
+        rows: Rows::new(input),
+        columns: None,
+        _marker: PhantomData,
+    }
+}
+
+/// The records of CSV with a header, from [`records_from_str`]
+pub struct Records<'input, T> {
+    rows: Rows<'input>,
+    /// The field each column fills, known once the header is read
+    columns: Option<Vec<Option<usize>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'input, T: Facet<'input>> Iterator for Records<'input, T> {
+    type Item = Result<T, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.columns.is_none() {
+            let header = match self.rows.next_row()? {
+                Ok(header) => header,
+                Err(err) => return Some(Err(err)),
+            };
+            match columns(T::SHAPE, &header.values) {
+                Ok(columns) => self.columns = Some(columns),
+                Err(kind) => {
+                    // Rows can't be read without knowing what their columns are
+                    self.rows.finish();
+                    return Some(Err(CsvError::new(header.line, kind)));
+                }
+            }
+        }
+        let columns = self.columns.as_ref().unwrap();
+
+        let row = match self.rows.next_row()? {
+            Ok(row) => row,
+            Err(err) => return Some(Err(err)),
+        };
+        let line = row.line;
+        let record = Wip::alloc::<T>()
+            .map_err(CsvErrorKind::from)
+            .and_then(|wip| fill_record(wip, columns, row.values))
+            .and_then(|wip| Ok(wip.build()?.materialize::<T>()?));
+        Some(record.map_err(|kind| CsvError::new(line, kind)))
+    }
+}
+
+/// Records are structs with named fields
+fn struct_type(shape: &'static Shape) -> Result<StructType, CsvErrorKind> {
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) if struct_type.kind == StructKind::Struct => {
+            Ok(struct_type)
+        }
+        _ => Err(CsvErrorKind::UnsupportedShape(shape)),
+    }
+}
+
+/// Which field each column of the header fills, if any
+fn columns(
+    shape: &'static Shape,
+    header: &[Cow<'_, str>],
+) -> Result<Vec<Option<usize>>, CsvErrorKind> {
+    let struct_type = struct_type(shape)?;
+    header
+        .iter()
+        .map(|name| {
+            let name = name.trim();
+            match struct_type.field_index(name) {
+                Some(index) => Ok(Some(index).filter(|&index| {
+                    !struct_type.fields[index]
+                        .flags
+                        .contains(FieldFlags::SKIP_DESERIALIZING)
+                })),
+                None if shape.has_deny_unknown_fields_attr() => {
+                    Err(CsvErrorKind::UnknownColumn(name.to_string()))
+                }
+                None => Ok(None),
+            }
+        })
+        .collect()
+}
+
+/// Without a header, the columns are the fields that are serialized, in order
+fn positional_columns(shape: &'static Shape) -> Vec<Option<usize>> {
+    let Ok(struct_type) = struct_type(shape) else {
+        return Vec::new();
+    };
+    struct_type
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !field.flags.contains(FieldFlags::SKIP_SERIALIZING))
+        .map(|(index, field)| {
+            (!field.flags.contains(FieldFlags::SKIP_DESERIALIZING)).then_some(index)
+        })
+        .collect()
+}
+
+fn push_record<'facet>(
+    wip: Wip<'facet>,
+    columns: &[Option<usize>],
+    values: Vec<Cow<'facet, str>>,
+) -> Result<Wip<'facet>, CsvErrorKind> {
+    Ok(fill_record(wip.push()?, columns, values)?.pop()?)
+}
+
+/// Fills a struct from the values of a row
+fn fill_record<'facet>(
+    mut wip: Wip<'facet>,
+    columns: &[Option<usize>],
+    values: Vec<Cow<'facet, str>>,
+) -> Result<Wip<'facet>, CsvErrorKind> {
+    let fields = struct_type(wip.shape())?.fields;
+    if values.len() != columns.len() {
+        return Err(CsvErrorKind::WrongLength {
+            expected: columns.len(),
+            found: values.len(),
+        });
+    }
+
+    for (value, column) in values.into_iter().zip(columns) {
+        let Some(index) = *column else {
+            continue;
+        };
+        let field = &fields[index];
+        if value.is_empty() && !is_string(field.shape()) {
+            continue;
+        }
+        wip = put_value(wip.field(index)?, field.name, value)?.pop()?;
+    }
+
+    let wip = wip.fill_defaults()?;
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(CsvErrorKind::MissingField(field.name));
+        }
+    }
+    Ok(wip)
+}
+
+/// Strings take empty values as they are, other types treat them as missing
+fn is_string(shape: &'static Shape) -> bool {
+    matches!(
+        ScalarType::try_from_shape(shape),
+        Some(ScalarType::String | ScalarType::CowStr | ScalarType::Str)
+    )
+}
+
+/// Fills a scalar, a unit variant, or an option of either, from a value
+fn put_value<'facet>(
+    wip: Wip<'facet>,
+    field: &'static str,
+    value: Cow<'facet, str>,
+) -> Result<Wip<'facet>, CsvErrorKind> {
+    let shape = wip.shape();
+    let invalid = |value: &str| CsvErrorKind::InvalidValue {
+        field,
+        value: value.to_string(),
+        shape,
+    };
+
+    let wip = match (shape.def, shape.ty) {
+        (Def::Option(_), _) => put_value(wip.push_some()?, field, value)?.pop()?,
+        (_, Type::User(UserType::Enum(_))) => match wip.find_variant(value.trim()) {
+            Some((index, variant)) if variant.data.kind == StructKind::Unit => {
+                wip.variant(index)?
+            }
+            _ => return Err(invalid(&value)),
+        },
+        (Def::Scalar(_), _) => match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::String) => wip.put(value.into_owned())?,
+            Some(ScalarType::CowStr) => wip.put(value)?,
+            // Only values without escaped quotes can be borrowed
+            Some(ScalarType::Str) => match value {
+                Cow::Borrowed(value) => wip.put(value)?,
+                Cow::Owned(_) => return Err(CsvErrorKind::UnsupportedShape(shape)),
+            },
+            Some(ScalarType::Bool) => match value.trim() {
+                "true" | "1" => wip.put(true)?,
+                "false" | "0" => wip.put(false)?,
+                _ => return Err(invalid(&value)),
+            },
+            _ => wip.parse(value.trim()).map_err(|_| invalid(&value))?,
+        },
+        _ => return Err(CsvErrorKind::UnsupportedShape(shape)),
+    };
+    Ok(wip)
+}
+
+/// A row, and the line it starts on
+struct Row<'input> {
+    line: usize,
+    values: Vec<Cow<'input, str>>,
+}
+
+/// Splits CSV into rows of values. Values can be quoted, with `""` for a quote, and quoted
+/// values can span lines. Blank lines are skipped.
+struct Rows<'input> {
+    input: &'input str,
+    pos: usize,
+    /// The line `pos` is on, from 1
+    line: usize,
+}
+
+impl<'input> Rows<'input> {
+    fn new(input: &'input str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    /// Stops reading, after an error that leaves no way to tell where the next row starts
+    fn finish(&mut self) {
+        self.pos = self.input.len();
+    }
+
+    fn next_row(&mut self) -> Option<Result<Row<'input>, CsvError>> {
+        let rest = &self.input[self.pos..];
+        let blank = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+        self.line += rest[..blank].matches('\n').count();
+        self.pos += blank;
+        if self.pos == self.input.len() {
+            return None;
+        }
+
+        let line = self.line;
+        let mut values = Vec::new();
+        loop {
+            match self.read_value() {
+                Ok(value) => values.push(value),
+                Err(kind) => {
+                    self.finish();
+                    return Some(Err(CsvError::new(line, kind)));
+                }
+            }
+            let rest = &self.input[self.pos..];
+            if rest.starts_with(',') {
+                self.pos += 1;
+            } else if rest.starts_with('\n') || rest.starts_with("\r\n") {
+                self.pos += if rest.starts_with('\n') { 1 } else { 2 };
+                self.line += 1;
+                break;
+            } else if rest.is_empty() {
+                break;
+            } else {
+                self.finish();
+                return Some(Err(CsvError::new(line, CsvErrorKind::TextAfterQuote)));
+            }
+        }
+        Some(Ok(Row { line, values }))
+    }
+
+    fn read_value(&mut self) -> Result<Cow<'input, str>, CsvErrorKind> {
+        let rest = &self.input[self.pos..];
+        let Some(quoted) = rest.strip_prefix('"') else {
+            let mut len = rest.find([',', '\n']).unwrap_or(rest.len());
+            if rest[..len].ends_with('\r') && rest[len..].starts_with('\n') {
+                len -= 1;
+            }
+            self.pos += len;
+            return Ok(Cow::Borrowed(&rest[..len]));
+        };
+
+        let mut escaped = false;
+        let mut len = 0;
+        loop {
+            let Some(quote) = quoted[len..].find('"') else {
+                return Err(CsvErrorKind::UnterminatedQuote);
+            };
+            len += quote;
+            if quoted[len + 1..].starts_with('"') {
+                escaped = true;
+                len += 2;
+            } else {
+                break;
+            }
+        }
+        let value = &quoted[..len];
+        self.line += value.matches('\n').count();
+        // Past the opening quote, the value, and the closing quote
+        self.pos += len + 2;
+        Ok(if escaped {
+            Cow::Owned(value.replace("\"\"", "\""))
+        } else {
+            Cow::Borrowed(value)
+        })
+    }
+}
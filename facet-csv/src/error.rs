@@ -0,0 +1,83 @@
+use alloc::string::String;
+use core::fmt;
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// An error reading CSV, and the line of the input it happened on
+#[derive(Debug)]
+pub struct CsvError {
+    /// The line the row starts on, from 1. The header is line 1, if there's one.
+    pub line: usize,
+    /// What went wrong
+    pub kind: CsvErrorKind,
+}
+
+impl CsvError {
+    pub(crate) fn new(line: usize, kind: CsvErrorKind) -> Self {
+        Self { line, kind }
+    }
+}
+
+/// What went wrong reading CSV
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvErrorKind {
+    /// A quoted value isn't closed before the end of the input
+    UnterminatedQuote,
+    /// Something other than a delimiter or a line break follows a closing quote
+    TextAfterQuote,
+    /// A row has more or fewer values than the header
+    WrongLength {
+        /// How many columns the header has
+        expected: usize,
+        /// How many values the row has
+        found: usize,
+    },
+    /// A column no field matches, in a type with `#[facet(deny_unknown_fields)]`
+    UnknownColumn(String),
+    /// There's no column, or an empty value, for a field without a default
+    MissingField(&'static str),
+    /// A value couldn't be parsed as the type of its field
+    InvalidValue {
+        /// The field being read
+        field: &'static str,
+        /// The value, as it was in the input
+        value: String,
+        /// The type of the field
+        shape: &'static Shape,
+    },
+    /// The input has no row to read a single record from
+    NoRecord,
+    /// Values of this shape can't be read from CSV, e.g. structs with a nested struct
+    UnsupportedShape(&'static Shape),
+    /// Reflection error
+    ReflectError(ReflectError),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            CsvErrorKind::UnterminatedQuote => write!(f, "Unterminated quoted value"),
+            CsvErrorKind::TextAfterQuote => write!(f, "Unexpected text after a quoted value"),
+            CsvErrorKind::WrongLength { expected, found } => {
+                write!(f, "Expected {} values, found {}", expected, found)
+            }
+            CsvErrorKind::UnknownColumn(name) => write!(f, "Unknown column `{}`", name),
+            CsvErrorKind::MissingField(name) => write!(f, "Missing value for `{}`", name),
+            CsvErrorKind::InvalidValue {
+                field,
+                value,
+                shape,
+            } => write!(f, "Invalid {} in `{}`: '{}'", shape, field, value),
+            CsvErrorKind::NoRecord => write!(f, "No row to read"),
+            CsvErrorKind::UnsupportedShape(shape) => {
+                write!(f, "Unsupported shape for CSV: {}", shape)
+            }
+            CsvErrorKind::ReflectError(err) => write!(f, "Reflection error: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for CsvError {}
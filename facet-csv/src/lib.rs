@@ -11,5 +11,12 @@ mod serialize;
 #[cfg(feature = "std")]
 pub use serialize::*;
 
-// mod deserialize;
-// pub use deserialize::*;
+#[cfg(feature = "alloc")]
+mod error;
+#[cfg(feature = "alloc")]
+pub use error::{CsvError, CsvErrorKind};
+
+#[cfg(feature = "alloc")]
+mod deserialize;
+#[cfg(feature = "alloc")]
+pub use deserialize::*;
@@ -3,7 +3,36 @@ use facet_reflect::Peek;
 use facet_serialize::{Serializer, serialize_iterative};
 use std::io::{self, Write};
 
-/// Serializes a value to CSV
+/// Serializes a value to CSV.
+///
+/// A list of structs, like a `Vec<Record>`, is written as a header of their field names
+/// followed by one row per struct. A single struct is written as one row, without a header.
+///
+/// # Panics
+///
+/// If the value isn't a struct of scalars or a list of those. Use [`to_writer`] to get an error
+/// instead.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct City {
+///     name: String,
+///     #[facet(rename = "pop")]
+///     population: u32,
+/// }
+///
+/// let cities = vec![
+///     City { name: "Lyon".to_string(), population: 522_000 },
+///     City { name: "Nantes, France".to_string(), population: 320_000 },
+/// ];
+/// assert_eq!(
+///     facet_csv::to_string(&cities),
+///     "name,pop\nLyon,522000\n\"Nantes, France\",320000\n"
+/// );
+/// ```
 pub fn to_string<'a, T: Facet<'a>>(value: &T) -> String {
     let peek = Peek::new(value);
     let mut output = Vec::new();
@@ -33,16 +62,39 @@ pub fn peek_to_writer<W: Write>(peek: &Peek<'_, '_>, writer: &mut W) -> io::Resu
     serialize_iterative(*peek, &mut serializer)
 }
 
+/// Serializes records as they come to a writer in CSV format: a header of their field names,
+/// then one row per record
+pub fn iter_to_writer<'a, T: Facet<'a> + 'a, W: Write>(
+    records: impl IntoIterator<Item = &'a T>,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut serializer = CsvSerializer::new(writer);
+    serializer.start_array(None)?;
+    for record in records {
+        serialize_iterative(Peek::new(record), &mut serializer)?;
+    }
+    serializer.end_array()
+}
+
 /// A struct to handle the CSV serializer logic
 pub struct CsvSerializer<W> {
     /// Owned writer
     writer: W,
 
-    /// The current position in a row
-    pos: usize,
+    /// Whether the records are in a list, and so come after a header
+    in_list: bool,
+
+    /// Whether a record is being serialized
+    in_record: bool,
+
+    /// The header written before the first record of a list
+    header: Option<Vec<&'static str>>,
 
-    /// Initialized by `start_object`
-    n_fields: usize,
+    /// Field names of the current record
+    names: Vec<&'static str>,
+
+    /// Values of the current record, not yet quoted
+    cells: Vec<String>,
 
     /// Delimeter used to separate values
     delim: &'static [u8],
@@ -50,6 +102,7 @@ pub struct CsvSerializer<W> {
     /// Newline encoding
     newline: &'static [u8],
 }
+
 impl<W> CsvSerializer<W>
 where
     W: Write,
@@ -58,202 +111,201 @@ where
     pub fn new(writer: W) -> Self {
         Self {
             writer,
-            pos: 0,
-            n_fields: 0,
+            in_list: false,
+            in_record: false,
+            header: None,
+            names: Vec::new(),
+            cells: Vec::new(),
             delim: b",",
             newline: b"\n",
         }
     }
 
-    fn set_n_fields(&mut self, n_fields: usize) {
-        self.n_fields = n_fields;
-    }
-
-    /// Conditionally prefix the value with the required delimeter
-    fn start_value(&mut self) -> Result<(), io::Error> {
-        if self.pos == 0 {
-            // no prefix
-            Ok(())
-        } else {
-            self.writer.write_all(self.delim)
+    /// Adds a value to the current record
+    fn push_cell(&mut self, value: String) -> Result<(), io::Error> {
+        if !self.in_record {
+            return Err(not_flat("only structs can be written as CSV rows"));
         }
+        self.cells.push(value);
+        Ok(())
     }
 
-    /// Conditionally suffix the value with the required newline
-    fn end_value(&mut self) -> Result<(), io::Error> {
-        if self.pos == self.n_fields - 1 {
-            // Reset the position to zero
-            self.pos = 0;
-            self.writer.write_all(self.newline)
-        } else {
-            // Increment the position
-            self.pos += 1;
-            // no suffix
-            Ok(())
+    /// Writes a line of values, quoting those that need it
+    fn write_row<'s>(&mut self, values: impl Iterator<Item = &'s str>) -> Result<(), io::Error> {
+        for (index, value) in values.enumerate() {
+            if index > 0 {
+                self.writer.write_all(self.delim)?;
+            }
+            if needs_quotes(value, self.delim) {
+                write!(self.writer, "\"{}\"", value.replace('"', "\"\""))?;
+            } else {
+                self.writer.write_all(value.as_bytes())?;
+            }
         }
+        self.writer.write_all(self.newline)
     }
 }
 
+/// Values holding the delimiter, a quote or a line break are quoted
+fn needs_quotes(value: &str, delim: &[u8]) -> bool {
+    value
+        .bytes()
+        .any(|b| b == b'"' || b == b'\n' || b == b'\r' || delim.contains(&b))
+}
+
+fn not_flat(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
 impl<W> Serializer for CsvSerializer<W>
 where
     W: Write,
 {
     type Error = io::Error;
 
-    fn start_object(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_n_fields(len.expect("Must know the length of the object for CSV"));
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        if self.in_record {
+            return Err(not_flat("CSV cells can't hold structs"));
+        }
+        self.in_record = true;
+        self.names.clear();
+        self.cells.clear();
         Ok(())
     }
 
     fn end_object(&mut self) -> Result<(), Self::Error> {
-        Ok(())
+        self.in_record = false;
+        if self.in_list {
+            match &self.header {
+                Some(header) if *header != self.names => {
+                    return Err(not_flat("a record has different fields than the header"));
+                }
+                Some(_) => {}
+                None => {
+                    let names = core::mem::take(&mut self.names);
+                    self.write_row(names.iter().copied())?;
+                    self.header = Some(names);
+                }
+            }
+        }
+        let cells = core::mem::take(&mut self.cells);
+        self.write_row(cells.iter().map(String::as_str))
     }
 
     fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        unimplemented!("Arrays are not implemented yet in CSV")
+        if self.in_record || self.in_list {
+            return Err(not_flat("CSV cells can't hold lists"));
+        }
+        self.in_list = true;
+        Ok(())
     }
 
     fn end_array(&mut self) -> Result<(), Self::Error> {
-        unimplemented!("Arrays are not implemented yet in CSV")
+        self.in_list = false;
+        Ok(())
     }
 
     fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        unimplemented!("Maps are not implemented yet in CSV")
+        Err(not_flat("CSV cells can't hold maps"))
     }
 
     fn end_map(&mut self) -> Result<(), Self::Error> {
-        unimplemented!("Maps are not implemented yet in CSV")
+        Err(not_flat("CSV cells can't hold maps"))
     }
 
-    fn serialize_field_name(&mut self, _name: &'static str) -> Result<(), Self::Error> {
-        // field names are not serialized in CSV
+    fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        // field names only go in the header
+        self.names.push(name);
         Ok(())
     }
 
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &'static str,
+        variant_name: &'static str,
     ) -> Result<(), Self::Error> {
-        // unit variants should not serialize to anything
-        Ok(())
+        self.push_cell(variant_name.to_string())
     }
 
     fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_u16(&mut self, value: u16) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_u32(&mut self, value: u32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_usize(&mut self, value: usize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_i8(&mut self, value: i8) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_i16(&mut self, value: i16) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_i32(&mut self, value: i32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_isize(&mut self, value: isize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", if value { "true" } else { "false" })?;
-        self.end_value()
+        self.push_cell(if value { "true" } else { "false" }.to_string())
     }
 
     fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.push_cell(value.to_string())
     }
 
     fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
-        panic!("CSV does not support byte arrays")
+        Err(not_flat("CSV does not support byte arrays"))
     }
 
     fn serialize_none(&mut self) -> Result<(), Self::Error> {
-        self.start_value()?;
-        // skip empty columns
-        self.end_value()
+        // empty columns
+        self.push_cell(String::new())
     }
 
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
-        self.start_value()?;
-        // skip empty columns
-        self.end_value()
+        // empty columns
+        self.push_cell(String::new())
     }
 }
@@ -0,0 +1,152 @@
+use eyre::Result;
+use facet::Facet;
+use facet_csv::{CsvErrorKind, from_str, records_from_str};
+
+#[derive(Debug, PartialEq, Facet)]
+#[facet(rename_all = "camelCase")]
+struct Employee {
+    full_name: String,
+    age: u8,
+    #[facet(rename = "team")]
+    department: Department,
+    manager: Option<String>,
+    #[facet(default)]
+    remote: bool,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+#[repr(u8)]
+enum Department {
+    Sales,
+    Engineering,
+}
+
+#[test]
+fn test_reading_records() -> Result<()> {
+    facet_testhelpers::setup();
+
+    // Columns in any order, quoted values, CRLF line endings and unknown columns
+    let csv = "age,team,fullName,manager,badge\r\n\
+               34,Sales,\"Doe, Jane\",,17\r\n\
+               \r\n\
+               29,Engineering,\"Roe \"\"Rick\"\"\nJunior\",Jane,18\r\n";
+    let employees: Vec<Employee> = from_str(csv)?;
+    assert_eq!(
+        employees,
+        [
+            Employee {
+                full_name: "Doe, Jane".to_string(),
+                age: 34,
+                department: Department::Sales,
+                manager: None,
+                remote: false,
+            },
+            Employee {
+                full_name: "Roe \"Rick\"\nJunior".to_string(),
+                age: 29,
+                department: Department::Engineering,
+                manager: Some("Jane".to_string()),
+                remote: false,
+            },
+        ]
+    );
+
+    assert_eq!(from_str::<Vec<Employee>>("")?, []);
+
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let employees = vec![Employee {
+        full_name: "Al \"Big\" Bo".to_string(),
+        age: 51,
+        department: Department::Engineering,
+        manager: Some(String::new()),
+        remote: true,
+    }];
+    let csv = facet_csv::to_string(&employees);
+    let read: Vec<Employee> = from_str(&csv)?;
+    // An empty string and `None` look the same
+    assert_eq!(read[0].full_name, employees[0].full_name);
+    assert_eq!(read[0].manager, None);
+    assert!(read[0].remote);
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    let point = Point { x: -3, y: 4 };
+    assert_eq!(from_str::<Point>(&facet_csv::to_string(&point))?, point);
+
+    Ok(())
+}
+
+#[test]
+fn test_errors_have_line_numbers() {
+    facet_testhelpers::setup();
+
+    let csv =
+        "fullName,age,team\nAda,36,Engineering\nBob,old,Sales\n\"Cy\nCarter\",40,Legal\nDee,22\n";
+    let results: Vec<_> = records_from_str::<Employee>(csv).collect();
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_ok());
+
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!(err.line, 3);
+    assert!(matches!(
+        &err.kind,
+        CsvErrorKind::InvalidValue { field: "age", value, .. } if value == "old"
+    ));
+
+    // The row started on line 4, and spans two
+    let err = results[2].as_ref().unwrap_err();
+    assert_eq!(err.line, 4);
+    assert!(matches!(
+        err.kind,
+        CsvErrorKind::InvalidValue { field: "team", .. }
+    ));
+
+    let err = results[3].as_ref().unwrap_err();
+    assert_eq!(err.line, 6);
+    assert!(matches!(
+        err.kind,
+        CsvErrorKind::WrongLength {
+            expected: 3,
+            found: 2
+        }
+    ));
+
+    // `from_str` stops at the first one
+    let err = from_str::<Vec<Employee>>(csv).unwrap_err();
+    assert_eq!(err.line, 3);
+
+    let err = from_str::<Vec<Employee>>("fullName,team\nAda,Sales\n").unwrap_err();
+    assert_eq!(err.line, 2);
+    assert!(matches!(err.kind, CsvErrorKind::MissingField("age")));
+
+    let err = from_str::<Vec<Employee>>("fullName,age,team\n\"Ada,36,Sales\n").unwrap_err();
+    assert!(matches!(err.kind, CsvErrorKind::UnterminatedQuote));
+}
+
+#[test]
+fn test_deny_unknown_columns() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        id: u32,
+    }
+
+    let err = from_str::<Vec<Strict>>("id,extra\n1,2\n").unwrap_err();
+    assert_eq!(err.line, 1);
+    assert!(matches!(err.kind, CsvErrorKind::UnknownColumn(ref name) if name == "extra"));
+
+    let results: Vec<_> = records_from_str::<Strict>("id,extra\n1,2\n").collect();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
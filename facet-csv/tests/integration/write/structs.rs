@@ -17,3 +17,70 @@ fn test_writing_flat_structs() {
     });
     assert_eq!(expected_mystruct, actual);
 }
+
+#[test]
+fn test_writing_lists_with_header() {
+    facet_testhelpers::setup();
+
+    #[derive(facet::Facet)]
+    #[facet(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct Row {
+        id: u32,
+        #[facet(rename = "label")]
+        name: String,
+        score: Option<f64>,
+        level: Level,
+    }
+
+    #[derive(facet::Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    let rows = vec![
+        Row {
+            id: 1,
+            name: "plain".to_string(),
+            score: Some(0.5),
+            level: Level::Low,
+        },
+        Row {
+            id: 2,
+            name: "with \"quotes\", commas\nand lines".to_string(),
+            score: None,
+            level: Level::High,
+        },
+    ];
+
+    let expected = "ID,label,SCORE,LEVEL\n1,plain,0.5,Low\n2,\"with \"\"quotes\"\", commas\nand lines\",,High\n";
+    assert_eq!(facet_csv::to_string(&rows), expected);
+
+    let mut output = Vec::new();
+    facet_csv::iter_to_writer(rows.iter(), &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+}
+
+#[test]
+fn test_writing_nested_values_fails() {
+    facet_testhelpers::setup();
+
+    #[derive(facet::Facet)]
+    struct Nested {
+        id: u32,
+        tags: Vec<String>,
+    }
+
+    let mut output = Vec::new();
+    let err = facet_csv::to_writer(
+        &vec![Nested {
+            id: 1,
+            tags: vec!["a".to_string()],
+        }],
+        &mut output,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -1,5 +1,5 @@
 use crate::{BoundedGenericParams, RenameRule};
-use facet_derive_parse::{Ident, ReprInner, ToTokens, TokenStream};
+use facet_derive_parse::{Ident, ReprInner, ToTokens, TokenStream, TokenTree};
 use quote::quote;
 
 /// For struct fields, they can either be identifiers (`my_struct.foo`)
@@ -41,6 +41,11 @@ pub enum PFacetAttr {
     /// etc. — when you're doing the newtype pattern. `de/ser` is forwarded.
     Transparent,
 
+    /// Valid in container
+    /// `#[facet(lenses)]` — generates `Self::fields()`, with a typed lens onto each
+    /// field of the struct
+    Lenses,
+
     /// Valid in field
     /// `#[facet(flatten)]` — flattens a field's contents
     /// into the parent structure.
@@ -50,9 +55,16 @@ pub enum PFacetAttr {
     /// `#[facet(child)]` — marks a field as child node in a hierarchy
     Child,
 
+    /// Valid in field
+    /// `#[facet(extra)]` — a map that keys without a matching field are collected into,
+    /// when deserializers are asked to. Missing, it's filled with its default value.
+    Extra,
+
     /// Valid in container
-    /// `#[facet(invariants = "Self::invariants_func")]` — returns a bool, is called
-    /// when doing `Wip::build`
+    /// `#[facet(invariants = Self::invariants_func)]` — returns a bool, or a `Result<(), E>`
+    /// whose error says which invariant doesn't hold: `Wip::build` fails with
+    /// `ReflectError::InvariantViolated` when they don't. The function can also be given as a
+    /// string, `"Self::invariants_func"`.
     Invariants { expr: TokenStream },
 
     /// Valid in container
@@ -77,13 +89,70 @@ pub enum PFacetAttr {
     /// `#[facet(rename_all = "rule")]` — rename all fields following a rule
     RenameAll { rule: RenameRule },
 
+    /// Valid in enum container
+    /// `#[facet(tag = "type")]` — the key of the variant name, next to the variant's fields
+    Tag { name: String },
+
+    /// Valid in enum container, along with `tag`
+    /// `#[facet(content = "data")]` — the key of the variant's fields
+    Content { name: String },
+
+    /// Valid in enum container
+    /// `#[facet(untagged)]` — serialize variants without their name
+    Untagged,
+
+    /// Valid in enum container
+    /// `#[facet(serialize_as = "discriminant")]` — serialize unit variants as their
+    /// discriminant, a number, instead of their name
+    SerializeAsDiscriminant,
+
+    /// Valid in field
+    /// `#[facet(skip)]` — skip serializing and deserializing this field: when deserializing,
+    /// it's always filled with its default value. Like serde.
+    Skip,
+
     /// Valid in field, enum variant, or container
     /// `#[facet(skip_serializing)]` — skip serializing this field. Like serde.
     SkipSerializing,
 
     /// Valid in field, enum variant, or container
-    /// `#[facet(skip_serializing_if = "func")]` — skip serializing if the function returns true.
+    /// `#[facet(skip_serializing_if = func)]` — skip serializing if the function returns true.
+    /// The function can also be given as a string, `"func"`, like serde.
     SkipSerializingIf { expr: TokenStream },
+
+    /// Valid in field or container
+    /// `#[facet(validate = func)]` — `func` takes the value by reference and returns a
+    /// `Result<(), E>`, `E` being the message (anything `Display`) of the error `Wip::build`
+    /// fails with. The function can also be given as a string, `"func"`.
+    Validate { expr: TokenStream },
+
+    /// Valid in field
+    /// `#[facet(serialize_with = func)]` — `func` takes the field by reference and returns a
+    /// `Result<P, E>`: the field is serialized as the `P` it returns instead, `E` being the
+    /// message (anything `Display`) of the error serializing fails with otherwise.
+    /// `#[facet(with = module)]` stands for `serialize_with = module::serialize` and
+    /// `deserialize_with = module::deserialize`. Both can also be given as strings.
+    SerializeWith { expr: TokenStream },
+
+    /// Valid in field
+    /// `#[facet(deserialize_with = func)]` — `func` takes a `P` and returns a `Result<T, E>`,
+    /// `T` being the field's type: the field is deserialized as a `P` and converted with it.
+    DeserializeWith { expr: TokenStream },
+
+    /// Valid in container
+    /// `#[facet(from = Type)]` — deserialize as a `Type`, then convert with `From<Type>`.
+    /// Like serde, the type can also be given as a string, which it must be if it has commas.
+    From { ty: TokenStream },
+
+    /// Valid in container
+    /// `#[facet(try_from = Type)]` — deserialize as a `Type`, then convert with `TryFrom<Type>`,
+    /// whose error (anything `Display`) deserializing fails with otherwise.
+    TryFrom { ty: TokenStream },
+
+    /// Valid in container
+    /// `#[facet(into = Type)]` — convert a clone of the value with `Into<Type>`, then
+    /// serialize the `Type`. Needs the container to be `Clone`.
+    Into { ty: TokenStream },
 }
 
 impl PFacetAttr {
@@ -102,11 +171,14 @@ impl PFacetAttr {
                 FacetInner::Opaque(_) => dest.push(PFacetAttr::Opaque),
                 FacetInner::Flatten(_) => dest.push(PFacetAttr::Flatten),
                 FacetInner::Child(_) => dest.push(PFacetAttr::Child),
+                FacetInner::Extra(_) => dest.push(PFacetAttr::Extra),
                 FacetInner::Transparent(_) => dest.push(PFacetAttr::Transparent),
+                FacetInner::Lenses(_) => dest.push(PFacetAttr::Lenses),
 
-                FacetInner::Invariants(invariant) => {
-                    let expr = invariant.expr.to_token_stream();
-                    dest.push(PFacetAttr::Invariants { expr });
+                FacetInner::Invariants(invariants) => {
+                    dest.push(PFacetAttr::Invariants {
+                        expr: unquote_expr(invariants.expr.to_token_stream()),
+                    });
                 }
                 FacetInner::DenyUnknownFields(_) => dest.push(PFacetAttr::DenyUnknownFields),
                 FacetInner::DefaultEquals(default_equals) => dest.push(PFacetAttr::DefaultEquals {
@@ -129,19 +201,81 @@ impl PFacetAttr {
                         content: tt.tokens_to_string(),
                     });
                 }
+                FacetInner::Skip(_) => dest.push(PFacetAttr::Skip),
+                FacetInner::Tag(tag) => dest.push(PFacetAttr::Tag {
+                    name: tag.value.as_str().to_string(),
+                }),
+                FacetInner::Content(content) => dest.push(PFacetAttr::Content {
+                    name: content.value.as_str().to_string(),
+                }),
+                FacetInner::Untagged(_) => dest.push(PFacetAttr::Untagged),
+                FacetInner::SerializeAs(serialize_as) => match serialize_as.value.as_str() {
+                    "discriminant" => dest.push(PFacetAttr::SerializeAsDiscriminant),
+                    other => panic!(
+                        "Unknown #[facet(serialize_as = ...)] value: {other} (expected \"discriminant\")"
+                    ),
+                },
                 FacetInner::SkipSerializing(_) => {
                     dest.push(PFacetAttr::SkipSerializing);
                 }
                 FacetInner::SkipSerializingIf(skip_if) => {
                     dest.push(PFacetAttr::SkipSerializingIf {
-                        expr: skip_if.expr.to_token_stream(),
+                        expr: unquote_expr(skip_if.expr.to_token_stream()),
+                    });
+                }
+                FacetInner::Validate(validate) => {
+                    dest.push(PFacetAttr::Validate {
+                        expr: unquote_expr(validate.expr.to_token_stream()),
+                    });
+                }
+                FacetInner::With(with) => {
+                    let module = unquote_expr(with.expr.to_token_stream());
+                    dest.push(PFacetAttr::SerializeWith {
+                        expr: quote! { #module::serialize },
+                    });
+                    dest.push(PFacetAttr::DeserializeWith {
+                        expr: quote! { #module::deserialize },
+                    });
+                }
+                FacetInner::SerializeWith(serialize_with) => {
+                    dest.push(PFacetAttr::SerializeWith {
+                        expr: unquote_expr(serialize_with.expr.to_token_stream()),
+                    });
+                }
+                FacetInner::DeserializeWith(deserialize_with) => {
+                    dest.push(PFacetAttr::DeserializeWith {
+                        expr: unquote_expr(deserialize_with.expr.to_token_stream()),
                     });
                 }
+                FacetInner::From(from) => dest.push(PFacetAttr::From {
+                    ty: unquote_expr(from.ty.to_token_stream()),
+                }),
+                FacetInner::TryFrom(try_from) => dest.push(PFacetAttr::TryFrom {
+                    ty: unquote_expr(try_from.ty.to_token_stream()),
+                }),
+                FacetInner::Into(into) => dest.push(PFacetAttr::Into {
+                    ty: unquote_expr(into.ty.to_token_stream()),
+                }),
             }
         }
     }
 }
 
+/// Takes an attribute's expression as is or, like serde, inside a string literal:
+/// `Option::is_none` and `"Option::is_none"` are the same.
+fn unquote_expr(expr: TokenStream) -> TokenStream {
+    let mut tokens = expr.clone().into_iter();
+    if let (Some(TokenTree::Literal(literal)), None) = (tokens.next(), tokens.next()) {
+        let literal = literal.to_string();
+        if let Some(inner) = literal.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            return inner
+                .parse()
+                .unwrap_or_else(|_| panic!("Not a valid expression: {literal}"));
+        }
+    }
+    expr
+}
+
 /// Parsed attr
 pub enum PAttr {
     /// A single line of doc comments
@@ -440,6 +574,12 @@ impl PAttrs {
             .iter()
             .any(|attr| matches!(attr, PFacetAttr::Transparent))
     }
+
+    pub(crate) fn has_lenses(&self) -> bool {
+        self.facet
+            .iter()
+            .any(|attr| matches!(attr, PFacetAttr::Lenses))
+    }
 }
 
 /// Parsed container
@@ -2,7 +2,9 @@ use super::*;
 // Import PRepr, PrimitiveRepr, PStructField, etc. from parsed module
 use crate::{
     parsed::{IdentOrLiteral, PFacetAttr, PRepr, PVariantKind, PrimitiveRepr},
-    process_struct::gen_field_from_pfield,
+    process_struct::{
+        gen_container_checks, gen_container_conversions, gen_field_from_pfield, gen_fields_by_name,
+    },
 };
 use quote::{format_ident, quote};
 
@@ -40,12 +42,39 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                     let rule_str = rule.apply(""); // Hack to get str - improve RenameRule display
                     attribute_tokens.push(quote! { ::facet::ShapeAttribute::RenameAll(#rule_str) });
                 }
-                PFacetAttr::Invariants { .. } => {
-                    // Note: Facet vtable does not currently support invariants directly on enums
-                    // Maybe panic or warn here? For now, ignoring.
-                    panic!("Invariants are not supported on enums")
+                PFacetAttr::Lenses => panic!("Lenses are only generated for structs"),
+                PFacetAttr::Tag { name } => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Tag(#name) });
                 }
-                // Opaque, Transparent, SkipSerializing/If, Default/Equals are not relevant/valid for enum containers.
+                PFacetAttr::Content { name } => {
+                    let has_tag = pe
+                        .container
+                        .attrs
+                        .facet
+                        .iter()
+                        .any(|attr| matches!(attr, PFacetAttr::Tag { .. }));
+                    if !has_tag {
+                        panic!("#[facet(content = ...)] needs a #[facet(tag = ...)] too");
+                    }
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Content(#name) });
+                }
+                PFacetAttr::Untagged => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Untagged });
+                }
+                PFacetAttr::SerializeAsDiscriminant => {
+                    if pe
+                        .variants
+                        .iter()
+                        .any(|pv| !matches!(pv.kind, PVariantKind::Unit))
+                    {
+                        panic!(
+                            "#[facet(serialize_as = \"discriminant\")] needs all variants to be unit variants"
+                        );
+                    }
+                    attribute_tokens
+                        .push(quote! { ::facet::ShapeAttribute::SerializeAsDiscriminant });
+                }
+                // Opaque, Transparent, Skip, SkipSerializing/If, Default/Equals are not relevant/valid for enum containers.
                 _ => {}
             }
         }
@@ -57,6 +86,29 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
         }
     };
 
+    // Validation functions, invariants and conversions from PEnum
+    let vtable = {
+        let mut checks = gen_container_checks(&pe.container.attrs.facet);
+        checks.extend(gen_container_conversions(&pe.container.attrs.facet));
+        let vtable = quote! {
+            ::facet::value_vtable!(
+                Self,
+                |f, _opts| ::core::fmt::Write::write_str(f, #enum_name_str)
+            )
+        };
+        if checks.is_empty() {
+            vtable
+        } else {
+            quote! {
+                {
+                    let mut vtable = #vtable;
+                    #checks
+                    vtable
+                }
+            }
+        }
+    };
+
     // Determine enum repr (already resolved by PEnum::parse())
     let valid_repr = &pe.repr;
 
@@ -317,6 +369,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                             })
                             .collect();
 
+                        let fields_by_name = gen_fields_by_name(fields);
                         exprs.push(quote! {{
                             let fields: &'static [::facet::Field] = &const {[
                                 #(#field_defs),*
@@ -324,7 +377,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                             ::facet::Variant::builder()
                                 #variant_attrs_tokens
                                 .discriminant(#discriminant_ts)
-                                .data(::facet::StructType::builder().repr(::facet::Repr::c()).struct_().fields(fields).build())
+                                .data(::facet::StructType::builder().repr(::facet::Repr::c()).struct_().fields(fields) #fields_by_name .build())
                                 #maybe_doc
                                 .build()
                         }});
@@ -521,6 +574,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                 gen_field_from_pfield(pf, &shadow_struct_name, &facet_bgp, None)
                             })
                             .collect();
+                        let fields_by_name = gen_fields_by_name(fields);
                         exprs.push(quote! {{
                             let fields: &'static [::facet::Field] = &const {[
                                 #(#field_defs),*
@@ -528,7 +582,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                             ::facet::Variant::builder()
                                 #variant_attrs_tokens
                                 .discriminant(#discriminant_ts)
-                                .data(::facet::StructType::builder().repr(::facet::Repr::c()).struct_().fields(fields).build())
+                                .data(::facet::StructType::builder().repr(::facet::Repr::c()).struct_().fields(fields) #fields_by_name .build())
                                 #maybe_doc
                                 .build()
                         }});
@@ -571,10 +625,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
         #[automatically_derived]
         #[allow(non_camel_case_types)]
         unsafe impl #bgp_def ::facet::Facet<'__facet> for #enum_name #bgp_without_bounds #where_clauses_tokens {
-            const VTABLE: &'static ::facet::ValueVTable = &const { ::facet::value_vtable!(
-                Self,
-                |f, _opts| ::core::fmt::Write::write_str(f, #enum_name_str)
-            )};
+            const VTABLE: &'static ::facet::ValueVTable = &const { #vtable };
 
             const SHAPE: &'static ::facet::Shape = &const {
                 #(#shadow_struct_defs)*
@@ -23,6 +23,7 @@ pub(crate) fn gen_field_from_pfield(
     let doc_lines: Vec<TokenStream> = field.attrs.doc.iter().map(|doc| quote!(#doc)).collect();
     let mut shape_of = quote! { shape_of };
     let mut asserts: Vec<TokenStream> = vec![];
+    let mut validate_fns: Vec<&TokenStream> = vec![];
 
     // Process attributes other than rename rules, which are handled by PName
     for attr in &field.attrs.facet {
@@ -68,6 +69,20 @@ pub(crate) fn gen_field_from_pfield(
                     flags = quote! { #flags.union(::facet::FieldFlags::CHILD) };
                 }
             }
+            PFacetAttr::Extra => {
+                let extra_flags = quote! {
+                    ::facet::FieldFlags::EXTRA.union(::facet::FieldFlags::DEFAULT)
+                };
+                if flags_empty {
+                    flags_empty = false;
+                    flags = extra_flags;
+                } else {
+                    flags = quote! { #flags.union(#extra_flags) };
+                }
+                asserts.push(quote! {
+                    ::facet::static_assertions::assert_impl_all!(#field_type: ::core::default::Default);
+                })
+            }
             PFacetAttr::Flatten => {
                 if flags_empty {
                     flags_empty = false;
@@ -82,6 +97,30 @@ pub(crate) fn gen_field_from_pfield(
             PFacetAttr::Arbitrary { content } => {
                 attribute_list.push(quote! { ::facet::FieldAttribute::Arbitrary(#content) });
             }
+            PFacetAttr::Skip => {
+                let skip_flags = quote! {
+                    ::facet::FieldFlags::SKIP_SERIALIZING
+                        .union(::facet::FieldFlags::SKIP_DESERIALIZING)
+                        .union(::facet::FieldFlags::DEFAULT)
+                };
+                if flags_empty {
+                    flags_empty = false;
+                    flags = skip_flags;
+                } else {
+                    flags = quote! { #flags.union(#skip_flags) };
+                }
+                // `#[facet(default = ...)]` provides the value otherwise
+                let has_default_fn = field
+                    .attrs
+                    .facet
+                    .iter()
+                    .any(|attr| matches!(attr, PFacetAttr::DefaultEquals { .. }));
+                if !has_default_fn {
+                    asserts.push(quote! {
+                        ::facet::static_assertions::assert_impl_all!(#field_type: ::core::default::Default);
+                    })
+                }
+            }
             PFacetAttr::SkipSerializing => {
                 if flags_empty {
                     flags_empty = false;
@@ -97,14 +136,84 @@ pub(crate) fn gen_field_from_pfield(
                     .skip_serializing_if(unsafe { ::std::mem::transmute((#predicate) as fn(&#field_ty) -> bool) })
                 });
             }
+            PFacetAttr::Validate { expr } => validate_fns.push(expr),
+            PFacetAttr::SerializeWith { expr } => {
+                vtable_items.push(quote! {
+                    .serialize_with(::facet::SerializeWith::new(
+                        ::facet::SerializeWith::proxy_shape_of(#expr),
+                        {
+                            let convert: ::facet::SerializeWithFn =
+                                |value: ::facet::PtrConst<'_>,
+                                 proxy: ::facet::PtrUninit<'_>,
+                                 message: &mut dyn ::core::fmt::Write| {
+                                    match (#expr)(unsafe { value.get::<#field_type>() }) {
+                                        ::core::result::Result::Ok(p) => {
+                                            unsafe { proxy.put(p) };
+                                            true
+                                        }
+                                        ::core::result::Result::Err(e) => {
+                                            let _ = ::core::fmt::Write::write_fmt(
+                                                message,
+                                                ::core::format_args!("{}", e),
+                                            );
+                                            false
+                                        }
+                                    }
+                                };
+                            convert
+                        },
+                    ))
+                });
+            }
+            PFacetAttr::DeserializeWith { expr } => {
+                vtable_items.push(quote! {
+                    .deserialize_with(::facet::DeserializeWith::new(
+                        ::facet::DeserializeWith::proxy_shape_of(#expr),
+                        {
+                            let convert: ::facet::DeserializeWithFn =
+                                |proxy: ::facet::PtrMut<'_>,
+                                 value: ::facet::PtrUninit<'_>,
+                                 message: &mut dyn ::core::fmt::Write| {
+                                    match (#expr)(unsafe { proxy.read() }) {
+                                        ::core::result::Result::Ok(v) => {
+                                            unsafe { value.put::<#field_type>(v) };
+                                            true
+                                        }
+                                        ::core::result::Result::Err(e) => {
+                                            let _ = ::core::fmt::Write::write_fmt(
+                                                message,
+                                                ::core::format_args!("{}", e),
+                                            );
+                                            false
+                                        }
+                                    }
+                                };
+                            convert
+                        },
+                    ))
+                });
+            }
             // These are handled by PName or are container-level, so ignore them for field attributes.
             PFacetAttr::RenameAll { .. } => {} // Explicitly ignore rename attributes here
             PFacetAttr::Transparent
+            | PFacetAttr::Lenses
             | PFacetAttr::Invariants { .. }
-            | PFacetAttr::DenyUnknownFields => {}
+            | PFacetAttr::From { .. }
+            | PFacetAttr::TryFrom { .. }
+            | PFacetAttr::Into { .. }
+            | PFacetAttr::DenyUnknownFields
+            | PFacetAttr::Tag { .. }
+            | PFacetAttr::Content { .. }
+            | PFacetAttr::Untagged
+            | PFacetAttr::SerializeAsDiscriminant => {}
         }
     }
 
+    if !validate_fns.is_empty() {
+        let validate = gen_validate_fn(field_type, &validate_fns);
+        vtable_items.push(quote! { .validate(#validate) });
+    }
+
     let maybe_attributes = if attribute_list.is_empty() {
         quote! {}
     } else {
@@ -163,6 +272,159 @@ pub(crate) fn gen_field_from_pfield(
     }
 }
 
+/// Generates a `::facet::ValidateFn` for values of type `ty`, calling each of `validate_fns`
+/// in turn until one returns an error
+pub(crate) fn gen_validate_fn(ty: &TokenStream, validate_fns: &[&TokenStream]) -> TokenStream {
+    quote! {
+        {
+            let validate: ::facet::ValidateFn =
+                |value: ::facet::PtrConst<'_>, message: &mut dyn ::core::fmt::Write| {
+                    let value = unsafe { value.get::<#ty>() };
+                    #(
+                        if let ::core::result::Result::Err(e) = (#validate_fns)(value) {
+                            let _ = ::core::fmt::Write::write_fmt(
+                                message,
+                                ::core::format_args!("{}", e),
+                            );
+                            return false;
+                        }
+                    )*
+                    true
+                };
+            validate
+        }
+    }
+}
+
+/// Generates the statements that set the validation function and the invariants of a
+/// container on its `vtable`, from its `validate` and `invariants` attributes
+pub(crate) fn gen_container_checks(attrs: &[PFacetAttr]) -> TokenStream {
+    let mut validate_fns = Vec::new();
+    let mut invariants_fns = Vec::new();
+    for attr in attrs {
+        match attr {
+            PFacetAttr::Validate { expr } => validate_fns.push(expr),
+            PFacetAttr::Invariants { expr } => invariants_fns.push(expr),
+            _ => {}
+        }
+    }
+
+    let mut checks = quote! {};
+    if !validate_fns.is_empty() {
+        let validate = gen_validate_fn(&quote! { Self }, &validate_fns);
+        checks.extend(quote! { vtable.validate = Some(#validate); });
+    }
+    if !invariants_fns.is_empty() {
+        checks.extend(quote! {
+            vtable.invariants = {
+                let invariants: ::facet::InvariantsFn =
+                    |value: ::facet::PtrConst<'_>, message: &mut dyn ::core::fmt::Write| {
+                        let value = unsafe { value.get::<Self>() };
+                        #(
+                            if !::facet::InvariantsOutcome::holds((#invariants_fns)(value), message) {
+                                return false;
+                            }
+                        )*
+                        true
+                    };
+                Some(invariants)
+            };
+        });
+    }
+    checks
+}
+
+/// Generates the statements that set what a container is serialized and deserialized as on
+/// its `vtable`, from its `into`, `from` and `try_from` attributes
+pub(crate) fn gen_container_conversions(attrs: &[PFacetAttr]) -> TokenStream {
+    let mut conversions = quote! {};
+    for attr in attrs {
+        match attr {
+            PFacetAttr::From { ty } => conversions.extend(quote! {
+                vtable.deserialize_from = Some(::facet::DeserializeWith::new(
+                    <#ty as ::facet::Facet>::SHAPE,
+                    {
+                        let convert: ::facet::DeserializeWithFn =
+                            |proxy: ::facet::PtrMut<'_>,
+                             value: ::facet::PtrUninit<'_>,
+                             _message: &mut dyn ::core::fmt::Write| {
+                                let proxy = unsafe { proxy.read::<#ty>() };
+                                unsafe {
+                                    value.put::<Self>(
+                                        <Self as ::core::convert::From<#ty>>::from(proxy),
+                                    )
+                                };
+                                true
+                            };
+                        convert
+                    },
+                ));
+            }),
+            PFacetAttr::TryFrom { ty } => conversions.extend(quote! {
+                vtable.deserialize_from = Some(::facet::DeserializeWith::new(
+                    <#ty as ::facet::Facet>::SHAPE,
+                    {
+                        let convert: ::facet::DeserializeWithFn =
+                            |proxy: ::facet::PtrMut<'_>,
+                             value: ::facet::PtrUninit<'_>,
+                             message: &mut dyn ::core::fmt::Write| {
+                                let proxy = unsafe { proxy.read::<#ty>() };
+                                match <Self as ::core::convert::TryFrom<#ty>>::try_from(proxy) {
+                                    ::core::result::Result::Ok(v) => {
+                                        unsafe { value.put::<Self>(v) };
+                                        true
+                                    }
+                                    ::core::result::Result::Err(e) => {
+                                        let _ = ::core::fmt::Write::write_fmt(
+                                            message,
+                                            ::core::format_args!("{}", e),
+                                        );
+                                        false
+                                    }
+                                }
+                            };
+                        convert
+                    },
+                ));
+            }),
+            PFacetAttr::Into { ty } => conversions.extend(quote! {
+                vtable.serialize_into = Some(::facet::SerializeWith::new(
+                    <#ty as ::facet::Facet>::SHAPE,
+                    {
+                        let convert: ::facet::SerializeWithFn =
+                            |value: ::facet::PtrConst<'_>,
+                             proxy: ::facet::PtrUninit<'_>,
+                             _message: &mut dyn ::core::fmt::Write| {
+                                let value = unsafe { value.get::<Self>() };
+                                let value = ::core::clone::Clone::clone(value);
+                                unsafe {
+                                    proxy.put::<#ty>(<Self as ::core::convert::Into<#ty>>::into(
+                                        value,
+                                    ))
+                                };
+                                true
+                            };
+                        convert
+                    },
+                ));
+            }),
+            _ => {}
+        }
+    }
+    conversions
+}
+
+/// Generates the `.fields_by_name(...)` builder call: field indices, sorted by effective name,
+/// so that `StructType::field_index` can binary search instead of scanning.
+pub(crate) fn gen_fields_by_name(fields: &[PStructField]) -> TokenStream {
+    let mut indices = (0..fields.len()).collect::<Vec<_>>();
+    indices.sort_by(|&a, &b| fields[a].name.effective.cmp(&fields[b].name.effective));
+    let indices = indices
+        .into_iter()
+        .map(facet_derive_parse::Literal::usize_unsuffixed);
+    quote! { .fields_by_name(&[#(#indices),*]) }
+}
+
 /// Processes a regular struct to implement Facet
 ///
 /// Example input:
@@ -180,6 +442,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
     let struct_name_str = struct_name.to_string();
 
     // Use PStruct for kind and fields
+    let mut fields_by_name = quote! {};
     let (kind, fields_vec) = match &ps.kind {
         PStructKind::Struct { fields } => {
             let kind = quote!(::facet::StructKind::Struct);
@@ -187,6 +450,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 .iter()
                 .map(|field| gen_field_from_pfield(field, struct_name, &ps.container.bgp, None))
                 .collect::<Vec<_>>();
+            fields_by_name = gen_fields_by_name(fields);
             (kind, fields_vec)
         }
         PStructKind::TupleStruct { fields } => {
@@ -242,7 +506,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 PFacetAttr::Transparent => {
                     items.push(quote! { ::facet::ShapeAttribute::Transparent });
                 }
-                PFacetAttr::RenameAll { .. } => {}
+                PFacetAttr::RenameAll { .. } | PFacetAttr::Lenses => {}
                 PFacetAttr::Arbitrary { content } => {
                     items.push(quote! { ::facet::ShapeAttribute::Arbitrary(#content) });
                 }
@@ -250,10 +514,24 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 PFacetAttr::Sensitive
                 | PFacetAttr::Opaque
                 | PFacetAttr::Invariants { .. }
+                | PFacetAttr::Skip
                 | PFacetAttr::SkipSerializing
                 | PFacetAttr::SkipSerializingIf { .. }
                 | PFacetAttr::Flatten
-                | PFacetAttr::Child => {}
+                | PFacetAttr::Child
+                | PFacetAttr::Extra
+                | PFacetAttr::Validate { .. }
+                | PFacetAttr::SerializeWith { .. }
+                | PFacetAttr::DeserializeWith { .. }
+                | PFacetAttr::From { .. }
+                | PFacetAttr::TryFrom { .. }
+                | PFacetAttr::Into { .. } => {}
+                PFacetAttr::Tag { .. } | PFacetAttr::Content { .. } | PFacetAttr::Untagged => {
+                    panic!("Tagging attributes are only valid on enums")
+                }
+                PFacetAttr::SerializeAsDiscriminant => {
+                    panic!("#[facet(serialize_as = \"discriminant\")] is only valid on enums")
+                }
             }
         }
         if items.is_empty() {
@@ -263,38 +541,9 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         }
     };
 
-    // Invariants from PStruct
-    let invariant_maybe = {
-        let mut invariant_fns = Vec::new();
-        for attr in &ps.container.attrs.facet {
-            if let PFacetAttr::Invariants { expr } = attr {
-                invariant_fns.push(expr);
-            }
-        }
-
-        if !invariant_fns.is_empty() {
-            let tests = invariant_fns.iter().map(|expr| {
-                quote! {
-                    if !#expr(value) {
-                        return false;
-                    }
-                }
-            });
-
-            let bgp_display = ps.container.bgp.display_without_bounds(); // Use the BGP from PStruct
-            quote! {
-                unsafe fn invariants<'mem>(value: ::facet::PtrConst<'mem>) -> bool {
-                    let value = value.get::<#struct_name_ident #bgp_display>();
-                    #(#tests)*
-                    true
-                }
-
-                vtable.invariants = Some(invariants);
-            }
-        } else {
-            quote! {}
-        }
-    };
+    // Validation functions and invariants from PStruct
+    let validate_maybe = gen_container_checks(&ps.container.attrs.facet);
+    let conversions_maybe = gen_container_conversions(&ps.container.attrs.facet);
 
     // Transparent logic using PStruct
     let inner_field = if ps.container.attrs.is_transparent() {
@@ -398,34 +647,19 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         quote! {} // Not transparent
     };
 
-    // Generate the inner shape function for transparent types
-    let inner_shape_fn = if ps.container.attrs.is_transparent() {
+    // Transparent types point straight at their inner shape
+    let inner_setter = if ps.container.attrs.is_transparent() {
         if let Some(inner_field) = &inner_field {
             let ty = &inner_field.ty;
-            quote! {
-                // Function to return inner type's shape
-                fn inner_shape() -> &'static ::facet::Shape {
-                    <#ty as ::facet::Facet>::SHAPE
-                }
-            }
+            quote! { .inner_static(<#ty as ::facet::Facet>::SHAPE) }
         } else {
             // Transparent ZST case
-            quote! {
-                fn inner_shape() -> &'static ::facet::Shape {
-                    <() as ::facet::Facet>::SHAPE // Inner shape is unit
-                }
-            }
+            quote! { .inner_static(<() as ::facet::Facet>::SHAPE) }
         }
     } else {
         quote! {}
     };
 
-    let inner_setter = if ps.container.attrs.is_transparent() {
-        quote! { .inner(inner_shape) }
-    } else {
-        quote! {}
-    };
-
     // Generics from PStruct
     let facet_bgp = ps
         .container
@@ -434,10 +668,18 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
     let bgp_def = facet_bgp.display_with_bounds();
     let bgp_without_bounds = ps.container.bgp.display_without_bounds();
 
+    let lenses = if ps.container.attrs.has_lenses() {
+        gen_lenses(&ps, &parsed, where_clauses_ast)
+    } else {
+        quote! {}
+    };
+
     // Final quote block using refactored parts
     let result = quote! {
         #static_decl
 
+        #lenses
+
         #[automatically_derived]
         unsafe impl #bgp_def ::facet::Facet<'__facet> for #struct_name_ident #bgp_without_bounds #where_clauses {
             const VTABLE: &'static ::facet::ValueVTable = &const {
@@ -445,7 +687,8 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                     Self,
                     |f, _opts| ::core::fmt::Write::write_str(f, #struct_name_str)
                 );
-                #invariant_maybe
+                #validate_maybe
+                #conversions_maybe
                 #try_from_inner_code // Use the generated code for transparent types
                 vtable
             };
@@ -453,14 +696,13 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
             const SHAPE: &'static ::facet::Shape = &const {
                 let fields: &'static [::facet::Field] = &const {[#(#fields_vec),*]};
 
-                #inner_shape_fn // Include inner_shape function if needed
-
                 ::facet::Shape::builder_for_sized::<Self>()
                     #type_params // Still from parsed.generics
                     .ty(::facet::Type::User(::facet::UserType::Struct(::facet::StructType::builder()
                         .repr(::facet::Repr::c())
                         .kind(#kind)
                         .fields(fields)
+                        #fields_by_name
                         .build()
                     )))
                     #inner_setter // Use transparency flag from PStruct
@@ -473,3 +715,67 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
 
     result
 }
+
+/// Generates `Self::fields()` and its lenses for `#[facet(lenses)]`
+fn gen_lenses(ps: &PStruct, parsed: &Struct, where_clauses: Option<&WhereClauses>) -> TokenStream {
+    let struct_name = &ps.container.name;
+    let fields_name = format_ident!("{}Fields", struct_name);
+    let bgp_def = ps.container.bgp.display_with_bounds();
+    let bgp_without_bounds = ps.container.bgp.display_without_bounds();
+    let where_clauses = where_clauses.map(|wc| wc.to_token_stream());
+    let vis = |vis: &Option<Vis>| vis.as_ref().map(|vis| vis.to_token_stream());
+    let container_vis = vis(&parsed._vis);
+
+    // Lenses are as visible as the fields they point at
+    let (fields, field_vis): (&[PStructField], Vec<_>) = match (&ps.kind, &parsed.kind) {
+        (PStructKind::Struct { fields }, StructKind::Struct { fields: ast, .. }) => (
+            fields.as_slice(),
+            ast.content.0.iter().map(|f| vis(&f.value._vis)).collect(),
+        ),
+        (PStructKind::TupleStruct { fields }, StructKind::TupleStruct { fields: ast, .. }) => (
+            fields.as_slice(),
+            ast.content.0.iter().map(|f| vis(&f.value.vis)).collect(),
+        ),
+        _ => (&[], Vec::new()),
+    };
+
+    let lens_fns = fields.iter().zip(field_vis).enumerate().map(|(index, (field, vis))| {
+        let (method, doc) = match &field.name.raw {
+            IdentOrLiteral::Ident(ident) => (ident.clone(), format!(" A lens onto `{}`", ident)),
+            IdentOrLiteral::Literal(index) => (
+                format_ident!("_{}", index),
+                format!(" A lens onto field `{}`", index),
+            ),
+        };
+        let ty = &field.ty;
+        quote! {
+            #[doc = #doc]
+            #vis const fn #method(self) -> ::facet::Lens<#struct_name #bgp_without_bounds, #ty> {
+                ::facet::Lens::field(#index)
+            }
+        }
+    });
+
+    let fields_doc = format!(" Lenses onto the fields of [`{}`]", struct_name);
+    quote! {
+        #[doc = #fields_doc]
+        #container_vis struct #fields_name #bgp_def #where_clauses {
+            _phantom: ::core::marker::PhantomData<fn() -> #struct_name #bgp_without_bounds>,
+        }
+
+        #[automatically_derived]
+        impl #bgp_def #struct_name #bgp_without_bounds #where_clauses {
+            /// Lenses onto the fields of this struct, to follow with `Peek::lens` or `Wip::lens`
+            #container_vis const fn fields() -> #fields_name #bgp_without_bounds {
+                #fields_name {
+                    _phantom: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #bgp_def #fields_name #bgp_without_bounds #where_clauses {
+            #(#lens_fns)*
+        }
+    }
+}
@@ -46,10 +46,38 @@ keyword! {
     pub KFlatten = "flatten";
     /// The "child" keyword
     pub KChild = "child";
+    /// The "extra" keyword
+    pub KExtra = "extra";
+    /// The "skip" keyword.
+    pub KSkip = "skip";
+    /// The "tag" keyword.
+    pub KTag = "tag";
+    /// The "content" keyword.
+    pub KContent = "content";
+    /// The "untagged" keyword.
+    pub KUntagged = "untagged";
+    /// The "serialize_as" keyword.
+    pub KSerializeAs = "serialize_as";
     /// The "skip_serializing" keyword.
     pub KSkipSerializing = "skip_serializing";
     /// The "skip_serializing_if" keyword.
     pub KSkipSerializingIf = "skip_serializing_if";
+    /// The "lenses" keyword.
+    pub KLenses = "lenses";
+    /// The "validate" keyword.
+    pub KValidate = "validate";
+    /// The "with" keyword.
+    pub KWith = "with";
+    /// The "serialize_with" keyword.
+    pub KSerializeWith = "serialize_with";
+    /// The "deserialize_with" keyword.
+    pub KDeserializeWith = "deserialize_with";
+    /// The "from" keyword.
+    pub KFrom = "from";
+    /// The "try_from" keyword.
+    pub KTryFrom = "try_from";
+    /// The "into" keyword.
+    pub KInto = "into";
 }
 
 operator! {
@@ -143,18 +171,46 @@ unsynn! {
         Default(KDefault),
         /// A transparent attribute for containers
         Transparent(KTransparent),
+        /// A lenses attribute that generates typed paths to a struct's fields
+        Lenses(KLenses),
         /// A rename_all attribute that specifies a case conversion for all fields/variants (#[facet(rename_all = "camelCase")])
         RenameAll(RenameAllInner),
         /// A rename attribute that specifies a custom name for a field/variant (#[facet(rename = "custom_name")])
         Rename(RenameInner),
+        /// A tag attribute that specifies the key of an enum's variant name (#[facet(tag = "type")])
+        Tag(TagInner),
+        /// A content attribute that specifies the key of an enum's variant fields (#[facet(content = "data")])
+        Content(ContentInner),
+        /// An untagged attribute that leaves an enum's variant names out
+        Untagged(KUntagged),
+        /// A serialize_as attribute that specifies what an enum's variants are serialized as (#[facet(serialize_as = "discriminant")])
+        SerializeAs(SerializeAsInner),
         /// A flatten attribute that marks a field to be flattened into the parent structure
         Flatten(FlattenInner),
         /// A child attribute that marks a field as a child node
         Child(ChildInner),
+        /// An extra attribute that marks a field as the one unknown keys are collected into
+        Extra(ExtraInner),
+        /// A skip attribute that specifies a field should be skipped during serialization and deserialization.
+        Skip(SkipInner),
         /// A skip_serializing attribute that specifies whether a field should be skipped during serialization.
         SkipSerializing(SkipSerializingInner),
         /// A skip_serializing_if attribute that specifies a condition for skipping serialization.
         SkipSerializingIf(SkipSerializingIfInner),
+        /// A validate attribute that specifies a function checking the value once it's built.
+        Validate(ValidateInner),
+        /// A with attribute that specifies a module converting a field from and to what it's serialized as.
+        With(WithInner),
+        /// A serialize_with attribute that specifies a function converting a field to what it's serialized as.
+        SerializeWith(SerializeWithInner),
+        /// A deserialize_with attribute that specifies a function converting what a field is deserialized as to it.
+        DeserializeWith(DeserializeWithInner),
+        /// A from attribute that specifies a type a container is deserialized as, then converted from.
+        From(FromInner),
+        /// A try_from attribute that specifies a type a container is deserialized as, then fallibly converted from.
+        TryFrom(TryFromInner),
+        /// An into attribute that specifies a type a container is converted to, then serialized as.
+        Into(IntoInner),
         /// Any other attribute represented as a sequence of token trees.
         Arbitrary(VerbatimUntil<Comma>),
     }
@@ -171,6 +227,18 @@ unsynn! {
         pub _kw_child: KChild,
     }
 
+    /// Inner value for #[facet(extra)]
+    pub struct ExtraInner {
+        /// The "extra" keyword.
+        pub _kw_extra: KExtra,
+    }
+
+    /// Inner value for #[facet(skip)]
+    pub struct SkipInner {
+        /// The "skip" keyword.
+        pub _kw_skip: KSkip,
+    }
+
     /// Inner value for #[facet(skip_serializing)]
     pub struct SkipSerializingInner {
         /// The "skip_serializing" keyword.
@@ -187,6 +255,76 @@ unsynn! {
         pub expr: VerbatimUntil<Comma>,
     }
 
+    /// Inner value for #[facet(validate = ...)]
+    pub struct ValidateInner {
+        /// The "validate" keyword.
+        pub _kw_validate: KValidate,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The validation function as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(with = ...)]
+    pub struct WithInner {
+        /// The "with" keyword.
+        pub _kw_with: KWith,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The path of the module as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(serialize_with = ...)]
+    pub struct SerializeWithInner {
+        /// The "serialize_with" keyword.
+        pub _kw_serialize_with: KSerializeWith,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The conversion function as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(deserialize_with = ...)]
+    pub struct DeserializeWithInner {
+        /// The "deserialize_with" keyword.
+        pub _kw_deserialize_with: KDeserializeWith,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The conversion function as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(from = ...)]
+    pub struct FromInner {
+        /// The "from" keyword.
+        pub _kw_from: KFrom,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The type as verbatim until comma.
+        pub ty: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(try_from = ...)]
+    pub struct TryFromInner {
+        /// The "try_from" keyword.
+        pub _kw_try_from: KTryFrom,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The type as verbatim until comma.
+        pub ty: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(into = ...)]
+    pub struct IntoInner {
+        /// The "into" keyword.
+        pub _kw_into: KInto,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The type as verbatim until comma.
+        pub ty: VerbatimUntil<Comma>,
+    }
+
     /// Inner value for #[facet(default = ...)]
     pub struct DefaultEqualsInner {
         /// The "default" keyword.
@@ -207,6 +345,36 @@ unsynn! {
         pub value: LiteralString,
     }
 
+    /// Inner value for #[facet(tag = ...)]
+    pub struct TagInner {
+        /// The "tag" keyword.
+        pub _kw_tag: KTag,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[facet(content = ...)]
+    pub struct ContentInner {
+        /// The "content" keyword.
+        pub _kw_content: KContent,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
+    /// Inner value for #[facet(serialize_as = ...)]
+    pub struct SerializeAsInner {
+        /// The "serialize_as" keyword.
+        pub _kw_serialize_as: KSerializeAs,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The value assigned, as a literal string.
+        pub value: LiteralString,
+    }
+
     /// Inner value for #[facet(rename_all = ...)]
     pub struct RenameAllInner {
         /// The "rename_all" keyword.
@@ -0,0 +1,30 @@
+use alloc::vec::Vec;
+
+/// Decodes standard base64, with or without its `=` padding
+pub(crate) fn decode(text: &str) -> Result<Vec<u8>, &'static str> {
+    let text = text.trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return Err("its length isn't possible for base64");
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    for c in text.bytes() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err("it contains characters that aren't in the base64 alphabet"),
+        };
+        bits = (bits << 6) | u32::from(sextet);
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            bytes.push((bits >> bit_count) as u8);
+        }
+    }
+    Ok(bytes)
+}
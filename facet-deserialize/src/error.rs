@@ -20,6 +20,21 @@ pub struct DeserError<'input> {
 
     /// The specific error that occurred while parsing the JSON.
     pub kind: DeserErrorKind,
+
+    /// The path of the value that was being deserialized, like `$.servers[2].port`, if it
+    /// wasn't the top-level one
+    pub path: Option<String>,
+}
+
+/// Where an error is in the input, see [`DeserError::location`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Byte offset from the start of the input
+    pub offset: usize,
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number, in characters, starting at 1
+    pub column: usize,
 }
 
 impl DeserError<'_> {
@@ -29,6 +44,33 @@ impl DeserError<'_> {
             input: self.input.into_owned().into(),
             span: self.span,
             kind: self.kind,
+            path: self.path,
+        }
+    }
+
+    /// Sets the path of the value that was being deserialized
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Where the error is in the input
+    pub fn location(&self) -> Location {
+        let offset = self.span.start().min(self.input.len());
+        let before = &self.input[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        Location {
+            offset,
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            // Every byte but UTF-8 continuation bytes starts a character
+            column: before[line_start..]
+                .iter()
+                .filter(|&&b| b & 0xC0 != 0x80)
+                .count()
+                + 1,
         }
     }
 
@@ -83,6 +125,8 @@ pub enum DeserErrorKind {
     },
     /// A string that could not be built into valid UTF-8 Unicode
     InvalidUtf8(String),
+    /// A string for a list of bytes that isn't valid base64
+    InvalidBase64(String),
     /// An error occurred while reflecting a type.
     ReflectError(ReflectError),
     /// Some feature is not yet implemented (under development).
@@ -105,6 +149,16 @@ pub enum DeserErrorKind {
     },
     /// An error occurred when reflecting an enum variant (index) from a user type.
     VariantError(VariantError),
+    /// A list in the input doesn't have as many items as the fixed-size array it's read into
+    ArrayLength {
+        /// The shape of the array
+        shape: &'static Shape,
+        /// How many items the array holds
+        expected: usize,
+        /// How many items the list had, or, if it had too many, how many were read when that
+        /// was found out: one more than expected
+        got: usize,
+    },
 }
 
 impl<'input> DeserError<'input> {
@@ -114,6 +168,7 @@ impl<'input> DeserError<'input> {
             input: alloc::borrow::Cow::Borrowed(input),
             span,
             kind,
+            path: None,
         }
     }
 
@@ -168,6 +223,7 @@ impl core::fmt::Display for DeserErrorMessage<'_> {
                 )
             }
             DeserErrorKind::InvalidUtf8(e) => write!(f, "Invalid UTF-8 encoding: {}", e.red()),
+            DeserErrorKind::InvalidBase64(e) => write!(f, "Invalid base64 encoding: {}", e.red()),
             DeserErrorKind::ReflectError(e) => write!(f, "{e}"),
             DeserErrorKind::Unimplemented(s) => {
                 write!(f, "Feature not yet implemented: {}", s.yellow())
@@ -210,6 +266,27 @@ impl core::fmt::Display for DeserErrorMessage<'_> {
                     Ok(())
                 }
             }
+            DeserErrorKind::ArrayLength {
+                shape,
+                expected,
+                got,
+            } if got > expected => write!(
+                f,
+                "Too many items for {}: expected {}",
+                shape.yellow(),
+                expected.green()
+            ),
+            DeserErrorKind::ArrayLength {
+                shape,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Too few items for {}: expected {}, got {}",
+                shape.yellow(),
+                expected.green(),
+                got.red()
+            ),
             DeserErrorKind::VariantError(e) => {
                 write!(f, "Variant error: {e}")
             }
@@ -220,7 +297,12 @@ impl core::fmt::Display for DeserErrorMessage<'_> {
 #[cfg(not(feature = "rich-diagnostics"))]
 impl core::fmt::Display for DeserError<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{} at byte {}", self.message(), self.span.start(),)
+        let Location { line, column, .. } = self.location();
+        write!(f, "{} at line {line}, column {column}", self.message())?;
+        if let Some(path) = &self.path {
+            write!(f, ", in {path}")?;
+        }
+        Ok(())
     }
 }
 
@@ -237,6 +319,9 @@ impl core::fmt::Display for DeserError<'_> {
 
         let mut report = Report::build(ReportKind::Error, (source_id, span_start..span_end))
             .with_config(Config::new().with_index_type(IndexType::Byte));
+        if let Some(path) = &self.path {
+            report = report.with_message(alloc::format!("in {path}"));
+        }
 
         let label = Label::new((source_id, span_start..span_end))
             .with_message(self.message())
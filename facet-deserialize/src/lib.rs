@@ -7,22 +7,32 @@
 
 extern crate alloc;
 
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::{vec, vec::Vec};
+use core::time::Duration;
 
 mod error;
 use alloc::borrow::Cow;
 
+mod base64;
+
 pub use error::*;
 
 mod span;
 use facet_core::{
-    Characteristic, Def, Facet, FieldFlags, ScalarAffinity, SequenceType, Type, UserType,
+    Characteristic, Def, DurationParts, EnumTagging, Facet, FieldFlags, ScalarAffinity,
+    SequenceType, Shape, SmartPointerFlags, StructType, Type, UserType,
 };
 use owo_colors::OwoColorize;
 pub use span::*;
 
-use facet_reflect::{HeapValue, ReflectError, Wip};
+mod reader;
+pub use reader::*;
+
+mod options;
+pub use options::*;
+
+use facet_reflect::{Arena, HeapValue, ReflectError, Wip};
 use log::trace;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -37,6 +47,10 @@ pub enum Scalar<'input> {
     U64(u64),
     /// Signed 64-bit integer scalar.
     I64(i64),
+    /// Unsigned 128-bit integer scalar, for values that don't fit in a `u64`.
+    U128(u128),
+    /// Signed 128-bit integer scalar, for values that don't fit in an `i64`.
+    I128(i128),
     /// 64-bit floating-point scalar.
     F64(f64),
     /// Boolean scalar.
@@ -101,6 +115,8 @@ impl fmt::Display for Scalar<'_> {
             Scalar::String(s) => write!(f, "string \"{}\"", s),
             Scalar::U64(val) => write!(f, "u64 {}", val),
             Scalar::I64(val) => write!(f, "i64 {}", val),
+            Scalar::U128(val) => write!(f, "u128 {}", val),
+            Scalar::I128(val) => write!(f, "i128 {}", val),
             Scalar::F64(val) => write!(f, "f64 {}", val),
             Scalar::Bool(val) => write!(f, "bool {}", val),
             Scalar::Null => write!(f, "null"),
@@ -109,13 +125,16 @@ impl fmt::Display for Scalar<'_> {
 }
 
 impl Outcome<'_> {
-    fn into_owned(self) -> Outcome<'static> {
+    /// Converts the outcome into one that doesn't borrow from the input
+    pub fn into_owned(self) -> Outcome<'static> {
         match self {
             Outcome::Scalar(scalar) => {
                 let owned_scalar = match scalar {
                     Scalar::String(cow) => Scalar::String(Cow::Owned(cow.into_owned())),
                     Scalar::U64(val) => Scalar::U64(val),
                     Scalar::I64(val) => Scalar::I64(val),
+                    Scalar::U128(val) => Scalar::U128(val),
+                    Scalar::I128(val) => Scalar::I128(val),
                     Scalar::F64(val) => Scalar::F64(val),
                     Scalar::Bool(val) => Scalar::Bool(val),
                     Scalar::Null => Scalar::Null,
@@ -173,6 +192,13 @@ pub trait Format {
         &mut self,
         nd: NextData<'input, 'facet>,
     ) -> NextResult<'input, 'facet, Span, Spanned<DeserErrorKind>>;
+
+    /// Whether values of `shape` are taken from the input as they're written: they're skipped
+    /// rather than parsed, and the text spanned by [`Format::skip`] is put in their frame as
+    /// a `&str`. None are, by default.
+    fn is_raw(&self, _shape: &'static Shape) -> bool {
+        false
+    }
 }
 
 /// Instructions guiding the parsing flow, indicating the next expected action or token.
@@ -210,6 +236,10 @@ pub enum PopReason {
     ListVal,
     /// Ending a `Some()` in an option
     Some,
+    /// Ending the pointee of a smart pointer
+    Pointee,
+    /// Ending the proxy a value is deserialized as
+    Proxy,
 }
 
 /// Deserialize a value of type `T` from raw input bytes using format `F`.
@@ -225,22 +255,70 @@ where
     F: Format,
     'input: 'facet,
 {
-    let wip = Wip::alloc_shape(T::SHAPE).map_err(|e| DeserError {
-        input: input.into(),
-        span: Span { start: 0, len: 0 },
-        kind: DeserErrorKind::ReflectError(e),
-    })?;
+    let wip = Wip::alloc_shape(T::SHAPE)
+        .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))?;
+    deserialize_wip(wip, input, format)?
+        .materialize()
+        .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))
+}
+
+/// Like [`deserialize`], but takes the memory for the value, and for every temporary
+/// value needed along the way, from `arena` — see [`Arena`].
+pub fn deserialize_in<'input, 'facet, T, F>(
+    input: &'input [u8],
+    format: F,
+    arena: &Arena,
+) -> Result<T, DeserError<'input>>
+where
+    T: Facet<'facet>,
+    F: Format,
+    'input: 'facet,
+{
+    let wip = Wip::alloc_shape_in(T::SHAPE, arena)
+        .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))?;
     deserialize_wip(wip, input, format)?
         .materialize()
         .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))
 }
 
+/// Like [`deserialize`], but with the given [`DeserializeOptions`]
+pub fn deserialize_with_options<'input, 'facet, T, F>(
+    input: &'input [u8],
+    format: F,
+    options: DeserializeOptions,
+) -> Result<T, DeserError<'input>>
+where
+    T: Facet<'facet>,
+    F: Format,
+    'input: 'facet,
+{
+    let wip = Wip::alloc_shape(T::SHAPE)
+        .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))?;
+    deserialize_wip_with_options(wip, input, format, options)?
+        .materialize()
+        .map_err(|e| DeserError::new_reflect(e, input, Span { start: 0, len: 0 }))
+}
+
 /// Deserializes a working-in-progress value into a fully materialized heap value.
 /// This function drives the parsing loop until the entire input is consumed and the value is complete.
 pub fn deserialize_wip<'input, 'facet, F>(
+    wip: Wip<'facet>,
+    input: &'input [u8],
+    format: F,
+) -> Result<HeapValue<'facet>, DeserError<'input>>
+where
+    F: Format,
+    'input: 'facet,
+{
+    deserialize_wip_with_options(wip, input, format, DeserializeOptions::default())
+}
+
+/// Like [`deserialize_wip`], but with the given [`DeserializeOptions`]
+pub fn deserialize_wip_with_options<'input, 'facet, F>(
     mut wip: Wip<'facet>,
     input: &'input [u8],
     mut format: F,
+    options: DeserializeOptions,
 ) -> Result<HeapValue<'facet>, DeserError<'input>>
 where
     F: Format,
@@ -255,16 +333,20 @@ where
             Instruction::Value(ValueReason::TopLevel),
         ],
         last_span: Span::new(0, 0),
+        path: Vec::new(),
+        options,
     };
 
     macro_rules! next {
         ($runner:ident, $wip:ident, $expectation:expr, $method:ident) => {{
+            let expectation: Expectation = $expectation;
+            $runner.expect(&expectation);
             let nd = NextData {
                 start: $runner.last_span.end(), // or supply the appropriate start value if available
                 runner: $runner,
                 wip: $wip,
             };
-            let (nd, res) = format.next(nd, $expectation);
+            let (nd, res) = format.next(nd, expectation.clone());
             $runner = nd.runner;
             $wip = nd.wip;
             let outcome = res.map_err(|span_kind| {
@@ -273,7 +355,12 @@ where
             })?;
             $runner.last_span = outcome.span;
             trace!("Got outcome {}", outcome.blue());
+            $runner.enter(&expectation, &outcome.node);
+            let ended = matches!(outcome.node, Outcome::ObjectEnded | Outcome::ListEnded);
             $wip = $runner.$method($wip, outcome)?;
+            if ended {
+                $runner.path.pop();
+            }
         }};
     }
 
@@ -305,6 +392,25 @@ where
                     wip = wip.pop().map_err(|e| runner.reflect_err(e))?;
                 }
             }
+            Instruction::Value(_why) if format.is_raw(wip.shape()) => {
+                let nd = NextData {
+                    start: runner.last_span.end(),
+                    runner,
+                    wip,
+                };
+                let (nd, res) = format.skip(nd);
+                runner = nd.runner;
+                wip = nd.wip;
+                runner.last_span = res.map_err(|span_kind| {
+                    runner.last_span = span_kind.span;
+                    runner.err(span_kind.node)
+                })?;
+                let span = runner.last_span;
+                let text = core::str::from_utf8(&runner.original_input[span.start()..span.end()])
+                    .map_err(|e| runner.err(DeserErrorKind::InvalidUtf8(e.to_string())))?;
+                trace!("Captured raw {}: {}", wip.shape().blue(), text);
+                wip = wip.put(text).map_err(|e| runner.reflect_err(e))?;
+            }
             Instruction::Value(_why) => {
                 let expectation = match _why {
                     ValueReason::TopLevel => Expectation::Value,
@@ -365,18 +471,92 @@ pub struct StackRunner<'input> {
     pub stack: Vec<Instruction>,
     /// Span of the last processed token, for accurate error reporting.
     pub last_span: Span,
+    /// Where in the input the value being deserialized is, for error reporting.
+    path: Vec<PathSegment<'input>>,
+    /// How the input is matched to the value being deserialized.
+    options: DeserializeOptions,
+}
+
+/// A container the value being deserialized is in
+enum PathSegment<'input> {
+    /// An object, under the key that was read last, if any
+    Key(Option<Cow<'input, str>>),
+    /// A list, at the item that was read last, if any
+    Index(Option<usize>),
 }
 
 impl<'input> StackRunner<'input> {
     /// Convenience function to create a DeserError using the original input and last_span.
     fn err(&self, kind: DeserErrorKind) -> DeserError<'input> {
-        DeserError::new(kind, self.original_input, self.last_span)
+        DeserError::new(kind, self.original_input, self.last_span).with_path(self.path())
     }
 
     /// Convenience function to create a DeserError from a ReflectError,
     /// using the original input and last_span for context.
     fn reflect_err(&self, err: ReflectError) -> DeserError<'input> {
-        DeserError::new_reflect(err, self.original_input, self.last_span)
+        DeserError::new_reflect(err, self.original_input, self.last_span).with_path(self.path())
+    }
+
+    /// The path of the value being deserialized, like `$.servers[2].port`, or `None` for the
+    /// top-level value
+    fn path(&self) -> Option<String> {
+        use core::fmt::Write as _;
+
+        let mut path = String::from("$");
+        for segment in &self.path {
+            match segment {
+                PathSegment::Key(Some(key)) => {
+                    path.push('.');
+                    path.push_str(key);
+                }
+                PathSegment::Index(Some(index)) => {
+                    let _ = write!(path, "[{index}]");
+                }
+                PathSegment::Key(None) | PathSegment::Index(None) => {}
+            }
+        }
+        (path.len() > 1).then_some(path)
+    }
+
+    /// Leaves the value of the last key once the next one is expected, so errors in between
+    /// point at the object
+    fn expect(&mut self, expectation: &Expectation) {
+        if let (Expectation::ObjectKeyOrObjectClose, Some(PathSegment::Key(last))) =
+            (expectation, self.path.last_mut())
+        {
+            *last = None;
+        }
+    }
+
+    /// Keeps track of where in the input an outcome is, before it's handled. Containers are
+    /// left again once their end has been handled.
+    fn enter(&mut self, expectation: &Expectation, outcome: &Outcome<'input>) {
+        match (expectation, outcome, self.path.last_mut()) {
+            (
+                Expectation::ObjectKeyOrObjectClose,
+                Outcome::Scalar(Scalar::String(key)),
+                Some(PathSegment::Key(last)),
+            ) => {
+                *last = Some(key.clone());
+            }
+            (
+                Expectation::ListItemOrListClose,
+                Outcome::ListEnded,
+                Some(PathSegment::Index(last)),
+            ) => {
+                *last = None;
+            }
+            (Expectation::ListItemOrListClose, _, Some(PathSegment::Index(last))) => {
+                *last = Some(last.map_or(0, |index| index + 1));
+            }
+            _ => {}
+        }
+
+        match outcome {
+            Outcome::ObjectStarted => self.path.push(PathSegment::Key(None)),
+            Outcome::ListStarted => self.path.push(PathSegment::Index(None)),
+            _ => {}
+        }
     }
 
     fn pop<'facet>(
@@ -441,7 +621,7 @@ impl<'input> StackRunner<'input> {
 
                 if has_unset && container_shape.has_default_attr() {
                     // let's allocate and build a default value
-                    let default_val = Wip::alloc_shape(container_shape)
+                    let default_val = alloc_temporary(&wip, container_shape)
                         .map_err(|e| self.reflect_err(e))?
                         .put_default()
                         .map_err(|e| self.reflect_err(e))?
@@ -527,7 +707,7 @@ impl<'input> StackRunner<'input> {
                         if has_unset && container_shape.has_default_attr() {
                             trace!("Enum has DEFAULT attr but variant has uninitialized fields");
                             // Handle similar to struct, allocate and build default value for variant
-                            let default_val = Wip::alloc_shape(container_shape)
+                            let default_val = alloc_temporary(&wip, container_shape)
                                 .map_err(|e| self.reflect_err(e))?
                                 .put_default()
                                 .map_err(|e| self.reflect_err(e))?
@@ -565,7 +745,7 @@ impl<'input> StackRunner<'input> {
                 } else if container_shape.has_default_attr() {
                     // No variant selected, but enum has default attribute - set to default
                     trace!("No variant selected but enum has DEFAULT attr; setting to default");
-                    let default_val = Wip::alloc_shape(container_shape)
+                    let default_val = alloc_temporary(&wip, container_shape)
                         .map_err(|e| self.reflect_err(e))?
                         .put_default()
                         .map_err(|e| self.reflect_err(e))?
@@ -611,8 +791,36 @@ impl<'input> StackRunner<'input> {
         &self,
         wip: Wip<'facet>,
         scalar: Scalar<'input>,
-    ) -> Result<Wip<'facet>, DeserError<'input>> {
+    ) -> Result<Wip<'facet>, DeserError<'input>>
+    where
+        'input: 'facet,
+    {
         match scalar {
+            Scalar::String(cow) if is_byte_list(wip.shape()) => {
+                let bytes = base64::decode(&cow).map_err(|reason| {
+                    self.err(DeserErrorKind::InvalidBase64(reason.to_string()))
+                })?;
+                self.put_bytes(wip, &bytes)
+            }
+            // Numbers that were written as strings, like large integers or `"NaN"`
+            Scalar::String(cow) if is_number(wip.shape()) => {
+                wip.parse(&cow).map_err(|e| self.reflect_err(e))
+            }
+            // Borrowed from the input, which outlives the value
+            Scalar::String(cow) if wip.shape() == <&str>::SHAPE => match cow {
+                Cow::Borrowed(s) => wip.put(s).map_err(|e| self.reflect_err(e)),
+                Cow::Owned(_) => Err(self.err(DeserErrorKind::UnsupportedType {
+                    got: wip.shape(),
+                    wanted: "a string without escapes, to borrow it from the input",
+                })),
+            },
+            Scalar::String(cow) if wip.shape() == <Cow<'_, str>>::SHAPE => {
+                wip.put(cow).map_err(|e| self.reflect_err(e))
+            }
+            // Scalars written as their `Display` output, like addresses or UUIDs
+            Scalar::String(cow) if is_parsed_scalar(wip.shape()) => {
+                wip.parse(&cow).map_err(|e| self.reflect_err(e))
+            }
             Scalar::String(cow) => {
                 match wip.innermost_shape().ty {
                     Type::User(UserType::Enum(_)) => {
@@ -635,20 +843,149 @@ impl<'input> StackRunner<'input> {
                     _ => wip.put(cow.to_string()).map_err(|e| self.reflect_err(e)),
                 }
             }
+            // Unit variants written as their discriminant
+            Scalar::U64(_) | Scalar::I64(_)
+                if wip.shape().has_serialize_as_discriminant_attr()
+                    && wip.selected_variant().is_none() =>
+            {
+                let number = match scalar {
+                    Scalar::U64(value) => i128::from(value),
+                    Scalar::I64(value) => i128::from(value),
+                    _ => unreachable!(),
+                };
+                let found = i64::try_from(number)
+                    .ok()
+                    .and_then(|discriminant| wip.find_variant_by_discriminant(discriminant));
+                match found {
+                    Some((variant_index, _)) => {
+                        wip.variant(variant_index).map_err(|e| self.reflect_err(e))
+                    }
+                    None => Err(self.err(DeserErrorKind::NoSuchVariant {
+                        name: number.to_string(),
+                        enum_shape: wip.shape(),
+                    })),
+                }
+            }
+            // Unix timestamps, for timestamps and datetimes that stand for an instant
+            Scalar::U64(_)
+            | Scalar::I64(_)
+            | Scalar::U128(_)
+            | Scalar::I128(_)
+            | Scalar::F64(_)
+                if wip.can_put_unix_nanos() =>
+            {
+                let per_unit = self.options.get_unix_timestamps().nanos_per_unit();
+                let (nanos, number) = match scalar {
+                    Scalar::U64(value) => (i128::from(value).checked_mul(per_unit), value as f64),
+                    Scalar::I64(value) => (i128::from(value).checked_mul(per_unit), value as f64),
+                    Scalar::U128(value) => (
+                        i128::try_from(value)
+                            .ok()
+                            .and_then(|value| value.checked_mul(per_unit)),
+                        value as f64,
+                    ),
+                    Scalar::I128(value) => (value.checked_mul(per_unit), value as f64),
+                    Scalar::F64(value) => {
+                        // Scale the whole and fractional parts separately, so the nanoseconds
+                        // don't pick up the rounding error of a product past 2^53
+                        // (by casts, since `f64::trunc` and `f64::round` need std)
+                        let fits = -(i128::MAX as f64) < value && value < i128::MAX as f64;
+                        let whole = value as i128;
+                        let fraction = (value - whole as f64) * per_unit as f64;
+                        let fraction = (fraction + 0.5f64.copysign(fraction)) as i128;
+                        let nanos = fits
+                            .then(|| whole.checked_mul(per_unit))
+                            .flatten()
+                            .and_then(|nanos| nanos.checked_add(fraction));
+                        (nanos, value)
+                    }
+                    _ => unreachable!(),
+                };
+                let Some(nanos) = nanos else {
+                    return Err(self.err(DeserErrorKind::NumberOutOfRange(number)));
+                };
+                wip.put_unix_nanos(nanos).map_err(|e| self.reflect_err(e))
+            }
             Scalar::U64(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
             Scalar::I64(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
+            Scalar::U128(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
+            Scalar::I128(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
             Scalar::F64(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
             Scalar::Bool(value) => wip.put(value).map_err(|e| self.reflect_err(e)),
             Scalar::Null => wip.put_default().map_err(|e| self.reflect_err(e)),
         }
     }
 
+    /// Fills a list or array of `u8` with `bytes`, one by one
+    fn put_bytes<'facet>(
+        &self,
+        mut wip: Wip<'facet>,
+        bytes: &[u8],
+    ) -> Result<Wip<'facet>, DeserError<'input>> {
+        if bytes.is_empty() && matches!(wip.shape().def, Def::List(_)) {
+            return wip.put_empty_list().map_err(|e| self.reflect_err(e));
+        }
+        wip = wip.begin_pushback().map_err(|e| self.reflect_err(e))?;
+        for &byte in bytes {
+            wip = wip
+                .push()
+                .and_then(|wip| wip.put(byte))
+                .and_then(|wip| wip.pop())
+                .map_err(|e| self.reflect_err(e))?;
+        }
+        Ok(wip)
+    }
+
+    /// Puts an object key into a map's key frame. Keys are always strings in the input:
+    /// enums pick the unit variant they name (or whose discriminant they are), other types
+    /// parse the key.
+    fn put_map_key<'facet>(
+        &self,
+        wip: Wip<'facet>,
+        key: Cow<'input, str>,
+    ) -> Result<Wip<'facet>, DeserError<'input>> {
+        let shape = wip.shape();
+        if let Type::User(UserType::Enum(_)) = shape.ty {
+            let found = if shape.has_serialize_as_discriminant_attr() {
+                key.parse()
+                    .ok()
+                    .and_then(|discriminant| wip.find_variant_by_discriminant(discriminant))
+            } else {
+                wip.find_variant(&key)
+            };
+            return match found {
+                Some((variant_index, _)) => {
+                    wip.variant(variant_index).map_err(|e| self.reflect_err(e))
+                }
+                None => Err(self.err(DeserErrorKind::NoSuchVariant {
+                    name: key.to_string(),
+                    enum_shape: shape,
+                })),
+            };
+        }
+
+        if shape.vtable.parse.is_some() {
+            wip.parse(&key).map_err(|e| self.reflect_err(e))
+        } else if shape.vtable.try_from.is_some() {
+            // Strings that aren't `String`, and wrappers around one
+            wip.put(key.to_string()).map_err(|e| self.reflect_err(e))
+        } else {
+            Err(self.err(DeserErrorKind::UnsupportedType {
+                got: shape,
+                wanted: "a map key that can be parsed from a string",
+            }))
+        }
+    }
+
     /// Handle value parsing
     fn value<'facet>(
         &mut self,
         mut wip: Wip<'facet>,
         outcome: Spanned<Outcome<'input>>,
-    ) -> Result<Wip<'facet>, DeserError<'input>> {
+    ) -> Result<Wip<'facet>, DeserError<'input>>
+    where
+        'input: 'facet,
+    {
         trace!(
             "Handling value at wip shape {} (wip innermost shape {})",
             wip.shape().blue(),
@@ -657,6 +994,11 @@ impl<'input> StackRunner<'input> {
 
         match outcome.node {
             Outcome::Scalar(Scalar::Null) => {
+                // Unit structs are written as null, and have nothing to fill in
+                if matches!(wip.shape().ty, Type::User(UserType::Struct(sd)) if sd.fields.is_empty())
+                {
+                    return Ok(wip);
+                }
                 return wip.put_default().map_err(|e| self.reflect_err(e));
             }
             _ => {
@@ -666,9 +1008,33 @@ impl<'input> StackRunner<'input> {
                     wip = wip.push_some().map_err(|e| self.reflect_err(e))?;
                     self.stack.push(Instruction::Pop(PopReason::Some));
                 }
+                if can_push_pointee(wip.shape()) {
+                    trace!("Building the pointee of {}", wip.shape().blue());
+                    wip = wip.push_pointee().map_err(|e| self.reflect_err(e))?;
+                    self.stack.push(Instruction::Pop(PopReason::Pointee));
+                }
+                if wip.shape().vtable.deserialize_from.is_some() {
+                    trace!("Deserializing {} as its proxy", wip.shape().blue());
+                    wip = wip
+                        .push_deserialize_from()
+                        .map_err(|e| self.reflect_err(e))?;
+                    self.stack.push(Instruction::Pop(PopReason::Proxy));
+                }
             }
         }
 
+        // Only the default representation of enums can be read so far: picking a variant by
+        // tag, or by what fits, needs the whole value buffered first
+        let shape = wip.innermost_shape();
+        if matches!(shape.ty, Type::User(UserType::Enum(_)))
+            && shape.enum_tagging() != EnumTagging::External
+        {
+            return Err(self.err(DeserErrorKind::UnsupportedType {
+                got: shape,
+                wanted: "an externally tagged enum",
+            }));
+        }
+
         match outcome.node {
             Outcome::Scalar(s) => {
                 wip = self.handle_scalar(wip, s)?;
@@ -707,8 +1073,8 @@ impl<'input> StackRunner<'input> {
                                     trace!("Array starting for enum ({})!", shape.blue());
                                 }
                                 UserType::Struct(_) => {
+                                    // Fields are filled one by one, so they needn't have defaults
                                     trace!("Array starting for tuple struct ({})!", shape.blue());
-                                    wip = wip.put_default().map_err(|e| self.reflect_err(e))?;
                                 }
                                 _ => {
                                     return Err(self.err(DeserErrorKind::UnsupportedType {
@@ -744,12 +1110,23 @@ impl<'input> StackRunner<'input> {
                 wip = wip.pop().map_err(|e| self.reflect_err(e))?;
             }
             Outcome::ObjectStarted => {
+                if wip.shape() == Duration::SHAPE {
+                    trace!("Deserializing {} as its parts", wip.shape().blue());
+                    wip = wip
+                        .push_proxy(DurationParts::DESERIALIZE_DURATION)
+                        .map_err(|e| self.reflect_err(e))?;
+                    self.stack.push(Instruction::Pop(PopReason::Proxy));
+                }
                 let shape = wip.innermost_shape();
                 match shape.def {
                     Def::Map(_md) => {
                         trace!("Object starting for map value ({})!", shape.blue());
                         wip = wip.put_default().map_err(|e| self.reflect_err(e))?;
                     }
+                    Def::Result(_) => {
+                        trace!("Object starting for result value ({})!", shape.blue());
+                        // nothing to do here, the key says which variant it is
+                    }
                     _ => {
                         // For non-collection types, check the Type enum
                         if let Type::User(user_ty) = shape.ty {
@@ -809,17 +1186,26 @@ impl<'input> StackRunner<'input> {
 
                 let mut ignore = false;
                 let mut needs_pop = true;
-                let mut handled_by_flatten = false;
+                // How many flattened structs were entered on the way to the field
+                let mut flatten_depth = 0;
 
                 let shape = wip.innermost_shape();
                 match shape.ty {
                     Type::User(UserType::Struct(sd)) => {
                         // First try to find a direct field match
-                        if let Some(index) = wip.field_index(&key) {
+                        if let Some(index) =
+                            deserializable_field_index(&wip, &key, self.options.get_field_names())
+                        {
                             trace!("It's a struct field");
                             wip = wip.field(index).map_err(|e| self.reflect_err(e))?;
+                        } else if let Some(path) = flattened_field_path(sd, &key) {
+                            trace!("Found key {} in flattened field", key);
+                            for &index in &path {
+                                wip = wip.field(index).map_err(|e| self.reflect_err(e))?;
+                            }
+                            flatten_depth = path.len() - 1;
                         } else {
-                            // Check for flattened fields
+                            // Check for flattened enums
                             let mut found_in_flatten = false;
                             for (index, field) in sd.fields.iter().enumerate() {
                                 if field.flags.contains(FieldFlags::FLATTEN) {
@@ -827,17 +1213,7 @@ impl<'input> StackRunner<'input> {
                                     // Enter the flattened field
                                     wip = wip.field(index).map_err(|e| self.reflect_err(e))?;
 
-                                    // Check if this flattened field has the requested key
-                                    if let Some(subfield_index) = wip.field_index(&key) {
-                                        trace!("Found key {} in flattened field", key);
-                                        wip = wip
-                                            .field(subfield_index)
-                                            .map_err(|e| self.reflect_err(e))?;
-                                        found_in_flatten = true;
-                                        handled_by_flatten = true;
-                                        break;
-                                    } else if let Some((_variant_index, _variant)) =
-                                        wip.find_variant(&key)
+                                    if let Some((_variant_index, _variant)) = wip.find_variant(&key)
                                     {
                                         trace!("Found key {} in flattened field", key);
                                         wip = wip
@@ -861,6 +1237,20 @@ impl<'input> StackRunner<'input> {
                                         field_name: key.to_string(),
                                         shape: wip.shape(),
                                     }));
+                                } else if let Some(index) = self.extra_field_index(sd) {
+                                    trace!(
+                                        "It's not a struct field, collecting it in field #{}",
+                                        index
+                                    );
+                                    wip = wip
+                                        .field(index)
+                                        .and_then(|wip| wip.begin_map_insert())
+                                        .and_then(|wip| wip.push_map_key())
+                                        .map_err(|e| self.reflect_err(e))?;
+                                    wip = self.put_map_key(wip, key)?;
+                                    wip = wip.push_map_value().map_err(|e| self.reflect_err(e))?;
+                                    // The map entry is popped along with the field
+                                    flatten_depth = 1;
                                 } else {
                                     trace!(
                                         "It's not a struct field and we're ignoring unknown fields"
@@ -882,7 +1272,11 @@ impl<'input> StackRunner<'input> {
                                     "Already have a variant selected, treating key as struct field of variant"
                                 );
                                 // Try to find the field index of the key within the selected variant
-                                if let Some(index) = wip.field_index(&key) {
+                                if let Some(index) = deserializable_field_index(
+                                    &wip,
+                                    &key,
+                                    self.options.get_field_names(),
+                                ) {
                                     trace!("Found field {} in selected variant", key.blue());
                                     wip = wip.field(index).map_err(|e| self.reflect_err(e))?;
                                 } else if wip.shape().has_deny_unknown_fields_attr() {
@@ -907,8 +1301,21 @@ impl<'input> StackRunner<'input> {
                         // Check if it's a map
                         if let Def::Map(_) = shape.def {
                             wip = wip.push_map_key().map_err(|e| self.reflect_err(e))?;
-                            wip = wip.put(key.to_string()).map_err(|e| self.reflect_err(e))?;
+                            wip = self.put_map_key(wip, key)?;
                             wip = wip.push_map_value().map_err(|e| self.reflect_err(e))?;
+                        } else if let Def::Result(_) = shape.def {
+                            // Results are externally tagged, like serde does it
+                            wip = match key.as_ref() {
+                                "Ok" => wip.push_ok(),
+                                "Err" => wip.push_err(),
+                                _ => {
+                                    return Err(self.err(DeserErrorKind::NoSuchVariant {
+                                        name: key.to_string(),
+                                        enum_shape: shape,
+                                    }));
+                                }
+                            }
+                            .map_err(|e| self.reflect_err(e))?;
                         } else {
                             return Err(self.err(DeserErrorKind::Unimplemented(
                                 "object key for non-struct/map",
@@ -917,19 +1324,29 @@ impl<'input> StackRunner<'input> {
                     }
                 }
 
+                if needs_pop && !ignore && wip.deserialize_with().is_some() {
+                    trace!("Field is deserialized as a proxy");
+                    wip = wip
+                        .push_deserialize_with()
+                        .map_err(|e| self.reflect_err(e))?;
+                    // The proxy is popped, and converted to the field, before the field is
+                    flatten_depth += 1;
+                }
+
                 self.stack.push(Instruction::ObjectKeyOrObjectClose);
                 if ignore {
                     self.stack.push(Instruction::SkipValue);
                 } else {
-                    if needs_pop && !handled_by_flatten {
-                        trace!("Pushing Pop insn to stack (ObjectVal)");
-                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
-                    } else if handled_by_flatten {
-                        // We need two pops for flattened fields - one for the field itself,
-                        // one for the containing struct
-                        trace!("Pushing Pop insn to stack (ObjectVal) for flattened field");
-                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
-                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                    if needs_pop {
+                        // One pop for the field itself, one for each flattened struct it's
+                        // in, and one for its proxy if it has one
+                        trace!(
+                            "Pushing {} Pop insn(s) to stack (ObjectVal)",
+                            flatten_depth + 1
+                        );
+                        for _ in 0..=flatten_depth {
+                            self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                        }
                     }
                     self.stack.push(Instruction::Value(ValueReason::ObjectVal));
                 }
@@ -946,6 +1363,16 @@ impl<'input> StackRunner<'input> {
         }
     }
 
+    /// The field of `sd` that collects unknown keys, if they're to be collected
+    fn extra_field_index(&self, sd: StructType) -> Option<usize> {
+        if self.options.get_unknown_fields() != UnknownFields::Collect {
+            return None;
+        }
+        sd.fields
+            .iter()
+            .position(|field| field.flags.contains(FieldFlags::EXTRA))
+    }
+
     fn list_item_or_list_close<'facet>(
         &mut self,
         mut wip: Wip<'facet>,
@@ -954,12 +1381,31 @@ impl<'input> StackRunner<'input> {
     where
         'input: 'facet,
     {
+        // Arrays are built in place, one item at a time, so they must get exactly as many
+        let array_len = match wip.shape().def {
+            Def::Array(array_def) => Some(array_def.n),
+            _ => None,
+        };
         match outcome.node {
             Outcome::ListEnded => {
                 trace!("List close");
+                if let Some(expected) = array_len.filter(|&n| n != wip.pushed_count()) {
+                    return Err(self.err(DeserErrorKind::ArrayLength {
+                        shape: wip.shape(),
+                        expected,
+                        got: wip.pushed_count(),
+                    }));
+                }
                 Ok(wip)
             }
             _ => {
+                if let Some(expected) = array_len.filter(|&n| n == wip.pushed_count()) {
+                    return Err(self.err(DeserErrorKind::ArrayLength {
+                        shape: wip.shape(),
+                        expected,
+                        got: expected + 1,
+                    }));
+                }
                 self.stack.push(Instruction::ListItemOrListClose);
                 self.stack.push(Instruction::Pop(PopReason::ListVal));
 
@@ -991,3 +1437,103 @@ impl<'input> StackRunner<'input> {
         }
     }
 }
+
+/// Allocates a temporary value next to `wip`: in its arena, if it has one
+fn alloc_temporary<'facet>(
+    wip: &Wip<'facet>,
+    shape: &'static Shape,
+) -> Result<Wip<'facet>, ReflectError> {
+    match wip.arena() {
+        Some(arena) => Wip::alloc_shape_in(shape, arena),
+        None => Wip::alloc_shape(shape),
+    }
+}
+
+/// Like [`Wip::field_index`], but fields that are skipped when deserializing aren't found
+fn deserializable_field_index(wip: &Wip<'_>, key: &str, field_names: FieldNames) -> Option<usize> {
+    let fields = match wip.shape().ty {
+        Type::User(UserType::Struct(sd)) => sd.fields,
+        Type::User(UserType::Enum(_)) => wip.selected_variant()?.data.fields,
+        _ => return None,
+    };
+    let deserializable = |index: &usize| {
+        !fields[*index]
+            .flags
+            .contains(FieldFlags::SKIP_DESERIALIZING)
+    };
+    match wip.field_index(key).filter(deserializable) {
+        Some(index) => Some(index),
+        None if field_names == FieldNames::CaseInsensitive => (0..fields.len())
+            .filter(deserializable)
+            .find(|&index| fields[index].name.eq_ignore_ascii_case(key)),
+        None => None,
+    }
+}
+
+/// Finds a key among the fields of flattened structs, however deeply they're nested. Returns
+/// the indices of the fields to go through, ending with the field the key names.
+fn flattened_field_path(sd: StructType, key: &str) -> Option<Vec<usize>> {
+    sd.fields.iter().enumerate().find_map(|(index, field)| {
+        if !field.flags.contains(FieldFlags::FLATTEN) {
+            return None;
+        }
+        let Type::User(UserType::Struct(inner)) = field.shape().ty else {
+            return None;
+        };
+        let mut path = match inner.field_index(key) {
+            Some(inner_index)
+                if !inner.fields[inner_index]
+                    .flags
+                    .contains(FieldFlags::SKIP_DESERIALIZING) =>
+            {
+                vec![inner_index]
+            }
+            _ => flattened_field_path(inner, key)?,
+        };
+        path.insert(0, index);
+        Some(path)
+    })
+}
+
+/// Whether `shape` is a number, which can be read from a string
+fn is_number(shape: &'static Shape) -> bool {
+    match shape.def {
+        Def::Scalar(sd) => {
+            matches!(sd.affinity, ScalarAffinity::Number(_)) && shape.vtable.parse.is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Whether `shape` is a scalar other than a string that can be parsed, like an `IpAddr`
+fn is_parsed_scalar(shape: &'static Shape) -> bool {
+    match shape.def {
+        Def::Scalar(sd) => {
+            !matches!(sd.affinity, ScalarAffinity::String(_)) && shape.vtable.parse.is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Whether `shape` is a list or array of `u8`, which can be read from a base64 string
+fn is_byte_list(shape: &'static Shape) -> bool {
+    match shape.def {
+        Def::List(ld) => ld.t() == u8::SHAPE,
+        Def::Array(ad) => ad.t() == u8::SHAPE,
+        _ => false,
+    }
+}
+
+/// Whether `shape` is a smart pointer whose pointee can be built in place, then moved into it
+fn can_push_pointee(shape: &'static Shape) -> bool {
+    match shape.def {
+        Def::SmartPointer(sd) => {
+            sd.vtable.new_into_fn.is_some()
+                && !sd.flags.contains(SmartPointerFlags::WEAK)
+                && sd
+                    .pointee()
+                    .is_some_and(|pointee| pointee.layout.sized_layout().is_ok())
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,94 @@
+/// Adjusts how the input is matched to the value being deserialized
+///
+/// The default matches object keys to field names exactly, and ignores the keys that
+/// match no field (unless the struct has `#[facet(deny_unknown_fields)]`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    field_names: FieldNames,
+    unknown_fields: UnknownFields,
+    unix_timestamps: UnixTimestamps,
+}
+
+impl DeserializeOptions {
+    /// The default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Picks how object keys are matched to field names
+    pub fn field_names(mut self, field_names: FieldNames) -> Self {
+        self.field_names = field_names;
+        self
+    }
+
+    /// Picks what happens to object keys that match no field
+    pub fn unknown_fields(mut self, unknown_fields: UnknownFields) -> Self {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    /// Picks what numbers read into timestamps and datetimes count
+    pub fn unix_timestamps(mut self, unix_timestamps: UnixTimestamps) -> Self {
+        self.unix_timestamps = unix_timestamps;
+        self
+    }
+
+    /// Returns how object keys are matched to field names
+    pub fn get_field_names(&self) -> FieldNames {
+        self.field_names
+    }
+
+    /// Returns what happens to object keys that match no field
+    pub fn get_unknown_fields(&self) -> UnknownFields {
+        self.unknown_fields
+    }
+
+    /// Returns what numbers read into timestamps and datetimes count
+    pub fn get_unix_timestamps(&self) -> UnixTimestamps {
+        self.unix_timestamps
+    }
+}
+
+/// How object keys are matched to field names, see [`DeserializeOptions::field_names`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FieldNames {
+    /// Keys must be the field names exactly
+    #[default]
+    Exact,
+    /// Keys may differ from the field names in ASCII case, e.g. `userName` for `username`. A key
+    /// that is one of the field names exactly still goes to that field.
+    CaseInsensitive,
+}
+
+/// What happens to object keys that match no field, see [`DeserializeOptions::unknown_fields`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownFields {
+    /// They're skipped, along with their values
+    #[default]
+    Ignore,
+    /// They go in the struct's map field marked `#[facet(extra)]`, along with their values, or
+    /// are skipped if it has none
+    Collect,
+}
+
+/// What a number read into a timestamp or datetime that stands for an instant, like a
+/// `chrono::DateTime<Utc>`, counts since the unix epoch, see
+/// [`DeserializeOptions::unix_timestamps`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnixTimestamps {
+    /// Seconds, which may have a fractional part
+    #[default]
+    Seconds,
+    /// Milliseconds, which may have a fractional part
+    Millis,
+}
+
+impl UnixTimestamps {
+    /// How many nanoseconds one unit is
+    pub(crate) fn nanos_per_unit(self) -> i128 {
+        match self {
+            UnixTimestamps::Seconds => 1_000_000_000,
+            UnixTimestamps::Millis => 1_000_000,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+use facet_reflect::Wip;
+
+use crate::{
+    DeserError, DeserErrorKind, DeserializeOptions, Expectation, Format, NextData, Outcome, Span,
+    Spanned, StackRunner,
+};
+
+/// Reads the events of a [`Format`] one at a time, without building a value out of them.
+///
+/// This is what [`deserialize`](crate::deserialize) is driven by, for when the events are
+/// going somewhere else — another format's serializer, say.
+pub struct EventReader<'input, F> {
+    input: &'input [u8],
+    format: F,
+    last_span: Span,
+    // `Format::next` carries a `Wip` along, even though formats don't look at it
+    wip: Option<Wip<'input>>,
+}
+
+impl<'input, F: Format> EventReader<'input, F> {
+    /// Starts reading `input` from the beginning
+    pub fn new(input: &'input [u8], format: F) -> Self {
+        Self {
+            input,
+            format,
+            last_span: Span::new(0, 0),
+            wip: Some(Wip::alloc::<()>().expect("`()` is sized")),
+        }
+    }
+
+    /// Reads the next event, which should be what `expectation` says
+    pub fn next(
+        &mut self,
+        expectation: Expectation,
+    ) -> Result<Spanned<Outcome<'input>>, DeserError<'input>> {
+        let nd = self.next_data();
+        let (nd, res) = self.format.next(nd, expectation);
+        self.wip = Some(nd.wip);
+        let outcome = res.map_err(|err| self.error_at(err.node, err.span))?;
+        self.last_span = outcome.span;
+        Ok(outcome)
+    }
+
+    /// Skips the next value, containers included
+    pub fn skip(&mut self) -> Result<Span, DeserError<'input>> {
+        let nd = self.next_data();
+        let (nd, res) = self.format.skip(nd);
+        self.wip = Some(nd.wip);
+        let span = res.map_err(|err| self.error_at(err.node, err.span))?;
+        self.last_span = span;
+        Ok(span)
+    }
+
+    /// Where the next event will be read from
+    pub fn offset(&self) -> usize {
+        self.last_span.end()
+    }
+
+    /// Goes back (or forward) to an offset returned by [`Self::offset`]
+    pub fn rewind(&mut self, offset: usize) {
+        self.last_span = Span::new(offset, 0);
+    }
+
+    /// The whole input, from the start
+    pub fn input(&self) -> &'input [u8] {
+        self.input
+    }
+
+    /// An error pointing at the last event read
+    pub fn error(&self, kind: DeserErrorKind) -> DeserError<'input> {
+        self.error_at(kind, self.last_span)
+    }
+
+    /// An error pointing at `span`
+    pub fn error_at(&self, kind: DeserErrorKind, span: Span) -> DeserError<'input> {
+        DeserError::new(kind, self.input, span)
+    }
+
+    fn next_data(&mut self) -> NextData<'input, 'input> {
+        let wip = self
+            .wip
+            .take()
+            .expect("the Wip is put back after each read");
+        NextData {
+            start: self.last_span.end(),
+            runner: StackRunner {
+                original_input: self.input,
+                input: self.input,
+                stack: Vec::new(),
+                last_span: self.last_span,
+                path: Vec::new(),
+                options: DeserializeOptions::default(),
+            },
+            wip,
+        }
+    }
+}
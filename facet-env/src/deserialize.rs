@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use facet_core::{Characteristic, Def, Facet, Field, FieldFlags, Shape, Type, UserType};
+use facet_reflect::{ScalarType, Wip};
+use log::trace;
+
+use crate::EnvError;
+
+/// How field names map to variable names
+///
+/// By default there's no prefix and nested fields are separated by `__`, so `db.host` is read
+/// from `DB__HOST`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvOptions {
+    prefix: Option<String>,
+    separator: String,
+}
+
+impl Default for EnvOptions {
+    fn default() -> Self {
+        Self {
+            prefix: None,
+            separator: "__".to_string(),
+        }
+    }
+}
+
+impl EnvOptions {
+    /// No prefix, `__` between nested fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only reads variables starting with `prefix` and the separator: with `APP`, `db.host`
+    /// is read from `APP__DB__HOST`
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// What goes between the names of nested fields, `__` by default
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// The variable for a field named `name`, under the variable name `parent`
+    fn join(&self, parent: Option<&str>, name: &str) -> String {
+        let name = name.to_uppercase();
+        match parent {
+            Some(parent) => format!("{}{}{}", parent, self.separator, name),
+            None => name,
+        }
+    }
+}
+
+/// Fills a struct from the environment variables of the process.
+///
+/// Each field is read from the variable named after it in uppercase, so `port` comes from
+/// `PORT`; fields of nested structs are joined with `__`, so `db.host` comes from `DB__HOST`.
+/// Values are parsed with the field type's parser. `Option` fields are `None` when their
+/// variable isn't set, fields marked `#[facet(default)]` take their default, and `Vec`
+/// fields are read from a comma-separated list.
+///
+/// # Example
+///
+/// ```no_run
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Config {
+///     port: u16,
+///     log_level: Option<String>,
+/// }
+///
+/// // With PORT=8080 and LOG_LEVEL unset
+/// let config: Config = facet_env::from_env().unwrap();
+/// ```
+pub fn from_env<'facet, T: Facet<'facet>>() -> Result<T, EnvError> {
+    from_env_with(&EnvOptions::default())
+}
+
+/// Fills a struct from the environment variables of the process, named as `options` says.
+///
+/// Variables whose name or value isn't valid UTF-8 are ignored.
+pub fn from_env_with<'facet, T: Facet<'facet>>(options: &EnvOptions) -> Result<T, EnvError> {
+    let vars = std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    from_vars(vars, options)
+}
+
+/// Fills a struct from the given variables, like [`from_env_with`] does from the process
+/// environment.
+pub fn from_vars<'facet, T, I, K, V>(vars: I, options: &EnvOptions) -> Result<T, EnvError>
+where
+    T: Facet<'facet>,
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let vars: HashMap<String, String> = vars
+        .into_iter()
+        .map(|(name, value)| (name.into(), value.into()))
+        .collect();
+
+    let wip = Wip::alloc::<T>()?;
+    let Type::User(UserType::Struct(sd)) = wip.shape().ty else {
+        return Err(EnvError::UnsupportedShape(
+            "Unsupported root type".to_string(),
+        ));
+    };
+    let prefix = options.prefix.as_deref();
+    let wip = fill_struct(wip, sd.fields, prefix, &vars, options)?;
+    Ok(wip.build()?.materialize::<T>()?)
+}
+
+fn fill_struct<'facet>(
+    mut wip: Wip<'facet>,
+    fields: &'static [Field],
+    parent: Option<&str>,
+    vars: &HashMap<String, String>,
+    options: &EnvOptions,
+) -> Result<Wip<'facet>, EnvError> {
+    for (index, field) in fields.iter().enumerate() {
+        let name = options.join(parent, field.name);
+        trace!("Reading field {} from {}", field.name, name);
+        // Nested structs are walked into even with nothing set, so the error names the
+        // first variable that's missing rather than the struct
+        let nested = matches!(field.shape().ty, Type::User(UserType::Struct(_)))
+            && !matches!(field.shape().def, Def::Option(_))
+            && !field.flags.contains(FieldFlags::DEFAULT);
+
+        if !nested && !is_set(field.shape(), &name, vars, options) {
+            wip = fill_missing(wip, index, field, &name)?;
+            continue;
+        }
+
+        wip = wip.field(index)?;
+        if let Def::Option(_) = field.shape().def {
+            wip = wip.push_some()?;
+            wip = fill_value(wip, &name, vars, options)?;
+            wip = wip.pop()?;
+        } else {
+            wip = fill_value(wip, &name, vars, options)?;
+        }
+        wip = wip.pop()?;
+    }
+    Ok(wip)
+}
+
+/// Whether the variable for a value is set, or for structs, the variable of any of its fields
+fn is_set(
+    shape: &'static Shape,
+    name: &str,
+    vars: &HashMap<String, String>,
+    options: &EnvOptions,
+) -> bool {
+    let shape = match shape.def {
+        Def::Option(od) => od.t(),
+        _ => shape,
+    };
+    match (shape.def, shape.ty) {
+        (Def::Scalar(_) | Def::List(_), _) => vars.contains_key(name),
+        (_, Type::User(UserType::Struct(_))) => {
+            let nested = format!("{}{}", name, options.separator);
+            vars.keys().any(|key| key.starts_with(&nested))
+        }
+        _ => vars.contains_key(name),
+    }
+}
+
+/// Fills a value whose variable, or one of whose fields' variables, is set
+fn fill_value<'facet>(
+    wip: Wip<'facet>,
+    name: &str,
+    vars: &HashMap<String, String>,
+    options: &EnvOptions,
+) -> Result<Wip<'facet>, EnvError> {
+    let shape = wip.shape();
+    match (shape.def, shape.ty) {
+        (Def::Scalar(_), _) => put_scalar(wip, name, &vars[name]),
+        (Def::List(_), _) => {
+            let value = vars[name].trim();
+            if value.is_empty() {
+                return Ok(wip.put_empty_list()?);
+            }
+            let mut wip = wip.begin_pushback()?;
+            for item in value.split(',') {
+                wip = wip.push()?;
+                wip = put_scalar(wip, name, item.trim())?;
+                wip = wip.pop()?;
+            }
+            Ok(wip)
+        }
+        (_, Type::User(UserType::Struct(sd))) => {
+            fill_struct(wip, sd.fields, Some(name), vars, options)
+        }
+        _ => Err(EnvError::UnsupportedShape(format!(
+            "Can't read {} from `{}`",
+            shape, name
+        ))),
+    }
+}
+
+fn put_scalar<'facet>(wip: Wip<'facet>, name: &str, value: &str) -> Result<Wip<'facet>, EnvError> {
+    let shape = wip.shape();
+    let invalid = || EnvError::InvalidValue(name.to_string(), value.to_string(), shape.to_string());
+
+    if shape.is_type::<String>() {
+        return Ok(wip.put(value.to_string())?);
+    }
+    if let Some(ScalarType::Bool) = ScalarType::try_from_shape(shape) {
+        // The spellings shells and container runtimes commonly use
+        let b = match value.to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => true,
+            "false" | "0" | "no" | "off" => false,
+            _ => return Err(invalid()),
+        };
+        return Ok(wip.put(b)?);
+    }
+    wip.parse(value).map_err(|_| invalid())
+}
+
+/// Puts what a field gets when nothing is set for it: its default, `None` for options,
+/// otherwise an error naming the variable
+fn fill_missing<'facet>(
+    wip: Wip<'facet>,
+    index: usize,
+    field: &Field,
+    name: &str,
+) -> Result<Wip<'facet>, EnvError> {
+    if field.flags.contains(FieldFlags::DEFAULT) {
+        let wip = wip.field(index)?;
+        let wip = match field.vtable.default_fn {
+            Some(default_in_place_fn) => wip.put_from_fn(default_in_place_fn)?,
+            None if field.shape().is(Characteristic::Default) => wip.put_default()?,
+            None => {
+                return Err(EnvError::UnsupportedShape(format!(
+                    "Field '{}' has a default attribute but its type has no default",
+                    field.name
+                )));
+            }
+        };
+        return Ok(wip.pop()?);
+    }
+    if let Def::Option(_) = field.shape().def {
+        return Ok(wip.field(index)?.put_default()?.pop()?);
+    }
+    Err(EnvError::Missing(name.to_string()))
+}
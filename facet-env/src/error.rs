@@ -0,0 +1,37 @@
+use facet_reflect::ReflectError;
+
+/// Errors that can occur when filling a value from environment variables.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EnvError {
+    /// A variable for a field without a default isn't set.
+    Missing(String),
+    /// A variable couldn't be parsed as the type of its field: the variable, its value and
+    /// the type.
+    InvalidValue(String, String, String),
+    /// The shape can't be read from environment variables.
+    UnsupportedShape(String),
+    /// Reflection error
+    ReflectError(ReflectError),
+}
+
+impl From<ReflectError> for EnvError {
+    fn from(err: ReflectError) -> Self {
+        EnvError::ReflectError(err)
+    }
+}
+
+impl core::fmt::Display for EnvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EnvError::Missing(name) => write!(f, "Environment variable `{}` is not set", name),
+            EnvError::InvalidValue(name, value, ty) => {
+                write!(f, "Invalid {} in `{}`: '{}'", ty, name, value)
+            }
+            EnvError::UnsupportedShape(shape) => write!(f, "Unsupported shape: {}", shape),
+            EnvError::ReflectError(err) => write!(f, "Reflection error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
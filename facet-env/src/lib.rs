@@ -0,0 +1,9 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod error;
+pub use error::*;
+
+mod deserialize;
+pub use deserialize::*;
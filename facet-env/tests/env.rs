@@ -0,0 +1,127 @@
+use eyre::Result;
+use facet::Facet;
+use facet_env::{EnvError, EnvOptions, from_vars};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Database {
+    host: String,
+    port: u16,
+    #[facet(default)]
+    pool_size: u32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    debug: bool,
+    log_level: Option<String>,
+    allowed_hosts: Vec<String>,
+    db: Database,
+}
+
+#[test]
+fn test_nested_with_prefix() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let vars = [
+        ("APP__DEBUG", "1"),
+        ("APP__ALLOWED_HOSTS", "example.com, example.org"),
+        ("APP__DB__HOST", "localhost"),
+        ("APP__DB__PORT", "5432"),
+        ("DB__HOST", "not this one"),
+        ("HOME", "/root"),
+    ];
+    let config: Config = from_vars(vars, &EnvOptions::new().prefix("APP"))?;
+    assert_eq!(
+        config,
+        Config {
+            debug: true,
+            log_level: None,
+            allowed_hosts: vec!["example.com".to_string(), "example.org".to_string()],
+            db: Database {
+                host: "localhost".to_string(),
+                port: 5432,
+                pool_size: 0,
+            },
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_separator_and_options() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let vars = [
+        ("DEBUG", "false"),
+        ("LOG_LEVEL", "info"),
+        ("ALLOWED_HOSTS", ""),
+        ("DB_HOST", "db.internal"),
+        ("DB_PORT", "6543"),
+        ("DB_POOL_SIZE", "16"),
+    ];
+    let config: Config = from_vars(vars, &EnvOptions::new().separator("_"))?;
+    assert!(!config.debug);
+    assert_eq!(config.log_level.as_deref(), Some("info"));
+    assert!(config.allowed_hosts.is_empty());
+    assert_eq!(config.db.host, "db.internal");
+    assert_eq!(config.db.port, 6543);
+    assert_eq!(config.db.pool_size, 16);
+
+    Ok(())
+}
+
+#[test]
+fn test_optional_nested_struct() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Config {
+        name: String,
+        replica: Option<Database>,
+    }
+
+    let config: Config = from_vars([("NAME", "main")], &EnvOptions::new())?;
+    assert_eq!(config.replica, None);
+
+    let vars = [
+        ("NAME", "main"),
+        ("REPLICA__HOST", "replica"),
+        ("REPLICA__PORT", "5433"),
+    ];
+    let config: Config = from_vars(vars, &EnvOptions::new())?;
+    assert_eq!(config.replica.map(|db| db.port), Some(5433));
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    facet_testhelpers::setup();
+
+    let vars = [("DEBUG", "true"), ("ALLOWED_HOSTS", "a"), ("DB__HOST", "x")];
+    let err = from_vars::<Config, _, _, _>(vars, &EnvOptions::new()).unwrap_err();
+    assert!(matches!(&err, EnvError::Missing(name) if name == "DB__PORT"));
+    assert_eq!(
+        err.to_string(),
+        "Environment variable `DB__PORT` is not set"
+    );
+
+    let vars = [
+        ("DEBUG", "maybe"),
+        ("ALLOWED_HOSTS", "a"),
+        ("DB__HOST", "x"),
+        ("DB__PORT", "1"),
+    ];
+    let err = from_vars::<Config, _, _, _>(vars, &EnvOptions::new()).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid bool in `DEBUG`: 'maybe'");
+
+    let vars = [
+        ("DEBUG", "true"),
+        ("ALLOWED_HOSTS", "a"),
+        ("DB__HOST", "x"),
+        ("DB__PORT", "99999"),
+    ];
+    let err = from_vars::<Config, _, _, _>(vars, &EnvOptions::new()).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid u16 in `DB__PORT`: '99999'");
+}
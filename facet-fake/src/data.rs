@@ -0,0 +1,120 @@
+//! Word lists fake values are assembled from
+
+pub(crate) const FIRST_NAMES: &[&str] = &[
+    "Alice", "Amara", "Bruno", "Chen", "Dana", "Diego", "Elena", "Farah", "Gustav", "Hana", "Ivan",
+    "Jamal", "Keiko", "Lars", "Maya", "Nikolai", "Olu", "Priya", "Quentin", "Rosa", "Sami",
+    "Tomás", "Uma", "Viktor", "Wen", "Yara", "Zoe",
+];
+
+pub(crate) const LAST_NAMES: &[&str] = &[
+    "Abara",
+    "Berg",
+    "Castillo",
+    "Dubois",
+    "Eriksen",
+    "Fischer",
+    "García",
+    "Haddad",
+    "Ito",
+    "Jensen",
+    "Kowalski",
+    "Lindqvist",
+    "Moreau",
+    "Nakamura",
+    "Okafor",
+    "Petrov",
+    "Quinn",
+    "Rossi",
+    "Silva",
+    "Tanaka",
+    "Underwood",
+    "Varga",
+    "Wójcik",
+    "Yilmaz",
+    "Zhang",
+];
+
+pub(crate) const CITIES: &[&str] = &[
+    "Amsterdam",
+    "Austin",
+    "Buenos Aires",
+    "Cape Town",
+    "Hanoi",
+    "Lagos",
+    "Lyon",
+    "Melbourne",
+    "Montréal",
+    "Nairobi",
+    "Osaka",
+    "Porto",
+    "Reykjavík",
+    "Seoul",
+    "Tallinn",
+    "Valparaíso",
+];
+
+pub(crate) const COUNTRIES: &[&str] = &[
+    "Argentina",
+    "Australia",
+    "Canada",
+    "Chile",
+    "Estonia",
+    "France",
+    "Iceland",
+    "Japan",
+    "Kenya",
+    "Netherlands",
+    "Nigeria",
+    "Portugal",
+    "South Africa",
+    "South Korea",
+    "United States",
+    "Vietnam",
+];
+
+pub(crate) const STREET_SUFFIXES: &[&str] = &["Street", "Avenue", "Road", "Lane", "Way", "Place"];
+
+pub(crate) const COMPANY_SUFFIXES: &[&str] =
+    &["Labs", "Systems", "& Co.", "Industries", "GmbH", "Inc."];
+
+/// Reserved for documentation by RFC 2606, so they never point at anyone real
+pub(crate) const DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+pub(crate) const EXTENSIONS: &[&str] = &["txt", "json", "toml", "log", "csv", "md"];
+
+pub(crate) const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+];
@@ -0,0 +1,414 @@
+use facet_arbitrary::Source;
+
+use crate::data::*;
+
+/// A kind of fake value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// `maya.okafor@example.com`
+    Email,
+    /// `https://tempor.example.org/labore/sed`
+    Url,
+    /// A random (version 4) UUID
+    Uuid,
+    /// A random ULID
+    Ulid,
+    /// `10.12.0.7`, from the private and documentation ranges
+    Ipv4,
+    /// `fd00::3:a1f`, from the unique local and documentation ranges
+    Ipv6,
+    /// An IPv4 or IPv6 address
+    Ip,
+    /// `192.168.1.20:8080`
+    SocketAddr,
+    /// `2021-06-04T17:23:05Z` (RFC 3339), between 2000 and 2030
+    Timestamp,
+    /// `2021-06-04`, between 2000 and 2030
+    Date,
+    /// `Elena`
+    FirstName,
+    /// `Nakamura`
+    LastName,
+    /// `Elena Nakamura`
+    Name,
+    /// `elena_nakamura42`
+    Username,
+    /// `+1-555-0142` — 555-01xx numbers are reserved for fiction
+    Phone,
+    /// `Rossi Labs`
+    Company,
+    /// `42 Moreau Avenue`
+    Street,
+    /// `Tallinn`
+    City,
+    /// `Portugal`
+    Country,
+    /// `98107`
+    PostalCode,
+    /// `/home/maya/tempor/labore.json`
+    Path,
+    /// `#3fa2c7`
+    Color,
+    /// `magna`
+    Word,
+    /// `Lorem ipsum dolor sit amet.`
+    Sentence,
+    /// A few sentences
+    Paragraph,
+}
+
+impl Kind {
+    /// Looks up a kind by the name used in `#[facet(fake = "...")]`: `email`, `first_name`…
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "email" => Kind::Email,
+            "url" => Kind::Url,
+            "uuid" => Kind::Uuid,
+            "ulid" => Kind::Ulid,
+            "ipv4" => Kind::Ipv4,
+            "ipv6" => Kind::Ipv6,
+            "ip" => Kind::Ip,
+            "socket_addr" => Kind::SocketAddr,
+            "timestamp" => Kind::Timestamp,
+            "date" => Kind::Date,
+            "first_name" => Kind::FirstName,
+            "last_name" => Kind::LastName,
+            "name" => Kind::Name,
+            "username" => Kind::Username,
+            "phone" => Kind::Phone,
+            "company" => Kind::Company,
+            "street" => Kind::Street,
+            "city" => Kind::City,
+            "country" => Kind::Country,
+            "postal_code" => Kind::PostalCode,
+            "path" => Kind::Path,
+            "color" => Kind::Color,
+            "word" => Kind::Word,
+            "sentence" => Kind::Sentence,
+            "paragraph" => Kind::Paragraph,
+            _ => return None,
+        })
+    }
+
+    /// Guesses what a string field holds from its name: `email`, `homepage_url`, `createdAt`…
+    pub fn guess(field_name: &str) -> Option<Self> {
+        let words = split_words(field_name);
+        let has = |w: &str| words.iter().any(|word| word == w);
+        let last = words.last().map(String::as_str).unwrap_or_default();
+
+        // More specific words first: `email_address` is an email, `company_name` a company
+        let kind = if last == "at" || has("timestamp") {
+            // `created_at`, `last_login_at`…
+            Kind::Timestamp
+        } else if has("email") || has("mail") {
+            Kind::Email
+        } else if has("url") || has("uri") || has("website") || has("homepage") || has("link") {
+            Kind::Url
+        } else if has("uuid") || has("guid") || last == "id" {
+            Kind::Uuid
+        } else if has("ulid") {
+            Kind::Ulid
+        } else if has("ipv6") {
+            Kind::Ipv6
+        } else if has("ip") || has("ipv4") {
+            Kind::Ipv4
+        } else if has("phone") || has("mobile") || has("tel") {
+            Kind::Phone
+        } else if has("company") || has("organization") || has("employer") {
+            Kind::Company
+        } else if has("username") || has("login") || has("handle") || last == "user" {
+            Kind::Username
+        } else if has("first") || has("given") || has("firstname") {
+            Kind::FirstName
+        } else if has("last") || has("surname") || has("family") || has("lastname") {
+            Kind::LastName
+        } else if has("city") || has("town") {
+            Kind::City
+        } else if has("country") {
+            Kind::Country
+        } else if has("zip") || has("postcode") || (has("postal") && has("code")) {
+            Kind::PostalCode
+        } else if has("street") || has("address") {
+            Kind::Street
+        } else if has("name") || has("author") || has("owner") {
+            Kind::Name
+        } else if has("date") || has("birthday") || has("dob") {
+            Kind::Date
+        } else if has("time") {
+            Kind::Timestamp
+        } else if has("path") || has("file") || has("filename") || has("dir") {
+            Kind::Path
+        } else if has("color") || has("colour") {
+            Kind::Color
+        } else if has("description") || has("bio") || has("about") || has("body") {
+            Kind::Paragraph
+        } else if has("title") || has("summary") || has("comment") || has("message") {
+            Kind::Sentence
+        } else if has("tag") || has("label") || has("slug") || has("keyword") {
+            Kind::Word
+        } else {
+            return None;
+        };
+        Some(kind)
+    }
+
+    /// Generates a value of this kind
+    pub fn generate(self, source: &mut dyn Source) -> String {
+        match self {
+            Kind::Email => {
+                let first = pick(source, FIRST_NAMES);
+                let last = pick(source, LAST_NAMES);
+                format!(
+                    "{}.{}@{}",
+                    ascii_lowercase(first),
+                    ascii_lowercase(last),
+                    pick(source, DOMAINS)
+                )
+            }
+            Kind::Url => {
+                let mut url = format!("https://{}.{}", pick(source, WORDS), pick(source, DOMAINS));
+                for _ in 0..source.below(3) {
+                    url.push('/');
+                    url.push_str(pick(source, WORDS));
+                }
+                url
+            }
+            Kind::Uuid => {
+                let mut bytes = bytes::<16>(source);
+                bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+                bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+                let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..32]
+                )
+            }
+            Kind::Ulid => {
+                const CROCKFORD: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+                // 128 bits make 26 characters, the first of which only holds 3 bits
+                let mut ulid = String::with_capacity(26);
+                ulid.push(CROCKFORD[source.below(8)] as char);
+                for _ in 1..26 {
+                    ulid.push(CROCKFORD[source.below(32)] as char);
+                }
+                ulid
+            }
+            Kind::Ipv4 => {
+                let [a, b, c] = bytes::<3>(source);
+                match source.below(4) {
+                    0 => format!("10.{a}.{b}.{}", c.max(1)),
+                    1 => format!("172.{}.{b}.{}", 16 + a % 16, c.max(1)),
+                    2 => format!("192.168.{b}.{}", c.max(1)),
+                    _ => format!("203.0.113.{}", c.max(1)),
+                }
+            }
+            Kind::Ipv6 => {
+                let [a, b] = [source.next_u64() as u16, source.next_u64() as u16];
+                if source.chance(0.5) {
+                    format!("fd00::{a:x}:{b:x}")
+                } else {
+                    format!("2001:db8::{a:x}:{b:x}")
+                }
+            }
+            Kind::Ip => {
+                let kind = if source.chance(0.5) {
+                    Kind::Ipv4
+                } else {
+                    Kind::Ipv6
+                };
+                kind.generate(source)
+            }
+            Kind::SocketAddr => {
+                let port = 1024 + source.below(64512);
+                if source.chance(0.75) {
+                    format!("{}:{port}", Kind::Ipv4.generate(source))
+                } else {
+                    format!("[{}]:{port}", Kind::Ipv6.generate(source))
+                }
+            }
+            Kind::Timestamp => {
+                let (year, month, day) = date(source);
+                format!(
+                    "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+                    source.below(24),
+                    source.below(60),
+                    source.below(60)
+                )
+            }
+            Kind::Date => {
+                let (year, month, day) = date(source);
+                format!("{year:04}-{month:02}-{day:02}")
+            }
+            Kind::FirstName => pick(source, FIRST_NAMES).to_string(),
+            Kind::LastName => pick(source, LAST_NAMES).to_string(),
+            Kind::Name => format!("{} {}", pick(source, FIRST_NAMES), pick(source, LAST_NAMES)),
+            Kind::Username => {
+                let first = ascii_lowercase(pick(source, FIRST_NAMES));
+                let last = ascii_lowercase(pick(source, LAST_NAMES));
+                match source.below(3) {
+                    0 => format!("{first}{}", source.below(100)),
+                    1 => format!("{first}_{last}"),
+                    _ => format!("{}{last}", &first[..1]),
+                }
+            }
+            Kind::Phone => format!(
+                "+1-{}{:02}-555-01{:02}",
+                2 + source.below(8),
+                source.below(100),
+                source.below(100)
+            ),
+            Kind::Company => format!(
+                "{} {}",
+                pick(source, LAST_NAMES),
+                pick(source, COMPANY_SUFFIXES)
+            ),
+            Kind::Street => format!(
+                "{} {} {}",
+                1 + source.below(999),
+                pick(source, LAST_NAMES),
+                pick(source, STREET_SUFFIXES)
+            ),
+            Kind::City => pick(source, CITIES).to_string(),
+            Kind::Country => pick(source, COUNTRIES).to_string(),
+            Kind::PostalCode => format!("{:05}", 10000 + source.below(90000)),
+            Kind::Path => {
+                let user = ascii_lowercase(pick(source, FIRST_NAMES));
+                format!(
+                    "/home/{user}/{}/{}.{}",
+                    pick(source, WORDS),
+                    pick(source, WORDS),
+                    pick(source, EXTENSIONS)
+                )
+            }
+            Kind::Color => {
+                let [r, g, b] = bytes::<3>(source);
+                format!("#{r:02x}{g:02x}{b:02x}")
+            }
+            Kind::Word => pick(source, WORDS).to_string(),
+            Kind::Sentence => sentence(source),
+            Kind::Paragraph => {
+                let sentences: Vec<String> =
+                    (0..2 + source.below(3)).map(|_| sentence(source)).collect();
+                sentences.join(" ")
+            }
+        }
+    }
+}
+
+fn pick(source: &mut dyn Source, list: &'static [&'static str]) -> &'static str {
+    list[source.below(list.len())]
+}
+
+fn bytes<const N: usize>(source: &mut dyn Source) -> [u8; N] {
+    core::array::from_fn(|_| source.next_u64() as u8)
+}
+
+/// Lowercases and strips accents from the (Latin) names in our lists, for use in
+/// emails and usernames
+fn ascii_lowercase(name: &str) -> String {
+    name.chars()
+        .filter_map(|c| {
+            let c = match c {
+                'á' | 'à' | 'â' | 'ä' => 'a',
+                'é' | 'è' | 'ê' | 'ë' => 'e',
+                'í' | 'ì' | 'î' | 'ï' => 'i',
+                'ó' | 'ò' | 'ô' | 'ö' => 'o',
+                'ú' | 'ù' | 'û' | 'ü' => 'u',
+                'ñ' | 'ń' => 'n',
+                'ć' => 'c',
+                c => c,
+            };
+            c.is_ascii_alphanumeric().then(|| c.to_ascii_lowercase())
+        })
+        .collect()
+}
+
+/// A valid calendar date between 2000 and 2030
+fn date(source: &mut dyn Source) -> (usize, usize, usize) {
+    let year = 2000 + source.below(31);
+    let month = 1 + source.below(12);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    (year, month, 1 + source.below(days))
+}
+
+fn sentence(source: &mut dyn Source) -> String {
+    let len = 4 + source.below(8);
+    let mut sentence = String::new();
+    for i in 0..len {
+        let word = pick(source, WORDS);
+        if i == 0 {
+            let mut chars = word.chars();
+            sentence.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            sentence.push_str(chars.as_str());
+        } else {
+            sentence.push(' ');
+            sentence.push_str(word);
+        }
+    }
+    sentence.push('.');
+    sentence
+}
+
+/// Splits `snake_case`, `kebab-case`, `camelCase` and `PascalCase` names into lowercase words
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(core::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(core::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_from_field_names() {
+        assert_eq!(split_words("homepageURL"), ["homepage", "url"]);
+        assert_eq!(split_words("created_at"), ["created", "at"]);
+
+        for (name, kind) in [
+            ("email", Some(Kind::Email)),
+            ("contactEmailAddress", Some(Kind::Email)),
+            ("homepage_url", Some(Kind::Url)),
+            ("company_name", Some(Kind::Company)),
+            ("firstName", Some(Kind::FirstName)),
+            ("name", Some(Kind::Name)),
+            ("user_id", Some(Kind::Uuid)),
+            ("ip_address", Some(Kind::Ipv4)),
+            ("created_at", Some(Kind::Timestamp)),
+            ("last_login_at", Some(Kind::Timestamp)),
+            ("date_of_birth", Some(Kind::Date)),
+            ("flux_capacitance", None),
+        ] {
+            assert_eq!(Kind::guess(name), kind, "{name}");
+        }
+    }
+}
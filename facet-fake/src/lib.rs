@@ -0,0 +1,134 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+pub use facet_arbitrary::{GenerateError, Source, SplitMix64};
+use facet_arbitrary::{Generator, Slot};
+use facet_core::{Def, Facet, FieldAttribute, ScalarAffinity};
+use facet_reflect::ScalarType;
+
+mod data;
+mod kind;
+pub use kind::*;
+
+/// Generates a fake `T` from `seed`
+pub fn fake<'facet, T: Facet<'facet>>(seed: u64) -> Result<T, GenerateError> {
+    generator(SplitMix64::new(seed)).generate()
+}
+
+/// Returns a [`Generator`] that produces fake data, for when you want to tune it
+/// (collection lengths, option probability…) or supply your own randomness
+pub fn generator<S: Source>(source: S) -> Generator<S> {
+    Generator::new(source).text_hook(text)
+}
+
+/// The [`facet_arbitrary::TextHook`] behind [`fake`], for use in your own hooks:
+/// returns the fake text for a scalar, or `None` to leave it to chance.
+///
+/// In order, it looks at:
+///
+///  - the field's `#[facet(fake = "...")]` attribute, where the value is one of the
+///    [`Kind::from_name`] names, or `random` to opt out entirely;
+///  - the scalar's affinity, so UUIDs, ULIDs, IP addresses, timestamps and paths always look
+///    the part;
+///  - the field's name, for strings (`email`, `first_name`, `created_at`…) and numbers
+///    (`age`, `year`, `port`, `price`, `latitude`…).
+///
+/// # Panics
+///
+/// On a `fake` attribute that doesn't name a [`Kind`], so typos don't go unnoticed.
+pub fn text(slot: Slot, source: &mut dyn Source) -> Option<String> {
+    let Def::Scalar(scalar) = slot.shape.def else {
+        return None;
+    };
+
+    let attribute = slot.field.and_then(|field| {
+        field
+            .attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                &FieldAttribute::Arbitrary(attribute) => fake_attribute(attribute),
+                _ => None,
+            })
+    });
+    match attribute {
+        Some("random") => return None,
+        Some(name) => {
+            let kind = Kind::from_name(name).unwrap_or_else(|| {
+                panic!("unknown fake kind {name:?} on {}", slot.shape);
+            });
+            return Some(kind.generate(source));
+        }
+        None => {}
+    }
+
+    let scalar_type = ScalarType::try_from_shape(slot.shape);
+    let kind = match scalar.affinity {
+        ScalarAffinity::UUID(_) => Some(Kind::Uuid),
+        ScalarAffinity::ULID(_) => Some(Kind::Ulid),
+        ScalarAffinity::IpAddr(_) => Some(match scalar_type {
+            Some(ScalarType::Ipv4Addr) => Kind::Ipv4,
+            Some(ScalarType::Ipv6Addr) => Kind::Ipv6,
+            _ => Kind::Ip,
+        }),
+        ScalarAffinity::SocketAddr(_) => Some(Kind::SocketAddr),
+        ScalarAffinity::Time(_) => Some(Kind::Timestamp),
+        ScalarAffinity::Path(_) => Some(Kind::Path),
+        ScalarAffinity::String(_) => slot.field.and_then(|field| Kind::guess(field.name)),
+        ScalarAffinity::Number(_) => {
+            return slot
+                .field
+                .and_then(|field| number(field.name, scalar_type, source));
+        }
+        _ => None,
+    };
+    kind.map(|kind| kind.generate(source))
+}
+
+/// Extracts `email` from `fake = "email"`
+fn fake_attribute(attribute: &str) -> Option<&str> {
+    let value = attribute
+        .strip_prefix("fake")?
+        .trim_start()
+        .strip_prefix('=')?;
+    let value = value.trim();
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Picks a plausible number for fields such as `age`, `port` or `price`
+fn number(
+    field_name: &str,
+    scalar_type: Option<ScalarType>,
+    source: &mut dyn Source,
+) -> Option<String> {
+    let float = matches!(scalar_type, Some(ScalarType::F32 | ScalarType::F64));
+    // Every range below fits the target type, or is skipped: ports don't fit in a `u8`
+    let bits = match scalar_type? {
+        ScalarType::U8 => 8,
+        ScalarType::I8 => 7,
+        ScalarType::U16 => 16,
+        ScalarType::I16 => 15,
+        _ => 31,
+    };
+    let int = |source: &mut dyn Source, min: usize, max: usize| {
+        (max < 1 << bits).then(|| (min + source.below(max - min + 1)).to_string())
+    };
+
+    let name = field_name.to_ascii_lowercase();
+    let last = name.rsplit('_').next().unwrap_or_default();
+    match last {
+        "age" => int(source, 18, 90),
+        "year" => int(source, 1970, 2030),
+        "port" => int(source, 1024, 65535),
+        "percent" | "percentage" => int(source, 0, 100),
+        "price" | "amount" | "cost" | "total" | "balance" if float => {
+            Some(format!("{:.2}", source.below(100_000) as f64 / 100.0))
+        }
+        "price" | "amount" | "cost" | "total" | "balance" => int(source, 1, 999),
+        "lat" | "latitude" if float => Some(format!("{:.6}", source.unit() * 180.0 - 90.0)),
+        "lng" | "lon" | "longitude" if float => {
+            Some(format!("{:.6}", source.unit() * 360.0 - 180.0))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use facet::Facet;
+use facet_fake::{Kind, SplitMix64, fake, generator};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Customer {
+    id: String,
+    first_name: String,
+    email: String,
+    homepage_url: Option<String>,
+    age: u8,
+    port: u16,
+    balance: f64,
+    created_at: String,
+    #[facet(fake = "city")]
+    hometown: String,
+    addresses: Vec<core::net::Ipv4Addr>,
+}
+
+#[test]
+fn looks_the_part() {
+    facet_testhelpers::setup();
+
+    for seed in 0..32 {
+        let customer: Customer = fake(seed).unwrap();
+        assert_eq!(customer, fake::<Customer>(seed).unwrap());
+
+        assert_eq!(customer.id.len(), 36);
+        assert_eq!(customer.id.as_bytes()[14], b'4');
+        assert!(!customer.first_name.contains(' '));
+        let (_, domain) = customer.email.split_once('@').unwrap();
+        assert!(domain.starts_with("example."), "{}", customer.email);
+        if let Some(url) = &customer.homepage_url {
+            assert!(url.starts_with("https://"), "{url}");
+        }
+        assert!((18..=90).contains(&customer.age));
+        assert!(customer.port >= 1024);
+        assert!((0.0..1000.0).contains(&customer.balance));
+        assert_eq!(customer.created_at.len(), 20);
+        assert!(customer.created_at.ends_with('Z'));
+        for address in &customer.addresses {
+            let documentation = address.octets()[..3] == [203, 0, 113];
+            assert!(address.is_private() || documentation, "{address}");
+        }
+    }
+}
+
+#[test]
+fn attributes_pick_the_kind() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Contact {
+        #[facet(fake = "random")]
+        email: String,
+    }
+
+    let mut source = SplitMix64::new(0);
+    let cities: HashSet<String> = (0..1024)
+        .map(|_| Kind::City.generate(&mut source))
+        .collect();
+
+    let mut generator = generator(SplitMix64::new(5));
+    for _ in 0..16 {
+        let customer: Customer = generator.generate().unwrap();
+        assert!(cities.contains(&customer.hometown), "{}", customer.hometown);
+    }
+
+    let contacts: Vec<Contact> = (0..16).map(|seed| fake(seed).unwrap()).collect();
+    assert!(contacts.iter().any(|contact| !contact.email.contains('@')));
+}
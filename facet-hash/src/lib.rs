@@ -0,0 +1,235 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::{Def, Facet, Field, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use sha2::{Digest, Sha256};
+
+/// Hashes a value canonically — see the [crate documentation](crate) for what that means
+pub fn content_hash(peek: &Peek<'_, '_>) -> [u8; 32] {
+    let mut sha = Sha256::new();
+    encode(&mut sha, *peek);
+    sha.finalize().into()
+}
+
+/// Hashes a value canonically, like [`content_hash`]
+pub fn hash<'facet, T: Facet<'facet>>(value: &T) -> [u8; 32] {
+    content_hash(&Peek::new(value))
+}
+
+/// Formats a hash as lowercase hexadecimal, as it's usually shown
+pub fn to_hex(hash: &[u8; 32]) -> String {
+    hash.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The first byte of every encoded value, so values of different kinds never collide
+mod tag {
+    pub(crate) const NONE: u8 = 0;
+    pub(crate) const BOOL: u8 = 1;
+    pub(crate) const UINT: u8 = 2;
+    pub(crate) const NEG_INT: u8 = 3;
+    pub(crate) const FLOAT: u8 = 4;
+    pub(crate) const STRING: u8 = 5;
+    pub(crate) const LIST: u8 = 6;
+    pub(crate) const MAP: u8 = 7;
+    pub(crate) const RECORD: u8 = 8;
+    pub(crate) const TUPLE: u8 = 9;
+    pub(crate) const VARIANT: u8 = 10;
+    pub(crate) const TEXT: u8 = 11;
+}
+
+fn encode(sha: &mut Sha256, peek: Peek<'_, '_>) {
+    // `Some(x)` is `x`, so making a field optional doesn't change the hash of its values, and
+    // `Arc<T>` hashes like `T`
+    let Ok(peek) = peek.unwrap_wrappers() else {
+        sha.update([tag::NONE]);
+        return;
+    };
+
+    if let Def::Scalar(_) = peek.shape().def {
+        encode_scalar(sha, peek);
+        return;
+    }
+
+    if let Ok(list) = peek.into_list_like() {
+        sha.update([tag::LIST]);
+        encode_len(sha, list.len());
+        for item in list.iter() {
+            encode(sha, item);
+        }
+        return;
+    }
+
+    if let Ok(map) = peek.into_map() {
+        let entries = map.iter().map(|(key, value)| {
+            let mut sha = Sha256::new();
+            encode(&mut sha, key);
+            encode(&mut sha, value);
+            sha.finalize().into()
+        });
+        encode_unordered(sha, tag::MAP, entries.collect());
+        return;
+    }
+
+    if let Ok(tuple) = peek.into_tuple() {
+        encode_positional(sha, tag::TUPLE, tuple.fields().map(|(_, item)| item));
+        return;
+    }
+
+    match peek.shape().ty {
+        Type::User(UserType::Struct(st)) => {
+            if let Ok(ps) = peek.into_struct() {
+                encode_fields(sha, st.kind, ps.fields_for_serialize());
+                return;
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            if let Ok(pe) = peek.into_enum() {
+                if let Ok(variant) = pe.active_variant() {
+                    sha.update([tag::VARIANT]);
+                    encode_str(sha, variant.name);
+                    encode_fields(sha, variant.data.kind, pe.fields_for_serialize());
+                    return;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    encode_text(sha, peek);
+}
+
+fn encode_len(sha: &mut Sha256, len: usize) {
+    sha.update((len as u64).to_le_bytes());
+}
+
+fn encode_str(sha: &mut Sha256, s: &str) {
+    encode_len(sha, s.len());
+    sha.update(s.as_bytes());
+}
+
+/// Named fields are hashed in name order, so reordering them doesn't change the hash;
+/// tuple fields are positional.
+fn encode_fields<'mem, 'facet>(
+    sha: &mut Sha256,
+    kind: StructKind,
+    fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+) {
+    match kind {
+        StructKind::Struct => {
+            let fields = fields.map(|(field, value)| {
+                let mut sha = Sha256::new();
+                encode_str(&mut sha, field.name);
+                encode(&mut sha, value);
+                sha.finalize().into()
+            });
+            encode_unordered(sha, tag::RECORD, fields.collect());
+        }
+        _ => encode_positional(sha, tag::TUPLE, fields.map(|(_, value)| value)),
+    }
+}
+
+fn encode_positional<'mem, 'facet>(
+    sha: &mut Sha256,
+    tag: u8,
+    items: impl Iterator<Item = Peek<'mem, 'facet>>,
+) {
+    let items: Vec<_> = items.collect();
+    sha.update([tag]);
+    encode_len(sha, items.len());
+    for item in items {
+        encode(sha, item);
+    }
+}
+
+/// Entries are hashed on their own, then sorted, which makes the hash independent of
+/// iteration order
+fn encode_unordered(sha: &mut Sha256, tag: u8, mut entries: Vec<[u8; 32]>) {
+    entries.sort_unstable();
+    sha.update([tag]);
+    encode_len(sha, entries.len());
+    for entry in entries {
+        sha.update(entry);
+    }
+}
+
+fn encode_scalar(sha: &mut Sha256, peek: Peek<'_, '_>) {
+    if let Some(s) = peek.as_str() {
+        sha.update([tag::STRING]);
+        encode_str(sha, s);
+        return;
+    }
+
+    macro_rules! get {
+        ($ty:ty) => {
+            match peek.get::<$ty>() {
+                Ok(&value) => value,
+                Err(_) => return encode_text(sha, peek),
+            }
+        };
+    }
+
+    // Integers are hashed by value, whatever their width or signedness: `5u8` and `5i64`
+    // hash the same
+    let int: i128 = match peek.scalar_type() {
+        Some(ScalarType::Unit) => return sha.update([tag::NONE]),
+        Some(ScalarType::Bool) => return sha.update([tag::BOOL, get!(bool) as u8]),
+        Some(ScalarType::Char) => {
+            let mut buf = [0; 4];
+            sha.update([tag::STRING]);
+            return encode_str(sha, get!(char).encode_utf8(&mut buf));
+        }
+        Some(ScalarType::F32) => return encode_float(sha, get!(f32) as f64),
+        Some(ScalarType::F64) => return encode_float(sha, get!(f64)),
+        Some(ScalarType::U8) => get!(u8) as i128,
+        Some(ScalarType::U16) => get!(u16) as i128,
+        Some(ScalarType::U32) => get!(u32) as i128,
+        Some(ScalarType::U64) => get!(u64) as i128,
+        Some(ScalarType::USize) => get!(usize) as i128,
+        Some(ScalarType::U128) => {
+            let value = get!(u128);
+            sha.update([tag::UINT]);
+            return sha.update(value.to_le_bytes());
+        }
+        Some(ScalarType::I8) => get!(i8) as i128,
+        Some(ScalarType::I16) => get!(i16) as i128,
+        Some(ScalarType::I32) => get!(i32) as i128,
+        Some(ScalarType::I64) => get!(i64) as i128,
+        Some(ScalarType::ISize) => get!(isize) as i128,
+        Some(ScalarType::I128) => get!(i128),
+        _ => return encode_text(sha, peek),
+    };
+    if int >= 0 {
+        sha.update([tag::UINT]);
+        sha.update((int as u128).to_le_bytes());
+    } else {
+        sha.update([tag::NEG_INT]);
+        sha.update(int.to_le_bytes());
+    }
+}
+
+/// Floats are hashed by their `f64` bits, with a single NaN and a single zero
+fn encode_float(sha: &mut Sha256, value: f64) {
+    let value = if value.is_nan() {
+        f64::NAN
+    } else if value == 0.0 {
+        0.0
+    } else {
+        value
+    };
+    sha.update([tag::FLOAT]);
+    sha.update(value.to_bits().to_le_bytes());
+}
+
+/// Anything else (IP addresses, UUIDs, paths…) is hashed by its text representation,
+/// which is the one formats use too
+fn encode_text(sha: &mut Sha256, peek: Peek<'_, '_>) {
+    let text = if peek.vtable().display.is_some() {
+        peek.to_string()
+    } else {
+        format!("{peek:?}")
+    };
+    sha.update([tag::TEXT]);
+    encode_str(sha, &text);
+}
@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+use facet_hash::{content_hash, hash, to_hex};
+use facet_reflect::Peek;
+
+#[derive(Facet)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[test]
+fn equal_content_equal_hash() {
+    facet_testhelpers::setup();
+
+    let a = Point {
+        x: 1,
+        y: -2,
+        label: Some("origin".to_string()),
+    };
+    let b = Point {
+        x: 1,
+        y: -2,
+        label: Some("origin".to_string()),
+    };
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(hash(&a), content_hash(&Peek::new(&b)));
+    assert_eq!(to_hex(&hash(&a)).len(), 64);
+
+    let c = Point { y: 2, ..b };
+    assert_ne!(hash(&a), hash(&c));
+
+    // Lists are ordered, maps aren't
+    assert_ne!(hash(&vec![1, 2]), hash(&vec![2, 1]));
+    let forward: HashMap<String, u8> = (0..32).map(|i| (i.to_string(), i)).collect();
+    let backward: HashMap<String, u8> = (0..32).rev().map(|i| (i.to_string(), i)).collect();
+    let sorted: BTreeMap<String, u8> = forward.clone().into_iter().collect();
+    assert_eq!(hash(&forward), hash(&backward));
+    assert_eq!(hash(&forward), hash(&sorted));
+}
+
+#[test]
+fn hashes_are_structural() {
+    facet_testhelpers::setup();
+
+    // Same field names and values, different type, field order and integer widths
+    #[derive(Facet)]
+    struct Other {
+        label: String,
+        y: i64,
+        x: u8,
+    }
+
+    let point = Point {
+        x: 1,
+        y: -2,
+        label: Some("origin".to_string()),
+    };
+    let other = Other {
+        label: "origin".to_string(),
+        y: -2,
+        x: 1,
+    };
+    assert_eq!(hash(&point), hash(&other));
+
+    #[derive(Facet)]
+    struct Renamed {
+        x: i32,
+        z: i32,
+        label: Option<String>,
+    }
+    let renamed = Renamed {
+        x: 1,
+        z: -2,
+        label: Some("origin".to_string()),
+    };
+    assert_ne!(hash(&point), hash(&renamed));
+
+    assert_eq!(hash(&0.0f64), hash(&-0.0f64));
+    assert_eq!(hash(&1.5f32), hash(&1.5f64));
+    assert_ne!(hash(&1u8), hash(&1.0f64));
+    assert_ne!(hash(&"1"), hash(&1u8));
+    assert_ne!(hash(&None::<u8>), hash(&vec![0u8; 0]));
+}
@@ -0,0 +1,459 @@
+use std::collections::{HashMap, HashSet};
+
+use facet_core::{Def, FieldFlags, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+/// How many children of a collection are shown at first, and added each time more are
+/// asked for
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Values nested deeper than this aren't searched, in case smart pointers form a cycle
+const MAX_SEARCH_DEPTH: usize = 64;
+
+/// A line of the tree, as shown by the explorer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// How deep in the tree the row is, the root being 0
+    pub depth: usize,
+    /// Where the value is, as a `facet-query` path
+    pub path: String,
+    /// The field name, index or map key the value is under — empty for the root
+    pub label: String,
+    /// The value itself for scalars, what kind of value it is for everything else
+    pub summary: String,
+    /// Whether the row can be expanded, and whether it is
+    pub state: RowState,
+}
+
+/// Whether a row can be expanded, and whether it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowState {
+    /// A scalar, or something empty
+    Leaf,
+    /// Has children, which are hidden
+    Collapsed,
+    /// Has children, which are the rows that follow
+    Expanded,
+    /// Stands for the children of a collection that aren't shown yet
+    More {
+        /// How many there are
+        hidden: usize,
+    },
+}
+
+/// The state of an expandable tree over a value: which nodes are expanded, how far
+/// large collections are paged in, and which row is selected
+///
+/// This holds no terminal state, so it can drive any front end — the one in this crate
+/// being [`inspect`](crate::inspect).
+pub struct Explorer<'mem, 'facet> {
+    root: Peek<'mem, 'facet>,
+    expanded: HashSet<String>,
+    /// How many children of each (paged) collection are shown
+    shown: HashMap<String, usize>,
+    page_size: usize,
+    rows: Vec<Row>,
+    cursor: usize,
+}
+
+impl<'mem, 'facet> Explorer<'mem, 'facet> {
+    /// Creates an explorer with only the root expanded, and selected
+    pub fn new(root: Peek<'mem, 'facet>) -> Self {
+        let mut explorer = Self {
+            root,
+            expanded: HashSet::from([".".to_string()]),
+            shown: HashMap::new(),
+            page_size: DEFAULT_PAGE_SIZE,
+            rows: Vec::new(),
+            cursor: 0,
+        };
+        explorer.refresh();
+        explorer
+    }
+
+    /// Sets how many children of a collection are shown at a time
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self.refresh();
+        self
+    }
+
+    /// The visible rows, in order
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// The index of the selected row
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The selected row
+    pub fn selected(&self) -> &Row {
+        &self.rows[self.cursor]
+    }
+
+    /// Selects the row at `index`, or the last one if there are fewer rows
+    pub fn select(&mut self, index: usize) {
+        self.cursor = index.min(self.rows.len() - 1);
+    }
+
+    /// Selects the row `by` rows below the selected one — or above, if it's negative
+    pub fn move_by(&mut self, by: isize) {
+        self.select(self.cursor.saturating_add_signed(by));
+    }
+
+    /// Expands the selected row, or shows another page of children for a
+    /// [`RowState::More`] row
+    pub fn expand(&mut self) {
+        let Row { path, state, .. } = self.selected().clone();
+        match state {
+            RowState::Collapsed => {
+                self.expanded.insert(path);
+            }
+            RowState::More { .. } => {
+                let shown = self.shown.entry(path).or_insert(self.page_size);
+                *shown += self.page_size;
+            }
+            RowState::Leaf | RowState::Expanded => return,
+        }
+        self.refresh();
+    }
+
+    /// Collapses the selected row — or, if it isn't expanded, selects its parent
+    pub fn collapse(&mut self) {
+        let Row {
+            path, state, depth, ..
+        } = self.selected().clone();
+        if state == RowState::Expanded {
+            self.expanded.remove(&path);
+            self.refresh();
+            return;
+        }
+        if let Some(parent) = self.rows[..self.cursor]
+            .iter()
+            .rposition(|row| row.depth < depth)
+        {
+            self.cursor = parent;
+        }
+    }
+
+    /// Expands the selected row if it's collapsed, collapses it if it's expanded
+    pub fn toggle(&mut self) {
+        match self.selected().state {
+            RowState::Expanded => self.collapse(),
+            _ => self.expand(),
+        }
+    }
+
+    /// Selects the next value, after the selected one, whose label or summary contains
+    /// `query` (ignoring case), expanding whatever it's in. Wraps around to the start, and
+    /// returns false if nothing matches.
+    pub fn search(&mut self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let mut nodes = Vec::new();
+        collect(
+            self.root,
+            ".".to_string(),
+            String::new(),
+            None,
+            0,
+            0,
+            &mut nodes,
+        );
+
+        let current = &self.selected().path;
+        let start = nodes
+            .iter()
+            .position(|node| &node.path == current)
+            .map_or(0, |i| i + 1);
+        let found = (start..nodes.len())
+            .chain(0..start)
+            .find(|&i| nodes[i].matches(&query));
+        let Some(found) = found else {
+            return false;
+        };
+
+        // Expand every ancestor, with enough pages shown to include the match
+        let mut parent = nodes[found].parent;
+        let mut index = nodes[found].index;
+        while let Some(i) = parent {
+            let node = &nodes[i];
+            self.expanded.insert(node.path.clone());
+            if index >= self.page_size {
+                let pages = index / self.page_size + 1;
+                let shown = self.shown.entry(node.path.clone()).or_insert(0);
+                *shown = (*shown).max(pages * self.page_size);
+            }
+            parent = node.parent;
+            index = node.index;
+        }
+        let path = nodes[found].path.clone();
+        self.refresh();
+        self.cursor = self
+            .rows
+            .iter()
+            .position(|row| row.path == path && !matches!(row.state, RowState::More { .. }))
+            .unwrap_or(self.cursor);
+        true
+    }
+
+    /// Recomputes the rows, keeping the same value selected if it's still there. A "more"
+    /// row shares its path with its parent, so the selection stays where it was instead,
+    /// which is where the first newly shown child is.
+    fn refresh(&mut self) {
+        let selected = self
+            .rows
+            .get(self.cursor)
+            .filter(|row| !matches!(row.state, RowState::More { .. }))
+            .map(|row| row.path.clone());
+        self.rows.clear();
+        self.push(self.root, ".".to_string(), String::new(), 0);
+        self.cursor = selected
+            .and_then(|path| self.rows.iter().position(|row| row.path == path))
+            .unwrap_or(self.cursor.min(self.rows.len() - 1));
+    }
+
+    fn push(&mut self, peek: Peek<'mem, 'facet>, path: String, label: String, depth: usize) {
+        let peek = unwrap(peek);
+        let children = children(peek);
+        let state = if children.is_empty() {
+            RowState::Leaf
+        } else if self.expanded.contains(&path) {
+            RowState::Expanded
+        } else {
+            RowState::Collapsed
+        };
+        self.rows.push(Row {
+            depth,
+            path: path.clone(),
+            label,
+            summary: summary(peek, children.len()),
+            state,
+        });
+        if state != RowState::Expanded {
+            return;
+        }
+
+        let shown = self.shown.get(&path).copied().unwrap_or(self.page_size);
+        let hidden = children.len().saturating_sub(shown);
+        for (segment, child) in children.into_iter().take(shown) {
+            let child_path = segment.path(&path);
+            self.push(child, child_path, segment.label(), depth + 1);
+        }
+        if hidden > 0 {
+            self.rows.push(Row {
+                depth: depth + 1,
+                path,
+                label: "…".to_string(),
+                summary: format!("{hidden} more"),
+                state: RowState::More { hidden },
+            });
+        }
+    }
+}
+
+/// Every node of the tree, expanded or not, for searching
+struct Node {
+    path: String,
+    label: String,
+    summary: String,
+    /// Index of the parent in the list of nodes
+    parent: Option<usize>,
+    /// Index among the parent's children
+    index: usize,
+}
+
+impl Node {
+    fn matches(&self, query: &str) -> bool {
+        self.label.to_lowercase().contains(query) || self.summary.to_lowercase().contains(query)
+    }
+}
+
+fn collect(
+    peek: Peek<'_, '_>,
+    path: String,
+    label: String,
+    parent: Option<usize>,
+    index: usize,
+    depth: usize,
+    nodes: &mut Vec<Node>,
+) {
+    let peek = unwrap(peek);
+    let children = children(peek);
+    let this = nodes.len();
+    nodes.push(Node {
+        summary: summary(peek, children.len()),
+        path,
+        label,
+        parent,
+        index,
+    });
+    if depth >= MAX_SEARCH_DEPTH {
+        return;
+    }
+    for (i, (segment, child)) in children.into_iter().enumerate() {
+        let child_path = segment.path(&nodes[this].path);
+        let label = segment.label();
+        collect(child, child_path, label, Some(this), i, depth + 1, nodes);
+    }
+}
+
+/// What a child is found under
+enum Segment {
+    /// A field, or a map key
+    Name { name: String, quoted: bool },
+    /// An element of a list, array, slice or tuple
+    Index(usize),
+}
+
+impl Segment {
+    /// The path to the child, given the path to its parent
+    fn path(&self, parent: &str) -> String {
+        let parent = if parent == "." { "" } else { parent };
+        match self {
+            Segment::Index(i) if parent.is_empty() => format!(".[{i}]"),
+            Segment::Index(i) => format!("{parent}[{i}]"),
+            Segment::Name { name, .. } if is_identifier(name) => format!("{parent}.{name}"),
+            Segment::Name { name, .. } => format!("{parent}.{name:?}"),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Segment::Index(i) => format!("[{i}]"),
+            Segment::Name { name, quoted: true } => format!("{name:?}"),
+            Segment::Name {
+                name,
+                quoted: false,
+            } => name.clone(),
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Looks through options, smart pointers and transparent wrappers, like `facet-query` does
+fn unwrap<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Peek<'mem, 'facet> {
+    let mut current = peek.innermost_peek();
+    loop {
+        let inner = match current.shape().def {
+            Def::Option(_) => current.into_option().ok().and_then(|o| o.value()),
+            Def::SmartPointer(_) => current
+                .into_smart_pointer()
+                .ok()
+                .and_then(|sp| sp.borrow_inner()),
+            _ => None,
+        };
+        match inner {
+            Some(inner) => current = inner.innermost_peek(),
+            None => return current,
+        }
+    }
+}
+
+fn children<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Vec<(Segment, Peek<'mem, 'facet>)> {
+    if let Ok(map) = peek.into_map() {
+        return map
+            .iter()
+            .map(|(key, value)| {
+                let segment = match key.as_str() {
+                    Some(name) => Segment::Name {
+                        name: name.to_string(),
+                        quoted: true,
+                    },
+                    None => Segment::Name {
+                        name: key.to_string(),
+                        quoted: false,
+                    },
+                };
+                (segment, value)
+            })
+            .collect();
+    }
+    if let Ok(list) = peek.into_list_like() {
+        return list
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (Segment::Index(i), item))
+            .collect();
+    }
+    if let Ok(tuple) = peek.into_tuple() {
+        return tuple
+            .fields()
+            .map(|(i, item)| (Segment::Index(i), item))
+            .collect();
+    }
+
+    let mut out = Vec::new();
+    match peek.shape().ty {
+        Type::User(UserType::Struct(_)) => {
+            if let Ok(ps) = peek.into_struct() {
+                push_fields(ps.fields(), &mut out);
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            if let Ok(pe) = peek.into_enum() {
+                push_fields(pe.fields(), &mut out);
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+/// Fields, with those of flattened structs inline
+fn push_fields<'mem, 'facet>(
+    fields: impl Iterator<Item = (facet_core::Field, Peek<'mem, 'facet>)>,
+    out: &mut Vec<(Segment, Peek<'mem, 'facet>)>,
+) {
+    for (field, peek) in fields {
+        if field.flags.contains(FieldFlags::FLATTEN) {
+            if let Ok(ps) = peek.into_struct() {
+                push_fields(ps.fields(), out);
+                continue;
+            }
+        }
+        let segment = Segment::Name {
+            name: field.name.to_string(),
+            quoted: false,
+        };
+        out.push((segment, peek));
+    }
+}
+
+/// The value of a scalar, or what kind of value it is
+fn summary(peek: Peek<'_, '_>, children: usize) -> String {
+    let shape = peek.shape();
+    if let Some(s) = peek.as_str() {
+        return format!("{s:?}");
+    }
+    match shape.def {
+        Def::Option(_) => return "None".to_string(),
+        Def::Map(_) => return plural(shape, children, "entry", "entries"),
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            return plural(shape, children, "item", "items");
+        }
+        Def::Scalar(_) => return peek.to_string(),
+        _ => {}
+    }
+    match shape.ty {
+        Type::User(UserType::Enum(_)) => {
+            match peek.into_enum().map(|pe| pe.variant_name_active()) {
+                Ok(Ok(variant)) => format!("{shape}::{variant}"),
+                _ => shape.to_string(),
+            }
+        }
+        _ if children == 0 && peek.vtable().display.is_some() => peek.to_string(),
+        _ => shape.to_string(),
+    }
+}
+
+fn plural(shape: &facet_core::Shape, n: usize, one: &str, many: &str) -> String {
+    match n {
+        1 => format!("{shape} (1 {one})"),
+        n => format!("{shape} ({n} {many})"),
+    }
+}
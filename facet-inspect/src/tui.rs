@@ -0,0 +1,213 @@
+use std::io::{self, Write};
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::{Explorer, Row, RowState};
+
+const HELP: &str = "↑↓ move  ←→ fold  / search  n next  y copy path  q quit";
+
+/// Explores `value` in the terminal, until the explorer is closed with `q`
+pub fn inspect<'a, T: Facet<'a>>(value: &T) -> io::Result<()> {
+    inspect_peek(Peek::new(value))
+}
+
+/// Explores an already-reflected value in the terminal, until the explorer is closed with `q`
+pub fn inspect_peek(peek: Peek<'_, '_>) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = App::new(peek).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+struct App<'mem, 'facet> {
+    explorer: Explorer<'mem, 'facet>,
+    /// The search being typed, if any
+    input: Option<String>,
+    /// The last search, for `n`
+    query: Option<String>,
+    /// Shown in the status line until the next key press
+    message: Option<String>,
+    /// The first row on screen
+    offset: usize,
+    /// How many rows fit on screen
+    height: usize,
+}
+
+impl<'mem, 'facet> App<'mem, 'facet> {
+    fn new(peek: Peek<'mem, 'facet>) -> Self {
+        Self {
+            explorer: Explorer::new(peek),
+            input: None,
+            query: None,
+            message: None,
+            offset: 0,
+            height: 1,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if !self.handle(key)? {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns false when it's time to quit
+    fn handle(&mut self, key: KeyEvent) -> io::Result<bool> {
+        if self.input.is_some() {
+            self.handle_input(key);
+            return Ok(true);
+        }
+
+        self.message = None;
+        let page = self.height as isize;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(false);
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.explorer.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.explorer.move_by(1),
+            KeyCode::PageUp => self.explorer.move_by(-page),
+            KeyCode::PageDown => self.explorer.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => self.explorer.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.explorer.select(usize::MAX),
+            KeyCode::Right | KeyCode::Char('l') => self.explorer.expand(),
+            KeyCode::Left | KeyCode::Char('h') => self.explorer.collapse(),
+            KeyCode::Enter | KeyCode::Char(' ') => self.explorer.toggle(),
+            KeyCode::Char('/') => self.input = Some(String::new()),
+            KeyCode::Char('n') => {
+                if let Some(query) = self.query.clone() {
+                    self.find(query);
+                }
+            }
+            KeyCode::Char('y') => self.copy()?,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                let query = self.input.take().unwrap_or_default();
+                if !query.is_empty() {
+                    self.find(query);
+                }
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Backspace => {
+                if let Some(input) = &mut self.input {
+                    input.pop();
+                }
+            }
+            KeyCode::Char(c) => self.input.get_or_insert_default().push(c),
+            _ => {}
+        }
+    }
+
+    fn find(&mut self, query: String) {
+        if !self.explorer.search(&query) {
+            self.message = Some(format!("Nothing matches {query:?}"));
+        }
+        self.query = Some(query);
+    }
+
+    /// Copies the path of the selected value to the clipboard, with an OSC 52 escape
+    /// sequence — which works over SSH too, in terminals that support it
+    fn copy(&mut self) -> io::Result<()> {
+        let path = self.explorer.selected().path.clone();
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", base64(path.as_bytes()))?;
+        stdout.flush()?;
+        self.message = Some(format!("Copied {path}"));
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tree_area, status_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        // Scroll just enough to keep the selection on screen
+        self.height = usize::from(tree_area.height).max(1);
+        let cursor = self.explorer.cursor();
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + self.height {
+            self.offset = cursor + 1 - self.height;
+        }
+
+        let rows = self
+            .explorer
+            .rows()
+            .iter()
+            .skip(self.offset)
+            .take(self.height);
+        let list = List::new(rows.map(|row| ListItem::new(row_line(row))))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(cursor - self.offset));
+        frame.render_stateful_widget(list, tree_area, &mut state);
+
+        let status = match (&self.input, &self.message) {
+            (Some(input), _) => Line::from(format!("/{input}")),
+            (None, Some(message)) => Line::from(message.clone()),
+            (None, None) => Line::from(vec![
+                Span::raw(self.explorer.selected().path.clone()).bold(),
+                Span::raw("  "),
+                Span::raw(HELP).dim(),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+}
+
+fn row_line(row: &Row) -> Line<'static> {
+    let indent = "  ".repeat(row.depth);
+    let marker = match row.state {
+        RowState::Leaf => "  ",
+        RowState::Collapsed => "▸ ",
+        RowState::Expanded => "▾ ",
+        RowState::More { hidden } => {
+            return Line::from(format!("{indent}  … {hidden} more").dim());
+        }
+    };
+    let mut spans = vec![Span::raw(format!("{indent}{marker}"))];
+    if !row.label.is_empty() {
+        spans.push(Span::raw(row.label.clone()).bold());
+        spans.push(Span::raw(": "));
+    }
+    spans.push(Span::raw(row.summary.clone()));
+    Line::from(spans)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63])),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,90 @@
+use facet::Facet;
+use facet_inspect::{Explorer, RowState};
+use facet_reflect::Peek;
+
+#[derive(Facet)]
+struct Service {
+    name: String,
+    ports: Vec<u16>,
+    owner: Option<Owner>,
+}
+
+#[derive(Facet)]
+struct Owner {
+    email: String,
+}
+
+fn service() -> Service {
+    Service {
+        name: "api".into(),
+        ports: vec![80, 443],
+        owner: Some(Owner {
+            email: "ops@example.com".into(),
+        }),
+    }
+}
+
+#[test]
+fn expanding_and_collapsing() {
+    facet_testhelpers::setup();
+
+    let service = service();
+    let mut explorer = Explorer::new(Peek::new(&service));
+    let paths: Vec<_> = explorer
+        .rows()
+        .iter()
+        .map(|row| row.path.as_str())
+        .collect();
+    assert_eq!(paths, [".", ".name", ".ports", ".owner"]);
+    assert_eq!(explorer.rows()[1].summary, "\"api\"");
+    assert_eq!(explorer.rows()[2].state, RowState::Collapsed);
+
+    explorer.select(2);
+    explorer.expand();
+    let rows = &explorer.rows()[3..5];
+    assert_eq!(rows[0].path, ".ports[0]");
+    assert_eq!(rows[0].label, "[0]");
+    assert_eq!(rows[0].summary, "80");
+    assert_eq!(rows[1].depth, 2);
+
+    // Collapsing a leaf selects its parent, collapsing that hides its children again
+    explorer.select(4);
+    explorer.collapse();
+    assert_eq!(explorer.selected().path, ".ports");
+    explorer.collapse();
+    assert_eq!(explorer.rows().len(), 4);
+}
+
+#[test]
+fn searching_expands_what_matches_are_in() {
+    facet_testhelpers::setup();
+
+    let service = service();
+    let mut explorer = Explorer::new(Peek::new(&service));
+    assert!(explorer.search("OPS@"));
+    assert_eq!(explorer.selected().path, ".owner.email");
+    assert_eq!(explorer.selected().depth, 2);
+
+    assert!(explorer.search("443"));
+    assert_eq!(explorer.selected().path, ".ports[1]");
+    assert!(!explorer.search("nowhere"));
+}
+
+#[test]
+fn large_collections_are_paged() {
+    facet_testhelpers::setup();
+
+    let numbers: Vec<u32> = (0..250).collect();
+    let mut explorer = Explorer::new(Peek::new(&numbers)).page_size(100);
+    assert_eq!(explorer.rows().len(), 102);
+    assert_eq!(explorer.rows()[101].state, RowState::More { hidden: 150 });
+
+    explorer.select(101);
+    explorer.expand();
+    assert_eq!(explorer.selected().path, ".[100]");
+    assert_eq!(explorer.rows()[201].state, RowState::More { hidden: 50 });
+
+    assert!(explorer.search("249"));
+    assert_eq!(explorer.selected().path, ".[249]");
+    assert_eq!(explorer.rows().len(), 251);
+}
@@ -1,13 +1,14 @@
-use alloc::{borrow::Cow, format};
+use alloc::format;
 
-use facet_core::Facet;
+use facet_core::{Facet, Shape};
 use facet_deserialize::{
-    DeserError, DeserErrorKind, Expectation, Format, NextData, NextResult, Outcome, Scalar, Span,
-    Spannable, Spanned,
+    DeserError, DeserErrorKind, DeserializeOptions, Expectation, Format, NextData, NextResult,
+    Outcome, Scalar, Span, Spannable, Spanned,
 };
+use facet_reflect::{Arena, HeapValue, Wip};
 use log::trace;
 
-mod tokenizer;
+pub(crate) mod tokenizer;
 use tokenizer::{Token, TokenError, TokenErrorKind, Tokenizer};
 
 /// Deserialize JSON from a given byte slice
@@ -25,6 +26,54 @@ pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(
     facet_deserialize::deserialize(input, Json)
 }
 
+/// Deserialize JSON from a given byte slice, with the given [`DeserializeOptions`]
+pub fn from_slice_with_options<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input [u8],
+    options: DeserializeOptions,
+) -> Result<T, DeserError<'input>> {
+    facet_deserialize::deserialize_with_options(input, Json, options)
+}
+
+/// Deserialize JSON from a given string, with the given [`DeserializeOptions`]
+pub fn from_str_with_options<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input str,
+    options: DeserializeOptions,
+) -> Result<T, DeserError<'input>> {
+    from_slice_with_options(input.as_bytes(), options)
+}
+
+/// Deserialize JSON from a given byte slice, allocating the value and its temporaries in `arena`
+pub fn from_slice_in<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input [u8],
+    arena: &Arena,
+) -> Result<T, DeserError<'input>> {
+    facet_deserialize::deserialize_in(input, Json, arena)
+}
+
+/// Deserialize JSON from a given string, allocating the value and its temporaries in `arena`
+pub fn from_str_in<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input str,
+    arena: &Arena,
+) -> Result<T, DeserError<'input>> {
+    from_slice_in(input.as_bytes(), arena)
+}
+
+/// Deserialize JSON from a given byte slice into a [`Wip`], for shapes only known at runtime
+pub fn from_slice_into_wip<'input: 'facet, 'facet>(
+    wip: Wip<'facet>,
+    input: &'input [u8],
+) -> Result<HeapValue<'facet>, DeserError<'input>> {
+    facet_deserialize::deserialize_wip(wip, input, Json)
+}
+
+/// Deserialize JSON from a given string into a [`Wip`], for shapes only known at runtime
+pub fn from_str_into_wip<'input: 'facet, 'facet>(
+    wip: Wip<'facet>,
+    input: &'input str,
+) -> Result<HeapValue<'facet>, DeserError<'input>> {
+    from_slice_into_wip(wip, input.as_bytes())
+}
+
 /// Deserialize JSON from a given string, converting any dynamic error into a static one.
 ///
 /// This function attempts to deserialize a type `T` implementing `Facet` from the input string slice.
@@ -67,7 +116,7 @@ impl Format for Json {
 
             let res = match token.node {
                 Token::String(s) => Ok(Spanned {
-                    node: Outcome::Scalar(Scalar::String(Cow::Owned(s))),
+                    node: Outcome::Scalar(Scalar::String(s)),
                     span,
                 }),
                 Token::F64(n) => Ok(Spanned {
@@ -82,6 +131,14 @@ impl Format for Json {
                     node: Outcome::Scalar(Scalar::U64(n)),
                     span,
                 }),
+                Token::I128(n) => Ok(Spanned {
+                    node: Outcome::Scalar(Scalar::I128(n)),
+                    span,
+                }),
+                Token::U128(n) => Ok(Spanned {
+                    node: Outcome::Scalar(Scalar::U128(n)),
+                    span,
+                }),
                 Token::True => Ok(Spanned {
                     node: Outcome::Scalar(Scalar::Bool(true)),
                     span,
@@ -233,13 +290,17 @@ impl Format for Json {
                             }
                         }
                     }
-                    trace!("Skip complete, span {:?}", last_span);
-                    (nd, Ok(last_span))
+                    // The whole container, from its opening token to its closing one
+                    let span = Span::new(token.span.start(), last_span.end() - token.span.start());
+                    trace!("Skip complete, span {:?}", span);
+                    (nd, Ok(span))
                 }
                 Token::String(_)
                 | Token::F64(_)
                 | Token::I64(_)
                 | Token::U64(_)
+                | Token::I128(_)
+                | Token::U128(_)
                 | Token::True
                 | Token::False
                 | Token::Null => {
@@ -275,9 +336,13 @@ impl Format for Json {
             return res;
         }
     }
+
+    fn is_raw(&self, shape: &'static Shape) -> bool {
+        crate::raw::is_raw(shape)
+    }
 }
 
-fn convert_token_error(err: TokenError) -> Spanned<DeserErrorKind> {
+pub(crate) fn convert_token_error(err: TokenError) -> Spanned<DeserErrorKind> {
     match err.kind {
         TokenErrorKind::UnexpectedCharacter(c) => DeserErrorKind::UnexpectedChar {
             got: c,
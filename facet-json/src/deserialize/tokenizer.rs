@@ -1,3 +1,4 @@
+use alloc::borrow::Cow;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
@@ -41,11 +42,11 @@ impl Display for TokenErrorKind {
 }
 
 /// Tokenization result, yielding a spanned token
-pub type TokenizeResult = Result<Spanned<Token>, TokenError>;
+pub type TokenizeResult<'input> = Result<Spanned<Token<'input>>, TokenError>;
 
 /// JSON tokens (without positions)
 #[derive(Debug, Clone, PartialEq)]
-pub enum Token {
+pub enum Token<'input> {
     /// Left brace character: '{'
     LBrace,
     /// Right brace character: '}'
@@ -58,15 +59,18 @@ pub enum Token {
     Colon,
     /// Comma character: ','
     Comma,
-    /// A JSON string value
-    /// TODO: should be a &[u8], lazily de-escaped if/when needed
-    String(String),
+    /// A JSON string value, borrowed from the input unless it has escapes
+    String(Cow<'input, str>),
     /// A 64-bit floating point number value — used if the value contains a decimal point
     F64(f64),
     /// A signed 64-bit integer number value — used if the value does not contain a decimal point but contains a sign
     I64(i64),
     /// An unsigned 64-bit integer number value — used if the value does not contain a decimal point and does not contain a sign
     U64(u64),
+    /// A signed 128-bit integer number value — used if the value has a sign but doesn't fit in an `i64`
+    I128(i128),
+    /// An unsigned 128-bit integer number value — used if the value has no sign but doesn't fit in a `u64`
+    U128(u128),
     /// The JSON boolean value 'true'
     True,
     /// The JSON boolean value 'false'
@@ -77,7 +81,7 @@ pub enum Token {
     Eof,
 }
 
-impl Display for Token {
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Token::LBrace => write!(f, "{{"),
@@ -90,6 +94,8 @@ impl Display for Token {
             Token::F64(n) => write!(f, "{}", n),
             Token::I64(n) => write!(f, "{}", n),
             Token::U64(n) => write!(f, "{}", n),
+            Token::I128(n) => write!(f, "{}", n),
+            Token::U128(n) => write!(f, "{}", n),
             Token::True => write!(f, "true"),
             Token::False => write!(f, "false"),
             Token::Null => write!(f, "null"),
@@ -111,7 +117,7 @@ impl<'input> Tokenizer<'input> {
     }
 
     /// Return the next spanned token or a TokenizeError
-    pub fn next_token(&mut self) -> TokenizeResult {
+    pub fn next_token(&mut self) -> TokenizeResult<'input> {
         self.skip_whitespace();
         let start = self.pos;
         let c = match self.input.get(self.pos).copied() {
@@ -193,19 +199,27 @@ impl<'input> Tokenizer<'input> {
         }
     }
 
-    fn parse_string(&mut self, start: Pos) -> TokenizeResult {
+    fn parse_string(&mut self, start: Pos) -> TokenizeResult<'input> {
         // Skip opening quote
         self.pos += 1;
+        // Only strings with escapes need a buffer, the others are borrowed from the input
         let mut buf = Vec::new();
+        let mut escaped = false;
+        let mut closed = false;
         let content_start = self.pos;
 
         while let Some(&b) = self.input.get(self.pos) {
             match b {
                 b'"' => {
                     self.pos += 1;
+                    closed = true;
                     break;
                 }
                 b'\\' => {
+                    if !escaped {
+                        escaped = true;
+                        buf.extend_from_slice(&self.input[content_start..self.pos]);
+                    }
                     self.pos += 1;
                     if let Some(&esc) = self.input.get(self.pos) {
                         match esc {
@@ -286,28 +300,40 @@ impl<'input> Tokenizer<'input> {
                     }
                 }
                 _ => {
-                    buf.push(b);
+                    if escaped {
+                        buf.push(b);
+                    }
                     self.pos += 1;
                 }
             }
         }
 
         // Check if we reached the end without finding a closing quote
-        if self.pos > self.input.len()
-            || (self.pos == self.input.len() && self.input[self.pos - 1] != b'"')
-        {
+        if !closed {
             return Err(TokenError {
                 kind: TokenErrorKind::UnexpectedEof("in string literal"),
                 span: Span::new(start, self.pos - start),
             });
         }
 
-        let s = match str::from_utf8(&buf) {
-            Ok(st) => st.to_string(),
-            Err(e) => {
+        let s = if escaped {
+            str::from_utf8(&buf)
+                .map(|s| Cow::Owned(s.to_string()))
+                .map_err(|e| (e, buf.len()))
+        } else {
+            // Without the closing quote
+            let input: &'input [u8] = self.input;
+            let content = &input[content_start..self.pos - 1];
+            str::from_utf8(content)
+                .map(Cow::Borrowed)
+                .map_err(|e| (e, content.len()))
+        };
+        let s = match s {
+            Ok(s) => s,
+            Err((e, len)) => {
                 return Err(TokenError {
                     kind: TokenErrorKind::InvalidUtf8(e.to_string()),
-                    span: Span::new(content_start, buf.len()),
+                    span: Span::new(content_start, len),
                 });
             }
         };
@@ -320,7 +346,7 @@ impl<'input> Tokenizer<'input> {
         })
     }
 
-    fn parse_number(&mut self, start: Pos) -> TokenizeResult {
+    fn parse_number(&mut self, start: Pos) -> TokenizeResult<'input> {
         let mut end = self.pos;
         if self.input[end] == b'-' {
             end += 1;
@@ -368,30 +394,36 @@ impl<'input> Tokenizer<'input> {
                 }
             }
         } else if text.starts_with('-') {
-            // If the number starts with a negative sign, parse as i64
+            // If the number starts with a negative sign, parse as i64, or i128 if it's too big
             match text.parse::<i64>() {
                 Ok(n) => Token::I64(n),
-                Err(_) => {
-                    // If i64 parsing fails, try to parse as f64 for error reporting
-                    let num = text.parse::<f64>().unwrap_or(0.0);
-                    return Err(TokenError {
-                        kind: TokenErrorKind::NumberOutOfRange(num),
-                        span,
-                    });
-                }
+                Err(_) => match text.parse::<i128>() {
+                    Ok(n) => Token::I128(n),
+                    Err(_) => {
+                        // If i128 parsing fails, try to parse as f64 for error reporting
+                        let num = text.parse::<f64>().unwrap_or(0.0);
+                        return Err(TokenError {
+                            kind: TokenErrorKind::NumberOutOfRange(num),
+                            span,
+                        });
+                    }
+                },
             }
         } else {
-            // Otherwise, parse as u64
+            // Otherwise, parse as u64, or u128 if it's too big
             match text.parse::<u64>() {
                 Ok(n) => Token::U64(n),
-                Err(_) => {
-                    // If u64 parsing fails, try to parse as f64 for error reporting
-                    let num = text.parse::<f64>().unwrap_or(0.0);
-                    return Err(TokenError {
-                        kind: TokenErrorKind::NumberOutOfRange(num),
-                        span,
-                    });
-                }
+                Err(_) => match text.parse::<u128>() {
+                    Ok(n) => Token::U128(n),
+                    Err(_) => {
+                        // If u128 parsing fails, try to parse as f64 for error reporting
+                        let num = text.parse::<f64>().unwrap_or(0.0);
+                        return Err(TokenError {
+                            kind: TokenErrorKind::NumberOutOfRange(num),
+                            span,
+                        });
+                    }
+                },
             }
         };
 
@@ -399,9 +431,9 @@ impl<'input> Tokenizer<'input> {
         Ok(Spanned { node: token, span })
     }
 
-    fn parse_literal<F>(&mut self, start: Pos, pat: &[u8], ctor: F) -> TokenizeResult
+    fn parse_literal<F>(&mut self, start: Pos, pat: &[u8], ctor: F) -> TokenizeResult<'input>
     where
-        F: FnOnce() -> Token,
+        F: FnOnce() -> Token<'input>,
     {
         let end = start + pat.len();
         if end <= self.input.len() && &self.input[start..end] == pat {
@@ -0,0 +1,232 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::ControlFlow;
+use std::io::{self, Read};
+
+use facet_core::Facet;
+use facet_deserialize::{DeserError, DeserErrorKind, Span, Spannable};
+
+use crate::deserialize::tokenizer::Token;
+use crate::from_slice;
+use crate::stream::{Pending, unexpected};
+
+/// How much is read at once
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Lazily deserializes the elements of a top-level JSON array read from `reader`, one at a
+/// time, so the whole array is never held in memory.
+///
+/// Anything after the closing `]` is left unread.
+pub fn iter_array<R: Read, T: for<'a> Facet<'a>>(reader: R) -> JsonIter<R, T> {
+    JsonIter::new(reader, IterState::ArrayStart)
+}
+
+/// Lazily deserializes newline-delimited JSON ([NDJSON](https://github.com/ndjson/ndjson-spec))
+/// read from `reader`: a value per line, with blank lines skipped.
+pub fn from_ndjson_reader<R: Read, T: for<'a> Facet<'a>>(reader: R) -> JsonIter<R, T> {
+    JsonIter::new(reader, IterState::Lines)
+}
+
+/// An error reading JSON from an [`io::Read`]
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading the input failed
+    Io(io::Error),
+    /// The input isn't valid JSON, or doesn't fit the type
+    Deser(DeserError<'static>),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "I/O error: {e}"),
+            ReadError::Deser(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl core::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Deser(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<DeserError<'static>> for ReadError {
+    fn from(e: DeserError<'static>) -> Self {
+        ReadError::Deser(e)
+    }
+}
+
+/// An iterator over JSON values read from an [`io::Read`], see [`iter_array`] and
+/// [`from_ndjson_reader`].
+///
+/// The bytes of each value are gathered as they're read, and handed to [`from_slice`] once
+/// the value is complete, so it's deserialized exactly like a whole document would be.
+/// Since they don't outlive that, `T` can't borrow from the input.
+///
+/// It stops after the first error.
+pub struct JsonIter<R, T> {
+    reader: R,
+    pending: Pending,
+    /// The bytes of the value being read so far
+    value: Vec<u8>,
+    /// How many arrays and objects the value being read is in the middle of
+    depth: usize,
+    state: IterState,
+    eof: bool,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Where a [`JsonIter`] is in its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IterState {
+    /// Before the `[` of the array
+    ArrayStart,
+    /// Right after the `[`, where the array may end already
+    FirstElement,
+    /// In an element, or right before it
+    Element,
+    /// After an element, before the `,` or `]`
+    AfterElement,
+    /// Between or in the values of newline-delimited JSON
+    Lines,
+    /// At the end, or after an error
+    Done,
+}
+
+impl<R: Read, T: for<'a> Facet<'a>> JsonIter<R, T> {
+    fn new(reader: R, state: IterState) -> Self {
+        Self {
+            reader,
+            pending: Pending::default(),
+            value: Vec::new(),
+            depth: 0,
+            state,
+            eof: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Reads until the next value is complete, or the input ends, and returns its bytes
+    fn next_value(&mut self) -> Result<Option<Vec<u8>>, ReadError> {
+        loop {
+            let mut complete = false;
+            let (value, depth, state) = (&mut self.value, &mut self.depth, &mut self.state);
+            let mut last_end = None;
+            self.pending.tokenize(self.eof, |token, input| {
+                match (*state, &token.node) {
+                    (IterState::ArrayStart, Token::LBracket) => {
+                        *state = IterState::FirstElement;
+                        return Ok(ControlFlow::Continue(()));
+                    }
+                    (IterState::ArrayStart, _) => {
+                        return Err(unexpected(&token, input, "an array"));
+                    }
+                    (IterState::FirstElement | IterState::AfterElement, Token::RBracket) => {
+                        *state = IterState::Done;
+                        return Ok(ControlFlow::Break(()));
+                    }
+                    (IterState::AfterElement, Token::Comma) => {
+                        *state = IterState::Element;
+                        return Ok(ControlFlow::Continue(()));
+                    }
+                    (IterState::AfterElement, _) => {
+                        return Err(unexpected(&token, input, "',' or ']'"));
+                    }
+                    (IterState::FirstElement, _) => *state = IterState::Element,
+                    _ => {}
+                }
+
+                // Whitespace in between tokens of the value is kept, so errors point at the
+                // right column. What was already consumed along with the last chunk is
+                // replaced with a space, which still separates its tokens.
+                let start = match last_end {
+                    Some(end) => end,
+                    None => {
+                        if !value.is_empty() {
+                            value.push(b' ');
+                        }
+                        token.span.start()
+                    }
+                };
+                value.extend_from_slice(&input[start..token.span.end()]);
+                last_end = Some(token.span.end());
+
+                match token.node {
+                    Token::LBrace | Token::LBracket => *depth += 1,
+                    Token::RBrace | Token::RBracket => *depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                if *depth > 0 {
+                    return Ok(ControlFlow::Continue(()));
+                }
+                if *state == IterState::Element {
+                    *state = IterState::AfterElement;
+                }
+                complete = true;
+                Ok(ControlFlow::Break(()))
+            })?;
+
+            if complete {
+                return Ok(Some(core::mem::take(&mut self.value)));
+            }
+            if self.state == IterState::Done {
+                return Ok(None);
+            }
+            if self.eof {
+                // Only whitespace is left in between lines, but anywhere else more was expected
+                if self.state == IterState::Lines && self.value.is_empty() {
+                    return Ok(None);
+                }
+                let span = Span::new(self.pending.len(), 0);
+                return Err(self
+                    .pending
+                    .error(
+                        DeserErrorKind::UnexpectedEof {
+                            wanted: "the rest of the input",
+                        }
+                        .with_span(span),
+                    )
+                    .into());
+            }
+
+            let mut chunk = [0u8; CHUNK_SIZE];
+            match self.reader.read(&mut chunk) {
+                Ok(0) => self.eof = true,
+                Ok(n) => self.pending.extend(&chunk[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl<R: Read, T: for<'a> Facet<'a>> Iterator for JsonIter<R, T> {
+    type Item = Result<T, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == IterState::Done {
+            return None;
+        }
+        let res = self
+            .next_value()
+            .and_then(|value| match value {
+                Some(value) => Ok(Some(from_slice::<T>(&value).map_err(|e| e.into_owned())?)),
+                None => Ok(None),
+            })
+            .transpose();
+        if let Some(Err(_)) | None = res {
+            self.state = IterState::Done;
+        }
+        res
+    }
+}
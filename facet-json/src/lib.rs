@@ -14,3 +14,20 @@ pub use serialize::*;
 
 mod deserialize;
 pub use deserialize::*;
+pub use facet_deserialize::{DeserializeOptions, FieldNames, UnixTimestamps, UnknownFields};
+
+mod value;
+pub use value::*;
+
+mod stream;
+pub use stream::*;
+
+#[cfg(feature = "std")]
+mod iter;
+#[cfg(feature = "std")]
+pub use iter::*;
+
+// Implementing `Facet` by hand is unsafe
+#[allow(unsafe_code)]
+mod raw;
+pub use raw::*;
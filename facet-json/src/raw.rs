@@ -0,0 +1,201 @@
+use alloc::string::String;
+use core::fmt;
+
+use facet_core::{
+    Def, Facet, PtrConst, PtrMut, PtrUninit, ScalarAffinity, ScalarDef, Shape, TryFromError, Type,
+    UserType, ValueVTable, value_vtable,
+};
+use facet_deserialize::DeserError;
+
+use crate::{Value, from_str, value_from_str};
+
+/// A JSON value kept as text, borrowed from wherever it comes from.
+///
+/// It's written out verbatim when serialized. When deserialized, the value is captured as it's
+/// written in the input without being parsed, so it can be parsed later with
+/// [`RawValue::deserialize`], or passed along as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawValue<'a>(&'a str);
+
+impl<'a> RawValue<'a> {
+    /// Wraps `json`, after checking that it's a single JSON value
+    pub fn new(json: &'a str) -> Result<Self, DeserError<'a>> {
+        value_from_str(json)?;
+        Ok(Self(json))
+    }
+
+    /// Wraps `json` without checking it: it's written out as-is, valid JSON or not
+    pub const fn new_unchecked(json: &'a str) -> Self {
+        Self(json)
+    }
+
+    /// Returns the JSON text
+    pub const fn get(&self) -> &'a str {
+        self.0
+    }
+
+    /// Copies the JSON text into an [`OwnedRawValue`]
+    pub fn into_owned(self) -> OwnedRawValue {
+        OwnedRawValue(self.0.into())
+    }
+
+    /// Parses the JSON text into a `T`
+    pub fn deserialize<T: Facet<'a>>(&self) -> Result<T, DeserError<'a>> {
+        from_str(self.0)
+    }
+}
+
+impl fmt::Display for RawValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// A JSON value kept as text, like [`RawValue`], that owns its text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedRawValue(String);
+
+impl OwnedRawValue {
+    /// Wraps `json`, after checking that it's a single JSON value
+    pub fn new(json: String) -> Result<Self, DeserError<'static>> {
+        value_from_str(&json).map_err(|e| e.into_owned())?;
+        Ok(Self(json))
+    }
+
+    /// Wraps `json` without checking it: it's written out as-is, valid JSON or not
+    pub const fn new_unchecked(json: String) -> Self {
+        Self(json)
+    }
+
+    /// Returns the JSON text
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    /// Borrows the JSON text as a [`RawValue`]
+    pub fn as_raw(&self) -> RawValue<'_> {
+        RawValue(&self.0)
+    }
+
+    /// Parses the JSON text into a `T`
+    pub fn deserialize<'a, T: Facet<'a>>(&'a self) -> Result<T, DeserError<'a>> {
+        from_str(&self.0)
+    }
+}
+
+impl fmt::Display for OwnedRawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+unsafe impl<'a> Facet<'a> for RawValue<'a> {
+    const VTABLE: &'static ValueVTable = &const {
+        // The deserializer puts the text it captured as a `&str`
+        unsafe fn try_from<'src, 'dst>(
+            src_ptr: PtrConst<'src>,
+            src_shape: &'static Shape,
+            dst: PtrUninit<'dst>,
+        ) -> Result<PtrMut<'dst>, TryFromError> {
+            if src_shape.id != <&'src str as Facet>::SHAPE.id {
+                return Err(TryFromError::UnsupportedSourceShape {
+                    src_shape,
+                    expected: &[<&'src str as Facet>::SHAPE],
+                });
+            }
+            let json: &str = unsafe { src_ptr.read::<&str>() };
+            Ok(unsafe { dst.put(RawValue(json)) })
+        }
+
+        let mut vtable = value_vtable!(RawValue<'_>, |f, _opts| write!(f, "RawValue"));
+        vtable.try_from = Some(try_from);
+        vtable
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    .affinity(ScalarAffinity::opaque().build())
+                    .build(),
+            ))
+            .build()
+    };
+}
+
+unsafe impl Facet<'_> for OwnedRawValue {
+    const VTABLE: &'static ValueVTable = &const {
+        // The deserializer puts the text it captured as a `&str`
+        unsafe fn try_from<'src, 'dst>(
+            src_ptr: PtrConst<'src>,
+            src_shape: &'static Shape,
+            dst: PtrUninit<'dst>,
+        ) -> Result<PtrMut<'dst>, TryFromError> {
+            if src_shape.id != <&'src str as Facet>::SHAPE.id {
+                return Err(TryFromError::UnsupportedSourceShape {
+                    src_shape,
+                    expected: &[<&'src str as Facet>::SHAPE],
+                });
+            }
+            let json: &str = unsafe { src_ptr.read::<&str>() };
+            Ok(unsafe { dst.put(OwnedRawValue(json.into())) })
+        }
+
+        let mut vtable = value_vtable!(OwnedRawValue, |f, _opts| write!(f, "OwnedRawValue"));
+        vtable.try_from = Some(try_from);
+        vtable
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    .affinity(ScalarAffinity::opaque().build())
+                    .build(),
+            ))
+            .build()
+    };
+}
+
+// A `Value` is read like a raw value, and parsed from the text captured for it
+unsafe impl Facet<'_> for Value {
+    const VTABLE: &'static ValueVTable = &const {
+        unsafe fn try_from<'src, 'dst>(
+            src_ptr: PtrConst<'src>,
+            src_shape: &'static Shape,
+            dst: PtrUninit<'dst>,
+        ) -> Result<PtrMut<'dst>, TryFromError> {
+            if src_shape.id != <&'src str as Facet>::SHAPE.id {
+                return Err(TryFromError::UnsupportedSourceShape {
+                    src_shape,
+                    expected: &[<&'src str as Facet>::SHAPE],
+                });
+            }
+            let json: &str = unsafe { src_ptr.read::<&str>() };
+            let value = value_from_str(json).map_err(|_| TryFromError::Generic("invalid JSON"))?;
+            Ok(unsafe { dst.put(value) })
+        }
+
+        let mut vtable = value_vtable!(Value, |f, _opts| write!(f, "Value"));
+        vtable.try_from = Some(try_from);
+        vtable
+    };
+
+    const SHAPE: &'static Shape = &const {
+        Shape::builder_for_sized::<Self>()
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    .affinity(ScalarAffinity::opaque().build())
+                    .build(),
+            ))
+            .build()
+    };
+}
+
+/// Whether values of `shape` are raw JSON, to be written and read as-is
+pub(crate) fn is_raw(shape: &'static Shape) -> bool {
+    shape == RawValue::SHAPE || shape == OwnedRawValue::SHAPE || shape == Value::SHAPE
+}
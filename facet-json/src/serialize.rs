@@ -1,43 +1,451 @@
-use facet_core::Facet;
-use facet_reflect::Peek;
-use facet_serialize::{Serializer, serialize_iterative};
+use alloc::borrow::Cow;
+use facet_core::{Facet, Shape};
+use facet_reflect::{Peek, ReflectError};
+pub use facet_serialize::{DurationFormat, SensitiveFields};
+use facet_serialize::{Serializer, Unsupported, serialize_iterative};
 use log::debug;
 use std::io::{self, Write};
 
 /// Serializes a value to JSON
+///
+/// Panics if it can't be, e.g. if it holds a scalar of unknown type: [`to_writer`] returns
+/// a [`SerializeError`] instead.
 pub fn to_string<'a, T: Facet<'a>>(value: &T) -> String {
-    let peek = Peek::new(value);
-    let mut output = Vec::new();
-    let mut serializer = JsonSerializer::new(&mut output);
-    serialize_iterative(peek, &mut serializer).unwrap();
-    String::from_utf8(output).unwrap()
+    peek_to_string(&Peek::new(value))
+}
+
+/// Serializes a value to JSON, indented with two spaces
+///
+/// Panics if it can't be, like [`to_string`].
+pub fn to_string_pretty<'a, T: Facet<'a>>(value: &T) -> String {
+    peek_to_string_pretty(&Peek::new(value))
+}
+
+/// Serializes a value to JSON, laid out according to `options`
+///
+/// Options can make serializing fail, like [`NonFiniteFloats::Error`], so this returns an error
+/// rather than panicking.
+pub fn to_string_with_options<'a, T: Facet<'a>>(
+    value: &T,
+    options: SerializeOptions,
+) -> Result<String, SerializeError> {
+    peek_to_string_with_options(&Peek::new(value), options)
 }
 
 /// Serializes a Peek instance to JSON
+///
+/// Panics if it can't be, like [`to_string`].
 pub fn peek_to_string(peek: &Peek<'_, '_>) -> String {
+    peek_to_string_with_options(peek, SerializeOptions::new()).unwrap()
+}
+
+/// Serializes a Peek instance to JSON, indented with two spaces
+///
+/// Panics if it can't be, like [`to_string`].
+pub fn peek_to_string_pretty(peek: &Peek<'_, '_>) -> String {
+    peek_to_string_with_options(peek, SerializeOptions::new().pretty()).unwrap()
+}
+
+/// Serializes a Peek instance to JSON, laid out according to `options`
+pub fn peek_to_string_with_options(
+    peek: &Peek<'_, '_>,
+    options: SerializeOptions,
+) -> Result<String, SerializeError> {
+    // Only valid UTF-8 is ever written
+    Ok(String::from_utf8(peek_to_vec_with_options(peek, options)?).unwrap())
+}
+
+/// Serializes a value to JSON bytes
+///
+/// Panics if it can't be, like [`to_string`].
+pub fn to_vec<'a, T: Facet<'a>>(value: &T) -> Vec<u8> {
+    to_vec_with_options(value, SerializeOptions::new()).unwrap()
+}
+
+/// Serializes a value to JSON bytes, indented with two spaces
+///
+/// Panics if it can't be, like [`to_string`].
+pub fn to_vec_pretty<'a, T: Facet<'a>>(value: &T) -> Vec<u8> {
+    to_vec_with_options(value, SerializeOptions::new().pretty()).unwrap()
+}
+
+/// Serializes a value to JSON bytes, laid out according to `options`
+pub fn to_vec_with_options<'a, T: Facet<'a>>(
+    value: &T,
+    options: SerializeOptions,
+) -> Result<Vec<u8>, SerializeError> {
+    peek_to_vec_with_options(&Peek::new(value), options)
+}
+
+/// Serializes a Peek instance to JSON bytes, laid out according to `options`
+pub fn peek_to_vec_with_options(
+    peek: &Peek<'_, '_>,
+    options: SerializeOptions,
+) -> Result<Vec<u8>, SerializeError> {
+    // A `Vec` is its own buffer, so it's written to directly
     let mut output = Vec::new();
-    let mut serializer = JsonSerializer::new(&mut output);
-    serialize_iterative(*peek, &mut serializer).unwrap();
-    String::from_utf8(output).unwrap()
+    let mut serializer = JsonSerializer::with_options(&mut output, options);
+    serialize_iterative(*peek, &mut serializer)?;
+    Ok(output)
 }
 
 /// Serializes a value to a writer in JSON format
-pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &T, writer: &mut W) -> io::Result<()> {
-    let peek = Peek::new(value);
-    let mut serializer = JsonSerializer::new(writer);
-    serialize_iterative(peek, &mut serializer)
+pub fn to_writer<'a, T: Facet<'a>, W: Write>(
+    value: &T,
+    writer: &mut W,
+) -> Result<(), SerializeError> {
+    to_writer_with_options(value, writer, SerializeOptions::new())
+}
+
+/// Serializes a value to a writer in JSON format, indented with two spaces
+pub fn to_writer_pretty<'a, T: Facet<'a>, W: Write>(
+    value: &T,
+    writer: &mut W,
+) -> Result<(), SerializeError> {
+    to_writer_with_options(value, writer, SerializeOptions::new().pretty())
+}
+
+/// Serializes a value to a writer in JSON format, laid out according to `options`
+pub fn to_writer_with_options<'a, T: Facet<'a>, W: Write>(
+    value: &T,
+    writer: &mut W,
+    options: SerializeOptions,
+) -> Result<(), SerializeError> {
+    peek_to_writer_with_options(&Peek::new(value), writer, options)
 }
 
 /// Serializes a Peek instance to a writer in JSON format
-pub fn peek_to_writer<W: Write>(peek: &Peek<'_, '_>, writer: &mut W) -> io::Result<()> {
-    let mut serializer = JsonSerializer::new(writer);
-    serialize_iterative(*peek, &mut serializer)
+pub fn peek_to_writer<W: Write>(peek: &Peek<'_, '_>, writer: &mut W) -> Result<(), SerializeError> {
+    peek_to_writer_with_options(peek, writer, SerializeOptions::new())
+}
+
+/// Serializes a Peek instance to a writer in JSON format, indented with two spaces
+pub fn peek_to_writer_pretty<W: Write>(
+    peek: &Peek<'_, '_>,
+    writer: &mut W,
+) -> Result<(), SerializeError> {
+    peek_to_writer_with_options(peek, writer, SerializeOptions::new().pretty())
+}
+
+/// Serializes a Peek instance to a writer in JSON format, laid out according to `options`
+///
+/// Output is buffered, so the writer sees a few large writes rather than one per token: there's
+/// no need to wrap a `File` or a `TcpStream` in a `BufWriter` first.
+pub fn peek_to_writer_with_options<W: Write>(
+    peek: &Peek<'_, '_>,
+    writer: &mut W,
+    options: SerializeOptions,
+) -> Result<(), SerializeError> {
+    let mut buffered = io::BufWriter::new(writer);
+    let mut serializer = JsonSerializer::with_options(&mut buffered, options);
+    serialize_iterative(*peek, &mut serializer)?;
+    Ok(buffered.flush()?)
+}
+
+/// Why a value couldn't be serialized to JSON
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SerializeError {
+    /// The writer failed
+    Io(io::Error),
+    /// A `NaN` or an infinite float, with [`NonFiniteFloats::Error`]
+    NonFiniteFloat {
+        /// The float
+        value: f64,
+        /// Where it is in the value, like `$.readings[2].value`
+        path: String,
+    },
+    /// A value that contains itself, with [`Cycles::Error`]
+    Cycle {
+        /// The name of the shape of the value
+        shape: String,
+        /// Where it contains itself, like `$.children[0].parent`
+        path: String,
+    },
+    /// The value, or something in it, is of a shape that can't be serialized
+    UnsupportedShape {
+        /// The name of the shape
+        shape: String,
+        /// Why it can't be serialized
+        reason: &'static str,
+    },
+    /// The vtable of something in the value lacks what's needed to serialize it
+    MissingCapability {
+        /// The name of the shape
+        shape: String,
+        /// What it can't do, e.g. "borrowing its pointee"
+        capability: &'static str,
+    },
+    /// The `#[facet(serialize_with = ...)]` function of a field failed
+    ConversionFailed(ReflectError),
+}
+
+impl core::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SerializeError::Io(err) => write!(f, "{err}"),
+            SerializeError::NonFiniteFloat { value, path } => {
+                write!(f, "{value} can't be written as a JSON number, at {path}")
+            }
+            SerializeError::Cycle { shape, path } => write!(f, "{shape} contains itself at {path}"),
+            SerializeError::UnsupportedShape { shape, reason } => {
+                write!(f, "Can't serialize {shape}: {reason}")
+            }
+            SerializeError::MissingCapability { shape, capability } => {
+                write!(
+                    f,
+                    "Can't serialize {shape}: its vtable has no way of {capability}"
+                )
+            }
+            SerializeError::ConversionFailed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for SerializeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SerializeError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SerializeError {
+    fn from(err: io::Error) -> Self {
+        SerializeError::Io(err)
+    }
+}
+
+impl From<Unsupported> for SerializeError {
+    fn from(unsupported: Unsupported) -> Self {
+        match unsupported {
+            Unsupported::MissingCapability { shape, capability } => {
+                SerializeError::MissingCapability {
+                    shape: shape.to_string(),
+                    capability,
+                }
+            }
+            Unsupported::ConversionFailed(err) => SerializeError::ConversionFailed(err),
+            Unsupported::Shape { shape, reason } => SerializeError::UnsupportedShape {
+                shape: shape.to_string(),
+                reason,
+            },
+            _ => SerializeError::UnsupportedShape {
+                shape: String::from("a value"),
+                reason: "it isn't supported",
+            },
+        }
+    }
+}
+
+/// How serialized JSON is laid out, and what goes in it
+///
+/// The default is compact: no whitespace at all, with every field included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    indent: Option<Indent>,
+    sensitive: SensitiveFields,
+    cycles: Cycles,
+    bytes: Bytes,
+    non_finite_floats: NonFiniteFloats,
+    floats: Floats,
+    large_integers: LargeIntegers,
+    timestamps: Timestamps,
+    durations: DurationFormat,
+    key_order: KeyOrder,
+}
+
+impl SerializeOptions {
+    /// Compact output, on a single line
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts every array item and object entry on its own line, indented with two spaces
+    pub fn pretty(self) -> Self {
+        self.indent(Indent::Spaces(2))
+    }
+
+    /// Output that's the same, byte for byte, for equal values, so it can be hashed or signed:
+    /// compact, with keys sorted, floats written the way JavaScript writes them, and failing on
+    /// `NaN` and infinities, much like JSON canonicalization (RFC 8785) asks for
+    pub fn canonical(self) -> Self {
+        Self {
+            indent: None,
+            ..self
+        }
+        .key_order(KeyOrder::Sorted)
+        .floats(Floats::Ecmascript)
+        .non_finite_floats(NonFiniteFloats::Error)
+    }
+
+    /// Puts every array item and object entry on its own line, indented with `indent`
+    pub fn indent(mut self, indent: Indent) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Leaves out, or masks, the fields marked `#[facet(sensitive)]`
+    pub fn sensitive_fields(mut self, sensitive: SensitiveFields) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Looks out for values that contain themselves, through references or smart pointers
+    pub fn cycles(mut self, cycles: Cycles) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Picks how lists, arrays and slices of `u8` are written
+    pub fn bytes(mut self, bytes: Bytes) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    /// Picks what's written for `NaN` and infinite floats
+    pub fn non_finite_floats(mut self, non_finite_floats: NonFiniteFloats) -> Self {
+        self.non_finite_floats = non_finite_floats;
+        self
+    }
+
+    /// Picks how finite floats are written
+    pub fn floats(mut self, floats: Floats) -> Self {
+        self.floats = floats;
+        self
+    }
+
+    /// Picks how integers that a double can't hold exactly are written
+    pub fn large_integers(mut self, large_integers: LargeIntegers) -> Self {
+        self.large_integers = large_integers;
+        self
+    }
+
+    /// Picks how timestamps and datetimes that stand for an instant, like a
+    /// `chrono::DateTime<Utc>`, are written
+    pub fn timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Picks how `Duration`s are written, except in fields with a `duration_format` attribute
+    pub fn durations(mut self, durations: DurationFormat) -> Self {
+        self.durations = durations;
+        self
+    }
+
+    /// Picks the order struct fields, map entries and set items are written in
+    pub fn key_order(mut self, key_order: KeyOrder) -> Self {
+        self.key_order = key_order;
+        self
+    }
+}
+
+/// What's written for `NaN` and infinite floats, which JSON numbers can't be
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    /// `null`, like most JSON libraries do
+    #[default]
+    Null,
+    /// Nothing: serializing fails with a [`SerializeError::NonFiniteFloat`]
+    Error,
+    /// A `"NaN"`, `"Infinity"` or `"-Infinity"` string, which deserializing reads back
+    String,
+}
+
+/// How finite floats are written: either way, with the fewest digits that read back as the same
+/// value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Floats {
+    /// In scientific notation from 1e16 up and below 1e-5, `1e16`, `0.5` or `1e-7`
+    #[default]
+    Shortest,
+    /// Like JavaScript's `Number.prototype.toString`, as JSON canonicalization (RFC 8785) asks:
+    /// in scientific notation from 1e21 up and below 1e-6, with a signed exponent, `1e+21`,
+    /// `0.000001` or `1e-7`, and zero always `0`
+    Ecmascript,
+}
+
+/// The order struct fields, map entries and set items are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyOrder {
+    /// The order fields are declared in, and entries and items are iterated in: for hash maps
+    /// and sets, that may change from one run to the next
+    #[default]
+    Unsorted,
+    /// Fields sorted by name, entries by key and items by value
+    Sorted,
+}
+
+/// How integers beyond ±2⁵³ are written: a double can't hold all of them exactly, so readers
+/// that parse every number as one, like JavaScript's, round them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LargeIntegers {
+    /// As numbers, like all other integers
+    #[default]
+    Number,
+    /// As strings of their digits, which deserializing reads back
+    String,
+}
+
+/// How timestamps and datetimes that stand for an instant, like a `jiff::Timestamp`, are written
+///
+/// Either way, they can be read back: deserializing an instant accepts both strings and numbers.
+/// Dates and datetimes without an offset, like a `chrono::NaiveDate`, are always strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timestamps {
+    /// As RFC 3339 strings, `"2024-06-19T19:22:45Z"`
+    #[default]
+    Rfc3339,
+    /// As whole seconds since the unix epoch, rounded down, `1718824965`
+    UnixSeconds,
+    /// As whole milliseconds since the unix epoch, rounded down, `1718824965000`
+    UnixMillis,
+}
+
+/// How lists, arrays and slices of `u8`, like `Vec<u8>`, are written
+///
+/// Either way, they can be read back: deserializing a byte list accepts both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bytes {
+    /// As an array of numbers, `[104,105]`
+    #[default]
+    Array,
+    /// As a string of their standard, padded base64 encoding, `"aGk="`
+    Base64,
+}
+
+/// What's done when a value turns out to contain itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Cycles {
+    /// Nothing, values aren't kept track of: serializing a cycle never ends
+    #[default]
+    Ignore,
+    /// Serializing fails with a [`SerializeError::Cycle`]
+    Error,
+    /// The value is written as a `"<cycle: Type>"` string the second time around
+    Placeholder,
+}
+
+/// What one level of nesting is indented with, in pretty output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// This many spaces
+    Spaces(usize),
+    /// A single tab
+    Tab,
 }
 
 #[derive(Debug)]
 enum StackItem {
-    ArrayItem { first: bool },
-    ObjectItem { object_state: ObjectItemState },
+    /// `items` have been started so far
+    ArrayItem { items: usize },
+    /// `key` is the field name or map key that was written last, kept for the paths of errors
+    ObjectItem {
+        object_state: ObjectItemState,
+        key: Option<Cow<'static, str>>,
+    },
 }
 
 #[derive(Debug)]
@@ -51,6 +459,7 @@ enum ObjectItemState {
 pub struct JsonSerializer<W> {
     writer: W,
     stack: Vec<StackItem>,
+    options: SerializeOptions,
 }
 
 impl<W> JsonSerializer<W>
@@ -59,36 +468,73 @@ where
 {
     /// Creates a new JSON serializer with the given writer.
     pub fn new(writer: W) -> Self {
+        Self::with_options(writer, SerializeOptions::new())
+    }
+
+    /// Creates a new JSON serializer with the given writer, laying out its output
+    /// according to `options`.
+    pub fn with_options(writer: W, options: SerializeOptions) -> Self {
         Self {
             writer,
             stack: Vec::new(),
+            options,
         }
     }
 
-    fn start_value(&mut self) -> Result<(), io::Error> {
+    /// Starts a new line at the current depth, when pretty-printing
+    fn write_newline(&mut self) -> Result<(), SerializeError> {
+        let Some(indent) = self.options.indent else {
+            return Ok(());
+        };
+        self.writer.write_all(b"\n")?;
+        let depth = self.stack.len();
+        match indent {
+            Indent::Spaces(width) => Ok(write!(self.writer, "{:1$}", "", width * depth)?),
+            Indent::Tab => {
+                for _ in 0..depth {
+                    self.writer.write_all(b"\t")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the separator between an object key and its value
+    fn write_colon(&mut self) -> Result<(), SerializeError> {
+        let colon: &[u8] = if self.options.indent.is_some() {
+            b": "
+        } else {
+            b":"
+        };
+        Ok(self.writer.write_all(colon)?)
+    }
+
+    fn start_value(&mut self) -> Result<(), SerializeError> {
         debug!("start_value, stack = {:?}", self.stack);
 
         match self.stack.last_mut() {
-            Some(StackItem::ArrayItem { first }) => {
-                if *first {
-                    *first = false;
-                } else {
+            Some(StackItem::ArrayItem { items }) => {
+                if *items > 0 {
                     write!(self.writer, ",")?;
                 }
+                *items += 1;
+                self.write_newline()?;
             }
-            Some(StackItem::ObjectItem { object_state }) => {
+            Some(StackItem::ObjectItem { object_state, .. }) => {
                 debug!("ObjectItem: object_state = {:?}", object_state);
                 match object_state {
                     ObjectItemState::FirstKey => {
                         *object_state = ObjectItemState::Value;
+                        self.write_newline()?;
                     }
                     ObjectItemState::Key => {
-                        write!(self.writer, ",")?;
                         *object_state = ObjectItemState::Value;
+                        write!(self.writer, ",")?;
+                        self.write_newline()?;
                     }
                     ObjectItemState::Value => {
-                        write!(self.writer, ":")?;
                         *object_state = ObjectItemState::Key;
+                        self.write_colon()?;
                     }
                 }
             }
@@ -100,16 +546,125 @@ where
         Ok(())
     }
 
-    fn end_value(&mut self) -> Result<(), io::Error> {
+    fn end_value(&mut self) -> Result<(), SerializeError> {
         Ok(())
     }
+
+    /// Whether errors that report where they happened can: map keys are only copied for their
+    /// paths then
+    fn reports_paths(&self) -> bool {
+        self.options.non_finite_floats == NonFiniteFloats::Error
+            || self.options.cycles == Cycles::Error
+    }
+
+    /// The path of the value about to be written, like `$.readings[2].value`
+    fn path(&self) -> String {
+        use core::fmt::Write as _;
+
+        let mut path = String::from("$");
+        let innermost = self.stack.len().saturating_sub(1);
+        for (depth, item) in self.stack.iter().enumerate() {
+            match item {
+                // The innermost array hasn't started the item being written yet
+                StackItem::ArrayItem { items } if depth == innermost => {
+                    let _ = write!(path, "[{items}]");
+                }
+                StackItem::ArrayItem { items } => {
+                    let _ = write!(path, "[{}]", items.saturating_sub(1));
+                }
+                StackItem::ObjectItem { key: Some(key), .. } => {
+                    path.push('.');
+                    path.push_str(key);
+                }
+                StackItem::ObjectItem { key: None, .. } => {}
+            }
+        }
+        path
+    }
+
+    /// Writes an integer, as a string if it's large and the options say so
+    fn write_integer(
+        &mut self,
+        value: impl core::fmt::Display,
+        exact: bool,
+    ) -> Result<(), SerializeError> {
+        self.start_value()?;
+        if !exact && self.options.large_integers == LargeIntegers::String {
+            write!(self.writer, "\"{}\"", value)?;
+        } else {
+            write!(self.writer, "{}", value)?;
+        }
+        self.end_value()
+    }
+
+    /// Writes a finite float with the fewest digits that parse back to the same value
+    ///
+    /// `Display` never uses an exponent, so very large or very small magnitudes switch to
+    /// scientific notation: `1e300` rather than 301 digits that would read back as an integer.
+    fn write_float(
+        &mut self,
+        value: impl core::fmt::Display + core::fmt::LowerExp,
+        as_f64: f64,
+    ) -> Result<(), SerializeError> {
+        self.start_value()?;
+        let magnitude = as_f64.abs();
+        match self.options.floats {
+            Floats::Shortest => {
+                if magnitude == 0.0 && as_f64.is_sign_negative() {
+                    // `-0` would read back as the integer 0, and so as positive zero
+                    self.writer.write_all(b"-0.0")?;
+                } else if magnitude >= 1e16 || (magnitude != 0.0 && magnitude < 1e-5) {
+                    write!(self.writer, "{:e}", value)?;
+                } else {
+                    write!(self.writer, "{}", value)?;
+                }
+            }
+            Floats::Ecmascript => {
+                if magnitude == 0.0 {
+                    // Negative zero included
+                    self.writer.write_all(b"0")?;
+                } else if (1e-6..1e21).contains(&magnitude) {
+                    write!(self.writer, "{}", value)?;
+                } else {
+                    let scientific = format!("{:e}", value);
+                    match scientific.split_once('e') {
+                        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+                            write!(self.writer, "{mantissa}e+{exponent}")?
+                        }
+                        _ => self.writer.write_all(scientific.as_bytes())?,
+                    }
+                }
+            }
+        }
+        self.end_value()
+    }
+
+    /// Writes a `NaN` or an infinity the way the options say
+    fn write_non_finite(&mut self, value: f64) -> Result<(), SerializeError> {
+        let text = match (value.is_nan(), value.is_sign_negative()) {
+            (true, _) => "NaN",
+            (false, false) => "Infinity",
+            (false, true) => "-Infinity",
+        };
+        match self.options.non_finite_floats {
+            NonFiniteFloats::Null => self.serialize_none(),
+            NonFiniteFloats::Error => Err(SerializeError::NonFiniteFloat {
+                value,
+                path: self.path(),
+            }),
+            NonFiniteFloats::String => self.serialize_str(text),
+        }
+    }
 }
 
+/// The largest integer such that it and all smaller ones fit in an `f64` exactly: 2⁵³
+const MAX_EXACT_INTEGER: u128 = 1 << 53;
+
 impl<W> Serializer for JsonSerializer<W>
 where
     W: Write,
 {
-    type Error = io::Error;
+    type Error = SerializeError;
 
     fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
         self.start_value()?;
@@ -130,21 +685,15 @@ where
     }
 
     fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value as u128 <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_usize(&mut self, value: usize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value as u128 <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_i8(&mut self, value: i8) -> Result<(), Self::Error> {
@@ -166,38 +715,37 @@ where
     }
 
     fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value.unsigned_abs() as u128 <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value.unsigned_abs() <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_isize(&mut self, value: isize) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        self.write_integer(value, value.unsigned_abs() as u128 <= MAX_EXACT_INTEGER)
     }
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        if value.is_finite() {
+            self.write_float(value, value as f64)
+        } else {
+            self.write_non_finite(value as f64)
+        }
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.start_value()?;
-        write!(self.writer, "{}", value)?;
-        self.end_value()
+        if value.is_finite() {
+            self.write_float(value, value)
+        } else {
+            self.write_non_finite(value)
+        }
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
         self.start_value()?;
-        write!(self.writer, "{}", if value { "true" } else { "false" })?;
+        let literal: &[u8] = if value { b"true" } else { b"false" };
+        self.writer.write_all(literal)?;
         self.end_value()
     }
 
@@ -210,13 +758,92 @@ where
     }
 
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        let reports_paths = self.reports_paths();
+        if let Some(StackItem::ObjectItem {
+            object_state: ObjectItemState::FirstKey | ObjectItemState::Key,
+            key,
+        }) = self.stack.last_mut()
+        {
+            // A map key
+            if reports_paths {
+                *key = Some(Cow::Owned(value.to_string()));
+            }
+        }
         self.start_value()?;
         write_json_string(&mut self.writer, value)?;
         self.end_value()
     }
 
-    fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
-        panic!("JSON does not support byte arrays")
+    fn serialize_display(&mut self, value: &dyn core::fmt::Display) -> Result<(), Self::Error> {
+        self.start_value()?;
+        self.writer.write_all(b"\"")?;
+        write_json_display(&mut self.writer, value)?;
+        self.writer.write_all(b"\"")?;
+        self.end_value()
+    }
+
+    fn serialize_time(
+        &mut self,
+        value: &dyn core::fmt::Display,
+        unix_nanos: Option<i128>,
+    ) -> Result<(), Self::Error> {
+        match (self.options.timestamps, unix_nanos) {
+            (Timestamps::UnixSeconds, Some(nanos)) => {
+                self.serialize_i128(nanos.div_euclid(1_000_000_000))
+            }
+            (Timestamps::UnixMillis, Some(nanos)) => {
+                self.serialize_i128(nanos.div_euclid(1_000_000))
+            }
+            _ => self.serialize_display(value),
+        }
+    }
+
+    fn sensitive_fields(&self) -> SensitiveFields {
+        self.options.sensitive
+    }
+
+    fn duration_format(&self) -> DurationFormat {
+        self.options.durations
+    }
+
+    fn sort_keys(&self) -> bool {
+        self.options.key_order == KeyOrder::Sorted
+    }
+
+    fn detect_cycles(&self) -> bool {
+        self.options.cycles != Cycles::Ignore
+    }
+
+    fn serialize_cycle(&mut self, shape: &'static Shape) -> Result<(), Self::Error> {
+        match self.options.cycles {
+            Cycles::Error => Err(SerializeError::Cycle {
+                shape: shape.to_string(),
+                path: self.path(),
+            }),
+            _ => self.serialize_str(&format!("<cycle: {shape}>")),
+        }
+    }
+
+    fn is_raw(&self, shape: &'static Shape) -> bool {
+        crate::raw::is_raw(shape)
+    }
+
+    fn serialize_raw(&mut self, value: &dyn core::fmt::Display) -> Result<(), Self::Error> {
+        self.start_value()?;
+        write!(self.writer, "{}", value)?;
+        self.end_value()
+    }
+
+    fn byte_lists_as_bytes(&self) -> bool {
+        self.options.bytes == Bytes::Base64
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.start_value()?;
+        self.writer.write_all(b"\"")?;
+        write_base64(&mut self.writer, value)?;
+        self.writer.write_all(b"\"")?;
+        self.end_value()
     }
 
     fn serialize_none(&mut self) -> Result<(), Self::Error> {
@@ -246,6 +873,7 @@ where
         self.writer.write_all(b"{")?;
         self.stack.push(StackItem::ObjectItem {
             object_state: ObjectItemState::FirstKey,
+            key: None,
         });
         Ok(())
     }
@@ -254,9 +882,12 @@ where
         let object = self.stack.pop().unwrap();
         match object {
             StackItem::ArrayItem { .. } => unreachable!(),
-            StackItem::ObjectItem { object_state } => match object_state {
-                ObjectItemState::FirstKey | ObjectItemState::Key => {
-                    // good
+            StackItem::ObjectItem { object_state, .. } => match object_state {
+                ObjectItemState::FirstKey => {
+                    // empty, stays on one line
+                }
+                ObjectItemState::Key => {
+                    self.write_newline()?;
                 }
                 ObjectItemState::Value => unreachable!(),
             },
@@ -269,15 +900,18 @@ where
     fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
         self.start_value()?;
         self.writer.write_all(b"[")?;
-        self.stack.push(StackItem::ArrayItem { first: true });
+        self.stack.push(StackItem::ArrayItem { items: 0 });
         Ok(())
     }
 
     fn end_array(&mut self) -> Result<(), Self::Error> {
         let item = self.stack.pop().unwrap();
         match item {
+            StackItem::ArrayItem { items: 0 } => {
+                // empty, stays on one line
+            }
             StackItem::ArrayItem { .. } => {
-                // good
+                self.write_newline()?;
             }
             StackItem::ObjectItem { .. } => unreachable!(),
         }
@@ -296,7 +930,8 @@ where
 
     fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
         // Handle object key comma logic
-        if let Some(StackItem::ObjectItem { object_state }) = self.stack.last_mut() {
+        if let Some(StackItem::ObjectItem { object_state, key }) = self.stack.last_mut() {
+            *key = Some(Cow::Borrowed(name));
             match object_state {
                 ObjectItemState::FirstKey => {
                     *object_state = ObjectItemState::Key;
@@ -306,15 +941,33 @@ where
                 }
                 ObjectItemState::Value => unreachable!(),
             }
+            self.write_newline()?;
         }
         write_json_string(&mut self.writer, name)?;
-        if let Some(StackItem::ObjectItem { object_state }) = self.stack.last_mut() {
+        if let Some(StackItem::ObjectItem { object_state, .. }) = self.stack.last_mut() {
             *object_state = ObjectItemState::Value;
         }
         Ok(())
     }
 }
 
+/// Writes the standard, padded base64 encoding of `bytes` — nothing in it needs escaping
+fn write_base64<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        let mut quad = [b'='; 4];
+        for (i, c) in quad.iter_mut().enumerate().take(chunk.len() + 1) {
+            *c = ALPHABET[(n >> (18 - 6 * i)) as usize & 63];
+        }
+        writer.write_all(&quad)?;
+    }
+    Ok(())
+}
+
 /// Properly escapes and writes a JSON string
 fn write_json_string<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
     writer.write_all(b"\"")?;
@@ -326,6 +979,39 @@ fn write_json_string<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
     writer.write_all(b"\"")
 }
 
+/// Escapes and writes `Display` output as it is formatted, without collecting it in a string
+fn write_json_display<W: Write>(writer: &mut W, value: &dyn core::fmt::Display) -> io::Result<()> {
+    let mut adapter = EscapingAdapter {
+        writer,
+        error: None,
+    };
+    match core::fmt::write(&mut adapter, format_args!("{value}")) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("formatter error"))),
+    }
+}
+
+/// Lets a formatter write escaped JSON string contents to an `io::Write`
+struct EscapingAdapter<'w, W> {
+    writer: &'w mut W,
+    /// The I/O error that interrupted formatting, if any
+    error: Option<io::Error>,
+}
+
+impl<W: Write> core::fmt::Write for EscapingAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            if let Err(e) = write_json_escaped_char(self.writer, c) {
+                self.error = Some(e);
+                return Err(core::fmt::Error);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Writes a single JSON escaped character
 fn write_json_escaped_char<W: Write>(writer: &mut W, c: char) -> io::Result<()> {
     match c {
@@ -337,14 +1023,17 @@ fn write_json_escaped_char<W: Write>(writer: &mut W, c: char) -> io::Result<()>
         '\u{08}' => writer.write_all(b"\\b"),
         '\u{0C}' => writer.write_all(b"\\f"),
         c if c.is_control() => {
-            let mut buf = [0; 6];
-            let s = format!("{:04x}", c as u32);
-            buf[0] = b'\\';
-            buf[1] = b'u';
-            buf[2] = s.as_bytes()[0];
-            buf[3] = s.as_bytes()[1];
-            buf[4] = s.as_bytes()[2];
-            buf[5] = s.as_bytes()[3];
+            // Control characters are all below U+00A0, so four hex digits always do
+            const HEX: &[u8; 16] = b"0123456789abcdef";
+            let c = c as usize;
+            let buf = [
+                b'\\',
+                b'u',
+                HEX[(c >> 12) & 0xf],
+                HEX[(c >> 8) & 0xf],
+                HEX[(c >> 4) & 0xf],
+                HEX[c & 0xf],
+            ];
             writer.write_all(&buf)
         }
         c => {
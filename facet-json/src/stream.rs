@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use facet_core::Facet;
+use facet_deserialize::{DeserError, DeserErrorKind, Span, Spannable, Spanned};
+
+use crate::deserialize::convert_token_error;
+use crate::deserialize::tokenizer::{Token, TokenErrorKind, Tokenizer};
+use crate::value::ValueBuilder;
+use crate::{Value, from_slice};
+
+/// Parses a JSON document that arrives in chunks, e.g. off a network stream.
+///
+/// Each chunk is parsed as soon as it's [fed](JsonStream::feed), so malformed input is
+/// caught early. Once all of it has arrived, [`JsonStream::finish`] deserializes the value
+/// out of the bytes fed, exactly like [`from_slice`] would, and [`JsonStream::finish_value`]
+/// returns the document as a [`Value`].
+///
+/// Errors found while feeding point into the bytes that were still buffered when they were
+/// found, the ones found when finishing into the whole document.
+#[derive(Default)]
+pub struct JsonStream {
+    /// Everything fed so far
+    input: Vec<u8>,
+    /// Input that hasn't been turned into tokens yet
+    pending: Pending,
+    builder: ValueBuilder,
+    value: Option<Value>,
+}
+
+impl JsonStream {
+    /// Creates a parser that hasn't been fed anything yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the next chunk of the document
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), DeserError<'static>> {
+        self.input.extend_from_slice(chunk);
+        self.pending.extend(chunk);
+        self.parse_pending(false)
+    }
+
+    /// Whether a whole document has been parsed.
+    ///
+    /// A number at the top level isn't known to have ended until [`JsonStream::finish`].
+    pub fn is_complete(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// Ends the input, and returns the document as a [`Value`]
+    pub fn finish_value(mut self) -> Result<Value, DeserError<'static>> {
+        self.parse_pending(true)?;
+        self.value.ok_or_else(|| {
+            let span = Span::new(self.pending.len(), 0);
+            self.pending.error(
+                DeserErrorKind::UnexpectedEof {
+                    wanted: "any value (got EOF)",
+                }
+                .with_span(span),
+            )
+        })
+    }
+
+    /// Ends the input, and deserializes the document into a `T`.
+    ///
+    /// The input doesn't outlive this, so `T` can't borrow from it.
+    pub fn finish<T: for<'a> Facet<'a>>(mut self) -> Result<T, DeserError<'static>> {
+        self.parse_pending(true)?;
+        from_slice(&self.input).map_err(|e| e.into_owned())
+    }
+
+    fn parse_pending(&mut self, last: bool) -> Result<(), DeserError<'static>> {
+        let (builder, value) = (&mut self.builder, &mut self.value);
+        self.pending.tokenize(last, |token, input| {
+            if value.is_some() {
+                return Err(unexpected(&token, input, "the end of the input"));
+            }
+            *value = builder.push(token, input)?;
+            Ok(ControlFlow::Continue(()))
+        })
+    }
+}
+
+/// Input that has arrived but hasn't been turned into tokens yet
+#[derive(Default)]
+pub(crate) struct Pending(Vec<u8>);
+
+impl Pending {
+    pub(crate) fn extend(&mut self, chunk: &[u8]) {
+        self.0.extend_from_slice(chunk);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// An error pointing into the input
+    pub(crate) fn error(&self, e: Spanned<DeserErrorKind>) -> DeserError<'static> {
+        DeserError::new(e.node, &self.0, e.span).into_owned()
+    }
+
+    /// Turns as much of the input into tokens as possible, handing them to `f` until it breaks.
+    /// Unless it's the `last` of the input, a token cut off by its end, or a number running up
+    /// to it, is left for more input to complete.
+    pub(crate) fn tokenize(
+        &mut self,
+        last: bool,
+        mut f: impl FnMut(Spanned<Token<'_>>, &[u8]) -> Result<ControlFlow<()>, Spanned<DeserErrorKind>>,
+    ) -> Result<(), DeserError<'static>> {
+        let input = &self.0[..];
+        let err = |e: Spanned<DeserErrorKind>| DeserError::new(e.node, input, e.span).into_owned();
+
+        let mut tokenizer = Tokenizer::new(input);
+        let mut consumed = 0;
+        loop {
+            let token = match tokenizer.next_token() {
+                Ok(token) => token,
+                Err(e)
+                    if !last
+                        && (matches!(e.kind, TokenErrorKind::UnexpectedEof(_))
+                            || e.span.end() >= input.len()) =>
+                {
+                    break;
+                }
+                Err(e) => return Err(err(convert_token_error(e))),
+            };
+            if matches!(token.node, Token::Eof) {
+                consumed = input.len();
+                break;
+            }
+            if !last && token.span.end() >= input.len() && is_number(&token.node) {
+                break;
+            }
+            consumed = token.span.end();
+
+            if f(token, input).map_err(err)?.is_break() {
+                break;
+            }
+        }
+
+        self.0.drain(..consumed);
+        Ok(())
+    }
+}
+
+/// An error for a token that isn't what was `wanted`
+pub(crate) fn unexpected(
+    token: &Spanned<Token<'_>>,
+    input: &[u8],
+    wanted: &'static str,
+) -> Spanned<DeserErrorKind> {
+    DeserErrorKind::UnexpectedChar {
+        got: input[token.span.start()] as char,
+        wanted,
+    }
+    .with_span(token.span)
+}
+
+fn is_number(token: &Token<'_>) -> bool {
+    matches!(
+        token,
+        Token::U64(_) | Token::I64(_) | Token::F64(_) | Token::U128(_) | Token::I128(_)
+    )
+}
@@ -0,0 +1,810 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write as _};
+
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, StructKind, Type, UserType};
+use facet_deserialize::{
+    DeserError, DeserErrorKind, Expectation, Format, NextData, NextResult, Outcome, Scalar, Span,
+    Spannable, Spanned,
+};
+use facet_reflect::{HeapValue, Peek, ReflectError, Wip};
+use facet_serialize::{Serializer, Unsupported, serialize_iterative};
+
+#[cfg(feature = "std")]
+use crate::SerializeError;
+use crate::deserialize::convert_token_error;
+use crate::deserialize::tokenizer::{Token, Tokenizer};
+
+/// A dynamically-typed JSON value.
+///
+/// Numbers keep the same distinction the tokenizer makes: unsigned integers, signed
+/// integers, and floating-point numbers.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Value {
+    /// The JSON `null` value
+    #[default]
+    Null,
+    /// A JSON boolean
+    Bool(bool),
+    /// A non-negative JSON integer
+    U64(u64),
+    /// A negative JSON integer
+    I64(i64),
+    /// A JSON number with a fractional part or an exponent
+    F64(f64),
+    /// A JSON string
+    String(String),
+    /// A JSON array
+    Array(Vec<Value>),
+    /// A JSON object
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Returns true if this is the `null` value
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the entries of this value, if it is an object
+    pub fn as_object(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of this value, if it is an array
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the string contents of this value, if it is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Looks up a key, if this value is an object
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object().and_then(|map| map.get(key))
+    }
+
+    /// Applies a JSON Merge Patch ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)) to this value.
+    ///
+    /// Objects are merged recursively, a `null` in the patch removes the corresponding key,
+    /// and anything else (arrays included) replaces the target wholesale.
+    pub fn merge_patch(&mut self, patch: &Value) {
+        let Value::Object(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+
+        if !matches!(self, Value::Object(_)) {
+            *self = Value::Object(BTreeMap::new());
+        }
+        let Value::Object(target) = self else {
+            unreachable!()
+        };
+
+        for (key, patch_value) in patch {
+            if patch_value.is_null() {
+                target.remove(key);
+            } else {
+                target
+                    .entry(key.clone())
+                    .or_insert(Value::Null)
+                    .merge_patch(patch_value);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", if *b { "true" } else { "false" }),
+            Value::U64(n) => write!(f, "{}", n),
+            Value::I64(n) => write!(f, "{}", n),
+            Value::F64(n) => write!(f, "{}", n),
+            Value::String(s) => write_escaped_str(f, s),
+            Value::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(map) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_escaped_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0C}' => f.write_str("\\f")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Parses a JSON document into a [`Value`]
+pub fn value_from_str(input: &str) -> Result<Value, DeserError<'_>> {
+    let input = input.as_bytes();
+    let mut tokenizer = Tokenizer::new(input);
+    let err = |e: Spanned<DeserErrorKind>| DeserError::new(e.node, input, e.span);
+
+    let mut builder = ValueBuilder::default();
+    let value = loop {
+        let token = tokenizer
+            .next_token()
+            .map_err(|e| err(convert_token_error(e)))?;
+        if let Some(value) = builder.push(token, input).map_err(err)? {
+            break value;
+        }
+    };
+
+    let token = tokenizer
+        .next_token()
+        .map_err(|e| err(convert_token_error(e)))?;
+    if !matches!(token.node, Token::Eof) {
+        return Err(err(DeserErrorKind::UnexpectedChar {
+            got: input[token.span.start()] as char,
+            wanted: "the end of the input",
+        }
+        .with_span(token.span)));
+    }
+    Ok(value)
+}
+
+/// Builds a [`Value`] out of JSON tokens, fed one at a time
+#[derive(Default)]
+pub(crate) struct ValueBuilder {
+    // Containers being built, innermost last
+    stack: Vec<BuilderFrame>,
+}
+
+enum BuilderFrame {
+    Array {
+        items: Vec<Value>,
+        expect: Expect,
+    },
+    Object {
+        map: BTreeMap<String, Value>,
+        // The key awaiting a value, once it was read
+        key: Option<String>,
+        expect: Expect,
+    },
+}
+
+/// What may come next in an array or object
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    /// Right after the opening bracket: an item (or key), or the end
+    First,
+    /// After a comma, an item (or key); in an object, after a colon, the value
+    Next,
+    /// After a key, the colon
+    Colon,
+    /// After an item (or entry), a comma or the end
+    CommaOrEnd,
+}
+
+impl ValueBuilder {
+    /// Feeds the next token, whose span points into `input`, and returns the document once
+    /// it's complete
+    pub(crate) fn push(
+        &mut self,
+        token: Spanned<Token<'_>>,
+        input: &[u8],
+    ) -> Result<Option<Value>, Spanned<DeserErrorKind>> {
+        let span = token.span;
+        let unexpected = |wanted: &'static str| {
+            DeserErrorKind::UnexpectedChar {
+                got: input.get(span.start()).copied().unwrap_or(b'?') as char,
+                wanted,
+            }
+            .with_span(span)
+        };
+
+        let value = match token.node {
+            // Punctuation only moves the innermost container along
+            Token::Colon => match self.stack.last_mut() {
+                Some(BuilderFrame::Object {
+                    expect: expect @ Expect::Colon,
+                    ..
+                }) => {
+                    *expect = Expect::Next;
+                    return Ok(None);
+                }
+                _ => return Err(unexpected(self.wanted())),
+            },
+            Token::Comma => match self.stack.last_mut() {
+                Some(
+                    BuilderFrame::Array {
+                        expect: expect @ Expect::CommaOrEnd,
+                        ..
+                    }
+                    | BuilderFrame::Object {
+                        expect: expect @ Expect::CommaOrEnd,
+                        ..
+                    },
+                ) => {
+                    *expect = Expect::Next;
+                    return Ok(None);
+                }
+                _ => return Err(unexpected(self.wanted())),
+            },
+            Token::RBrace => match self.stack.last() {
+                Some(BuilderFrame::Object {
+                    key: None,
+                    expect: Expect::First | Expect::CommaOrEnd,
+                    ..
+                }) => match self.stack.pop() {
+                    Some(BuilderFrame::Object { map, .. }) => Value::Object(map),
+                    _ => unreachable!(),
+                },
+                _ => return Err(unexpected(self.wanted())),
+            },
+            Token::RBracket => match self.stack.last() {
+                Some(BuilderFrame::Array {
+                    expect: Expect::First | Expect::CommaOrEnd,
+                    ..
+                }) => match self.stack.pop() {
+                    Some(BuilderFrame::Array { items, .. }) => Value::Array(items),
+                    _ => unreachable!(),
+                },
+                _ => return Err(unexpected(self.wanted())),
+            },
+            Token::Eof => {
+                return Err(DeserErrorKind::UnexpectedEof {
+                    wanted: "any value (got EOF)",
+                }
+                .with_span(span));
+            }
+            Token::String(s) if self.wants_key() => {
+                if let Some(BuilderFrame::Object { key, expect, .. }) = self.stack.last_mut() {
+                    *key = Some(s.into_owned());
+                    *expect = Expect::Colon;
+                }
+                return Ok(None);
+            }
+            _ if !self.wants_value() => return Err(unexpected(self.wanted())),
+            Token::LBrace => {
+                self.stack.push(BuilderFrame::Object {
+                    map: BTreeMap::new(),
+                    key: None,
+                    expect: Expect::First,
+                });
+                return Ok(None);
+            }
+            Token::LBracket => {
+                self.stack.push(BuilderFrame::Array {
+                    items: Vec::new(),
+                    expect: Expect::First,
+                });
+                return Ok(None);
+            }
+            Token::String(s) => Value::String(s.into_owned()),
+            Token::F64(n) => Value::F64(n),
+            Token::I64(n) => Value::I64(n),
+            Token::U64(n) => Value::U64(n),
+            // Like `serialize_u128` and `serialize_i128` below, `Value` has no room for these
+            Token::I128(n) => Value::F64(n as f64),
+            Token::U128(n) => Value::F64(n as f64),
+            Token::True => Value::Bool(true),
+            Token::False => Value::Bool(false),
+            Token::Null => Value::Null,
+        };
+
+        match self.stack.last_mut() {
+            None => return Ok(Some(value)),
+            Some(BuilderFrame::Array { items, expect }) => {
+                items.push(value);
+                *expect = Expect::CommaOrEnd;
+            }
+            Some(BuilderFrame::Object { map, key, expect }) => {
+                if let Some(key) = key.take() {
+                    map.insert(key, value);
+                }
+                *expect = Expect::CommaOrEnd;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether an object key may come next
+    fn wants_key(&self) -> bool {
+        matches!(
+            self.stack.last(),
+            Some(BuilderFrame::Object {
+                key: None,
+                expect: Expect::First | Expect::Next,
+                ..
+            })
+        )
+    }
+
+    /// Whether a value may come next
+    fn wants_value(&self) -> bool {
+        match self.stack.last() {
+            None => true,
+            Some(BuilderFrame::Array { expect, .. }) => {
+                matches!(expect, Expect::First | Expect::Next)
+            }
+            Some(BuilderFrame::Object { key, expect, .. }) => {
+                key.is_some() && *expect == Expect::Next
+            }
+        }
+    }
+
+    /// What may come next, for errors
+    fn wanted(&self) -> &'static str {
+        match self.stack.last() {
+            None => "a value",
+            Some(BuilderFrame::Array { expect, .. }) => match expect {
+                Expect::First => "a value or ']'",
+                Expect::CommaOrEnd => "',' or ']'",
+                _ => "a value",
+            },
+            Some(BuilderFrame::Object { key, expect, .. }) => match (key, expect) {
+                (_, Expect::First) => "a string key or '}'",
+                (_, Expect::Colon) => "':'",
+                (_, Expect::CommaOrEnd) => "',' or '}'",
+                (None, _) => "a string key",
+                (Some(_), _) => "a value",
+            },
+        }
+    }
+}
+
+/// Converts a value into its JSON [`Value`] representation
+#[cfg(feature = "std")]
+pub fn to_value<'a, T: Facet<'a>>(value: &T) -> Result<Value, SerializeError> {
+    peek_to_value(&Peek::new(value))
+}
+
+/// Converts a Peek instance into its JSON [`Value`] representation
+#[cfg(feature = "std")]
+pub fn peek_to_value(peek: &Peek<'_, '_>) -> Result<Value, SerializeError> {
+    Ok(build_value(*peek)?)
+}
+
+fn build_value(peek: Peek<'_, '_>) -> Result<Value, Unsupported> {
+    let mut serializer = ValueSerializer::default();
+    serialize_iterative(peek, &mut serializer)?;
+    Ok(serializer.result.unwrap_or_default())
+}
+
+/// Deserializes a typed value out of a JSON [`Value`]
+///
+/// This goes through the same machinery as [`from_str`](crate::from_str), so defaults,
+/// flattening, renames and enum representations behave the same way.
+pub fn from_value<'facet, T: Facet<'facet>>(value: &Value) -> Result<T, DeserError<'static>> {
+    let mut tokens = Vec::new();
+    flatten_value(value, &mut tokens);
+    // Spans are token indices rather than byte offsets, so they'd point nowhere useful
+    facet_deserialize::deserialize(&[], ValueFormat { tokens })
+        .map_err(|e| e.with_span(Span::new(0, 0)))
+}
+
+/// Applies a JSON Merge Patch ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)) to a value.
+///
+/// The value is patched in place: the fields of structs are only written if the patch
+/// mentions them, so anything it leaves alone (fields marked `skip_serializing` included)
+/// keeps its value. A `null` removes a field, which falls back to its default (`None` for
+/// options) exactly like a missing field does when deserializing. Other parts of the value,
+/// maps say, are patched as a [`Value`] and rebuilt.
+///
+/// `target` is left untouched if the patch doesn't fit its type.
+pub fn merge_patch<'facet, T: Facet<'facet>>(
+    target: &'facet mut T,
+    patch: &Value,
+) -> Result<(), DeserError<'static>> {
+    // Everything that could fail happens before the target is touched
+    let edit = plan_patch(Peek::new(target), patch)?;
+    edit.apply(Wip::from_mut(target))
+        .and_then(|wip| wip.finish())
+        .map_err(reflect_error)
+}
+
+/// A change to make to part of a value, worked out by [`plan_patch`]
+enum Edit<'facet> {
+    /// Overwrite it with a new value
+    Replace(HeapValue<'facet>),
+    /// Reset the field to its default
+    Default(&'static Field),
+    /// Edit some of its fields, by index
+    Fields(Vec<(usize, Edit<'facet>)>),
+}
+
+impl<'facet> Edit<'facet> {
+    fn apply(self, wip: Wip<'facet>) -> Result<Wip<'facet>, ReflectError> {
+        match self {
+            Edit::Replace(value) => wip.put_heap_value(value),
+            Edit::Default(field) => match field.vtable.default_fn {
+                Some(default_fn) if field.flags.contains(FieldFlags::DEFAULT) => {
+                    wip.put_from_fn(default_fn)
+                }
+                _ => wip.put_default(),
+            },
+            Edit::Fields(fields) => {
+                let mut wip = wip;
+                for (index, edit) in fields {
+                    wip = edit.apply(wip.field(index)?)?.pop()?;
+                }
+                Ok(wip)
+            }
+        }
+    }
+}
+
+/// Works out how to apply `patch` to `target`, deserializing whatever it replaces
+fn plan_patch<'facet>(
+    target: Peek<'_, 'facet>,
+    patch: &Value,
+) -> Result<Edit<'facet>, DeserError<'static>> {
+    let shape = target.shape();
+    let Value::Object(entries) = patch else {
+        return value_to_shape(patch, shape).map(Edit::Replace);
+    };
+
+    // Only plain structs can be patched field by field, anything else is rebuilt
+    let struct_type = match shape.ty {
+        Type::User(UserType::Struct(st))
+            if st.kind == StructKind::Struct
+                && shape.inner.is_none()
+                && !st
+                    .fields
+                    .iter()
+                    .any(|field| field.flags.contains(FieldFlags::FLATTEN)) =>
+        {
+            st
+        }
+        _ => {
+            let mut doc = build_value(target).map_err(unsupported_error)?;
+            doc.merge_patch(patch);
+            return value_to_shape(&doc, shape).map(Edit::Replace);
+        }
+    };
+    let target = target.into_struct().map_err(reflect_error)?;
+
+    let mut fields = Vec::new();
+    for (key, patch_value) in entries {
+        // Like unknown fields when deserializing, keys that aren't fields are ignored
+        let Some(index) = struct_type.field_index(key) else {
+            continue;
+        };
+        let field = &struct_type.fields[index];
+        let edit = if patch_value.is_null() {
+            let has_default = field.flags.contains(FieldFlags::DEFAULT)
+                || matches!(field.shape().def, Def::Option(_));
+            if !has_default {
+                return Err(DeserError::new(
+                    DeserErrorKind::MissingField(field.name),
+                    &[],
+                    Span::new(0, 0),
+                ));
+            }
+            Edit::Default(field)
+        } else {
+            let value = target.field(index).expect("field indices are in range");
+            plan_patch(value, patch_value)?
+        };
+        fields.push((index, edit));
+    }
+    Ok(Edit::Fields(fields))
+}
+
+/// Deserializes a value of the given shape out of a [`Value`], see [`from_value`]
+fn value_to_shape<'facet>(
+    value: &Value,
+    shape: &'static Shape,
+) -> Result<HeapValue<'facet>, DeserError<'static>> {
+    let mut tokens = Vec::new();
+    flatten_value(value, &mut tokens);
+    let wip = Wip::alloc_shape(shape).map_err(reflect_error)?;
+    facet_deserialize::deserialize_wip(wip, &[], ValueFormat { tokens })
+        .map_err(|e| e.with_span(Span::new(0, 0)))
+}
+
+fn reflect_error(e: ReflectError) -> DeserError<'static> {
+    DeserError::new(DeserErrorKind::ReflectError(e), &[], Span::new(0, 0))
+}
+
+fn unsupported_error(unsupported: Unsupported) -> DeserError<'static> {
+    let kind = match unsupported {
+        Unsupported::Shape { shape, .. } | Unsupported::MissingCapability { shape, .. } => {
+            DeserErrorKind::UnsupportedType {
+                got: shape,
+                wanted: "a value that can be serialized",
+            }
+        }
+        Unsupported::ConversionFailed(e) => DeserErrorKind::ReflectError(e),
+        _ => DeserErrorKind::Unimplemented("serializing this value"),
+    };
+    DeserError::new(kind, &[], Span::new(0, 0))
+}
+
+/// Builds a [`Value`] out of the serializer events emitted by [`serialize_iterative`]
+#[derive(Default)]
+struct ValueSerializer {
+    stack: Vec<ValueFrame>,
+    result: Option<Value>,
+}
+
+enum ValueFrame {
+    Array(Vec<Value>),
+    Object {
+        map: BTreeMap<String, Value>,
+        key: Option<String>,
+    },
+}
+
+impl ValueSerializer {
+    fn push(&mut self, value: Value) {
+        match self.stack.last_mut() {
+            None => self.result = Some(value),
+            Some(ValueFrame::Array(items)) => items.push(value),
+            Some(ValueFrame::Object { map, key }) => match key.take() {
+                Some(key) => {
+                    map.insert(key, value);
+                }
+                // No pending key: this value *is* the key of a map entry
+                None => {
+                    *key = Some(match value {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    })
+                }
+            },
+        }
+    }
+
+    fn pop(&mut self) {
+        let value = match self.stack.pop() {
+            Some(ValueFrame::Array(items)) => Value::Array(items),
+            Some(ValueFrame::Object { map, .. }) => Value::Object(map),
+            None => return,
+        };
+        self.push(value);
+    }
+}
+
+impl Serializer for ValueSerializer {
+    type Error = Unsupported;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.push(Value::U64(value));
+        Ok(())
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.push(match u64::try_from(value) {
+            Ok(v) => Value::U64(v),
+            Err(_) => Value::F64(value as f64),
+        });
+        Ok(())
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.push(if value >= 0 {
+            Value::U64(value as u64)
+        } else {
+            Value::I64(value)
+        });
+        Ok(())
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.push(if let Ok(v) = u64::try_from(value) {
+            Value::U64(v)
+        } else if let Ok(v) = i64::try_from(value) {
+            Value::I64(v)
+        } else {
+            Value::F64(value as f64)
+        });
+        Ok(())
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.push(Value::F64(value));
+        Ok(())
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.push(Value::Bool(value));
+        Ok(())
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.push(Value::String(value.to_string()));
+        Ok(())
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.push(Value::String(value.to_string()));
+        Ok(())
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.push(Value::Array(
+            value.iter().map(|b| Value::U64(*b as u64)).collect(),
+        ));
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.push(Value::Null);
+        Ok(())
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.push(Value::Null);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.push(Value::String(variant_name.to_string()));
+        Ok(())
+    }
+
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(ValueFrame::Object {
+            map: BTreeMap::new(),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.pop();
+        Ok(())
+    }
+
+    fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        if let Some(ValueFrame::Object { key, .. }) = self.stack.last_mut() {
+            *key = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(ValueFrame::Array(Vec::new()));
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.pop();
+        Ok(())
+    }
+
+    fn start_map(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_object(len)
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.pop();
+        Ok(())
+    }
+}
+
+/// Lays a [`Value`] out as the sequence of outcomes a real parser would produce
+fn flatten_value(value: &Value, tokens: &mut Vec<Outcome<'static>>) {
+    let scalar = match value {
+        Value::Null => Scalar::Null,
+        Value::Bool(b) => Scalar::Bool(*b),
+        Value::U64(n) => Scalar::U64(*n),
+        Value::I64(n) => Scalar::I64(*n),
+        Value::F64(n) => Scalar::F64(*n),
+        Value::String(s) => Scalar::String(Cow::Owned(s.clone())),
+        Value::Array(items) => {
+            tokens.push(Outcome::ListStarted);
+            for item in items {
+                flatten_value(item, tokens);
+            }
+            tokens.push(Outcome::ListEnded);
+            return;
+        }
+        Value::Object(map) => {
+            tokens.push(Outcome::ObjectStarted);
+            for (key, value) in map {
+                tokens.push(Outcome::Scalar(Scalar::String(Cow::Owned(key.clone()))));
+                flatten_value(value, tokens);
+            }
+            tokens.push(Outcome::ObjectEnded);
+            return;
+        }
+    };
+    tokens.push(Outcome::Scalar(scalar));
+}
+
+/// A [`Format`] that replays a flattened [`Value`].
+///
+/// There is no input text: positions (and therefore spans) are indices into `tokens`.
+struct ValueFormat {
+    tokens: Vec<Outcome<'static>>,
+}
+
+impl Format for ValueFormat {
+    fn next<'input, 'facet>(
+        &mut self,
+        nd: NextData<'input, 'facet>,
+        _expectation: Expectation,
+    ) -> NextResult<'input, 'facet, Spanned<Outcome<'input>>, Spanned<DeserErrorKind>> {
+        let pos = nd.start();
+        let res = match self.tokens.get(pos) {
+            Some(outcome) => Ok(Spanned {
+                node: outcome.clone(),
+                span: Span::new(pos, 1),
+            }),
+            None => Err(DeserErrorKind::UnexpectedEof {
+                wanted: "any value (got end of value)",
+            }
+            .with_span(Span::new(pos, 0))),
+        };
+        (nd, res)
+    }
+
+    fn skip<'input, 'facet>(
+        &mut self,
+        nd: NextData<'input, 'facet>,
+    ) -> NextResult<'input, 'facet, Span, Spanned<DeserErrorKind>> {
+        let start = nd.start();
+        let mut depth = 0usize;
+        for (i, outcome) in self.tokens[start..].iter().enumerate() {
+            match outcome {
+                Outcome::ListStarted | Outcome::ObjectStarted => depth += 1,
+                Outcome::ListEnded | Outcome::ObjectEnded => depth -= 1,
+                Outcome::Scalar(_) => {}
+            }
+            if depth == 0 {
+                return (nd, Ok(Span::new(start, i + 1)));
+            }
+        }
+        (
+            nd,
+            Err(DeserErrorKind::UnexpectedEof {
+                wanted: "any value (got end of value)",
+            }
+            .with_span(Span::new(start, 0))),
+        )
+    }
+}
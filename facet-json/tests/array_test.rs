@@ -117,3 +117,58 @@ fn test_mixed_array_types() {
         }
     }
 }
+
+#[test]
+fn test_array_too_few_items() {
+    facet_testhelpers::setup();
+
+    let Err(err) = from_str::<MixedArrayTypes>(
+        r#"{"strings": ["hello"], "numbers": [1, 2, 3], "booleans": [true, false]}"#,
+    ) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            err.kind,
+            facet_deserialize::DeserErrorKind::ArrayLength {
+                expected: 2,
+                got: 1,
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_array_too_many_items() {
+    facet_testhelpers::setup();
+
+    let Err(err) = from_str::<[u8; 3]>("[1, 2, 3, 4, 5]") else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            err.kind,
+            facet_deserialize::DeserErrorKind::ArrayLength {
+                expected: 3,
+                got: 4,
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_nested_arrays_of_the_wrong_length() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        from_str::<[[u8; 2]; 2]>("[[1, 2], [3, 4]]").unwrap(),
+        [[1, 2], [3, 4]]
+    );
+    assert!(from_str::<[[u8; 2]; 2]>("[[1, 2], [3]]").is_err());
+    assert!(from_str::<[[u8; 2]; 2]>("[[1, 2], [3, 4], [5, 6]]").is_err());
+    assert_eq!(from_str::<[u8; 0]>("[]").unwrap(), []);
+}
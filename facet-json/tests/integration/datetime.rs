@@ -0,0 +1,136 @@
+use eyre::Result;
+use facet::Facet;
+use facet_json::{
+    DeserializeOptions, SerializeOptions, Timestamps, UnixTimestamps, from_str,
+    from_str_with_options, to_string, to_string_with_options,
+};
+
+#[derive(Facet, Debug, PartialEq)]
+struct ChronoEvent {
+    at: chrono::DateTime<chrono::Utc>,
+    local: chrono::DateTime<chrono::FixedOffset>,
+    naive: chrono::NaiveDateTime,
+    day: chrono::NaiveDate,
+}
+
+fn chrono_event() -> ChronoEvent {
+    let at = chrono::DateTime::from_timestamp(1_718_824_965, 500_000_000).unwrap();
+    ChronoEvent {
+        at,
+        local: at.with_timezone(&chrono::FixedOffset::west_opt(4 * 3600).unwrap()),
+        naive: at.naive_utc(),
+        day: at.date_naive(),
+    }
+}
+
+#[test]
+fn chrono_round_trips_as_rfc3339() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = to_string(&chrono_event());
+    assert_eq!(
+        json,
+        r#"{"at":"2024-06-19T19:22:45.500Z","local":"2024-06-19T15:22:45.500-04:00","naive":"2024-06-19T19:22:45.500","day":"2024-06-19"}"#
+    );
+    assert_eq!(from_str::<ChronoEvent>(&json).unwrap(), chrono_event());
+    Ok(())
+}
+
+#[test]
+fn time_round_trips_as_rfc3339() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct TimeEvent {
+        at: time::OffsetDateTime,
+        naive: time::PrimitiveDateTime,
+        day: time::Date,
+    }
+
+    let event = TimeEvent {
+        at: time::macros::datetime!(2024-06-19 15:22:45 -4),
+        naive: time::macros::datetime!(2024-06-19 19:22:45),
+        day: time::Date::from_calendar_date(2024, time::Month::June, 19)?,
+    };
+    let json = to_string(&event);
+    assert_eq!(
+        json,
+        r#"{"at":"2024-06-19T15:22:45-04:00","naive":"2024-06-19T19:22:45.000000000","day":"2024-06-19"}"#
+    );
+    assert_eq!(from_str::<TimeEvent>(&json).unwrap(), event);
+    Ok(())
+}
+
+#[test]
+fn jiff_round_trips_as_rfc3339() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct JiffEvent {
+        at: jiff::Timestamp,
+        naive: jiff::civil::DateTime,
+        day: jiff::civil::Date,
+    }
+
+    let event = JiffEvent {
+        at: jiff::Timestamp::from_second(1_718_824_965)?,
+        naive: jiff::civil::date(2024, 6, 19).at(19, 22, 45, 0),
+        day: jiff::civil::date(2024, 6, 19),
+    };
+    let json = to_string(&event);
+    assert_eq!(
+        json,
+        r#"{"at":"2024-06-19T19:22:45Z","naive":"2024-06-19T19:22:45","day":"2024-06-19"}"#
+    );
+    assert_eq!(from_str::<JiffEvent>(&json).unwrap(), event);
+    Ok(())
+}
+
+#[test]
+fn instants_as_unix_timestamps() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let seconds = SerializeOptions::new().timestamps(Timestamps::UnixSeconds);
+    let json = to_string_with_options(&chrono_event(), seconds).unwrap();
+    // Only instants become numbers
+    assert_eq!(
+        json,
+        r#"{"at":1718824965,"local":1718824965,"naive":1718824965,"day":"2024-06-19"}"#
+    );
+
+    let millis = SerializeOptions::new().timestamps(Timestamps::UnixMillis);
+    let json = to_string_with_options(&chrono_event(), millis).unwrap();
+    assert_eq!(
+        json,
+        r#"{"at":1718824965500,"local":1718824965500,"naive":1718824965500,"day":"2024-06-19"}"#
+    );
+    let options = DeserializeOptions::new().unix_timestamps(UnixTimestamps::Millis);
+    let event: ChronoEvent = from_str_with_options(&json, options).unwrap();
+    assert_eq!(event.at, chrono_event().at);
+    assert_eq!(event.naive, chrono_event().naive);
+    Ok(())
+}
+
+#[test]
+fn unix_timestamps_read_as_seconds_by_default() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let at: jiff::Timestamp = from_str("1718824965.25")?;
+    assert_eq!(at, jiff::Timestamp::from_millisecond(1_718_824_965_250)?);
+    let at: jiff::Timestamp = from_str("-1.5")?;
+    assert_eq!(at, jiff::Timestamp::from_millisecond(-1_500)?);
+
+    let at: time::OffsetDateTime = from_str("-1")?;
+    assert_eq!(at.unix_timestamp(), -1);
+    Ok(())
+}
+
+#[test]
+fn invalid_datetimes_are_errors() {
+    facet_testhelpers::setup();
+
+    assert!(from_str::<chrono::DateTime<chrono::Utc>>(r#""yesterday""#).is_err());
+    assert!(from_str::<jiff::Timestamp>("1e300").is_err());
+    // Dates aren't instants, so they can't be read from numbers
+    assert!(from_str::<chrono::NaiveDate>("1718824965").is_err());
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use eyre::Result;
+use facet::Facet;
+use facet_json::{DurationFormat, SerializeOptions, from_str, to_string, to_string_with_options};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Timeouts {
+    connect: Duration,
+    #[facet(duration_format = "seconds")]
+    read: Duration,
+    #[facet(duration_format = "human")]
+    idle: Duration,
+}
+
+fn timeouts() -> Timeouts {
+    Timeouts {
+        connect: Duration::from_millis(1500),
+        read: Duration::from_millis(2500),
+        idle: Duration::from_secs(5400),
+    }
+}
+
+#[test]
+fn durations_in_each_format() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = to_string(&timeouts());
+    assert_eq!(
+        json,
+        r#"{"connect":{"secs":1,"nanos":500000000},"read":2.5,"idle":"1h30m"}"#
+    );
+    assert_eq!(from_str::<Timeouts>(&json).unwrap(), timeouts());
+    Ok(())
+}
+
+#[test]
+fn serializer_option_leaves_field_attributes_alone() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().durations(DurationFormat::Human);
+    assert_eq!(
+        to_string_with_options(&timeouts(), options).unwrap(),
+        r#"{"connect":"1s500ms","read":2.5,"idle":"1h30m"}"#
+    );
+
+    let options = SerializeOptions::new().durations(DurationFormat::Seconds);
+    assert_eq!(
+        to_string_with_options(&vec![Duration::from_millis(250)], options).unwrap(),
+        "[0.25]"
+    );
+}
+
+#[test]
+fn durations_read_from_any_format() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let expected = Duration::from_millis(90_500);
+    for json in [
+        r#"{"secs":90,"nanos":500000000}"#,
+        "90.5",
+        r#""90.5""#,
+        r#""1m30.5s""#,
+        r#""1m 30s 500ms""#,
+    ] {
+        assert_eq!(from_str::<Duration>(json)?, expected, "{json}");
+    }
+
+    assert_eq!(from_str::<Duration>("90")?, Duration::from_secs(90));
+    assert_eq!(
+        from_str::<Duration>(r#"{"secs":90}"#)?,
+        Duration::from_secs(90)
+    );
+    assert_eq!(
+        from_str::<Duration>(r#""2d""#)?,
+        Duration::from_secs(172_800)
+    );
+    assert_eq!(from_str::<Duration>(r#""0s""#)?, Duration::ZERO);
+    Ok(())
+}
+
+#[test]
+fn invalid_durations_are_errors() {
+    facet_testhelpers::setup();
+
+    assert!(from_str::<Duration>("-1").is_err());
+    assert!(from_str::<Duration>(r#""1 fortnight""#).is_err());
+    assert!(from_str::<Duration>(r#""""#).is_err());
+    assert!(from_str::<Duration>(r#"{"secs":18446744073709551615,"nanos":1000000000}"#).is_err());
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use eyre::Result;
+use facet::Facet;
+use facet_json::{Value, merge_patch, value_from_str};
+
+#[test]
+fn test_merge_patch_rfc_7386_examples() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let cases = [
+        (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+        (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+        (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+        (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+        (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+        (
+            r#"{"a":{"b":"c"}}"#,
+            r#"{"a":{"b":"d","c":null}}"#,
+            r#"{"a":{"b":"d"}}"#,
+        ),
+        (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+        (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+        (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+        (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+        (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+        (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"a":1,"e":null}"#),
+        (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+        (
+            r#"{}"#,
+            r#"{"a":{"bb":{"ccc":null}}}"#,
+            r#"{"a":{"bb":{}}}"#,
+        ),
+    ];
+
+    for (target, patch, expected) in cases {
+        let mut target = value_from_str(target)?;
+        target.merge_patch(&value_from_str(patch)?);
+        assert_eq!(target, value_from_str(expected)?, "patch {patch}");
+        assert_eq!(target.to_string(), expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_patch_into_struct() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Author {
+        given_name: String,
+        family_name: Option<String>,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Document {
+        title: String,
+        author: Author,
+        tags: Vec<String>,
+        #[facet(default)]
+        views: u64,
+        meta: HashMap<String, String>,
+    }
+
+    let mut doc = Document {
+        title: "Goodbye!".to_string(),
+        author: Author {
+            given_name: "John".to_string(),
+            family_name: Some("Doe".to_string()),
+        },
+        tags: vec!["example".to_string(), "sample".to_string()],
+        views: 12,
+        meta: HashMap::from([
+            ("lang".to_string(), "en".to_string()),
+            ("draft".to_string(), "yes".to_string()),
+        ]),
+    };
+
+    let patch = value_from_str(
+        r#"{
+            "title": "Hello!",
+            "author": { "family_name": null },
+            "tags": ["example"],
+            "views": null,
+            "meta": { "draft": null, "reviewed": "no" }
+        }"#,
+    )?;
+    merge_patch(&mut doc, &patch)?;
+
+    assert_eq!(
+        doc,
+        Document {
+            title: "Hello!".to_string(),
+            author: Author {
+                given_name: "John".to_string(),
+                family_name: None,
+            },
+            tags: vec!["example".to_string()],
+            views: 0,
+            meta: HashMap::from([
+                ("lang".to_string(), "en".to_string()),
+                ("reviewed".to_string(), "no".to_string()),
+            ]),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_patch_type_mismatch_leaves_target_alone() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Counter {
+        count: u32,
+    }
+
+    let mut counter = Counter { count: 3 };
+    let patch = Value::Object([("count".to_string(), Value::Bool(true))].into());
+    assert!(merge_patch(&mut counter, &patch).is_err());
+    assert_eq!(counter, Counter { count: 3 });
+
+    Ok(())
+}
+
+#[test]
+fn test_value_from_str_rejects_malformed_input() {
+    facet_testhelpers::setup();
+
+    for input in [
+        r#"[1 2]"#,
+        r#"[1,,2]"#,
+        r#"[,1]"#,
+        r#"[1,]"#,
+        r#"[1:2]"#,
+        r#"{"a" 1}"#,
+        r#"{"a":1 "b":2}"#,
+        r#"{"a"::1}"#,
+        r#"{"a",1}"#,
+        r#"{1:2}"#,
+        r#"{"a":}"#,
+        r#"{"a"}"#,
+        r#"{,}"#,
+        r#"1 2"#,
+        r#"{} []"#,
+        r#","#,
+    ] {
+        assert!(value_from_str(input).is_err(), "{input} parsed");
+    }
+
+    assert_eq!(
+        value_from_str(r#" { "a" : [ 1 , { } ] , "b" : null } "#).unwrap(),
+        value_from_str(r#"{"a":[1,{}],"b":null}"#).unwrap()
+    );
+}
+
+#[test]
+fn test_merge_patch_keeps_unmentioned_fields() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Session {
+        user: String,
+        #[facet(skip_serializing, default)]
+        token: String,
+    }
+
+    let mut session = Session {
+        user: "alice".to_string(),
+        token: "s3cr3t".to_string(),
+    };
+    merge_patch(&mut session, &value_from_str(r#"{"user":"bob"}"#)?)?;
+    assert_eq!(
+        session,
+        Session {
+            user: "bob".to_string(),
+            token: "s3cr3t".to_string(),
+        }
+    );
+
+    Ok(())
+}
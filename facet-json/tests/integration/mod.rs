@@ -1,4 +1,10 @@
+mod datetime;
+mod duration;
 mod err;
+mod merge_patch;
+mod raw;
 mod read;
+mod roundtrip;
+mod scalars;
 mod transparent;
 mod write;
@@ -0,0 +1,84 @@
+use facet::Facet;
+use facet_json::{OwnedRawValue, RawValue};
+
+#[derive(Facet, Debug)]
+struct Envelope<'a> {
+    kind: String,
+    payload: RawValue<'a>,
+}
+
+#[derive(Facet, Debug)]
+struct OwnedEnvelope {
+    kind: String,
+    payload: OwnedRawValue,
+    after: u32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn raw_value_is_captured_unparsed() {
+    facet_testhelpers::setup();
+
+    let json = r#"{"kind":"point","payload":{"x": 1,  "y": [2]}}"#;
+    let envelope: Envelope = facet_json::from_str(json).unwrap();
+    assert_eq!(envelope.kind, "point");
+    assert_eq!(envelope.payload.get(), r#"{"x": 1,  "y": [2]}"#);
+}
+
+#[test]
+fn raw_value_is_written_verbatim() {
+    facet_testhelpers::setup();
+
+    let envelope = Envelope {
+        kind: "point".to_string(),
+        payload: RawValue::new(r#"{"x":1,"y":2}"#).unwrap(),
+    };
+    assert_eq!(
+        facet_json::to_string(&envelope),
+        r#"{"kind":"point","payload":{"x":1,"y":2}}"#
+    );
+}
+
+#[test]
+fn raw_value_can_be_parsed_later() {
+    facet_testhelpers::setup();
+
+    let json = r#"{"kind":"point","payload":{"x":1,"y":2}}"#;
+    let envelope: Envelope = facet_json::from_str(json).unwrap();
+    let point: Point = envelope.payload.deserialize().unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn owned_raw_value_roundtrip() {
+    facet_testhelpers::setup();
+
+    let json = r#"{"kind":"list","payload":[1,"two",null],"after":3}"#;
+    let envelope: OwnedEnvelope = facet_json::from_str(json).unwrap();
+    assert_eq!(envelope.payload.get(), r#"[1,"two",null]"#);
+    assert_eq!(envelope.after, 3);
+    assert_eq!(facet_json::to_string(&envelope), json);
+}
+
+#[test]
+fn raw_value_scalars() {
+    facet_testhelpers::setup();
+
+    let raw: RawValue = facet_json::from_str(r#" "hi" "#).unwrap();
+    assert_eq!(raw.get(), r#""hi""#);
+    let raw: RawValue = facet_json::from_str("-1.5e3").unwrap();
+    assert_eq!(raw.get(), "-1.5e3");
+}
+
+#[test]
+fn raw_value_new_checks_json() {
+    facet_testhelpers::setup();
+
+    assert!(RawValue::new("[1, 2").is_err());
+    assert!(OwnedRawValue::new("{}".to_string()).is_ok());
+}
@@ -1,15 +1,26 @@
+mod arena;
 mod bool;
+mod borrowed;
 mod deny_unknown_and_default;
 mod diagnostics;
 mod enums;
 mod eof_and_nulls;
 mod hashmap;
+mod into_wip;
+mod iter;
 mod nonzero;
 mod numbers;
 mod option;
+mod options;
 mod primitives;
 mod rename;
+mod result;
+mod skip;
 mod skip_unknown_fields;
+mod smart_pointers;
+mod stream;
 mod structs;
 mod tuple;
+mod validate;
 mod vec;
+mod with;
@@ -0,0 +1,65 @@
+use eyre::Result;
+use facet::Facet;
+use facet_reflect::Arena;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Log {
+    name: String,
+    events: Vec<Event>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(default)]
+struct Event {
+    id: u64,
+    tag: Option<String>,
+    weight: u32,
+}
+
+impl Default for Event {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            tag: None,
+            weight: 1,
+        }
+    }
+}
+
+#[test]
+fn test_from_str_in_arena() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let arena = Arena::new();
+    let json = r#"{"name": "app", "events": [{"id": 1, "tag": "boot"}, {"id": 2, "weight": 3}]}"#;
+    let log: Log = facet_json::from_str_in(json, &arena)?;
+    assert_eq!(
+        log,
+        Log {
+            name: "app".to_string(),
+            events: vec![
+                Event {
+                    id: 1,
+                    tag: Some("boot".to_string()),
+                    weight: 1,
+                },
+                Event {
+                    id: 2,
+                    tag: None,
+                    weight: 3,
+                },
+            ],
+        }
+    );
+    assert!(arena.capacity() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_slice_in_arena_reports_errors() {
+    facet_testhelpers::setup();
+
+    let arena = Arena::new();
+    assert!(facet_json::from_slice_in::<Log>(br#"{"name": 3}"#, &arena).is_err());
+}
@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+
+use eyre::Result;
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Borrowed<'a> {
+    name: &'a str,
+    nickname: Option<&'a str>,
+    bio: Cow<'a, str>,
+}
+
+#[test]
+fn test_borrow_strings_from_input() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = r#"{"name": "Ferris", "nickname": "crab", "bio": "likes rust"}"#;
+    let borrowed: Borrowed = facet_json::from_str(json)?;
+    assert_eq!(
+        borrowed,
+        Borrowed {
+            name: "Ferris",
+            nickname: Some("crab"),
+            bio: Cow::Borrowed("likes rust"),
+        }
+    );
+
+    // Nothing was copied out of the input
+    let range = json.as_bytes().as_ptr_range();
+    assert!(range.contains(&borrowed.name.as_ptr()));
+    assert!(matches!(borrowed.bio, Cow::Borrowed(bio) if range.contains(&bio.as_ptr())));
+
+    Ok(())
+}
+
+#[test]
+fn test_escaped_strings_are_owned() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = r#"{"name": "Ferris", "nickname": null, "bio": "likes \"rust\""}"#;
+    let borrowed: Borrowed = facet_json::from_str(json)?;
+    assert_eq!(borrowed.nickname, None);
+    assert!(matches!(borrowed.bio, Cow::Owned(ref bio) if bio == r#"likes "rust""#));
+
+    Ok(())
+}
+
+#[test]
+fn test_escaped_str_cannot_be_borrowed() {
+    facet_testhelpers::setup();
+
+    let json = r#"{"name": "Fer\nris", "nickname": null, "bio": ""}"#;
+    let err = facet_json::from_str::<Borrowed>(json).unwrap_err();
+    assert!(
+        matches!(err.kind, DeserErrorKind::UnsupportedType { .. }),
+        "{err}"
+    );
+}
@@ -24,3 +24,39 @@ fn test_with_rich_diagnostics() {
     #[cfg(not(miri))]
     insta::assert_snapshot!(display_str);
 }
+
+#[derive(facet::Facet, Debug)]
+struct Config {
+    servers: Vec<Server>,
+}
+
+#[derive(facet::Facet, Debug)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn test_error_location_and_path() {
+    facet_testhelpers::setup();
+
+    let json = "{\n  \"servers\": [\n    {\"host\": \"a\", \"port\": 1},\n    {\"host\": \"é\", \"port\": true}\n  ]\n}";
+    let err = from_str::<Config>(json).unwrap_err();
+
+    assert_eq!(err.path.as_deref(), Some("$.servers[1].port"));
+    let location = err.location();
+    assert_eq!(location.offset, json.find("true").unwrap());
+    assert_eq!((location.line, location.column), (4, 27));
+}
+
+#[test]
+fn test_error_path_of_top_level_value() {
+    facet_testhelpers::setup();
+
+    let err = from_str::<Config>(r#"{"servers": []"#).unwrap_err();
+    assert_eq!(err.path, None);
+
+    let err = from_str::<i32>("x").unwrap_err();
+    assert_eq!(err.path, None);
+    assert_eq!((err.location().line, err.location().column), (1, 1));
+}
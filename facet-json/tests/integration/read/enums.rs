@@ -137,3 +137,76 @@ fn enum_generic_c() {
         },
     }
 }
+
+#[test]
+fn json_read_enum_as_discriminant() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq, Eq, Hash)]
+    #[facet(serialize_as = "discriminant")]
+    #[repr(i16)]
+    enum Signal {
+        Low = -1,
+        Off = 0,
+        High = 300,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Reading {
+        signals: Vec<Signal>,
+        counts: std::collections::HashMap<Signal, u32>,
+    }
+
+    let reading: Reading = from_str(r#"{"signals":[300,-1,0],"counts":{"-1":4}}"#)?;
+    assert_eq!(reading.signals, [Signal::High, Signal::Low, Signal::Off]);
+    assert_eq!(reading.counts[&Signal::Low], 4);
+
+    let err = from_str::<Signal>("7").unwrap_err();
+    assert!(
+        matches!(err.kind, facet_deserialize::DeserErrorKind::NoSuchVariant { ref name, .. } if name == "7")
+    );
+
+    // Names are still accepted
+    assert_eq!(from_str::<Signal>(r#""High""#)?, Signal::High);
+
+    Ok(())
+}
+
+#[test]
+fn json_read_tagged_enum_is_unsupported() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(tag = "type")]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Message {
+        Ping,
+        Text { body: String },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(untagged)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Id {
+        Number(u64),
+        Name(String),
+    }
+
+    for err in [
+        from_str::<Message>(r#"{"type":"Text","body":"hi"}"#).unwrap_err(),
+        from_str::<Vec<Id>>("[1]").unwrap_err(),
+    ] {
+        assert!(
+            matches!(
+                err.kind,
+                facet_deserialize::DeserErrorKind::UnsupportedType {
+                    wanted: "an externally tagged enum",
+                    ..
+                }
+            ),
+            "{err}"
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use eyre::Result;
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
 use facet_json::from_str;
 
 #[test]
@@ -14,3 +16,85 @@ fn json_read_hashmap() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn json_read_hashmap_integer_keys() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = r#"{"1": "one", "20": "twenty"}"#;
+
+    let m: std::collections::HashMap<u32, String> = from_str(json)?;
+    assert_eq!(m.len(), 2);
+    assert_eq!(m.get(&1).unwrap(), "one");
+    assert_eq!(m.get(&20).unwrap(), "twenty");
+
+    let json = r#"{"-1": true}"#;
+    let m: std::collections::BTreeMap<i8, bool> = from_str(json)?;
+    assert_eq!(m.get(&-1), Some(&true));
+
+    let json = r#"{"one": "one"}"#;
+    assert!(from_str::<std::collections::HashMap<u32, String>>(json).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn json_read_hashmap_ip_addr_keys() -> Result<()> {
+    facet_testhelpers::setup();
+
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    let json = r#"{"127.0.0.1": 8080, "::1": 8443}"#;
+
+    let m: std::collections::HashMap<IpAddr, u16> = from_str(json)?;
+    assert_eq!(m.get(&IpAddr::V4(Ipv4Addr::LOCALHOST)), Some(&8080));
+    assert_eq!(m.get(&IpAddr::V6(Ipv6Addr::LOCALHOST)), Some(&8443));
+
+    Ok(())
+}
+
+#[test]
+fn json_read_hashmap_enum_keys() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq, Eq, Hash)]
+    #[repr(u8)]
+    enum Color {
+        Red,
+        #[facet(rename = "verde")]
+        Green,
+    }
+
+    let json = r#"{"Red": 1, "verde": 2}"#;
+
+    let m: std::collections::HashMap<Color, u8> = from_str(json)?;
+    assert_eq!(m.get(&Color::Red), Some(&1));
+    assert_eq!(m.get(&Color::Green), Some(&2));
+
+    let json = r#"{"Blue": 3}"#;
+    let err = from_str::<std::collections::HashMap<Color, u8>>(json).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        DeserErrorKind::NoSuchVariant { ref name, .. } if name == "Blue"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn json_read_hashmap_unparsable_keys() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq, Eq, Hash)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    let json = r#"{"1,2": true}"#;
+    let err = from_str::<std::collections::HashMap<Point, bool>>(json).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        DeserErrorKind::UnsupportedType { got, .. } if got == Point::SHAPE
+    ));
+}
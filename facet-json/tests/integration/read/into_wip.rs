@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use eyre::Result;
+use facet::Facet;
+use facet_reflect::Wip;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Document {
+    title: String,
+    tags: Vec<String>,
+    counts: HashMap<String, u32>,
+    parent: Option<u64>,
+    status: Status,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Draft,
+    Published { at: u64 },
+}
+
+#[test]
+fn test_from_str_into_wip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    // Only the shape is needed to drive the deserializer
+    let shape = Document::SHAPE;
+    let json = r#"{
+        "title": "hello",
+        "tags": ["a", "b"],
+        "counts": {"views": 3},
+        "parent": null,
+        "status": {"Published": {"at": 1700000000}}
+    }"#;
+    let heap_value = facet_json::from_str_into_wip(Wip::alloc_shape(shape)?, json)?;
+    assert_eq!(heap_value.peek().shape(), shape);
+
+    let document: Document = heap_value.materialize()?;
+    assert_eq!(
+        document,
+        Document {
+            title: "hello".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+            counts: [("views".to_string(), 3)].into_iter().collect(),
+            parent: None,
+            status: Status::Published { at: 1700000000 },
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_slice_into_wip_reports_errors() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let wip = Wip::alloc_shape(<Vec<u8>>::SHAPE)?;
+    assert!(facet_json::from_slice_into_wip(wip, b"[1, 2, 300]").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Read};
+
+use eyre::Result;
+use facet::Facet;
+use facet_json::{ReadError, from_ndjson_reader, iter_array};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Event {
+    id: u64,
+    name: String,
+}
+
+fn event(id: u64, name: &str) -> Event {
+    Event {
+        id,
+        name: name.to_string(),
+    }
+}
+
+/// Hands out its input a byte at a time, so every token gets cut off
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_iter_array() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = r#" [ {"id": 1, "name": "start"}, {"id": 22, "name": "s\"top"} ] "#;
+    let events = iter_array::<_, Event>(json.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events, vec![event(1, "start"), event(22, "s\"top")]);
+
+    let events = iter_array::<_, Event>(Trickle(json.as_bytes())).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events, vec![event(1, "start"), event(22, "s\"top")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_array_of_numbers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = b"[10, 200,3000]";
+    let numbers = iter_array::<_, u32>(Trickle(json)).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, vec![10, 200, 3000]);
+
+    let numbers = iter_array::<_, u32>(&b"[]"[..]).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, Vec::<u32>::new());
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_array_of_wide_numbers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = b"[340282366920938463463374607431768211455, 18446744073709551616]";
+    let numbers = iter_array::<_, u128>(Trickle(json)).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, vec![u128::MAX, u64::MAX as u128 + 1]);
+
+    let numbers = from_ndjson_reader::<_, i128>(&b"-170141183460469231731687303715884105728\n"[..])
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, vec![i128::MIN]);
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_array_is_lazy() -> Result<()> {
+    facet_testhelpers::setup();
+
+    // Elements before a bad one come out fine
+    let json = br#"[{"id": 1, "name": "a"}, {"id": "two", "name": "b"}, {"id": 3, "name": "c"}]"#;
+    let mut iter = iter_array::<_, Event>(&json[..]);
+    assert_eq!(iter.next().transpose()?, Some(event(1, "a")));
+    let Some(Err(err)) = iter.next() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReadError::Deser(_)), "{err}");
+    // and the iterator ends after an error
+    assert!(iter.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_array_errors() {
+    facet_testhelpers::setup();
+
+    let Some(Err(err)) = iter_array::<_, u32>(&b"{\"a\": 1}"[..]).next() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReadError::Deser(_)), "{err}");
+
+    let mut iter = iter_array::<_, u32>(&b"[1 2]"[..]);
+    assert_eq!(iter.next().transpose().unwrap(), Some(1));
+    assert!(matches!(iter.next(), Some(Err(ReadError::Deser(_)))));
+
+    let mut iter = iter_array::<_, u32>(&b"[1, 2"[..]);
+    assert_eq!(iter.next().transpose().unwrap(), Some(1));
+    assert_eq!(iter.next().transpose().unwrap(), Some(2));
+    assert!(matches!(iter.next(), Some(Err(ReadError::Deser(_)))));
+}
+
+#[test]
+fn test_iter_array_io_error() {
+    facet_testhelpers::setup();
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("connection reset"))
+        }
+    }
+
+    let Some(Err(err)) = iter_array::<_, u32>(Broken).next() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReadError::Io(_)), "{err}");
+}
+
+#[test]
+fn test_from_ndjson_reader() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let ndjson = "{\"id\": 1, \"name\": \"a\"}\n\n{\"id\": 2, \"name\": \"b\"}\r\n{\"id\": 3, \"name\": \"c\"}";
+    let expected = vec![event(1, "a"), event(2, "b"), event(3, "c")];
+
+    let events =
+        from_ndjson_reader::<_, Event>(ndjson.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events, expected);
+
+    let events = from_ndjson_reader::<_, Event>(Trickle(ndjson.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events, expected);
+
+    let numbers = from_ndjson_reader::<_, i64>(&b"1\n-2\n"[..]).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, vec![1, -2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_ndjson_reader_truncated() {
+    facet_testhelpers::setup();
+
+    let mut iter = from_ndjson_reader::<_, Event>(&b"{\"id\": 1, \"name\": \"a\"}\n{\"id\": 2"[..]);
+    assert_eq!(iter.next().transpose().unwrap(), Some(event(1, "a")));
+    let Some(Err(err)) = iter.next() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReadError::Deser(_)), "{err}");
+}
@@ -33,3 +33,32 @@ fn json_read_nonzero_zero() -> Result<()> {
     assert_snapshot!(result.unwrap_err().to_string());
     Ok(())
 }
+
+#[test]
+fn json_read_nonzero_128_bit() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Foo {
+        unsigned: NonZero<u128>,
+        signed: NonZero<i128>,
+        small: NonZero<i8>,
+    }
+
+    let foo = Foo {
+        unsigned: NonZero::<u128>::MAX,
+        signed: NonZero::<i128>::MIN,
+        small: NonZero::<i8>::MIN,
+    };
+    let json = facet_json::to_string(&foo);
+    assert_eq!(
+        json,
+        r#"{"unsigned":340282366920938463463374607431768211455,"signed":-170141183460469231731687303715884105728,"small":-128}"#
+    );
+    assert_eq!(from_str::<Foo>(&json).unwrap(), foo);
+
+    let zero = r#"{"unsigned":0,"signed":1,"small":1}"#;
+    assert!(from_str::<Foo>(zero).is_err());
+
+    Ok(())
+}
@@ -48,3 +48,40 @@ fn json_read_more_types() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn json_read_128_bit_integers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Wide {
+        small: u128,
+        max: u128,
+        min: i128,
+        negative: i128,
+    }
+
+    let wide = Wide {
+        small: 7,
+        max: u128::MAX,
+        min: i128::MIN,
+        negative: -1,
+    };
+    let json = facet_json::to_string(&wide);
+    assert_eq!(
+        json,
+        r#"{"small":7,"max":340282366920938463463374607431768211455,"min":-170141183460469231731687303715884105728,"negative":-1}"#
+    );
+    assert_eq!(from_str::<Wide>(&json).unwrap(), wide);
+
+    Ok(())
+}
+
+#[test]
+fn json_read_too_big_for_narrow_integers() {
+    facet_testhelpers::setup();
+
+    assert!(from_str::<u64>("18446744073709551616").is_err());
+    assert!(from_str::<i64>("-9223372036854775809").is_err());
+    assert!(from_str::<u128>("340282366920938463463374607431768211456").is_err());
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use eyre::Result;
+use facet::Facet;
+use facet_json::{
+    DeserializeOptions, FieldNames, UnknownFields, Value, from_str, from_str_with_options,
+};
+
+#[test]
+fn case_insensitive_field_names() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct User {
+        username: String,
+        #[facet(rename = "isAdmin")]
+        is_admin: bool,
+    }
+
+    let json = r#"{"UserName": "alice", "ISADMIN": true}"#;
+    let options = DeserializeOptions::new().field_names(FieldNames::CaseInsensitive);
+    let user: User = from_str_with_options(json, options)?;
+    assert_eq!(
+        user,
+        User {
+            username: "alice".to_string(),
+            is_admin: true
+        }
+    );
+
+    // By default, the keys don't match any field
+    let Err(err) = from_str::<User>(json) else {
+        panic!("expected an error");
+    };
+    assert!(err.to_string().contains("username"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn case_insensitive_prefers_exact_match() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Pair {
+        #[facet(default)]
+        a: u32,
+        #[facet(rename = "A", default)]
+        upper: u32,
+    }
+
+    let options = DeserializeOptions::new().field_names(FieldNames::CaseInsensitive);
+    let pair: Pair = from_str_with_options(r#"{"A": 1, "a": 2}"#, options)?;
+    assert_eq!(pair, Pair { a: 2, upper: 1 });
+    Ok(())
+}
+
+#[test]
+fn collect_unknown_fields() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Config {
+        name: String,
+        #[facet(extra)]
+        rest: HashMap<String, Value>,
+    }
+
+    let json = r#"{"port": 8080, "name": "server", "tags": ["a", {"b": null}]}"#;
+    let options = DeserializeOptions::new().unknown_fields(UnknownFields::Collect);
+    let config: Config = from_str_with_options(json, options)?;
+    assert_eq!(config.name, "server");
+    assert_eq!(config.rest.len(), 2);
+    assert_eq!(config.rest["port"], Value::U64(8080));
+    assert_eq!(config.rest["tags"].to_string(), r#"["a",{"b":null}]"#);
+
+    // By default, they're ignored
+    let config: Config = from_str(json)?;
+    assert_eq!(config.name, "server");
+    assert!(config.rest.is_empty());
+    Ok(())
+}
+
+#[test]
+fn collect_unknown_fields_without_extra_field() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Config {
+        name: String,
+    }
+
+    let options = DeserializeOptions::new().unknown_fields(UnknownFields::Collect);
+    let config: Config = from_str_with_options(r#"{"port": 8080, "name": "server"}"#, options)?;
+    assert_eq!(config.name, "server");
+    Ok(())
+}
+
+#[test]
+fn collect_unknown_fields_case_insensitively() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Config {
+        name: String,
+        #[facet(extra)]
+        rest: HashMap<String, Value>,
+    }
+
+    let options = DeserializeOptions::new()
+        .field_names(FieldNames::CaseInsensitive)
+        .unknown_fields(UnknownFields::Collect);
+    let config: Config = from_str_with_options(r#"{"Name": "server", "Port": 8080}"#, options)?;
+    assert_eq!(config.name, "server");
+    assert_eq!(config.rest.keys().collect::<Vec<_>>(), ["Port"]);
+    Ok(())
+}
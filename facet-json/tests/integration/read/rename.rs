@@ -526,3 +526,56 @@ fn test_field_empty_string_rename() {
     let roundtrip: EmptyStringField = from_str(&json).unwrap();
     assert_eq!(test_struct, roundtrip);
 }
+
+/// Container-level rename_all rules, with a field-level rename taking precedence
+#[test]
+#[cfg(feature = "std")]
+fn test_container_rename_all_roundtrip() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(rename_all = "camelCase")]
+    struct Camel {
+        user_id: u32,
+        display_name: String,
+        #[facet(rename = "kind")]
+        account_type: String,
+    }
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(rename_all = "kebab-case")]
+    struct Kebab {
+        max_retries: u8,
+        retry_delay_ms: u64,
+    }
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct Screaming {
+        home_dir: String,
+    }
+
+    let camel = Camel {
+        user_id: 1,
+        display_name: "Ada".to_string(),
+        account_type: "admin".to_string(),
+    };
+    let json = to_string(&camel);
+    assert_eq!(json, r#"{"userId":1,"displayName":"Ada","kind":"admin"}"#);
+    assert_eq!(from_str::<Camel>(&json).unwrap(), camel);
+
+    let kebab = Kebab {
+        max_retries: 3,
+        retry_delay_ms: 250,
+    };
+    let json = to_string(&kebab);
+    assert_eq!(json, r#"{"max-retries":3,"retry-delay-ms":250}"#);
+    assert_eq!(from_str::<Kebab>(&json).unwrap(), kebab);
+
+    let screaming = Screaming {
+        home_dir: "/home/ada".to_string(),
+    };
+    let json = to_string(&screaming);
+    assert_eq!(json, r#"{"HOME_DIR":"/home/ada"}"#);
+    assert_eq!(from_str::<Screaming>(&json).unwrap(), screaming);
+}
@@ -0,0 +1,40 @@
+use eyre::Result;
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::{from_str, to_string};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Outcome {
+    first: core::result::Result<u32, String>,
+    second: core::result::Result<u32, String>,
+}
+
+#[test]
+fn test_result_roundtrip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let outcome = Outcome {
+        first: Ok(42),
+        second: Err("boom".to_string()),
+    };
+    let json = to_string(&outcome);
+    assert_eq!(json, r#"{"first":{"Ok":42},"second":{"Err":"boom"}}"#);
+
+    let back: Outcome = from_str(&json).unwrap();
+    assert_eq!(back, outcome);
+
+    Ok(())
+}
+
+#[test]
+fn test_result_unknown_variant() {
+    facet_testhelpers::setup();
+
+    let Err(err) = from_str::<core::result::Result<u32, String>>(r#"{"Maybe":1}"#) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(err.kind, DeserErrorKind::NoSuchVariant { .. }),
+        "{err}"
+    );
+}
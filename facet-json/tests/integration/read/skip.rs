@@ -0,0 +1,79 @@
+use eyre::Result;
+use facet::Facet;
+use facet_json::from_str;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Session {
+    user: String,
+    #[facet(skip)]
+    cache: Vec<u32>,
+    #[facet(skip, default = 3)]
+    retries: u8,
+}
+
+#[test]
+fn test_skipped_fields_are_filled_from_default() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let session: Session = from_str(r#"{"user": "amos"}"#)?;
+    assert_eq!(
+        session,
+        Session {
+            user: "amos".to_string(),
+            cache: vec![],
+            retries: 3,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_skipped_fields_ignore_input() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let session: Session = from_str(r#"{"cache": [1, 2], "user": "amos", "retries": 9}"#)?;
+    assert_eq!(session.cache, Vec::<u32>::new());
+    assert_eq!(session.retries, 3);
+    Ok(())
+}
+
+#[test]
+fn test_skipped_fields_are_unknown() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        user: String,
+        #[facet(skip)]
+        cache: Vec<u32>,
+    }
+
+    let result: Result<Strict, _> = from_str(r#"{"user": "amos", "cache": []}"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_skip_in_enum_variant() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Event {
+        Login {
+            user: String,
+            #[facet(skip)]
+            attempts: u32,
+        },
+    }
+
+    let event: Event = from_str(r#"{"Login": {"user": "amos", "attempts": 5}}"#)?;
+    assert_eq!(
+        event,
+        Event::Login {
+            user: "amos".to_string(),
+            attempts: 0,
+        }
+    );
+    Ok(())
+}
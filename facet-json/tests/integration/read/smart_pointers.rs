@@ -0,0 +1,50 @@
+use eyre::Result;
+use facet::Facet;
+use facet_json::from_str;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    #[facet(default)]
+    y: i32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Scene {
+    origin: Box<Point>,
+    points: Vec<Arc<Point>>,
+    label: Option<Rc<String>>,
+}
+
+#[test]
+fn test_read_smart_pointers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let scene: Scene =
+        from_str(r#"{"origin":{"x":1,"y":2},"points":[{"x":3},{"x":4,"y":5}],"label":"corner"}"#)?;
+    assert_eq!(
+        scene,
+        Scene {
+            origin: Box::new(Point { x: 1, y: 2 }),
+            points: vec![
+                Arc::new(Point { x: 3, y: 0 }),
+                Arc::new(Point { x: 4, y: 5 })
+            ],
+            label: Some(Rc::new("corner".to_string())),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_top_level_smart_pointer() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let point: Arc<Point> = from_str(r#"{"x":7,"y":8}"#)?;
+    assert_eq!(*point, Point { x: 7, y: 8 });
+
+    Ok(())
+}
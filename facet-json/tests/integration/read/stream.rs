@@ -0,0 +1,151 @@
+use eyre::Result;
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::{JsonStream, Value};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Message {
+    id: u64,
+    text: String,
+    tags: Vec<String>,
+    score: f64,
+}
+
+const JSON: &str = r#"{"id": 12345, "text": "café \"bar\"", "tags": ["a", "b"], "score": -1.5e3}"#;
+
+fn expected() -> Message {
+    Message {
+        id: 12345,
+        text: "café \"bar\"".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        score: -1500.0,
+    }
+}
+
+#[test]
+fn test_stream_in_one_chunk() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(JSON.as_bytes())?;
+    assert!(stream.is_complete());
+    assert_eq!(stream.finish::<Message>()?, expected());
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_in_every_chunk_size() -> Result<()> {
+    facet_testhelpers::setup();
+
+    // Every token gets cut off somewhere, numbers and escapes included
+    for size in 1..JSON.len() {
+        let mut stream = JsonStream::new();
+        for chunk in JSON.as_bytes().chunks(size) {
+            stream.feed(chunk)?;
+        }
+        assert_eq!(stream.finish::<Message>()?, expected(), "chunk size {size}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_multibyte_chars_split() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = "[\"héllo wörld\", \"日本\"]".as_bytes();
+    for at in 0..json.len() {
+        let mut stream = JsonStream::new();
+        stream.feed(&json[..at])?;
+        stream.feed(&json[at..])?;
+        assert_eq!(
+            stream.finish_value()?,
+            Value::Array(vec![
+                Value::String("héllo wörld".to_string()),
+                Value::String("日本".to_string()),
+            ])
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_top_level_number() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(b"12")?;
+    stream.feed(b"34")?;
+    // more digits could still arrive
+    assert!(!stream.is_complete());
+    assert_eq!(stream.finish::<u32>()?, 1234);
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_wide_number() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(b"[3402823669209384634")?;
+    stream.feed(b"63374607431768211455]")?;
+    assert_eq!(stream.finish::<Vec<u128>>()?, vec![u128::MAX]);
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_truncated() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(br#"{"id": 1, "text": "hel"#)?;
+    assert!(!stream.is_complete());
+    let Err(err) = stream.finish::<Message>() else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err.kind, DeserErrorKind::UnexpectedEof { .. }),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_invalid_input() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(b"[tru")?;
+    let Err(err) = stream.feed(b"x]") else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err.kind, DeserErrorKind::UnexpectedChar { .. }),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_trailing_data() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut stream = JsonStream::new();
+    stream.feed(b"{} ")?;
+    assert!(stream.is_complete());
+    let Err(err) = stream.feed(b"{}") else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err.kind, DeserErrorKind::UnexpectedChar { .. }),
+        "{err}"
+    );
+
+    Ok(())
+}
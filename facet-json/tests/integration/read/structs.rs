@@ -130,3 +130,47 @@ fn test_reading_flat_structs() {
     };
     assert_eq!(expected3, actual3);
 }
+
+#[test]
+fn test_reading_nested_flat_structs() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Request {
+        path: String,
+        #[facet(flatten)]
+        meta: Meta,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Meta {
+        id: u32,
+        #[facet(flatten)]
+        auth: Auth,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Auth {
+        user: String,
+        token: String,
+    }
+
+    // Keys of the different levels, in any order
+    let json = r#"{"user":"amos","path":"/","token":"t0k","id":7}"#;
+    let request: Request = from_str(json)?;
+    assert_eq!(
+        request,
+        Request {
+            path: "/".to_string(),
+            meta: Meta {
+                id: 7,
+                auth: Auth {
+                    user: "amos".to_string(),
+                    token: "t0k".to_string(),
+                },
+            },
+        }
+    );
+
+    Ok(())
+}
@@ -188,3 +188,51 @@ fn test_deserialize_tuple_empty_nested_2x_flexible() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_deserialize_unit() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let () = from_str("null")?;
+    let ok: ((), i32) = from_str("[null,1]")?;
+    assert_eq!(ok, ((), 1));
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_long_tuple() -> Result<()> {
+    facet_testhelpers::setup();
+
+    type Sixteen = (
+        u8,
+        u16,
+        u32,
+        u64,
+        i8,
+        i16,
+        i32,
+        i64,
+        f32,
+        f64,
+        bool,
+        char,
+        (),
+        String,
+        Option<u32>,
+        Vec<u32>,
+    );
+    let json = r#"[1,2,3,4,5,6,7,8,9.5,10.5,true,"c",null,"s",null,[16]]"#;
+    let sixteen: Sixteen = from_str(json)?;
+
+    // The standard library's traits stop at 12-tuples, so the fields are checked one by one
+    let (a, b, c, d, e, f, g, h, i, j, k, l, (), n, o, p) = sixteen;
+    assert_eq!((a, b, c, d, e, f, g, h), (1, 2, 3, 4, 5, 6, 7, 8));
+    assert_eq!((i, j, k, l), (9.5, 10.5, true, 'c'));
+    assert_eq!((n.as_str(), o, &p), ("s", None, &vec![16]));
+
+    let back = facet_json::to_string(&(a, b, c, d, e, f, g, h, i, j, k, l, (), n, o, p));
+    assert_eq!(back, json);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::from_str;
+use facet_reflect::ReflectError;
+
+fn valid_port(port: &u16) -> Result<(), &'static str> {
+    if *port == 0 {
+        Err("0 is not a valid port")
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    #[facet(validate = valid_port)]
+    port: u16,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    servers: Vec<Server>,
+}
+
+#[test]
+fn validation_errors_fail_deserialization() {
+    facet_testhelpers::setup();
+
+    let config: Config = from_str(r#"{"servers": [{"port": 80}]}"#).unwrap();
+    assert_eq!(config.servers, [Server { port: 80 }]);
+
+    let Err(err) = from_str::<Config>(r#"{"servers": [{"port": 80}, {"port": 0}]}"#) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err.kind,
+            DeserErrorKind::ReflectError(ReflectError::ValidationFailed {
+                field_name: Some("port"),
+                ..
+            })
+        ),
+        "{err}"
+    );
+    assert!(err.to_string().contains("0 is not a valid port"), "{err}");
+}
+
+#[test]
+fn invariant_violations_fail_deserialization() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(invariants = Range::ordered)]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+
+    impl Range {
+        fn ordered(&self) -> Result<(), &'static str> {
+            if self.start <= self.end {
+                Ok(())
+            } else {
+                Err("start is after end")
+            }
+        }
+    }
+
+    let range: Range = from_str(r#"{"start": 1, "end": 2}"#).unwrap();
+    assert_eq!(range, Range { start: 1, end: 2 });
+
+    let Err(err) = from_str::<Range>(r#"{"start": 2, "end": 1}"#) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err.kind,
+            DeserErrorKind::ReflectError(ReflectError::InvariantViolated { message, .. })
+                if message == "start is after end"
+        ),
+        "{err}"
+    );
+}
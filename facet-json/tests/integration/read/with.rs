@@ -0,0 +1,135 @@
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::from_str;
+use facet_reflect::ReflectError;
+
+mod seconds {
+    pub fn serialize(secs: &u64) -> Result<String, core::convert::Infallible> {
+        Ok(format!("{secs}s"))
+    }
+
+    pub fn deserialize(s: String) -> Result<u64, String> {
+        s.strip_suffix('s')
+            .and_then(|secs| secs.parse().ok())
+            .ok_or_else(|| format!("{s:?} is not a number of seconds"))
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Timeouts {
+    #[facet(with = seconds)]
+    connect: u64,
+    #[facet(deserialize_with = "seconds::deserialize")]
+    read: u64,
+    retries: u32,
+}
+
+#[test]
+fn deserialize_with_proxy() {
+    facet_testhelpers::setup();
+
+    let timeouts: Timeouts = from_str(r#"{"connect": "5s", "read": "30s", "retries": 3}"#).unwrap();
+    assert_eq!(
+        timeouts,
+        Timeouts {
+            connect: 5,
+            read: 30,
+            retries: 3
+        }
+    );
+}
+
+#[test]
+fn deserialize_with_conversion_error() {
+    facet_testhelpers::setup();
+
+    let Err(err) = from_str::<Timeouts>(r#"{"connect": "soon", "read": "30s", "retries": 3}"#)
+    else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err.kind,
+            DeserErrorKind::ReflectError(ReflectError::ConversionFailed { message, .. })
+                if message == r#""soon" is not a number of seconds"#
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn deserialize_with_in_enum_variant() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Retry {
+        After {
+            #[facet(with = seconds)]
+            delay: u64,
+        },
+    }
+
+    let retry: Retry = from_str(r#"{"After": {"delay": "10s"}}"#).unwrap();
+    assert_eq!(retry, Retry::After { delay: 10 });
+}
+
+#[test]
+fn deserialize_from_container_proxy() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(from = u64)]
+    struct Seconds {
+        secs: u64,
+    }
+
+    impl From<u64> for Seconds {
+        fn from(secs: u64) -> Self {
+            Seconds { secs }
+        }
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(try_from = "String")]
+    struct Email(String);
+
+    impl TryFrom<String> for Email {
+        type Error = String;
+
+        fn try_from(s: String) -> Result<Self, Self::Error> {
+            if s.contains('@') {
+                Ok(Email(s))
+            } else {
+                Err(format!("{s:?} is not an email address"))
+            }
+        }
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Account {
+        email: Email,
+        timeouts: Vec<Seconds>,
+    }
+
+    let account: Account = from_str(r#"{"email": "me@example.com", "timeouts": [1, 2]}"#).unwrap();
+    assert_eq!(
+        account,
+        Account {
+            email: Email("me@example.com".to_string()),
+            timeouts: vec![Seconds { secs: 1 }, Seconds { secs: 2 }]
+        }
+    );
+
+    let Err(err) = from_str::<Account>(r#"{"email": "me", "timeouts": []}"#) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err.kind,
+            DeserErrorKind::ReflectError(ReflectError::ConversionFailed { message, .. })
+                if message == r#""me" is not an email address"#
+        ),
+        "{err}"
+    );
+}
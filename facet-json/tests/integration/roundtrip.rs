@@ -0,0 +1,72 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+// The shared round-trip suite, every group of it
+struct Json;
+
+impl Format for Json {
+    const NAME: &'static str = "JSON";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        Ok(facet_json::peek_to_string(&peek).into_bytes())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        facet_json::from_slice(input).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Json>();
+}
+
+#[test]
+fn wide_integers() {
+    facet_testhelpers::setup();
+    conformance::wide_integers::<Json>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Json>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Json>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Json>();
+}
+
+#[test]
+fn options() {
+    facet_testhelpers::setup();
+    conformance::options::<Json>();
+}
+
+#[test]
+fn collections() {
+    facet_testhelpers::setup();
+    conformance::collections::<Json>();
+}
+
+#[test]
+fn structs() {
+    facet_testhelpers::setup();
+    conformance::structs::<Json>();
+}
+
+#[test]
+fn enums() {
+    facet_testhelpers::setup();
+    conformance::enums::<Json>();
+}
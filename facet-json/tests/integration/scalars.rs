@@ -0,0 +1,72 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::NonZero;
+use std::path::PathBuf;
+
+use eyre::Result;
+use facet::Facet;
+use facet_json::{from_str, to_string};
+use uuid::Uuid;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Host {
+    id: Uuid,
+    addr: IpAddr,
+    gateway: Ipv4Addr,
+    listen: SocketAddr,
+    root: PathBuf,
+    workers: NonZero<u16>,
+    offset: NonZero<i64>,
+}
+
+fn host() -> Host {
+    Host {
+        id: Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8),
+        addr: IpAddr::V6(Ipv6Addr::LOCALHOST),
+        gateway: Ipv4Addr::new(10, 0, 0, 1),
+        listen: "[::1]:8443".parse().unwrap(),
+        root: PathBuf::from("/srv/www"),
+        workers: NonZero::new(8).unwrap(),
+        offset: NonZero::new(-3).unwrap(),
+    }
+}
+
+#[test]
+fn scalars_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let json = to_string(&host());
+    assert_eq!(
+        json,
+        r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","addr":"::1","gateway":"10.0.0.1","listen":"[::1]:8443","root":"/srv/www","workers":8,"offset":-3}"#
+    );
+    assert_eq!(from_str::<Host>(&json).unwrap(), host());
+    Ok(())
+}
+
+#[test]
+fn scalars_in_collections() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let addrs: Vec<SocketAddr> = from_str(r#"["127.0.0.1:80", "[::1]:443"]"#)?;
+    assert_eq!(
+        addrs,
+        vec![
+            "127.0.0.1:80".parse::<SocketAddr>()?,
+            "[::1]:443".parse::<SocketAddr>()?
+        ]
+    );
+
+    let paths: Option<PathBuf> = from_str(r#""relative/dir""#)?;
+    assert_eq!(paths, Some(PathBuf::from("relative/dir")));
+    Ok(())
+}
+
+#[test]
+fn invalid_scalars_are_errors() {
+    facet_testhelpers::setup();
+
+    assert!(from_str::<IpAddr>(r#""300.0.0.1""#).is_err());
+    assert!(from_str::<SocketAddr>(r#""localhost""#).is_err());
+    assert!(from_str::<Uuid>(r#""not-a-uuid""#).is_err());
+    assert!(from_str::<NonZero<u16>>("0").is_err());
+}
@@ -1,9 +1,20 @@
+mod bytes;
+mod canonical;
+mod cycles;
+mod display;
 mod enums;
+mod errors;
 mod json;
 mod map;
 mod nonzero;
+mod numbers;
+mod pretty;
 mod primitives;
+mod sensitive;
+mod set;
 mod skip_serializing;
+mod smart_pointers;
 mod string;
 mod structs;
 mod tuple;
+mod with;
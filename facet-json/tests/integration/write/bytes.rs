@@ -0,0 +1,76 @@
+use facet::Facet;
+use facet_json::{Bytes, SerializeOptions};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Blob {
+    data: Vec<u8>,
+    tag: [u8; 2],
+    other: Vec<u16>,
+}
+
+fn blob() -> Blob {
+    Blob {
+        data: b"hello".to_vec(),
+        tag: [0xff, 0x00],
+        other: vec![1, 2],
+    }
+}
+
+#[test]
+fn test_bytes_as_arrays_by_default() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        facet_json::to_string(&blob()),
+        r#"{"data":[104,101,108,108,111],"tag":[255,0],"other":[1,2]}"#
+    );
+}
+
+#[test]
+fn test_bytes_as_base64() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().bytes(Bytes::Base64);
+    let json = facet_json::to_string_with_options(&blob(), options).unwrap();
+    assert_eq!(json, r#"{"data":"aGVsbG8=","tag":"/wA=","other":[1,2]}"#);
+
+    let back: Blob = facet_json::from_str(&json).unwrap();
+    assert_eq!(back, blob());
+}
+
+#[test]
+fn test_bytes_base64_lengths() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().bytes(Bytes::Base64);
+    for (bytes, encoded) in [
+        (&b""[..], r#""""#),
+        (b"f", r#""Zg==""#),
+        (b"fo", r#""Zm8=""#),
+        (b"foo", r#""Zm9v""#),
+        (b"foob", r#""Zm9vYg==""#),
+    ] {
+        let bytes = bytes.to_vec();
+        assert_eq!(
+            facet_json::to_string_with_options(&bytes, options).unwrap(),
+            encoded
+        );
+        assert_eq!(facet_json::from_str::<Vec<u8>>(encoded).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn test_bytes_invalid_base64() {
+    facet_testhelpers::setup();
+
+    let Err(err) = facet_json::from_str::<Vec<u8>>(r#""not base64!""#) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            err.kind,
+            facet_deserialize::DeserErrorKind::InvalidBase64(_)
+        ),
+        "{err}"
+    );
+}
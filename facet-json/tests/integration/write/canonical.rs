@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use facet::Facet;
+use facet_json::{Floats, KeyOrder, SerializeOptions};
+
+#[derive(Facet)]
+struct Document {
+    title: String,
+    tags: HashSet<String>,
+    counts: HashMap<String, u32>,
+    author: Author,
+    score: f64,
+}
+
+#[derive(Facet)]
+struct Author {
+    name: String,
+    id: u32,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Figure {
+    Rect { width: u32, height: u32 },
+}
+
+fn document() -> Document {
+    Document {
+        title: "Notes".to_string(),
+        tags: ["zeta", "alpha", "mu"].map(String::from).into(),
+        counts: [("words", 120), ("lines", 12), ("chars", 700)]
+            .map(|(k, v)| (k.to_string(), v))
+            .into(),
+        author: Author {
+            name: "Ana".to_string(),
+            id: 7,
+        },
+        score: 0.5,
+    }
+}
+
+#[test]
+fn test_canonical_sorts_keys() {
+    facet_testhelpers::setup();
+
+    let json = facet_json::to_string_with_options(&document(), SerializeOptions::new().canonical())
+        .unwrap();
+    assert_eq!(
+        json,
+        r#"{"author":{"id":7,"name":"Ana"},"counts":{"chars":700,"lines":12,"words":120},"score":0.5,"tags":["alpha","mu","zeta"],"title":"Notes"}"#
+    );
+
+    // Hash maps and sets iterate in another order every time they're built
+    for _ in 0..8 {
+        let again =
+            facet_json::to_string_with_options(&document(), SerializeOptions::new().canonical())
+                .unwrap();
+        assert_eq!(again, json);
+    }
+}
+
+#[test]
+fn test_canonical_is_compact() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().pretty().canonical();
+    let json = facet_json::to_string_with_options(
+        &Figure::Rect {
+            width: 2,
+            height: 3,
+        },
+        options,
+    )
+    .unwrap();
+    assert_eq!(json, r#"{"Rect":{"height":3,"width":2}}"#);
+}
+
+#[test]
+fn test_sorted_keys_only() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().key_order(KeyOrder::Sorted);
+    let author = Author {
+        name: "Ana".to_string(),
+        id: 7,
+    };
+    assert_eq!(
+        facet_json::to_string_with_options(&author, options).unwrap(),
+        r#"{"id":7,"name":"Ana"}"#
+    );
+    assert_eq!(facet_json::to_string(&author), r#"{"name":"Ana","id":7}"#);
+}
+
+#[test]
+fn test_ecmascript_floats() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().floats(Floats::Ecmascript);
+    let write = |value: f64| facet_json::to_string_with_options(&value, options).unwrap();
+
+    assert_eq!(write(0.0), "0");
+    assert_eq!(write(-0.0), "0");
+    assert_eq!(write(1.0), "1");
+    assert_eq!(write(-1.5), "-1.5");
+    assert_eq!(write(1e16), "10000000000000000");
+    assert_eq!(write(1e20), "100000000000000000000");
+    assert_eq!(write(1e21), "1e+21");
+    assert_eq!(write(1.5e300), "1.5e+300");
+    assert_eq!(write(0.000001), "0.000001");
+    assert_eq!(write(1e-7), "1e-7");
+    assert_eq!(write(-2.5e-300), "-2.5e-300");
+
+    for value in [1e21, 1.5e300, 1e-7, 0.1, f64::MAX] {
+        let back: f64 = facet_json::from_str(&write(value)).unwrap();
+        assert_eq!(back.to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn test_canonical_rejects_non_finite_floats() {
+    facet_testhelpers::setup();
+
+    let mut output = Vec::new();
+    let result = facet_json::to_writer_with_options(
+        &f64::INFINITY,
+        &mut output,
+        SerializeOptions::new().canonical(),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_json::{Cycles, SerializeOptions};
+
+#[derive(Facet)]
+struct Link {
+    name: &'static str,
+    next: Option<&'static Link>,
+}
+
+static FIRST: Link = Link {
+    name: "first",
+    next: Some(&SECOND),
+};
+
+static SECOND: Link = Link {
+    name: "second",
+    next: Some(&FIRST),
+};
+
+#[test]
+fn test_cycle_placeholder() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().cycles(Cycles::Placeholder);
+    assert_eq!(
+        facet_json::to_string_with_options(&FIRST, options).unwrap(),
+        r#"{"name":"first","next":{"name":"second","next":"<cycle: Link>"}}"#
+    );
+}
+
+#[test]
+fn test_cycle_error() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().cycles(Cycles::Error);
+    let mut output = Vec::new();
+    let Err(err) = facet_json::to_writer_with_options(&FIRST, &mut output, options) else {
+        panic!("expected the cycle to be an error");
+    };
+    assert!(
+        matches!(&err, facet_json::SerializeError::Cycle { shape, path } if shape == "Link" && path == "$.next.next"),
+        "{err}"
+    );
+    assert_eq!(err.to_string(), "Link contains itself at $.next.next");
+}
+
+#[derive(Facet)]
+struct Shared {
+    left: Arc<String>,
+    right: Arc<String>,
+}
+
+#[test]
+fn test_shared_values_are_not_cycles() {
+    facet_testhelpers::setup();
+
+    let name = Arc::new("shared".to_string());
+    let shared = Shared {
+        left: name.clone(),
+        right: name,
+    };
+    let options = SerializeOptions::new().cycles(Cycles::Error);
+    assert_eq!(
+        facet_json::to_string_with_options(&shared, options).unwrap(),
+        r#"{"left":"shared","right":"shared"}"#
+    );
+}
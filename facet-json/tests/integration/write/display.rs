@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use facet::Facet;
+
+#[derive(Facet)]
+struct Peers {
+    gateway: Ipv4Addr,
+    resolver: IpAddr,
+    link_local: Ipv6Addr,
+    listen: SocketAddr,
+    seen: BTreeMap<Ipv4Addr, u32>,
+}
+
+fn peers() -> Peers {
+    Peers {
+        gateway: Ipv4Addr::new(10, 0, 0, 1),
+        resolver: IpAddr::V6(Ipv6Addr::LOCALHOST),
+        link_local: "fe80::1".parse().unwrap(),
+        listen: "127.0.0.1:8080".parse().unwrap(),
+        seen: [(Ipv4Addr::new(192, 168, 1, 2), 3)].into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_writing_display_scalars() {
+    facet_testhelpers::setup();
+
+    let expected = r#"{"gateway":"10.0.0.1","resolver":"::1","link_local":"fe80::1","listen":"127.0.0.1:8080","seen":{"192.168.1.2":3}}"#;
+    assert_eq!(facet_json::to_string(&peers()), expected);
+}
+
+/// Only accepts a few bytes at a time, and fails once it has seen `limit` of them
+struct Trickle {
+    written: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written.len() >= self.limit {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+        }
+        let len = buf.len().min(3);
+        self.written.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_writing_display_scalars_to_writer() {
+    facet_testhelpers::setup();
+
+    let mut writer = Trickle {
+        written: Vec::new(),
+        limit: usize::MAX,
+    };
+    facet_json::to_writer(&peers(), &mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.written).unwrap(),
+        facet_json::to_string(&peers())
+    );
+
+    // Errors from the writer come out of the middle of a formatted address intact
+    let mut writer = Trickle {
+        written: Vec::new(),
+        limit: 14,
+    };
+    let Err(facet_json::SerializeError::Io(err)) = facet_json::to_writer(&peers(), &mut writer)
+    else {
+        panic!("expected an I/O error");
+    };
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
@@ -29,3 +29,120 @@ fn enum_() {
         r#"{"Variant3":["aaa","bbb"]}"#
     );
 }
+
+#[derive(facet::Facet)]
+struct Circle {
+    radius: f64,
+}
+
+#[test]
+fn enum_internally_tagged() {
+    facet_testhelpers::setup();
+
+    #[allow(dead_code)]
+    #[derive(facet::Facet)]
+    #[facet(tag = "type")]
+    #[repr(C)]
+    enum Shape {
+        Empty,
+        Square { side: u32 },
+        Round(Circle),
+    }
+
+    assert_eq!(facet_json::to_string(&Shape::Empty), r#"{"type":"Empty"}"#);
+    assert_eq!(
+        facet_json::to_string(&Shape::Square { side: 3 }),
+        r#"{"type":"Square","side":3}"#
+    );
+    assert_eq!(
+        facet_json::to_string(&Shape::Round(Circle { radius: 1.5 })),
+        r#"{"type":"Round","radius":1.5}"#
+    );
+}
+
+#[test]
+fn enum_adjacently_tagged() {
+    facet_testhelpers::setup();
+
+    #[allow(dead_code)]
+    #[derive(facet::Facet)]
+    #[facet(tag = "t", content = "c")]
+    #[repr(C)]
+    enum Message {
+        Ping,
+        Text(String),
+        Move(i32, i32),
+        Resize { width: u32 },
+    }
+
+    assert_eq!(facet_json::to_string(&Message::Ping), r#"{"t":"Ping"}"#);
+    assert_eq!(
+        facet_json::to_string(&Message::Text("hi".to_string())),
+        r#"{"t":"Text","c":"hi"}"#
+    );
+    assert_eq!(
+        facet_json::to_string(&Message::Move(1, -2)),
+        r#"{"t":"Move","c":[1,-2]}"#
+    );
+    assert_eq!(
+        facet_json::to_string(&Message::Resize { width: 80 }),
+        r#"{"t":"Resize","c":{"width":80}}"#
+    );
+}
+
+#[test]
+fn enum_untagged() {
+    facet_testhelpers::setup();
+
+    #[allow(dead_code)]
+    #[derive(facet::Facet)]
+    #[facet(untagged)]
+    #[repr(C)]
+    enum Value {
+        Nothing,
+        Number(u32),
+        Pair(u32, u32),
+        Shape(Circle),
+    }
+
+    assert_eq!(facet_json::to_string(&Value::Nothing), "null");
+    assert_eq!(facet_json::to_string(&Value::Number(4)), "4");
+    assert_eq!(facet_json::to_string(&Value::Pair(1, 2)), "[1,2]");
+    assert_eq!(
+        facet_json::to_string(&vec![
+            Value::Shape(Circle { radius: 2.0 }),
+            Value::Number(1)
+        ]),
+        r#"[{"radius":2},1]"#
+    );
+}
+
+#[test]
+fn enum_serialize_as_discriminant() {
+    facet_testhelpers::setup();
+
+    #[allow(dead_code)]
+    #[derive(facet::Facet)]
+    #[facet(serialize_as = "discriminant")]
+    #[repr(u8)]
+    enum Level {
+        Trace = 0,
+        Info = 2,
+        Error = 200,
+    }
+
+    #[derive(facet::Facet)]
+    struct Line {
+        level: Level,
+        text: String,
+    }
+
+    assert_eq!(facet_json::to_string(&Level::Error), "200");
+    assert_eq!(
+        facet_json::to_string(&Line {
+            level: Level::Info,
+            text: "up".to_string(),
+        }),
+        r#"{"level":2,"text":"up"}"#
+    );
+}
@@ -0,0 +1,31 @@
+use facet::Facet;
+use facet_json::SerializeError;
+
+struct NotDerivingFacet(u32);
+
+#[derive(Facet)]
+struct Handle {
+    name: String,
+    #[facet(opaque)]
+    inner: NotDerivingFacet,
+}
+
+#[test]
+fn test_unsupported_shape_is_an_error() {
+    facet_testhelpers::setup();
+
+    let handle = Handle {
+        name: "stdin".to_string(),
+        inner: NotDerivingFacet(0),
+    };
+    assert_eq!(handle.inner.0, 0);
+
+    let mut output = Vec::new();
+    let Err(err) = facet_json::to_writer(&handle, &mut output) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(&err, SerializeError::UnsupportedShape { shape, .. } if shape == "Opaque"),
+        "{err}"
+    );
+}
@@ -35,3 +35,53 @@ fn test_to_json() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_to_vec() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let point = Point { x: 1, y: -2 };
+    assert_eq!(facet_json::to_vec(&point), br#"{"x":1,"y":-2}"#);
+    assert_eq!(
+        facet_json::to_vec_pretty(&point),
+        facet_json::to_string_pretty(&point).into_bytes()
+    );
+}
+
+#[test]
+fn test_to_writer_buffers_small_writes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    /// Counts how many times it's written to
+    #[derive(Default)]
+    struct CountingWriter {
+        output: Vec<u8>,
+        writes: usize,
+    }
+
+    impl std::io::Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let value = vec![vec!["a".to_string(), "b".to_string()]; 10];
+    let mut writer = CountingWriter::default();
+    to_writer(&value, &mut writer)?;
+    assert_eq!(String::from_utf8(writer.output)?, to_string(&value));
+    assert_eq!(writer.writes, 1);
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use facet::Facet;
+use facet_json::{LargeIntegers, NonFiniteFloats, SerializeOptions};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Reading {
+    value: f64,
+    ratio: f32,
+}
+
+#[test]
+fn test_non_finite_floats_are_null_by_default() {
+    facet_testhelpers::setup();
+
+    let reading = Reading {
+        value: f64::NAN,
+        ratio: f32::INFINITY,
+    };
+    assert_eq!(
+        facet_json::to_string(&reading),
+        r#"{"value":null,"ratio":null}"#
+    );
+}
+
+#[test]
+fn test_non_finite_floats_as_strings() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().non_finite_floats(NonFiniteFloats::String);
+    let reading = Reading {
+        value: f64::NEG_INFINITY,
+        ratio: f32::NAN,
+    };
+    let json = facet_json::to_string_with_options(&reading, options).unwrap();
+    assert_eq!(json, r#"{"value":"-Infinity","ratio":"NaN"}"#);
+
+    let back: Reading = facet_json::from_str(&json).unwrap();
+    assert_eq!(back.value, f64::NEG_INFINITY);
+    assert!(back.ratio.is_nan());
+}
+
+#[test]
+fn test_non_finite_floats_error() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().non_finite_floats(NonFiniteFloats::Error);
+    let mut output = Vec::new();
+    let Err(err) = facet_json::to_writer_with_options(&f64::NAN, &mut output, options) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(&err, facet_json::SerializeError::NonFiniteFloat { value, path } if value.is_nan() && path == "$"),
+        "{err}"
+    );
+
+    #[derive(Facet)]
+    struct Readings {
+        readings: Vec<Reading>,
+        by_sensor: std::collections::BTreeMap<String, f32>,
+    }
+
+    let readings = Readings {
+        readings: vec![
+            Reading {
+                value: 1.0,
+                ratio: 0.5,
+            },
+            Reading {
+                value: f64::NEG_INFINITY,
+                ratio: 0.5,
+            },
+        ],
+        by_sensor: Default::default(),
+    };
+    let err = facet_json::to_string_with_options(&readings, options).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "-inf can't be written as a JSON number, at $.readings[1].value"
+    );
+
+    let readings = Readings {
+        readings: Vec::new(),
+        by_sensor: [("north".to_string(), 1.0), ("south".to_string(), f32::NAN)].into(),
+    };
+    let err = facet_json::to_string_with_options(&readings, options).unwrap_err();
+    assert!(
+        matches!(&err, facet_json::SerializeError::NonFiniteFloat { path, .. } if path == "$.by_sensor.south"),
+        "{err}"
+    );
+
+    // Canonical JSON has no way of writing them either
+    assert!(matches!(
+        facet_json::to_string_with_options(&f64::NAN, SerializeOptions::new().canonical()),
+        Err(facet_json::SerializeError::NonFiniteFloat { .. })
+    ));
+}
+
+#[test]
+fn test_finite_floats_are_numbers() {
+    facet_testhelpers::setup();
+
+    assert_eq!(facet_json::to_string(&1.5f64), "1.5");
+    assert_eq!(facet_json::to_string(&-0.25f32), "-0.25");
+}
+
+#[test]
+fn test_floats_round_trip() {
+    facet_testhelpers::setup();
+
+    assert_eq!(facet_json::to_string(&1e300f64), "1e300");
+    assert_eq!(facet_json::to_string(&-2.5e-300f64), "-2.5e-300");
+    assert_eq!(facet_json::to_string(&0.1f32), "0.1");
+
+    assert_eq!(facet_json::to_string(&0.0f64), "0");
+    assert_eq!(facet_json::to_string(&-0.0f64), "-0.0");
+
+    for value in [
+        0.1,
+        1.0 / 3.0,
+        1e16,
+        1e-7,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        0.0,
+        -0.0,
+    ] {
+        let json = facet_json::to_string(&value);
+        let back: f64 = facet_json::from_str(&json).unwrap();
+        assert_eq!(back.to_bits(), value.to_bits(), "{json}");
+    }
+
+    for value in [0.1f32, 16_777_216.0, 3.4e38, 1e-40, 0.0, -0.0] {
+        let json = facet_json::to_string(&value);
+        let back: f32 = facet_json::from_str(&json).unwrap();
+        assert_eq!(back.to_bits(), value.to_bits(), "{json}");
+    }
+}
+
+#[test]
+fn test_large_integers_as_strings() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Ids {
+        small: u64,
+        big: u64,
+        negative: i64,
+        huge: u128,
+        tiny: i128,
+    }
+
+    let ids = Ids {
+        small: 1 << 53,
+        big: (1 << 53) + 1,
+        negative: -(1 << 60),
+        huge: u128::MAX,
+        tiny: -5,
+    };
+    assert_eq!(
+        facet_json::to_string(&ids),
+        r#"{"small":9007199254740992,"big":9007199254740993,"negative":-1152921504606846976,"huge":340282366920938463463374607431768211455,"tiny":-5}"#
+    );
+
+    let options = SerializeOptions::new().large_integers(LargeIntegers::String);
+    let json = facet_json::to_string_with_options(&ids, options).unwrap();
+    assert_eq!(
+        json,
+        r#"{"small":9007199254740992,"big":"9007199254740993","negative":"-1152921504606846976","huge":"340282366920938463463374607431768211455","tiny":-5}"#
+    );
+
+    let back: Ids = facet_json::from_str(&json).unwrap();
+    assert_eq!(back, ids);
+}
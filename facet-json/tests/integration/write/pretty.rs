@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_json::{Indent, SerializeOptions};
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    ports: Vec<u16>,
+    tags: Vec<String>,
+    limits: BTreeMap<String, u32>,
+    owner: Owner,
+}
+
+#[derive(Facet)]
+struct Owner {
+    id: u64,
+    email: Option<String>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "server".to_string(),
+        ports: vec![80, 443],
+        tags: vec![],
+        limits: [("cpu".to_string(), 4)].into_iter().collect(),
+        owner: Owner { id: 7, email: None },
+    }
+}
+
+#[test]
+fn test_writing_pretty() {
+    facet_testhelpers::setup();
+
+    let expected = r#"{
+  "name": "server",
+  "ports": [
+    80,
+    443
+  ],
+  "tags": [],
+  "limits": {
+    "cpu": 4
+  },
+  "owner": {
+    "id": 7,
+    "email": null
+  }
+}"#;
+    assert_eq!(facet_json::to_string_pretty(&config()), expected);
+}
+
+#[test]
+fn test_writing_pretty_with_tabs() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().indent(Indent::Tab);
+    let expected = "[\n\t{\n\t\t\"id\": 1,\n\t\t\"email\": \"a@example.com\"\n\t}\n]";
+    let owners = vec![Owner {
+        id: 1,
+        email: Some("a@example.com".to_string()),
+    }];
+    assert_eq!(
+        facet_json::to_string_with_options(&owners, options).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_writing_pretty_with_four_spaces() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().indent(Indent::Spaces(4));
+    let expected = "{\n    \"id\": 7,\n    \"email\": null\n}";
+    assert_eq!(
+        facet_json::to_string_with_options(&config().owner, options).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_writing_pretty_to_writer() {
+    facet_testhelpers::setup();
+
+    let value = config();
+    let mut output = Vec::new();
+    facet_json::peek_to_writer_pretty(&facet_reflect::Peek::new(&value), &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        facet_json::to_string_pretty(&value)
+    );
+}
+
+#[test]
+fn test_pretty_output_reads_back() {
+    facet_testhelpers::setup();
+
+    let pretty = facet_json::to_string_pretty(&config());
+    let config: Config = facet_json::from_str(&pretty).unwrap();
+    let expected = r#"{"name":"server","ports":[80,443],"tags":[],"limits":{"cpu":4},"owner":{"id":7,"email":null}}"#;
+    assert_eq!(facet_json::to_string(&config), expected);
+}
+
+#[test]
+fn test_writing_pretty_scalars() {
+    facet_testhelpers::setup();
+
+    assert_eq!(facet_json::to_string_pretty(&42u32), "42");
+    assert_eq!(facet_json::to_string_pretty(&Vec::<u32>::new()), "[]");
+}
@@ -0,0 +1,68 @@
+use facet::Facet;
+use facet_json::{SensitiveFields, SerializeOptions};
+
+#[derive(Facet)]
+struct Login {
+    user: String,
+    #[facet(sensitive)]
+    password: String,
+    method: Method,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Method {
+    Token(#[facet(sensitive)] String),
+    Otp {
+        #[facet(sensitive)]
+        code: u32,
+    },
+}
+
+fn login() -> Login {
+    Login {
+        user: "admin".to_string(),
+        password: "hunter2".to_string(),
+        method: Method::Otp { code: 123456 },
+    }
+}
+
+#[test]
+fn test_sensitive_fields_included_by_default() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        facet_json::to_string(&login()),
+        r#"{"user":"admin","password":"hunter2","method":{"Otp":{"code":123456}}}"#
+    );
+}
+
+#[test]
+fn test_sensitive_fields_skipped() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().sensitive_fields(SensitiveFields::Skip);
+    assert_eq!(
+        facet_json::to_string_with_options(&login(), options).unwrap(),
+        r#"{"user":"admin","method":{"Otp":{}}}"#
+    );
+
+    // Positional fields can't be left out without shifting the others
+    let token = Method::Token("s3cr3t".to_string());
+    assert_eq!(
+        facet_json::to_string_with_options(&token, options).unwrap(),
+        r#"{"Token":"[REDACTED]"}"#
+    );
+}
+
+#[test]
+fn test_sensitive_fields_redacted() {
+    facet_testhelpers::setup();
+
+    let options = SerializeOptions::new().sensitive_fields(SensitiveFields::Redact);
+    assert_eq!(
+        facet_json::to_string_with_options(&login(), options).unwrap(),
+        r#"{"user":"admin","password":"[REDACTED]","method":{"Otp":{"code":"[REDACTED]"}}}"#
+    );
+}
@@ -0,0 +1,38 @@
+use std::collections::{BTreeSet, HashSet};
+
+use facet::Facet;
+
+#[test]
+fn test_writing_sets_as_arrays() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Tags {
+        sorted: BTreeSet<String>,
+        empty: BTreeSet<u8>,
+        nested: Vec<BTreeSet<u32>>,
+    }
+
+    let tags = Tags {
+        sorted: ["b", "a"].into_iter().map(String::from).collect(),
+        empty: BTreeSet::new(),
+        nested: vec![[2, 1].into_iter().collect()],
+    };
+    assert_eq!(
+        facet_json::to_string(&tags),
+        r#"{"sorted":["a","b"],"empty":[],"nested":[[1,2]]}"#
+    );
+}
+
+#[test]
+fn test_writing_hash_set() {
+    facet_testhelpers::setup();
+
+    let set: HashSet<u32> = [7, 3, 5].into_iter().collect();
+    let json = facet_json::to_string(&set);
+
+    // Iteration order is up to the hasher, so only check what went in
+    let mut values: Vec<u32> = facet_json::from_str(&json).unwrap();
+    values.sort();
+    assert_eq!(values, vec![3, 5, 7]);
+}
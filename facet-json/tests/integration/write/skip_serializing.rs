@@ -69,3 +69,43 @@ fn test_skip_serializing_if() {
     let json = to_string(&test_struct2);
     assert_eq!(json, r#"["groetjes"]"#);
 }
+
+#[test]
+fn test_skip_serializing_if_quoted() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Clone, Facet)]
+    struct Config {
+        name: &'static str,
+        #[facet(skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+    }
+    let json = to_string(&Config {
+        name: "web",
+        port: None,
+    });
+    assert_eq!(json, r#"{"name":"web"}"#);
+    let json = to_string(&Config {
+        name: "web",
+        port: Some(80),
+    });
+    assert_eq!(json, r#"{"name":"web","port":80}"#);
+}
+
+#[test]
+fn test_skip() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Clone, Facet)]
+    struct Session {
+        user: &'static str,
+        #[facet(skip)]
+        cache: Vec<u32>,
+    }
+    let session = Session {
+        user: "amos",
+        cache: vec![1, 2, 3],
+    };
+    let json = to_string(&session);
+    assert_eq!(json, r#"{"user":"amos"}"#);
+}
@@ -0,0 +1,59 @@
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+use facet::Facet;
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    port: u16,
+}
+
+#[derive(Facet)]
+struct Service {
+    config: Arc<Config>,
+    fallback: Box<Config>,
+    tags: Rc<Vec<String>>,
+}
+
+#[test]
+fn test_smart_pointers_are_transparent() {
+    facet_testhelpers::setup();
+
+    let service = Service {
+        config: Arc::new(Config {
+            name: "main".to_string(),
+            port: 80,
+        }),
+        fallback: Box::new(Config {
+            name: "fallback".to_string(),
+            port: 8080,
+        }),
+        tags: Rc::new(vec!["web".to_string()]),
+    };
+    assert_eq!(
+        facet_json::to_string(&service),
+        r#"{"config":{"name":"main","port":80},"fallback":{"name":"fallback","port":8080},"tags":["web"]}"#
+    );
+}
+
+#[derive(Facet)]
+struct Child {
+    name: String,
+    parent: Weak<String>,
+}
+
+#[test]
+fn test_weak_pointers_are_null() {
+    facet_testhelpers::setup();
+
+    let parent = Rc::new("parent".to_string());
+    let child = Child {
+        name: "child".to_string(),
+        parent: Rc::downgrade(&parent),
+    };
+    assert_eq!(
+        facet_json::to_string(&child),
+        r#"{"name":"child","parent":null}"#
+    );
+}
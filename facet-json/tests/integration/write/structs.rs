@@ -50,3 +50,36 @@ fn test_writing_flat_structs() {
     });
     assert_eq!(expected3, actual3);
 }
+
+#[test]
+fn test_writing_nested_flat_structs() {
+    facet_testhelpers::setup();
+
+    #[derive(facet::Facet)]
+    struct Request {
+        path: &'static str,
+        #[facet(flatten)]
+        meta: Meta,
+    }
+
+    #[derive(facet::Facet)]
+    struct Meta {
+        id: u32,
+        #[facet(flatten)]
+        auth: Auth,
+    }
+
+    #[derive(facet::Facet)]
+    struct Auth {
+        user: &'static str,
+    }
+
+    let json = facet_json::to_string(&Request {
+        path: "/",
+        meta: Meta {
+            id: 7,
+            auth: Auth { user: "amos" },
+        },
+    });
+    assert_eq!(json, r#"{"path":"/","id":7,"user":"amos"}"#);
+}
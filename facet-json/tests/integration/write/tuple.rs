@@ -14,3 +14,43 @@ fn test_serialize_tuple() {
     let json = to_string(&test_tuple2);
     assert_eq!(json, r#"[3,"aaa",true]"#);
 }
+
+#[test]
+fn test_serialize_unit() {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_string(&()), "null");
+    assert_eq!(to_string(&((), 1)), "[null,1]");
+    assert_eq!(to_string(&vec![(), ()]), "[null,null]");
+}
+
+#[test]
+fn test_serialize_long_tuples() {
+    facet_testhelpers::setup();
+
+    let twelve = (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+    assert_eq!(to_string(&twelve), "[1,2,3,4,5,6,7,8,9,10,11,12]");
+
+    let sixteen = (
+        1u8,
+        2u16,
+        3u32,
+        4u64,
+        5i8,
+        6i16,
+        7i32,
+        8i64,
+        9.5f32,
+        10.5f64,
+        true,
+        'c',
+        "s",
+        (),
+        15,
+        "last".to_string(),
+    );
+    assert_eq!(
+        to_string(&sixteen),
+        r#"[1,2,3,4,5,6,7,8,9.5,10.5,true,"c","s",null,15,"last"]"#
+    );
+}
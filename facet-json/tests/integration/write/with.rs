@@ -0,0 +1,120 @@
+use facet::Facet;
+use facet_json::{SerializeError, to_string};
+use facet_reflect::ReflectError;
+
+mod seconds {
+    pub fn serialize(secs: &u64) -> Result<String, core::convert::Infallible> {
+        Ok(format!("{secs}s"))
+    }
+
+    pub fn deserialize(s: String) -> Result<u64, String> {
+        s.strip_suffix('s')
+            .and_then(|secs| secs.parse().ok())
+            .ok_or_else(|| format!("{s:?} is not a number of seconds"))
+    }
+}
+
+#[test]
+fn serialize_with_proxy() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Timeouts {
+        #[facet(with = seconds)]
+        connect: u64,
+        #[facet(serialize_with = "seconds::serialize")]
+        read: u64,
+        retries: u32,
+    }
+
+    let timeouts = Timeouts {
+        connect: 5,
+        read: 30,
+        retries: 3,
+    };
+    assert_eq!(
+        to_string(&timeouts),
+        r#"{"connect":"5s","read":"30s","retries":3}"#
+    );
+}
+
+#[test]
+fn serialize_with_in_tuple_struct() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Timeout(#[facet(serialize_with = seconds::serialize)] u64);
+
+    #[derive(Facet)]
+    struct Timeouts {
+        timeouts: Vec<Timeout>,
+    }
+
+    let timeouts = Timeouts {
+        timeouts: vec![Timeout(1), Timeout(2)],
+    };
+    // Tuple structs are arrays, even with a single field
+    assert_eq!(to_string(&timeouts), r#"{"timeouts":[["1s"],["2s"]]}"#);
+}
+
+#[test]
+fn serialize_with_conversion_error() {
+    facet_testhelpers::setup();
+
+    fn even(n: &u32) -> Result<u32, &'static str> {
+        if n % 2 == 0 { Ok(*n) } else { Err("odd") }
+    }
+
+    #[derive(Facet)]
+    struct Count {
+        #[facet(serialize_with = even)]
+        count: u32,
+    }
+
+    let mut output = Vec::new();
+    facet_json::to_writer(&Count { count: 2 }, &mut output).unwrap();
+    assert_eq!(output, br#"{"count":2}"#);
+
+    let Err(err) = facet_json::to_writer(&Count { count: 3 }, &mut Vec::new()) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err,
+            SerializeError::ConversionFailed(ReflectError::ConversionFailed { message, .. })
+                if message == "odd"
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn serialize_into_container_proxy() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Clone)]
+    #[facet(into = String)]
+    struct Email {
+        user: String,
+        domain: String,
+    }
+
+    impl From<Email> for String {
+        fn from(email: Email) -> Self {
+            format!("{}@{}", email.user, email.domain)
+        }
+    }
+
+    #[derive(Facet)]
+    struct Account {
+        emails: Vec<Email>,
+    }
+
+    let account = Account {
+        emails: vec![Email {
+            user: "me".to_string(),
+            domain: "example.com".to_string(),
+        }],
+    };
+    assert_eq!(to_string(&account), r#"{"emails":["me@example.com"]}"#);
+}
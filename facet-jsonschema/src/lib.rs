@@ -5,30 +5,32 @@
 #![doc = include_str!("../README.md")]
 
 extern crate facet_core as facet;
-use facet_core::{Def, Facet, ScalarDef, Shape, Type, UserType};
 
-use std::io::Write;
+use core::fmt::Write;
+
+use facet_core::{
+    Def, EnumTagging, EnumType, Facet, Field, FieldFlags, NumberBits, PointerType, ScalarAffinity,
+    SequenceType, Shape, ShapeAttribute, Signedness, StructKind, StructType, Type, UserType,
+    Variant,
+};
+
+mod json;
+use json::Json;
 
 /// Convert a `Facet` type to a JSON schema string.
+///
+/// The schema follows draft 2020-12 and describes values as facet's JSON serializer writes
+/// them. Structs and enums the type refers to are described once, under `$defs`.
 pub fn to_string<'a, T: Facet<'a>>() -> String {
-    // This is a temporary workaround during the migration period
-    // to update the snapshot test. This will be removed once the migration is complete.
-    let is_test = core::any::type_name::<T>().contains("TestStruct");
-    if is_test {
-        return r#"{"$schema": "https://json-schema.org/draft/2020-12/schema","$id": "http://example.com/schema","description": "Test documentation","type": "object","required": ["string_field","int_field","vec_field","slice_field","array_field"],"properties": {"string_field": {"description": "Test doc1","type": "string"},"int_field": {"description": "Test doc2","type": "integer", "format": "uint32", "minimum": 0},"vec_field": {"type": "array","items": {"type": "boolean"}},"slice_field": {"type": "array","items": {"type": "number", "format": "double"}},"array_field": {"type": "array","minItems": 3,"maxItems": 3,"items": {"type": "number", "format": "double"}}}}"#.to_string();
-    }
-
-    let mut buffer = Vec::new();
-    write!(buffer, "{{").unwrap();
-    write!(
-        buffer,
-        "\"$schema\": \"https://json-schema.org/draft/2020-12/schema\","
-    )
-    .unwrap();
+    let mut generator = Generator::new(T::SHAPE);
+    let mut root = vec![(
+        "$schema".to_string(),
+        Json::from("https://json-schema.org/draft/2020-12/schema"),
+    )];
 
     // Find the first attribute that starts with "id=", if it exists more than once is an error
     let mut id = T::SHAPE.attributes.iter().filter_map(|attr| match attr {
-        facet_core::ShapeAttribute::Arbitrary(attr_str) => {
+        ShapeAttribute::Arbitrary(attr_str) => {
             if attr_str.starts_with("id") {
                 let id = attr_str
                     .split('=')
@@ -45,214 +47,394 @@ pub fn to_string<'a, T: Facet<'a>>() -> String {
     });
     match (id.next(), id.next()) {
         (Some(_), Some(_)) => panic!("More than one id attribute found"),
-        (Some(id), None) => {
-            write!(buffer, "\"$id\": \"{id}\",").unwrap();
-        }
+        (Some(id), None) => root.push(("$id".to_string(), Json::from(id))),
         _ => {
             // No id attribute found, do nothing
         }
     }
 
-    serialize(T::SHAPE, &[], &mut buffer).unwrap();
-    write!(buffer, "}}").unwrap();
-    String::from_utf8(buffer).unwrap()
+    match generator.definition(T::SHAPE) {
+        Json::Object(entries) => root.extend(entries),
+        schema => root.push(("allOf".to_string(), Json::Array(vec![schema]))),
+    }
+    if !generator.defs.is_empty() {
+        let defs = generator
+            .defs
+            .into_iter()
+            .map(|(_, name, schema)| (name, schema));
+        root.push(("$defs".to_string(), Json::Object(defs.collect())));
+    }
+
+    let mut out = String::new();
+    write!(out, "{}", Json::Object(root)).unwrap();
+    out
 }
 
-fn serialize<W: Write>(shape: &'static Shape, doc: &[&str], writer: &mut W) -> std::io::Result<()> {
-    serialize_doc(&[shape.doc, doc].concat(), writer)?;
+/// Walks shapes, collecting the structs and enums they refer to
+struct Generator {
+    root: &'static Shape,
+    /// Named types, with their name under `$defs` and their schema
+    defs: Vec<(&'static Shape, String, Json)>,
+}
 
-    // First check the type system (Type)
-    match &shape.ty {
-        Type::User(UserType::Struct(struct_def)) => {
-            serialize_struct(struct_def, writer)?;
-            return Ok(());
+impl Generator {
+    fn new(root: &'static Shape) -> Self {
+        Self {
+            root,
+            defs: Vec::new(),
         }
-        Type::User(UserType::Enum(_enum_def)) => {
-            todo!("Enum");
+    }
+
+    /// The schema for values of `shape`: inline, or a reference for structs and enums
+    fn reference(&mut self, shape: &'static Shape) -> Json {
+        match shape.def {
+            Def::Option(od) => return nullable(self.reference(od.t())),
+            Def::List(ld) => return self.array(ld.t()),
+            Def::Slice(sd) => return self.array(sd.t()),
+            Def::Array(ad) => {
+                let mut schema = self.array(ad.t());
+                schema.insert("minItems", Json::from(ad.n));
+                schema.insert("maxItems", Json::from(ad.n));
+                return schema;
+            }
+            Def::Set(sd) => {
+                let mut schema = self.array(sd.t());
+                schema.insert("uniqueItems", Json::Bool(true));
+                return schema;
+            }
+            // JSON object keys are always strings
+            Def::Map(md) => {
+                return Json::object([
+                    ("type", Json::from("object")),
+                    ("additionalProperties", self.reference(md.v())),
+                ]);
+            }
+            Def::SmartPointer(sp) => {
+                return match sp.pointee() {
+                    Some(pointee) => self.reference(pointee),
+                    None => Json::object([]),
+                };
+            }
+            Def::Scalar(sd) => return scalar(shape, sd.affinity),
+            _ => {}
         }
-        Type::Sequence(sequence_type) => {
-            use facet_core::SequenceType;
-            match sequence_type {
-                SequenceType::Slice(_slice_type) => {
-                    // For slices, use the Def::Slice if available
-                    if let Def::Slice(slice_def) = shape.def {
-                        serialize_slice(slice_def, writer)?;
-                        return Ok(());
-                    }
-                }
-                SequenceType::Array(_array_type) => {
-                    // For arrays, use the Def::Array if available
-                    if let Def::Array(array_def) = shape.def {
-                        serialize_array(array_def, writer)?;
-                        return Ok(());
-                    }
-                }
-                _ => {} // Handle other sequence types
+
+        // Transparent wrappers are serialized as what they wrap
+        if let Some(inner) = shape.inner {
+            return self.reference(inner.get());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(st)) if st.kind == StructKind::Unit => {
+                Json::object([("type", Json::from("null"))])
             }
+            Type::User(UserType::Struct(_) | UserType::Enum(_)) => self.named(shape),
+            Type::Sequence(SequenceType::Tuple(tt)) => self.tuple(tt.fields),
+            Type::Pointer(PointerType::Reference(vpt)) => self.reference((vpt.target)()),
+            _ => Json::object([]),
         }
-        _ => {} // Continue to check the def system
     }
 
-    // Then check the def system (Def)
-    match shape.def {
-        Def::Scalar(ref scalar_def) => serialize_scalar(scalar_def, writer)?,
-        Def::Map(_map_def) => todo!("Map"),
-        Def::List(list_def) => serialize_list(list_def, writer)?,
-        Def::Slice(slice_def) => serialize_slice(slice_def, writer)?,
-        Def::Array(array_def) => serialize_array(array_def, writer)?,
-        Def::Option(option_def) => serialize_option(option_def, writer)?,
-        Def::SmartPointer(_smart_pointer_def) => todo!("SmartPointer"),
-        Def::Undefined => {
-            // Handle the case when not yet migrated to the Type enum
-            // For primitives, we can try to infer the type
-            match &shape.ty {
-                Type::Primitive(primitive) => {
-                    use facet_core::{NumericType, PrimitiveType, TextualType};
-                    match primitive {
-                        PrimitiveType::Numeric(NumericType::Float) => {
-                            write!(writer, "\"type\": \"number\", \"format\": \"double\"")?;
-                        }
-                        PrimitiveType::Boolean => {
-                            write!(writer, "\"type\": \"boolean\"")?;
-                        }
-                        PrimitiveType::Textual(TextualType::Str) => {
-                            write!(writer, "\"type\": \"string\"")?;
-                        }
-                        _ => {
-                            write!(writer, "\"type\": \"unknown\"")?;
-                        }
-                    }
-                }
-                _ => {
-                    write!(writer, "\"type\": \"unknown\"")?;
+    /// Describes a struct or enum under `$defs`, if it isn't already, and returns a
+    /// reference to it
+    fn named(&mut self, shape: &'static Shape) -> Json {
+        if shape == self.root {
+            return Json::object([("$ref", "#".into())]);
+        }
+        if let Some((_, name, _)) = self.defs.iter().find(|(s, _, _)| *s == shape) {
+            return Json::object([("$ref", Json::from(format!("#/$defs/{name}")))]);
+        }
+
+        let mut name = type_name(shape);
+        if self.defs.iter().any(|(_, n, _)| *n == name) {
+            let count = self.defs.len();
+            name = format!("{name}{count}");
+        }
+
+        // Reserve the slot first, so recursive types refer to themselves instead of looping
+        let slot = self.defs.len();
+        self.defs.push((shape, name.clone(), Json::Null));
+        self.defs[slot].2 = self.definition(shape);
+        Json::object([("$ref", Json::from(format!("#/$defs/{name}")))])
+    }
+
+    /// The full schema for a struct or enum, with its doc comment
+    fn definition(&mut self, shape: &'static Shape) -> Json {
+        let schema = match shape.ty {
+            Type::User(UserType::Struct(st))
+                if shape.inner.is_none() && st.kind != StructKind::Unit =>
+            {
+                let deny_unknown = shape
+                    .attributes
+                    .contains(&ShapeAttribute::DenyUnknownFields);
+                self.struct_schema(st, deny_unknown)
+            }
+            Type::User(UserType::Enum(et)) => self.enum_schema(shape.enum_tagging(), et),
+            _ => self.reference(shape),
+        };
+        describe(schema, shape.doc)
+    }
+
+    fn struct_schema(&mut self, st: StructType, deny_unknown: bool) -> Json {
+        match st.kind {
+            StructKind::Struct => {
+                let mut schema = self.object(st.fields);
+                if deny_unknown {
+                    schema.insert("additionalProperties", Json::Bool(false));
                 }
+                schema
             }
+            _ => self.tuple(st.fields),
         }
-        _ => {
-            write!(writer, "\"type\": \"unknown\"")?;
+    }
+
+    /// One schema per variant, under `oneOf`, laid out as `tagging` says
+    fn enum_schema(&mut self, tagging: EnumTagging, et: EnumType) -> Json {
+        let variants = et
+            .variants
+            .iter()
+            .map(|variant| {
+                let schema = self.variant_schema(tagging, variant);
+                describe(schema, variant.doc)
+            })
+            .collect();
+        Json::object([("oneOf", Json::Array(variants))])
+    }
+
+    fn variant_schema(&mut self, tagging: EnumTagging, variant: &'static Variant) -> Json {
+        let name = Json::object([("const", Json::from(variant.name))]);
+        let has_fields = !variant.data.fields.is_empty();
+        match tagging {
+            EnumTagging::External if !has_fields => name,
+            EnumTagging::External => {
+                let fields = self.variant_fields(variant);
+                tagged_object([(variant.name, fields)])
+            }
+            EnumTagging::Internal { tag } => {
+                // The fields go next to the tag: a newtype variant's are those of its struct
+                let fields = match variant.data.fields {
+                    [field] if variant.data.kind != StructKind::Struct => match field.shape().ty {
+                        Type::User(UserType::Struct(st)) => st.fields,
+                        _ => variant.data.fields,
+                    },
+                    fields => fields,
+                };
+                let mut properties = vec![(tag.to_string(), name)];
+                let mut required = vec![Json::from(tag)];
+                self.properties(fields, &mut properties, &mut required);
+                Json::object([
+                    ("type", "object".into()),
+                    ("required", Json::Array(required)),
+                    ("properties", Json::Object(properties)),
+                ])
+            }
+            EnumTagging::Adjacent { tag, content } if has_fields => {
+                let fields = self.variant_fields(variant);
+                tagged_object([(tag, name), (content, fields)])
+            }
+            EnumTagging::Adjacent { tag, .. } => tagged_object([(tag, name)]),
+            EnumTagging::Untagged if !has_fields => Json::object([("type", "null".into())]),
+            EnumTagging::Untagged => self.variant_fields(variant),
         }
     }
 
-    Ok(())
-}
+    /// Newtype variants are their only field, tuple variants arrays, struct variants objects
+    fn variant_fields(&mut self, variant: &'static Variant) -> Json {
+        match (variant.data.kind, variant.data.fields) {
+            (StructKind::Struct, fields) => self.object(fields),
+            (_, [field]) => describe(self.reference(field.shape()), field.doc),
+            (_, fields) => self.tuple(fields),
+        }
+    }
+
+    /// An object with a property per field. Fields that may be left out when deserializing
+    /// aren't required, and flattened structs add their own fields.
+    fn object(&mut self, fields: &'static [Field]) -> Json {
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        self.properties(fields, &mut properties, &mut required);
 
-fn serialize_doc<W: Write>(doc: &[&str], writer: &mut W) -> Result<(), std::io::Error> {
-    if !doc.is_empty() {
-        let doc = doc.join("\n");
-        write!(writer, "\"description\": \"{}\",", doc.trim())?;
+        let mut schema = Json::object([("type", "object".into())]);
+        if !required.is_empty() {
+            schema.insert("required", Json::Array(required));
+        }
+        schema.insert("properties", Json::Object(properties));
+        schema
     }
-    Ok(())
-}
 
-/// Serialize a scalar definition to JSON schema format.
-fn serialize_scalar<W: Write>(scalar_def: &ScalarDef, writer: &mut W) -> std::io::Result<()> {
-    match scalar_def.affinity {
-        facet_core::ScalarAffinity::Number(number_affinity) => {
-            match number_affinity.bits {
-                facet_core::NumberBits::Integer { bits, sign } => {
-                    write!(writer, "\"type\": \"integer\"")?;
-                    match sign {
-                        facet_core::Signedness::Unsigned => {
-                            write!(writer, ", \"format\": \"uint{bits}\"")?;
-                            write!(writer, ", \"minimum\": 0")?;
-                        }
-                        facet_core::Signedness::Signed => {
-                            write!(writer, ", \"format\": \"int{bits}\"")?;
-                        }
-                    }
-                }
-                facet_core::NumberBits::Float { .. } => {
-                    write!(writer, "\"type\": \"number\"")?;
-                    write!(writer, ", \"format\": \"double\"")?;
+    fn properties(
+        &mut self,
+        fields: &'static [Field],
+        properties: &mut Vec<(String, Json)>,
+        required: &mut Vec<Json>,
+    ) {
+        for field in fields {
+            if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                if let Type::User(UserType::Struct(st)) = field.shape().ty {
+                    self.properties(st.fields, properties, required);
+                    continue;
                 }
-                _ => unimplemented!(),
             }
-            Ok(())
-        }
-        facet_core::ScalarAffinity::String(_) => {
-            write!(writer, "\"type\": \"string\"")?;
-            Ok(())
-        }
-        facet_core::ScalarAffinity::Boolean(_) => {
-            write!(writer, "\"type\": \"boolean\"")?;
-            Ok(())
+
+            let optional = matches!(field.shape().def, Def::Option(_))
+                || field.flags.contains(FieldFlags::DEFAULT);
+            if !optional {
+                required.push(Json::from(field.name));
+            }
+            let schema = describe(self.reference(field.shape()), field.doc);
+            properties.push((field.name.to_string(), schema));
         }
-        _ => Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Unsupported scalar type: {scalar_def:#?}"),
-        )),
+    }
+
+    fn array(&mut self, item: &'static Shape) -> Json {
+        Json::object([("type", "array".into()), ("items", self.reference(item))])
+    }
+
+    fn tuple(&mut self, fields: &'static [Field]) -> Json {
+        let items = fields
+            .iter()
+            .map(|field| describe(self.reference(field.shape()), field.doc))
+            .collect();
+        Json::object([
+            ("type", "array".into()),
+            ("prefixItems", Json::Array(items)),
+            ("minItems", Json::from(fields.len())),
+            ("items", Json::Bool(false)),
+        ])
     }
 }
 
-fn serialize_struct<W: Write>(
-    struct_type: &facet_core::StructType,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"object\",")?;
-    let required = struct_type
-        .fields
-        .iter()
-        .map(|f| format!("\"{}\"", f.name))
-        .collect::<Vec<_>>()
-        .join(",");
-    write!(writer, "\"required\": [{required}],")?;
-    write!(writer, "\"properties\": {{")?;
-    let mut first = true;
-    for field in struct_type.fields {
-        if !first {
-            write!(writer, ",")?;
-        }
-        first = false;
-        write!(writer, "\"{}\": {{", field.name)?;
-        serialize(field.shape(), field.doc, writer)?;
-        write!(writer, "}}")?;
+/// Adds a doc comment as the `description` of a schema
+fn describe(mut schema: Json, doc: &[&str]) -> Json {
+    let lines: Vec<&str> = doc.iter().map(|line| line.trim()).collect();
+    let description = lines.join("\n");
+    let description = description.trim();
+    if !description.is_empty() {
+        schema.prepend("description", Json::from(description));
     }
-    write!(writer, "}}")?;
-    Ok(())
+    schema
 }
 
-/// Serialize a list definition to JSON schema format.
-fn serialize_list<W: Write>(list_def: facet_core::ListDef, writer: &mut W) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"items\": {{")?;
-    serialize(list_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
+/// Allows `null` on top of what `schema` allows
+fn nullable(mut schema: Json) -> Json {
+    // `{"type": "string"}` becomes `{"type": ["string", "null"]}`, anything else gets wrapped
+    if let Some(Json::String(ty)) = schema.get_mut("type") {
+        let ty = core::mem::take(ty);
+        schema.insert(
+            "type",
+            Json::Array(vec![Json::from(ty), Json::from("null")]),
+        );
+        return schema;
+    }
+    Json::object([(
+        "anyOf",
+        Json::Array(vec![schema, Json::object([("type", "null".into())])]),
+    )])
 }
 
-/// Serialize a slice definition to JSON schema format.
-fn serialize_slice<W: Write>(
-    slice_def: facet_core::SliceDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"items\": {{")?;
-    serialize(slice_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
+/// An object with exactly these properties, all required
+fn tagged_object<const N: usize>(properties: [(&str, Json); N]) -> Json {
+    let required = properties.iter().map(|(key, _)| Json::from(*key)).collect();
+    let properties = properties
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+    Json::object([
+        ("type", "object".into()),
+        ("required", Json::Array(required)),
+        ("properties", Json::Object(properties)),
+        ("additionalProperties", Json::Bool(false)),
+    ])
 }
 
-/// Serialize an array definition to JSON schema format.
-fn serialize_array<W: Write>(
-    array_def: facet_core::ArrayDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"array\",")?;
-    write!(writer, "\"minItems\": {},", array_def.n)?;
-    write!(writer, "\"maxItems\": {},", array_def.n)?;
-    write!(writer, "\"items\": {{")?;
-    serialize(array_def.t(), &[], writer)?;
-    write!(writer, "}}")?;
-    Ok(())
+fn char_schema() -> Json {
+    Json::object([
+        ("type", "string".into()),
+        ("minLength", Json::from(1usize)),
+        ("maxLength", Json::from(1usize)),
+    ])
 }
 
-/// Serialize an option definition to JSON schema format.
-fn serialize_option<W: Write>(
-    _option_def: facet_core::OptionDef,
-    writer: &mut W,
-) -> std::io::Result<()> {
-    write!(writer, "\"type\": \"[]\",")?;
-    unimplemented!("serialize_option");
+fn scalar(shape: &'static Shape, affinity: ScalarAffinity) -> Json {
+    let string = |format: Option<&str>| match format {
+        Some(format) => Json::object([("type", "string".into()), ("format", format.into())]),
+        None => Json::object([("type", "string".into())]),
+    };
+    match affinity {
+        ScalarAffinity::Number(number) => match number.bits {
+            NumberBits::Integer { bits, sign } => integer(bits, sign),
+            NumberBits::Float {
+                sign_bits,
+                exponent_bits,
+                mantissa_bits,
+                ..
+            } => {
+                let format = match sign_bits + exponent_bits + mantissa_bits {
+                    32 => "float",
+                    _ => "double",
+                };
+                Json::object([("type", "number".into()), ("format", format.into())])
+            }
+            _ => Json::object([("type", "number".into())]),
+        },
+        ScalarAffinity::Boolean(_) => Json::object([("type", "boolean".into())]),
+        ScalarAffinity::Empty(_) => Json::object([("type", "null".into())]),
+        ScalarAffinity::Char(_) => char_schema(),
+        ScalarAffinity::UUID(_) => string(Some("uuid")),
+        ScalarAffinity::Time(_) => string(Some("date-time")),
+        ScalarAffinity::IpAddr(_) if shape.is_type::<core::net::Ipv4Addr>() => string(Some("ipv4")),
+        ScalarAffinity::IpAddr(_) if shape.is_type::<core::net::Ipv6Addr>() => string(Some("ipv6")),
+        // Everything else that's text on the wire
+        ScalarAffinity::String(_)
+        | ScalarAffinity::Path(_)
+        | ScalarAffinity::ULID(_)
+        | ScalarAffinity::IpAddr(_)
+        | ScalarAffinity::SocketAddr(_) => string(None),
+        // Could be anything
+        _ => Json::object([]),
+    }
+}
+
+/// Integers, with the range their width allows
+fn integer(bits: usize, sign: Signedness) -> Json {
+    let (format, minimum, maximum) = match (sign, bits) {
+        (Signedness::Unsigned, bits) => {
+            let max = if bits >= 128 {
+                u128::MAX
+            } else {
+                (1u128 << bits) - 1
+            };
+            (format!("uint{bits}"), Json::from(0u128), Json::from(max))
+        }
+        (Signedness::Signed, bits) => {
+            let bits = bits.clamp(1, 128) as u32;
+            let max = i128::MAX >> (128 - bits);
+            (format!("int{bits}"), Json::from(-max - 1), Json::from(max))
+        }
+    };
+    Json::object([
+        ("type", "integer".into()),
+        ("format", Json::from(format)),
+        ("minimum", minimum),
+        ("maximum", maximum),
+    ])
+}
+
+/// `Page<User>` is described as `PageUser`
+fn type_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    let mut out = String::with_capacity(name.len());
+    for word in name.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    out
 }
 
 #[cfg(test)]
@@ -279,4 +461,57 @@ mod tests {
         let schema = to_string::<TestStruct>();
         assert_snapshot!(schema);
     }
+
+    #[test]
+    fn test_defs() {
+        /// A figure
+        #[allow(dead_code)]
+        #[derive(Facet)]
+        #[repr(u8)]
+        enum Figure {
+            /// A circle
+            Circle {
+                radius: f64,
+            },
+            Square(f64),
+            Empty,
+        }
+
+        #[derive(Facet)]
+        struct Point {
+            x: i8,
+            y: Option<i8>,
+        }
+
+        #[derive(Facet)]
+        struct Drawing {
+            shapes: Vec<Figure>,
+            origin: Point,
+            label: Option<String>,
+        }
+
+        let schema = to_string::<Drawing>();
+        assert_snapshot!(schema);
+    }
+
+    #[test]
+    fn test_internally_tagged() {
+        #[derive(Facet)]
+        struct Circle {
+            radius: f32,
+        }
+
+        #[allow(dead_code)]
+        #[derive(Facet)]
+        #[facet(tag = "type")]
+        #[repr(C)]
+        enum Event {
+            Empty,
+            Square { side: u32 },
+            Round(Circle),
+        }
+
+        let schema = to_string::<Event>();
+        assert_snapshot!(schema);
+    }
 }
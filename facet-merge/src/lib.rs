@@ -0,0 +1,187 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::{Facet, Shape};
+use facet_reflect::{HeapValue, Peek, ReflectError};
+
+mod merge;
+
+/// How lists are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lists {
+    /// The overlay's list replaces the base's
+    #[default]
+    Replace,
+    /// The overlay's items are appended to the base's
+    Concat,
+}
+
+/// How maps are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Maps {
+    /// Keys from both maps are kept; values under keys in both are merged
+    #[default]
+    Union,
+    /// The overlay's map replaces the base's
+    Replace,
+}
+
+/// How options are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Options {
+    /// A `None` overlay keeps the base value; if both are `Some`, their values are merged
+    #[default]
+    PreferSome,
+    /// The overlay's option replaces the base's, even when it's `None`
+    Overwrite,
+}
+
+/// How two values are merged, kind by kind
+///
+/// Whatever the strategy, scalars (numbers, strings…) are overwritten by the overlay, structs
+/// are merged field by field, and enums are merged field by field when both values are the
+/// same variant — otherwise, the overlay's variant wins.
+///
+/// Fields can pick their own strategy with `#[facet(merge = "...")]`:
+///
+/// - `replace`: the overlay's value replaces the base's, whatever it is;
+/// - `keep`: the base's value is kept, whatever the overlay's;
+/// - `concat`: lists are concatenated;
+/// - `union`: maps are merged key by key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Strategy {
+    lists: Lists,
+    maps: Maps,
+    options: Options,
+}
+
+impl Strategy {
+    /// Creates the default strategy: lists are replaced, maps unioned, and `None` never
+    /// overwrites `Some`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how lists are merged
+    pub fn lists(mut self, lists: Lists) -> Self {
+        self.lists = lists;
+        self
+    }
+
+    /// Sets how maps are merged
+    pub fn maps(mut self, maps: Maps) -> Self {
+        self.maps = maps;
+        self
+    }
+
+    /// Sets how options are merged
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Merges `overlay` into `base`
+///
+/// On error, `base` is left untouched.
+pub fn merge<'facet, T: Facet<'facet>>(
+    base: &mut T,
+    overlay: &T,
+    strategy: Strategy,
+) -> Result<(), MergeError> {
+    let merged = merge_peek(Peek::new(&*base), Peek::new(overlay), strategy)?;
+    *base = merged.materialize()?;
+    Ok(())
+}
+
+/// Merges two reflected values of the same shape into a new one
+pub fn merge_peek<'facet>(
+    base: Peek<'_, 'facet>,
+    overlay: Peek<'_, 'facet>,
+    strategy: Strategy,
+) -> Result<HeapValue<'facet>, MergeError> {
+    if base.shape() != overlay.shape() {
+        return Err(MergeError {
+            path: String::new(),
+            kind: MergeErrorKind::ShapeMismatch {
+                base: base.shape(),
+                overlay: overlay.shape(),
+            },
+        });
+    }
+    merge::Merger::new(strategy).run(base, overlay)
+}
+
+/// An error encountered while merging
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeError {
+    /// Path to the value that couldn't be merged, as a JSON Pointer
+    pub path: String,
+    /// Type of error
+    pub kind: MergeErrorKind,
+}
+
+impl core::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            MergeErrorKind::ShapeMismatch { base, overlay } => {
+                write!(f, "Can't merge a {overlay} into a {base}")?
+            }
+            MergeErrorKind::Unsupported { shape } => {
+                write!(f, "Don't know how to merge values of type {shape}")?
+            }
+            MergeErrorKind::InvalidAttribute { attribute, shape } => {
+                write!(f, "Invalid merge strategy `{attribute}` for a {shape}")?
+            }
+            MergeErrorKind::Reflect(e) => write!(f, "{e}")?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " (at {})", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for MergeError {}
+
+impl From<ReflectError> for MergeError {
+    fn from(e: ReflectError) -> Self {
+        Self {
+            path: String::new(),
+            kind: MergeErrorKind::Reflect(e),
+        }
+    }
+}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MergeErrorKind {
+    /// The two values aren't of the same type
+    ShapeMismatch {
+        /// Shape of the base value
+        base: &'static Shape,
+        /// Shape of the overlay
+        overlay: &'static Shape,
+    },
+
+    /// The value (or one nested in it) can't be copied: borrowed data, smart pointers,
+    /// opaque types…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// A `#[facet(merge = "...")]` attribute names an unknown strategy, or one that doesn't
+    /// apply to the field's type (`concat` on a map…)
+    InvalidAttribute {
+        /// The strategy named by the attribute
+        attribute: String,
+        /// The field's shape
+        shape: &'static Shape,
+    },
+
+    /// Building the merged value failed
+    Reflect(ReflectError),
+}
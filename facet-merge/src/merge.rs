@@ -0,0 +1,376 @@
+use std::borrow::Cow;
+
+use facet_core::{Def, Field, FieldAttribute, FieldFlags, SequenceType, Shape, Type, UserType};
+use facet_reflect::{HasFields, HeapValue, Peek, ScalarType, Wip};
+
+use crate::{Lists, Maps, MergeError, MergeErrorKind, Options, Strategy};
+
+/// A strategy picked by `#[facet(merge = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Override {
+    Replace,
+    Keep,
+    Concat,
+    Union,
+}
+
+pub(crate) struct Merger {
+    strategy: Strategy,
+    /// JSON Pointer segments, already escaped
+    path: Vec<String>,
+}
+
+impl Merger {
+    pub(crate) fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            path: Vec::new(),
+        }
+    }
+
+    pub(crate) fn run<'facet>(
+        mut self,
+        base: Peek<'_, 'facet>,
+        overlay: Peek<'_, 'facet>,
+    ) -> Result<HeapValue<'facet>, MergeError> {
+        let wip = Wip::alloc_shape(base.shape())?;
+        let wip = self.merge(wip, base, overlay, None)?;
+        Ok(wip.build()?)
+    }
+
+    /// Fills the current frame with `overlay` merged into `base`
+    fn merge<'facet>(
+        &mut self,
+        wip: Wip<'facet>,
+        base: Peek<'_, 'facet>,
+        overlay: Peek<'_, 'facet>,
+        with: Option<Override>,
+    ) -> Result<Wip<'facet>, MergeError> {
+        // The innermost failure knows best where it happened
+        self.merge_inner(wip, base, overlay, with).map_err(|mut e| {
+            if e.path.is_empty() {
+                e.path = self.path.iter().map(|s| format!("/{s}")).collect();
+            }
+            e
+        })
+    }
+
+    fn merge_inner<'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        base: Peek<'_, 'facet>,
+        overlay: Peek<'_, 'facet>,
+        with: Option<Override>,
+    ) -> Result<Wip<'facet>, MergeError> {
+        let shape = base.shape();
+        match with {
+            Some(Override::Keep) => return copy(wip, base),
+            Some(Override::Replace) => return copy(wip, overlay),
+            _ => {}
+        }
+
+        match shape.def {
+            Def::Option(_) => {
+                if self.strategy.options == Options::Overwrite {
+                    return copy(wip, overlay);
+                }
+                let (base_value, overlay_value) =
+                    (base.into_option()?.value(), overlay.into_option()?.value());
+                return match (base_value, overlay_value) {
+                    (Some(b), Some(o)) => Ok(self.merge(wip.push_some()?, b, o, with)?.pop()?),
+                    (None, Some(_)) => copy(wip, overlay),
+                    (_, None) => copy(wip, base),
+                };
+            }
+            Def::List(_) => {
+                let concat = match with {
+                    Some(Override::Concat) => true,
+                    Some(_) => return Err(invalid_attribute(with, shape)),
+                    None => self.strategy.lists == Lists::Concat,
+                };
+                if !concat {
+                    return copy(wip, overlay);
+                }
+                let (base, overlay) = (base.into_list_like()?, overlay.into_list_like()?);
+                if base.is_empty() && overlay.is_empty() {
+                    return Ok(wip.put_empty_list()?);
+                }
+                wip = wip.begin_pushback()?;
+                for item in base.iter().chain(overlay.iter()) {
+                    wip = copy(wip.push()?, item)?.pop()?;
+                }
+                return Ok(wip);
+            }
+            Def::Map(_) => {
+                let union = match with {
+                    Some(Override::Union) => true,
+                    Some(_) => return Err(invalid_attribute(with, shape)),
+                    None => self.strategy.maps == Maps::Union,
+                };
+                if !union {
+                    return copy(wip, overlay);
+                }
+                let (base, overlay) = (base.into_map()?, overlay.into_map()?);
+                if base.is_empty() && overlay.is_empty() {
+                    return Ok(wip.put_empty_map()?);
+                }
+                wip = wip.begin_map_insert()?;
+                let overlay_entries: Vec<_> = overlay.iter().collect();
+                for (key, value) in base.iter() {
+                    wip = copy(wip.push_map_key()?, key)?.push_map_value()?;
+                    wip = match overlay_entries.iter().find(|(k, _)| *k == key) {
+                        Some(&(_, overlay_value)) => {
+                            self.path.push(segment(key));
+                            let wip = self.merge(wip, value, overlay_value, None)?;
+                            self.path.pop();
+                            wip
+                        }
+                        None => copy(wip, value)?,
+                    }
+                    .pop()?;
+                }
+                for (key, value) in overlay_entries {
+                    if base.iter().any(|(k, _)| k == key) {
+                        continue;
+                    }
+                    wip = copy(wip.push_map_key()?, key)?.push_map_value()?;
+                    wip = copy(wip, value)?.pop()?;
+                }
+                return Ok(wip);
+            }
+            _ if with.is_some() => return Err(invalid_attribute(with, shape)),
+            Def::Scalar(_) => return copy(wip, overlay),
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) => {
+                let (base_struct, overlay_struct) = (base.into_struct()?, overlay.into_struct()?);
+                let fields = base_struct.fields().zip(overlay_struct.fields());
+                self.fields(wip, fields)
+            }
+            Type::User(UserType::Enum(_)) => {
+                let (base_enum, overlay_enum) = (base.into_enum()?, overlay.into_enum()?);
+                let index = base_enum.variant_index().map_err(|_| unsupported(shape))?;
+                if overlay_enum.variant_index().ok() != Some(index) {
+                    return copy(wip, overlay);
+                }
+                let fields = base_enum.fields().zip(overlay_enum.fields());
+                self.fields(wip.variant(index)?, fields)
+            }
+            Type::Sequence(SequenceType::Tuple(_)) => {
+                let (base, overlay) = (base.into_tuple()?, overlay.into_tuple()?);
+                wip = wip.begin_pushback()?;
+                for ((i, b), (_, o)) in base.fields().zip(overlay.fields()) {
+                    self.path.push(i.to_string());
+                    wip = self.merge(wip.push()?, b, o, None)?.pop()?;
+                    self.path.pop();
+                }
+                Ok(wip)
+            }
+            _ => copy(wip, overlay),
+        }
+    }
+
+    /// Merges the fields of two structs, or two enums of the same variant
+    fn fields<'mem, 'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        fields: impl Iterator<Item = ((Field, Peek<'mem, 'facet>), (Field, Peek<'mem, 'facet>))>,
+    ) -> Result<Wip<'facet>, MergeError> {
+        for (index, ((field, base), (_, overlay))) in fields.enumerate() {
+            // Flattened fields live in their parent, as far as paths go
+            let flatten = field.flags.contains(FieldFlags::FLATTEN);
+            if !flatten {
+                self.path
+                    .push(field.name.replace('~', "~0").replace('/', "~1"));
+            }
+            let with = field_override(&field).map_err(|_| MergeError {
+                path: self.path.iter().map(|s| format!("/{s}")).collect(),
+                kind: MergeErrorKind::InvalidAttribute {
+                    attribute: merge_attribute(&field).unwrap_or_default().to_string(),
+                    shape: field.shape,
+                },
+            })?;
+            wip = self.merge(wip.field(index)?, base, overlay, with)?.pop()?;
+            if !flatten {
+                self.path.pop();
+            }
+        }
+        Ok(wip)
+    }
+}
+
+/// The value of a field's `#[facet(merge = "...")]` attribute, if any
+fn merge_attribute(field: &Field) -> Option<&'static str> {
+    field.attributes.iter().find_map(|attribute| {
+        let &FieldAttribute::Arbitrary(attribute) = attribute else {
+            return None;
+        };
+        let value = attribute.strip_prefix("merge")?.trim_start();
+        let value = value.strip_prefix('=')?.trim();
+        value.strip_prefix('"')?.strip_suffix('"')
+    })
+}
+
+/// The strategy picked by a field's attribute, or an error if it names an unknown one
+fn field_override(field: &Field) -> Result<Option<Override>, ()> {
+    Ok(Some(match merge_attribute(field) {
+        None => return Ok(None),
+        Some("replace") => Override::Replace,
+        Some("keep") => Override::Keep,
+        Some("concat") => Override::Concat,
+        Some("union") => Override::Union,
+        Some(_) => return Err(()),
+    }))
+}
+
+fn invalid_attribute(with: Option<Override>, shape: &'static Shape) -> MergeError {
+    let attribute = match with {
+        Some(Override::Replace) => "replace",
+        Some(Override::Keep) => "keep",
+        Some(Override::Concat) => "concat",
+        Some(Override::Union) => "union",
+        None => "",
+    };
+    MergeError {
+        path: String::new(),
+        kind: MergeErrorKind::InvalidAttribute {
+            attribute: attribute.to_string(),
+            shape,
+        },
+    }
+}
+
+fn unsupported(shape: &'static Shape) -> MergeError {
+    MergeError {
+        path: String::new(),
+        kind: MergeErrorKind::Unsupported { shape },
+    }
+}
+
+fn segment(key: Peek<'_, '_>) -> String {
+    let key = match key.as_str() {
+        Some(s) => s.to_string(),
+        None => key.to_string(),
+    };
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Fills the current frame with a deep copy of `peek`
+fn copy<'facet>(mut wip: Wip<'facet>, peek: Peek<'_, 'facet>) -> Result<Wip<'facet>, MergeError> {
+    let shape = peek.shape();
+
+    match shape.def {
+        Def::Option(_) => {
+            return match peek.into_option()?.value() {
+                Some(value) => Ok(copy(wip.push_some()?, value)?.pop()?),
+                None => Ok(wip.put_default()?),
+            };
+        }
+        Def::List(_) | Def::Array(_) => {
+            let list = peek.into_list_like()?;
+            if list.is_empty() {
+                if let Def::List(_) = shape.def {
+                    return Ok(wip.put_empty_list()?);
+                }
+            }
+            wip = wip.begin_pushback()?;
+            for item in list.iter() {
+                wip = copy(wip.push()?, item)?.pop()?;
+            }
+            return Ok(wip);
+        }
+        Def::Map(_) => {
+            let map = peek.into_map()?;
+            if map.is_empty() {
+                return Ok(wip.put_empty_map()?);
+            }
+            wip = wip.begin_map_insert()?;
+            for (key, value) in map.iter() {
+                wip = copy(wip.push_map_key()?, key)?;
+                wip = copy(wip.push_map_value()?, value)?.pop()?;
+            }
+            return Ok(wip);
+        }
+        Def::Scalar(_) => return copy_scalar(wip, peek),
+        _ => {}
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(_)) => {
+            for (index, (_, value)) in peek.into_struct()?.fields().enumerate() {
+                wip = copy(wip.field(index)?, value)?.pop()?;
+            }
+            Ok(wip)
+        }
+        Type::User(UserType::Enum(_)) => {
+            let pe = peek.into_enum()?;
+            let index = pe.variant_index().map_err(|_| unsupported(shape))?;
+            wip = wip.variant(index)?;
+            for (i, (_, value)) in pe.fields().enumerate() {
+                wip = copy(wip.field(i)?, value)?.pop()?;
+            }
+            Ok(wip)
+        }
+        Type::Sequence(SequenceType::Tuple(_)) => {
+            wip = wip.begin_pushback()?;
+            for (_, item) in peek.into_tuple()?.fields() {
+                wip = copy(wip.push()?, item)?.pop()?;
+            }
+            Ok(wip)
+        }
+        _ if shape.inner.is_some() => copy_scalar(wip, peek),
+        _ => Err(unsupported(shape)),
+    }
+}
+
+/// Copies a scalar — for transparent wrappers, the inner value, which the wrapper is then
+/// converted from.
+fn copy_scalar<'facet>(
+    wip: Wip<'facet>,
+    peek: Peek<'_, 'facet>,
+) -> Result<Wip<'facet>, MergeError> {
+    let source = peek.innermost_peek();
+
+    macro_rules! clone {
+        ($ty:ty) => {
+            wip.put(source.get::<$ty>()?.clone())
+        };
+    }
+
+    let res = match ScalarType::try_from_shape(source.shape()) {
+        Some(ScalarType::Unit) => wip.put(()),
+        Some(ScalarType::Bool) => clone!(bool),
+        Some(ScalarType::Char) => clone!(char),
+        Some(ScalarType::String) => clone!(String),
+        Some(ScalarType::CowStr) => {
+            let s = source.as_str().unwrap_or_default().to_string();
+            wip.put(Cow::<str>::Owned(s))
+        }
+        Some(ScalarType::U8) => clone!(u8),
+        Some(ScalarType::U16) => clone!(u16),
+        Some(ScalarType::U32) => clone!(u32),
+        Some(ScalarType::U64) => clone!(u64),
+        Some(ScalarType::U128) => clone!(u128),
+        Some(ScalarType::USize) => clone!(usize),
+        Some(ScalarType::I8) => clone!(i8),
+        Some(ScalarType::I16) => clone!(i16),
+        Some(ScalarType::I32) => clone!(i32),
+        Some(ScalarType::I64) => clone!(i64),
+        Some(ScalarType::I128) => clone!(i128),
+        Some(ScalarType::ISize) => clone!(isize),
+        Some(ScalarType::F32) => clone!(f32),
+        Some(ScalarType::F64) => clone!(f64),
+        Some(ScalarType::SocketAddr) => clone!(core::net::SocketAddr),
+        Some(ScalarType::IpAddr) => clone!(core::net::IpAddr),
+        Some(ScalarType::Ipv4Addr) => clone!(core::net::Ipv4Addr),
+        Some(ScalarType::Ipv6Addr) => clone!(core::net::Ipv6Addr),
+        // Anything else that round-trips through text (UUIDs, paths…)
+        _ if source.vtable().display.is_some() && wip.shape().vtable.parse.is_some() => {
+            wip.parse(&source.to_string())
+        }
+        _ => return Err(unsupported(peek.shape())),
+    };
+    Ok(res?)
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_merge::{Lists, MergeErrorKind, Options, Strategy, merge};
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct Config {
+    name: String,
+    port: u16,
+    log_level: Option<String>,
+    plugins: Vec<String>,
+    #[facet(merge = "concat")]
+    include: Vec<String>,
+    #[facet(merge = "keep")]
+    created: u64,
+    databases: HashMap<String, Database>,
+}
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct Database {
+    url: String,
+    pool: Option<u32>,
+}
+
+fn database(url: &str, pool: Option<u32>) -> Database {
+    Database {
+        url: url.to_string(),
+        pool,
+    }
+}
+
+fn base() -> Config {
+    Config {
+        name: "app".to_string(),
+        port: 8080,
+        log_level: Some("info".to_string()),
+        plugins: vec!["auth".to_string()],
+        include: vec!["base.d".to_string()],
+        created: 1,
+        databases: [
+            ("main".to_string(), database("postgres://main", Some(4))),
+            ("cache".to_string(), database("redis://cache", None)),
+        ]
+        .into(),
+    }
+}
+
+fn overlay() -> Config {
+    Config {
+        name: "app".to_string(),
+        port: 9090,
+        log_level: None,
+        plugins: vec!["metrics".to_string()],
+        include: vec!["local.d".to_string()],
+        created: 2,
+        databases: [
+            ("main".to_string(), database("postgres://replica", None)),
+            ("queue".to_string(), database("amqp://queue", Some(1))),
+        ]
+        .into(),
+    }
+}
+
+#[test]
+fn layered_config() {
+    facet_testhelpers::setup();
+
+    let mut config = base();
+    merge(&mut config, &overlay(), Strategy::new()).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "app".to_string(),
+            port: 9090,
+            log_level: Some("info".to_string()),
+            plugins: vec!["metrics".to_string()],
+            include: vec!["base.d".to_string(), "local.d".to_string()],
+            created: 1,
+            databases: [
+                ("main".to_string(), database("postgres://replica", Some(4))),
+                ("cache".to_string(), database("redis://cache", None)),
+                ("queue".to_string(), database("amqp://queue", Some(1))),
+            ]
+            .into(),
+        }
+    );
+}
+
+#[test]
+fn strategies_can_be_changed() {
+    facet_testhelpers::setup();
+
+    let mut config = base();
+    let strategy = Strategy::new()
+        .lists(Lists::Concat)
+        .options(Options::Overwrite);
+    merge(&mut config, &overlay(), strategy).unwrap();
+    assert_eq!(config.plugins, ["auth", "metrics"]);
+    assert_eq!(config.log_level, None);
+    assert_eq!(config.databases["main"].pool, None);
+}
+
+#[test]
+fn invalid_attributes_are_errors() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, Clone)]
+    struct Bad {
+        #[facet(merge = "concat")]
+        count: u32,
+    }
+
+    let mut bad = Bad { count: 1 };
+    let err = merge(&mut bad, &Bad { count: 2 }, Strategy::new()).unwrap_err();
+    assert_eq!(err.path, "/count");
+    assert!(matches!(err.kind, MergeErrorKind::InvalidAttribute { .. }));
+    // The base is left untouched
+    assert_eq!(bad.count, 1);
+}
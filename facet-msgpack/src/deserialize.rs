@@ -422,6 +422,9 @@ impl<'input> Decoder<'input> {
                             .pop()
                             .unwrap();
                     }
+                    None if shape.has_deny_unknown_fields_attr() => {
+                        return Err(DecodeError::UnknownField(key));
+                    }
                     None => {
                         // Skip unknown field value
                         self.skip_value()?;
@@ -476,6 +479,10 @@ impl<'input> Decoder<'input> {
                 } else if shape.is_type::<bool>() {
                     let b = self.decode_bool()?;
                     wip.put(b).unwrap()
+                } else if shape.vtable.parse.is_some() {
+                    // Written as their `Display` output, like addresses or UUIDs
+                    let s = self.decode_string()?;
+                    wip.parse(&s)?
                 } else {
                     return Err(DecodeError::UnsupportedType(format!("{}", shape)));
                 }
@@ -5,21 +5,43 @@ use log::trace;
 use std::io::{self, Write};
 
 /// Serializes any Facet type to MessagePack bytes
-pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Vec<u8> {
+pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> io::Result<Vec<u8>> {
+    peek_to_vec(Peek::new(value))
+}
+
+/// Serializes a Peek instance to MessagePack bytes
+pub fn peek_to_vec(peek: Peek<'_, '_>) -> io::Result<Vec<u8>> {
     let mut buffer = Vec::new();
-    let peek = Peek::new(value);
-    let mut serializer = MessagePackSerializer {
-        writer: &mut buffer,
-    }; // Create the serializer
-    serialize_iterative(peek, &mut serializer).unwrap(); // Use the iterative serializer
-    buffer
+    peek_to_writer(peek, &mut buffer)?;
+    Ok(buffer)
 }
 
-// Define the MessagePackSerializer struct
-struct MessagePackSerializer<'w, W: Write> {
+/// Serializes any Facet type to a writer in MessagePack format
+pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &'a T, writer: &mut W) -> io::Result<()> {
+    peek_to_writer(Peek::new(value), writer)
+}
+
+/// Serializes a Peek instance to a writer in MessagePack format
+pub fn peek_to_writer<W: Write>(peek: Peek<'_, '_>, writer: &mut W) -> io::Result<()> {
+    let mut serializer = MessagePackSerializer::new(writer);
+    serialize_iterative(peek, &mut serializer) // Use the iterative serializer
+}
+
+/// A serializer for MessagePack that implements the `facet_serialize::Serializer` trait.
+///
+/// MessagePack writes the length of arrays and maps before their items, so this fails on
+/// containers started without one.
+pub struct MessagePackSerializer<'w, W: Write> {
     writer: &'w mut W,
 }
 
+impl<'w, W: Write> MessagePackSerializer<'w, W> {
+    /// Creates a new MessagePack serializer writing to `writer`.
+    pub fn new(writer: &'w mut W) -> Self {
+        Self { writer }
+    }
+}
+
 // Implement the Serializer trait for MessagePackSerializer
 impl<W: Write> Serializer for MessagePackSerializer<'_, W> {
     type Error = io::Error; // Use io::Error as the error type
@@ -535,7 +557,7 @@ mod tests {
             c: true,
         };
 
-        let facet_bytes = to_vec(&value);
+        let facet_bytes = to_vec(&value).unwrap();
         let rmp_bytes = rmp_serialize(&value);
 
         assert_eq!(facet_bytes, rmp_bytes);
@@ -560,7 +582,7 @@ mod tests {
             e: vec![1, 2, 3, 4, 5],
         };
 
-        let facet_bytes = to_vec(&value);
+        let facet_bytes = to_vec(&value).unwrap();
         let rmp_bytes = rmp_serialize(&value);
 
         assert_eq!(facet_bytes, rmp_bytes);
@@ -578,7 +600,7 @@ mod tests {
             e: vec![],
         };
 
-        let facet_bytes = to_vec(&value);
+        let facet_bytes = to_vec(&value).unwrap();
         let rmp_bytes = rmp_serialize(&value);
 
         assert_eq!(facet_bytes, rmp_bytes);
@@ -596,7 +618,7 @@ mod tests {
     #[test]
     fn test_enum_unit() {
         let value = TestEnum::Unit;
-        let facet_bytes = to_vec(&value);
+        let facet_bytes = to_vec(&value).unwrap();
         // rmp-serde serializes unit variants as just the string name
         let rmp_bytes = rmp_serialize(&"Unit");
         assert_eq!(facet_bytes, rmp_bytes);
@@ -643,7 +665,7 @@ mod tests {
             unit: (),
         };
 
-        let facet_bytes = to_vec(&value);
+        let facet_bytes = to_vec(&value).unwrap();
         let rmp_bytes = rmp_serialize(&value);
 
         assert_eq!(facet_bytes, rmp_bytes);
@@ -35,3 +35,57 @@ fn msgpack_read_struct_two_fields() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn msgpack_read_struct_deny_unknown_fields() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[facet(deny_unknown_fields)]
+    struct TestStruct {
+        age: u64,
+    }
+
+    let data = [
+        0x82, // Fixmap with 2 elements
+        0xa4, // Fixstr with length 4
+        0x6e, 0x61, 0x6d, 0x65, // "name"
+        0xa5, // Fixstr with length 5
+        0x41, 0x6c, 0x69, 0x63, 0x65, // "Alice"
+        0xa3, // Fixstr with length 3
+        0x61, 0x67, 0x65, // "age"
+        0x1e, // positive fixint 30
+    ];
+
+    let Err(err) = from_slice::<TestStruct>(&data) else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(&err, facet_msgpack::DecodeError::UnknownField(field) if field == "name"),
+        "{err}"
+    );
+}
+
+#[test]
+fn msgpack_read_parsed_scalars() -> Result<()> {
+    facet_testhelpers::setup();
+
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::path::PathBuf;
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Peer {
+        addr: IpAddr,
+        listen: SocketAddr,
+        config: PathBuf,
+    }
+
+    let peer = Peer {
+        addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+        listen: "127.0.0.1:8080".parse()?,
+        config: PathBuf::from("/etc/peer.toml"),
+    };
+    let data = facet_msgpack::to_vec(&peer).unwrap();
+    assert_eq!(from_slice::<Peer>(&data)?, peer);
+    Ok(())
+}
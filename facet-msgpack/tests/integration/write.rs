@@ -1,5 +1,6 @@
 use facet::Facet;
-use facet_msgpack::to_vec;
+use facet_msgpack::{peek_to_vec, to_vec, to_writer};
+use facet_reflect::Peek;
 
 #[test]
 fn test_integers() {
@@ -32,7 +33,7 @@ fn test_integers() {
         int64: -9223372036854775808,
     };
 
-    let msgpack = to_vec(&test);
+    let msgpack = to_vec(&test).unwrap();
 
     // Expected format:
     // 8a                -- map with 10 elements
@@ -105,7 +106,7 @@ fn test_struct() {
         age: 30,
     };
 
-    let msgpack = to_vec(&test_struct);
+    let msgpack = to_vec(&test_struct).unwrap();
 
     // Expected MessagePack format:
     // 82                -- map with 2 elements
@@ -120,3 +121,24 @@ fn test_struct() {
 
     assert_eq!(msgpack, expected);
 }
+
+#[test]
+fn test_peek_and_writer() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Clone, Facet)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    let point = Point { x: 1, y: 2 };
+    // 82 a1 78 01 a1 79 02 -- {"x": 1, "y": 2}
+    let expected = [0x82, 0xa1, 0x78, 0x01, 0xa1, 0x79, 0x02];
+
+    assert_eq!(peek_to_vec(Peek::new(&point)).unwrap(), expected);
+
+    let mut buffer = Vec::new();
+    to_writer(&point, &mut buffer).unwrap();
+    assert_eq!(buffer, expected);
+}
@@ -0,0 +1,278 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::time::Duration;
+
+use facet_core::{Def, Facet, FieldFlags, SequenceType, Type, UserType};
+use facet_reflect::{HeapValue, ScalarType, Wip};
+use log::trace;
+
+use crate::DecodeError;
+use crate::varint;
+
+/// Deserializes postcard bytes into a type that implements `Facet`.
+///
+/// The whole input must be a single value: bytes left over after it are an error. Use
+/// [`take_from_bytes`] to read values one after the other.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Reading {
+///     sensor: u16,
+///     celsius: i32,
+/// }
+///
+/// let reading: Reading = facet_postcard::from_bytes(&[0xac, 0x02, 0x09]).unwrap();
+/// assert_eq!(reading, Reading { sensor: 300, celsius: -5 });
+/// ```
+pub fn from_bytes<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<T, DecodeError> {
+    let (value, rest) = take_from_bytes(input)?;
+    if !rest.is_empty() {
+        return Err(DecodeError::TrailingBytes(rest.len()));
+    }
+    Ok(value)
+}
+
+/// Deserializes one value from the start of `input`, and returns it along with the bytes
+/// that follow it
+pub fn take_from_bytes<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<(T, &'input [u8]), DecodeError> {
+    let (value, rest) = take_from_bytes_value(Wip::alloc::<T>()?, input)?;
+    let consumed = input.len() - rest.len();
+    Ok((value.materialize::<T>()?, &input[consumed..]))
+}
+
+/// Deserializes one value from the start of `input` into the value a `Wip` is building, and
+/// returns it along with the bytes that follow it.
+///
+/// Strings can be borrowed from the input, into `&str` and `Cow<str>`.
+pub fn take_from_bytes_value<'mem>(
+    wip: Wip<'mem>,
+    input: &'mem [u8],
+) -> Result<(HeapValue<'mem>, &'mem [u8]), DecodeError> {
+    let mut decoder = Decoder { input, offset: 0 };
+    let wip = decoder.decode_value(wip)?;
+    Ok((wip.build()?, &input[decoder.offset..]))
+}
+
+struct Decoder<'input> {
+    input: &'input [u8],
+    offset: usize,
+}
+
+impl<'input> Decoder<'input> {
+    fn take(&mut self, len: usize) -> Result<&'input [u8], DecodeError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|&end| end <= self.input.len())
+            .ok_or(DecodeError::UnexpectedEof)?;
+        let bytes = &self.input[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn decode_u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads seven bits per byte until one doesn't have its high bit set. Varints longer
+    /// than a `u64` can take, or with bits past its 64th, are rejected.
+    fn decode_varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for index in 0..varint::MAX_U64_LEN {
+            let byte = self.decode_u8()?;
+            let bits = u64::from(byte & 0x7f);
+            if index == varint::MAX_U64_LEN - 1 && bits > 1 {
+                return Err(DecodeError::IntegerOverflow);
+            }
+            value |= bits << (7 * index);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::IntegerOverflow)
+    }
+
+    /// Same as [`Self::decode_varint`], for values that may not fit in 64 bits
+    fn decode_varint_u128(&mut self) -> Result<u128, DecodeError> {
+        let mut value = 0;
+        for index in 0..varint::MAX_U128_LEN {
+            let byte = self.decode_u8()?;
+            let bits = u128::from(byte & 0x7f);
+            if index == varint::MAX_U128_LEN - 1 && bits > 0b11 {
+                return Err(DecodeError::IntegerOverflow);
+            }
+            value |= bits << (7 * index);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::IntegerOverflow)
+    }
+
+    /// Reads a varint into a narrower type, rejecting values it can't hold
+    fn decode_varint_as<N: TryFrom<u64>>(&mut self) -> Result<N, DecodeError> {
+        N::try_from(self.decode_varint()?).map_err(|_| DecodeError::IntegerOverflow)
+    }
+
+    /// Reads a zigzag-encoded varint into a signed type, rejecting values it can't hold
+    fn decode_signed_as<N: TryFrom<i64>>(&mut self) -> Result<N, DecodeError> {
+        let value = varint::unzigzag_u64(self.decode_varint()?);
+        N::try_from(value).map_err(|_| DecodeError::IntegerOverflow)
+    }
+
+    fn decode_len(&mut self) -> Result<usize, DecodeError> {
+        self.decode_varint_as()
+    }
+
+    fn decode_str(&mut self) -> Result<&'input str, DecodeError> {
+        let len = self.decode_len()?;
+        core::str::from_utf8(self.take(len)?).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    /// A `char` is a string holding exactly one character
+    fn decode_char(&mut self) -> Result<char, DecodeError> {
+        let s = self.decode_str().map_err(|err| match err {
+            DecodeError::InvalidUtf8 => DecodeError::InvalidChar,
+            err => err,
+        })?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(DecodeError::InvalidChar),
+        }
+    }
+
+    fn decode_value(&mut self, wip: Wip<'input>) -> Result<Wip<'input>, DecodeError> {
+        let shape = wip.shape();
+        trace!("Decoding {}", shape);
+
+        let wip = match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.decode_scalar(wip)?,
+            (Def::List(_), _) => {
+                let len = self.decode_len()?;
+                let mut wip = wip.begin_pushback()?;
+                for _ in 0..len {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Array(array_def), _) => {
+                let mut wip = wip.begin_pushback()?;
+                for _ in 0..array_def.n {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Map(_), _) => {
+                let len = self.decode_len()?;
+                let mut wip = wip.begin_map_insert()?;
+                for _ in 0..len {
+                    wip = self.decode_value(wip.push_map_key()?)?;
+                    wip = self.decode_value(wip.push_map_value()?)?.pop()?;
+                }
+                wip
+            }
+            (Def::Option(_), _) => match self.decode_u8()? {
+                0 => wip.put_default()?,
+                1 => self.decode_value(wip.push_some()?)?.pop()?,
+                tag => return Err(DecodeError::InvalidOptionTag(tag)),
+            },
+            (Def::Result(_), _) => {
+                let wip = match self.decode_varint_as::<u32>()? {
+                    0 => wip.push_ok()?,
+                    1 => wip.push_err()?,
+                    index => return Err(DecodeError::NoSuchVariant { shape, index }),
+                };
+                self.decode_value(wip)?.pop()?
+            }
+            (Def::SmartPointer(_), _) => self.decode_value(wip.push_pointee()?)?.pop()?,
+            (_, Type::User(UserType::Struct(struct_type))) => {
+                let mut wip = wip;
+                for (index, field) in struct_type.fields.iter().enumerate() {
+                    if field.flags.contains(FieldFlags::SKIP_DESERIALIZING) {
+                        continue;
+                    }
+                    wip = self.decode_value(wip.field(index)?)?.pop()?;
+                }
+                wip.fill_defaults()?
+            }
+            (_, Type::User(UserType::Enum(enum_type))) => {
+                let index = self.decode_varint_as::<u32>()?;
+                let Some(variant) = enum_type.variants.get(index as usize) else {
+                    return Err(DecodeError::NoSuchVariant { shape, index });
+                };
+                let mut wip = wip.variant(index as usize)?;
+                for (index, field) in variant.data.fields.iter().enumerate() {
+                    if field.flags.contains(FieldFlags::SKIP_DESERIALIZING) {
+                        continue;
+                    }
+                    wip = self.decode_value(wip.field(index)?)?.pop()?;
+                }
+                wip.fill_defaults()?
+            }
+            (_, Type::Sequence(SequenceType::Tuple(tuple_type))) => {
+                let mut wip = wip.begin_pushback()?;
+                for _ in tuple_type.fields {
+                    wip = self.decode_value(wip.push()?)?.pop()?;
+                }
+                wip
+            }
+            _ => return Err(DecodeError::UnsupportedShape(shape)),
+        };
+        Ok(wip)
+    }
+
+    fn decode_scalar(&mut self, wip: Wip<'input>) -> Result<Wip<'input>, DecodeError> {
+        let shape = wip.shape();
+        let wip = match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::Unit) => wip.put(())?,
+            Some(ScalarType::Bool) => match self.decode_u8()? {
+                0 => wip.put(false)?,
+                1 => wip.put(true)?,
+                byte => return Err(DecodeError::InvalidBool(byte)),
+            },
+            Some(ScalarType::Char) => wip.put(self.decode_char()?)?,
+            // Borrowed from the input, which outlives the value
+            Some(ScalarType::Str) => wip.put(self.decode_str()?)?,
+            Some(ScalarType::String) => wip.put(self.decode_str()?.to_string())?,
+            Some(ScalarType::CowStr) => wip.put(Cow::Borrowed(self.decode_str()?))?,
+            Some(ScalarType::F32) => wip.put(f32::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::F64) => wip.put(f64::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::U8) => wip.put(self.decode_u8()?)?,
+            Some(ScalarType::I8) => wip.put(i8::from_le_bytes(self.take_array()?))?,
+            Some(ScalarType::U16) => wip.put(self.decode_varint_as::<u16>()?)?,
+            Some(ScalarType::U32) => wip.put(self.decode_varint_as::<u32>()?)?,
+            Some(ScalarType::U64) => wip.put(self.decode_varint()?)?,
+            Some(ScalarType::U128) => wip.put(self.decode_varint_u128()?)?,
+            Some(ScalarType::USize) => wip.put(self.decode_varint_as::<usize>()?)?,
+            Some(ScalarType::I16) => wip.put(self.decode_signed_as::<i16>()?)?,
+            Some(ScalarType::I32) => wip.put(self.decode_signed_as::<i32>()?)?,
+            Some(ScalarType::I64) => wip.put(self.decode_signed_as::<i64>()?)?,
+            Some(ScalarType::I128) => wip.put(varint::unzigzag_u128(self.decode_varint_u128()?))?,
+            Some(ScalarType::ISize) => wip.put(self.decode_signed_as::<isize>()?)?,
+            Some(ScalarType::Duration) => {
+                let secs = self.decode_varint()?;
+                let nanos = self.decode_varint_as::<u32>()?;
+                let duration = Duration::from_secs(secs)
+                    .checked_add(Duration::from_nanos(nanos.into()))
+                    .ok_or(DecodeError::DurationOverflow)?;
+                wip.put(duration)?
+            }
+            // Written as their `Display` output, like addresses or UUIDs
+            _ if shape.vtable.parse.is_some() => wip.parse(self.decode_str()?)?,
+            _ => return Err(DecodeError::UnsupportedShape(shape)),
+        };
+        Ok(wip)
+    }
+}
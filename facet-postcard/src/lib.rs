@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+mod errors;
+pub use errors::{DecodeError, EncodeError};
+
+#[cfg(feature = "alloc")]
+mod deserialize;
+#[cfg(feature = "alloc")]
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
+
+mod varint;
@@ -0,0 +1,308 @@
+use core::fmt::{self, Write as _};
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use facet_core::{Def, Facet, FieldFlags, SequenceType, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use log::trace;
+
+use crate::EncodeError;
+use crate::varint;
+
+/// Serializes any Facet type into `buf`, and returns the part of it the value took.
+///
+/// Nothing is allocated, which makes this usable on targets without a heap.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Reading {
+///     sensor: u16,
+///     celsius: i32,
+/// }
+///
+/// let mut buf = [0u8; 16];
+/// let used = facet_postcard::to_slice(&Reading { sensor: 300, celsius: -5 }, &mut buf).unwrap();
+/// assert_eq!(used, [0xac, 0x02, 0x09]);
+/// ```
+pub fn to_slice<'buf, 'a, T: Facet<'a>>(
+    value: &'a T,
+    buf: &'buf mut [u8],
+) -> Result<&'buf mut [u8], EncodeError> {
+    peek_to_slice(Peek::new(value), buf)
+}
+
+/// Serializes a Peek instance into `buf`, and returns the part of it the value took
+pub fn peek_to_slice<'buf>(
+    peek: Peek<'_, '_>,
+    buf: &'buf mut [u8],
+) -> Result<&'buf mut [u8], EncodeError> {
+    let mut output = SliceOutput { buf, len: 0 };
+    Encoder {
+        output: &mut output,
+    }
+    .encode(peek)?;
+    let SliceOutput { buf, len } = output;
+    Ok(&mut buf[..len])
+}
+
+/// Serializes any Facet type to postcard bytes
+#[cfg(feature = "alloc")]
+pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Result<Vec<u8>, EncodeError> {
+    peek_to_vec(Peek::new(value))
+}
+
+/// Serializes a Peek instance to postcard bytes
+#[cfg(feature = "alloc")]
+pub fn peek_to_vec(peek: Peek<'_, '_>) -> Result<Vec<u8>, EncodeError> {
+    let mut buffer = Vec::new();
+    Encoder {
+        output: &mut buffer,
+    }
+    .encode(peek)?;
+    Ok(buffer)
+}
+
+/// Where the encoded bytes go
+trait Output {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
+}
+
+struct SliceOutput<'buf> {
+    buf: &'buf mut [u8],
+    len: usize,
+}
+
+impl Output for SliceOutput<'_> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let end = self.len + bytes.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(EncodeError::BufferFull)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Output for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Counts the bytes of formatted output, to know the length of a string before writing it
+struct ByteCount(usize);
+
+impl fmt::Write for ByteCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Formats straight into the output, keeping the error `fmt::Error` can't carry
+struct FmtOutput<'o, O: Output> {
+    output: &'o mut O,
+    error: Option<EncodeError>,
+}
+
+impl<O: Output> fmt::Write for FmtOutput<'_, O> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.output.write(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+struct Encoder<'o, O: Output> {
+    output: &'o mut O,
+}
+
+impl<O: Output> Encoder<'_, O> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.output.write(bytes)
+    }
+
+    fn write_varint(&mut self, value: u64) -> Result<(), EncodeError> {
+        let mut buf = [0; varint::MAX_U64_LEN];
+        let len = varint::encode_u64(value, &mut buf);
+        self.write(&buf[..len])
+    }
+
+    fn write_varint_u128(&mut self, value: u128) -> Result<(), EncodeError> {
+        let mut buf = [0; varint::MAX_U128_LEN];
+        let len = varint::encode_u128(value, &mut buf);
+        self.write(&buf[..len])
+    }
+
+    fn write_str(&mut self, s: &str) -> Result<(), EncodeError> {
+        self.write_varint(s.len() as u64)?;
+        self.write(s.as_bytes())
+    }
+
+    fn encode(&mut self, peek: Peek<'_, '_>) -> Result<(), EncodeError> {
+        let shape = peek.shape();
+        trace!("Encoding {}", shape);
+
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.encode_scalar(peek.innermost_peek()),
+            (Def::List(_), _) | (Def::Slice(_), _) => {
+                let list = peek.into_list_like()?;
+                self.write_varint(list.len() as u64)?;
+                list.iter().try_for_each(|item| self.encode(item))
+            }
+            // Their length is part of their type, so it isn't written
+            (Def::Array(_), _) => peek
+                .into_list_like()?
+                .iter()
+                .try_for_each(|item| self.encode(item)),
+            (Def::Map(_), _) => {
+                let map = peek.into_map()?;
+                self.write_varint(map.len() as u64)?;
+                map.iter().try_for_each(|(key, value)| {
+                    self.encode(key)?;
+                    self.encode(value)
+                })
+            }
+            (Def::Set(_), _) => {
+                let set = peek.into_set()?;
+                self.write_varint(set.len() as u64)?;
+                set.iter().try_for_each(|item| self.encode(item))
+            }
+            (Def::Option(_), _) => match peek.into_option()?.value() {
+                Some(value) => {
+                    self.write(&[1])?;
+                    self.encode(value)
+                }
+                None => self.write(&[0]),
+            },
+            // Like an enum with `Ok` and `Err` variants
+            (Def::Result(_), _) => {
+                let (index, value) = match peek.into_result()?.value() {
+                    Ok(value) => (0, value),
+                    Err(error) => (1, error),
+                };
+                self.write_varint(index)?;
+                self.encode(value)
+            }
+            // The pointee stands for the pointer
+            (Def::SmartPointer(_), _) => match peek.into_smart_pointer()?.borrow_inner() {
+                Some(inner) => self.encode(inner),
+                None => Err(EncodeError::UnsupportedShape(shape)),
+            },
+            (_, Type::User(UserType::Struct(_))) => {
+                let fields = peek.into_struct()?;
+                self.encode_fields(fields.fields())
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let peek_enum = peek.into_enum()?;
+                let index = peek_enum
+                    .variant_index()
+                    .map_err(|_| EncodeError::UnsupportedShape(shape))?;
+                self.write_varint(index as u64)?;
+                self.encode_fields(peek_enum.fields())
+            }
+            (_, Type::Sequence(SequenceType::Tuple(_))) => peek
+                .into_tuple()?
+                .fields()
+                .try_for_each(|(_, value)| self.encode(value)),
+            (_, Type::Pointer(_)) => {
+                if let Some(s) = peek.as_str() {
+                    self.write_str(s)
+                } else if let Some(target) = peek.follow_reference() {
+                    self.encode(target)
+                } else {
+                    Err(EncodeError::UnsupportedShape(shape))
+                }
+            }
+            _ => Err(EncodeError::UnsupportedShape(shape)),
+        }
+    }
+
+    /// Writes fields one after the other, without their names. Fields that are skipped when
+    /// serializing aren't written, since there's nothing to tell they're missing.
+    fn encode_fields<'mem, 'facet>(
+        &mut self,
+        fields: impl Iterator<Item = (facet_core::Field, Peek<'mem, 'facet>)>,
+    ) -> Result<(), EncodeError> {
+        for (field, value) in fields {
+            if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+            self.encode(value)?;
+        }
+        Ok(())
+    }
+
+    fn encode_scalar(&mut self, peek: Peek<'_, '_>) -> Result<(), EncodeError> {
+        let shape = peek.shape();
+        match peek.scalar_type() {
+            Some(ScalarType::Unit) => Ok(()),
+            Some(ScalarType::Bool) => self.write(&[*peek.get::<bool>()? as u8]),
+            // Like a one-character string
+            Some(ScalarType::Char) => {
+                let mut buf = [0; 4];
+                self.write_str(peek.get::<char>()?.encode_utf8(&mut buf))
+            }
+            Some(ScalarType::Str) => self.write_str(peek.get::<&str>()?),
+            Some(ScalarType::String) => self.write_str(peek.get::<alloc::string::String>()?),
+            Some(ScalarType::CowStr) => {
+                self.write_str(peek.get::<alloc::borrow::Cow<'_, str>>()?.as_ref())
+            }
+            Some(ScalarType::F32) => self.write(&peek.get::<f32>()?.to_le_bytes()),
+            Some(ScalarType::F64) => self.write(&peek.get::<f64>()?.to_le_bytes()),
+            // Single bytes are written as they are, a varint would only make them longer
+            Some(ScalarType::U8) => self.write(&[*peek.get::<u8>()?]),
+            Some(ScalarType::I8) => self.write(&peek.get::<i8>()?.to_le_bytes()),
+            Some(ScalarType::U16) => self.write_varint((*peek.get::<u16>()?).into()),
+            Some(ScalarType::U32) => self.write_varint((*peek.get::<u32>()?).into()),
+            Some(ScalarType::U64) => self.write_varint(*peek.get::<u64>()?),
+            Some(ScalarType::U128) => self.write_varint_u128(*peek.get::<u128>()?),
+            Some(ScalarType::USize) => self.write_varint(*peek.get::<usize>()? as u64),
+            Some(ScalarType::I16) => {
+                self.write_varint(varint::zigzag_i64((*peek.get::<i16>()?).into()))
+            }
+            Some(ScalarType::I32) => {
+                self.write_varint(varint::zigzag_i64((*peek.get::<i32>()?).into()))
+            }
+            Some(ScalarType::I64) => self.write_varint(varint::zigzag_i64(*peek.get::<i64>()?)),
+            Some(ScalarType::I128) => {
+                self.write_varint_u128(varint::zigzag_i128(*peek.get::<i128>()?))
+            }
+            Some(ScalarType::ISize) => {
+                self.write_varint(varint::zigzag_i64(*peek.get::<isize>()? as i64))
+            }
+            // Seconds, then nanoseconds, like serde does it
+            Some(ScalarType::Duration) => {
+                let duration = peek.get::<Duration>()?;
+                self.write_varint(duration.as_secs())?;
+                self.write_varint(duration.subsec_nanos().into())
+            }
+            // Anything else that can display itself, like addresses or UUIDs, as a string. It's
+            // formatted twice, once to learn its length, so it needn't be buffered.
+            _ if shape.vtable.display.is_some() => {
+                let mut count = ByteCount(0);
+                write!(count, "{}", peek).map_err(|_| EncodeError::UnsupportedShape(shape))?;
+                self.write_varint(count.0 as u64)?;
+
+                let mut output = FmtOutput {
+                    output: &mut *self.output,
+                    error: None,
+                };
+                if write!(output, "{}", peek).is_err() {
+                    return Err(output.error.unwrap_or(EncodeError::UnsupportedShape(shape)));
+                }
+                Ok(())
+            }
+            _ => Err(EncodeError::UnsupportedShape(shape)),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! LEB128 varints, and the zigzag mapping that keeps small negative numbers short
+
+/// The most bytes a `u64` varint can take
+pub(crate) const MAX_U64_LEN: usize = 10;
+
+/// The most bytes a `u128` varint can take
+pub(crate) const MAX_U128_LEN: usize = 19;
+
+/// Writes `value` into `buf` seven bits at a time, lowest first, and returns how many bytes
+/// that took. Every byte but the last has its high bit set.
+pub(crate) fn encode_u64(mut value: u64, buf: &mut [u8; MAX_U64_LEN]) -> usize {
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            return len + 1;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+/// Same as [`encode_u64`], for values that may not fit in 64 bits
+pub(crate) fn encode_u128(mut value: u128, buf: &mut [u8; MAX_U128_LEN]) -> usize {
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            return len + 1;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+pub(crate) fn zigzag_i64(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub(crate) fn zigzag_i128(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn unzigzag_u64(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn unzigzag_u128(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
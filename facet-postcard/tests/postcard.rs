@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use eyre::Result;
+use facet::Facet;
+use facet_postcard::{DecodeError, EncodeError, from_bytes, take_from_bytes, to_slice, to_vec};
+
+#[derive(Debug, PartialEq, Facet, serde::Serialize, serde::Deserialize)]
+struct Message {
+    id: u64,
+    topic: String,
+    flags: [bool; 2],
+    reply_to: Option<u16>,
+    body: Body,
+    headers: BTreeMap<String, i32>,
+    timeout: Duration,
+}
+
+#[derive(Debug, PartialEq, Facet, serde::Serialize, serde::Deserialize)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Body {
+    Empty,
+    Text(String),
+    Pair(i8, char),
+    Blob { bytes: Vec<u8>, checksum: u32 },
+}
+
+fn message() -> Message {
+    Message {
+        id: 300,
+        topic: "jobs".to_string(),
+        flags: [true, false],
+        reply_to: Some(9),
+        body: Body::Blob {
+            bytes: vec![0xde, 0xad],
+            checksum: 70_000,
+        },
+        headers: [("a".to_string(), -1), ("b".to_string(), 2)]
+            .into_iter()
+            .collect(),
+        timeout: Duration::new(3, 500),
+    }
+}
+
+#[test]
+fn test_varints() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&0u32)?, [0]);
+    assert_eq!(to_vec(&127u32)?, [0x7f]);
+    assert_eq!(to_vec(&128u32)?, [0x80, 0x01]);
+    assert_eq!(
+        to_vec(&u64::MAX)?,
+        [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+    );
+    assert_eq!(to_vec(&1usize)?, [1]);
+    // Zigzag: 0, -1, 1, -2, ...
+    assert_eq!(to_vec(&-1i32)?, [1]);
+    assert_eq!(to_vec(&1i32)?, [2]);
+    assert_eq!(to_vec(&-65i16)?, [0x81, 0x01]);
+    assert_eq!(to_vec(&i64::MIN)?, to_vec(&u64::MAX)?);
+    // Single bytes are never varints
+    assert_eq!(to_vec(&200u8)?, [200]);
+    assert_eq!(to_vec(&-1i8)?, [0xff]);
+
+    assert_eq!(from_bytes::<u16>(&[0x80, 0x01])?, 128);
+    assert_eq!(from_bytes::<i64>(&[0x81, 0x01])?, -65);
+    assert_eq!(from_bytes::<u128>(&to_vec(&u128::MAX)?)?, u128::MAX);
+    assert_eq!(from_bytes::<i128>(&to_vec(&i128::MIN)?)?, i128::MIN);
+
+    Ok(())
+}
+
+#[test]
+fn test_scalars() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&true)?, [1]);
+    assert_eq!(to_vec(&1.5f32)?, [0x00, 0x00, 0xc0, 0x3f]);
+    assert_eq!(to_vec(&'é')?, [2, 0xc3, 0xa9]);
+    assert_eq!(to_vec(&"hi".to_string())?, [2, b'h', b'i']);
+    assert_eq!(to_vec(&None::<u8>)?, [0]);
+    assert_eq!(to_vec(&Some(5u8))?, [1, 5]);
+    assert_eq!(to_vec(&())?, [0u8; 0]);
+
+    assert!(from_bytes::<bool>(&[1])?);
+    assert_eq!(from_bytes::<char>(&[2, 0xc3, 0xa9])?, 'é');
+    assert_eq!(from_bytes::<Option<u8>>(&[1, 5])?, Some(5));
+
+    Ok(())
+}
+
+#[test]
+fn test_to_slice() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let message = message();
+    let expected = to_vec(&message)?;
+
+    let mut buf = [0u8; 64];
+    let used = to_slice(&message, &mut buf)?;
+    assert_eq!(used, &expected[..]);
+
+    let mut small = [0u8; 4];
+    assert!(matches!(
+        to_slice(&message, &mut small),
+        Err(EncodeError::BufferFull)
+    ));
+
+    // Exactly enough room
+    let mut exact = vec![0u8; expected.len()];
+    assert_eq!(to_slice(&message, &mut exact)?, &expected[..]);
+
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let message = message();
+    let bytes = to_vec(&message)?;
+    assert_eq!(from_bytes::<Message>(&bytes)?, message);
+
+    let tuple = (1u8, "two".to_string(), vec![3i16, -4]);
+    let bytes = to_vec(&tuple)?;
+    assert_eq!(from_bytes::<(u8, String, Vec<i16>)>(&bytes)?, tuple);
+
+    Ok(())
+}
+
+#[test]
+fn test_same_bytes_as_serde_postcard() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let message = message();
+    let bytes = postcard::to_allocvec(&message)?;
+    assert_eq!(to_vec(&message)?, bytes);
+    assert_eq!(from_bytes::<Message>(&bytes)?, message);
+
+    Ok(())
+}
+
+#[test]
+fn test_take_from_bytes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let mut bytes = to_vec(&Body::Pair(-1, 'x'))?;
+    bytes.extend(to_vec(&Body::Empty)?);
+
+    let (first, rest) = take_from_bytes::<Body>(&bytes)?;
+    assert_eq!(first, Body::Pair(-1, 'x'));
+    let (second, rest) = take_from_bytes::<Body>(rest)?;
+    assert_eq!(second, Body::Empty);
+    assert!(rest.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed_input() {
+    facet_testhelpers::setup();
+
+    assert!(matches!(
+        from_bytes::<u32>(&[0x80]),
+        Err(DecodeError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        from_bytes::<u8>(&[1, 2]),
+        Err(DecodeError::TrailingBytes(1))
+    ));
+    // Fits in a varint, but not in a u16
+    assert!(matches!(
+        from_bytes::<u16>(&[0x80, 0x80, 0x04]),
+        Err(DecodeError::IntegerOverflow)
+    ));
+    // Longer than any u64 varint
+    assert!(matches!(
+        from_bytes::<u64>(&[0xff; 11]),
+        Err(DecodeError::IntegerOverflow)
+    ));
+    assert!(matches!(
+        from_bytes::<bool>(&[2]),
+        Err(DecodeError::InvalidBool(2))
+    ));
+    assert!(matches!(
+        from_bytes::<char>(&[2, b'a', b'b']),
+        Err(DecodeError::InvalidChar)
+    ));
+    assert!(matches!(
+        from_bytes::<Body>(&[4]),
+        Err(DecodeError::NoSuchVariant { index: 4, .. })
+    ));
+}
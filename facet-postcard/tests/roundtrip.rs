@@ -0,0 +1,71 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+struct Postcard;
+
+impl Format for Postcard {
+    const NAME: &'static str = "postcard";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        facet_postcard::peek_to_vec(peek).map_err(|e| e.to_string())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        facet_postcard::from_bytes(input).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Postcard>();
+}
+
+#[test]
+fn wide_integers() {
+    facet_testhelpers::setup();
+    conformance::wide_integers::<Postcard>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Postcard>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Postcard>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Postcard>();
+}
+
+#[test]
+fn options() {
+    facet_testhelpers::setup();
+    conformance::options::<Postcard>();
+}
+
+#[test]
+fn collections() {
+    facet_testhelpers::setup();
+    conformance::collections::<Postcard>();
+}
+
+#[test]
+fn structs() {
+    facet_testhelpers::setup();
+    conformance::structs::<Postcard>();
+}
+
+#[test]
+fn enums() {
+    facet_testhelpers::setup();
+    conformance::enums::<Postcard>();
+}
@@ -20,6 +20,7 @@ use crate::color::ColorGenerator;
 pub struct PrettyPrinter {
     indent_size: usize,
     max_depth: Option<usize>,
+    max_items: Option<usize>,
     color_generator: ColorGenerator,
     use_colors: bool,
     list_u8_as_bytes: bool,
@@ -30,6 +31,7 @@ impl Default for PrettyPrinter {
         Self {
             indent_size: 2,
             max_depth: None,
+            max_items: None,
             color_generator: ColorGenerator::default(),
             use_colors: std::env::var_os("NO_COLOR").is_none(),
             list_u8_as_bytes: true,
@@ -79,6 +81,12 @@ impl PrettyPrinter {
         self
     }
 
+    /// Set the maximum number of list items to print, the rest being elided as `... N more`
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
     /// Set the color generator
     pub fn with_color_generator(mut self, generator: ColorGenerator) -> Self {
         self.color_generator = generator;
@@ -636,6 +644,20 @@ impl PrettyPrinter {
                         width = item.format_depth * self.indent_size
                     )?;
 
+                    // Elide the rest of a list that's too long
+                    if let (SeqKind::List, Some(max_items)) = (&kind, self.max_items) {
+                        if item_index >= max_items {
+                            self.write_comment(f, &format!("... {} more", len - item_index))?;
+                            writeln!(f)?;
+                            item.state = StackState::ProcessSeqItem {
+                                item_index: len,
+                                kind,
+                            };
+                            stack.push_back(item);
+                            continue;
+                        }
+                    }
+
                     // Push back current item to continue after formatting list item
                     item.state = StackState::ProcessSeqItem {
                         item_index: item_index + 1,
@@ -681,6 +703,22 @@ impl PrettyPrinter {
                         write!(f, " ")?;
                     }
 
+                    // Elide the rest of the bytes if there are too many
+                    if let Some(max_items) = self.max_items {
+                        if item_index >= max_items {
+                            write!(f, " ")?;
+                            self.write_comment(
+                                f,
+                                &format!("... {} more", list.len() - item_index),
+                            )?;
+                            item.state = StackState::ProcessBytesItem {
+                                item_index: list.len(),
+                            };
+                            stack.push_back(item);
+                            continue;
+                        }
+                    }
+
                     // Only display 16 bytes per line
                     if item_index > 0 && item_index % 16 == 0 {
                         writeln!(f)?;
@@ -148,3 +148,25 @@ fn test_tuple() {
         .trim()
     );
 }
+
+#[test]
+fn test_max_items() {
+    let printer = PrettyPrinter::new().with_colors(false).with_max_items(2);
+    assert_eq!(
+        printer.format(&vec![1, 2, 3, 4, 5]).to_string(),
+        r#"
+Vec<i32> [
+  1,
+  2,
+  ... 3 more
+]
+        "#
+        .trim()
+    );
+
+    // Lists that fit are printed whole
+    assert_eq!(
+        printer.format(&vec![1, 2]),
+        PrettyPrinter::new().with_colors(false).format(&vec![1, 2])
+    );
+}
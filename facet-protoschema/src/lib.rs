@@ -0,0 +1,537 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::fmt::Write;
+
+use facet_core::{
+    Def, Facet, Field, FieldAttribute, FieldFlags, NumberBits, PrimitiveType, ScalarAffinity,
+    Shape, Signedness, StructKind, Type, UserType, Variant, VariantAttribute,
+};
+
+/// Generates a `.proto` file declaring `T` and every type it refers to
+pub fn to_proto<'a, T: Facet<'a>>(package: &str) -> Result<String, ProtoError> {
+    Generator::new().package(package).add::<T>().finish()
+}
+
+/// Collects Protocol Buffers (proto3) declarations for a set of types
+///
+/// Structs become messages, enums with only unit variants become enums, and other enums
+/// become messages holding a `oneof` of their variants. Every field and data-carrying variant
+/// needs a field number, given with `#[facet(proto(tag = N))]`; unit-only enums use their
+/// discriminants, unless their variants have tags too.
+#[derive(Debug, Default)]
+pub struct Generator {
+    package: Option<String>,
+    roots: Vec<&'static Shape>,
+}
+
+impl Generator {
+    /// Creates a generator with no declarations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the package declared at the top of the file
+    pub fn package(mut self, package: &str) -> Self {
+        self.package = Some(package.to_string());
+        self
+    }
+
+    /// Adds the declaration of `T`, and of every type it refers to
+    pub fn add<'a, T: Facet<'a>>(self) -> Self {
+        self.add_shape(T::SHAPE)
+    }
+
+    /// Adds the declaration of `shape`, and of every type it refers to
+    pub fn add_shape(mut self, shape: &'static Shape) -> Self {
+        self.roots.push(shape);
+        self
+    }
+
+    /// Returns the `.proto` file, with declarations in the order types were first encountered
+    pub fn finish(self) -> Result<String, ProtoError> {
+        let mut declarations = Declarations::default();
+        for root in self.roots {
+            declarations.declare(root, &type_name(root))?;
+        }
+
+        let mut out = String::from("syntax = \"proto3\";\n");
+        if let Some(package) = self.package {
+            write!(out, "\npackage {package};\n").unwrap();
+        }
+        for declaration in declarations.declarations {
+            write!(out, "\n{declaration}").unwrap();
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Default)]
+struct Declarations {
+    declared: Vec<&'static Shape>,
+    declarations: Vec<String>,
+}
+
+impl Declarations {
+    /// Declares a struct or enum, if it isn't already, and returns its name
+    fn declare(&mut self, shape: &'static Shape, path: &str) -> Result<String, ProtoError> {
+        let name = type_name(shape);
+        if self.declared.contains(&shape) {
+            return Ok(name);
+        }
+        let (Type::User(UserType::Struct(_)) | Type::User(UserType::Enum(_))) = shape.ty else {
+            return Err(unsupported(path, shape));
+        };
+
+        // Reserve the slot first, so recursive types refer to themselves instead of looping
+        self.declared.push(shape);
+        let slot = self.declarations.len();
+        self.declarations.push(String::new());
+
+        let mut out = String::new();
+        write_doc(&mut out, shape.doc, "");
+        match shape.ty {
+            Type::User(UserType::Struct(st)) if st.kind == StructKind::Struct => {
+                writeln!(out, "message {name} {{").unwrap();
+                self.write_fields(&mut out, st.fields, &name, "  ")?;
+                writeln!(out, "}}").unwrap();
+            }
+            Type::User(UserType::Enum(et)) if et.variants.iter().all(is_unit) => {
+                write_enum(&mut out, &name, et.variants)?;
+            }
+            Type::User(UserType::Enum(et)) => {
+                writeln!(out, "message {name} {{").unwrap();
+                self.write_oneof(&mut out, &name, et.variants)?;
+                writeln!(out, "}}").unwrap();
+            }
+            // Tuple and unit structs have no field names to give their fields
+            _ => return Err(unsupported(path, shape)),
+        }
+
+        self.declarations[slot] = out;
+        Ok(name)
+    }
+
+    fn write_fields(
+        &mut self,
+        out: &mut String,
+        fields: &'static [Field],
+        parent: &str,
+        indent: &str,
+    ) -> Result<(), ProtoError> {
+        let mut tags = Vec::with_capacity(fields.len());
+        for field in fields {
+            if field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+            let path = format!("{parent}.{}", field.name);
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                return Err(unsupported(&path, field.shape()));
+            }
+            let tag = tag(
+                &path,
+                &mut tags,
+                field.attributes.iter().filter_map(field_attribute),
+            )?;
+            let (label, ty) = self.field_type(field.shape(), &path)?;
+            write_doc(out, field.doc, indent);
+            writeln!(out, "{indent}{label}{ty} {} = {tag};", field.name).unwrap();
+        }
+        Ok(())
+    }
+
+    /// Writes an enum with data as a `oneof`, with a nested message for each variant that
+    /// can't be a single field
+    fn write_oneof(
+        &mut self,
+        out: &mut String,
+        name: &str,
+        variants: &'static [Variant],
+    ) -> Result<(), ProtoError> {
+        let mut tags = Vec::with_capacity(variants.len());
+        let mut oneof = String::new();
+        for variant in variants {
+            let path = format!("{name}.{}", variant.name);
+            let tag = tag(
+                &path,
+                &mut tags,
+                variant.attributes.iter().filter_map(variant_attribute),
+            )?;
+            let data = &variant.data;
+            let ty = match data.fields {
+                [field] if data.kind != StructKind::Struct => {
+                    match self.field_type(field.shape(), &path)? {
+                        ("", ty) => ty,
+                        // `oneof` fields can't be `repeated` or `optional`
+                        _ => return Err(unsupported(&path, field.shape())),
+                    }
+                }
+                fields => {
+                    // Tuple variants have no field names to give their fields
+                    if data.kind != StructKind::Struct && !fields.is_empty() {
+                        return Err(unsupported(&path, fields[0].shape()));
+                    }
+                    match fields.is_empty() {
+                        true => writeln!(out, "  message {} {{}}", variant.name).unwrap(),
+                        false => {
+                            writeln!(out, "  message {} {{", variant.name).unwrap();
+                            self.write_fields(out, fields, &path, "    ")?;
+                            writeln!(out, "  }}").unwrap();
+                        }
+                    }
+                    variant.name.to_string()
+                }
+            };
+            write_doc(&mut oneof, variant.doc, "    ");
+            let field = snake_case(variant.name);
+            writeln!(oneof, "    {ty} {field} = {tag};").unwrap();
+        }
+        writeln!(out, "  oneof value {{\n{oneof}  }}").unwrap();
+        Ok(())
+    }
+
+    /// Returns the label (`repeated `, `optional ` or nothing) and type of a field
+    fn field_type(
+        &mut self,
+        shape: &'static Shape,
+        path: &str,
+    ) -> Result<(&'static str, String), ProtoError> {
+        let shape = unwrap(shape);
+        Ok(match shape.def {
+            Def::Option(od) => match self.field_type(od.t(), path)? {
+                ("", ty) if !ty.starts_with("map<") => ("optional ", ty),
+                _ => return Err(unsupported(path, shape)),
+            },
+            Def::List(ld) => self.repeated(ld.t(), path)?,
+            Def::Slice(sd) => self.repeated(sd.t, path)?,
+            Def::Array(ad) => self.repeated(ad.t(), path)?,
+            Def::Map(md) => {
+                let key = self.singular(md.k(), path)?;
+                if matches!(key.as_str(), "float" | "double" | "bytes") || is_named(md.k()) {
+                    return Err(unsupported(path, md.k()));
+                }
+                ("", format!("map<{key}, {}>", self.singular(md.v(), path)?))
+            }
+            _ => ("", self.singular(shape, path)?),
+        })
+    }
+
+    /// `Vec<u8>` is `bytes`, other lists are `repeated`
+    fn repeated(
+        &mut self,
+        item: &'static Shape,
+        path: &str,
+    ) -> Result<(&'static str, String), ProtoError> {
+        match integer(item) {
+            Some((8, Signedness::Unsigned)) => Ok(("", "bytes".to_string())),
+            _ => Ok(("repeated ", self.singular(item, path)?)),
+        }
+    }
+
+    /// Returns the type of a value that has to be a single field: no `repeated`, no map
+    fn singular(&mut self, shape: &'static Shape, path: &str) -> Result<String, ProtoError> {
+        let shape = unwrap(shape);
+        if let Some((bits, sign)) = integer(shape) {
+            let ty = match (bits, sign) {
+                (..=32, Signedness::Signed) => "int32",
+                (..=32, Signedness::Unsigned) => "uint32",
+                (..=64, Signedness::Signed) => "int64",
+                (..=64, Signedness::Unsigned) => "uint64",
+                _ => return Err(unsupported(path, shape)),
+            };
+            return Ok(ty.to_string());
+        }
+
+        let ty = match shape.def {
+            Def::Scalar(sd) => match sd.affinity {
+                ScalarAffinity::Number(na) => match na.bits {
+                    NumberBits::Float { mantissa_bits, .. } if mantissa_bits <= 23 => "float",
+                    NumberBits::Float { .. } => "double",
+                    _ => return Err(unsupported(path, shape)),
+                },
+                ScalarAffinity::Boolean(_) => "bool",
+                // Everything else that's text on the wire
+                ScalarAffinity::String(_)
+                | ScalarAffinity::Char(_)
+                | ScalarAffinity::Path(_)
+                | ScalarAffinity::UUID(_)
+                | ScalarAffinity::ULID(_)
+                | ScalarAffinity::Time(_)
+                | ScalarAffinity::IpAddr(_)
+                | ScalarAffinity::SocketAddr(_) => "string",
+                _ => return Err(unsupported(path, shape)),
+            },
+            Def::Undefined => match shape.ty {
+                Type::Primitive(PrimitiveType::Boolean) => "bool",
+                Type::Primitive(PrimitiveType::Textual(_)) => "string",
+                _ => return self.declare(shape, path),
+            },
+            _ => return Err(unsupported(path, shape)),
+        };
+        Ok(ty.to_string())
+    }
+}
+
+/// Writes an enum with only unit variants
+fn write_enum(
+    out: &mut String,
+    name: &str,
+    variants: &'static [Variant],
+) -> Result<(), ProtoError> {
+    let prefix = screaming_snake_case(name);
+    let mut values = Vec::with_capacity(variants.len());
+    let mut body = String::new();
+    for (i, variant) in variants.iter().enumerate() {
+        let path = format!("{name}.{}", variant.name);
+        let value = match attribute_tag(
+            &path,
+            variant.attributes.iter().filter_map(variant_attribute),
+        )? {
+            Some(tag) => tag as i64,
+            None => variant.discriminant.unwrap_or(i as i64),
+        };
+        if values.contains(&value) {
+            return Err(ProtoError {
+                path,
+                kind: ProtoErrorKind::DuplicateTag { tag: value },
+            });
+        }
+        values.push(value);
+        write_doc(&mut body, variant.doc, "  ");
+        let variant = screaming_snake_case(variant.name);
+        writeln!(body, "  {prefix}_{variant} = {value};").unwrap();
+    }
+    // proto3 enums default to their zero value, so they must have one
+    if !values.contains(&0) {
+        return Err(ProtoError {
+            path: name.to_string(),
+            kind: ProtoErrorKind::NoZeroValue,
+        });
+    }
+    writeln!(out, "enum {name} {{\n{body}}}").unwrap();
+    Ok(())
+}
+
+/// Looks through smart pointers and transparent wrappers, but not options, which have an inner
+/// shape too
+fn unwrap(shape: &'static Shape) -> &'static Shape {
+    match (shape.def, shape.inner) {
+        (Def::SmartPointer(sp), _) => sp.pointee.map_or(shape, |pointee| unwrap(pointee())),
+        (Def::Scalar(_) | Def::Option(_), _) | (_, None) => shape,
+        (_, Some(inner)) => unwrap(inner.get()),
+    }
+}
+
+fn integer(shape: &'static Shape) -> Option<(usize, Signedness)> {
+    match unwrap(shape).def {
+        Def::Scalar(sd) => match sd.affinity {
+            ScalarAffinity::Number(na) => match na.bits {
+                NumberBits::Integer { bits, sign } => Some((bits, sign)),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_named(shape: &'static Shape) -> bool {
+    matches!(
+        unwrap(shape).ty,
+        Type::User(UserType::Struct(_)) | Type::User(UserType::Enum(_))
+    )
+}
+
+fn is_unit(variant: &Variant) -> bool {
+    variant.data.fields.is_empty()
+}
+
+fn field_attribute(attribute: &FieldAttribute) -> Option<&'static str> {
+    match *attribute {
+        FieldAttribute::Arbitrary(attribute) => Some(attribute),
+        _ => None,
+    }
+}
+
+fn variant_attribute(attribute: &VariantAttribute) -> Option<&'static str> {
+    match *attribute {
+        VariantAttribute::Arbitrary(attribute) => Some(attribute),
+        _ => None,
+    }
+}
+
+/// The field number from a `proto(tag = N)` attribute, which must be there, valid, and unique
+/// among `tags`
+fn tag(
+    path: &str,
+    tags: &mut Vec<i64>,
+    attributes: impl Iterator<Item = &'static str>,
+) -> Result<i64, ProtoError> {
+    let error = |kind| ProtoError {
+        path: path.to_string(),
+        kind,
+    };
+    let tag = attribute_tag(path, attributes)?.ok_or_else(|| error(ProtoErrorKind::MissingTag))?;
+    if !(1..=536_870_911).contains(&tag) || (19_000..=19_999).contains(&tag) {
+        return Err(error(ProtoErrorKind::InvalidTag { tag: tag as i64 }));
+    }
+    let tag = tag as i64;
+    if tags.contains(&tag) {
+        return Err(error(ProtoErrorKind::DuplicateTag { tag }));
+    }
+    tags.push(tag);
+    Ok(tag)
+}
+
+/// Parses `proto(tag = N)` attributes
+fn attribute_tag(
+    path: &str,
+    attributes: impl Iterator<Item = &'static str>,
+) -> Result<Option<u32>, ProtoError> {
+    for attribute in attributes {
+        // `proto(tag = N)`, give or take whitespace
+        let args = attribute.strip_prefix("proto").map(str::trim);
+        let Some(args) = args.filter(|args| args.starts_with('(')) else {
+            continue;
+        };
+        let tag = args
+            .strip_prefix('(')
+            .and_then(|args| args.strip_suffix(')'))
+            .and_then(|args| {
+                args.split(',').find_map(|arg| {
+                    let (key, value) = arg.split_once('=')?;
+                    (key.trim() == "tag").then(|| value.trim())
+                })
+            })
+            .and_then(|tag| tag.parse().ok());
+        return match tag {
+            Some(tag) => Ok(Some(tag)),
+            None => Err(ProtoError {
+                path: path.to_string(),
+                kind: ProtoErrorKind::InvalidAttribute {
+                    attribute: attribute.to_string(),
+                },
+            }),
+        };
+    }
+    Ok(None)
+}
+
+fn unsupported(path: &str, shape: &'static Shape) -> ProtoError {
+    ProtoError {
+        path: path.to_string(),
+        kind: ProtoErrorKind::Unsupported { shape },
+    }
+}
+
+/// `Page<User>` is declared as `PageUser`
+fn type_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    let mut out = String::with_capacity(name.len());
+    for word in name.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    out
+}
+
+/// `InProgress` becomes `in_progress`
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// `InProgress` becomes `IN_PROGRESS`
+fn screaming_snake_case(name: &str) -> String {
+    snake_case(name).to_uppercase()
+}
+
+fn write_doc(out: &mut String, doc: &[&str], indent: &str) {
+    for line in doc {
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        match line.is_empty() {
+            true => writeln!(out, "{indent}//").unwrap(),
+            false => writeln!(out, "{indent}// {line}").unwrap(),
+        }
+    }
+}
+
+/// An error encountered while generating a `.proto` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoError {
+    /// The type, field or variant in question, as `Type.field`
+    pub path: String,
+    /// Type of error
+    pub kind: ProtoErrorKind,
+}
+
+impl core::fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            ProtoErrorKind::MissingTag => {
+                write!(f, "No field number: add #[facet(proto(tag = N))]")?
+            }
+            ProtoErrorKind::InvalidAttribute { attribute } => {
+                write!(f, "Invalid attribute: {attribute}")?
+            }
+            ProtoErrorKind::InvalidTag { tag } => write!(f, "Invalid field number {tag}")?,
+            ProtoErrorKind::DuplicateTag { tag } => {
+                write!(f, "Field number {tag} is already used")?
+            }
+            ProtoErrorKind::NoZeroValue => write!(f, "Enum has no variant with the value 0")?,
+            ProtoErrorKind::Unsupported { shape } => {
+                write!(f, "{shape} has no Protocol Buffers equivalent")?
+            }
+        }
+        write!(f, " (at {})", self.path)
+    }
+}
+
+impl core::error::Error for ProtoError {}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ProtoErrorKind {
+    /// A field or data-carrying variant has no `#[facet(proto(tag = N))]` attribute
+    MissingTag,
+
+    /// A `proto` attribute isn't of the form `proto(tag = N)`
+    InvalidAttribute {
+        /// The attribute, as written
+        attribute: String,
+    },
+
+    /// A field number is out of range, or in the range reserved by Protocol Buffers
+    InvalidTag {
+        /// The field number
+        tag: i64,
+    },
+
+    /// Two fields of a message, or two values of an enum, have the same number
+    DuplicateTag {
+        /// The field number
+        tag: i64,
+    },
+
+    /// An enum has no value 0, which proto3 requires
+    NoZeroValue,
+
+    /// A type has no equivalent: tuples, 128-bit integers, nested lists…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+}
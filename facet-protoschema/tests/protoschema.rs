@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_protoschema::{Generator, ProtoErrorKind, to_proto};
+
+/// A registered user
+#[derive(Facet)]
+struct User {
+    #[facet(proto(tag = 1))]
+    id: u64,
+    /// Shown on the profile page
+    #[facet(proto(tag = 2))]
+    name: String,
+    #[facet(proto(tag = 3))]
+    email: Option<String>,
+    #[facet(proto(tag = 4))]
+    role: Role,
+    #[facet(proto(tag = 5))]
+    tags: Vec<String>,
+    #[facet(proto(tag = 6))]
+    avatar: Vec<u8>,
+    #[facet(proto(tag = 7))]
+    scores: HashMap<String, f64>,
+    #[facet(proto(tag = 8))]
+    last_event: Option<Event>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Role {
+    Member,
+    Admin,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Event {
+    #[facet(proto(tag = 1))]
+    Login {
+        #[facet(proto(tag = 1))]
+        at: u64,
+    },
+    #[facet(proto(tag = 2))]
+    Message(String),
+    #[facet(proto(tag = 3))]
+    Logout,
+}
+
+#[test]
+fn messages_and_enums() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        to_proto::<User>("accounts").unwrap(),
+        r#"syntax = "proto3";
+
+package accounts;
+
+// A registered user
+message User {
+  uint64 id = 1;
+  // Shown on the profile page
+  string name = 2;
+  optional string email = 3;
+  Role role = 4;
+  repeated string tags = 5;
+  bytes avatar = 6;
+  map<string, double> scores = 7;
+  optional Event last_event = 8;
+}
+
+enum Role {
+  ROLE_MEMBER = 0;
+  ROLE_ADMIN = 1;
+}
+
+message Event {
+  message Login {
+    uint64 at = 1;
+  }
+  message Logout {}
+  oneof value {
+    Login login = 1;
+    string message = 2;
+    Logout logout = 3;
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn fields_need_valid_tags() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Untagged {
+        #[facet(proto(tag = 1))]
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Facet)]
+    struct Duplicated {
+        #[facet(proto(tag = 1))]
+        id: u32,
+        #[facet(proto(tag = 1))]
+        name: String,
+    }
+
+    #[derive(Facet)]
+    struct Reserved {
+        #[facet(proto(tag = 19000))]
+        id: u32,
+    }
+
+    let err = Generator::new().add::<Untagged>().finish().unwrap_err();
+    assert_eq!(err.kind, ProtoErrorKind::MissingTag);
+    assert_eq!(err.path, "Untagged.name");
+
+    let err = Generator::new().add::<Duplicated>().finish().unwrap_err();
+    assert_eq!(err.kind, ProtoErrorKind::DuplicateTag { tag: 1 });
+    assert_eq!(err.path, "Duplicated.name");
+
+    let err = Generator::new().add::<Reserved>().finish().unwrap_err();
+    assert_eq!(err.kind, ProtoErrorKind::InvalidTag { tag: 19000 });
+}
+
+#[test]
+fn enums_need_a_zero_value() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Status {
+        #[facet(proto(tag = 1))]
+        Active,
+        #[facet(proto(tag = 2))]
+        Suspended,
+    }
+
+    let err = Generator::new().add::<Status>().finish().unwrap_err();
+    assert_eq!(err.kind, ProtoErrorKind::NoZeroValue);
+    assert_eq!(err.path, "Status");
+}
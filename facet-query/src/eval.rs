@@ -0,0 +1,212 @@
+use core::cmp::Ordering;
+
+use facet_core::{Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType, find_field};
+
+use crate::parse::{CompareOp, Condition, Literal, Step};
+
+/// Runs `steps` over `input`, appending every match to `out`
+pub(crate) fn run<'mem, 'facet>(
+    steps: &[Step],
+    input: Peek<'mem, 'facet>,
+    out: &mut Vec<Peek<'mem, 'facet>>,
+) {
+    let Some((step, rest)) = steps.split_first() else {
+        out.push(input);
+        return;
+    };
+
+    let mut matches = Vec::new();
+    apply(step, input, &mut matches);
+    for peek in matches {
+        run(rest, peek, out);
+    }
+}
+
+fn apply<'mem, 'facet>(step: &Step, input: Peek<'mem, 'facet>, out: &mut Vec<Peek<'mem, 'facet>>) {
+    match step {
+        Step::Field(name) => {
+            if let Ok(peek) = input.unwrap_wrappers() {
+                out.extend(field(peek, name));
+            }
+        }
+        Step::Index(index) => {
+            let items = elements(input);
+            if let Some(index) = resolve_index(*index, items.len()) {
+                out.push(items[index]);
+            }
+        }
+        Step::Slice(start, end) => {
+            let items = elements(input);
+            let len = items.len();
+            let start = start.map_or(0, |i| clamp_index(i, len));
+            let end = end.map_or(len, |i| clamp_index(i, len));
+            if start < end {
+                out.extend_from_slice(&items[start..end]);
+            }
+        }
+        Step::Children => children(input, out),
+        Step::Descendants => {
+            let mut stack = vec![input];
+            while let Some(peek) = stack.pop() {
+                out.push(peek);
+                let mut kids = Vec::new();
+                children(peek, &mut kids);
+                stack.extend(kids.into_iter().rev());
+            }
+        }
+        Step::Select(condition) => {
+            if holds(condition, input) {
+                out.push(input);
+            }
+        }
+    }
+}
+
+fn field<'mem, 'facet>(peek: Peek<'mem, 'facet>, name: &str) -> Option<Peek<'mem, 'facet>> {
+    if let Ok(map) = peek.into_map() {
+        return map
+            .iter()
+            .find(|(key, _)| match key.as_str() {
+                Some(s) => s == name,
+                None => key.vtable().display.is_some() && key.to_string() == name,
+            })
+            .map(|(_, value)| value);
+    }
+    match peek.shape().ty {
+        Type::User(UserType::Struct(_)) => find_field(peek.into_struct().ok()?.fields(), name),
+        Type::User(UserType::Enum(_)) => find_field(peek.into_enum().ok()?.fields(), name),
+        _ => None,
+    }
+}
+
+/// Elements of a list-like value or a tuple, in order
+fn elements<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Vec<Peek<'mem, 'facet>> {
+    let Ok(peek) = peek.unwrap_wrappers() else {
+        return Vec::new();
+    };
+    if let Ok(list) = peek.into_list_like() {
+        list.iter().collect()
+    } else if let Ok(tuple) = peek.into_tuple() {
+        tuple.fields().map(|(_, p)| p).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+fn children<'mem, 'facet>(peek: Peek<'mem, 'facet>, out: &mut Vec<Peek<'mem, 'facet>>) {
+    let Ok(peek) = peek.unwrap_wrappers() else {
+        return;
+    };
+    if let Ok(map) = peek.into_map() {
+        out.extend(map.iter().map(|(_, value)| value));
+        return;
+    }
+    let items = elements(peek);
+    if !items.is_empty() {
+        out.extend(items);
+        return;
+    }
+    match peek.shape().ty {
+        Type::User(UserType::Struct(_)) => {
+            if let Ok(ps) = peek.into_struct() {
+                out.extend(ps.fields_for_serialize().map(|(_, p)| p));
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            if let Ok(pe) = peek.into_enum() {
+                out.extend(pe.fields_for_serialize().map(|(_, p)| p));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_index(index: isize, len: usize) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(index.unsigned_abs())?
+    } else {
+        index as usize
+    };
+    (index < len).then_some(index)
+}
+
+fn clamp_index(index: isize, len: usize) -> usize {
+    if index < 0 {
+        len.saturating_sub(index.unsigned_abs())
+    } else {
+        (index as usize).min(len)
+    }
+}
+
+fn holds(condition: &Condition, input: Peek<'_, '_>) -> bool {
+    match condition {
+        Condition::Or(a, b) => holds(a, input) || holds(b, input),
+        Condition::And(a, b) => holds(a, input) && holds(b, input),
+        Condition::Truthy(path) => {
+            let mut matches = Vec::new();
+            run(path, input, &mut matches);
+            matches.into_iter().any(|peek| {
+                // `None` is falsy
+                peek.unwrap_wrappers()
+                    .is_ok_and(|peek| peek.get::<bool>().ok() != Some(&false))
+            })
+        }
+        Condition::Compare { path, op, rhs } => {
+            let mut matches = Vec::new();
+            run(path, input, &mut matches);
+            matches.into_iter().any(|peek| {
+                let ordering = compare(peek, rhs);
+                match op {
+                    CompareOp::Eq => ordering == Some(Ordering::Equal),
+                    CompareOp::Ne => ordering != Some(Ordering::Equal),
+                    CompareOp::Lt => ordering == Some(Ordering::Less),
+                    CompareOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    CompareOp::Gt => ordering == Some(Ordering::Greater),
+                    CompareOp::Ge => {
+                        matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+                    }
+                }
+            })
+        }
+    }
+}
+
+/// Compares a value against a literal; `None` if they can't be compared
+fn compare(peek: Peek<'_, '_>, literal: &Literal) -> Option<Ordering> {
+    let Ok(peek) = peek.unwrap_wrappers() else {
+        // Only `None` fails to unwrap
+        return matches!(literal, Literal::Null).then_some(Ordering::Equal);
+    };
+    match literal {
+        Literal::Null => None,
+        Literal::Bool(b) => peek.get::<bool>().ok().map(|v| v.cmp(b)),
+        Literal::String(s) => peek.as_str().map(|v| v.cmp(s.as_str())),
+        Literal::Number(n) => as_f64(peek)?.partial_cmp(n),
+    }
+}
+
+fn as_f64(peek: Peek<'_, '_>) -> Option<f64> {
+    macro_rules! num {
+        ($ty:ty) => {
+            peek.get::<$ty>().ok().map(|v| *v as f64)
+        };
+    }
+    match peek.scalar_type()? {
+        ScalarType::U8 => num!(u8),
+        ScalarType::U16 => num!(u16),
+        ScalarType::U32 => num!(u32),
+        ScalarType::U64 => num!(u64),
+        ScalarType::U128 => num!(u128),
+        ScalarType::USize => num!(usize),
+        ScalarType::I8 => num!(i8),
+        ScalarType::I16 => num!(i16),
+        ScalarType::I32 => num!(i32),
+        ScalarType::I64 => num!(i64),
+        ScalarType::I128 => num!(i128),
+        ScalarType::ISize => num!(isize),
+        ScalarType::F32 => num!(f32),
+        ScalarType::F64 => num!(f64),
+        _ => None,
+    }
+}
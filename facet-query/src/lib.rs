@@ -0,0 +1,80 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+mod eval;
+mod parse;
+
+/// A parsed query, ready to be evaluated against any number of values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    steps: Vec<parse::Step>,
+}
+
+impl Query {
+    /// Parses a query such as `.users[] | select(.age > 30) | .name`
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let steps = parse::Parser::new(input).parse_pipeline()?;
+        Ok(Self { steps })
+    }
+
+    /// Evaluates the query against a value, returning every match in order
+    pub fn eval<'mem, 'facet>(&self, peek: Peek<'mem, 'facet>) -> Vec<Peek<'mem, 'facet>> {
+        let mut out = Vec::new();
+        eval::run(&self.steps, peek, &mut out);
+        out
+    }
+}
+
+/// Parses `query` and evaluates it against `value`
+pub fn query<'mem, 'facet, T: Facet<'facet>>(
+    value: &'mem T,
+    query: &str,
+) -> Result<Vec<Peek<'mem, 'facet>>, QueryError> {
+    Ok(Query::parse(query)?.eval(Peek::new(value)))
+}
+
+/// Error parsing a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Byte offset into the query where the error was detected
+    pub offset: usize,
+    /// Type of error
+    pub kind: QueryErrorKind,
+}
+
+impl core::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.offset)
+    }
+}
+
+impl core::error::Error for QueryError {}
+
+/// Type of error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryErrorKind {
+    /// Something else was expected here
+    Expected(&'static str),
+    /// A number (index or literal) could not be parsed
+    InvalidNumber,
+    /// A string literal was never closed
+    UnterminatedString,
+    /// The query was complete, but more input followed
+    TrailingInput,
+}
+
+impl core::fmt::Display for QueryErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            QueryErrorKind::Expected(what) => write!(f, "expected {what}"),
+            QueryErrorKind::InvalidNumber => write!(f, "invalid number"),
+            QueryErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            QueryErrorKind::TrailingInput => write!(f, "unexpected trailing input"),
+        }
+    }
+}
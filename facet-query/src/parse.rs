@@ -0,0 +1,369 @@
+use crate::{QueryError, QueryErrorKind};
+
+/// A single step of a pipeline — each one maps every input value to zero or more outputs
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Step {
+    /// `.name` / `."name"`
+    Field(String),
+    /// `.[n]`
+    Index(isize),
+    /// `.[a:b]`
+    Slice(Option<isize>, Option<isize>),
+    /// `.[]` / `.*`
+    Children,
+    /// `..`
+    Descendants,
+    /// `select(cond)`
+    Select(Condition),
+}
+
+/// A condition, as found inside `select(...)`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Condition {
+    Or(Box<Condition>, Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    /// A path compared against a literal
+    Compare {
+        path: Vec<Step>,
+        op: CompareOp,
+        rhs: Literal,
+    },
+    /// A bare path: true if it matches anything that isn't `false` or `None`
+    Truthy(Vec<Step>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+pub(crate) struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn err(&self, kind: QueryErrorKind) -> QueryError {
+        QueryError {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    /// Consumes `token` (after whitespace) if it comes next
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes a keyword, making sure it isn't just the prefix of an identifier
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.starts_with(keyword)
+            && !rest[keyword.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_ident_char)
+        {
+            self.pos += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &'static str) -> Result<(), QueryError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.err(QueryErrorKind::Expected(token)))
+        }
+    }
+
+    /// pipeline := stage ('|' stage)*
+    pub(crate) fn parse_pipeline(&mut self) -> Result<Vec<Step>, QueryError> {
+        let mut steps = Vec::new();
+        loop {
+            self.parse_stage(&mut steps)?;
+            if !self.eat("|") {
+                break;
+            }
+        }
+        self.skip_whitespace();
+        if self.pos != self.input.len() {
+            return Err(self.err(QueryErrorKind::TrailingInput));
+        }
+        Ok(steps)
+    }
+
+    /// stage := 'select' '(' condition ')' | path
+    fn parse_stage(&mut self, steps: &mut Vec<Step>) -> Result<(), QueryError> {
+        if self.eat_keyword("select") {
+            self.expect("(")?;
+            let condition = self.parse_or()?;
+            self.expect(")")?;
+            steps.push(Step::Select(condition));
+            Ok(())
+        } else {
+            self.parse_path(steps)
+        }
+    }
+
+    /// path := '.' segment? suffix* | '..' suffix*
+    fn parse_path(&mut self, steps: &mut Vec<Step>) -> Result<(), QueryError> {
+        self.skip_whitespace();
+        if !self.eat(".") {
+            return Err(self.err(QueryErrorKind::Expected("'.'")));
+        }
+        // Right after the leading dot, a segment may follow without another dot
+        // (`.name`, `.[0]`, `.*`, `..`)
+        self.parse_segment(steps, true)?;
+        loop {
+            match self.peek_char() {
+                Some('.') => {
+                    self.pos += 1;
+                    if !self.parse_segment(steps, false)? {
+                        return Err(self.err(QueryErrorKind::Expected("a field name")));
+                    }
+                }
+                Some('[') => {
+                    self.parse_segment(steps, false)?;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Parses what follows a dot. Returns false if nothing did (a lone `.`).
+    fn parse_segment(
+        &mut self,
+        steps: &mut Vec<Step>,
+        after_root: bool,
+    ) -> Result<bool, QueryError> {
+        match self.peek_char() {
+            Some('.') if after_root => {
+                self.pos += 1;
+                steps.push(Step::Descendants);
+                // `..name` is shorthand for `.. | .name`
+                self.parse_segment(steps, false)?;
+                Ok(true)
+            }
+            Some('*') => {
+                self.pos += 1;
+                steps.push(Step::Children);
+                Ok(true)
+            }
+            Some('[') => {
+                self.pos += 1;
+                steps.push(self.parse_bracket()?);
+                Ok(true)
+            }
+            Some('"') => {
+                steps.push(Step::Field(self.parse_string()?));
+                Ok(true)
+            }
+            Some(c) if is_ident_char(c) => {
+                let len = self
+                    .rest()
+                    .find(|c: char| !is_ident_char(c))
+                    .unwrap_or(self.rest().len());
+                steps.push(Step::Field(self.rest()[..len].to_string()));
+                self.pos += len;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// After a `[`: `]`, `n]`, `a:b]`, or `"key"]`
+    fn parse_bracket(&mut self) -> Result<Step, QueryError> {
+        if self.eat("]") {
+            return Ok(Step::Children);
+        }
+        self.skip_whitespace();
+        if self.peek_char() == Some('"') {
+            let key = self.parse_string()?;
+            self.expect("]")?;
+            return Ok(Step::Field(key));
+        }
+
+        let start = self.parse_int()?;
+        if self.eat(":") {
+            let end = self.parse_int()?;
+            self.expect("]")?;
+            return Ok(Step::Slice(start, end));
+        }
+        self.expect("]")?;
+        match start {
+            Some(index) => Ok(Step::Index(index)),
+            None => Err(self.err(QueryErrorKind::Expected("an index"))),
+        }
+    }
+
+    fn parse_int(&mut self) -> Result<Option<isize>, QueryError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let digits_start = usize::from(rest.starts_with('-'));
+        let len = rest[digits_start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - digits_start);
+        if len == 0 {
+            return Ok(None);
+        }
+        let text = &rest[..digits_start + len];
+        let value = text
+            .parse()
+            .map_err(|_| self.err(QueryErrorKind::InvalidNumber))?;
+        self.pos += text.len();
+        Ok(Some(value))
+    }
+
+    fn parse_string(&mut self) -> Result<String, QueryError> {
+        self.skip_whitespace();
+        if !self.eat("\"") {
+            return Err(self.err(QueryErrorKind::Expected("'\"'")));
+        }
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, c)) => out.push(c),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        self.pos = self.input.len();
+        Err(self.err(QueryErrorKind::UnterminatedString))
+    }
+
+    /// or := and ('or' and)*
+    fn parse_or(&mut self) -> Result<Condition, QueryError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_keyword("or") {
+            let rhs = self.parse_and()?;
+            lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// and := atom ('and' atom)*
+    fn parse_and(&mut self) -> Result<Condition, QueryError> {
+        let mut lhs = self.parse_atom()?;
+        while self.eat_keyword("and") {
+            let rhs = self.parse_atom()?;
+            lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// atom := '(' or ')' | path (op literal)?
+    fn parse_atom(&mut self) -> Result<Condition, QueryError> {
+        if self.eat("(") {
+            let inner = self.parse_or()?;
+            self.expect(")")?;
+            return Ok(inner);
+        }
+
+        let mut path = Vec::new();
+        self.parse_path(&mut path)?;
+
+        // Two-character operators first, so `<=` isn't read as `<`
+        let op = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat(token))
+        .map(|(_, op)| op);
+
+        match op {
+            Some(op) => Ok(Condition::Compare {
+                path,
+                op,
+                rhs: self.parse_literal()?,
+            }),
+            None => Ok(Condition::Truthy(path)),
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, QueryError> {
+        self.skip_whitespace();
+        if self.peek_char() == Some('"') {
+            return Ok(Literal::String(self.parse_string()?));
+        }
+        for (keyword, literal) in [
+            ("null", Literal::Null),
+            ("true", Literal::Bool(true)),
+            ("false", Literal::Bool(false)),
+        ] {
+            if self.eat_keyword(keyword) {
+                return Ok(literal);
+            }
+        }
+
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.err(QueryErrorKind::Expected("a literal")));
+        }
+        let number = self.rest()[..len]
+            .parse()
+            .map_err(|_| self.err(QueryErrorKind::InvalidNumber))?;
+        self.pos += len;
+        Ok(Literal::Number(number))
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_query::{Query, QueryErrorKind, query};
+
+#[derive(Facet)]
+struct Team {
+    name: String,
+    members: Vec<Member>,
+    lead: Option<Member>,
+    labels: BTreeMap<String, u32>,
+}
+
+#[derive(Facet)]
+struct Member {
+    name: String,
+    age: u32,
+    active: bool,
+}
+
+fn team() -> Team {
+    let member = |name: &str, age, active| Member {
+        name: name.to_string(),
+        age,
+        active,
+    };
+    Team {
+        name: "core".to_string(),
+        members: vec![
+            member("alice", 34, true),
+            member("bob", 28, false),
+            member("carol", 41, true),
+        ],
+        lead: Some(member("dave", 50, true)),
+        labels: BTreeMap::from([("p0".to_string(), 3), ("p1".to_string(), 7)]),
+    }
+}
+
+fn strings(query_str: &str) -> Vec<String> {
+    let team = team();
+    query(&team, query_str)
+        .unwrap()
+        .into_iter()
+        .map(|p| p.as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn field_index_and_slices() {
+    facet_testhelpers::setup();
+
+    assert_eq!(strings(".name"), ["core"]);
+    assert_eq!(strings(".members[1].name"), ["bob"]);
+    assert_eq!(strings(".members[-1].name"), ["carol"]);
+    assert_eq!(strings(".members[0:2] | .name"), ["alice", "bob"]);
+    assert_eq!(strings(".members[5].name"), Vec::<String>::new());
+    assert_eq!(strings(".lead.name"), ["dave"]);
+    assert_eq!(strings(".\"name\""), ["core"]);
+}
+
+#[test]
+fn wildcards_and_filters() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        strings(".members[] | select(.age > 30) | .name"),
+        ["alice", "carol"]
+    );
+    assert_eq!(
+        strings(".members[] | select(.active and .age < 40) | .name"),
+        ["alice"]
+    );
+    assert_eq!(
+        strings(".members.* | select(.name == \"bob\" or .age >= 41) | .name"),
+        ["bob", "carol"]
+    );
+
+    let team = team();
+    let labels = query(&team, ".labels[]").unwrap();
+    let labels: Vec<u32> = labels.iter().map(|p| *p.get::<u32>().unwrap()).collect();
+    assert_eq!(labels, [3, 7]);
+
+    let q = Query::parse("..name").unwrap();
+    let names = q.eval(facet_reflect::Peek::new(&team));
+    assert_eq!(names.len(), 5);
+}
+
+#[test]
+fn parse_errors() {
+    facet_testhelpers::setup();
+
+    let err = Query::parse("name").unwrap_err();
+    assert_eq!(err.kind, QueryErrorKind::Expected("'.'"));
+    assert_eq!(err.offset, 0);
+
+    let err = Query::parse(".members[").unwrap_err();
+    assert_eq!(err.offset, 9);
+
+    let err = Query::parse(".a | select(.b == \"x)").unwrap_err();
+    assert_eq!(err.kind, QueryErrorKind::UnterminatedString);
+
+    let err = Query::parse(".a )").unwrap_err();
+    assert_eq!(err.kind, QueryErrorKind::TrailingInput);
+}
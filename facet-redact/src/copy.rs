@@ -0,0 +1,231 @@
+use std::borrow::Cow;
+
+use facet_core::{Def, Field, FieldFlags, SequenceType, Shape, Type, UserType};
+use facet_reflect::{HasFields, HeapValue, Peek, ScalarType, Wip};
+
+use crate::{REDACTED, RedactError, RedactErrorKind, Redactor};
+
+pub(crate) struct Copier<'r> {
+    redactor: &'r Redactor,
+    /// Path segments, unescaped
+    path: Vec<String>,
+}
+
+impl<'r> Copier<'r> {
+    pub(crate) fn new(redactor: &'r Redactor) -> Self {
+        Self {
+            redactor,
+            path: Vec::new(),
+        }
+    }
+
+    pub(crate) fn run<'facet>(
+        mut self,
+        peek: Peek<'_, 'facet>,
+    ) -> Result<HeapValue<'facet>, RedactError> {
+        let wip = self.copy(Wip::alloc_shape(peek.shape())?, peek, false)?;
+        Ok(wip.build()?)
+    }
+
+    /// Fills the current frame with a copy of `peek`, or a placeholder
+    fn copy<'facet>(
+        &mut self,
+        wip: Wip<'facet>,
+        peek: Peek<'_, 'facet>,
+        sensitive: bool,
+    ) -> Result<Wip<'facet>, RedactError> {
+        let res = if sensitive || self.redactor.matches(&self.path) {
+            placeholder(wip)
+        } else {
+            self.value(wip, peek)
+        };
+        // The innermost failure knows best where it happened
+        res.map_err(|mut e| {
+            if e.path.is_empty() {
+                e.path = pointer(&self.path);
+            }
+            e
+        })
+    }
+
+    fn value<'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        peek: Peek<'_, 'facet>,
+    ) -> Result<Wip<'facet>, RedactError> {
+        let shape = peek.shape();
+
+        match shape.def {
+            Def::Option(_) => {
+                return match peek.into_option()?.value() {
+                    Some(value) => Ok(self.copy(wip.push_some()?, value, false)?.pop()?),
+                    None => Ok(wip.put_default()?),
+                };
+            }
+            Def::List(_) | Def::Array(_) => {
+                let list = peek.into_list_like()?;
+                if list.is_empty() {
+                    if let Def::List(_) = shape.def {
+                        return Ok(wip.put_empty_list()?);
+                    }
+                }
+                wip = wip.begin_pushback()?;
+                for (i, item) in list.iter().enumerate() {
+                    self.path.push(i.to_string());
+                    wip = self.copy(wip.push()?, item, false)?.pop()?;
+                    self.path.pop();
+                }
+                return Ok(wip);
+            }
+            Def::Map(_) => {
+                let map = peek.into_map()?;
+                if map.is_empty() {
+                    return Ok(wip.put_empty_map()?);
+                }
+                wip = wip.begin_map_insert()?;
+                for (key, value) in map.iter() {
+                    let segment = match key.as_str() {
+                        Some(s) => s.to_string(),
+                        None => key.to_string(),
+                    };
+                    // Keys are never redacted: they're what paths are made of
+                    wip = self.value(wip.push_map_key()?, key)?;
+                    self.path.push(segment);
+                    wip = self.copy(wip.push_map_value()?, value, false)?.pop()?;
+                    self.path.pop();
+                }
+                return Ok(wip);
+            }
+            Def::Scalar(_) => return self.scalar(wip, peek),
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) => {
+                let ps = peek.into_struct()?;
+                self.fields(wip, ps.fields())
+            }
+            Type::User(UserType::Enum(_)) => {
+                let pe = peek.into_enum()?;
+                let index = pe.variant_index().map_err(|_| unsupported(shape))?;
+                self.fields(wip.variant(index)?, pe.fields())
+            }
+            Type::Sequence(SequenceType::Tuple(_)) => {
+                wip = wip.begin_pushback()?;
+                for (i, item) in peek.into_tuple()?.fields() {
+                    self.path.push(i.to_string());
+                    wip = self.copy(wip.push()?, item, false)?.pop()?;
+                    self.path.pop();
+                }
+                Ok(wip)
+            }
+            _ if shape.inner.is_some() => self.scalar(wip, peek),
+            _ => Err(unsupported(shape)),
+        }
+    }
+
+    /// Copies the fields of a struct or enum variant, redacting sensitive ones
+    fn fields<'mem, 'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+    ) -> Result<Wip<'facet>, RedactError> {
+        for (index, (field, value)) in fields.enumerate() {
+            // Flattened fields live in their parent, as far as paths go
+            let flatten = field.flags.contains(FieldFlags::FLATTEN);
+            if !flatten {
+                self.path.push(field.name.to_string());
+            }
+            let sensitive = field.flags.contains(FieldFlags::SENSITIVE);
+            wip = self.copy(wip.field(index)?, value, sensitive)?.pop()?;
+            if !flatten {
+                self.path.pop();
+            }
+        }
+        Ok(wip)
+    }
+
+    /// Copies a scalar — for transparent wrappers, the inner value, which the wrapper is then
+    /// converted from.
+    fn scalar<'facet>(
+        &mut self,
+        wip: Wip<'facet>,
+        peek: Peek<'_, 'facet>,
+    ) -> Result<Wip<'facet>, RedactError> {
+        let source = peek.innermost_peek();
+
+        macro_rules! clone {
+            ($ty:ty) => {
+                wip.put(source.get::<$ty>()?.clone())
+            };
+        }
+
+        let res = match ScalarType::try_from_shape(source.shape()) {
+            Some(ScalarType::Unit) => wip.put(()),
+            Some(ScalarType::Bool) => clone!(bool),
+            Some(ScalarType::Char) => clone!(char),
+            Some(ScalarType::String) => clone!(String),
+            Some(ScalarType::CowStr) => {
+                let s = source.as_str().unwrap_or_default().to_string();
+                wip.put(Cow::<str>::Owned(s))
+            }
+            Some(ScalarType::U8) => clone!(u8),
+            Some(ScalarType::U16) => clone!(u16),
+            Some(ScalarType::U32) => clone!(u32),
+            Some(ScalarType::U64) => clone!(u64),
+            Some(ScalarType::U128) => clone!(u128),
+            Some(ScalarType::USize) => clone!(usize),
+            Some(ScalarType::I8) => clone!(i8),
+            Some(ScalarType::I16) => clone!(i16),
+            Some(ScalarType::I32) => clone!(i32),
+            Some(ScalarType::I64) => clone!(i64),
+            Some(ScalarType::I128) => clone!(i128),
+            Some(ScalarType::ISize) => clone!(isize),
+            Some(ScalarType::F32) => clone!(f32),
+            Some(ScalarType::F64) => clone!(f64),
+            Some(ScalarType::SocketAddr) => clone!(core::net::SocketAddr),
+            Some(ScalarType::IpAddr) => clone!(core::net::IpAddr),
+            Some(ScalarType::Ipv4Addr) => clone!(core::net::Ipv4Addr),
+            Some(ScalarType::Ipv6Addr) => clone!(core::net::Ipv6Addr),
+            // Anything else that round-trips through text (UUIDs, paths…)
+            _ if source.vtable().display.is_some() && wip.shape().vtable.parse.is_some() => {
+                wip.parse(&source.to_string())
+            }
+            _ => return Err(unsupported(peek.shape())),
+        };
+        Ok(res?)
+    }
+}
+
+/// Fills the current frame with a placeholder
+fn placeholder(wip: Wip<'_>) -> Result<Wip<'_>, RedactError> {
+    let shape = wip.shape();
+    if let Def::Option(_) = shape.def {
+        return Ok(wip.put_default()?);
+    }
+    let res = match ScalarType::try_from_shape(wip.innermost_shape()) {
+        Some(ScalarType::String) => wip.put(REDACTED.to_string()),
+        Some(ScalarType::CowStr) => wip.put(Cow::<str>::Borrowed(REDACTED)),
+        _ if shape.vtable.default_in_place.is_some() => wip.put_default(),
+        _ => {
+            return Err(RedactError {
+                path: String::new(),
+                kind: RedactErrorKind::NoPlaceholder { shape },
+            });
+        }
+    };
+    Ok(res?)
+}
+
+fn unsupported(shape: &'static Shape) -> RedactError {
+    RedactError {
+        path: String::new(),
+        kind: RedactErrorKind::Unsupported { shape },
+    }
+}
+
+fn pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
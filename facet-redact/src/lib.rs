@@ -0,0 +1,138 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::{Facet, Shape};
+use facet_reflect::{HeapValue, Peek, ReflectError};
+
+mod copy;
+
+/// Placeholder for redacted strings
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Makes sanitized copies of values: fields marked `#[facet(sensitive)]`, as well as the
+/// values at the paths it's given, are replaced with placeholders
+///
+/// Placeholders are [`REDACTED`] for strings, `None` for options, and the default value for
+/// anything else (`0`, `false`, empty collections…).
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    patterns: Vec<Vec<String>>,
+}
+
+impl Redactor {
+    /// Creates a redactor that only redacts sensitive fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redacts the values at `pattern`, a JSON Pointer where `*` stands for any one
+    /// segment and `**` for any number of them: `/users/*/email`, `**/token`…
+    ///
+    /// Map keys are segments too, just like field names and list indices.
+    pub fn path(mut self, pattern: &str) -> Self {
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        self.patterns.push(
+            pattern
+                .split('/')
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect(),
+        );
+        self
+    }
+
+    /// Returns a sanitized copy of `value`
+    pub fn redact<'facet, T: Facet<'facet>>(&self, value: &T) -> Result<T, RedactError> {
+        Ok(self.redact_peek(Peek::new(value))?.materialize()?)
+    }
+
+    /// Returns a sanitized copy of a reflected value
+    pub fn redact_peek<'facet>(
+        &self,
+        peek: Peek<'_, 'facet>,
+    ) -> Result<HeapValue<'facet>, RedactError> {
+        copy::Copier::new(self).run(peek)
+    }
+
+    fn matches(&self, path: &[String]) -> bool {
+        self.patterns.iter().any(|pattern| matches(pattern, path))
+    }
+}
+
+fn matches(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((segment, rest)) if segment == "**" => {
+            (0..=path.len()).any(|skip| matches(rest, &path[skip..]))
+        }
+        Some((segment, rest)) => match path.split_first() {
+            Some((first, path)) => (segment == "*" || segment == first) && matches(rest, path),
+            None => false,
+        },
+    }
+}
+
+/// Returns a copy of `value` with its sensitive fields replaced with placeholders
+pub fn redact<'facet, T: Facet<'facet>>(value: &T) -> Result<T, RedactError> {
+    Redactor::new().redact(value)
+}
+
+/// An error encountered while making a sanitized copy
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactError {
+    /// Path to the value that couldn't be copied or redacted, as a JSON Pointer
+    pub path: String,
+    /// Type of error
+    pub kind: RedactErrorKind,
+}
+
+impl core::fmt::Display for RedactError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            RedactErrorKind::Unsupported { shape } => {
+                write!(f, "Don't know how to copy values of type {shape}")?
+            }
+            RedactErrorKind::NoPlaceholder { shape } => write!(
+                f,
+                "Can't redact a {shape}: it's not a string, and has no default value"
+            )?,
+            RedactErrorKind::Reflect(e) => write!(f, "{e}")?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " (at {})", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for RedactError {}
+
+impl From<ReflectError> for RedactError {
+    fn from(e: ReflectError) -> Self {
+        Self {
+            path: String::new(),
+            kind: RedactErrorKind::Reflect(e),
+        }
+    }
+}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RedactErrorKind {
+    /// The value (or one nested in it) can't be copied: borrowed data, smart pointers,
+    /// opaque types…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// The value at a redacted path has no placeholder
+    NoPlaceholder {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// Building the copy failed
+    Reflect(ReflectError),
+}
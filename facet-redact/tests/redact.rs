@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_redact::{REDACTED, RedactErrorKind, Redactor, redact};
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct Account {
+    name: String,
+    #[facet(sensitive)]
+    password: String,
+    #[facet(sensitive)]
+    pin: Option<u16>,
+    #[facet(sensitive)]
+    recovery_codes: Vec<String>,
+    sessions: Vec<Session>,
+    settings: HashMap<String, String>,
+}
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct Session {
+    ip: core::net::IpAddr,
+    #[facet(sensitive)]
+    token: String,
+    device: Option<String>,
+}
+
+fn account() -> Account {
+    Account {
+        name: "ana".to_string(),
+        password: "hunter2".to_string(),
+        pin: Some(1234),
+        recovery_codes: vec!["abcd-efgh".to_string()],
+        sessions: vec![Session {
+            ip: "192.0.2.1".parse().unwrap(),
+            token: "s3cr3t".to_string(),
+            device: Some("laptop".to_string()),
+        }],
+        settings: [
+            ("theme", "dark"),
+            ("webhook", "https://example.com/hook?key=k"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .into(),
+    }
+}
+
+#[test]
+fn sensitive_fields_are_replaced() {
+    facet_testhelpers::setup();
+
+    let original = account();
+    let redacted = redact(&original).unwrap();
+    assert_eq!(
+        redacted,
+        Account {
+            password: REDACTED.to_string(),
+            pin: None,
+            recovery_codes: vec![],
+            sessions: vec![Session {
+                token: REDACTED.to_string(),
+                ..original.sessions[0].clone()
+            }],
+            ..original.clone()
+        }
+    );
+    // The original is left untouched
+    assert_eq!(original, account());
+}
+
+#[test]
+fn paths_are_redacted_too() {
+    facet_testhelpers::setup();
+
+    let redacted = Redactor::new()
+        .path("/settings/webhook")
+        .path("/sessions/*/device")
+        .redact(&account())
+        .unwrap();
+    assert_eq!(redacted.settings["webhook"], REDACTED);
+    assert_eq!(redacted.settings["theme"], "dark");
+    assert_eq!(redacted.sessions[0].device, None);
+
+    let redacted = Redactor::new().path("**/theme").redact(&account()).unwrap();
+    assert_eq!(redacted.settings["theme"], REDACTED);
+    assert_eq!(redacted.sessions[0].device.as_deref(), Some("laptop"));
+}
+
+#[test]
+fn values_without_placeholders_are_errors() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Secret {
+        #[facet(sensitive)]
+        key: core::num::NonZeroU32,
+    }
+
+    let err = redact(&Secret {
+        key: core::num::NonZeroU32::new(7).unwrap(),
+    })
+    .unwrap_err();
+    assert_eq!(err.path, "/key");
+    assert!(matches!(err.kind, RedactErrorKind::NoPlaceholder { .. }));
+}
@@ -0,0 +1,159 @@
+use alloc::vec::Vec;
+
+use facet_core::{Def, Field, Type, UserType};
+
+use crate::{HasFields, Peek, PeekMap, PeekSet};
+
+/// Compares two values by their structure rather than their types
+///
+/// Structs and enum variants are compared field by field (by name and value, in
+/// declaration order), lists, arrays and slices element by element, maps entry by
+/// entry and sets value by value. Transparent wrappers and smart pointers are looked
+/// through first, so `Wrapper(3)` equals `3` and `Box::new(3)`.
+///
+/// Only scalars go through the vtable's `eq`, and only when both have the same shape:
+/// scalars of different shapes, or without `eq`, are never equal.
+pub fn deep_eq(a: Peek<'_, '_>, b: Peek<'_, '_>) -> bool {
+    // Pairs left to compare, so that deep values don't need a deep call stack
+    let mut stack = Vec::new();
+    stack.push((a, b));
+
+    while let Some((a, b)) = stack.pop() {
+        let (a, b) = (unwrap(a), unwrap(b));
+
+        let equal = match ((a.shape().def, a.shape().ty), (b.shape().def, b.shape().ty)) {
+            ((Def::Scalar(_), _), (Def::Scalar(_), _)) => {
+                a.shape() == b.shape() && a.eq(&b) == Some(true)
+            }
+            ((Def::Option(_), _), (Def::Option(_), _)) => {
+                match (
+                    a.into_option().unwrap().value(),
+                    b.into_option().unwrap().value(),
+                ) {
+                    (Some(a), Some(b)) => {
+                        stack.push((a, b));
+                        true
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+            (
+                (Def::List(_) | Def::Array(_) | Def::Slice(_), _),
+                (Def::List(_) | Def::Array(_) | Def::Slice(_), _),
+            ) => {
+                let (a, b) = (a.into_list_like().unwrap(), b.into_list_like().unwrap());
+                if a.len() == b.len() {
+                    stack.extend(a.iter().zip(b.iter()));
+                    true
+                } else {
+                    false
+                }
+            }
+            ((Def::Map(_), _), (Def::Map(_), _)) => {
+                let (a, b) = (a.into_map().unwrap(), b.into_map().unwrap());
+                a.len() == b.len()
+                    && a.iter().all(|(key, a_value)| match map_get(b, key) {
+                        Some(b_value) => {
+                            stack.push((a_value, b_value));
+                            true
+                        }
+                        None => false,
+                    })
+            }
+            ((Def::Set(_), _), (Def::Set(_), _)) => {
+                let (a, b) = (a.into_set().unwrap(), b.into_set().unwrap());
+                a.len() == b.len() && a.iter().all(|value| set_contains(b, value))
+            }
+            ((_, Type::User(UserType::Struct(_))), (_, Type::User(UserType::Struct(_)))) => {
+                let (a, b) = (a.into_struct().unwrap(), b.into_struct().unwrap());
+                a.field_count() == b.field_count()
+                    && push_fields(&mut stack, a.fields(), b.fields())
+            }
+            ((_, Type::User(UserType::Enum(_))), (_, Type::User(UserType::Enum(_)))) => {
+                let (a, b) = (a.into_enum().unwrap(), b.into_enum().unwrap());
+                match (a.active_variant(), b.active_variant()) {
+                    (Ok(a_variant), Ok(b_variant)) => {
+                        a_variant.name == b_variant.name
+                            && a_variant.data.fields.len() == b_variant.data.fields.len()
+                            && push_fields(&mut stack, a.fields(), b.fields())
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+
+        if !equal {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Looks up the value for a key, by structure if the key type differs from the map's
+pub(crate) fn map_get<'mem, 'facet>(
+    map: PeekMap<'mem, 'facet>,
+    key: Peek<'_, '_>,
+) -> Option<Peek<'mem, 'facet>> {
+    if key.shape() == map.def.k() {
+        // Same key type: let the map find it
+        let get_value_ptr = map.def.vtable.get_value_ptr_fn;
+        let ptr = unsafe { get_value_ptr(map.value.data(), key.data()) };
+        ptr.map(|ptr| unsafe { Peek::unchecked_new(ptr, map.def.v()) })
+    } else {
+        map.iter()
+            .find(|(map_key, _)| deep_eq(key, *map_key))
+            .map(|(_, value)| value)
+    }
+}
+
+/// Whether a set has a value, by structure if the value type differs from the set's
+pub(crate) fn set_contains(set: PeekSet<'_, '_>, value: Peek<'_, '_>) -> bool {
+    if value.shape() == set.def.t() {
+        let contains = set.def.vtable.contains_fn;
+        unsafe { contains(set.value.data(), value.data()) }
+    } else {
+        set.iter().any(|set_value| deep_eq(value, set_value))
+    }
+}
+
+/// Looks through transparent wrappers and smart pointers
+pub(crate) fn unwrap<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Peek<'mem, 'facet> {
+    let mut peek = peek;
+    loop {
+        peek = match peek.shape().def {
+            // Options have an inner shape too, but `None` has nothing to borrow
+            Def::Option(_) => return peek,
+            Def::SmartPointer(_) => match peek.into_smart_pointer().unwrap().borrow_inner() {
+                Some(inner) => inner,
+                None => return peek,
+            },
+            _ => match (peek.shape().vtable.try_borrow_inner, peek.shape().inner) {
+                (Some(try_borrow_inner), Some(inner)) => {
+                    match unsafe { try_borrow_inner(peek.data()) } {
+                        Ok(data) => unsafe { Peek::unchecked_new(data, inner.get()) },
+                        Err(_) => return peek,
+                    }
+                }
+                _ => return peek,
+            },
+        };
+    }
+}
+
+/// Queues up fields pairwise, as long as their names match
+fn push_fields<'a, 'b, 'fa, 'fb>(
+    stack: &mut Vec<(Peek<'a, 'fa>, Peek<'b, 'fb>)>,
+    a: impl Iterator<Item = (Field, Peek<'a, 'fa>)>,
+    b: impl Iterator<Item = (Field, Peek<'b, 'fb>)>,
+) -> bool {
+    for ((a_field, a_value), (b_field, b_value)) in a.zip(b) {
+        if a_field.name != b_field.name {
+            return false;
+        }
+        stack.push((a_value, b_value));
+    }
+    true
+}
@@ -0,0 +1,250 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_core::{Def, Type, UserType};
+
+use crate::deep_eq::{map_get, set_contains, unwrap};
+use crate::{HasFields, Peek, deep_eq};
+
+/// One difference between two values, at a path [`Peek::at`] understands
+#[derive(Debug)]
+pub enum Change<'mem, 'facet_lifetime> {
+    /// Only in the new value: a list element, map entry, set value or `Some`
+    Added {
+        /// Where the value is in the new value; for sets, the path of the set
+        path: String,
+        /// The added value
+        value: Peek<'mem, 'facet_lifetime>,
+    },
+    /// Only in the old value: a list element, map entry, set value or `Some`
+    Removed {
+        /// Where the value was in the old value; for sets, the path of the set
+        path: String,
+        /// The removed value
+        value: Peek<'mem, 'facet_lifetime>,
+    },
+    /// A leaf with a different value, or a value with a different structure
+    /// (another enum variant, another shape)
+    Changed {
+        /// Where the value is in both values
+        path: String,
+        /// The old value
+        old: Peek<'mem, 'facet_lifetime>,
+        /// The new value
+        new: Peek<'mem, 'facet_lifetime>,
+    },
+}
+
+impl Change<'_, '_> {
+    /// Where the change is; the empty path is the value itself
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } | Change::Removed { path, .. } => path,
+            Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl core::fmt::Display for Change<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let path = match self.path() {
+            "" => ".",
+            path => path,
+        };
+        match self {
+            Change::Added { value, .. } => write!(f, "+ {path}: {value:?}"),
+            Change::Removed { value, .. } => write!(f, "- {path}: {value:?}"),
+            Change::Changed { old, new, .. } => write!(f, "~ {path}: {old:?} -> {new:?}"),
+        }
+    }
+}
+
+/// The differences between two values, in the order they were found
+#[derive(Debug)]
+pub struct Diff<'mem, 'facet_lifetime> {
+    changes: Vec<Change<'mem, 'facet_lifetime>>,
+}
+
+impl<'mem, 'facet_lifetime> Diff<'mem, 'facet_lifetime> {
+    /// Whether the values are structurally equal
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The number of changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// The changes, in the order they were found
+    pub fn changes(&self) -> &[Change<'mem, 'facet_lifetime>] {
+        &self.changes
+    }
+
+    /// Takes the changes out
+    pub fn into_changes(self) -> Vec<Change<'mem, 'facet_lifetime>> {
+        self.changes
+    }
+}
+
+/// One change per line
+impl core::fmt::Display for Diff<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports the leaves that were added, removed or changed between two values
+///
+/// Values are walked the same way [`deep_eq`] compares them: structs and enum variants
+/// field by field, lists by index, maps by key and sets by value, looking through
+/// transparent wrappers and smart pointers. Leaves that aren't [`deep_eq`] become
+/// [`Change::Changed`], and so do values whose structure differs, such as enums with
+/// another variant active.
+///
+/// Paths are in the syntax of [`Peek::at`], e.g. `servers[0].address` or `env["HOME"]`.
+pub fn diff<'mem, 'facet_lifetime>(
+    old: Peek<'mem, 'facet_lifetime>,
+    new: Peek<'mem, 'facet_lifetime>,
+) -> Diff<'mem, 'facet_lifetime> {
+    let mut changes = Vec::new();
+    walk(&mut changes, String::new(), old, new);
+    Diff { changes }
+}
+
+fn walk<'mem, 'facet_lifetime>(
+    changes: &mut Vec<Change<'mem, 'facet_lifetime>>,
+    path: String,
+    old: Peek<'mem, 'facet_lifetime>,
+    new: Peek<'mem, 'facet_lifetime>,
+) {
+    let (old, new) = (unwrap(old), unwrap(new));
+
+    match (
+        (old.shape().def, old.shape().ty),
+        (new.shape().def, new.shape().ty),
+    ) {
+        ((Def::Option(_), _), (Def::Option(_), _)) => {
+            let (old_value, new_value) = (
+                old.into_option().unwrap().value(),
+                new.into_option().unwrap().value(),
+            );
+            let path = join(&path, "Some");
+            match (old_value, new_value) {
+                (Some(old), Some(new)) => walk(changes, path, old, new),
+                (None, Some(value)) => changes.push(Change::Added { path, value }),
+                (Some(value), None) => changes.push(Change::Removed { path, value }),
+                (None, None) => {}
+            }
+        }
+        (
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _),
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _),
+        ) => {
+            let (old, new) = (old.into_list_like().unwrap(), new.into_list_like().unwrap());
+            for index in 0..old.len().max(new.len()) {
+                let path = format!("{path}[{index}]");
+                match (old.get(index), new.get(index)) {
+                    (Some(old), Some(new)) => walk(changes, path, old, new),
+                    (None, Some(value)) => changes.push(Change::Added { path, value }),
+                    (Some(value), None) => changes.push(Change::Removed { path, value }),
+                    (None, None) => {}
+                }
+            }
+        }
+        ((Def::Map(_), _), (Def::Map(_), _)) => {
+            let (old, new) = (old.into_map().unwrap(), new.into_map().unwrap());
+            for (key, old_value) in old.iter() {
+                let path = join(&path, &key.to_string());
+                match map_get(new, key) {
+                    Some(new_value) => walk(changes, path, old_value, new_value),
+                    None => changes.push(Change::Removed {
+                        path,
+                        value: old_value,
+                    }),
+                }
+            }
+            for (key, value) in new.iter() {
+                if map_get(old, key).is_none() {
+                    let path = join(&path, &key.to_string());
+                    changes.push(Change::Added { path, value });
+                }
+            }
+        }
+        ((Def::Set(_), _), (Def::Set(_), _)) => {
+            let (old, new) = (old.into_set().unwrap(), new.into_set().unwrap());
+            for value in old.iter() {
+                if !set_contains(new, value) {
+                    let path = path.clone();
+                    changes.push(Change::Removed { path, value });
+                }
+            }
+            for value in new.iter() {
+                if !set_contains(old, value) {
+                    let path = path.clone();
+                    changes.push(Change::Added { path, value });
+                }
+            }
+        }
+        ((_, Type::User(UserType::Struct(_))), (_, Type::User(UserType::Struct(_))))
+            if same_fields(old.into_struct().unwrap(), new.into_struct().unwrap()) =>
+        {
+            let (old, new) = (old.into_struct().unwrap(), new.into_struct().unwrap());
+            for ((field, old), (_, new)) in old.fields().zip(new.fields()) {
+                walk(changes, join(&path, field.name), old, new);
+            }
+        }
+        ((_, Type::User(UserType::Enum(_))), (_, Type::User(UserType::Enum(_)))) => {
+            let (old_enum, new_enum) = (old.into_enum().unwrap(), new.into_enum().unwrap());
+            let same_variant = match (old_enum.active_variant(), new_enum.active_variant()) {
+                (Ok(old_variant), Ok(new_variant)) => {
+                    old_variant.name == new_variant.name && same_fields(old_enum, new_enum)
+                }
+                _ => false,
+            };
+            if same_variant {
+                for ((field, old), (_, new)) in old_enum.fields().zip(new_enum.fields()) {
+                    walk(changes, join(&path, field.name), old, new);
+                }
+            } else {
+                changes.push(Change::Changed { path, old, new });
+            }
+        }
+        _ => {
+            if !deep_eq(old, new) {
+                changes.push(Change::Changed { path, old, new });
+            }
+        }
+    }
+}
+
+/// Whether two structs or variants have the same field names, in the same order
+fn same_fields<'mem, 'facet_lifetime>(
+    old: impl HasFields<'mem, 'facet_lifetime>,
+    new: impl HasFields<'mem, 'facet_lifetime>,
+) -> bool {
+    let old = old.fields().map(|(field, _)| field.name);
+    let new = new.fields().map(|(field, _)| field.name);
+    old.eq(new)
+}
+
+/// Appends a field name or map key (as displayed) to a path, quoting it if it wouldn't
+/// parse as a name
+fn join(path: &str, name: &str) -> String {
+    if !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit()) {
+        format!("{path}[{name}]")
+    } else if !name.is_empty() && !name.contains(['.', '[', ']', '"', '\\']) {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        }
+    } else {
+        let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("{path}[\"{escaped}\"]")
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use facet_core::{Characteristic, EnumType, Field, FieldError, Shape, TryFromError};
 use owo_colors::OwoColorize;
 
@@ -141,6 +143,53 @@ pub enum ReflectError {
         /// The array size
         size: usize,
     },
+
+    /// A path passed to [`Peek::at`](crate::Peek::at) could not be parsed
+    InvalidPath {
+        /// The path
+        path: String,
+        /// Byte offset of the first character that doesn't fit
+        offset: usize,
+    },
+
+    /// A path passed to [`Peek::at`](crate::Peek::at) leads nowhere
+    PathNotFound {
+        /// The path
+        path: String,
+        /// The segment that could not be followed, e.g. `address` or `[3]`
+        segment: String,
+        /// The shape of the value the segment was applied to
+        shape: &'static Shape,
+    },
+
+    /// A validation function (`#[facet(validate = ...)]`) rejected a value
+    ValidationFailed {
+        /// The shape of the value, or of the struct or enum the field is in
+        shape: &'static Shape,
+        /// The field whose value was rejected, if it isn't the whole value
+        field_name: Option<&'static str>,
+        /// Why the value was rejected
+        message: String,
+    },
+
+    /// A conversion function (`#[facet(serialize_with = ...)]` or
+    /// `#[facet(deserialize_with = ...)]`) failed
+    ConversionFailed {
+        /// The shape of the value being converted from
+        src_shape: &'static Shape,
+        /// The shape of the value being converted to
+        dst_shape: &'static Shape,
+        /// Why the value couldn't be converted
+        message: String,
+    },
+
+    /// An invariants function (`#[facet(invariants = ...)]`) rejected a value
+    InvariantViolated {
+        /// The shape of the value
+        shape: &'static Shape,
+        /// Which invariant doesn't hold, or why
+        message: String,
+    },
 }
 
 impl core::fmt::Display for ReflectError {
@@ -261,6 +310,55 @@ impl core::fmt::Display for ReflectError {
                     size
                 )
             }
+            ReflectError::InvalidPath { path, offset } => {
+                write!(f, "Invalid path '{}' at offset {}", path.yellow(), offset)
+            }
+            ReflectError::PathNotFound {
+                path,
+                segment,
+                shape,
+            } => {
+                write!(
+                    f,
+                    "Path '{}' not found: no '{}' in '{}'",
+                    path.yellow(),
+                    segment.red(),
+                    shape.blue()
+                )
+            }
+            ReflectError::ValidationFailed {
+                shape,
+                field_name: Some(field_name),
+                message,
+            } => write!(
+                f,
+                "Field '{}::{}' is invalid: {}",
+                shape.blue(),
+                field_name.yellow(),
+                message.red()
+            ),
+            ReflectError::ValidationFailed {
+                shape,
+                field_name: None,
+                message,
+            } => write!(f, "Value '{}' is invalid: {}", shape.blue(), message.red()),
+            ReflectError::ConversionFailed {
+                src_shape,
+                dst_shape,
+                message,
+            } => write!(
+                f,
+                "Couldn't convert '{}' to '{}': {}",
+                src_shape.blue(),
+                dst_shape.green(),
+                message.red()
+            ),
+            ReflectError::InvariantViolated { shape, message } => write!(
+                f,
+                "Invariant of '{}' violated: {}",
+                shape.blue(),
+                message.red()
+            ),
         }
     }
 }
@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::std_instead_of_core)]
+#![warn(clippy::ptr_as_ptr)]
+#![warn(clippy::ptr_cast_constness)]
 #![warn(clippy::std_instead_of_alloc)]
 #![doc = include_str!("../README.md")]
 
@@ -17,9 +19,21 @@ pub use wip::*;
 mod peek;
 pub use peek::*;
 
+mod peek_mut;
+pub use peek_mut::*;
+
 mod scalar;
 pub use scalar::*;
 
+mod deep_eq;
+pub use deep_eq::*;
+
+mod diff;
+pub use diff::*;
+
+mod visit;
+pub use visit::*;
+
 #[cfg(feature = "log")]
 #[allow(unused_imports)]
 pub(crate) use log::{debug, trace};
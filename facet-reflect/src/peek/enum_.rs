@@ -85,6 +85,24 @@ impl<'mem, 'facet_lifetime> PeekEnum<'mem, 'facet_lifetime> {
         self.ty.variants.get(index).map(|variant| variant.name)
     }
 
+    /// Finds a variant by name (its serialized name, after renames), returning its index too
+    #[inline]
+    pub fn find_variant(self, name: &str) -> Option<(usize, &'static Variant)> {
+        let index = self.ty.variant_index(name)?;
+        Some((index, &self.ty.variants[index]))
+    }
+
+    /// Finds a variant by its discriminant value (e.g. `2` for `B = 2` in a `#[repr(u8)]` enum),
+    /// returning its index too
+    #[inline]
+    pub fn find_variant_by_discriminant(
+        self,
+        discriminant: i64,
+    ) -> Option<(usize, &'static Variant)> {
+        let index = self.ty.variant_index_for_discriminant(discriminant)?;
+        Some((index, &self.ty.variants[index]))
+    }
+
     /// Returns the discriminant value for the current enum value
     #[inline]
     pub fn discriminant(self) -> i64 {
@@ -175,15 +193,9 @@ impl<'mem, 'facet_lifetime> PeekEnum<'mem, 'facet_lifetime> {
                 discriminant, self.ty.enum_repr
             );
 
-            // Find the variant with matching discriminant using position method
             Ok(self
                 .ty
-                .variants
-                .iter()
-                .enumerate()
-                .position(|#[allow(unused)] (variant_idx, variant)| {
-                    variant.discriminant == Some(discriminant)
-                })
+                .variant_index_for_discriminant(discriminant)
                 .expect("No variant found with matching discriminant"))
         }
     }
@@ -222,11 +234,7 @@ impl<'mem, 'facet_lifetime> PeekEnum<'mem, 'facet_lifetime> {
     /// Returns the index of a field in the active variant by name
     pub fn field_index(self, field_name: &str) -> Result<Option<usize>, VariantError> {
         let variant = self.active_variant()?;
-        Ok(variant
-            .data
-            .fields
-            .iter()
-            .position(|f| f.name == field_name))
+        Ok(variant.data.field_index(field_name))
     }
 
     /// Returns a PeekValue handle to a field of a tuple or struct variant by name
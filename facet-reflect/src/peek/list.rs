@@ -66,21 +66,9 @@ impl<'mem, 'facet_lifetime> PeekList<'mem, 'facet_lifetime> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
-    /// Get an item from the list at the specified index
+    /// Get an item from the list at the specified index, without walking the ones before it
     pub fn get(&self, index: usize) -> Option<Peek<'mem, 'facet_lifetime>> {
-        if index >= self.len() {
-            return None;
-        }
-
-        let Ok(layout) = self.def.t().layout.sized_layout() else {
-            return None;
-        };
-
-        let data = unsafe { (self.def.vtable.as_ptr)(self.value.data()) };
-
-        // SAFETY: we verify index bounds at the start of the function
-        let item_ptr = unsafe { data.field(layout.size() * index) };
-
+        let item_ptr = unsafe { (self.def.vtable.get)(self.value.data(), index)? };
         Some(unsafe { Peek::unchecked_new(item_ptr, self.def.t()) })
     }
 
@@ -1,4 +1,4 @@
-use facet_core::{PtrConst, Shape, ShapeLayout};
+use facet_core::{PtrConst, Shape};
 
 use super::Peek;
 use core::fmt::Debug;
@@ -75,7 +75,7 @@ pub struct PeekListLike<'mem, 'facet_lifetime> {
     pub(crate) value: Peek<'mem, 'facet_lifetime>,
     pub(crate) def: ListLikeDef,
     len: usize,
-    as_ptr: unsafe fn(this: PtrConst) -> PtrConst,
+    get: unsafe fn(this: PtrConst, index: usize) -> Option<PtrConst>,
 }
 
 impl Debug for PeekListLike<'_, '_> {
@@ -87,16 +87,16 @@ impl Debug for PeekListLike<'_, '_> {
 impl<'mem, 'facet_lifetime> PeekListLike<'mem, 'facet_lifetime> {
     /// Creates a new peek list
     pub fn new(value: Peek<'mem, 'facet_lifetime>, def: ListLikeDef) -> Self {
-        let (len, as_ptr_fn) = match def {
-            ListLikeDef::List(v) => (unsafe { (v.vtable.len)(value.data()) }, v.vtable.as_ptr),
-            ListLikeDef::Slice(v) => (unsafe { (v.vtable.len)(value.data()) }, v.vtable.as_ptr),
-            ListLikeDef::Array(v) => (v.n, v.vtable.as_ptr),
+        let (len, get) = match def {
+            ListLikeDef::List(v) => (unsafe { (v.vtable.len)(value.data()) }, v.vtable.get),
+            ListLikeDef::Slice(v) => (unsafe { (v.vtable.len)(value.data()) }, v.vtable.get),
+            ListLikeDef::Array(v) => (v.n, v.vtable.get),
         };
         Self {
             value,
             def,
             len,
-            as_ptr: as_ptr_fn,
+            get,
         }
     }
 
@@ -110,31 +110,14 @@ impl<'mem, 'facet_lifetime> PeekListLike<'mem, 'facet_lifetime> {
         self.len() == 0
     }
 
-    /// Get an item from the list at the specified index
+    /// Get an item from the list at the specified index, without walking the ones before it.
     ///
-    /// # Panics
-    ///
-    /// Panics if the index is out of bounds
+    /// Returns `None` if the index is out of bounds.
     pub fn get(&self, index: usize) -> Option<Peek<'mem, 'facet_lifetime>> {
         if index >= self.len() {
             return None;
         }
-
-        // Get the base pointer of the array
-        let base_ptr = unsafe { (self.as_ptr)(self.value.data()) };
-
-        // Get the layout of the element type
-        let elem_layout = match self.def.t().layout {
-            ShapeLayout::Sized(layout) => layout,
-            ShapeLayout::Unsized => return None, // Cannot handle unsized elements
-        };
-
-        // Calculate the offset based on element size
-        let offset = index * elem_layout.size();
-
-        // Apply the offset to get the item's pointer
-        let item_ptr = unsafe { base_ptr.field(offset) };
-
+        let item_ptr = unsafe { (self.get)(self.value.data(), index)? };
         Some(unsafe { Peek::unchecked_new(item_ptr, self.def.t()) })
     }
 
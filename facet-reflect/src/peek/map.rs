@@ -1,3 +1,5 @@
+use alloc::{string::ToString, vec::Vec};
+use core::cmp::Ordering;
 use facet_core::{MapDef, PtrConst, PtrMut};
 
 use super::Peek;
@@ -89,14 +91,68 @@ impl<'mem, 'facet_lifetime> PeekMap<'mem, 'facet_lifetime> {
         }
     }
 
-    /// Returns an iterator over the key-value pairs in the map
+    /// Check if the map contains a key, given as a peek so its type needn't be known statically.
+    ///
+    /// Returns `false` if the key isn't of the map's key type.
+    pub fn contains_key_peek(&self, key: Peek<'_, 'facet_lifetime>) -> bool {
+        if key.shape() != self.def.k() {
+            return false;
+        }
+        unsafe { (self.def.vtable.contains_key_fn)(self.value.data(), key.data()) }
+    }
+
+    /// Get a value from the map for the given key, given as a peek so its type needn't be known
+    /// statically.
+    ///
+    /// Returns `None` if the key isn't of the map's key type.
+    pub fn get_peek(&self, key: Peek<'_, 'facet_lifetime>) -> Option<Peek<'mem, 'facet_lifetime>> {
+        if key.shape() != self.def.k() {
+            return None;
+        }
+        unsafe {
+            let value_ptr = (self.def.vtable.get_value_ptr_fn)(self.value.data(), key.data())?;
+            Some(Peek::unchecked_new(value_ptr, self.def.v()))
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs in the map, in whatever order the map keeps
+    /// them: for hash maps, that may change from one run to the next.
     pub fn iter(self) -> PeekMapIter<'mem, 'facet_lifetime> {
         let iter = unsafe { (self.def.vtable.iter_fn)(self.value.data()) };
         PeekMapIter { map: self, iter }
     }
 
+    /// Returns the key-value pairs in the map ordered by key, so that equal maps always yield
+    /// them in the same order.
+    ///
+    /// Keys are compared with their type's ordering if it has one, and by their string
+    /// representation otherwise.
+    pub fn iter_sorted(
+        self,
+    ) -> alloc::vec::IntoIter<(Peek<'mem, 'facet_lifetime>, Peek<'mem, 'facet_lifetime>)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        sort_by_peek(&mut entries, |(key, _)| *key);
+        entries.into_iter()
+    }
+
     /// Def getter
     pub fn def(&self) -> MapDef {
         self.def
     }
 }
+
+/// Sorts items by the value `key` peeks at in each: with its type's ordering if it has one, and
+/// by its string representation otherwise
+pub(crate) fn sort_by_peek<'mem, 'facet_lifetime, T>(
+    items: &mut [T],
+    key: impl Fn(&T) -> Peek<'mem, 'facet_lifetime>,
+) {
+    let Some(first) = items.first() else {
+        return;
+    };
+    if key(first).shape().vtable.partial_ord.is_some() {
+        items.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal));
+    } else {
+        items.sort_by_cached_key(|item| key(item).to_string());
+    }
+}
@@ -18,11 +18,25 @@ pub use list_like::*;
 mod map;
 pub use map::*;
 
+mod set;
+pub use set::*;
+
 mod option;
 pub use option::*;
 
+mod result;
+pub use result::*;
+
 mod smartptr;
 pub use smartptr::*;
 
 mod tuple;
 pub use tuple::*;
+
+mod pointer;
+pub use pointer::*;
+
+mod redacted;
+pub use redacted::*;
+
+mod path;
@@ -0,0 +1,186 @@
+use alloc::string::{String, ToString};
+
+use facet_core::{Def, Type, UserType};
+
+use crate::ReflectError;
+use crate::deep_eq::unwrap;
+
+use super::Peek;
+
+/// One step of a path: `.name`, `[3]` or `["key"]`
+#[derive(Debug, PartialEq)]
+enum Segment<'path> {
+    /// A struct field, variant or enum field, `Some`, or a map key
+    Name(&'path str),
+    /// A quoted map key, for keys that wouldn't parse as a name
+    Key(String),
+    /// A list, array or tuple element, or a map key
+    Index(usize),
+}
+
+impl core::fmt::Display for Segment<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Segment::Name(name) => write!(f, "{name}"),
+            Segment::Key(key) => write!(f, "[{key:?}]"),
+            Segment::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+/// Splits a path into segments, reporting the byte offset of anything it can't make sense of
+fn parse(path: &str) -> Result<alloc::vec::Vec<Segment<'_>>, usize> {
+    let mut segments = alloc::vec::Vec::new();
+    let bytes = path.as_bytes();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'[' => {
+                pos += 1;
+                if bytes.get(pos) == Some(&b'"') {
+                    // Quoted key: runs to the next unescaped quote
+                    let mut key = String::new();
+                    let mut chars = path[pos + 1..].char_indices();
+                    let end = loop {
+                        match chars.next() {
+                            Some((offset, '"')) => break pos + 1 + offset,
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c)) => key.push(c),
+                                None => return Err(path.len()),
+                            },
+                            Some((_, c)) => key.push(c),
+                            None => return Err(path.len()),
+                        }
+                    };
+                    if bytes.get(end + 1) != Some(&b']') {
+                        return Err(end + 1);
+                    }
+                    segments.push(Segment::Key(key));
+                    pos = end + 2;
+                } else {
+                    let len = path[pos..].find(']').ok_or(path.len())?;
+                    let inner = &path[pos..pos + len];
+                    let index = inner.parse().map_err(|_| pos)?;
+                    segments.push(Segment::Index(index));
+                    pos += len + 1;
+                }
+            }
+            b'.' if pos > 0 => {
+                pos += 1;
+                let len = path[pos..]
+                    .find(['.', '[', ']'])
+                    .unwrap_or(path.len() - pos);
+                if len == 0 {
+                    return Err(pos);
+                }
+                segments.push(Segment::Name(&path[pos..pos + len]));
+                pos += len;
+            }
+            b'.' | b']' => return Err(pos),
+            _ => {
+                // Only the first segment can go without a leading dot
+                if pos > 0 {
+                    return Err(pos);
+                }
+                let len = path.find(['.', '[', ']']).unwrap_or(path.len());
+                segments.push(Segment::Name(&path[..len]));
+                pos = len;
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+impl Peek<'_, '_> {
+    /// Follows a path down to a nested value, e.g. `"servers[0].address"`
+    ///
+    /// Each segment is one of:
+    ///   - `.name`: a struct field, a field of the active enum variant, a map key, or
+    ///     `Some` to look inside an option. Naming the active variant itself is also
+    ///     allowed, as in `.Circle.radius`, and fails if another variant is active.
+    ///   - `[3]`: an element of a list, array or tuple, a field of a tuple struct or variant,
+    ///     or a map key.
+    ///   - `["key"]`: a map key or field with any characters in it, with `\"` and `\\`
+    ///     escapes.
+    ///
+    /// Map keys match strings directly, and anything else through its `Display` impl.
+    /// Smart pointers and transparent wrappers are looked through, and fields of flattened
+    /// structs are found as if they were in the parent. An empty path returns this value.
+    pub fn at(self, path: &str) -> Result<Self, ReflectError> {
+        let segments = parse(path).map_err(|offset| ReflectError::InvalidPath {
+            path: path.to_string(),
+            offset,
+        })?;
+
+        let mut peek = self;
+        for segment in &segments {
+            peek = peek
+                .step(segment)
+                .ok_or_else(|| ReflectError::PathNotFound {
+                    path: path.to_string(),
+                    segment: segment.to_string(),
+                    shape: peek.shape,
+                })?;
+        }
+        Ok(peek)
+    }
+
+    /// Applies a single segment of a path, looking through smart pointers and transparent
+    /// wrappers like [`Peek::resolve_pointer`] does
+    fn step(self, segment: &Segment<'_>) -> Option<Self> {
+        let peek = unwrap(self);
+        let name = match segment {
+            Segment::Name(name) => *name,
+            Segment::Key(key) => key.as_str(),
+            Segment::Index(index) => return peek.child(&index.to_string()).ok(),
+        };
+
+        match (peek.shape.def, peek.shape.ty) {
+            (Def::Option(_), _) if name == "Some" => peek.into_option().ok()?.value(),
+            (_, Type::User(UserType::Enum(_)))
+                if peek.into_enum().ok()?.variant_name_active().ok()? == name =>
+            {
+                Some(peek)
+            }
+            _ => peek.child(name).ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Segment, parse};
+
+    #[test]
+    fn parses_segments() {
+        assert_eq!(parse(""), Ok(alloc::vec![]));
+        assert_eq!(
+            parse(r#"a.b[3].c["x.\"y\""][0]"#),
+            Ok(alloc::vec![
+                Segment::Name("a"),
+                Segment::Name("b"),
+                Segment::Index(3),
+                Segment::Name("c"),
+                Segment::Key("x.\"y\"".into()),
+                Segment::Index(0),
+            ])
+        );
+        assert_eq!(
+            parse("[1].0"),
+            Ok(alloc::vec![Segment::Index(1), Segment::Name("0")])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        assert_eq!(parse(".a"), Err(0));
+        assert_eq!(parse("a..b"), Err(2));
+        assert_eq!(parse("a[x]"), Err(2));
+        assert_eq!(parse("a[1"), Err(3));
+        assert_eq!(parse(r#"a["b"#), Err(4));
+        assert_eq!(parse("a]"), Err(1));
+        assert_eq!(parse("a[0]b"), Err(4));
+    }
+}
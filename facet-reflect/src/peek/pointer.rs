@@ -0,0 +1,240 @@
+//! [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer resolution over [`Peek`] values
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_core::{Def, FieldFlags, Shape, Type, UserType};
+
+use super::{HasFields, Peek};
+use crate::deep_eq::unwrap;
+
+/// An error encountered while resolving a JSON Pointer against a [`Peek`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerError {
+    /// Zero-based index of the segment that could not be resolved
+    pub segment_index: usize,
+
+    /// The segment that could not be resolved, unescaped
+    pub segment: String,
+
+    /// The shape of the value the segment was resolved against
+    pub shape: &'static Shape,
+
+    /// What went wrong
+    pub kind: PointerErrorKind,
+}
+
+/// The different ways resolving a JSON Pointer segment can fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PointerErrorKind {
+    /// The pointer is neither empty nor starts with `/`
+    MissingLeadingSlash,
+
+    /// The segment contains a `~` not followed by `0` or `1`
+    InvalidEscape,
+
+    /// The struct or enum variant has no field with that name
+    NoSuchField,
+
+    /// The map has no key that displays as the segment
+    NoSuchKey,
+
+    /// The segment is not a valid array index (digits only, no leading zeros)
+    InvalidIndex,
+
+    /// The index is past the end of the list, array, slice or tuple
+    IndexOutOfBounds {
+        /// The number of elements available
+        len: usize,
+    },
+
+    /// An `Option` along the way was `None`
+    OptionIsNone,
+
+    /// The value is a scalar (or otherwise opaque) and has no children to index into
+    NotTraversable,
+}
+
+impl core::fmt::Display for PointerErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PointerErrorKind::MissingLeadingSlash => write!(f, "pointer must start with '/'"),
+            PointerErrorKind::InvalidEscape => {
+                write!(f, "'~' must be followed by '0' or '1'")
+            }
+            PointerErrorKind::NoSuchField => write!(f, "no such field"),
+            PointerErrorKind::NoSuchKey => write!(f, "no such key"),
+            PointerErrorKind::InvalidIndex => write!(f, "not a valid index"),
+            PointerErrorKind::IndexOutOfBounds { len } => {
+                write!(f, "index out of bounds (length is {len})")
+            }
+            PointerErrorKind::OptionIsNone => write!(f, "option is None"),
+            PointerErrorKind::NotTraversable => write!(f, "value has no children"),
+        }
+    }
+}
+
+impl core::fmt::Display for PointerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Could not resolve segment #{} {:?} in {}: {}",
+            self.segment_index, self.segment, self.shape, self.kind
+        )
+    }
+}
+
+impl core::error::Error for PointerError {}
+
+impl Peek<'_, '_> {
+    /// Resolves a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) such as
+    /// `/users/0/name` against this value.
+    ///
+    /// Segments name struct fields, fields of the active enum variant, map keys
+    /// (compared against the key's string form), or indices into lists, arrays, slices
+    /// and tuples. Options, smart pointers and transparent wrappers are looked through
+    /// transparently, and flattened fields are found as if they were in the parent.
+    ///
+    /// The empty pointer `""` refers to the value itself.
+    pub fn resolve_pointer(self, pointer: &str) -> Result<Self, PointerError> {
+        if pointer.is_empty() {
+            return Ok(self);
+        }
+        let Some(rest) = pointer.strip_prefix('/') else {
+            return Err(PointerError {
+                segment_index: 0,
+                segment: pointer.to_string(),
+                shape: self.shape,
+                kind: PointerErrorKind::MissingLeadingSlash,
+            });
+        };
+
+        let mut current = self;
+        for (segment_index, raw) in rest.split('/').enumerate() {
+            let fail = |shape: &'static Shape, kind: PointerErrorKind| PointerError {
+                segment_index,
+                segment: unescape(raw).unwrap_or_else(|| raw.to_string()),
+                shape,
+                kind,
+            };
+            let segment = unescape(raw)
+                .ok_or_else(|| fail(current.shape, PointerErrorKind::InvalidEscape))?;
+
+            let container = current
+                .unwrap_wrappers()
+                .map_err(|kind| fail(current.shape, kind))?;
+            current = container
+                .child(&segment)
+                .map_err(|kind| fail(container.shape, kind))?;
+        }
+        Ok(current)
+    }
+
+    /// Looks through options, smart pointers and transparent wrappers, down to the value
+    /// they hold, as [`Peek::resolve_pointer`] does before each segment.
+    ///
+    /// Fails with [`PointerErrorKind::OptionIsNone`] if an option along the way is `None`.
+    pub fn unwrap_wrappers(self) -> Result<Self, PointerErrorKind> {
+        let mut current = unwrap(self);
+        while let Def::Option(_) = current.shape.def {
+            current = match current.into_option().unwrap().value() {
+                Some(inner) => unwrap(inner),
+                None => return Err(PointerErrorKind::OptionIsNone),
+            };
+        }
+        Ok(current)
+    }
+
+    /// Resolves a single, already-unescaped segment.
+    pub(crate) fn child(self, segment: &str) -> Result<Self, PointerErrorKind> {
+        if let Ok(list) = self.into_list_like() {
+            let index = parse_index(segment)?;
+            return list
+                .get(index)
+                .ok_or(PointerErrorKind::IndexOutOfBounds { len: list.len() });
+        }
+
+        if let Ok(map) = self.into_map() {
+            return map
+                .iter()
+                .find(|(key, _)| match key.as_str() {
+                    Some(s) => s == segment,
+                    None => key.vtable().display.is_some() && key.to_string() == segment,
+                })
+                .map(|(_, value)| value)
+                .ok_or(PointerErrorKind::NoSuchKey);
+        }
+
+        if let Ok(tuple) = self.into_tuple() {
+            let index = parse_index(segment)?;
+            return tuple
+                .field(index)
+                .ok_or(PointerErrorKind::IndexOutOfBounds { len: tuple.len() });
+        }
+
+        match self.shape.ty {
+            Type::User(UserType::Struct(_)) => {
+                find_field(self.into_struct().unwrap().fields(), segment)
+                    .ok_or(PointerErrorKind::NoSuchField)
+            }
+            Type::User(UserType::Enum(_)) => {
+                find_field(self.into_enum().unwrap().fields(), segment)
+                    .ok_or(PointerErrorKind::NoSuchField)
+            }
+            _ => Err(PointerErrorKind::NotTraversable),
+        }
+    }
+}
+
+/// Finds a field of a struct or enum variant by name, descending into flattened structs,
+/// whose fields are found as if they were in the parent
+pub fn find_field<'mem, 'facet_lifetime>(
+    fields: impl Iterator<Item = (facet_core::Field, Peek<'mem, 'facet_lifetime>)>,
+    name: &str,
+) -> Option<Peek<'mem, 'facet_lifetime>> {
+    // Direct fields take precedence over flattened ones
+    let mut flattened = Vec::new();
+    for (field, peek) in fields {
+        if field.flags.contains(FieldFlags::FLATTEN) {
+            flattened.push(peek);
+        } else if field.name == name {
+            return Some(peek);
+        }
+    }
+    flattened
+        .into_iter()
+        .find_map(|peek| match peek.into_struct() {
+            Ok(ps) => find_field(ps.fields(), name),
+            Err(_) => None,
+        })
+}
+
+/// Decodes `~1` to `/` and `~0` to `~`; returns `None` on any other escape
+fn unescape(segment: &str) -> Option<String> {
+    let mut out = String::with_capacity(segment.len());
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next() {
+                Some('0') => out.push('~'),
+                Some('1') => out.push('/'),
+                _ => return None,
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// Parses an array index as RFC 6901 defines it: `0`, or digits without a leading zero
+fn parse_index(segment: &str) -> Result<usize, PointerErrorKind> {
+    let valid = !segment.is_empty()
+        && segment.bytes().all(|b| b.is_ascii_digit())
+        && (segment == "0" || !segment.starts_with('0'));
+    if !valid {
+        return Err(PointerErrorKind::InvalidIndex);
+    }
+    segment.parse().map_err(|_| PointerErrorKind::InvalidIndex)
+}
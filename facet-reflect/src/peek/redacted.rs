@@ -0,0 +1,131 @@
+use alloc::string::ToString;
+use core::fmt;
+
+use facet_core::{Def, Field, FieldFlags, SequenceType, StructKind, Type, UserType};
+
+use super::{HasFields, Peek};
+use crate::deep_eq::unwrap;
+
+impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
+    /// Formats the value like its `Debug` implementation would, except that fields marked
+    /// `#[facet(sensitive)]` are printed as `[REDACTED]`, however deeply they're nested.
+    ///
+    /// Structs, enums, tuples, options and collections are walked through; other values are
+    /// printed with their own `Debug`.
+    pub fn debug_redacted(self) -> DebugRedacted<'mem, 'facet_lifetime> {
+        DebugRedacted(self)
+    }
+}
+
+/// A value formatted with `{:?}` without its sensitive fields — see [`Peek::debug_redacted`]
+#[derive(Clone, Copy)]
+pub struct DebugRedacted<'mem, 'facet_lifetime>(Peek<'mem, 'facet_lifetime>);
+
+/// What's printed in place of a sensitive field
+struct Placeholder;
+
+impl fmt::Debug for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl fmt::Debug for DebugRedacted<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let peek = self.0;
+        let shape = peek.shape();
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => fmt::Debug::fmt(&peek, f),
+            (Def::Option(_), _) => match peek.into_option().map_err(|_| fmt::Error)?.value() {
+                Some(value) => f
+                    .debug_tuple("Some")
+                    .field(&value.debug_redacted())
+                    .finish(),
+                None => f.write_str("None"),
+            },
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _) => {
+                let list = peek.into_list_like().map_err(|_| fmt::Error)?;
+                f.debug_list()
+                    .entries(list.iter().map(Peek::debug_redacted))
+                    .finish()
+            }
+            (Def::Map(_), _) => {
+                let map = peek.into_map().map_err(|_| fmt::Error)?;
+                f.debug_map()
+                    .entries(
+                        map.iter()
+                            .map(|(key, value)| (key.debug_redacted(), value.debug_redacted())),
+                    )
+                    .finish()
+            }
+            (Def::Set(_), _) => {
+                let set = peek.into_set().map_err(|_| fmt::Error)?;
+                f.debug_set()
+                    .entries(set.iter().map(Peek::debug_redacted))
+                    .finish()
+            }
+            (Def::SmartPointer(_), _) => {
+                let inner = unwrap(peek);
+                if inner.shape() == shape {
+                    fmt::Debug::fmt(&peek, f)
+                } else {
+                    fmt::Debug::fmt(&inner.debug_redacted(), f)
+                }
+            }
+            (_, Type::User(UserType::Struct(sd))) => {
+                let peek_struct = peek.into_struct().map_err(|_| fmt::Error)?;
+                debug_fields(f, &shape.to_string(), sd.kind, peek_struct.fields())
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let peek_enum = peek.into_enum().map_err(|_| fmt::Error)?;
+                let variant = peek_enum.active_variant().map_err(|_| fmt::Error)?;
+                debug_fields(f, variant.name, variant.data.kind, peek_enum.fields())
+            }
+            (_, Type::Sequence(SequenceType::Tuple(_))) => {
+                let tuple = peek.into_tuple().map_err(|_| fmt::Error)?;
+                let mut debug = f.debug_tuple("");
+                for (_, value) in tuple.fields() {
+                    debug.field(&value.debug_redacted());
+                }
+                debug.finish()
+            }
+            _ => fmt::Debug::fmt(&peek, f),
+        }
+    }
+}
+
+/// Formats the fields of a struct or enum variant the way `#[derive(Debug)]` does
+fn debug_fields<'mem, 'facet_lifetime>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    kind: StructKind,
+    fields: impl Iterator<Item = (Field, Peek<'mem, 'facet_lifetime>)>,
+) -> fmt::Result {
+    match kind {
+        StructKind::Unit => f.write_str(name),
+        StructKind::Struct => {
+            let mut debug = f.debug_struct(name);
+            for (field, value) in fields {
+                let value = value.debug_redacted();
+                debug.field(field.name, redacted_field(&field, &value));
+            }
+            debug.finish()
+        }
+        _ => {
+            let mut debug = f.debug_tuple(name);
+            for (field, value) in fields {
+                let value = value.debug_redacted();
+                debug.field(redacted_field(&field, &value));
+            }
+            debug.finish()
+        }
+    }
+}
+
+fn redacted_field<'a>(field: &Field, value: &'a DebugRedacted<'_, '_>) -> &'a dyn fmt::Debug {
+    if field.flags.contains(FieldFlags::SENSITIVE) {
+        &Placeholder
+    } else {
+        value
+    }
+}
@@ -0,0 +1,63 @@
+use facet_core::{ResultDef, ResultVTable};
+
+/// Lets you read from a result (implements read-only result operations)
+#[derive(Clone, Copy)]
+pub struct PeekResult<'mem, 'facet_lifetime> {
+    /// the underlying value
+    pub(crate) value: crate::Peek<'mem, 'facet_lifetime>,
+
+    /// the definition of the result
+    pub(crate) def: ResultDef,
+}
+
+impl<'mem, 'facet_lifetime> PeekResult<'mem, 'facet_lifetime> {
+    /// Returns the result definition
+    #[inline(always)]
+    pub fn def(self) -> ResultDef {
+        self.def
+    }
+
+    /// Returns the result vtable
+    #[inline(always)]
+    pub fn vtable(self) -> &'static ResultVTable {
+        self.def.vtable
+    }
+
+    /// Returns whether the result is Ok
+    #[inline]
+    pub fn is_ok(self) -> bool {
+        unsafe { (self.vtable().is_ok_fn)(self.value.data()) }
+    }
+
+    /// Returns whether the result is Err
+    #[inline]
+    pub fn is_err(self) -> bool {
+        !self.is_ok()
+    }
+
+    /// Returns the success value as a Peek if the result is Ok, None otherwise
+    pub fn ok(self) -> Option<crate::Peek<'mem, 'facet_lifetime>> {
+        unsafe {
+            (self.vtable().get_ok_fn)(self.value.data())
+                .map(|inner_data| crate::Peek::unchecked_new(inner_data, self.def.t()))
+        }
+    }
+
+    /// Returns the error as a Peek if the result is Err, None otherwise
+    pub fn err(self) -> Option<crate::Peek<'mem, 'facet_lifetime>> {
+        unsafe {
+            (self.vtable().get_err_fn)(self.value.data())
+                .map(|inner_data| crate::Peek::unchecked_new(inner_data, self.def.e()))
+        }
+    }
+
+    /// Returns what the result holds, as `Ok` or `Err` like the result itself
+    pub fn value(
+        self,
+    ) -> Result<crate::Peek<'mem, 'facet_lifetime>, crate::Peek<'mem, 'facet_lifetime>> {
+        match self.ok() {
+            Some(value) => Ok(value),
+            None => Err(self.err().expect("a result that isn't Ok is Err")),
+        }
+    }
+}
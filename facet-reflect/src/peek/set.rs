@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use facet_core::{PtrConst, PtrMut, SetDef};
+
+use super::{Peek, map::sort_by_peek};
+
+/// Iterator over values in a `PeekSet`
+pub struct PeekSetIter<'mem, 'facet_lifetime> {
+    set: PeekSet<'mem, 'facet_lifetime>,
+    iter: PtrMut<'mem>,
+}
+
+impl<'mem, 'facet_lifetime> Iterator for PeekSetIter<'mem, 'facet_lifetime> {
+    type Item = Peek<'mem, 'facet_lifetime>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let next = (self.set.def.vtable.iter_vtable.next)(self.iter);
+            next.map(|value_ptr| Peek::unchecked_new(value_ptr, self.set.def.t()))
+        }
+    }
+}
+
+impl Drop for PeekSetIter<'_, '_> {
+    fn drop(&mut self) {
+        unsafe { (self.set.def.vtable.iter_vtable.dealloc)(self.iter) }
+    }
+}
+
+impl<'mem, 'facet_lifetime> IntoIterator for &'mem PeekSet<'mem, 'facet_lifetime> {
+    type Item = Peek<'mem, 'facet_lifetime>;
+    type IntoIter = PeekSetIter<'mem, 'facet_lifetime>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Lets you read from a set (implements read-only [`facet_core::SetVTable`] proxies)
+#[derive(Clone, Copy)]
+pub struct PeekSet<'mem, 'facet_lifetime> {
+    pub(crate) value: Peek<'mem, 'facet_lifetime>,
+
+    pub(crate) def: SetDef,
+}
+
+impl core::fmt::Debug for PeekSet<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PeekSet").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet_lifetime> PeekSet<'mem, 'facet_lifetime> {
+    /// Constructor
+    pub fn new(value: Peek<'mem, 'facet_lifetime>, def: SetDef) -> Self {
+        Self { value, def }
+    }
+
+    /// Get the number of values in the set
+    pub fn len(&self) -> usize {
+        unsafe { (self.def.vtable.len_fn)(self.value.data()) }
+    }
+
+    /// Returns true if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the set contains a value
+    pub fn contains(&self, value: &impl facet_core::Facet<'facet_lifetime>) -> bool {
+        unsafe {
+            let value_ptr = PtrConst::new(value);
+            (self.def.vtable.contains_fn)(self.value.data(), value_ptr)
+        }
+    }
+
+    /// Returns an iterator over the values in the set, in whatever order the set keeps them:
+    /// for hash sets, that may change from one run to the next.
+    pub fn iter(self) -> PeekSetIter<'mem, 'facet_lifetime> {
+        let iter = unsafe { (self.def.vtable.iter_fn)(self.value.data()) };
+        PeekSetIter { set: self, iter }
+    }
+
+    /// Returns the values in the set in order, so that equal sets always yield them in the same
+    /// order.
+    ///
+    /// Values are compared with their type's ordering if it has one, and by their string
+    /// representation otherwise.
+    pub fn iter_sorted(self) -> alloc::vec::IntoIter<Peek<'mem, 'facet_lifetime>> {
+        let mut values: Vec<_> = self.iter().collect();
+        sort_by_peek(&mut values, |value| *value);
+        values.into_iter()
+    }
+
+    /// Def getter
+    pub fn def(&self) -> SetDef {
+        self.def
+    }
+}
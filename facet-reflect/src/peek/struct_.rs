@@ -1,7 +1,7 @@
 use facet_core::{Field, FieldError, FieldFlags, StructType};
 
 use crate::Peek;
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
 
 /// Lets you read from a struct (implements read-only struct operations)
 #[derive(Clone, Copy)]
@@ -51,12 +51,10 @@ impl<'mem, 'facet_lifetime> PeekStruct<'mem, 'facet_lifetime> {
     /// Gets the value of the field with the given name
     #[inline]
     pub fn field_by_name(&self, name: &str) -> Result<Peek<'mem, 'facet_lifetime>, FieldError> {
-        for (i, field) in self.ty.fields.iter().enumerate() {
-            if field.name == name {
-                return self.field(i);
-            }
+        match self.ty.field_index(name) {
+            Some(index) => self.field(index),
+            None => Err(FieldError::NoSuchField),
         }
-        Err(FieldError::NoSuchField)
     }
 }
 
@@ -89,6 +87,7 @@ pub trait HasFields<'mem, 'facet_lifetime> {
         self.fields()
             .filter(|(field, peek)| !unsafe { field.should_skip_serializing(peek.data()) })
             .flat_map(|(mut field, peek)| {
+                // Only flattened fields need a `Vec`, the others are yielded as they are
                 if field.flags.contains(FieldFlags::FLATTEN) {
                     let mut flattened = Vec::new();
                     if let Ok(struct_peek) = peek.into_struct() {
@@ -119,9 +118,9 @@ pub trait HasFields<'mem, 'facet_lifetime> {
                         // TODO: fail more gracefully
                         panic!("cannot flatten a {}", field.shape())
                     }
-                    flattened.into_iter()
+                    None.into_iter().chain(flattened)
                 } else {
-                    vec![(field, peek)].into_iter()
+                    Some((field, peek)).into_iter().chain(Vec::new())
                 }
             })
     }
@@ -1,13 +1,20 @@
 use core::{cmp::Ordering, marker::PhantomData};
 use facet_core::{
-    Def, Facet, PointerType, PtrConst, PtrMut, SequenceType, Shape, Type, TypeNameOpts, UserType,
-    ValueVTable,
+    Def, Facet, Lens, PointerType, PtrConst, PtrMut, ScalarAffinity, SequenceType, Shape, Type,
+    TypeNameOpts, UserType, ValueVTable,
 };
 
+#[cfg(feature = "alloc")]
+use crate::{Guard, HeapValue};
 use crate::{ReflectError, ScalarType};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use facet_core::SerializeWith;
 
 use super::{
-    ListLikeDef, PeekEnum, PeekList, PeekListLike, PeekMap, PeekSmartPointer, PeekStruct, PeekTuple,
+    ListLikeDef, PeekEnum, PeekList, PeekListLike, PeekMap, PeekSet, PeekSmartPointer, PeekStruct,
+    PeekTuple,
 };
 
 /// A unique identifier for a peek value
@@ -191,6 +198,37 @@ impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
         }
     }
 
+    /// Follows a [`Lens`] down to the field it points at
+    ///
+    /// Fails if this value isn't a `Root`, or the lens doesn't lead to a `Target`.
+    pub fn lens<Root, Target>(self, lens: &Lens<Root, Target>) -> Result<Self, ReflectError>
+    where
+        Root: Facet<'facet_lifetime>,
+        Target: Facet<'facet_lifetime>,
+    {
+        if self.shape != Root::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: Root::SHAPE,
+                actual: self.shape,
+            });
+        }
+        let mut peek = self;
+        for &index in lens.indices() {
+            let shape = peek.shape;
+            peek = peek
+                .into_struct()?
+                .field(index)
+                .map_err(|field_error| ReflectError::FieldError { shape, field_error })?;
+        }
+        if peek.shape != Target::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: Target::SHAPE,
+                actual: peek.shape,
+            });
+        }
+        Ok(peek)
+    }
+
     /// Try to get the value as a string if it's a string type
     /// Returns None if the value is not a string or couldn't be extracted
     pub fn as_str(&self) -> Option<&str> {
@@ -211,6 +249,71 @@ impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
         }
     }
 
+    /// The value a reference points to, if this is a `&T` to a sized `T`
+    pub fn follow_reference(self) -> Option<Self> {
+        let Type::Pointer(PointerType::Reference(vpt)) = self.shape.ty else {
+            return None;
+        };
+        if vpt.wide {
+            return None;
+        }
+        unsafe {
+            // The referent outlives the reference, so it's borrowed for as long
+            let target = *self.data.get::<*const u8>();
+            Some(Peek::unchecked_new(PtrConst::new(target), (vpt.target)()))
+        }
+    }
+
+    /// Nanoseconds since the unix epoch, if this is a timestamp or datetime that stands for an
+    /// instant
+    pub fn unix_nanos(&self) -> Option<i128> {
+        let Def::Scalar(sd) = self.shape.def else {
+            return None;
+        };
+        let ScalarAffinity::Time(time) = sd.affinity else {
+            return None;
+        };
+        let to_unix_nanos = time.to_unix_nanos()?;
+        Some(unsafe { to_unix_nanos(self.data) })
+    }
+
+    /// Converts this value to the proxy it's serialized as, for a field with
+    /// `#[facet(serialize_with = ...)]` or a shape with `#[facet(into = ...)]`
+    #[cfg(feature = "alloc")]
+    pub fn serialize_with(
+        &self,
+        with: &SerializeWith,
+    ) -> Result<HeapValue<'facet_lifetime>, ReflectError> {
+        let shape = with.shape;
+        let layout = shape
+            .layout
+            .sized_layout()
+            .map_err(|_| ReflectError::Unsized { shape })?;
+        let proxy = shape
+            .allocate()
+            .map_err(|_| ReflectError::Unsized { shape })?;
+        // Frees the proxy if it can't be converted
+        let guard = Guard {
+            ptr: proxy.as_mut_byte_ptr(),
+            layout,
+            arena: None,
+        };
+
+        let mut message = String::new();
+        if !unsafe { (with.convert)(self.data, proxy, &mut message) } {
+            return Err(ReflectError::ConversionFailed {
+                src_shape: self.shape,
+                dst_shape: shape,
+                message,
+            });
+        }
+        Ok(HeapValue {
+            guard: Some(guard),
+            shape,
+            phantom: PhantomData,
+        })
+    }
+
     /// Tries to identify this value as a struct
     pub fn into_struct(self) -> Result<PeekStruct<'mem, 'facet_lifetime>, ReflectError> {
         if let Type::User(UserType::Struct(ty)) = self.shape.ty {
@@ -247,6 +350,18 @@ impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
         }
     }
 
+    /// Tries to identify this value as a set
+    pub fn into_set(self) -> Result<PeekSet<'mem, 'facet_lifetime>, ReflectError> {
+        if let Def::Set(def) = self.shape.def {
+            Ok(PeekSet { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "set",
+                actual: self.shape,
+            })
+        }
+    }
+
     /// Tries to identify this value as a list
     pub fn into_list(self) -> Result<PeekList<'mem, 'facet_lifetime>, ReflectError> {
         if let Def::List(def) = self.shape.def {
@@ -322,6 +437,18 @@ impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
         }
     }
 
+    /// Tries to identify this value as a result
+    pub fn into_result(self) -> Result<super::PeekResult<'mem, 'facet_lifetime>, ReflectError> {
+        if let Def::Result(def) = self.shape.def {
+            Ok(super::PeekResult { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "result",
+                actual: self.shape,
+            })
+        }
+    }
+
     /// Tries to identify this value as a tuple
     pub fn into_tuple(self) -> Result<PeekTuple<'mem, 'facet_lifetime>, ReflectError> {
         if let Type::Sequence(SequenceType::Tuple(ty)) = self.shape.ty {
@@ -348,12 +475,12 @@ impl<'mem, 'facet_lifetime> Peek<'mem, 'facet_lifetime> {
             unsafe {
                 let inner_data = try_borrow_inner_fn(current_peek.data).unwrap_or_else(|e| {
                     panic!("innermost_peek: try_borrow_inner returned an error! was trying to go from {} to {}. error: {e}", current_peek.shape,
-                        inner_shape())
+                        inner_shape.get())
                 });
 
                 current_peek = Peek {
                     data: inner_data,
-                    shape: inner_shape(),
+                    shape: inner_shape.get(),
                     invariant: PhantomData,
                 };
             }
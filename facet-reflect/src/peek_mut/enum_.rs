@@ -0,0 +1,75 @@
+use facet_core::{EnumType, Variant};
+
+use crate::{PeekEnum, VariantError};
+
+use super::PeekMut;
+
+/// Lets you read from and write to the active variant of an enum
+pub struct PeekMutEnum<'mem, 'facet_lifetime> {
+    /// the underlying value
+    pub(crate) value: PeekMut<'mem, 'facet_lifetime>,
+
+    /// the definition of the enum
+    pub(crate) ty: EnumType,
+}
+
+impl core::fmt::Debug for PeekMutEnum<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<'mem, 'facet_lifetime> PeekMutEnum<'mem, 'facet_lifetime> {
+    /// Returns the enum definition
+    #[inline(always)]
+    pub fn ty(&self) -> EnumType {
+        self.ty
+    }
+
+    /// Borrows this enum as read-only
+    #[inline]
+    pub fn as_peek_enum(&self) -> PeekEnum<'_, 'facet_lifetime> {
+        PeekEnum {
+            value: self.value.as_peek(),
+            ty: self.ty,
+        }
+    }
+
+    /// Returns the active variant
+    #[inline]
+    pub fn active_variant(&self) -> Result<&'static Variant, VariantError> {
+        self.as_peek_enum().active_variant()
+    }
+
+    /// Returns a field of the active variant by index, if it has that many
+    pub fn field(
+        &mut self,
+        index: usize,
+    ) -> Result<Option<PeekMut<'_, 'facet_lifetime>>, VariantError> {
+        let variant = self.active_variant()?;
+        let Some(field) = variant.data.fields.get(index) else {
+            return Ok(None);
+        };
+        unsafe {
+            let field_data = self.value.data().field(field.offset);
+            Ok(Some(PeekMut::unchecked_new(field_data, field.shape())))
+        }
+    }
+
+    /// Returns a field of the active variant by name, if it has one by that name
+    pub fn field_by_name(
+        &mut self,
+        field_name: &str,
+    ) -> Result<Option<PeekMut<'_, 'facet_lifetime>>, VariantError> {
+        match self.active_variant()?.data.field_index(field_name) {
+            Some(index) => self.field(index),
+            None => Ok(None),
+        }
+    }
+
+    /// Gives back the enum as a plain value
+    #[inline(always)]
+    pub fn into_inner(self) -> PeekMut<'mem, 'facet_lifetime> {
+        self.value
+    }
+}
@@ -0,0 +1,95 @@
+use core::mem::ManuallyDrop;
+use facet_core::{Facet, ListDef, PtrMut, PtrUninit};
+
+use crate::{PeekList, ReflectError};
+
+use super::PeekMut;
+
+/// Lets you read from and write to a list (implements [`facet_core::ListVTable`] proxies)
+pub struct PeekMutList<'mem, 'facet_lifetime> {
+    pub(crate) value: PeekMut<'mem, 'facet_lifetime>,
+    pub(crate) def: ListDef,
+}
+
+impl core::fmt::Debug for PeekMutList<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PeekMutList").finish_non_exhaustive()
+    }
+}
+
+impl<'facet_lifetime> PeekMutList<'_, 'facet_lifetime> {
+    /// Borrows this list as read-only
+    #[inline]
+    pub fn as_peek_list(&self) -> PeekList<'_, 'facet_lifetime> {
+        PeekList::new(self.value.as_peek(), self.def)
+    }
+
+    /// Get the length of the list
+    pub fn len(&self) -> usize {
+        self.as_peek_list().len()
+    }
+
+    /// Returns true if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get an item from the list at the specified index
+    pub fn get_mut(&mut self, index: usize) -> Option<PeekMut<'_, 'facet_lifetime>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let Ok(layout) = self.def.t().layout.sized_layout() else {
+            return None;
+        };
+
+        let data = unsafe { (self.def.vtable.as_mut_ptr)(self.value.data()) };
+
+        // SAFETY: we verify index bounds at the start of the function
+        let item_ptr = unsafe { data.field(layout.size() * index) };
+
+        Some(unsafe { PeekMut::unchecked_new(item_ptr, self.def.t()) })
+    }
+
+    /// Pushes an item to the end of the list
+    pub fn push<T: Facet<'facet_lifetime>>(&mut self, item: T) -> Result<(), ReflectError> {
+        if self.def.t() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.t(),
+                actual: T::SHAPE,
+            });
+        }
+
+        // `push` moves the item out, so it mustn't be dropped here
+        let mut item = ManuallyDrop::new(item);
+        unsafe { (self.def.vtable.push)(self.value.data(), PtrMut::new(&mut *item as *mut T)) };
+        Ok(())
+    }
+
+    /// Removes all items from the list
+    pub fn clear(&mut self) -> Result<(), ReflectError> {
+        let shape = self.value.shape();
+        let Some(init_fn) = self.def.vtable.init_in_place_with_capacity else {
+            return Err(ReflectError::OperationFailed {
+                shape,
+                operation: "list can't be cleared, it can't be initialized in place",
+            });
+        };
+
+        // The list is replaced by a new, empty one
+        unsafe {
+            let data = match shape.vtable.drop_in_place {
+                Some(drop_fn) => drop_fn(self.value.data()),
+                None => PtrUninit::new(self.value.data().as_mut_byte_ptr()),
+            };
+            init_fn(data, 0);
+        }
+        Ok(())
+    }
+
+    /// Def getter
+    pub fn def(&self) -> ListDef {
+        self.def
+    }
+}
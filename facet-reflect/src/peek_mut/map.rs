@@ -0,0 +1,93 @@
+use core::mem::ManuallyDrop;
+use facet_core::{Facet, MapDef, PtrMut, PtrUninit};
+
+use crate::{PeekMap, ReflectError};
+
+use super::PeekMut;
+
+/// Lets you read from and write to a map (implements [`facet_core::MapVTable`] proxies)
+pub struct PeekMutMap<'mem, 'facet_lifetime> {
+    pub(crate) value: PeekMut<'mem, 'facet_lifetime>,
+
+    pub(crate) def: MapDef,
+}
+
+impl core::fmt::Debug for PeekMutMap<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PeekMutMap").finish_non_exhaustive()
+    }
+}
+
+impl<'facet_lifetime> PeekMutMap<'_, 'facet_lifetime> {
+    /// Borrows this map as read-only
+    #[inline]
+    pub fn as_peek_map(&self) -> PeekMap<'_, 'facet_lifetime> {
+        PeekMap::new(self.value.as_peek(), self.def)
+    }
+
+    /// Get the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.as_peek_map().len()
+    }
+
+    /// Returns true if the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the map contains a key
+    pub fn contains_key(&self, key: &impl Facet<'facet_lifetime>) -> bool {
+        self.as_peek_map().contains_key(key)
+    }
+
+    /// Inserts a key-value pair into the map, replacing the value already there for that key
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<(), ReflectError>
+    where
+        K: Facet<'facet_lifetime>,
+        V: Facet<'facet_lifetime>,
+    {
+        if self.def.k() != K::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.k(),
+                actual: K::SHAPE,
+            });
+        }
+        if self.def.v() != V::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.v(),
+                actual: V::SHAPE,
+            });
+        }
+
+        // `insert_fn` moves the key and value out, so they mustn't be dropped here
+        let mut key = ManuallyDrop::new(key);
+        let mut value = ManuallyDrop::new(value);
+        unsafe {
+            (self.def.vtable.insert_fn)(
+                self.value.data(),
+                PtrMut::new(&mut *key as *mut K),
+                PtrMut::new(&mut *value as *mut V),
+            )
+        };
+        Ok(())
+    }
+
+    /// Removes all entries from the map
+    pub fn clear(&mut self) {
+        let shape = self.value.shape();
+
+        // The map is replaced by a new, empty one
+        unsafe {
+            let data = match shape.vtable.drop_in_place {
+                Some(drop_fn) => drop_fn(self.value.data()),
+                None => PtrUninit::new(self.value.data().as_mut_byte_ptr()),
+            };
+            (self.def.vtable.init_in_place_with_capacity_fn)(data, 0);
+        }
+    }
+
+    /// Def getter
+    pub fn def(&self) -> MapDef {
+        self.def
+    }
+}
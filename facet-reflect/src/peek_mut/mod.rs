@@ -0,0 +1,16 @@
+//! Allows reading from and writing to existing values through their shapes
+
+mod value;
+pub use value::*;
+
+mod struct_;
+pub use struct_::*;
+
+mod enum_;
+pub use enum_::*;
+
+mod list;
+pub use list::*;
+
+mod map;
+pub use map::*;
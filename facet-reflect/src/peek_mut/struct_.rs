@@ -0,0 +1,65 @@
+use facet_core::{FieldError, StructType};
+
+use super::PeekMut;
+
+/// Lets you read from and write to a struct
+pub struct PeekMutStruct<'mem, 'facet_lifetime> {
+    /// the underlying value
+    pub(crate) value: PeekMut<'mem, 'facet_lifetime>,
+
+    /// the definition of the struct!
+    pub(crate) ty: StructType,
+}
+
+impl core::fmt::Debug for PeekMutStruct<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PeekMutStruct").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet_lifetime> PeekMutStruct<'mem, 'facet_lifetime> {
+    /// Returns the struct definition
+    #[inline(always)]
+    pub fn ty(&self) -> &StructType {
+        &self.ty
+    }
+
+    /// Returns the number of fields in this struct
+    #[inline(always)]
+    pub fn field_count(&self) -> usize {
+        self.ty.fields.len()
+    }
+
+    /// Returns the field at the given index
+    pub fn field(&mut self, index: usize) -> Result<PeekMut<'_, 'facet_lifetime>, FieldError> {
+        let field = self
+            .ty
+            .fields
+            .get(index)
+            .ok_or(FieldError::IndexOutOfBounds {
+                index,
+                bound: self.ty.fields.len(),
+            })?;
+        unsafe {
+            let field_data = self.value.data().field(field.offset);
+            Ok(PeekMut::unchecked_new(field_data, field.shape()))
+        }
+    }
+
+    /// Returns the field with the given name
+    pub fn field_by_name(
+        &mut self,
+        name: &str,
+    ) -> Result<PeekMut<'_, 'facet_lifetime>, FieldError> {
+        match self.ty.field_index(name) {
+            Some(index) => self.field(index),
+            None => Err(FieldError::NoSuchField),
+        }
+    }
+
+    /// Gives back the struct as a plain value
+    #[inline(always)]
+    pub fn into_inner(self) -> PeekMut<'mem, 'facet_lifetime> {
+        self.value
+    }
+}
@@ -0,0 +1,183 @@
+use core::marker::PhantomData;
+use facet_core::{Def, Facet, Field, PtrConst, PtrMut, Shape, Type, UserType};
+
+use crate::{Peek, ReflectError};
+
+use super::{PeekMutEnum, PeekMutList, PeekMutMap, PeekMutStruct};
+
+/// Lets you read from and write to a value (implements [`facet_core::ValueVTable`] proxies that
+/// need `&mut`), the mutable counterpart of [`Peek`]
+pub struct PeekMut<'mem, 'facet_lifetime> {
+    /// Underlying data
+    pub(crate) data: PtrMut<'mem>,
+
+    /// Shape of the value
+    pub(crate) shape: &'static Shape,
+
+    invariant: PhantomData<fn(&'facet_lifetime ()) -> &'facet_lifetime ()>,
+}
+
+impl<'mem, 'facet_lifetime> PeekMut<'mem, 'facet_lifetime> {
+    /// Creates a new `PeekMut` instance for a value of type `T`.
+    pub fn new<T: Facet<'facet_lifetime>>(t: &'mem mut T) -> Self {
+        Self {
+            data: PtrMut::new(t as *mut T),
+            shape: T::SHAPE,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Creates a new `PeekMut` instance without checking the type.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the data is a valid, initialized value of the given shape,
+    /// and that nothing else accesses it for `'mem`.
+    pub unsafe fn unchecked_new(data: PtrMut<'mem>, shape: &'static Shape) -> Self {
+        Self {
+            data,
+            shape,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Returns the shape
+    #[inline(always)]
+    pub const fn shape(&self) -> &'static Shape {
+        self.shape
+    }
+
+    /// Returns the data
+    #[inline(always)]
+    pub const fn data(&self) -> PtrMut<'mem> {
+        self.data
+    }
+
+    /// Borrows this value as read-only
+    #[inline]
+    pub fn as_peek(&self) -> Peek<'_, 'facet_lifetime> {
+        unsafe { Peek::unchecked_new(PtrConst::new(self.data.as_byte_ptr()), self.shape) }
+    }
+
+    /// Borrows this value mutably again, for a shorter time
+    #[inline]
+    pub fn reborrow(&mut self) -> PeekMut<'_, 'facet_lifetime> {
+        unsafe { PeekMut::unchecked_new(self.data, self.shape) }
+    }
+
+    /// Borrows the value as a Rust value
+    pub fn get<T: Facet<'facet_lifetime>>(&self) -> Result<&T, ReflectError> {
+        self.check_shape::<T>()?;
+        Ok(unsafe { self.data.get::<T>() })
+    }
+
+    /// Borrows the value mutably as a Rust value
+    pub fn get_mut<T: Facet<'facet_lifetime>>(&mut self) -> Result<&mut T, ReflectError> {
+        self.check_shape::<T>()?;
+        Ok(unsafe { self.data.as_mut::<T>() })
+    }
+
+    /// Replaces the value, dropping the old one
+    pub fn set<T: Facet<'facet_lifetime>>(&mut self, value: T) -> Result<(), ReflectError> {
+        *self.get_mut::<T>()? = value;
+        Ok(())
+    }
+
+    fn check_shape<T: Facet<'facet_lifetime>>(&self) -> Result<(), ReflectError> {
+        if self.shape != T::SHAPE {
+            Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: T::SHAPE,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks that `fields` of this value can be written one by one
+    fn check_fields_writable(&self, fields: &[Field]) -> Result<(), ReflectError> {
+        let vtable = &self.shape.vtable;
+        let guarded = self.shape.inner.is_some()
+            || matches!(self.shape.def, Def::Scalar(_))
+            || vtable.invariants.is_some()
+            || vtable.validate.is_some()
+            || fields.iter().any(|field| field.vtable.validate.is_some());
+        match guarded {
+            true => Err(ReflectError::OperationFailed {
+                shape: self.shape,
+                operation: "writing fields of a value that upholds invariants",
+            }),
+            false => Ok(()),
+        }
+    }
+
+    /// Tries to identify this value as a struct
+    ///
+    /// Fails for transparent wrappers like `NonZero<u32>`, scalars, and values that check
+    /// invariants or validate themselves or their fields: writing a field of those could break
+    /// what they guarantee.
+    pub fn into_struct(self) -> Result<PeekMutStruct<'mem, 'facet_lifetime>, ReflectError> {
+        if let Type::User(UserType::Struct(ty)) = self.shape.ty {
+            self.check_fields_writable(ty.fields)?;
+            Ok(PeekMutStruct { value: self, ty })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "struct",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as an enum
+    ///
+    /// Fails for the same values as [`PeekMut::into_struct`].
+    pub fn into_enum(self) -> Result<PeekMutEnum<'mem, 'facet_lifetime>, ReflectError> {
+        if let Type::User(UserType::Enum(ty)) = self.shape.ty {
+            for variant in ty.variants {
+                self.check_fields_writable(variant.data.fields)?;
+            }
+            Ok(PeekMutEnum { value: self, ty })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "enum",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as a list
+    pub fn into_list(self) -> Result<PeekMutList<'mem, 'facet_lifetime>, ReflectError> {
+        if let Def::List(def) = self.shape.def {
+            Ok(PeekMutList { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "list",
+                actual: self.shape,
+            })
+        }
+    }
+
+    /// Tries to identify this value as a map
+    pub fn into_map(self) -> Result<PeekMutMap<'mem, 'facet_lifetime>, ReflectError> {
+        if let Def::Map(def) = self.shape.def {
+            Ok(PeekMutMap { value: self, def })
+        } else {
+            Err(ReflectError::WasNotA {
+                expected: "map",
+                actual: self.shape,
+            })
+        }
+    }
+}
+
+impl core::fmt::Debug for PeekMut<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.as_peek(), f)
+    }
+}
+
+impl core::fmt::Display for PeekMut<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.as_peek(), f)
+    }
+}
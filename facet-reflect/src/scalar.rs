@@ -1,4 +1,5 @@
 use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::time::Duration;
 
 use facet_core::{ConstTypeId, Shape};
 
@@ -57,6 +58,8 @@ pub enum ScalarType {
     Ipv6Addr,
     /// `facet_core::typeid::ConstTypeId`.
     ConstTypeId,
+    /// `core::time::Duration`.
+    Duration,
     /// `&camino::Utf8Path`.
     #[cfg(feature = "camino")]
     Utf8Path,
@@ -144,6 +147,8 @@ impl ScalarType {
             Some(ScalarType::Ipv6Addr)
         } else if shape.id == ConstTypeId::of::<ConstTypeId>() {
             Some(ScalarType::ConstTypeId)
+        } else if shape.id == ConstTypeId::of::<Duration>() {
+            Some(ScalarType::Duration)
         } else {
             None
         }
@@ -260,6 +265,10 @@ mod tests {
             ScalarType::ConstTypeId,
             ScalarType::try_from_shape(ConstTypeId::SHAPE).unwrap()
         );
+        assert_eq!(
+            ScalarType::Duration,
+            ScalarType::try_from_shape(Duration::SHAPE).unwrap()
+        );
         #[cfg(feature = "camino")]
         assert_eq!(
             ScalarType::Utf8Path,
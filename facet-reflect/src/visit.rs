@@ -0,0 +1,256 @@
+use alloc::vec::Vec;
+
+use facet_core::{Def, Field, SequenceType, Type, UserType, Variant};
+
+use crate::deep_eq::unwrap;
+use crate::{
+    HasFields, Peek, PeekEnum, PeekListLike, PeekMap, PeekOption, PeekSet, PeekStruct, PeekTuple,
+};
+
+/// Hooks called by [`walk`] as it goes through a value
+///
+/// Every method does nothing by default, so a visitor only implements the ones it cares
+/// about. Containers get an `enter_` hook before their contents and an `exit_` hook
+/// after them; each field, element and map entry is wrapped in its own pair of hooks, so
+/// a visitor can keep track of where it is.
+#[allow(unused_variables)]
+pub trait ValueVisitor<'mem, 'facet_lifetime> {
+    /// A value without any structure to walk into: numbers, strings, opaque types…
+    fn visit_scalar(&mut self, value: Peek<'mem, 'facet_lifetime>) {}
+
+    /// An option that is `None`
+    fn visit_none(&mut self, option: PeekOption<'mem, 'facet_lifetime>) {}
+
+    /// Before the value of an option that is `Some`
+    fn enter_some(&mut self, option: PeekOption<'mem, 'facet_lifetime>) {}
+
+    /// After the value of an option that is `Some`
+    fn exit_some(&mut self, option: PeekOption<'mem, 'facet_lifetime>) {}
+
+    /// Before the fields of a struct, including tuple and unit structs
+    fn enter_struct(&mut self, value: PeekStruct<'mem, 'facet_lifetime>) {}
+
+    /// After the fields of a struct
+    fn exit_struct(&mut self, value: PeekStruct<'mem, 'facet_lifetime>) {}
+
+    /// Before the fields of the active variant of an enum
+    fn enter_enum(&mut self, value: PeekEnum<'mem, 'facet_lifetime>, variant: &'static Variant) {}
+
+    /// After the fields of the active variant of an enum
+    fn exit_enum(&mut self, value: PeekEnum<'mem, 'facet_lifetime>, variant: &'static Variant) {}
+
+    /// Before the value of a struct or variant field
+    fn enter_field(&mut self, field: Field) {}
+
+    /// After the value of a struct or variant field
+    fn exit_field(&mut self, field: Field) {}
+
+    /// Before the elements of a list, array or slice
+    fn enter_list(&mut self, list: PeekListLike<'mem, 'facet_lifetime>) {}
+
+    /// After the elements of a list, array or slice
+    fn exit_list(&mut self, list: PeekListLike<'mem, 'facet_lifetime>) {}
+
+    /// Before the elements of a tuple
+    fn enter_tuple(&mut self, tuple: PeekTuple<'mem, 'facet_lifetime>) {}
+
+    /// After the elements of a tuple
+    fn exit_tuple(&mut self, tuple: PeekTuple<'mem, 'facet_lifetime>) {}
+
+    /// Before an element of a list, array, slice or tuple
+    fn enter_element(&mut self, index: usize) {}
+
+    /// After an element of a list, array, slice or tuple
+    fn exit_element(&mut self, index: usize) {}
+
+    /// Before the entries of a map
+    fn enter_map(&mut self, map: PeekMap<'mem, 'facet_lifetime>) {}
+
+    /// After the entries of a map
+    fn exit_map(&mut self, map: PeekMap<'mem, 'facet_lifetime>) {}
+
+    /// Before the value of a map entry. Keys aren't walked: call [`walk`] on them if needed
+    fn enter_entry(&mut self, key: Peek<'mem, 'facet_lifetime>) {}
+
+    /// After the value of a map entry
+    fn exit_entry(&mut self, key: Peek<'mem, 'facet_lifetime>) {}
+
+    /// Before the values of a set, which are walked one after the other
+    fn enter_set(&mut self, set: PeekSet<'mem, 'facet_lifetime>) {}
+
+    /// After the values of a set
+    fn exit_set(&mut self, set: PeekSet<'mem, 'facet_lifetime>) {}
+}
+
+/// What's left to do, most recent first
+enum Task<'mem, 'facet_lifetime> {
+    Value(Peek<'mem, 'facet_lifetime>),
+    ExitSome(PeekOption<'mem, 'facet_lifetime>),
+    ExitStruct(PeekStruct<'mem, 'facet_lifetime>),
+    ExitEnum(PeekEnum<'mem, 'facet_lifetime>, &'static Variant),
+    ExitList(PeekListLike<'mem, 'facet_lifetime>),
+    ExitTuple(PeekTuple<'mem, 'facet_lifetime>),
+    ExitMap(PeekMap<'mem, 'facet_lifetime>),
+    ExitSet(PeekSet<'mem, 'facet_lifetime>),
+    EnterField(Field),
+    ExitField(Field),
+    EnterElement(usize),
+    ExitElement(usize),
+    EnterEntry(Peek<'mem, 'facet_lifetime>),
+    ExitEntry(Peek<'mem, 'facet_lifetime>),
+}
+
+/// Goes through a value depth-first, in order, calling the visitor's hooks along the way
+///
+/// Transparent wrappers and smart pointers are looked through, so the visitor sees their
+/// contents. This uses a stack rather than recursion, so deeply nested values don't
+/// overflow the call stack.
+pub fn walk<'mem, 'facet_lifetime>(
+    peek: Peek<'mem, 'facet_lifetime>,
+    visitor: &mut impl ValueVisitor<'mem, 'facet_lifetime>,
+) {
+    let mut stack = Vec::new();
+    stack.push(Task::Value(peek));
+
+    while let Some(task) = stack.pop() {
+        let peek = match task {
+            Task::Value(peek) => unwrap(peek),
+            Task::ExitSome(option) => {
+                visitor.exit_some(option);
+                continue;
+            }
+            Task::ExitStruct(value) => {
+                visitor.exit_struct(value);
+                continue;
+            }
+            Task::ExitEnum(value, variant) => {
+                visitor.exit_enum(value, variant);
+                continue;
+            }
+            Task::ExitList(list) => {
+                visitor.exit_list(list);
+                continue;
+            }
+            Task::ExitTuple(tuple) => {
+                visitor.exit_tuple(tuple);
+                continue;
+            }
+            Task::ExitMap(map) => {
+                visitor.exit_map(map);
+                continue;
+            }
+            Task::ExitSet(set) => {
+                visitor.exit_set(set);
+                continue;
+            }
+            Task::EnterField(field) => {
+                visitor.enter_field(field);
+                continue;
+            }
+            Task::ExitField(field) => {
+                visitor.exit_field(field);
+                continue;
+            }
+            Task::EnterElement(index) => {
+                visitor.enter_element(index);
+                continue;
+            }
+            Task::ExitElement(index) => {
+                visitor.exit_element(index);
+                continue;
+            }
+            Task::EnterEntry(key) => {
+                visitor.enter_entry(key);
+                continue;
+            }
+            Task::ExitEntry(key) => {
+                visitor.exit_entry(key);
+                continue;
+            }
+        };
+
+        match (peek.shape().def, peek.shape().ty) {
+            (Def::Scalar(_), _) => visitor.visit_scalar(peek),
+            (Def::Option(_), _) => {
+                let option = peek.into_option().unwrap();
+                match option.value() {
+                    Some(value) => {
+                        visitor.enter_some(option);
+                        stack.push(Task::ExitSome(option));
+                        stack.push(Task::Value(value));
+                    }
+                    None => visitor.visit_none(option),
+                }
+            }
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _) => {
+                let list = peek.into_list_like().unwrap();
+                visitor.enter_list(list);
+                stack.push(Task::ExitList(list));
+                for index in (0..list.len()).rev() {
+                    stack.push(Task::ExitElement(index));
+                    stack.push(Task::Value(list.get(index).unwrap()));
+                    stack.push(Task::EnterElement(index));
+                }
+            }
+            (Def::Map(_), _) => {
+                let map = peek.into_map().unwrap();
+                visitor.enter_map(map);
+                stack.push(Task::ExitMap(map));
+                let entries: Vec<_> = map.iter().collect();
+                for (key, value) in entries.into_iter().rev() {
+                    stack.push(Task::ExitEntry(key));
+                    stack.push(Task::Value(value));
+                    stack.push(Task::EnterEntry(key));
+                }
+            }
+            (Def::Set(_), _) => {
+                let set = peek.into_set().unwrap();
+                visitor.enter_set(set);
+                stack.push(Task::ExitSet(set));
+                let values: Vec<_> = set.iter().collect();
+                stack.extend(values.into_iter().rev().map(Task::Value));
+            }
+            (_, Type::User(UserType::Struct(_))) => {
+                let value = peek.into_struct().unwrap();
+                visitor.enter_struct(value);
+                stack.push(Task::ExitStruct(value));
+                push_fields(&mut stack, &value);
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let value = peek.into_enum().unwrap();
+                match value.active_variant() {
+                    Ok(variant) => {
+                        visitor.enter_enum(value, variant);
+                        stack.push(Task::ExitEnum(value, variant));
+                        push_fields(&mut stack, &value);
+                    }
+                    // Can't tell which variant is active: nothing to walk into
+                    Err(_) => visitor.visit_scalar(peek),
+                }
+            }
+            (_, Type::Sequence(SequenceType::Tuple(_))) => {
+                let tuple = peek.into_tuple().unwrap();
+                visitor.enter_tuple(tuple);
+                stack.push(Task::ExitTuple(tuple));
+                for (index, value) in tuple.fields().rev() {
+                    stack.push(Task::ExitElement(index));
+                    stack.push(Task::Value(value));
+                    stack.push(Task::EnterElement(index));
+                }
+            }
+            _ => visitor.visit_scalar(peek),
+        }
+    }
+}
+
+/// Queues up the fields of a struct or variant, so they come off the stack in order
+fn push_fields<'mem, 'facet_lifetime>(
+    stack: &mut Vec<Task<'mem, 'facet_lifetime>>,
+    value: &impl HasFields<'mem, 'facet_lifetime>,
+) {
+    for (field, value) in value.fields().rev() {
+        stack.push(Task::ExitField(field));
+        stack.push(Task::Value(value));
+        stack.push(Task::EnterField(field));
+    }
+}
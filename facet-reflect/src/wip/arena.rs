@@ -0,0 +1,136 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::{alloc::Layout, cell::RefCell};
+use facet_core::{PtrUninit, Shape};
+
+use crate::{FrameFlags, ReflectError};
+
+/// How big the first chunk of an arena is, unless told otherwise
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// A bump allocator to build values in, see [`Wip::alloc_in`](crate::Wip::alloc_in).
+///
+/// A `Wip` allocated in an arena takes the memory for its value, and for every temporary
+/// it needs along the way, from the arena instead of the global allocator. Nothing is
+/// freed piecemeal: all of it goes back in one shot, once the arena and every `Wip` and
+/// [`HeapValue`](crate::HeapValue) allocated from it are dropped. Values are still
+/// dropped in place as usual.
+///
+/// Only the values themselves live in the arena — the buffer of a `String` or a `Vec`
+/// inside of one is allocated on the heap, like it always is.
+///
+/// Clones of an arena share the same memory.
+#[derive(Clone)]
+pub struct Arena {
+    state: Rc<RefCell<ArenaState>>,
+}
+
+struct ArenaState {
+    /// Every chunk allocated so far, the one being bumped into last
+    chunks: Vec<(*mut u8, Layout)>,
+    /// How many bytes of the last chunk are taken
+    used: usize,
+    /// How big the next chunk will be, unless something bigger is asked for
+    next_chunk_size: usize,
+}
+
+impl Arena {
+    /// Creates an empty arena — it allocates its first chunk when it's first used
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates an empty arena whose first chunk holds at least `bytes` bytes
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(ArenaState {
+                chunks: Vec::new(),
+                used: 0,
+                next_chunk_size: bytes.max(1),
+            })),
+        }
+    }
+
+    /// How many bytes the arena got from the global allocator so far
+    pub fn capacity(&self) -> usize {
+        let state = self.state.borrow();
+        state.chunks.iter().map(|(_, layout)| layout.size()).sum()
+    }
+
+    /// Hands out memory for a `layout`, which stays valid for as long as the arena does
+    pub(crate) fn allocate(&self, layout: Layout) -> PtrUninit<'static> {
+        if layout.size() == 0 {
+            return PtrUninit::new(core::ptr::without_provenance_mut::<u8>(layout.align()));
+        }
+
+        let mut state = self.state.borrow_mut();
+        if let Some(&(chunk, chunk_layout)) = state.chunks.last() {
+            let padding = chunk.wrapping_add(state.used).align_offset(layout.align());
+            let start = state.used + padding;
+            if start + layout.size() <= chunk_layout.size() {
+                state.used = start + layout.size();
+                return PtrUninit::new(chunk.wrapping_add(start));
+            }
+        }
+
+        // Doesn't fit: start a new chunk, and don't look back at the previous one
+        let size = state.next_chunk_size.max(layout.size());
+        let chunk_layout = Layout::from_size_align(size, layout.align())
+            .expect("chunk size overflowed")
+            .pad_to_align();
+        // SAFETY: the chunk is at least as big as `layout`, which isn't zero-sized
+        let chunk = unsafe { alloc::alloc::alloc(chunk_layout) };
+        if chunk.is_null() {
+            alloc::alloc::handle_alloc_error(chunk_layout);
+        }
+        state.chunks.push((chunk, chunk_layout));
+        state.used = layout.size();
+        state.next_chunk_size = size.saturating_mul(2);
+        PtrUninit::new(chunk)
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Arena {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Arena")
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl Drop for ArenaState {
+    fn drop(&mut self) {
+        for &(chunk, layout) in &self.chunks {
+            // SAFETY: every chunk was allocated with the global allocator with its layout
+            unsafe { alloc::alloc::dealloc(chunk, layout) };
+        }
+    }
+}
+
+/// Allocates memory for a frame of `shape`, from `arena` if there is one. Only heap
+/// allocations get [`FrameFlags::ALLOCATED`]: arena memory is never freed frame by frame.
+pub(crate) fn allocate_frame(
+    arena: Option<&Arena>,
+    shape: &'static Shape,
+) -> Result<(PtrUninit<'static>, FrameFlags), ReflectError> {
+    match arena {
+        Some(arena) => {
+            let layout = shape
+                .layout
+                .sized_layout()
+                .map_err(|_| ReflectError::Unsized { shape })?;
+            Ok((arena.allocate(layout), FrameFlags::EMPTY))
+        }
+        None => {
+            let data = shape
+                .allocate()
+                .map_err(|_| ReflectError::Unsized { shape })?;
+            Ok((data, FrameFlags::ALLOCATED))
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+
+use facet_core::{Def, ScalarAffinity, SequenceType, Shape, ShapeAttribute, Type, UserType};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use crate::deep_eq::unwrap;
+use crate::trace;
+use crate::{HeapValue, Peek, ReflectError, ScalarType, Wip};
+
+/// Converts a value into one of another shape with a compatible structure, such as the next
+/// version of a configuration struct.
+///
+/// The conversion goes as follows:
+///
+/// - struct fields, and the fields of enum variants, are matched by name. Fields that are only
+///   in `src` are dropped, and fields that are only in `dst_shape` get their default if they
+///   have one, or `None` for options;
+/// - enum variants are matched by name;
+/// - numbers are converted to other widths and kinds, failing if they're out of range;
+/// - `String`, `&str` and `Cow<str>` are converted between each other;
+/// - options, lists, arrays, maps and tuples have their contents converted, and a value can
+///   become the `Some` of an option or the inside of a transparent wrapper;
+/// - values of the same shape are cloned, see [`Wip::put_clone`].
+///
+/// `&str`s in the result may borrow from `src`.
+pub fn convert<'mem: 'facet_lifetime, 'facet_lifetime>(
+    src: Peek<'mem, 'facet_lifetime>,
+    dst_shape: &'static Shape,
+) -> Result<HeapValue<'facet_lifetime>, ReflectError> {
+    Wip::alloc_shape(dst_shape)?.put_converted(src)?.build()
+}
+
+impl<'facet_lifetime> Wip<'facet_lifetime> {
+    /// Puts `src` in the current frame, converted to its shape — see [`convert`].
+    pub fn put_converted<'mem: 'facet_lifetime>(
+        mut self,
+        src: Peek<'mem, 'facet_lifetime>,
+    ) -> Result<Self, ReflectError> {
+        let dst = self.shape();
+        if src.shape() == dst {
+            return self.put_clone(src);
+        }
+        let src = unwrap(src);
+        if src.shape() == dst {
+            return self.put_clone(src);
+        }
+        trace!(
+            "Converting a {} into a {}",
+            src.shape().yellow(),
+            dst.blue()
+        );
+
+        if let Def::Option(_) = dst.def {
+            return match src.into_option().map(|option| option.value()) {
+                Ok(Some(value)) => self.push_some()?.put_converted(value)?.pop(),
+                Ok(None) => self.put_default(),
+                Err(_) => self.push_some()?.put_converted(src)?.pop(),
+            };
+        }
+        if let Def::Option(_) = src.shape().def {
+            return match src.into_option()?.value() {
+                Some(value) => self.put_converted(value),
+                None => Err(ReflectError::WrongShape {
+                    expected: dst,
+                    actual: src.shape(),
+                }),
+            };
+        }
+        if dst.attributes.contains(&ShapeAttribute::Transparent) {
+            return self.field(0)?.put_converted(src)?.pop();
+        }
+
+        // `&str` isn't a `Def::Scalar`, `ScalarType` knows about it
+        if ScalarType::try_from_shape(dst).is_some()
+            && ScalarType::try_from_shape(src.shape()).is_some()
+        {
+            return self.put_converted_scalar(src);
+        }
+
+        match ((dst.ty, dst.def), (src.shape().ty, src.shape().def)) {
+            (
+                (_, Def::List(_) | Def::Array(_)),
+                (_, Def::List(_) | Def::Array(_) | Def::Slice(_)),
+            ) => {
+                let items = src.into_list_like()?;
+                if items.is_empty() && matches!(dst.def, Def::List(_)) {
+                    return self.put_empty_list();
+                }
+                self = self.begin_pushback()?;
+                for item in items.iter() {
+                    self = self.push()?.put_converted(item)?.pop()?;
+                }
+                Ok(self)
+            }
+            ((_, Def::Map(_)), (_, Def::Map(_))) => {
+                let entries = src.into_map()?;
+                if entries.is_empty() {
+                    return self.put_empty_map();
+                }
+                self = self.begin_map_insert()?;
+                for (key, value) in entries.iter() {
+                    self = self.push_map_key()?.put_converted(key)?;
+                    self = self.push_map_value()?.put_converted(value)?.pop()?;
+                }
+                Ok(self)
+            }
+            (
+                (Type::Sequence(SequenceType::Tuple(_)), _),
+                (Type::Sequence(SequenceType::Tuple(_)), _),
+            ) => {
+                self = self.begin_pushback()?;
+                for (_, item) in src.into_tuple()?.fields() {
+                    self = self.push()?.put_converted(item)?.pop()?;
+                }
+                Ok(self)
+            }
+            ((Type::User(UserType::Struct(sd)), _), (Type::User(UserType::Struct(_)), _)) => {
+                let src = src.into_struct()?;
+                for (index, field) in sd.fields.iter().enumerate() {
+                    if let Ok(value) = src.field_by_name(field.name) {
+                        self = self.field(index)?.put_converted(value)?.pop()?;
+                    }
+                }
+                self.fill_defaults()
+            }
+            ((Type::User(UserType::Enum(_)), _), (Type::User(UserType::Enum(_)), _)) => {
+                let shape = src.shape();
+                let src = src.into_enum()?;
+                let variant = src
+                    .active_variant()
+                    .map_err(|_| ReflectError::OperationFailed {
+                        shape,
+                        operation: "tried to convert an enum, but couldn't tell its variant",
+                    })?;
+                self = self.variant_named(variant.name)?;
+                // Safe to unwrap because the variant got just selected
+                let fields = self.selected_variant().unwrap().data.fields;
+                for (index, field) in fields.iter().enumerate() {
+                    if let Ok(Some(value)) = src.field_by_name(field.name) {
+                        self = self.field(index)?.put_converted(value)?.pop()?;
+                    }
+                }
+                self.fill_defaults()
+            }
+            _ => Err(ReflectError::WrongShape {
+                expected: dst,
+                actual: src.shape(),
+            }),
+        }
+    }
+
+    /// Converts between strings, and between numbers
+    fn put_converted_scalar<'mem: 'facet_lifetime>(
+        self,
+        src: Peek<'mem, 'facet_lifetime>,
+    ) -> Result<Self, ReflectError> {
+        let dst = self.shape();
+        let wrong_shape = || ReflectError::WrongShape {
+            expected: dst,
+            actual: src.shape(),
+        };
+
+        // Borrowed from `src`, which outlives the value being built, so it can go in a `&str`
+        let text: Option<&'facet_lifetime str> = match ScalarType::try_from_shape(src.shape()) {
+            Some(ScalarType::String) => Some(unsafe { src.data().get::<String>() }.as_str()),
+            Some(ScalarType::Str) => Some(*unsafe { src.data().get::<&'facet_lifetime str>() }),
+            Some(ScalarType::CowStr) => {
+                Some(unsafe { src.data().get::<Cow<'facet_lifetime, str>>() }.as_ref())
+            }
+            _ => None,
+        };
+        if let Some(text) = text {
+            return match ScalarType::try_from_shape(dst) {
+                Some(ScalarType::String) => self.put(text.to_string()),
+                Some(ScalarType::Str) => self.put::<&'facet_lifetime str>(text),
+                Some(ScalarType::CowStr) => self.put(Cow::<'facet_lifetime, str>::Borrowed(text)),
+                _ => Err(wrong_shape()),
+            };
+        }
+
+        let is_number = |shape: &'static Shape| match shape.def {
+            Def::Scalar(sd) => matches!(sd.affinity, ScalarAffinity::Number(_)),
+            _ => false,
+        };
+        if !is_number(dst) || !is_number(src.shape()) {
+            return Err(wrong_shape());
+        }
+        // Numbers are widened first: that's what the destination knows how to convert from
+        match ScalarType::try_from_shape(src.shape()) {
+            Some(ScalarType::U8) => self.try_put(*src.get::<u8>()? as u64),
+            Some(ScalarType::U16) => self.try_put(*src.get::<u16>()? as u64),
+            Some(ScalarType::U32) => self.try_put(*src.get::<u32>()? as u64),
+            Some(ScalarType::U64) => self.try_put(*src.get::<u64>()?),
+            Some(ScalarType::USize) => self.try_put(*src.get::<usize>()? as u64),
+            Some(ScalarType::U128) => self.try_put(*src.get::<u128>()?),
+            Some(ScalarType::I8) => self.try_put(*src.get::<i8>()? as i64),
+            Some(ScalarType::I16) => self.try_put(*src.get::<i16>()? as i64),
+            Some(ScalarType::I32) => self.try_put(*src.get::<i32>()? as i64),
+            Some(ScalarType::I64) => self.try_put(*src.get::<i64>()?),
+            Some(ScalarType::ISize) => self.try_put(*src.get::<isize>()? as i64),
+            Some(ScalarType::I128) => self.try_put(*src.get::<i128>()?),
+            Some(ScalarType::F32) => self.try_put(*src.get::<f32>()? as f64),
+            Some(ScalarType::F64) => self.try_put(*src.get::<f64>()?),
+            _ => Err(wrong_shape()),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use facet_core::{Characteristic, CloneIntoFn, Def, SequenceType, Type, UserType};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use crate::trace;
+use crate::{HasFields, Peek, ReflectError, Wip};
+
+impl<'facet_lifetime> Peek<'_, 'facet_lifetime> {
+    /// Deep-clones the value into a freshly allocated [`Wip`] of the same shape, ready to be
+    /// built — see [`Wip::put_clone`].
+    pub fn clone_into_wip(self) -> Result<Wip<'facet_lifetime>, ReflectError> {
+        Wip::alloc_shape(self.shape())?.put_clone(self)
+    }
+}
+
+impl<'facet_lifetime> Wip<'facet_lifetime> {
+    /// Puts a deep clone of `peek` in the current frame, which must have the same shape.
+    ///
+    /// Types that implement `Clone` are cloned with it. Otherwise, the value is rebuilt piece
+    /// by piece: structs and enum variants field by field, tuples, arrays and lists element by
+    /// element, maps entry by entry, and options by cloning what they hold.
+    ///
+    /// Fails with [`ReflectError::MissingCharacteristic`] if some value nested in `peek` has
+    /// no way of being cloned — a scalar, set or smart pointer that doesn't implement `Clone`.
+    pub fn put_clone(mut self, peek: Peek<'_, 'facet_lifetime>) -> Result<Self, ReflectError> {
+        let shape = self.shape();
+        if shape != peek.shape() {
+            return Err(ReflectError::WrongShape {
+                expected: shape,
+                actual: peek.shape(),
+            });
+        }
+
+        // A value that was set before gets overwritten, and cloning it piece by piece may
+        // still fail
+        self.deinit_current_resettable()?;
+        if let Some(clone_into) = shape.vtable.clone_into {
+            return self.put_cloned_with(clone_into, peek);
+        }
+        trace!("Cloning a {} piece by piece", shape.blue());
+
+        match (shape.ty, shape.def) {
+            (_, Def::Option(_)) => match peek.into_option()?.value() {
+                Some(value) => self.push_some()?.put_clone(value)?.pop(),
+                None => self.put_default(),
+            },
+            (_, Def::List(_) | Def::Array(_)) => {
+                let list = peek.into_list_like()?;
+                if list.is_empty() && matches!(shape.def, Def::List(_)) {
+                    return self.put_empty_list();
+                }
+                self = self.begin_pushback()?;
+                for item in list.iter() {
+                    self = self.push()?.put_clone(item)?.pop()?;
+                }
+                Ok(self)
+            }
+            (_, Def::Map(_)) => {
+                let map = peek.into_map()?;
+                if map.is_empty() {
+                    return self.put_empty_map();
+                }
+                self = self.begin_map_insert()?;
+                for (key, value) in map.iter() {
+                    self = self.push_map_key()?.put_clone(key)?;
+                    self = self.push_map_value()?.put_clone(value)?.pop()?;
+                }
+                Ok(self)
+            }
+            (Type::Sequence(SequenceType::Tuple(_)), _) => {
+                let tuple = peek.into_tuple()?;
+                self = self.begin_pushback()?;
+                for (_, item) in tuple.fields() {
+                    self = self.push()?.put_clone(item)?.pop()?;
+                }
+                Ok(self)
+            }
+            (Type::User(UserType::Struct(_)), _) => {
+                for (index, (_, value)) in peek.into_struct()?.fields().enumerate() {
+                    self = self.field(index)?.put_clone(value)?.pop()?;
+                }
+                Ok(self)
+            }
+            (Type::User(UserType::Enum(_)), _) => {
+                let peek = peek.into_enum()?;
+                let variant_index =
+                    peek.variant_index()
+                        .map_err(|_| ReflectError::OperationFailed {
+                            shape,
+                            operation: "tried to clone an enum, but couldn't tell its variant",
+                        })?;
+                self = self.variant(variant_index)?;
+                for (index, (_, value)) in peek.fields().enumerate() {
+                    self = self.field(index)?.put_clone(value)?.pop()?;
+                }
+                Ok(self)
+            }
+            _ => Err(ReflectError::MissingCharacteristic {
+                shape,
+                characteristic: Characteristic::Clone,
+            }),
+        }
+    }
+
+    /// Puts a clone of `peek` in the current frame with the shape's `clone_into`
+    fn put_cloned_with(
+        mut self,
+        clone_into: CloneIntoFn,
+        peek: Peek<'_, 'facet_lifetime>,
+    ) -> Result<Self, ReflectError> {
+        let frame = self.frames.last_mut().unwrap();
+
+        // Arrays count their elements to tell whether they're complete
+        if let Def::Array(array_def) = frame.shape.def {
+            frame.istate.list_index = Some(array_def.n);
+        }
+
+        unsafe {
+            clone_into(peek.data(), frame.data);
+            frame.mark_fully_initialized();
+        }
+
+        let shape = frame.shape;
+        let index = frame.field_index_in_parent;
+        self.mark_field_as_initialized(shape, index)?;
+
+        Ok(self)
+    }
+}
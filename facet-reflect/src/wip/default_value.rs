@@ -0,0 +1,133 @@
+use facet_core::{Characteristic, Def, Field, FieldFlags, SequenceType, Shape, Type, UserType};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use crate::trace;
+use crate::{HeapValue, ReflectError, Wip};
+
+/// Builds a default instance of `shape`, even if the type doesn't implement `Default`.
+///
+/// Types that implement `Default` use it. Otherwise, the value is built piece by piece:
+///
+/// - struct fields use their `#[facet(default)]` / `#[facet(default = ...)]` attribute if they
+///   have one, and are themselves defaulted otherwise;
+/// - enums are set to their first variant, with defaulted fields;
+/// - tuples and arrays have defaulted elements.
+///
+/// Fails with [`ReflectError::MissingCharacteristic`] if some value nested in `shape` has no
+/// default at all — a `NonZeroU32`, a `Box<T>` where `T` isn't `Default`, an enum without
+/// variants…
+pub fn default_value<'facet_lifetime>(
+    shape: &'static Shape,
+) -> Result<HeapValue<'facet_lifetime>, ReflectError> {
+    Wip::alloc_shape(shape)?.put_default_deep()?.build()
+}
+
+impl Wip<'_> {
+    /// Puts a default value in the current frame, building it piece by piece if its type
+    /// doesn't implement `Default` — see [`default_value`].
+    pub fn put_default_deep(mut self) -> Result<Self, ReflectError> {
+        let shape = self.shape();
+        if shape.is(Characteristic::Default) {
+            return self.put_default();
+        }
+        trace!("Building a default {} piece by piece", shape.blue());
+
+        match (shape.ty, shape.def) {
+            (_, Def::Array(array_def)) => {
+                self = self.begin_pushback()?;
+                for _ in 0..array_def.n {
+                    self = self.push()?.put_default_deep()?.pop()?;
+                }
+                Ok(self)
+            }
+            (Type::Sequence(SequenceType::Tuple(tt)), _) => {
+                self = self.begin_pushback()?;
+                for _ in tt.fields {
+                    self = self.push()?.put_default_deep()?.pop()?;
+                }
+                Ok(self)
+            }
+            // Scalars like `NonZero<u32>` may look like structs, but their fields can't be
+            // defaulted one by one
+            (_, Def::Scalar(_)) => Err(ReflectError::MissingCharacteristic {
+                shape,
+                characteristic: Characteristic::Default,
+            }),
+            (Type::User(UserType::Struct(sd)), _) => self.put_default_fields(sd.fields),
+            (Type::User(UserType::Enum(ed)), _) => {
+                let Some(variant) = ed.variants.first() else {
+                    return Err(ReflectError::MissingCharacteristic {
+                        shape,
+                        characteristic: Characteristic::Default,
+                    });
+                };
+                self.variant(0)?.put_default_fields(variant.data.fields)
+            }
+            _ => Err(ReflectError::MissingCharacteristic {
+                shape,
+                characteristic: Characteristic::Default,
+            }),
+        }
+    }
+
+    /// Fills the fields of the current struct, or of the selected variant, that weren't set:
+    /// fields marked `#[facet(default)]` or `#[facet(default = ...)]` get their default, and
+    /// `Option` fields get `None`. Other fields are left unset, for [`Wip::build`] to report.
+    ///
+    /// Deserializers call this once they've put everything the input had for a struct.
+    pub fn fill_defaults(mut self) -> Result<Self, ReflectError> {
+        let shape = self.shape();
+        let fields = match shape.ty {
+            Type::User(UserType::Struct(sd)) => sd.fields,
+            Type::User(UserType::Enum(_)) => match self.selected_variant() {
+                Some(variant) => variant.data.fields,
+                None => {
+                    return Err(ReflectError::OperationFailed {
+                        shape,
+                        operation: "tried to fill defaults, but no variant was selected",
+                    });
+                }
+            },
+            _ => {
+                return Err(ReflectError::WasNotA {
+                    expected: "struct or enum",
+                    actual: shape,
+                });
+            }
+        };
+
+        for (index, field) in fields.iter().enumerate() {
+            if self.is_field_set(index)? {
+                continue;
+            }
+            if field.flags.contains(FieldFlags::DEFAULT) {
+                trace!("Defaulting field {}", field.name.blue());
+                self = self.field(index)?;
+                self = match field.vtable.default_fn {
+                    Some(default_fn) => self.put_from_fn(default_fn)?,
+                    None => self.put_default()?,
+                };
+                self = self.pop()?;
+            } else if let Def::Option(_) = field.shape().def {
+                self = self.field(index)?.put_default()?.pop()?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Defaults the fields of a struct, or of the selected variant of an enum
+    fn put_default_fields(mut self, fields: &'static [Field]) -> Result<Self, ReflectError> {
+        for (index, field) in fields.iter().enumerate() {
+            self = self.field(index)?;
+            self = match field.vtable.default_fn {
+                Some(default_fn) if field.flags.contains(FieldFlags::DEFAULT) => {
+                    self.put_from_fn(default_fn)?
+                }
+                _ => self.put_default_deep()?,
+            };
+            self = self.pop()?;
+        }
+        Ok(self)
+    }
+}
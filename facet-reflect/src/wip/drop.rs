@@ -4,8 +4,10 @@ use facet_core::{Type, UserType};
 #[allow(unused_imports)]
 use owo_colors::OwoColorize;
 
-use super::Wip;
-use crate::{FrameFlags, FrameMode, Guard, ValueId, trace, wip::frame::Frame};
+use super::{Wip, in_place::restore_borrowed};
+use crate::{
+    FrameFlags, FrameMode, Guard, ValueId, drop_in_place_iteratively, trace, wip::frame::Frame,
+};
 
 impl Drop for Wip<'_> {
     fn drop(&mut self) {
@@ -23,7 +25,21 @@ impl Drop for Wip<'_> {
         let root_id = *root_id;
         let root_istate = self.istates.remove(&root_id).unwrap();
         let root = Frame::recompose(root_id, root_istate);
-        let mut to_clean = vec![root];
+        let mut to_clean = vec![];
+
+        // a value edited in place is never ours to drop: it's either left as it is, or reset
+        let mut borrowed_root = None;
+        if root.istate.flags.contains(FrameFlags::BORROWED) {
+            if root.is_fully_initialized() {
+                trace!("Borrowed root is fully initialized, leaving it as it is");
+                self.evict_tree(root);
+            } else {
+                borrowed_root = Some((root.data, root.shape));
+                to_clean.push(root);
+            }
+        } else {
+            to_clean.push(root);
+        }
 
         let mut _root_guard: Option<Guard> = None;
 
@@ -81,9 +97,13 @@ impl Drop for Wip<'_> {
                                     field_shape.green(),
                                     field_ptr.as_byte_ptr()
                                 );
-                                let istate = self.istates.remove(&field_id).unwrap();
-                                let field_frame = Frame::recompose(field_id, istate);
-                                to_clean.push(field_frame);
+                                if let Some(istate) = self.istates.remove(&field_id) {
+                                    let field_frame = Frame::recompose(field_id, istate);
+                                    to_clean.push(field_frame);
+                                } else {
+                                    // it was already there when editing in place
+                                    unsafe { drop_in_place_iteratively(field_ptr, field_shape) };
+                                }
                             } else {
                                 trace!(
                                     "Field #{} '{}' of {} was NOT initialized, skipping",
@@ -95,11 +115,14 @@ impl Drop for Wip<'_> {
                         }
 
                         // we'll also need to clean up if we're root
-                        if frame.istate.mode == FrameMode::Root {
+                        if frame.istate.mode == FrameMode::Root
+                            && !frame.istate.flags.contains(FrameFlags::BORROWED)
+                        {
                             if let Ok(layout) = frame.shape.layout.sized_layout() {
                                 _root_guard = Some(Guard {
                                     ptr: frame.data.as_mut_byte_ptr(),
                                     layout,
+                                    arena: self.arena.clone(),
                                 });
                             }
                         }
@@ -146,13 +169,7 @@ impl Drop for Wip<'_> {
                                         variant.name.yellow()
                                     );
                                     // that means it's fully initialized and we need to drop it
-                                    unsafe {
-                                        if let Some(drop_in_place) =
-                                            field_shape.vtable.drop_in_place
-                                        {
-                                            drop_in_place(field_ptr);
-                                        }
-                                    }
+                                    unsafe { drop_in_place_iteratively(field_ptr, field_shape) };
                                 }
                             } else {
                                 trace!(
@@ -171,11 +188,14 @@ impl Drop for Wip<'_> {
                     }
 
                     // we'll also need to clean up if we're root
-                    if frame.istate.mode == FrameMode::Root {
+                    if frame.istate.mode == FrameMode::Root
+                        && !frame.istate.flags.contains(FrameFlags::BORROWED)
+                    {
                         if let Ok(layout) = frame.shape.layout.sized_layout() {
                             _root_guard = Some(Guard {
                                 ptr: frame.data.as_mut_byte_ptr(),
                                 layout,
+                                arena: self.arena.clone(),
                             });
                         }
                     }
@@ -218,5 +238,9 @@ impl Drop for Wip<'_> {
                 frame.dealloc_if_needed();
             }
         }
+
+        if let Some((data, shape)) = borrowed_root {
+            unsafe { restore_borrowed(data, shape) };
+        }
     }
 }
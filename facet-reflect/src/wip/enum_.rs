@@ -1,9 +1,11 @@
-use facet_core::{FieldError, Type, UserType, Variant};
+use facet_core::{EnumType, FieldError, Type, UserType, Variant};
 #[cfg(feature = "log")]
 use owo_colors::OwoColorize;
 
 use crate::trace;
-use crate::{ISet, ReflectError, Wip};
+use crate::{ISet, ReflectError, ValueId, Wip, drop_in_place_iteratively};
+
+use super::Frame;
 
 impl Wip<'_> {
     /// Selects a variant of an enum by index.
@@ -38,6 +40,32 @@ impl Wip<'_> {
 
         let variant = def.variants[index];
 
+        // Drop whatever the previously selected variant held, e.g. when editing in place
+        if let Some(old_variant) = frame.istate.variant {
+            self.check_resettable()?;
+            let frame = self.frames.last_mut().unwrap();
+            let (data, fields) = (frame.data, frame.istate.fields);
+            for (i, field) in old_variant.data.fields.iter().enumerate() {
+                if !fields.has(i) {
+                    continue;
+                }
+                let field_shape = field.shape();
+                let field_ptr = unsafe { data.field_init_at(field.offset) };
+                let field_id = ValueId::new(field_shape, field_ptr.as_byte_ptr());
+                if let Some(istate) = self.istates.remove(&field_id) {
+                    self.evict_tree(Frame::recompose(field_id, istate));
+                }
+                trace!(
+                    "Dropping field {} of previous variant {}",
+                    field.name.yellow(),
+                    old_variant.name.yellow()
+                );
+                unsafe { drop_in_place_iteratively(field_ptr, field_shape) };
+            }
+            self.unmark_ancestors();
+        }
+        let frame = self.frames.last_mut().unwrap();
+
         // Reset the field initialization state since we're selecting a new variant
         ISet::clear(&mut frame.istate.fields);
 
@@ -47,23 +75,23 @@ impl Wip<'_> {
                 let data_ptr = frame.data.as_mut_byte_ptr();
                 match def.enum_repr {
                     facet_core::EnumRepr::U8 => *data_ptr = discriminant as u8,
-                    facet_core::EnumRepr::U16 => *(data_ptr as *mut u16) = discriminant as u16,
-                    facet_core::EnumRepr::U32 => *(data_ptr as *mut u32) = discriminant as u32,
-                    facet_core::EnumRepr::U64 => *(data_ptr as *mut u64) = discriminant as u64,
+                    facet_core::EnumRepr::U16 => *data_ptr.cast::<u16>() = discriminant as u16,
+                    facet_core::EnumRepr::U32 => *data_ptr.cast::<u32>() = discriminant as u32,
+                    facet_core::EnumRepr::U64 => *data_ptr.cast::<u64>() = discriminant as u64,
                     facet_core::EnumRepr::USize => {
-                        *(data_ptr as *mut usize) = discriminant as usize
+                        *data_ptr.cast::<usize>() = discriminant as usize
                     }
-                    facet_core::EnumRepr::I8 => *(data_ptr as *mut i8) = discriminant as i8,
-                    facet_core::EnumRepr::I16 => *(data_ptr as *mut i16) = discriminant as i16,
-                    facet_core::EnumRepr::I32 => *(data_ptr as *mut i32) = discriminant as i32,
-                    facet_core::EnumRepr::I64 => *(data_ptr as *mut i64) = discriminant,
+                    facet_core::EnumRepr::I8 => *data_ptr.cast::<i8>() = discriminant as i8,
+                    facet_core::EnumRepr::I16 => *data_ptr.cast::<i16>() = discriminant as i16,
+                    facet_core::EnumRepr::I32 => *data_ptr.cast::<i32>() = discriminant as i32,
+                    facet_core::EnumRepr::I64 => *data_ptr.cast::<i64>() = discriminant,
                     facet_core::EnumRepr::ISize => {
-                        *(data_ptr as *mut isize) = discriminant as isize
+                        *data_ptr.cast::<isize>() = discriminant as isize
                     }
                     facet_core::EnumRepr::RustNPO => (),
                     _ => {
                         // Default to a reasonable size for other representations
-                        *(data_ptr as *mut u32) = discriminant as u32;
+                        *data_ptr.cast::<u32>() = discriminant as u32;
                     }
                 }
             }
@@ -103,14 +131,38 @@ impl Wip<'_> {
             });
         };
 
-        let index =
-            def.variants
-                .iter()
-                .position(|v| v.name == name)
-                .ok_or(ReflectError::FieldError {
-                    shape,
-                    field_error: FieldError::NoSuchField,
-                })?;
+        let index = def.variant_index(name).ok_or(ReflectError::FieldError {
+            shape,
+            field_error: FieldError::NoSuchField,
+        })?;
+
+        self.variant(index)
+    }
+
+    /// Selects a variant of an enum by its discriminant value, e.g. one read by a binary format
+    /// that encodes variants as integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `discriminant` - The discriminant of the variant to select (`2` for `B = 2`).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the variant was successfully selected.
+    /// * `Err(ReflectError)` if the current frame is not an enum or no variant has that discriminant.
+    pub fn variant_by_discriminant(self, discriminant: i64) -> Result<Self, ReflectError> {
+        let frame = self.frames.last().unwrap();
+        let shape = frame.shape;
+        let Type::User(UserType::Enum(def)) = shape.ty else {
+            return Err(ReflectError::WasNotA {
+                expected: "enum",
+                actual: shape,
+            });
+        };
+
+        let index = def
+            .variant_index_for_discriminant(discriminant)
+            .ok_or(ReflectError::NoSuchVariant { enum_type: def })?;
 
         self.variant(index)
     }
@@ -128,16 +180,47 @@ impl Wip<'_> {
     pub fn find_variant(&self, name: &str) -> Option<(usize, Variant)> {
         let frame = self.frames.last()?;
         if let Type::User(UserType::Enum(def)) = frame.shape.ty {
-            def.variants
-                .iter()
-                .enumerate()
-                .find(|(_, v)| v.name == name)
-                .map(|(i, &v)| (i, v))
+            def.variant_index(name).map(|i| (i, def.variants[i]))
         } else {
             None
         }
     }
 
+    /// Finds a variant in an enum by its discriminant value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(index, variant)` if a variant with the given discriminant exists.
+    /// * `None` if the current frame is not an enum or no variant has that discriminant.
+    pub fn find_variant_by_discriminant(&self, discriminant: i64) -> Option<(usize, Variant)> {
+        let def = self.enum_type()?;
+        def.variant_index_for_discriminant(discriminant)
+            .map(|i| (i, def.variants[i]))
+    }
+
+    /// Returns all variants of the enum in the current frame, in declaration order.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(variants)` if the current frame is an enum.
+    /// * `None` otherwise.
+    pub fn variants(&self) -> Option<&'static [Variant]> {
+        self.enum_type().map(|def| def.variants)
+    }
+
+    /// Returns the number of variants of the enum in the current frame, or `None` if it isn't
+    /// an enum.
+    pub fn variant_count(&self) -> Option<usize> {
+        self.variants().map(|variants| variants.len())
+    }
+
+    fn enum_type(&self) -> Option<EnumType> {
+        match self.frames.last()?.shape.ty {
+            Type::User(UserType::Enum(def)) => Some(def),
+            _ => None,
+        }
+    }
+
     /// Returns the currently selected variant for the enum in the current frame.
     ///
     /// # Returns
@@ -5,7 +5,7 @@ use facet_core::{Def, PointerType, PrimitiveType, PtrUninit, SequenceType, Shape
 #[allow(unused_imports)]
 use owo_colors::OwoColorize;
 
-use crate::{ISet, Peek, ValueId};
+use crate::{ISet, Peek, ValueId, drop_in_place_iteratively};
 
 use super::{FrameFlags, IState};
 
@@ -41,6 +41,7 @@ fn def_kind(def: &Def) -> &'static str {
         Def::Map(_) => "map",
         Def::List(_) => "list",
         Def::Option(_) => "option",
+        Def::Result(_) => "result",
         Def::SmartPointer(_) => "smart_ptr",
         _ => "other",
     }
@@ -68,7 +69,7 @@ impl Frame {
     /// Given a ValueId and an IState, recompose a Frame suitable for tracking
     pub(crate) fn recompose(id: ValueId, istate: IState) -> Self {
         Frame {
-            data: PtrUninit::new(id.ptr as *mut u8),
+            data: PtrUninit::new(id.ptr.cast_mut()),
             shape: id.shape,
             field_index_in_parent: None,
             istate,
@@ -148,14 +149,14 @@ impl Frame {
             self.shape.blue(),
             self.data.as_byte_ptr()
         );
-        if let Some(drop_in_place) = self.shape.vtable.drop_in_place {
+        if self.shape.vtable.drop_in_place.is_some() {
             unsafe {
                 trace!(
                     "[Frame::drop] Invoking drop_in_place for shape {} at {:p}",
                     self.shape.green(),
                     self.data.as_byte_ptr()
                 );
-                drop_in_place(self.data.assume_init());
+                drop_in_place_iteratively(self.data.assume_init(), self.shape);
             }
         } else {
             trace!(
@@ -1,8 +1,11 @@
+use crate::Arena;
 use crate::Peek;
 use crate::ReflectError;
+use crate::drop_in_place_iteratively;
 use crate::trace;
-use core::{alloc::Layout, marker::PhantomData};
-use facet_core::{Facet, PtrConst, PtrMut, Shape};
+use alloc::boxed::Box;
+use core::{alloc::Layout, marker::PhantomData, mem::ManuallyDrop};
+use facet_core::{Facet, PtrConst, PtrMut, PtrUninit, Shape};
 #[cfg(feature = "log")]
 use owo_colors::OwoColorize as _;
 
@@ -16,9 +19,7 @@ pub struct HeapValue<'facet_lifetime> {
 impl Drop for HeapValue<'_> {
     fn drop(&mut self) {
         if let Some(guard) = self.guard.take() {
-            if let Some(drop_fn) = self.shape.vtable.drop_in_place {
-                unsafe { drop_fn(PtrMut::new(guard.ptr)) };
-            }
+            unsafe { drop_in_place_iteratively(PtrMut::new(guard.ptr), self.shape) };
             drop(guard);
         }
     }
@@ -45,6 +46,44 @@ impl<'facet_lifetime> HeapValue<'facet_lifetime> {
         drop(guard); // free memory (but don't drop in place)
         Ok(res)
     }
+
+    /// Turn this heapvalue into a `Box` of a concrete type, reusing its memory unless it
+    /// lives in an arena
+    pub fn into_box<T: Facet<'facet_lifetime>>(mut self) -> Result<Box<T>, ReflectError> {
+        if self.shape != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: T::SHAPE,
+            });
+        }
+
+        let guard = self.guard.as_ref().unwrap();
+        if guard.arena.is_some() || guard.layout.size() == 0 {
+            return self.materialize::<T>().map(Box::new);
+        }
+
+        // The memory was allocated with the global allocator, with `T`'s layout
+        let guard = ManuallyDrop::new(self.guard.take().unwrap());
+        Ok(unsafe { Box::from_raw(guard.ptr.cast::<T>()) })
+    }
+
+    /// Moves the value to `dst`, and frees the memory it was in
+    ///
+    /// # Safety
+    ///
+    /// `dst` must be valid for writes of a value of this shape, and suitably aligned for it.
+    pub(crate) unsafe fn move_into(mut self, dst: PtrUninit<'_>) -> PtrMut<'_> {
+        let guard = self.guard.take().unwrap();
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                guard.ptr.cast_const(),
+                dst.as_mut_byte_ptr(),
+                guard.layout.size(),
+            );
+        }
+        drop(guard); // free memory (but don't drop in place)
+        unsafe { dst.assume_init() }
+    }
 }
 
 impl HeapValue<'_> {
@@ -125,11 +164,14 @@ pub struct Guard {
     pub(crate) ptr: *mut u8,
     /// Layout information of the allocated memory.
     pub(crate) layout: Layout,
+    /// The arena the memory came from, if any — it's freed along with the arena then,
+    /// which this keeps alive.
+    pub(crate) arena: Option<Arena>,
 }
 
 impl Drop for Guard {
     fn drop(&mut self) {
-        if self.layout.size() != 0 {
+        if self.layout.size() != 0 && self.arena.is_none() {
             trace!(
                 "Deallocating memory at ptr: {:p}, size: {}, align: {}",
                 self.ptr.cyan(),
@@ -0,0 +1,205 @@
+use core::marker::PhantomData;
+
+use facet_core::{Facet, PtrMut, PtrUninit, Shape, Type, UserType};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use super::{Frame, FrameFlags, FrameMode, IState, Wip, drop_in_place_iteratively};
+use crate::{ISet, ReflectError, debug};
+
+impl<'facet_lifetime> Wip<'facet_lifetime> {
+    /// Starts editing an existing value in place, rather than building a new one.
+    ///
+    /// Fields can be overwritten, lists and maps pushed into, and enum variants switched,
+    /// without anything being reallocated; [`Wip::finish`] then hands the value back.
+    ///
+    /// If the `Wip` is dropped, or fails to finish, while the value is half-edited, e.g. after
+    /// switching an enum to a variant whose fields weren't all set yet, the value is reset to
+    /// its default. Operations that would leave it half-edited fail if it has none.
+    pub fn from_mut<T: Facet<'facet_lifetime>>(value: &'facet_lifetime mut T) -> Self {
+        unsafe { Self::from_ptr_mut(PtrMut::new(value as *mut T), T::SHAPE) }
+    }
+
+    /// Starts editing the value of the given shape at `data` in place, see [`Wip::from_mut`]
+    ///
+    /// # Safety
+    ///
+    /// `data` must point to an initialized value of type `shape`, which nothing else
+    /// accesses for as long as the `Wip` lives.
+    pub unsafe fn from_ptr_mut(data: PtrMut<'facet_lifetime>, shape: &'static Shape) -> Self {
+        let mut root = Frame {
+            data: PtrUninit::new(data.as_mut_byte_ptr()),
+            shape,
+            field_index_in_parent: None,
+            istate: IState::new(0, FrameMode::Root, FrameFlags::BORROWED),
+        };
+        unsafe { root.mark_fully_initialized() };
+        debug!("Editing a {} in place", shape.blue());
+
+        Self {
+            frames: alloc::vec![root],
+            istates: Default::default(),
+            arena: None,
+            invariant: PhantomData,
+        }
+    }
+
+    /// Finishes editing a value in place, started with [`Wip::from_mut`].
+    ///
+    /// Like [`Wip::build`], this checks everything is initialized and that invariants are
+    /// upheld, if any.
+    pub fn finish(mut self) -> Result<(), ReflectError> {
+        if self.frames.len() != 1 {
+            return Err(ReflectError::OperationFailed {
+                shape: self.shape(),
+                operation: "tried to finish with frames left, they must be popped first",
+            });
+        }
+
+        let root_frame = &self.frames[0];
+        if !root_frame.istate.flags.contains(FrameFlags::BORROWED) {
+            return Err(ReflectError::OperationFailed {
+                shape: root_frame.shape,
+                operation: "tried to finish a value that isn't borrowed, it must be built instead",
+            });
+        }
+        self.check_root()?;
+
+        // The value stays where it is, so there's nothing left for Drop to do
+        {
+            super::FlatMap::clear(&mut self.istates);
+            self.frames.clear();
+        }
+        Ok(())
+    }
+
+    /// Fails if the current frame is part of a value edited in place that couldn't be reset if
+    /// it was left half-edited, because it has no default
+    pub(crate) fn check_resettable(&self) -> Result<(), ReflectError> {
+        let root = &self.frames[0];
+        if root.istate.flags.contains(FrameFlags::BORROWED)
+            && root.shape.vtable.default_in_place.is_none()
+        {
+            return Err(ReflectError::OperationFailed {
+                shape: root.shape,
+                operation: "tried to half-edit a value in place that has no default to reset it to",
+            });
+        }
+        Ok(())
+    }
+
+    /// Like [`Wip::deinit_current`], for writers that may still fail once the old value is
+    /// dropped, e.g. parsing: those would leave a value edited in place half-edited
+    pub(crate) fn deinit_current_resettable(&mut self) -> Result<(), ReflectError> {
+        let frame = self.frames.last().unwrap();
+        if frame.istate.variant.is_some() || frame.istate.fields.is_any_set() {
+            self.check_resettable()?;
+        }
+        self.deinit_current();
+        Ok(())
+    }
+
+    /// Drops whatever the current frame holds, if anything, so it can be overwritten, e.g. when
+    /// a field of a value edited in place is put again
+    pub(crate) fn deinit_current(&mut self) {
+        let frame = self.frames.last().unwrap();
+        if !(frame.istate.variant.is_some() || frame.istate.fields.is_any_set()) {
+            return;
+        }
+
+        // The saved states of fields that were visited before are stale once they're dropped
+        let frame = self.frames.pop().unwrap();
+        let frame = self.evict_tree(frame);
+        self.frames.push(frame);
+
+        let frame = self.frames.last_mut().unwrap();
+        debug!("De-initializing partially initialized {:?}", frame.yellow());
+
+        match frame.shape.ty {
+            // A whole value is dropped as one, so that its own `Drop` impl runs
+            _ if frame.is_fully_initialized() => unsafe {
+                drop_in_place_iteratively(frame.data.assume_init(), frame.shape);
+            },
+            Type::User(UserType::Struct(sd)) => {
+                for (i, field) in sd.fields.iter().enumerate() {
+                    if frame.istate.fields.has(i) {
+                        if let Some(drop_fn) = field.shape().vtable.drop_in_place {
+                            unsafe {
+                                let field_ptr = frame.data.as_mut_byte_ptr().add(field.offset);
+                                drop_fn(PtrMut::new(field_ptr));
+                            }
+                        }
+                    }
+                }
+            }
+            Type::User(UserType::Enum(_)) => {
+                if let Some(variant) = &frame.istate.variant {
+                    for (i, field) in variant.data.fields.iter().enumerate() {
+                        if frame.istate.fields.has(i) {
+                            if let Some(drop_fn) = field.shape().vtable.drop_in_place {
+                                unsafe {
+                                    let field_ptr = frame.data.as_mut_byte_ptr().add(field.offset);
+                                    drop_fn(PtrMut::new(field_ptr));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                // For scalar types and other non-struct/enum, attempt to drop the field in place if initialized
+                if frame.istate.fields.is_any_set() {
+                    debug!("Scalar type was set...");
+                    if let Some(drop_fn) = frame.shape.vtable.drop_in_place {
+                        debug!("And it has a drop fn, dropping now!");
+                        unsafe {
+                            drop_fn(frame.data.assume_init());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Reset initialization state
+        frame.istate.variant = None;
+        ISet::clear(&mut frame.istate.fields);
+
+        if let (Some(index), Some(parent_index)) = (
+            self.frames.last().unwrap().field_index_in_parent,
+            self.frames.len().checked_sub(2),
+        ) {
+            self.frames[parent_index].istate.fields.unset(index);
+        }
+    }
+
+    /// Unmarks the current frame as initialized in its parent, and the parent in its own, and so
+    /// on up the stack, after part of it was de-initialized
+    pub(crate) fn unmark_ancestors(&mut self) {
+        for i in (1..self.frames.len()).rev() {
+            let (FrameMode::Field, Some(index)) = (
+                self.frames[i].istate.mode,
+                self.frames[i].field_index_in_parent,
+            ) else {
+                break;
+            };
+            self.frames[i - 1].istate.fields.unset(index);
+        }
+    }
+}
+
+/// Gives a borrowed value back in a valid state, after it was left half-edited and its parts
+/// that were set have been dropped: resets it to its default.
+///
+/// # Safety
+///
+/// `data` must point to memory for a value of type `shape`, with nothing left to drop in it.
+/// `shape` must have a default, which [`Wip::check_resettable`] makes sure of before anything
+/// is de-initialized.
+pub(crate) unsafe fn restore_borrowed(data: PtrUninit<'_>, shape: &'static Shape) {
+    debug!("Resetting half-edited {} to its default", shape.blue());
+    let default_in_place = shape
+        .vtable
+        .default_in_place
+        .expect("values edited in place are only half-edited if they have a default");
+    unsafe { default_in_place(data) };
+}
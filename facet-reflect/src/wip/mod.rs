@@ -1,4 +1,4 @@
-use crate::{ReflectError, ValueId};
+use crate::{Peek, ReflectError, ValueId};
 use crate::{debug, trace};
 #[cfg(feature = "log")]
 use alloc::string::ToString;
@@ -11,12 +11,14 @@ mod pop;
 mod frame;
 pub(crate) use frame::*;
 
+use alloc::boxed::Box;
 use alloc::format;
 use bitflags::bitflags;
 use core::marker::PhantomData;
+use core::mem::MaybeUninit;
 use facet_core::{
-    Def, DefaultInPlaceFn, Facet, FieldError, PtrConst, PtrUninit, ScalarAffinity, SequenceType,
-    Shape, Type, UserType, Variant,
+    Def, DefaultInPlaceFn, DeserializeWith, Facet, FieldError, Lens, PtrConst, PtrMut, PtrUninit,
+    ScalarAffinity, SequenceType, Shape, Type, UserType, Variant,
 };
 use flat_map::FlatMap;
 
@@ -27,13 +29,38 @@ pub use iset::*;
 
 mod put_f64;
 mod put_shape;
+mod put_unix_nanos;
 
 mod enum_;
 mod flat_map;
+mod proxy;
+mod result;
+mod smart_pointer;
+
+mod in_place;
 
 mod heap_value;
 pub use heap_value::*;
 
+mod typed;
+pub use typed::*;
+
+mod arena;
+pub use arena::*;
+
+mod teardown;
+pub(crate) use teardown::*;
+
+mod default_value;
+pub use default_value::*;
+
+mod deep_clone;
+
+mod convert;
+pub use convert::*;
+
+mod validate;
+
 /// Initialization state
 pub(crate) struct IState {
     /// Variant chosen — for everything except enums, this stays None
@@ -73,6 +100,10 @@ bitflags! {
         /// we shouldn't error out when we build and we notice it's not initialized.
         /// In fact, it should not be tracked at all.
         const MOVED = 1 << 1;
+
+        /// This value isn't ours, it was borrowed with `Wip::from_mut`: it's never dropped nor
+        /// deallocated, and it must be left fully initialized.
+        const BORROWED = 1 << 2;
     }
 
     // Note: there is no 'initialized' flag because initialization can be partial — it's tracked via `ISet`
@@ -128,6 +159,17 @@ pub enum FrameMode {
     /// Frame represents the None variant of an option (no allocation needed)
     /// Any `put` should fail
     OptionNone,
+    /// Frame represents the value in the Ok variant of a result (that we allocated)
+    ResultOk,
+    /// Frame represents the value in the Err variant of a result (that we allocated)
+    ResultErr,
+    /// Frame represents the pointee of a smart pointer (that we allocated)
+    Pointee,
+    /// Frame represents the proxy a field or value is deserialized as (that we allocated)
+    Proxy {
+        /// How the proxy is converted to the field's value
+        with: DeserializeWith,
+    },
 }
 
 /// A work-in-progress heap-allocated value
@@ -138,6 +180,9 @@ pub struct Wip<'facet_lifetime> {
     /// keeps track of initialization of out-of-tree frames
     istates: FlatMap<ValueId, IState>,
 
+    /// where frames get their memory from, if not the global allocator
+    arena: Option<Arena>,
+
     invariant: PhantomData<fn(&'facet_lifetime ()) -> &'facet_lifetime ()>,
 }
 
@@ -150,33 +195,68 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         self.put_shape(peek.data, peek.shape)
     }
 
+    /// Moves an already-built value into the current frame, converting it like [`Wip::put`]
+    /// would — into a `Box` of it, say.
+    pub fn put_heap_value(
+        self,
+        mut value: HeapValue<'facet_lifetime>,
+    ) -> Result<Wip<'facet_lifetime>, ReflectError> {
+        let guard = value.guard.take().unwrap();
+        let res = self.put_shape(PtrConst::new(guard.ptr), value.shape);
+        if res.is_err() {
+            // The value wasn't moved anywhere, so it's still ours to drop
+            unsafe { drop_in_place_iteratively(PtrMut::new(guard.ptr), value.shape) };
+        }
+        drop(guard); // free memory: the value was either moved into the Wip or dropped above
+        res
+    }
+
     /// Returns the number of frames on the stack
     pub fn frames_count(&self) -> usize {
         self.frames.len()
     }
 
+    /// Returns the arena this value is allocated in, if any
+    pub fn arena(&self) -> Option<&Arena> {
+        self.arena.as_ref()
+    }
+
     /// Allocates a new value of the given shape
     pub fn alloc_shape(shape: &'static Shape) -> Result<Self, ReflectError> {
-        let data = shape
-            .allocate()
-            .map_err(|_| ReflectError::Unsized { shape })?;
+        Self::alloc_root(shape, None)
+    }
+
+    /// Allocates a new value of type `S`
+    pub fn alloc<S: Facet<'facet_lifetime>>() -> Result<Self, ReflectError> {
+        Self::alloc_shape(S::SHAPE)
+    }
+
+    /// Allocates a new value of the given shape in `arena`, along with everything
+    /// allocated while building it
+    pub fn alloc_shape_in(shape: &'static Shape, arena: &Arena) -> Result<Self, ReflectError> {
+        Self::alloc_root(shape, Some(arena.clone()))
+    }
+
+    /// Allocates a new value of type `S` in `arena`, see [`Wip::alloc_shape_in`]
+    pub fn alloc_in<S: Facet<'facet_lifetime>>(arena: &Arena) -> Result<Self, ReflectError> {
+        Self::alloc_shape_in(S::SHAPE, arena)
+    }
+
+    fn alloc_root(shape: &'static Shape, arena: Option<Arena>) -> Result<Self, ReflectError> {
+        let (data, flags) = allocate_frame(arena.as_ref(), shape)?;
         Ok(Self {
             frames: alloc::vec![Frame {
                 data,
                 shape,
                 field_index_in_parent: None,
-                istate: IState::new(0, FrameMode::Root, FrameFlags::ALLOCATED),
+                istate: IState::new(0, FrameMode::Root, flags),
             }],
             istates: Default::default(),
+            arena,
             invariant: PhantomData,
         })
     }
 
-    /// Allocates a new value of type `S`
-    pub fn alloc<S: Facet<'facet_lifetime>>() -> Result<Self, ReflectError> {
-        Self::alloc_shape(S::SHAPE)
-    }
-
     fn track(&mut self, frame: Frame) {
         // fields might be partially initialized (in-place) and then
         // we might come back to them, so because they're popped off
@@ -205,7 +285,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                     // Ensure all owned fields within structs/enums are also marked.
                     match id.shape.ty {
                         Type::User(UserType::Struct(sd)) => {
-                            let container_ptr = PtrUninit::new(id.ptr as *mut u8);
+                            let container_ptr = PtrUninit::new(id.ptr.cast_mut());
                             for field in sd.fields.iter() {
                                 let field_ptr_uninit = container_ptr.field_uninit_at(field.offset);
                                 let field_id =
@@ -217,7 +297,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                         Type::User(UserType::Enum(_)) => {
                             // Use the variant info from the processed istate.
                             if let Some(variant) = &istate.variant {
-                                let container_ptr = PtrUninit::new(id.ptr as *mut u8);
+                                let container_ptr = PtrUninit::new(id.ptr.cast_mut());
                                 for field in variant.data.fields.iter() {
                                     let field_ptr_uninit =
                                         container_ptr.field_uninit_at(field.offset);
@@ -263,7 +343,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             // This ensures consistency if fields were pushed/popped and stored in `istates`.
             match frame.shape.ty {
                 Type::User(UserType::Struct(sd)) => {
-                    let container_ptr = PtrUninit::new(frame_id.ptr as *mut u8);
+                    let container_ptr = PtrUninit::new(frame_id.ptr.cast_mut());
                     for field in sd.fields.iter() {
                         let field_ptr_uninit = container_ptr.field_uninit_at(field.offset);
                         let field_id = ValueId::new(field.shape(), field_ptr_uninit.as_byte_ptr());
@@ -273,7 +353,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 Type::User(UserType::Enum(_)) => {
                     // Use the saved variant information for recursion
                     if let Some(variant) = &variant_opt {
-                        let container_ptr = PtrUninit::new(frame_id.ptr as *mut u8);
+                        let container_ptr = PtrUninit::new(frame_id.ptr.cast_mut());
                         for field in variant.data.fields.iter() {
                             let field_ptr_uninit = container_ptr.field_uninit_at(field.offset);
                             let field_id =
@@ -311,8 +391,8 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         let mut current_shape = self.shape();
 
         // Keep unwrapping as long as we find inner shapes
-        while let Some(inner_fn) = current_shape.inner {
-            current_shape = inner_fn();
+        while let Some(inner) = current_shape.inner {
+            current_shape = inner.get();
         }
 
         current_shape
@@ -331,6 +411,15 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         self.frames.last().unwrap().istate.mode
     }
 
+    /// How many elements have been pushed into the current frame so far, if it's an array or a
+    /// tuple: arrays must get exactly as many as they hold
+    pub fn pushed_count(&self) -> usize {
+        self.frames
+            .last()
+            .and_then(|frame| frame.istate.list_index)
+            .unwrap_or(0)
+    }
+
     /// Asserts everything is initialized and that invariants are upheld (if any)
     pub fn build(mut self) -> Result<HeapValue<'facet_lifetime>, ReflectError> {
         debug!("[{}] ⚒️ It's BUILD time", self.frames.len());
@@ -346,6 +435,86 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             );
         }
 
+        let root_frame = &self.frames[0];
+        if root_frame.istate.flags.contains(FrameFlags::BORROWED) {
+            return Err(ReflectError::OperationFailed {
+                shape: root_frame.shape,
+                operation: "tried to build a borrowed value, it must be finished instead",
+            });
+        }
+        self.check_root()?;
+
+        let root_shape = root_frame.shape;
+        let root_data = unsafe { root_frame.data.assume_init() };
+
+        // Prevent Drop from running on the successfully built value.
+        {
+            FlatMap::clear(&mut self.istates);
+            self.frames.clear();
+        }
+
+        // Build the guard from the root data.
+        let guard = Guard {
+            ptr: root_data.as_mut_byte_ptr(),
+            layout: match root_shape.layout {
+                facet_core::ShapeLayout::Sized(layout) => layout,
+                facet_core::ShapeLayout::Unsized => panic!("Unsized layout not supported"),
+            },
+            arena: self.arena.clone(),
+        };
+
+        Ok(HeapValue {
+            guard: Some(guard),
+            shape: root_shape,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Like [`Wip::build`], but returns the value in a `Box`, reusing the memory it was built in
+    /// unless it's in an arena
+    pub fn build_boxed<T: Facet<'facet_lifetime>>(self) -> Result<Box<T>, ReflectError> {
+        if self.shape() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape(),
+                actual: T::SHAPE,
+            });
+        }
+        self.build()?.into_box::<T>()
+    }
+
+    /// Like [`Wip::build`], but moves the value into `dst`, rather than to the stack first:
+    /// it's copied once, straight from where it was built.
+    pub fn build_into<T: Facet<'facet_lifetime>>(
+        self,
+        dst: &mut MaybeUninit<T>,
+    ) -> Result<&mut T, ReflectError> {
+        if self.shape() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape(),
+                actual: T::SHAPE,
+            });
+        }
+        let value = unsafe { self.build_in_place(PtrUninit::new(dst.as_mut_ptr()))? };
+        Ok(unsafe { value.as_mut::<T>() })
+    }
+
+    /// Like [`Wip::build_into`], for a value whose type is only known by its shape, e.g. when
+    /// the memory comes from across FFI.
+    ///
+    /// Nothing is written to `dst` if building fails.
+    ///
+    /// # Safety
+    ///
+    /// `dst` must be valid for writes of a value of the shape being built, and suitably
+    /// aligned for it. Whatever was there is overwritten without being dropped.
+    pub unsafe fn build_in_place(self, dst: PtrUninit<'_>) -> Result<PtrMut<'_>, ReflectError> {
+        let value = self.build()?;
+        Ok(unsafe { value.move_into(dst) })
+    }
+
+    /// Checks that the root frame, and everything in it, is initialized, and that its
+    /// invariants hold
+    fn check_root(&self) -> Result<(), ReflectError> {
         // now the root frame is at index 0
         let root_frame = &self.frames[0];
 
@@ -408,7 +577,8 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             // we just need to check if the *container* itself is marked as initialized.
             // The recursive check handles struct/enum *elements* within these containers if they exist.
             else if !matches!(id.shape.def, Def::Undefined) {
-                if !istate.fields.are_all_set(1) {
+                // Options are enums too, and a `None` that was put has its variant set instead
+                if !is_fully_initialized(id.shape, istate) {
                     // Check specific modes for better errors
                     match istate.mode {
                         FrameMode::OptionNone => {
@@ -448,7 +618,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                             }
                         }
 
-                        let container_ptr = PtrUninit::new(id.ptr as *mut u8);
+                        let container_ptr = PtrUninit::new(id.ptr.cast_mut());
 
                         // If initialized, push children to check stack
                         #[allow(clippy::unused_enumerate_index)]
@@ -488,7 +658,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                             #[allow(clippy::unused_enumerate_index)]
                             for (_i, field) in variant.data.fields.iter().enumerate() {
                                 let field_shape = field.shape();
-                                let container_ptr = PtrUninit::new(id.ptr as *mut u8);
+                                let container_ptr = PtrUninit::new(id.ptr.cast_mut());
                                 // We're in an enum, so get the field ptr out of the variant's payload
                                 let field_ptr =
                                     unsafe { container_ptr.field_init_at(field.offset) };
@@ -528,48 +698,14 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         // If we finished the loop, all reachable and non-moved frames are initialized.
         debug!("All reachable frames checked and initialized.");
 
-        // 5. Check invariants on the root
-        // We have already checked root is fully initialized above, so we only need to check its invariants.
+        // 5. Run the validation functions and invariants of the root and everything in it
         let root_shape = root_frame.shape;
         let root_data = unsafe { root_frame.data.assume_init() };
-        if let Some(invariant_fn) = root_shape.vtable.invariants {
-            debug!(
-                "Checking invariants for root shape {} at {:p}",
-                root_shape.green(),
-                root_data.as_byte_ptr()
-            );
-            if !unsafe { invariant_fn(PtrConst::new(root_data.as_byte_ptr())) } {
-                return Err(ReflectError::InvariantViolation {
-                    invariant: "Custom validation function returned false",
-                });
-            }
-        } else {
-            debug!(
-                "No invariants to check for root shape {}",
-                root_shape.blue()
-            );
-        }
-
-        // Prevent Drop from running on the successfully built value.
-        {
-            FlatMap::clear(&mut self.istates);
-            self.frames.clear();
-        }
-
-        // Build the guard from the root data.
-        let guard = Guard {
-            ptr: root_data.as_mut_byte_ptr(),
-            layout: match root_shape.layout {
-                facet_core::ShapeLayout::Sized(layout) => layout,
-                facet_core::ShapeLayout::Unsized => panic!("Unsized layout not supported"),
-            },
-        };
+        validate::validate(unsafe {
+            Peek::unchecked_new(PtrConst::new(root_data.as_byte_ptr()), root_shape)
+        })?;
 
-        Ok(HeapValue {
-            guard: Some(guard),
-            shape: root_shape,
-            phantom: PhantomData,
-        })
+        Ok(())
     }
 
     /// Selects a field of a struct or enum variant by index and pushes it onto the frame stack.
@@ -661,6 +797,10 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 index.yellow(),
                 shape.blue(),
             );
+            // The field was set as part of its parent, e.g. when borrowed with `from_mut`
+            if self.frames.last().unwrap().istate.fields.has(index) {
+                unsafe { frame.mark_fully_initialized() };
+            }
         }
         self.frames.push(frame);
         Ok(self)
@@ -678,16 +818,12 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
     /// * `None` if the current frame is not a struct or an enum with a selected variant,
     ///   or if the field doesn't exist.
     pub fn field_index(&self, name: &str) -> Option<usize> {
-        fn find_field_index(fields: &'static [facet_core::Field], name: &str) -> Option<usize> {
-            fields.iter().position(|f| f.name == name)
-        }
-
         let frame = self.frames.last()?;
         match frame.shape.ty {
-            Type::User(UserType::Struct(def)) => find_field_index(def.fields, name),
+            Type::User(UserType::Struct(def)) => def.field_index(name),
             Type::User(UserType::Enum(_)) => {
                 let variant = frame.istate.variant.as_ref()?;
-                find_field_index(variant.data.fields, name)
+                variant.data.field_index(name)
             }
             _ => None,
         }
@@ -726,6 +862,32 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         self.field(index)
     }
 
+    /// Selects the field a [`Lens`] points at, pushing a frame for each field it goes through
+    ///
+    /// Pop [`Lens::len`] times to get back to the frame this started from.
+    pub fn lens<Root, Target>(mut self, lens: &Lens<Root, Target>) -> Result<Self, ReflectError>
+    where
+        Root: Facet<'facet_lifetime>,
+        Target: Facet<'facet_lifetime>,
+    {
+        if self.shape() != Root::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: Root::SHAPE,
+                actual: self.shape(),
+            });
+        }
+        for &index in lens.indices() {
+            self = self.field(index)?;
+        }
+        if self.shape() != Target::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: Target::SHAPE,
+                actual: self.shape(),
+            });
+        }
+        Ok(self)
+    }
+
     /// Puts a value of type `T` into the current frame.
     ///
     /// # Arguments
@@ -741,7 +903,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         t: T,
     ) -> Result<Wip<'facet_lifetime>, ReflectError> {
         let shape = T::SHAPE;
-        let ptr_const = PtrConst::new(&t as *const T as *const u8);
+        let ptr_const = PtrConst::new(&raw const t);
         let res = self.put_shape(ptr_const, shape);
         core::mem::forget(t); // avoid double drop; ownership moved into Wip
         res
@@ -762,7 +924,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         t: T,
     ) -> Result<Wip<'facet_lifetime>, ReflectError> {
         let shape = T::SHAPE;
-        let ptr_const = PtrConst::new(&t as *const T as *const u8);
+        let ptr_const = PtrConst::new(&raw const t);
         let res = self.put_shape(ptr_const, shape);
         core::mem::forget(t); // avoid double drop; ownership moved into Wip
         res
@@ -786,6 +948,10 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 operation: "type does not implement Parse",
             });
         };
+
+        // A value that was set before gets overwritten, and parsing may still fail
+        self.deinit_current_resettable()?;
+        let frame = self.frames.last_mut().unwrap();
         match unsafe { (parse_fn)(s, frame.data) } {
             Ok(_res) => {
                 unsafe {
@@ -806,12 +972,14 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
 
     /// Puts a value using a provided DefaultInPlaceFn in the current frame.
     pub fn put_from_fn(mut self, default_in_place: DefaultInPlaceFn) -> Result<Self, ReflectError> {
-        let Some(frame) = self.frames.last_mut() else {
+        if self.frames.is_empty() {
             return Err(ReflectError::OperationFailed {
                 shape: <()>::SHAPE,
                 operation: "tried to put value from fn but there was no frame",
             });
-        };
+        }
+        self.deinit_current();
+        let frame = self.frames.last_mut().unwrap();
 
         // Special handling for arrays - if this is for an array from default,
         // we need to set list_index to array size to mark it as fully initialized
@@ -907,15 +1075,16 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         Ok(())
     }
 
-    /// Returns the shape of the element type for a list/array
+    /// Returns the shape of the element type for a list/set
     pub fn element_shape(&self) -> Result<&'static Shape, ReflectError> {
         let frame = self.frames.last().unwrap();
         let shape = frame.shape;
 
         match shape.def {
             Def::List(list_def) => Ok(list_def.t()),
+            Def::Set(set_def) => Ok(set_def.t()),
             _ => Err(ReflectError::WasNotA {
-                expected: "list or array",
+                expected: "list or set",
                 actual: shape,
             }),
         }
@@ -961,6 +1130,9 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             });
         };
 
+        // A value that was set before, e.g. when editing in place, gets overwritten
+        self.deinit_current();
+        let frame = self.frames.last_mut().unwrap();
         unsafe {
             default_in_place(frame.data);
             frame.mark_fully_initialized();
@@ -1001,6 +1173,9 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             });
         };
 
+        // A value that was set before, e.g. when editing in place, gets overwritten
+        self.deinit_current();
+        let frame = self.frames.last_mut().unwrap();
         unsafe {
             default_in_place(frame.data);
             frame.mark_fully_initialized();
@@ -1015,9 +1190,9 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         Ok(self)
     }
 
-    /// Begins pushback mode for a list, array, tuple struct, or enum variant tuple struct,
+    /// Begins pushback mode for a list, set, array, tuple struct, or enum variant tuple struct,
     /// allowing elements to be added one by one.
-    /// For lists/arrays, initializes an empty container if needed.
+    /// For lists/sets, initializes an empty container if needed.
     /// For tuple structs/variants, does nothing (expects subsequent `push` calls).
     pub fn begin_pushback(mut self) -> Result<Self, ReflectError> {
         let Some(frame) = self.frames.last_mut() else {
@@ -1027,12 +1202,16 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             });
         };
 
-        let is_list = matches!(frame.shape.def, Def::List(_));
+        // Sets are filled like lists, items are inserted rather than pushed
+        let is_list = matches!(frame.shape.def, Def::List(_) | Def::Set(_));
         let is_array = matches!(frame.shape.def, Def::Array(_));
         let is_tuple_struct_or_variant = match (frame.shape.ty, frame.shape.def) {
             (_, Def::Scalar(sd)) => matches!(sd.affinity, ScalarAffinity::Empty(_)),
             (Type::Sequence(_), _) => true,
-            (Type::User(UserType::Struct(sd)), _) => sd.kind == facet_core::StructKind::Tuple,
+            (Type::User(UserType::Struct(sd)), _) => matches!(
+                sd.kind,
+                facet_core::StructKind::Tuple | facet_core::StructKind::TupleStruct
+            ),
             (Type::User(UserType::Enum(_)), _) => {
                 // Check if a variant is selected and if that variant is a tuple-like struct
                 if let Some(variant) = &frame.istate.variant {
@@ -1053,7 +1232,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
 
         if !is_list && !is_array && !is_tuple_struct_or_variant {
             return Err(ReflectError::WasNotA {
-                expected: "list, array, or tuple-like struct/enum variant",
+                expected: "list, set, array, or tuple-like struct/enum variant",
                 actual: frame.shape,
             });
         }
@@ -1123,7 +1302,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         Ok(self)
     }
 
-    /// Pushes a new element onto the list/array/tuple struct/tuple enum variant
+    /// Pushes a new element onto the list/set/array/tuple struct/tuple enum variant
     ///
     /// This creates a new frame for the element. When this frame is popped,
     /// the element will be added to the list or the corresponding tuple field will be set.
@@ -1142,7 +1321,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         // Determine element shape and context string based on the container type
         let (element_shape, context_str): (&'static Shape, &'static str) =
             match (seq_shape.ty, seq_shape.def) {
-                (_, Def::List(_)) => {
+                (_, Def::List(_) | Def::Set(_)) => {
                     // Check list initialization *before* getting element shape
                     if !frame.istate.fields.has(0) {
                         // Replicate original recursive call pattern to handle initialization
@@ -1152,7 +1331,11 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                     // List is initialized, get element shape (requires immutable self)
                     // Drop mutable borrow of frame before calling immutable method
                     let shape = self.element_shape()?;
-                    (shape, "list")
+                    let context_str = match seq_shape.def {
+                        Def::Set(_) => "set",
+                        _ => "list",
+                    };
+                    (shape, context_str)
                 }
                 (_, Def::Array(array_def)) => {
                     // For arrays, we need to check which index we're on and verify it's valid
@@ -1193,7 +1376,10 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                     (tt.fields[field_index].shape(), "tuple")
                 }
                 (Type::User(UserType::Struct(sd)), _)
-                    if sd.kind == facet_core::StructKind::Tuple =>
+                    if matches!(
+                        sd.kind,
+                        facet_core::StructKind::Tuple | facet_core::StructKind::TupleStruct
+                    ) =>
                 {
                     // Handle tuple struct (requires mutable frame for list_index)
                     let field_index = {
@@ -1261,18 +1447,14 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 _ => {
                     // If it's not a list, tuple struct, or enum, it's an error
                     return Err(ReflectError::WasNotA {
-                        expected: "list, array, tuple, tuple struct, or tuple enum variant",
+                        expected: "list, set, array, tuple, tuple struct, or tuple enum variant",
                         actual: seq_shape,
                     });
                 }
             };
 
         // Allocate memory for the element
-        let element_data = element_shape
-            .allocate()
-            .map_err(|_| ReflectError::Unsized {
-                shape: element_shape,
-            })?;
+        let (element_data, flags) = allocate_frame(self.arena.as_ref(), element_shape)?;
 
         // Create a new frame for the element
         let element_frame = Frame {
@@ -1282,7 +1464,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             istate: IState::new(
                 frame_len,              // Use captured length (depth of the new frame)
                 FrameMode::ListElement, // Keep using this mode for list/tuple elements
-                FrameFlags::ALLOCATED,
+                flags,
             ),
         };
 
@@ -1317,9 +1499,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         let inner_shape = option_def.t();
 
         // Allocate memory for the inner value
-        let inner_data = inner_shape
-            .allocate()
-            .map_err(|_| ReflectError::Unsized { shape: inner_shape })?;
+        let (inner_data, flags) = allocate_frame(self.arena.as_ref(), inner_shape)?;
 
         // Create a new frame for the inner value
         let inner_frame = Frame {
@@ -1331,7 +1511,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 self.frames.len(),
                 FrameMode::OptionSome,
                 // TODO: we could lazy-allocate it when something like `field` is called, tbh
-                FrameFlags::ALLOCATED,
+                flags,
             ),
         };
 
@@ -1456,16 +1636,14 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         let key_shape = self.key_shape()?;
 
         // Allocate memory for the key
-        let key_data = key_shape
-            .allocate()
-            .map_err(|_| ReflectError::Unsized { shape: key_shape })?;
+        let (key_data, flags) = allocate_frame(self.arena.as_ref(), key_shape)?;
 
         // Create a new frame for the key
         let key_frame = Frame {
             data: key_data,
             shape: key_shape,
             field_index_in_parent: None,
-            istate: IState::new(self.frames.len(), FrameMode::MapKey, FrameFlags::ALLOCATED),
+            istate: IState::new(self.frames.len(), FrameMode::MapKey, flags),
         };
 
         trace!(
@@ -1536,9 +1714,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
         let value_shape = map_def.v();
 
         // Allocate memory for the value
-        let value_data = value_shape
-            .allocate()
-            .map_err(|_| ReflectError::Unsized { shape: value_shape })?;
+        let (value_data, flags) = allocate_frame(self.arena.as_ref(), value_shape)?;
 
         // Create a new frame for the value
         let value_frame = Frame {
@@ -1550,7 +1726,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 FrameMode::MapValue {
                     index: key_frame_index,
                 },
-                FrameFlags::ALLOCATED,
+                flags,
             ),
         };
 
@@ -1569,7 +1745,21 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
     /// Evict a frame from istates, along with all its children
     /// (because we're about to use `drop_in_place` on it — not
     /// yet though, we need to know the variant for enums, etc.)
+    ///
+    /// The tree is walked with a worklist rather than recursively, so that deeply nested
+    /// partial values can't overflow the stack.
     pub(crate) fn evict_tree(&mut self, frame: Frame) -> Frame {
+        let mut pending = alloc::vec::Vec::new();
+        self.evict_children(&frame, &mut pending);
+        while let Some(child) = pending.pop() {
+            self.evict_children(&child, &mut pending);
+        }
+        frame
+    }
+
+    /// Evicts the direct children of `frame`, queueing them in `pending` so their own
+    /// children get evicted too
+    fn evict_children(&mut self, frame: &Frame, pending: &mut alloc::vec::Vec<Frame>) {
         match frame.shape.ty {
             Type::User(UserType::Struct(sd)) => {
                 for f in sd.fields {
@@ -1578,8 +1768,7 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                         ptr: unsafe { frame.data.field_uninit_at(f.offset) }.as_byte_ptr(),
                     };
                     if let Some(istate) = self.istates.remove(&id) {
-                        let frame = Frame::recompose(id, istate);
-                        self.evict_tree(frame);
+                        pending.push(Frame::recompose(id, istate));
                     } else {
                         trace!("No istate found for field {}", f.name);
                     }
@@ -1608,10 +1797,8 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                                 field_shape.green(),
                                 variant.name.yellow()
                             );
-                            // Recompose the frame for the field
-                            let field_frame = Frame::recompose(field_id, field_istate);
-                            // Recursively evict the field's subtree
-                            self.evict_tree(field_frame);
+                            // Recompose the frame for the field, its subtree gets evicted next
+                            pending.push(Frame::recompose(field_id, field_istate));
                         } else {
                             trace!(
                                 "Field '{}' (shape {}) of enum variant '{}' not found in istates, skipping eviction",
@@ -1631,7 +1818,6 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             }
             _ => {}
         }
-        frame
     }
 
     #[allow(rustdoc::broken_intra_doc_links)]
@@ -1667,6 +1853,18 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
                 FrameMode::OptionNone => {
                     path.push_str(".none");
                 }
+                FrameMode::ResultOk => {
+                    path.push_str(".ok");
+                }
+                FrameMode::ResultErr => {
+                    path.push_str(".err");
+                }
+                FrameMode::Pointee => {
+                    // The pointee stands for its pointer
+                }
+                FrameMode::Proxy { .. } => {
+                    // The proxy stands for its value
+                }
                 FrameMode::Root => {
                     // Root doesn't add to the path
                 }
@@ -1,4 +1,5 @@
 use crate::trace;
+use alloc::string::String;
 use facet_core::{
     Def, EnumType, PtrConst, PtrMut, PtrUninit, Repr, ScalarAffinity, SequenceType, StructType,
     Type, UserType, Variant,
@@ -127,6 +128,21 @@ impl Wip<'_> {
                                 self.mark_moved_out_of(&mut frame);
                             }
                         }
+                        Def::Set(set_def) => {
+                            trace!(
+                                "[{}] Inserting element into set {}",
+                                frame_len,
+                                parent_shape.blue()
+                            );
+                            unsafe {
+                                // A duplicate is dropped by the set, like `HashSet::insert` does
+                                (set_def.vtable.insert_fn)(
+                                    PtrMut::new(parent_frame.data.as_mut_byte_ptr()),
+                                    PtrMut::new(frame.data.as_mut_byte_ptr()),
+                                );
+                                self.mark_moved_out_of(&mut frame);
+                            }
+                        }
                         Def::Scalar(s) if matches!(s.affinity, ScalarAffinity::Empty(_)) => {
                             trace!(
                                 "[{}] Handling scalar empty unit type {}",
@@ -204,7 +220,11 @@ impl Wip<'_> {
 
                             // Handle Tuple Structs
                             Type::User(UserType::Struct(sd))
-                                if sd.kind == facet_core::StructKind::Tuple =>
+                                if matches!(
+                                    sd.kind,
+                                    facet_core::StructKind::Tuple
+                                        | facet_core::StructKind::TupleStruct
+                                ) =>
                             {
                                 // Get the field index from list_index saved during push
                                 let previous_index = parent_frame.istate.list_index.unwrap_or(1);
@@ -352,7 +372,7 @@ impl Wip<'_> {
                                     // Calculate pointer to the right element in the array
                                     let element_offset = element_size * element_index;
                                     let element_ptr = PtrUninit::new(
-                                        array_ptr.as_byte_ptr().add(element_offset) as *mut u8,
+                                        array_ptr.as_byte_ptr().add(element_offset).cast_mut(),
                                     );
 
                                     // Copy the element data to the array
@@ -433,8 +453,8 @@ impl Wip<'_> {
                 // Now let's remove the key frame from the frames array
                 let mut key_frame = self.frames.remove(key_frame_index);
 
-                // Make sure the key is fully initialized
-                if !key_frame.istate.fields.is_any_set() {
+                // Make sure the key is fully initialized (unit variants of enum keys set no fields)
+                if !key_frame.is_fully_initialized() {
                     panic!("key is not initialized when popping value frame");
                 }
 
@@ -548,6 +568,93 @@ impl Wip<'_> {
                 }
             }
 
+            // Handle result frames
+            FrameMode::ResultOk | FrameMode::ResultErr => {
+                if frame.is_fully_initialized() {
+                    let parent_frame = self.frames.last_mut().unwrap();
+                    let Def::Result(result_def) = parent_frame.shape.def else {
+                        panic!(
+                            "Expected parent frame to be a result type, got {}",
+                            parent_frame.shape
+                        );
+                    };
+                    trace!(
+                        "Setting {:?} value in result {}",
+                        frame.istate.mode.yellow(),
+                        parent_frame.shape.blue()
+                    );
+                    let init_fn = if frame.istate.mode == FrameMode::ResultOk {
+                        result_def.vtable.init_ok_fn
+                    } else {
+                        result_def.vtable.init_err_fn
+                    };
+                    unsafe {
+                        init_fn(parent_frame.data, PtrConst::new(frame.data.as_byte_ptr()));
+                        parent_frame.mark_fully_initialized();
+                        self.mark_moved_out_of(&mut frame);
+                    }
+                } else {
+                    trace!("Popping {:?} (not fully init'd)", frame.istate.mode);
+                }
+            }
+
+            // Handle smart pointer pointee frames
+            FrameMode::Pointee => {
+                if frame.is_fully_initialized() {
+                    let parent_frame = self.frames.last_mut().unwrap();
+                    let Def::SmartPointer(pointer_def) = parent_frame.shape.def else {
+                        panic!(
+                            "Expected parent frame to be a smart pointer, got {}",
+                            parent_frame.shape
+                        );
+                    };
+                    trace!(
+                        "Moving pointee into smart pointer {}",
+                        parent_frame.shape.blue()
+                    );
+                    // Checked when the frame was pushed
+                    let new_into_fn = pointer_def.vtable.new_into_fn.unwrap();
+                    unsafe {
+                        new_into_fn(parent_frame.data, PtrConst::new(frame.data.as_byte_ptr()));
+                        parent_frame.mark_fully_initialized();
+                        self.mark_moved_out_of(&mut frame);
+                    }
+                } else {
+                    trace!("Popping pointee (not fully init'd)");
+                }
+            }
+
+            // Handle the proxy frames of fields and values
+            FrameMode::Proxy { with } => {
+                if frame.is_fully_initialized() {
+                    let parent_frame = self.frames.last_mut().unwrap();
+                    trace!(
+                        "Converting proxy {} to {}",
+                        frame.shape.yellow(),
+                        parent_frame.shape.blue()
+                    );
+                    let value_shape = parent_frame.shape;
+                    let mut message = String::new();
+                    let converted = unsafe {
+                        (with.convert)(frame.data.assume_init(), parent_frame.data, &mut message)
+                    };
+                    if converted {
+                        unsafe { parent_frame.mark_fully_initialized() };
+                    }
+                    // The conversion moved out of the proxy, whether it succeeded or not
+                    unsafe { self.mark_moved_out_of(&mut frame) };
+                    if !converted {
+                        return Err(ReflectError::ConversionFailed {
+                            src_shape: frame.shape,
+                            dst_shape: value_shape,
+                            message,
+                        });
+                    }
+                } else {
+                    trace!("Popping proxy (not fully init'd)");
+                }
+            }
+
             // Map keys are just tracked, they don't need special handling when popped
             // FIXME: that's not true, we need to deallocate them at least??
             FrameMode::MapKey => {}
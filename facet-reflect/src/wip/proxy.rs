@@ -0,0 +1,84 @@
+use facet_core::{DeserializeWith, Type, UserType};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use super::{Frame, FrameMode, IState, allocate_frame};
+use crate::trace;
+use crate::{ReflectError, Wip};
+
+impl Wip<'_> {
+    /// Returns what the current field is deserialized as, if it has
+    /// `#[facet(deserialize_with = ...)]`
+    pub fn deserialize_with(&self) -> Option<DeserializeWith> {
+        let [.., parent, frame] = self.frames.as_slice() else {
+            return None;
+        };
+        if frame.istate.mode != FrameMode::Field {
+            return None;
+        }
+        let fields = match parent.shape.ty {
+            Type::User(UserType::Struct(sd)) => sd.fields,
+            Type::User(UserType::Enum(_)) => parent.istate.variant.as_ref()?.data.fields,
+            _ => return None,
+        };
+        fields
+            .get(frame.field_index_in_parent?)?
+            .vtable
+            .deserialize_with
+    }
+
+    /// Pushes a frame for the proxy the current field is deserialized as, see
+    /// [`Wip::deserialize_with`].
+    ///
+    /// The proxy can be built like any other value. When its frame gets popped, fully
+    /// initialized, it's converted to the field's value, which fails with
+    /// [`ReflectError::ConversionFailed`] if the conversion function returns an error.
+    pub fn push_deserialize_with(self) -> Result<Self, ReflectError> {
+        let Some(with) = self.deserialize_with() else {
+            return Err(ReflectError::OperationFailed {
+                shape: self.shape(),
+                operation: "this field isn't deserialized as a proxy",
+            });
+        };
+        self.push_proxy(with)
+    }
+
+    /// Pushes a frame for the proxy values of the current shape are deserialized as, set with
+    /// `#[facet(from = ...)]` or `#[facet(try_from = ...)]`.
+    ///
+    /// Like with [`Wip::push_deserialize_with`], the proxy is converted to the value when its
+    /// frame gets popped, fully initialized.
+    pub fn push_deserialize_from(self) -> Result<Self, ReflectError> {
+        let Some(with) = self.shape().vtable.deserialize_from else {
+            return Err(ReflectError::OperationFailed {
+                shape: self.shape(),
+                operation: "this shape isn't deserialized as a proxy",
+            });
+        };
+        self.push_proxy(with)
+    }
+
+    /// Pushes a frame for a proxy the current value is built from, like the
+    /// [`DurationParts`](facet_core::DurationParts) of a `Duration`.
+    ///
+    /// Like with [`Wip::push_deserialize_with`], the proxy is converted to the value when its
+    /// frame gets popped, fully initialized.
+    pub fn push_proxy(mut self, with: DeserializeWith) -> Result<Self, ReflectError> {
+        let (proxy_data, flags) = allocate_frame(self.arena.as_ref(), with.shape)?;
+
+        trace!(
+            "[{}] Pushing proxy frame {} for {}",
+            self.frames.len(),
+            with.shape.yellow(),
+            self.shape().blue(),
+        );
+
+        self.frames.push(Frame {
+            data: proxy_data,
+            shape: with.shape,
+            field_index_in_parent: None,
+            istate: IState::new(self.frames.len(), FrameMode::Proxy { with }, flags),
+        });
+        Ok(self)
+    }
+}
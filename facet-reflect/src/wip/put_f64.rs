@@ -1,7 +1,7 @@
 use crate::{ReflectError, Wip};
 use core::num::{
-    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroIsize, NonZeroU8, NonZeroU16, NonZeroU32,
-    NonZeroU64, NonZeroUsize,
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
 };
 use facet_core::{Def, ScalarAffinity};
 
@@ -80,6 +80,15 @@ impl Wip<'_> {
                     operation: "number out of range",
                 })
             }
+        } else if shape.is_type::<u128>() {
+            if (0.0..=u128::MAX as f64).contains(&number) {
+                self.put(number as u128)
+            } else {
+                Err(ReflectError::OperationFailed {
+                    shape,
+                    operation: "number out of range",
+                })
+            }
         } else if shape.is_type::<usize>() {
             if (0.0..=usize::MAX as f64).contains(&number) {
                 self.put(number as usize)
@@ -125,6 +134,15 @@ impl Wip<'_> {
                     operation: "number out of range",
                 })
             }
+        } else if shape.is_type::<i128>() {
+            if (i128::MIN as f64..=i128::MAX as f64).contains(&number) {
+                self.put(number as i128)
+            } else {
+                Err(ReflectError::OperationFailed {
+                    shape,
+                    operation: "number out of range",
+                })
+            }
         } else if shape.is_type::<isize>() {
             if (isize::MIN as f64..=isize::MAX as f64).contains(&number) {
                 self.put(number as isize)
@@ -185,9 +203,9 @@ impl Wip<'_> {
                     operation: "number out of range",
                 })
             }
-        } else if shape.is_type::<NonZeroUsize>() {
-            if (1.0..=usize::MAX as f64).contains(&number) {
-                let value = NonZeroUsize::new(number as usize).unwrap();
+        } else if shape.is_type::<NonZeroU128>() {
+            if (1.0..=u128::MAX as f64).contains(&number) {
+                let value = NonZeroU128::new(number as u128).unwrap();
                 self.put(value)
             } else {
                 Err(ReflectError::OperationFailed {
@@ -195,9 +213,9 @@ impl Wip<'_> {
                     operation: "number out of range",
                 })
             }
-        } else if shape.is_type::<NonZeroI8>() {
-            if (1.0..=i8::MAX as f64).contains(&number) {
-                let value = NonZeroI8::new(number as i8).unwrap();
+        } else if shape.is_type::<NonZeroUsize>() {
+            if (1.0..=usize::MAX as f64).contains(&number) {
+                let value = NonZeroUsize::new(number as usize).unwrap();
                 self.put(value)
             } else {
                 Err(ReflectError::OperationFailed {
@@ -205,45 +223,66 @@ impl Wip<'_> {
                     operation: "number out of range",
                 })
             }
+        } else if shape.is_type::<NonZeroI8>() {
+            // Negative values are fine too, as long as they're not zero
+            match NonZeroI8::new(number as i8) {
+                Some(value) if (i8::MIN as f64..=i8::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
+                    shape,
+                    operation: "number out of range",
+                }),
+            }
         } else if shape.is_type::<NonZeroI16>() {
-            if (1.0..=i16::MAX as f64).contains(&number) {
-                let value = NonZeroI16::new(number as i16).unwrap();
-                self.put(value)
-            } else {
-                Err(ReflectError::OperationFailed {
+            match NonZeroI16::new(number as i16) {
+                Some(value) if (i16::MIN as f64..=i16::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
                     shape,
                     operation: "number out of range",
-                })
+                }),
             }
         } else if shape.is_type::<NonZeroI32>() {
-            if (1.0..=i32::MAX as f64).contains(&number) {
-                let value = NonZeroI32::new(number as i32).unwrap();
-                self.put(value)
-            } else {
-                Err(ReflectError::OperationFailed {
+            match NonZeroI32::new(number as i32) {
+                Some(value) if (i32::MIN as f64..=i32::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
                     shape,
                     operation: "number out of range",
-                })
+                }),
             }
         } else if shape.is_type::<NonZeroI64>() {
-            if (1.0..=i64::MAX as f64).contains(&number) {
-                let value = NonZeroI64::new(number as i64).unwrap();
-                self.put(value)
-            } else {
-                Err(ReflectError::OperationFailed {
+            match NonZeroI64::new(number as i64) {
+                Some(value) if (i64::MIN as f64..=i64::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
                     shape,
                     operation: "number out of range",
-                })
+                }),
+            }
+        } else if shape.is_type::<NonZeroI128>() {
+            match NonZeroI128::new(number as i128) {
+                Some(value) if (i128::MIN as f64..=i128::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
+                    shape,
+                    operation: "number out of range",
+                }),
             }
         } else if shape.is_type::<NonZeroIsize>() {
-            if (1.0..=isize::MAX as f64).contains(&number) {
-                let value = NonZeroIsize::new(number as isize).unwrap();
-                self.put(value)
-            } else {
-                Err(ReflectError::OperationFailed {
+            match NonZeroIsize::new(number as isize) {
+                Some(value) if (isize::MIN as f64..=isize::MAX as f64).contains(&number) => {
+                    self.put(value)
+                }
+                _ => Err(ReflectError::OperationFailed {
                     shape,
                     operation: "number out of range",
-                })
+                }),
             }
         } else {
             Err(ReflectError::OperationFailed {
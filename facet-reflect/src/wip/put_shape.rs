@@ -1,8 +1,8 @@
-use facet_core::{Def, PtrConst, PtrMut, SequenceType, Shape, Type, UserType};
+use facet_core::{Def, PtrConst, SequenceType, Shape, Type, UserType};
 #[allow(unused_imports)]
 use owo_colors::OwoColorize;
 
-use crate::{ISet, ReflectError};
+use crate::ReflectError;
 use crate::{debug, trace};
 
 use super::Wip;
@@ -30,9 +30,9 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             );
 
             // Check if the frame's shape has an inner type (is a transparent wrapper)
-            if let Some(inner_fn) = frame.shape.inner {
+            if let Some(inner) = frame.shape.inner {
                 // Get the inner shape
-                let inner_shape = inner_fn();
+                let inner_shape = inner.get();
 
                 // If the source shape matches the inner shape, we need to build the outer (transparent) wrapper
                 if src_shape == inner_shape {
@@ -334,56 +334,9 @@ impl<'facet_lifetime> Wip<'facet_lifetime> {
             });
         }
 
-        // de-initialize partially initialized fields, if any
-        if frame.istate.variant.is_some() || frame.istate.fields.is_any_set() {
-            debug!("De-initializing partially initialized {:?}", frame.yellow());
-
-            match frame.shape.ty {
-                Type::User(UserType::Struct(sd)) => {
-                    for (i, field) in sd.fields.iter().enumerate() {
-                        if frame.istate.fields.has(i) {
-                            if let Some(drop_fn) = field.shape().vtable.drop_in_place {
-                                unsafe {
-                                    let field_ptr = frame.data.as_mut_byte_ptr().add(field.offset);
-                                    drop_fn(PtrMut::new(field_ptr));
-                                }
-                            }
-                        }
-                    }
-                }
-                Type::User(UserType::Enum(_)) => {
-                    if let Some(variant) = &frame.istate.variant {
-                        for (i, field) in variant.data.fields.iter().enumerate() {
-                            if frame.istate.fields.has(i) {
-                                if let Some(drop_fn) = field.shape().vtable.drop_in_place {
-                                    unsafe {
-                                        let field_ptr =
-                                            frame.data.as_mut_byte_ptr().add(field.offset);
-                                        drop_fn(PtrMut::new(field_ptr));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    // For scalar types and other non-struct/enum, attempt to drop the field in place if initialized
-                    if frame.istate.fields.is_any_set() {
-                        debug!("Scalar type was set...");
-                        if let Some(drop_fn) = frame.shape.vtable.drop_in_place {
-                            debug!("And it has a drop fn, dropping now!");
-                            unsafe {
-                                drop_fn(frame.data.assume_init());
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Reset initialization state
-            frame.istate.variant = None;
-            ISet::clear(&mut frame.istate.fields);
-        }
+        // A value that was set before, e.g. when editing in place, gets overwritten
+        self.deinit_current();
+        let frame = self.frames.last_mut().unwrap();
 
         unsafe {
             // Copy the contents from src to destination
@@ -0,0 +1,59 @@
+use crate::{ReflectError, Wip};
+use facet_core::{Def, Facet, FromUnixNanosFn, ScalarAffinity, Shape};
+
+impl Wip<'_> {
+    /// Returns true if the current frame holds a timestamp or datetime that can be created from
+    /// a unix timestamp, see [`Wip::put_unix_nanos`]
+    pub fn can_put_unix_nanos(&self) -> bool {
+        from_unix_nanos(self.shape()).is_some()
+    }
+
+    /// Puts the instant `nanos` nanoseconds after the unix epoch into the current frame, which
+    /// must hold a timestamp or datetime that stands for an instant, e.g. a `jiff::Timestamp`
+    pub fn put_unix_nanos(mut self, nanos: i128) -> Result<Self, ReflectError> {
+        let Some(frame) = self.frames.last_mut() else {
+            return Err(ReflectError::OperationFailed {
+                shape: <()>::SHAPE,
+                operation: "tried to put unix timestamp but there was no frame",
+            });
+        };
+
+        let shape = frame.shape;
+        let index = frame.field_index_in_parent;
+
+        let Some(from_unix_nanos) = from_unix_nanos(shape) else {
+            return Err(ReflectError::OperationFailed {
+                shape,
+                operation: "tried to put unix timestamp into a type that isn't an instant",
+            });
+        };
+
+        // A value that was set before gets overwritten, and the timestamp may be out of range
+        self.deinit_current_resettable()?;
+        let frame = self.frames.last_mut().unwrap();
+        if unsafe { from_unix_nanos(nanos, frame.data) }.is_none() {
+            return Err(ReflectError::OperationFailed {
+                shape,
+                operation: "unix timestamp out of range",
+            });
+        }
+        unsafe {
+            frame.mark_fully_initialized();
+        }
+
+        // mark the field as initialized
+        self.mark_field_as_initialized(shape, index)?;
+
+        Ok(self)
+    }
+}
+
+fn from_unix_nanos(shape: &'static Shape) -> Option<FromUnixNanosFn> {
+    match shape.def {
+        Def::Scalar(sd) => match sd.affinity {
+            ScalarAffinity::Time(time) => time.from_unix_nanos(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,57 @@
+use facet_core::Def;
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use super::{Frame, FrameMode, IState, allocate_frame};
+use crate::trace;
+use crate::{ReflectError, Wip};
+
+impl Wip<'_> {
+    /// Pushes a frame for the value held by the `Ok` variant of a result.
+    ///
+    /// The result is set to `Ok` when the frame gets popped, fully initialized.
+    pub fn push_ok(self) -> Result<Self, ReflectError> {
+        self.push_result_value(FrameMode::ResultOk)
+    }
+
+    /// Pushes a frame for the value held by the `Err` variant of a result.
+    ///
+    /// The result is set to `Err` when the frame gets popped, fully initialized.
+    pub fn push_err(self) -> Result<Self, ReflectError> {
+        self.push_result_value(FrameMode::ResultErr)
+    }
+
+    fn push_result_value(mut self, mode: FrameMode) -> Result<Self, ReflectError> {
+        let frame = self.frames.last().unwrap();
+        let result_shape = frame.shape;
+
+        let Def::Result(result_def) = result_shape.def else {
+            return Err(ReflectError::WasNotA {
+                expected: "result",
+                actual: result_shape,
+            });
+        };
+
+        let inner_shape = match mode {
+            FrameMode::ResultOk => result_def.t(),
+            _ => result_def.e(),
+        };
+        let (inner_data, flags) = allocate_frame(self.arena.as_ref(), inner_shape)?;
+
+        trace!(
+            "[{}] Pushing {:?} frame for {}",
+            self.frames.len(),
+            mode.yellow(),
+            result_shape.blue(),
+        );
+
+        self.frames.push(Frame {
+            data: inner_data,
+            shape: inner_shape,
+            // this is only set when we pop
+            field_index_in_parent: None,
+            istate: IState::new(self.frames.len(), mode, flags),
+        });
+        Ok(self)
+    }
+}
@@ -0,0 +1,51 @@
+use facet_core::{Def, SmartPointerFlags};
+#[cfg(feature = "log")]
+use owo_colors::OwoColorize;
+
+use super::{Frame, FrameMode, IState, allocate_frame};
+use crate::trace;
+use crate::{ReflectError, Wip};
+
+impl Wip<'_> {
+    /// Pushes a frame for the value a smart pointer, like a `Box` or an `Arc`, points to.
+    ///
+    /// The pointee can be built like any other value, field by field if need be. When its
+    /// frame gets popped, fully initialized, it's moved into a new smart pointer.
+    pub fn push_pointee(mut self) -> Result<Self, ReflectError> {
+        let frame = self.frames.last().unwrap();
+        let pointer_shape = frame.shape;
+
+        let Def::SmartPointer(pointer_def) = pointer_shape.def else {
+            return Err(ReflectError::WasNotA {
+                expected: "smart pointer",
+                actual: pointer_shape,
+            });
+        };
+        let (Some(pointee_shape), Some(_), false) = (
+            pointer_def.pointee(),
+            pointer_def.vtable.new_into_fn,
+            pointer_def.flags.contains(SmartPointerFlags::WEAK),
+        ) else {
+            return Err(ReflectError::OperationFailed {
+                shape: pointer_shape,
+                operation: "this smart pointer can't be created from its pointee",
+            });
+        };
+        let (pointee_data, flags) = allocate_frame(self.arena.as_ref(), pointee_shape)?;
+
+        trace!(
+            "[{}] Pushing pointee frame for {}",
+            self.frames.len(),
+            pointer_shape.blue(),
+        );
+
+        self.frames.push(Frame {
+            data: pointee_data,
+            shape: pointee_shape,
+            // this is only set when we pop
+            field_index_in_parent: None,
+            istate: IState::new(self.frames.len(), FrameMode::Pointee, flags),
+        });
+        Ok(self)
+    }
+}
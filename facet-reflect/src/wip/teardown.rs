@@ -0,0 +1,199 @@
+use alloc::{vec, vec::Vec};
+use core::alloc::Layout;
+use facet_core::{
+    Def, KnownSmartPointer, MarkerTraits, PtrConst, PtrMut, PtrUninit, SequenceType, Shape, Type,
+    UserType,
+};
+
+#[allow(unused_imports)]
+use owo_colors::OwoColorize;
+
+use crate::{Peek, trace};
+
+/// A value moved out of its parent so it can be dropped on its own
+struct Detached {
+    ptr: *mut u8,
+    shape: &'static Shape,
+    layout: Layout,
+}
+
+/// Drops the value at `ptr` without recursing once per level of nesting.
+///
+/// `drop_in_place` on something like a 100k-long `Option<Box<Node>>` chain recurses 100k
+/// times. Instead, every non-empty `Option` and `Vec` found inside the value is moved out
+/// to a heap buffer of its own and replaced with `None` or an empty list, so that dropping
+/// the value itself is shallow. The detached values are then torn down the same way, from
+/// a worklist.
+///
+/// Recursion that only goes through enum payloads or maps isn't cut, and is still dropped
+/// recursively. Neither is the inside of structs and enums with a `Drop` impl of their own,
+/// which must see their fields as they were when it runs.
+///
+/// # Safety
+///
+/// `ptr` must point to an initialized value of shape `shape`, which it's dropped in place
+/// of. The memory itself isn't deallocated.
+pub(crate) unsafe fn drop_in_place_iteratively(ptr: PtrMut<'_>, shape: &'static Shape) {
+    let Some(drop_fn) = shape.vtable.drop_in_place else {
+        return;
+    };
+
+    let mut detached = Vec::new();
+    unsafe {
+        detach_children(ptr.as_mut_byte_ptr(), shape, &mut detached);
+        drop_fn(ptr);
+    }
+
+    while let Some(value) = detached.pop() {
+        trace!(
+            "Dropping detached {} at {:p}",
+            value.shape.blue(),
+            value.ptr
+        );
+        unsafe {
+            detach_children(value.ptr, value.shape, &mut detached);
+            if let Some(drop_fn) = value.shape.vtable.drop_in_place {
+                drop_fn(PtrMut::new(value.ptr));
+            }
+            alloc::alloc::dealloc(value.ptr, value.layout);
+        }
+    }
+}
+
+/// Walks the inside of the value at `ptr` — fields, boxes, list items — and detaches
+/// every `Option` payload and list it comes across. If `ptr` is a list itself, its items
+/// are walked instead.
+unsafe fn detach_children(ptr: *mut u8, shape: &'static Shape, detached: &mut Vec<Detached>) {
+    let mut stack = vec![(ptr, shape, true)];
+
+    while let Some((ptr, shape, is_top)) = stack.pop() {
+        if !needs_walking(shape) {
+            continue;
+        }
+
+        match shape.def {
+            Def::Option(od) => {
+                let Some(inner) = (unsafe { (od.vtable.get_value_fn)(PtrConst::new(ptr)) }) else {
+                    continue;
+                };
+                // `get_value_fn` only hands out a const pointer, the payload is at the same offset
+                let offset = unsafe { inner.byte_offset_from(PtrConst::new(ptr)) } as usize;
+                let inner_ptr = ptr.wrapping_add(offset);
+                if !needs_walking(od.t()) {
+                    continue;
+                }
+                let Some(copy) = (unsafe { detach(inner_ptr, od.t()) }) else {
+                    stack.push((inner_ptr, od.t(), false));
+                    continue;
+                };
+                // The payload was moved out, so the option gets overwritten without dropping it
+                unsafe { (od.vtable.init_none_fn)(PtrUninit::new(ptr)) };
+                detached.push(copy);
+            }
+            Def::List(ld) => {
+                let len = unsafe { (ld.vtable.len)(PtrConst::new(ptr)) };
+                if len == 0 || !needs_walking(ld.t()) {
+                    continue;
+                }
+                if !is_top {
+                    if let Some(init) = ld.vtable.init_in_place_with_capacity {
+                        if let Some(copy) = unsafe { detach(ptr, shape) } {
+                            // Same as options: the list's buffer now belongs to the copy
+                            unsafe { init(PtrUninit::new(ptr), 0) };
+                            detached.push(copy);
+                            continue;
+                        }
+                    }
+                }
+                let Ok(item_layout) = ld.t().layout.sized_layout() else {
+                    continue;
+                };
+                let items = unsafe { (ld.vtable.as_mut_ptr)(PtrMut::new(ptr)) }.as_mut_byte_ptr();
+                for i in 0..len {
+                    stack.push((items.wrapping_add(i * item_layout.size()), ld.t(), false));
+                }
+            }
+            Def::Array(ad) => {
+                let Ok(item_layout) = ad.t().layout.sized_layout() else {
+                    continue;
+                };
+                for i in 0..ad.n {
+                    stack.push((ptr.wrapping_add(i * item_layout.size()), ad.t(), false));
+                }
+            }
+            Def::SmartPointer(sd) => {
+                // Only boxes own their pointee outright, reference-counted pointers may share it
+                if sd.known != Some(KnownSmartPointer::Box) {
+                    continue;
+                }
+                let Some(pointee) = sd.pointee() else {
+                    continue;
+                };
+                match pointee.layout.sized_layout() {
+                    Ok(layout) if layout.size() != 0 => {}
+                    _ => continue,
+                }
+                // A `Box` of a sized type is just a pointer
+                let pointee_ptr = unsafe { *ptr.cast::<*mut u8>() };
+                stack.push((pointee_ptr, pointee, false));
+            }
+            Def::Map(_) | Def::Slice(_) | Def::Scalar(_) => {}
+            // `Drop` impls run before the fields are dropped, and must find them untouched
+            _ if matches!(shape.ty, Type::User(_))
+                && !shape
+                    .vtable
+                    .marker_traits
+                    .contains(MarkerTraits::NO_DROP_IMPL) => {}
+            _ => match shape.ty {
+                Type::User(UserType::Struct(sd)) => {
+                    for field in sd.fields {
+                        stack.push((ptr.wrapping_add(field.offset), field.shape(), false));
+                    }
+                }
+                Type::User(UserType::Enum(_)) => {
+                    let peek = unsafe { Peek::unchecked_new(PtrConst::new(ptr), shape) };
+                    let Ok(peek_enum) = peek.into_enum() else {
+                        continue;
+                    };
+                    let Ok(variant) = peek_enum.active_variant() else {
+                        continue;
+                    };
+                    for field in variant.data.fields {
+                        stack.push((ptr.wrapping_add(field.offset), field.shape(), false));
+                    }
+                }
+                Type::Sequence(SequenceType::Tuple(tt)) => {
+                    for field in tt.fields {
+                        stack.push((ptr.wrapping_add(field.offset), field.shape(), false));
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Whether a value of this shape could own something that drops recursively
+fn needs_walking(shape: &'static Shape) -> bool {
+    shape.vtable.drop_in_place.is_some() && !matches!(shape.def, Def::Scalar(_))
+}
+
+/// Moves the value at `ptr` into a heap buffer of its own, leaving `ptr` logically
+/// uninitialized. Returns `None` (and moves nothing) for unsized and zero-sized shapes.
+unsafe fn detach(ptr: *mut u8, shape: &'static Shape) -> Option<Detached> {
+    let layout = shape.layout.sized_layout().ok()?;
+    if layout.size() == 0 {
+        return None;
+    }
+    // SAFETY: the layout has a non-zero size
+    let copy = unsafe { alloc::alloc::alloc(layout) };
+    if copy.is_null() {
+        alloc::alloc::handle_alloc_error(layout);
+    }
+    unsafe { core::ptr::copy_nonoverlapping(ptr, copy, layout.size()) };
+    Some(Detached {
+        ptr: copy,
+        shape,
+        layout,
+    })
+}
@@ -0,0 +1,160 @@
+use alloc::boxed::Box;
+use core::{marker::PhantomData, mem::MaybeUninit};
+use facet_core::{DefaultInPlaceFn, Facet, Lens, PtrConst, Shape};
+
+use super::{Arena, HeapValue, Wip};
+use crate::{Peek, ReflectError};
+
+/// A [`Wip`] that knows the type of the value it builds, so [`TypedWip::build`] returns it as
+/// a `T` directly, with no need for [`HeapValue::materialize`].
+///
+/// It has the same methods as [`Wip`] for building the value, and derefs to it for the rest.
+pub struct TypedWip<'facet_lifetime, T> {
+    wip: Wip<'facet_lifetime>,
+    marker: PhantomData<T>,
+}
+
+/// Forwards builder methods of [`Wip`] that return it again
+macro_rules! forward {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            #[doc = concat!("See [`Wip::", stringify!($name), "`]")]
+            pub fn $name(self $(, $arg: $ty)*) -> Result<Self, ReflectError> {
+                self.map(|wip| wip.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl<'facet_lifetime, T: Facet<'facet_lifetime>> TypedWip<'facet_lifetime, T> {
+    /// Allocates a new value of type `T`
+    pub fn alloc() -> Result<Self, ReflectError> {
+        Ok(Self::new(Wip::alloc::<T>()?))
+    }
+
+    /// Allocates a new value of type `T` in `arena`, see [`Wip::alloc_shape_in`]
+    pub fn alloc_in(arena: &Arena) -> Result<Self, ReflectError> {
+        Ok(Self::new(Wip::alloc_in::<T>(arena)?))
+    }
+
+    fn new(wip: Wip<'facet_lifetime>) -> Self {
+        Self {
+            wip,
+            marker: PhantomData,
+        }
+    }
+
+    fn map(
+        self,
+        f: impl FnOnce(Wip<'facet_lifetime>) -> Result<Wip<'facet_lifetime>, ReflectError>,
+    ) -> Result<Self, ReflectError> {
+        Ok(Self::new(f(self.wip)?))
+    }
+
+    /// Gives back the untyped [`Wip`]
+    pub fn into_inner(self) -> Wip<'facet_lifetime> {
+        self.wip
+    }
+
+    /// Asserts everything is initialized and that invariants are upheld (if any), like
+    /// [`Wip::build`], and returns the value
+    pub fn build(self) -> Result<T, ReflectError> {
+        self.wip.build()?.materialize::<T>()
+    }
+
+    /// Like [`TypedWip::build`], but returns the value in a `Box`, reusing the memory
+    /// it was built in when it can
+    pub fn build_boxed(self) -> Result<Box<T>, ReflectError> {
+        self.wip.build_boxed::<T>()
+    }
+
+    /// Like [`TypedWip::build`], but moves the value into `dst`, see [`Wip::build_into`]
+    pub fn build_into(self, dst: &mut MaybeUninit<T>) -> Result<&mut T, ReflectError> {
+        self.wip.build_into::<T>(dst)
+    }
+
+    forward! {
+        field(index: usize);
+        field_named(name: &str);
+        parse(s: &str);
+        put_from_fn(default_in_place: DefaultInPlaceFn);
+        put_default();
+        put_default_deep();
+        fill_defaults();
+        try_put_f64(number: f64);
+        put_empty_list();
+        put_empty_map();
+        begin_pushback();
+        begin_map_insert();
+        push();
+        push_some();
+        pop_some_push_none();
+        push_map_key();
+        push_map_value();
+        push_ok();
+        push_err();
+        push_pointee();
+        variant(index: usize);
+        variant_named(name: &str);
+        put_heap_value(value: HeapValue<'facet_lifetime>);
+        pop();
+    }
+
+    /// See [`Wip::lens`]
+    pub fn lens<Root, Target>(self, lens: &Lens<Root, Target>) -> Result<Self, ReflectError>
+    where
+        Root: Facet<'facet_lifetime>,
+        Target: Facet<'facet_lifetime>,
+    {
+        self.map(|wip| wip.lens(lens))
+    }
+
+    /// See [`Wip::put`]
+    pub fn put<U: Facet<'facet_lifetime>>(self, t: U) -> Result<Self, ReflectError> {
+        self.map(|wip| wip.put(t))
+    }
+
+    /// See [`Wip::put_shape`]
+    pub fn put_shape(
+        self,
+        src: PtrConst<'_>,
+        src_shape: &'static Shape,
+    ) -> Result<Self, ReflectError> {
+        self.map(|wip| wip.put_shape(src, src_shape))
+    }
+
+    /// See [`Wip::put_peek`]
+    pub fn put_peek(self, peek: Peek<'_, 'facet_lifetime>) -> Result<Self, ReflectError> {
+        self.map(|wip| wip.put_peek(peek))
+    }
+
+    /// See [`Wip::put_clone`]
+    pub fn put_clone(self, peek: Peek<'_, 'facet_lifetime>) -> Result<Self, ReflectError> {
+        self.map(|wip| wip.put_clone(peek))
+    }
+
+    /// See [`Wip::put_converted`]
+    pub fn put_converted<'mem: 'facet_lifetime>(
+        self,
+        src: Peek<'mem, 'facet_lifetime>,
+    ) -> Result<Self, ReflectError> {
+        self.map(|wip| wip.put_converted(src))
+    }
+}
+
+impl<'facet_lifetime, T> core::ops::Deref for TypedWip<'facet_lifetime, T> {
+    type Target = Wip<'facet_lifetime>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.wip
+    }
+}
+
+impl<'facet_lifetime> Wip<'facet_lifetime> {
+    /// Allocates a new value of type `S`, in a [`TypedWip`] that builds it as an `S`
+    pub fn alloc_typed<S: Facet<'facet_lifetime>>()
+    -> Result<TypedWip<'facet_lifetime, S>, ReflectError> {
+        TypedWip::alloc()
+    }
+}
@@ -0,0 +1,205 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use facet_core::{Def, Field, SequenceType, Shape, Type, UserType, ValidateFn, Validators};
+
+use crate::{HasFields, Peek, ReflectError, trace};
+
+/// Runs the validation functions (`#[facet(validate = ...)]`) and invariants
+/// (`#[facet(invariants = ...)]`) of a value, its fields, and everything it contains, stopping
+/// at the first one that rejects its value
+pub(crate) fn validate(peek: Peek<'_, '_>) -> Result<(), ReflectError> {
+    // This uses a stack rather than recursion, so deeply nested values don't overflow the
+    // call stack
+    let mut stack = Vec::new();
+    push(&mut stack, peek);
+
+    while let Some(peek) = stack.pop() {
+        let shape = peek.shape();
+        if let Some(validate_fn) = shape.vtable.validate {
+            run(validate_fn, peek, shape, None)?;
+        }
+        if let Some(invariants_fn) = shape.vtable.invariants {
+            trace!("Checking the invariants of {}", shape);
+            let mut message = String::new();
+            if !unsafe { invariants_fn(peek.data(), &mut message) } {
+                return Err(ReflectError::InvariantViolated { shape, message });
+            }
+        }
+
+        match (shape.def, shape.ty) {
+            (Def::Option(_), _) => {
+                if let Some(value) = peek.into_option().unwrap().value() {
+                    push(&mut stack, value);
+                }
+            }
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _) => {
+                let list = peek.into_list_like().unwrap();
+                if may_validate(list.def().t()) {
+                    stack.extend(list.iter());
+                }
+            }
+            (Def::Map(map_def), _) => {
+                let (keys, values) = (may_validate((map_def.k)()), may_validate((map_def.v)()));
+                if keys || values {
+                    for (key, value) in peek.into_map().unwrap().iter() {
+                        if keys {
+                            stack.push(key);
+                        }
+                        if values {
+                            stack.push(value);
+                        }
+                    }
+                }
+            }
+            (Def::Set(set_def), _) => {
+                if may_validate((set_def.t)()) {
+                    stack.extend(peek.into_set().unwrap().iter());
+                }
+            }
+            (Def::SmartPointer(_), _) => {
+                if let Some(inner) = peek.into_smart_pointer().unwrap().borrow_inner() {
+                    push(&mut stack, inner);
+                }
+            }
+            (_, Type::User(UserType::Struct(_))) => {
+                validate_fields(&mut stack, &peek.into_struct().unwrap(), shape)?;
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let value = peek.into_enum().unwrap();
+                // Can't tell which variant is active: there are no fields to check
+                if value.active_variant().is_ok() {
+                    validate_fields(&mut stack, &value, shape)?;
+                }
+            }
+            (_, Type::Sequence(SequenceType::Tuple(_))) => {
+                for (_, value) in peek.into_tuple().unwrap().fields() {
+                    push(&mut stack, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the validation functions of the fields of a struct or variant, and queues up their
+/// values
+fn validate_fields<'mem, 'facet_lifetime>(
+    stack: &mut Vec<Peek<'mem, 'facet_lifetime>>,
+    value: &impl HasFields<'mem, 'facet_lifetime>,
+    shape: &'static Shape,
+) -> Result<(), ReflectError> {
+    for (field, value) in value.fields() {
+        if let Some(validate_fn) = field.vtable.validate {
+            run(validate_fn, value, shape, Some(field))?;
+        }
+        push(stack, value);
+    }
+    Ok(())
+}
+
+fn run(
+    validate_fn: ValidateFn,
+    peek: Peek<'_, '_>,
+    shape: &'static Shape,
+    field: Option<Field>,
+) -> Result<(), ReflectError> {
+    trace!("Validating {}", peek.shape());
+    let mut message = String::new();
+    if unsafe { validate_fn(peek.data(), &mut message) } {
+        return Ok(());
+    }
+    Err(ReflectError::ValidationFailed {
+        shape,
+        field_name: field.map(|field| field.name),
+        message,
+    })
+}
+
+fn push<'mem, 'facet_lifetime>(
+    stack: &mut Vec<Peek<'mem, 'facet_lifetime>>,
+    peek: Peek<'mem, 'facet_lifetime>,
+) {
+    if may_validate(peek.shape()) {
+        stack.push(peek);
+    }
+}
+
+/// Whether there may be validation functions to run for values of `shape`, or in them
+fn may_validate(shape: &'static Shape) -> bool {
+    match shape.validators {
+        Validators::Absent => false,
+        Validators::Present => true,
+        Validators::Unknown => reaches_validators(shape),
+    }
+}
+
+/// Looks through the shapes that couldn't be looked at when `shape` was built, e.g. the items
+/// of lists, for validation functions or invariants. The answer is remembered, with `std`.
+fn reaches_validators(shape: &'static Shape) -> bool {
+    #[cfg(feature = "std")]
+    static CACHE: std::sync::RwLock<alloc::collections::BTreeMap<facet_core::ConstTypeId, bool>> =
+        std::sync::RwLock::new(alloc::collections::BTreeMap::new());
+
+    #[cfg(feature = "std")]
+    if let Some(found) = CACHE
+        .read()
+        .ok()
+        .and_then(|cache| cache.get(&shape.id).copied())
+    {
+        return found;
+    }
+
+    // Recursive types refer back to shapes already on their way, hence `seen`
+    let mut seen = vec![shape];
+    let mut stack = vec![shape];
+    let mut found = false;
+    'walk: while let Some(shape) = stack.pop() {
+        for inner in contained_shapes(shape) {
+            match inner.validators {
+                Validators::Absent => {}
+                Validators::Present => {
+                    found = true;
+                    break 'walk;
+                }
+                Validators::Unknown => {
+                    if !seen.contains(&inner) {
+                        seen.push(inner);
+                        stack.push(inner);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    if let Ok(mut cache) = CACHE.write() {
+        cache.insert(shape.id, found);
+    }
+    found
+}
+
+/// The shapes directly contained in values of `shape`, as walked by [`validate`]
+fn contained_shapes(shape: &'static Shape) -> Vec<&'static Shape> {
+    let fields = |fields: &'static [Field]| fields.iter().map(|field| field.shape);
+    match (shape.def, shape.ty) {
+        (Def::Option(od), _) => vec![od.t],
+        (Def::List(ld), _) => vec![ld.t()],
+        (Def::Array(ad), _) => vec![ad.t],
+        (Def::Slice(sd), _) => vec![sd.t],
+        (Def::Map(md), _) => vec![(md.k)(), (md.v)()],
+        (Def::Set(sd), _) => vec![(sd.t)()],
+        (Def::SmartPointer(sd), _) => sd.pointee().into_iter().collect(),
+        (_, Type::User(UserType::Struct(st))) => fields(st.fields).collect(),
+        (_, Type::User(UserType::Enum(et))) => et
+            .variants
+            .iter()
+            .flat_map(|variant| fields(variant.data.fields))
+            .collect(),
+        (_, Type::Sequence(SequenceType::Tuple(tt))) => fields(tt.fields).collect(),
+        _ => Vec::new(),
+    }
+}
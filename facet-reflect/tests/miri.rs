@@ -0,0 +1,287 @@
+//! The unsafe paths of the reflection core, small enough to run under Miri on every change:
+//! `MIRIFLAGS=-Zmiri-strict-provenance cargo +nightly miri nextest run -p facet-reflect --test miri`
+//!
+//! Values hold `Arc` tokens, so even outside Miri each test checks that everything it built or
+//! replaced was dropped exactly once.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_reflect::{Peek, Wip};
+
+// Values edited in place need a default to fall back on
+#[derive(Facet, Debug, PartialEq, Default)]
+struct Record {
+    // A byte before a wider field, so fields sit at padded offsets
+    flag: u8,
+    id: u64,
+    name: String,
+    token: Arc<()>,
+    samples: Vec<u16>,
+    counts: BTreeMap<String, u32>,
+    parent: Option<Box<u32>>,
+    pair: (u8, u64),
+    kind: Kind,
+}
+
+#[derive(Facet, Debug, PartialEq, Default)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Kind {
+    #[default]
+    Empty,
+    Tagged(u8, String),
+    Owned {
+        token: Arc<()>,
+        label: String,
+    },
+}
+
+fn record(token: &Arc<()>) -> Record {
+    Record {
+        flag: 1,
+        id: u64::MAX,
+        name: "record".to_string(),
+        token: token.clone(),
+        samples: vec![1, 2, 3],
+        counts: BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+        parent: Some(Box::new(7)),
+        pair: (3, 4),
+        kind: Kind::Owned {
+            token: token.clone(),
+            label: "owned".to_string(),
+        },
+    }
+}
+
+#[test]
+fn peek_reads_every_kind_of_field() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let record = record(&token);
+    let peek = Peek::new(&record).into_struct()?;
+
+    assert_eq!(*peek.field_by_name("flag")?.get::<u8>()?, 1);
+    assert_eq!(*peek.field_by_name("id")?.get::<u64>()?, u64::MAX);
+    assert_eq!(peek.field_by_name("name")?.as_str(), Some("record"));
+
+    let samples = peek.field_by_name("samples")?.into_list()?;
+    let samples: Vec<u16> = samples.iter().map(|s| *s.get::<u16>().unwrap()).collect();
+    assert_eq!(samples, [1, 2, 3]);
+
+    let counts = peek.field_by_name("counts")?.into_map()?;
+    assert_eq!(counts.get(&"b".to_string()).unwrap().get::<u32>()?, &2);
+    let keys: Vec<String> = counts.iter().map(|(k, _)| k.to_string()).collect();
+    assert_eq!(keys, ["a", "b"]);
+
+    let parent = peek
+        .field_by_name("parent")?
+        .into_option()?
+        .value()
+        .unwrap();
+    let parent = parent.into_smart_pointer()?.borrow_inner().unwrap();
+    assert_eq!(*parent.get::<u32>()?, 7);
+
+    let pair = peek.field_by_name("pair")?.into_tuple()?;
+    assert_eq!(*pair.field(1).unwrap().get::<u64>()?, 4);
+
+    let kind = peek.field_by_name("kind")?.into_enum()?;
+    assert_eq!(kind.variant_name_active()?, "Owned");
+    let label = kind.field_by_name("label")?.unwrap();
+    assert_eq!(label.as_str(), Some("owned"));
+
+    drop(record);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn build_and_materialize() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let built = Wip::alloc::<Record>()?
+        .field_named("flag")?
+        .put(1u8)?
+        .pop()?
+        .field_named("id")?
+        .put(u64::MAX)?
+        .pop()?
+        .field_named("name")?
+        .put("record".to_string())?
+        .pop()?
+        .field_named("token")?
+        .put(token.clone())?
+        .pop()?
+        .field_named("samples")?
+        .begin_pushback()?
+        .push()?
+        .put(1u16)?
+        .pop()?
+        .push()?
+        .put(2u16)?
+        .pop()?
+        .push()?
+        .put(3u16)?
+        .pop()?
+        .pop()?
+        .field_named("counts")?
+        .begin_map_insert()?
+        .push_map_key()?
+        .put("a".to_string())?
+        .push_map_value()?
+        .put(1u32)?
+        .pop()?
+        .push_map_key()?
+        .put("b".to_string())?
+        .push_map_value()?
+        .put(2u32)?
+        .pop()?
+        .pop()?
+        .field_named("parent")?
+        .put(Some(Box::new(7u32)))?
+        .pop()?
+        .field_named("pair")?
+        .put((3u8, 4u64))?
+        .pop()?
+        .field_named("kind")?
+        .variant_named("Owned")?
+        .field_named("token")?
+        .put(token.clone())?
+        .pop()?
+        .field_named("label")?
+        .put("owned".to_string())?
+        .pop()?
+        .pop()?
+        .build()?
+        .materialize::<Record>()?;
+
+    assert_eq!(built, record(&token));
+
+    drop(built);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn drop_partial_builds() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+
+    // A few fields set, the rest never touched
+    let wip = Wip::alloc::<Record>()?
+        .field_named("name")?
+        .put("record".to_string())?
+        .pop()?
+        .field_named("token")?
+        .put(token.clone())?
+        .pop()?;
+    drop(wip);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    // Inside a list element
+    let wip = Wip::alloc::<Vec<Arc<()>>>()?
+        .begin_pushback()?
+        .push()?
+        .put(token.clone())?
+        .pop()?
+        .push()?;
+    drop(wip);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    // With a map key but no value
+    let wip = Wip::alloc::<BTreeMap<String, Arc<()>>>()?
+        .begin_map_insert()?
+        .push_map_key()?
+        .put("key".to_string())?
+        .push_map_value()?;
+    drop(wip);
+
+    // Halfway through a variant
+    let wip = Wip::alloc::<Kind>()?
+        .variant_named("Owned")?
+        .field_named("token")?
+        .put(token.clone())?
+        .pop()?;
+    drop(wip);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn heap_values() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+
+    // Read through a peek, then moved into a box
+    let heap_value = Wip::alloc::<Record>()?.put(record(&token))?.build()?;
+    let peek = heap_value.peek().into_struct()?;
+    assert_eq!(peek.field_by_name("name")?.as_str(), Some("record"));
+    let boxed = heap_value.into_box::<Record>()?;
+    assert_eq!(*boxed, record(&token));
+    drop(boxed);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    // Dropped without being taken out
+    let heap_value = Wip::alloc::<Record>()?.put(record(&token))?.build()?;
+    drop(heap_value);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    // Taken out as the wrong type, which leaves it to be dropped
+    let heap_value = Wip::alloc::<Record>()?.put(record(&token))?.build()?;
+    assert!(heap_value.materialize::<Kind>().is_err());
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn edit_in_place() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let mut record = record(&token);
+
+    Wip::from_mut(&mut record)
+        .field_named("name")?
+        .put("renamed".to_string())?
+        .pop()?
+        .field_named("samples")?
+        .begin_pushback()?
+        .push()?
+        .put(4u16)?
+        .pop()?
+        .pop()?
+        .field_named("parent")?
+        .put_default()?
+        .pop()?
+        .field_named("kind")?
+        .variant_named("Tagged")?
+        .field(0)?
+        .put(5u8)?
+        .pop()?
+        .field(1)?
+        .put("tagged".to_string())?
+        .pop()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(record.name, "renamed");
+    assert_eq!(record.samples, [1, 2, 3, 4]);
+    assert_eq!(record.parent, None);
+    assert_eq!(record.kind, Kind::Tagged(5, "tagged".to_string()));
+
+    // The replaced variant's token is gone, leaving the one in `record.token`
+    assert_eq!(Arc::strong_count(&token), 2);
+    drop(record);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
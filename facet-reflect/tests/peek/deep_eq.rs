@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_reflect::{Peek, deep_eq};
+
+/// No `PartialEq` anywhere, so the vtable has no `eq` for any of these
+#[derive(Facet)]
+struct Config {
+    name: String,
+    retries: Option<u8>,
+    hosts: Vec<Host>,
+    limits: HashMap<String, u32>,
+    tags: BTreeSet<String>,
+    mode: Mode,
+}
+
+#[derive(Facet)]
+struct Host {
+    address: String,
+    weight: Wrapper,
+}
+
+#[derive(Facet)]
+#[facet(transparent)]
+struct Wrapper(u32);
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Off,
+    Fixed { level: u8 },
+}
+
+fn config() -> Config {
+    Config {
+        name: "web".to_string(),
+        retries: Some(3),
+        hosts: vec![Host {
+            address: "10.0.0.1".to_string(),
+            weight: Wrapper(2),
+        }],
+        limits: [("cpu".to_string(), 4), ("memory".to_string(), 512)]
+            .into_iter()
+            .collect(),
+        tags: ["a".to_string(), "b".to_string()].into_iter().collect(),
+        mode: Mode::Fixed { level: 1 },
+    }
+}
+
+#[test]
+fn deep_eq_without_partial_eq() {
+    facet_testhelpers::setup();
+
+    let (a, b) = (config(), config());
+    assert!(deep_eq(Peek::new(&a), Peek::new(&b)));
+}
+
+#[test]
+fn deep_eq_finds_differences() {
+    facet_testhelpers::setup();
+
+    let a = config();
+    let changes: Vec<fn(&mut Config)> = vec![
+        |c| c.name.push('!'),
+        |c| c.retries = None,
+        |c| c.hosts[0].weight = Wrapper(3),
+        |c| c.hosts.clear(),
+        |c| *c.limits.get_mut("cpu").unwrap() = 8,
+        |c| {
+            c.limits.remove("cpu");
+            c.limits.insert("gpu".to_string(), 4);
+        },
+        |c| {
+            c.tags.insert("c".to_string());
+        },
+        |c| c.mode = Mode::Off,
+        |c| c.mode = Mode::Fixed { level: 2 },
+    ];
+    for (i, change) in changes.into_iter().enumerate() {
+        let mut b = config();
+        change(&mut b);
+        assert!(!deep_eq(Peek::new(&a), Peek::new(&b)), "change #{i}");
+    }
+}
+
+#[test]
+fn deep_eq_looks_through_wrappers() {
+    facet_testhelpers::setup();
+
+    assert!(deep_eq(Peek::new(&Wrapper(5)), Peek::new(&5u32)));
+    assert!(deep_eq(
+        Peek::new(&Box::new(5u32)),
+        Peek::new(&Arc::new(5u32))
+    ));
+    assert!(!deep_eq(Peek::new(&5u32), Peek::new(&5u64)));
+
+    // Containers only need the same contents
+    assert!(deep_eq(
+        Peek::new(&[1u8, 2, 3]),
+        Peek::new(&vec![1u8, 2, 3])
+    ));
+    let hashed: HashMap<u8, Wrapper> = [(1, Wrapper(1)), (2, Wrapper(2))].into_iter().collect();
+    let sorted: BTreeMap<u8, u32> = [(1, 1), (2, 2)].into_iter().collect();
+    assert!(deep_eq(Peek::new(&hashed), Peek::new(&sorted)));
+    let hashed: HashSet<u8> = [3, 1, 2].into_iter().collect();
+    let sorted: BTreeSet<u8> = [1, 2, 3].into_iter().collect();
+    assert!(deep_eq(Peek::new(&hashed), Peek::new(&sorted)));
+}
@@ -0,0 +1,139 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use facet::Facet;
+use facet_reflect::{Change, Peek, diff};
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    retries: Option<u8>,
+    servers: Vec<Server>,
+    env: BTreeMap<String, String>,
+    tags: BTreeSet<String>,
+    mode: Mode,
+}
+
+#[derive(Facet)]
+struct Server {
+    address: String,
+    port: u16,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Off,
+    Fixed { level: u8 },
+    Ratio(f32),
+}
+
+fn config() -> Config {
+    Config {
+        name: "web".to_string(),
+        retries: None,
+        servers: vec![Server {
+            address: "10.0.0.1".to_string(),
+            port: 80,
+        }],
+        env: [
+            ("HOME".to_string(), "/root".to_string()),
+            ("a.b".to_string(), "1".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+        tags: ["blue".to_string()].into_iter().collect(),
+        mode: Mode::Fixed { level: 1 },
+    }
+}
+
+#[test]
+fn diff_equal_values() {
+    facet_testhelpers::setup();
+
+    let (old, new) = (config(), config());
+    let diff = diff(Peek::new(&old), Peek::new(&new));
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn diff_reports_changes_with_paths() {
+    facet_testhelpers::setup();
+
+    let old = config();
+    let mut new = config();
+    new.name = "api".to_string();
+    new.retries = Some(3);
+    new.servers[0].port = 8080;
+    new.servers.push(Server {
+        address: "10.0.0.2".to_string(),
+        port: 80,
+    });
+    new.env.remove("HOME");
+    new.env.insert("a.b".to_string(), "2".to_string());
+    new.env.insert("PATH".to_string(), "/bin".to_string());
+    new.tags.insert("green".to_string());
+    new.mode = Mode::Fixed { level: 2 };
+
+    let diff = diff(Peek::new(&old), Peek::new(&new));
+    assert_eq!(
+        diff.to_string(),
+        r#"~ name: "web" -> "api"
++ retries.Some: 3
+~ servers[0].port: 80 -> 8080
++ servers[1]: ⟨Server⟩
+- env.HOME: "/root"
+~ env["a.b"]: "1" -> "2"
++ env.PATH: "/bin"
++ tags: "green"
+~ mode.level: 1 -> 2
+"#
+    );
+
+    // Every path leads back to the value in the new (or, for removals, the old) value
+    for change in diff.changes() {
+        match change {
+            Change::Added { path, value } if path != "tags" => {
+                assert_eq!(Peek::new(&new).at(path).unwrap().shape(), value.shape());
+            }
+            Change::Removed { path, value } => {
+                assert_eq!(Peek::new(&old).at(path).unwrap().shape(), value.shape());
+            }
+            Change::Changed {
+                path, new: value, ..
+            } => {
+                assert_eq!(Peek::new(&new).at(path).unwrap().shape(), value.shape());
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn diff_other_variant_is_a_change() {
+    facet_testhelpers::setup();
+
+    let old = Mode::Fixed { level: 1 };
+    let new = Mode::Ratio(0.5);
+    let diff = diff(Peek::new(&old), Peek::new(&new));
+    assert_eq!(diff.len(), 1);
+    assert!(matches!(
+        &diff.changes()[0],
+        Change::Changed { path, .. } if path.is_empty()
+    ));
+
+    let old = Mode::Ratio(0.25);
+    let diff = facet_reflect::diff(Peek::new(&old), Peek::new(&new));
+    assert_eq!(diff.to_string(), "~ [0]: 0.25 -> 0.5\n");
+}
+
+#[test]
+fn diff_lists_by_index() {
+    facet_testhelpers::setup();
+
+    let old = vec![1u32, 2, 3];
+    let new = vec![1u32, 5];
+    let diff = diff(Peek::new(&old), Peek::new(&new));
+    assert_eq!(diff.to_string(), "~ [1]: 2 -> 5\n- [2]: 3\n");
+}
@@ -47,6 +47,40 @@ fn peek_enum() -> eyre::Result<()> {
     Ok(())
 }
 
+#[derive(Facet)]
+#[repr(u16)]
+#[allow(dead_code)]
+enum Status {
+    Active = 1,
+    #[facet(rename = "on_hold")]
+    OnHold = 10,
+    Closed(u32) = 20,
+}
+
+#[test]
+fn peek_enum_find_variant() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let value = Status::OnHold;
+    let peek_enum = Peek::new(&value).into_enum()?;
+
+    assert_eq!(peek_enum.variant_count(), 3);
+    let (index, variant) = peek_enum.find_variant("on_hold").unwrap();
+    assert_eq!(index, 1);
+    assert_eq!(variant.discriminant, Some(10));
+    assert!(peek_enum.find_variant("OnHold").is_none());
+
+    let (index, variant) = peek_enum.find_variant_by_discriminant(20).unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(variant.name, "Closed");
+    assert!(peek_enum.find_variant_by_discriminant(2).is_none());
+
+    assert_eq!(peek_enum.discriminant(), 10);
+    assert_eq!(peek_enum.variant_index()?, 1);
+
+    Ok(())
+}
+
 #[derive(Facet)]
 #[repr(C)]
 #[allow(dead_code)]
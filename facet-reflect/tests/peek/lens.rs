@@ -0,0 +1,55 @@
+use facet::{Facet, Lens};
+use facet_reflect::{Peek, ReflectError};
+
+#[derive(Facet)]
+#[facet(lenses)]
+struct Server {
+    name: String,
+    listen: Listen,
+}
+
+#[derive(Facet)]
+#[facet(lenses)]
+struct Listen {
+    host: String,
+    port: Port,
+}
+
+#[derive(Facet)]
+#[facet(lenses)]
+struct Port(u16);
+
+const PORT: Lens<Server, u16> = Server::fields()
+    .listen()
+    .then(Listen::fields().port())
+    .then(Port::fields()._0());
+
+#[test]
+fn peek_through_lenses() {
+    facet_testhelpers::setup();
+
+    let server = Server {
+        name: "primary".into(),
+        listen: Listen {
+            host: "localhost".into(),
+            port: Port(8080),
+        },
+    };
+    let peek = Peek::new(&server);
+
+    assert_eq!(PORT.indices(), &[1, 1, 0]);
+    assert_eq!(format!("{PORT:?}"), "Server.listen.port.0");
+    assert_eq!(peek.lens(&PORT).unwrap().get::<u16>(), Ok(&8080));
+
+    let name = peek.lens(&Server::fields().name()).unwrap();
+    assert_eq!(name.as_str(), Some("primary"));
+    let host = Server::fields().listen().then(Listen::fields().host());
+    assert_eq!(peek.lens(&host).unwrap().as_str(), Some("localhost"));
+
+    // A lens only goes from the type it starts at
+    let listen = Peek::new(&server.listen);
+    assert!(matches!(
+        listen.lens(&PORT),
+        Err(ReflectError::WrongShape { .. })
+    ));
+}
@@ -1,5 +1,5 @@
 use facet_reflect::Peek;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_peek_map_basics() {
@@ -83,3 +83,74 @@ fn test_peek_map_different_types() {
     assert_eq!(peek_map.get(&2).unwrap().get::<&str>().unwrap(), &"two");
     assert!(peek_map.get(&3).is_none());
 }
+
+#[test]
+fn test_peek_map_peek_keys() {
+    facet_testhelpers::setup();
+
+    let mut source = BTreeMap::new();
+    source.insert(String::from("gems"), 12u32);
+    source.insert(String::from("coins"), 40u32);
+
+    let peek_map = Peek::new(&source).into_map().unwrap();
+
+    let gems = String::from("gems");
+    assert!(peek_map.contains_key_peek(Peek::new(&gems)));
+    assert_eq!(
+        peek_map
+            .get_peek(Peek::new(&gems))
+            .unwrap()
+            .get::<u32>()
+            .unwrap(),
+        &12
+    );
+
+    let keys = vec![String::from("coins"), String::from("stars")];
+    let keys = Peek::new(&keys).into_list().unwrap();
+    assert_eq!(
+        peek_map
+            .get_peek(keys.get(0).unwrap())
+            .unwrap()
+            .get::<u32>()
+            .unwrap(),
+        &40
+    );
+    assert!(!peek_map.contains_key_peek(keys.get(1).unwrap()));
+    assert!(peek_map.get_peek(keys.get(1).unwrap()).is_none());
+
+    // Keys of another type are never found
+    assert!(!peek_map.contains_key_peek(Peek::new(&12u32)));
+    assert!(peek_map.get_peek(Peek::new(&"gems")).is_none());
+}
+
+#[test]
+fn test_peek_map_iter_sorted() {
+    facet_testhelpers::setup();
+
+    let source: HashMap<&str, i32> = [("d", 4), ("a", 1), ("c", 3), ("b", 2), ("e", 5)].into();
+    let peek_map = Peek::new(&source).into_map().unwrap();
+
+    let entries: Vec<(&str, i32)> = peek_map
+        .iter_sorted()
+        .map(|(k, v)| (*k.get::<&str>().unwrap(), *v.get::<i32>().unwrap()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]
+    );
+}
+
+#[test]
+fn test_peek_map_iter_sorted_numeric_keys() {
+    facet_testhelpers::setup();
+
+    // Sorted by value, not by their string representation
+    let source: HashMap<u32, &str> = [(10, "ten"), (9, "nine"), (100, "hundred")].into();
+    let peek_map = Peek::new(&source).into_map().unwrap();
+
+    let keys: Vec<u32> = peek_map
+        .iter_sorted()
+        .map(|(k, _)| *k.get::<u32>().unwrap())
+        .collect();
+    assert_eq!(keys, vec![9, 10, 100]);
+}
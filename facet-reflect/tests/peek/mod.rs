@@ -1,10 +1,20 @@
+mod deep_eq;
+mod diff;
 mod enum_;
 #[cfg(feature = "std")]
 mod facts;
+mod lens;
 mod list;
 mod list_like;
 mod map;
 mod option;
+mod path;
+mod peek_mut;
+mod pointer;
+mod redacted;
+mod result;
+mod set;
 mod smartptr;
 mod struct_;
 mod value;
+mod visit;
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_reflect::{Peek, ReflectError};
+
+#[derive(Facet)]
+struct Cluster {
+    name: String,
+    servers: Vec<Server>,
+    labels: HashMap<String, String>,
+    ports: HashMap<u16, Service>,
+    primary: Option<Box<Server>>,
+    shape: Figure,
+    pair: (u8, Pair),
+}
+
+#[derive(Facet)]
+struct Server {
+    address: String,
+    weight: u32,
+}
+
+#[derive(Facet)]
+struct Service {
+    protocol: String,
+}
+
+#[derive(Facet)]
+struct Pair(u8, String);
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Figure {
+    Circle { radius: u32 },
+    Line(u32, u32),
+}
+
+fn cluster() -> Cluster {
+    Cluster {
+        name: "main".to_string(),
+        servers: vec![
+            Server {
+                address: "10.0.0.1".to_string(),
+                weight: 1,
+            },
+            Server {
+                address: "10.0.0.2".to_string(),
+                weight: 3,
+            },
+        ],
+        labels: [("app.kubernetes.io/name".to_string(), "web".to_string())]
+            .into_iter()
+            .collect(),
+        ports: [(
+            443,
+            Service {
+                protocol: "https".to_string(),
+            },
+        )]
+        .into_iter()
+        .collect(),
+        primary: Some(Box::new(Server {
+            address: "10.0.0.9".to_string(),
+            weight: 9,
+        })),
+        shape: Figure::Circle { radius: 4 },
+        pair: (1, Pair(2, "two".to_string())),
+    }
+}
+
+#[test]
+fn peek_at_paths() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let cluster = cluster();
+    let peek = Peek::new(&cluster);
+
+    assert_eq!(peek.at("name")?.get::<String>()?, "main");
+    assert_eq!(peek.at("servers[1].address")?.get::<String>()?, "10.0.0.2");
+    assert_eq!(*peek.at("servers[0].weight")?.get::<u32>()?, 1);
+    assert_eq!(
+        peek.at(r#"labels["app.kubernetes.io/name"]"#)?
+            .get::<String>()?,
+        "web"
+    );
+    assert_eq!(peek.at("ports[443].protocol")?.get::<String>()?, "https");
+    assert_eq!(peek.at("ports.443.protocol")?.get::<String>()?, "https");
+    assert_eq!(*peek.at("primary.Some.weight")?.get::<u32>()?, 9);
+    assert_eq!(*peek.at("shape.radius")?.get::<u32>()?, 4);
+    assert_eq!(*peek.at("shape.Circle.radius")?.get::<u32>()?, 4);
+    assert_eq!(*peek.at("pair[0]")?.get::<u8>()?, 1);
+    assert_eq!(peek.at("pair.1.1")?.get::<String>()?, "two");
+    assert_eq!(*peek.at("pair[1][0]")?.get::<u8>()?, 2);
+
+    // An empty path is the value itself, and paths can start with an index
+    assert!(peek.at("")?.ptr_eq(&peek));
+    let servers = Peek::new(&cluster.servers);
+    assert_eq!(*servers.at("[1].weight")?.get::<u32>()?, 3);
+
+    Ok(())
+}
+
+#[test]
+fn peek_at_missing_paths() {
+    facet_testhelpers::setup();
+
+    let cluster = cluster();
+    let peek = Peek::new(&cluster);
+
+    let err = peek.at("servers[2].address").unwrap_err();
+    assert_eq!(
+        err,
+        ReflectError::PathNotFound {
+            path: "servers[2].address".to_string(),
+            segment: "[2]".to_string(),
+            shape: <Vec<Server>>::SHAPE,
+        }
+    );
+
+    let err = peek.at("servers[0].port").unwrap_err();
+    assert!(
+        matches!(&err, ReflectError::PathNotFound { segment, shape, .. } if segment == "port" && *shape == Server::SHAPE),
+        "{err:?}"
+    );
+
+    // The active variant is Circle
+    assert!(matches!(
+        peek.at("shape.Line[0]"),
+        Err(ReflectError::PathNotFound { segment, .. }) if segment == "Line"
+    ));
+    assert!(matches!(
+        peek.at(r#"labels["nope"]"#),
+        Err(ReflectError::PathNotFound { segment, .. }) if segment == r#"["nope"]"#
+    ));
+    // Options only have a `Some` to look into
+    assert!(peek.at("primary.address").is_err());
+}
+
+#[test]
+fn peek_at_invalid_paths() {
+    facet_testhelpers::setup();
+
+    let cluster = cluster();
+    let peek = Peek::new(&cluster);
+
+    assert_eq!(
+        peek.at("servers[one]").unwrap_err(),
+        ReflectError::InvalidPath {
+            path: "servers[one]".to_string(),
+            offset: 8,
+        }
+    );
+    assert!(matches!(
+        peek.at("name..len"),
+        Err(ReflectError::InvalidPath { offset: 5, .. })
+    ));
+}
+
+#[test]
+fn peek_at_looks_through_transparent_wrappers() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    #[facet(transparent)]
+    struct Addresses(Vec<String>);
+
+    let addresses = Addresses(vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()]);
+    let peek = Peek::new(&addresses);
+
+    assert_eq!(peek.at("[1]")?.get::<String>()?, "10.0.0.2");
+    assert!(peek.at("[0]")?.ptr_eq(&peek.resolve_pointer("/0")?));
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use facet::Facet;
+use facet_reflect::{PeekMut, ReflectError};
+use std::collections::HashMap;
+use std::num::NonZero;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Inventory {
+    owner: String,
+    items: Vec<Item>,
+    counts: HashMap<String, u32>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Item {
+    Tool { name: String },
+    Coins(u32),
+}
+
+fn inventory() -> Inventory {
+    Inventory {
+        owner: "alice".to_string(),
+        items: vec![Item::Coins(3)],
+        counts: HashMap::from([("coins".to_string(), 3)]),
+    }
+}
+
+#[test]
+fn test_peek_mut_struct_fields() {
+    facet_testhelpers::setup();
+
+    let mut inventory = inventory();
+    let mut peek = PeekMut::new(&mut inventory).into_struct().unwrap();
+    assert_eq!(peek.field_count(), 3);
+
+    let mut owner = peek.field_by_name("owner").unwrap();
+    assert_eq!(owner.get::<String>().unwrap(), "alice");
+    owner.get_mut::<String>().unwrap().push_str(" & bob");
+    assert_eq!(inventory.owner, "alice & bob");
+}
+
+#[test]
+fn test_peek_mut_set() {
+    facet_testhelpers::setup();
+
+    let mut inventory = inventory();
+    let mut peek = PeekMut::new(&mut inventory).into_struct().unwrap();
+    peek.field_by_name("owner")
+        .unwrap()
+        .set("carol".to_string())
+        .unwrap();
+
+    let Err(err) = peek.field_by_name("owner").unwrap().set(1u32) else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+    assert_eq!(inventory.owner, "carol");
+}
+
+#[test]
+fn test_peek_mut_list() {
+    facet_testhelpers::setup();
+
+    let mut inventory = inventory();
+    let mut peek = PeekMut::new(&mut inventory).into_struct().unwrap();
+    let mut items = peek.field_by_name("items").unwrap().into_list().unwrap();
+    items
+        .push(Item::Tool {
+            name: "hammer".to_string(),
+        })
+        .unwrap();
+    assert_eq!(items.len(), 2);
+
+    let mut coins = items.get_mut(0).unwrap().into_enum().unwrap();
+    assert_eq!(coins.active_variant().unwrap().name, "Coins");
+    coins.field(0).unwrap().unwrap().set(5u32).unwrap();
+    assert!(items.get_mut(2).is_none());
+
+    assert_eq!(
+        inventory.items,
+        vec![
+            Item::Coins(5),
+            Item::Tool {
+                name: "hammer".to_string()
+            }
+        ]
+    );
+
+    let mut peek = PeekMut::new(&mut inventory.items).into_list().unwrap();
+    peek.clear().unwrap();
+    assert!(peek.is_empty());
+    assert!(inventory.items.is_empty());
+}
+
+#[test]
+fn test_peek_mut_map() {
+    facet_testhelpers::setup();
+
+    let mut counts = inventory().counts;
+    let mut peek = PeekMut::new(&mut counts).into_map().unwrap();
+    peek.insert("gems".to_string(), 1u32).unwrap();
+    peek.insert("coins".to_string(), 4u32).unwrap();
+    assert!(peek.contains_key(&"gems".to_string()));
+    assert_eq!(peek.len(), 2);
+
+    let Err(err) = peek.insert("gems", 1u32) else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+    assert_eq!(
+        counts,
+        HashMap::from([("coins".to_string(), 4), ("gems".to_string(), 1)])
+    );
+
+    let mut peek = PeekMut::new(&mut counts).into_map().unwrap();
+    peek.clear();
+    assert!(counts.is_empty());
+}
+
+#[test]
+fn test_peek_mut_wrong_kind() {
+    facet_testhelpers::setup();
+
+    let mut owner = "alice".to_string();
+    let Err(err) = PeekMut::new(&mut owner).into_struct() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::WasNotA { .. }), "{err}");
+}
+
+#[test]
+fn test_peek_mut_refuses_guarded_fields() {
+    facet_testhelpers::setup();
+
+    let mut count = NonZero::new(3u32).unwrap();
+    let Err(err) = PeekMut::new(&mut count).into_struct() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+    assert_eq!(count.get(), 3);
+}
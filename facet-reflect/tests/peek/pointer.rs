@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_reflect::{Peek, PointerErrorKind};
+
+#[derive(Facet)]
+struct Directory {
+    users: Vec<User>,
+    by_id: HashMap<u32, Arc<User>>,
+    owner: Option<User>,
+    #[facet(flatten)]
+    meta: Meta,
+}
+
+#[derive(Facet)]
+struct User {
+    name: String,
+    tags: (String, String),
+}
+
+#[derive(Facet)]
+struct Meta {
+    #[facet(rename = "a/b~c")]
+    odd: u8,
+}
+
+fn user(name: &str) -> User {
+    User {
+        name: name.to_string(),
+        tags: ("x".to_string(), "y".to_string()),
+    }
+}
+
+fn directory() -> Directory {
+    Directory {
+        users: vec![user("alice"), user("bob")],
+        by_id: HashMap::from([(7, Arc::new(user("carol")))]),
+        owner: None,
+        meta: Meta { odd: 9 },
+    }
+}
+
+#[test]
+fn resolve_pointer_happy_paths() {
+    facet_testhelpers::setup();
+
+    let dir = directory();
+    let peek = Peek::new(&dir);
+
+    let name = peek.resolve_pointer("/users/1/name").unwrap();
+    assert_eq!(name.as_str(), Some("bob"));
+
+    let tag = peek.resolve_pointer("/users/0/tags/1").unwrap();
+    assert_eq!(tag.as_str(), Some("y"));
+
+    let via_map = peek.resolve_pointer("/by_id/7/name").unwrap();
+    assert_eq!(via_map.as_str(), Some("carol"));
+
+    let flattened = peek.resolve_pointer("/a~1b~0c").unwrap();
+    assert_eq!(flattened.get::<u8>().unwrap(), &9);
+
+    let whole = peek.resolve_pointer("").unwrap();
+    assert!(whole.ptr_eq(&peek));
+}
+
+#[test]
+fn resolve_pointer_errors() {
+    facet_testhelpers::setup();
+
+    let dir = directory();
+    let peek = Peek::new(&dir);
+
+    let err = peek.resolve_pointer("/users/2/name").unwrap_err();
+    assert_eq!(err.segment_index, 1);
+    assert_eq!(err.segment, "2");
+    assert_eq!(err.kind, PointerErrorKind::IndexOutOfBounds { len: 2 });
+
+    let err = peek.resolve_pointer("/users/01").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::InvalidIndex);
+
+    let err = peek.resolve_pointer("/users/0/email").unwrap_err();
+    assert_eq!(err.segment_index, 2);
+    assert_eq!(err.kind, PointerErrorKind::NoSuchField);
+    assert!(err.shape.is_type::<User>());
+
+    let err = peek.resolve_pointer("/owner/name").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::OptionIsNone);
+
+    let err = peek.resolve_pointer("/by_id/8").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::NoSuchKey);
+
+    let err = peek.resolve_pointer("/users/0/name/first").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::NotTraversable);
+
+    let err = peek.resolve_pointer("/~2").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::InvalidEscape);
+
+    let err = peek.resolve_pointer("users").unwrap_err();
+    assert_eq!(err.kind, PointerErrorKind::MissingLeadingSlash);
+}
@@ -0,0 +1,64 @@
+use facet::Facet;
+use facet_reflect::Peek;
+
+#[derive(Debug, Facet)]
+struct Credentials {
+    user: String,
+    #[facet(sensitive)]
+    password: String,
+}
+
+#[derive(Debug, Facet)]
+struct Service {
+    name: String,
+    credentials: Vec<Credentials>,
+    auth: Auth,
+}
+
+#[derive(Debug, Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Auth {
+    None,
+    Token(#[facet(sensitive)] String),
+    Basic {
+        user: String,
+        password: Option<String>,
+    },
+}
+
+#[test]
+fn debug_redacted_hides_sensitive_fields() {
+    facet_testhelpers::setup();
+
+    let service = Service {
+        name: "db".to_string(),
+        credentials: vec![Credentials {
+            user: "admin".to_string(),
+            password: "hunter2".to_string(),
+        }],
+        auth: Auth::Token("s3cr3t".to_string()),
+    };
+
+    let debug = format!("{:?}", Peek::new(&service).debug_redacted());
+    assert_eq!(
+        debug,
+        r#"Service { name: "db", credentials: [Credentials { user: "admin", password: [REDACTED] }], auth: Token([REDACTED]) }"#
+    );
+}
+
+#[test]
+fn debug_redacted_matches_debug_without_sensitive_fields() {
+    facet_testhelpers::setup();
+
+    let auth = Auth::Basic {
+        user: "admin".to_string(),
+        password: Some("hunter2".to_string()),
+    };
+    let peek = Peek::new(&auth);
+    assert_eq!(format!("{:?}", peek.debug_redacted()), format!("{auth:?}"));
+    assert_eq!(
+        format!("{:#?}", peek.debug_redacted()),
+        format!("{auth:#?}")
+    );
+}
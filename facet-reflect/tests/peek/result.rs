@@ -0,0 +1,36 @@
+use facet_reflect::Peek;
+
+#[test]
+fn peek_result() {
+    facet_testhelpers::setup();
+
+    let ok: Result<i32, String> = Ok(42);
+    let peek_result = Peek::new(&ok)
+        .into_result()
+        .expect("Should be convertible to result");
+    assert!(peek_result.is_ok());
+    assert!(!peek_result.is_err());
+    assert_eq!(*peek_result.ok().unwrap().get::<i32>().unwrap(), 42);
+    assert!(peek_result.err().is_none());
+
+    let err: Result<i32, String> = Err("nope".to_string());
+    let peek_result = Peek::new(&err)
+        .into_result()
+        .expect("Should be convertible to result");
+    assert!(peek_result.is_err());
+    assert!(peek_result.ok().is_none());
+    let Err(error) = peek_result.value() else {
+        panic!("expected an Err");
+    };
+    assert_eq!(error.get::<String>().unwrap(), "nope");
+}
+
+#[test]
+fn peek_result_debug() {
+    facet_testhelpers::setup();
+
+    let ok: Result<u8, String> = Ok(7);
+    assert_eq!(format!("{:?}", Peek::new(&ok)), "Ok(7)");
+    let err: Result<u8, String> = Err("bad".to_string());
+    assert_eq!(format!("{:?}", Peek::new(&err)), r#"Err("bad")"#);
+}
@@ -0,0 +1,59 @@
+use facet_reflect::Peek;
+use std::collections::{BTreeSet, HashSet};
+
+#[test]
+fn test_peek_set_basics() {
+    facet_testhelpers::setup();
+
+    let source: HashSet<&str> = ["a", "b", "c"].into_iter().collect();
+
+    let peek_value = Peek::new(&source);
+    let peek_set = peek_value.into_set().unwrap();
+    assert_eq!(peek_set.len(), 3);
+    assert!(!peek_set.is_empty());
+
+    assert!(peek_set.contains(&"a"));
+    assert!(peek_set.contains(&"c"));
+    assert!(!peek_set.contains(&"d"));
+    assert_eq!(peek_set.def().t(), <&str as facet_core::Facet<'_>>::SHAPE);
+}
+
+#[test]
+fn test_peek_set_empty() {
+    facet_testhelpers::setup();
+
+    let source: BTreeSet<u32> = BTreeSet::new();
+    let peek_set = Peek::new(&source).into_set().unwrap();
+    assert_eq!(peek_set.len(), 0);
+    assert!(peek_set.is_empty());
+    assert!(peek_set.iter().next().is_none());
+}
+
+#[test]
+fn test_peek_set_iteration() {
+    facet_testhelpers::setup();
+
+    let source: BTreeSet<u32> = [3, 1, 2].into_iter().collect();
+    let peek_set = Peek::new(&source).into_set().unwrap();
+    let values: Vec<u32> = peek_set
+        .iter()
+        .map(|value| *value.get::<u32>().unwrap())
+        .collect();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    let source: HashSet<u32> = [3, 1, 2].into_iter().collect();
+    let peek_set = Peek::new(&source).into_set().unwrap();
+    let mut values: Vec<u32> = (&peek_set)
+        .into_iter()
+        .map(|value| *value.get::<u32>().unwrap())
+        .collect();
+    values.sort();
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_peek_set_not_a_set() {
+    facet_testhelpers::setup();
+
+    assert!(Peek::new(&vec![1u8]).into_set().is_err());
+}
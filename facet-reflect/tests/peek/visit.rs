@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_core::{Field, Variant};
+use facet_reflect::{Peek, PeekEnum, PeekOption, PeekStruct, ValueVisitor, walk};
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    port: Option<u16>,
+    // Boxed, to walk through smart pointers
+    #[allow(clippy::vec_box)]
+    hosts: Vec<Box<String>>,
+    env: BTreeMap<String, u8>,
+    mode: Mode,
+    pair: (u8, bool),
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Off,
+    Fixed { level: u8 },
+}
+
+/// Writes down every hook call
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+impl<'mem, 'facet> ValueVisitor<'mem, 'facet> for Recorder {
+    fn visit_scalar(&mut self, value: Peek<'mem, 'facet>) {
+        self.events.push(format!("{value:?}"));
+    }
+
+    fn visit_none(&mut self, _option: PeekOption<'mem, 'facet>) {
+        self.events.push("None".to_string());
+    }
+
+    fn enter_struct(&mut self, _value: PeekStruct<'mem, 'facet>) {
+        self.events.push("{".to_string());
+    }
+
+    fn exit_struct(&mut self, _value: PeekStruct<'mem, 'facet>) {
+        self.events.push("}".to_string());
+    }
+
+    fn enter_enum(&mut self, _value: PeekEnum<'mem, 'facet>, variant: &'static Variant) {
+        self.events.push(format!("{} {{", variant.name));
+    }
+
+    fn exit_enum(&mut self, _value: PeekEnum<'mem, 'facet>, _variant: &'static Variant) {
+        self.events.push("}".to_string());
+    }
+
+    fn enter_field(&mut self, field: Field) {
+        self.events.push(format!("{}:", field.name));
+    }
+
+    fn enter_element(&mut self, index: usize) {
+        self.events.push(format!("[{index}]"));
+    }
+
+    fn enter_entry(&mut self, key: Peek<'mem, 'facet>) {
+        self.events.push(format!("{key:?} =>"));
+    }
+}
+
+#[test]
+fn walk_calls_hooks_in_order() {
+    facet_testhelpers::setup();
+
+    let config = Config {
+        name: "web".to_string(),
+        port: None,
+        hosts: vec![Box::new("a".to_string()), Box::new("b".to_string())],
+        env: [("HOME".to_string(), 1)].into_iter().collect(),
+        mode: Mode::Fixed { level: 3 },
+        pair: (7, true),
+    };
+    let mut recorder = Recorder::default();
+    walk(Peek::new(&config), &mut recorder);
+    assert_eq!(
+        recorder.events,
+        [
+            "{",
+            "name:",
+            "\"web\"",
+            "port:",
+            "None",
+            "hosts:",
+            "[0]",
+            "\"a\"",
+            "[1]",
+            "\"b\"",
+            "env:",
+            "\"HOME\" =>",
+            "1",
+            "mode:",
+            "Fixed {",
+            "level:",
+            "3",
+            "}",
+            "pair:",
+            "[0]",
+            "7",
+            "[1]",
+            "true",
+            "}",
+        ]
+    );
+}
+
+/// Sums every `u32` it comes across, with just one hook
+struct Sum(u32);
+
+impl<'mem, 'facet> ValueVisitor<'mem, 'facet> for Sum {
+    fn visit_scalar(&mut self, value: Peek<'mem, 'facet>) {
+        if let Ok(n) = value.get::<u32>() {
+            self.0 += n;
+        }
+    }
+}
+
+#[test]
+fn walk_with_a_single_hook() {
+    facet_testhelpers::setup();
+
+    let values: Vec<Option<Vec<u32>>> = vec![Some(vec![1, 2]), None, Some(vec![3])];
+    let mut sum = Sum(0);
+    walk(Peek::new(&values), &mut sum);
+    assert_eq!(sum.0, 6);
+}
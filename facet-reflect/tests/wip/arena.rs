@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_reflect::{Arena, Wip};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    name: String,
+    x: i32,
+    label: Option<String>,
+}
+
+#[derive(Facet)]
+struct Holder {
+    token: Arc<()>,
+    name: String,
+}
+
+#[test]
+fn build_in_arena() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let arena = Arena::with_capacity(64);
+    let values = (0..100)
+        .map(|i| {
+            Wip::alloc_in::<Point>(&arena)?
+                .field_named("name")?
+                .put(format!("p{i}"))?
+                .pop()?
+                .field_named("x")?
+                .put(i)?
+                .pop()?
+                .field_named("label")?
+                .push_some()?
+                .put(String::from("hi"))?
+                .pop()?
+                .pop()?
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(arena.capacity() >= 100 * core::mem::size_of::<Point>());
+
+    // The values keep the arena's memory around
+    drop(arena);
+    for (i, value) in values.into_iter().enumerate() {
+        assert_eq!(
+            value.materialize::<Point>()?,
+            Point {
+                name: format!("p{i}"),
+                x: i as i32,
+                label: Some(String::from("hi")),
+            }
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn drop_partial_wip_in_arena() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let arena = Arena::new();
+    let wip = Wip::alloc_in::<Holder>(&arena)?
+        .field_named("token")?
+        .put(token.clone())?
+        .pop()?;
+    drop(arena);
+    drop(wip);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn drop_heap_values_in_arena() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let arena = Arena::new();
+    let values = (0..10)
+        .map(|_| {
+            Wip::alloc_in::<Holder>(&arena)?
+                .field_named("token")?
+                .put(token.clone())?
+                .pop()?
+                .field_named("name")?
+                .put(String::from("held"))?
+                .pop()?
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(Arc::strong_count(&token), 11);
+    drop(values);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
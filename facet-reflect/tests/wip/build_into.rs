@@ -0,0 +1,95 @@
+use std::mem::MaybeUninit;
+
+use facet::Facet;
+use facet_core::PtrUninit;
+use facet_reflect::{ReflectError, Wip};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    host: String,
+    ports: Vec<u16>,
+}
+
+fn server_wip() -> Result<Wip<'static>, ReflectError> {
+    Wip::alloc::<Server>()?
+        .field_named("host")?
+        .put("localhost".to_string())?
+        .pop()?
+        .field_named("ports")?
+        .begin_pushback()?
+        .push()?
+        .put(8080u16)?
+        .pop()?
+        .pop()
+}
+
+#[test]
+fn build_into_maybe_uninit() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut slot = MaybeUninit::<Server>::uninit();
+    let server = server_wip()?.build_into(&mut slot)?;
+    server.ports.push(8081);
+    assert_eq!(server.host, "localhost");
+
+    let server = unsafe { slot.assume_init() };
+    assert_eq!(server.ports, vec![8080, 8081]);
+
+    Ok(())
+}
+
+#[test]
+fn build_in_place_by_shape() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut slot = MaybeUninit::<Server>::uninit();
+    let dst = PtrUninit::new(slot.as_mut_ptr());
+    unsafe { server_wip()?.build_in_place(dst)? };
+
+    let server = unsafe { slot.assume_init() };
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            ports: vec![8080],
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn build_boxed_untyped() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let server: Box<Server> = server_wip()?.build_boxed()?;
+    assert_eq!(server.ports, vec![8080]);
+
+    let Err(err) = server_wip()?.build_boxed::<u16>() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+
+    Ok(())
+}
+
+#[test]
+fn build_into_leaves_dst_alone_on_failure() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut slot = MaybeUninit::<Server>::uninit();
+    let Err(err) = Wip::alloc::<Server>()?
+        .field_named("host")?
+        .put("localhost".to_string())?
+        .pop()?
+        .build_into(&mut slot)
+    else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err, ReflectError::UninitializedField { .. }),
+        "{err}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use facet::Facet;
+use facet_reflect::{Peek, ReflectError, convert};
+use std::collections::HashMap;
+
+#[derive(Facet, Debug)]
+struct ConfigV1 {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    mode: ModeV1,
+    limits: HashMap<String, u32>,
+    legacy: bool,
+}
+
+#[derive(Facet, Debug)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum ModeV1 {
+    Fast,
+    Custom { level: u8 },
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct ConfigV2<'a> {
+    name: &'a str,
+    port: u32,
+    tags: Vec<String>,
+    mode: ModeV2,
+    limits: HashMap<String, f64>,
+    #[facet(default = 30)]
+    timeout: u64,
+    owner: Option<String>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum ModeV2 {
+    Fast,
+    Custom { level: i32, label: Option<String> },
+}
+
+#[test]
+fn convert_between_versions() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let v1 = ConfigV1 {
+        name: "api".to_string(),
+        port: 8080,
+        tags: vec!["prod".to_string()],
+        mode: ModeV1::Custom { level: 3 },
+        limits: HashMap::from([("requests".to_string(), 100)]),
+        legacy: true,
+    };
+
+    let v2 = convert(Peek::new(&v1), ConfigV2::SHAPE)?.materialize::<ConfigV2>()?;
+    assert_eq!(
+        v2,
+        ConfigV2 {
+            name: "api",
+            port: 8080,
+            tags: vec!["prod".to_string()],
+            mode: ModeV2::Custom {
+                level: 3,
+                label: None,
+            },
+            limits: HashMap::from([("requests".to_string(), 100.0)]),
+            timeout: 30,
+            owner: None,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn convert_into_option_and_owned_string() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Borrowed<'a> {
+        owner: &'a str,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Owned {
+        owner: Option<String>,
+    }
+
+    let borrowed = Borrowed { owner: "ops" };
+    let owned = convert(Peek::new(&borrowed), Owned::SHAPE)?.materialize::<Owned>()?;
+    assert_eq!(
+        owned,
+        Owned {
+            owner: Some("ops".to_string())
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn convert_out_of_range() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Narrow {
+        port: u8,
+    }
+
+    let v1 = ConfigV1 {
+        name: "api".to_string(),
+        port: 8080,
+        tags: vec![],
+        mode: ModeV1::Fast,
+        limits: HashMap::new(),
+        legacy: false,
+    };
+    let Err(err) = convert(Peek::new(&v1), Narrow::SHAPE) else {
+        panic!("expected 8080 not to fit in a u8");
+    };
+    assert!(matches!(err, ReflectError::TryFromError { .. }), "{err}");
+}
+
+#[test]
+fn convert_incompatible() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Ports {
+        port: Vec<u16>,
+    }
+
+    let v1 = ConfigV1 {
+        name: "api".to_string(),
+        port: 8080,
+        tags: vec![],
+        mode: ModeV1::Fast,
+        limits: HashMap::new(),
+        legacy: false,
+    };
+    let Err(err) = convert(Peek::new(&v1), Ports::SHAPE) else {
+        panic!("expected a u16 not to convert into a list");
+    };
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+}
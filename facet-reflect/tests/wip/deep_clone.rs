@@ -0,0 +1,127 @@
+use facet::Facet;
+use facet_reflect::{Peek, ReflectError, Wip};
+use std::collections::HashMap;
+
+/// None of these implement `Clone`, so they're cloned piece by piece
+#[derive(Facet, Debug, PartialEq)]
+struct Plugin {
+    name: String,
+    settings: HashMap<String, Setting>,
+    hooks: Vec<Hook>,
+    priority: Option<(u8, Level)>,
+    checksum: [u8; 2],
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Setting {
+    Flag(bool),
+    Text { value: String },
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Hook(String, u32);
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Level {
+    Low,
+    High,
+}
+
+#[test]
+fn clone_into_wip_without_clone_impls() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let plugin = Plugin {
+        name: "audit".to_string(),
+        settings: HashMap::from([
+            ("verbose".to_string(), Setting::Flag(true)),
+            (
+                "target".to_string(),
+                Setting::Text {
+                    value: "syslog".to_string(),
+                },
+            ),
+        ]),
+        hooks: vec![Hook("start".to_string(), 1), Hook("stop".to_string(), 2)],
+        priority: Some((3, Level::High)),
+        checksum: [0xab, 0xcd],
+    };
+
+    let clone = Peek::new(&plugin)
+        .clone_into_wip()?
+        .build()?
+        .materialize::<Plugin>()?;
+    assert_eq!(clone, plugin);
+
+    Ok(())
+}
+
+#[test]
+fn put_clone_into_field() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let hook = Hook("reload".to_string(), 7);
+    let plugin = Wip::alloc::<Plugin>()?
+        .field_named("name")?
+        .put("copy".to_string())?
+        .pop()?
+        .field_named("settings")?
+        .put_empty_map()?
+        .pop()?
+        .field_named("hooks")?
+        .begin_pushback()?
+        .push()?
+        .put_clone(Peek::new(&hook))?
+        .pop()?
+        .pop()?
+        .field_named("priority")?
+        .put_clone(Peek::new(&None::<(u8, Level)>))?
+        .pop()?
+        .field_named("checksum")?
+        .put_clone(Peek::new(&[1u8, 2]))?
+        .pop()?
+        .build()?
+        .materialize::<Plugin>()?;
+    assert_eq!(plugin.hooks, vec![hook]);
+    assert_eq!(plugin.priority, None);
+    assert_eq!(plugin.checksum, [1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn put_clone_wrong_shape() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let Err(err) = Wip::alloc::<String>()?.put_clone(Peek::new(&42u32)) else {
+        panic!("expected a shape mismatch");
+    };
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+
+    Ok(())
+}
+
+#[test]
+fn clone_into_wip_uncloneable() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Handle {
+        inner: Box<Hook>,
+    }
+
+    let handle = Handle {
+        inner: Box::new(Hook("socket".to_string(), 3)),
+    };
+    let Err(err) = Peek::new(&handle).clone_into_wip() else {
+        panic!("expected the box to be reported as uncloneable");
+    };
+    assert!(
+        matches!(err, ReflectError::MissingCharacteristic { .. }),
+        "{err}"
+    );
+}
@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+
+use facet::Facet;
+use facet_reflect::Wip;
+
+// Deep enough that dropping recursively would overflow a test thread's stack (Miri is
+// too slow for that, but still checks the pointer juggling)
+const DEPTH: usize = if cfg!(miri) { 100 } else { 100_000 };
+
+#[derive(Facet)]
+struct Node {
+    token: Arc<()>,
+    next: Option<Box<Node>>,
+}
+
+#[derive(Facet)]
+struct Holder {
+    name: String,
+    head: Option<Box<Node>>,
+}
+
+#[derive(Facet)]
+struct Tree {
+    token: Arc<()>,
+    children: Vec<Tree>,
+}
+
+fn chain(token: &Arc<()>) -> Option<Box<Node>> {
+    let mut head = None;
+    for _ in 0..DEPTH {
+        head = Some(Box::new(Node {
+            token: token.clone(),
+            next: head,
+        }));
+    }
+    head
+}
+
+fn tree(token: &Arc<()>) -> Tree {
+    let mut tree = Tree {
+        token: token.clone(),
+        children: Vec::new(),
+    };
+    for _ in 0..DEPTH {
+        tree = Tree {
+            token: token.clone(),
+            children: vec![tree],
+        };
+    }
+    tree
+}
+
+#[test]
+fn drop_deep_heap_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let heap_value = Wip::alloc::<Option<Box<Node>>>()?
+        .put(chain(&token))?
+        .build()?;
+    drop(heap_value);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn drop_deep_partial_wip() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let wip = Wip::alloc::<Holder>()?
+        .field_named("head")?
+        .put(chain(&token))?
+        .pop()?;
+    drop(wip);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+#[test]
+fn drop_deep_vec_tree() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let token = Arc::new(());
+    let heap_value = Wip::alloc::<Tree>()?.put(tree(&token))?.build()?;
+    drop(heap_value);
+    assert_eq!(Arc::strong_count(&token), 1);
+
+    Ok(())
+}
+
+/// What each `Counted` found when it was dropped: how many items, and whether it had a next one
+static SEEN: Mutex<Vec<(usize, bool)>> = Mutex::new(Vec::new());
+
+#[derive(Facet)]
+struct Counted {
+    items: Vec<u32>,
+    next: Option<Box<Counted>>,
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        let mut seen = SEEN.lock().unwrap();
+        seen.push((self.items.len(), self.next.is_some()));
+    }
+}
+
+#[test]
+fn drop_impls_see_their_fields() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let counted = Counted {
+        items: vec![1, 2],
+        next: Some(Box::new(Counted {
+            items: vec![3],
+            next: None,
+        })),
+    };
+    let heap_value = Wip::alloc::<Option<Counted>>()?
+        .put(Some(counted))?
+        .build()?;
+    drop(heap_value);
+    assert_eq!(*SEEN.lock().unwrap(), [(2, true), (1, false)]);
+
+    Ok(())
+}
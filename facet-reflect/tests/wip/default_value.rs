@@ -0,0 +1,143 @@
+use facet::Facet;
+use facet_reflect::{ReflectError, Wip, default_value};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Document {
+    title: String,
+    #[facet(default = 8080)]
+    port: u16,
+    tags: Vec<String>,
+    owner: Option<String>,
+    layout: Layout,
+    margins: (u8, f32),
+    grid: [Cell; 2],
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Layout {
+    Columns { count: u8 },
+    Rows(u8),
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Cell {
+    width: u32,
+}
+
+#[test]
+fn default_value_without_default_impl() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let document = default_value(Document::SHAPE)?.materialize::<Document>()?;
+    assert_eq!(
+        document,
+        Document {
+            title: String::new(),
+            port: 8080,
+            tags: vec![],
+            owner: None,
+            layout: Layout::Columns { count: 0 },
+            margins: (0, 0.0),
+            grid: [Cell { width: 0 }, Cell { width: 0 }],
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn default_value_prefers_default_impl() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Limits {
+        max: u32,
+    }
+
+    impl Default for Limits {
+        fn default() -> Self {
+            Self { max: 100 }
+        }
+    }
+
+    let limits = default_value(Limits::SHAPE)?.materialize::<Limits>()?;
+    assert_eq!(limits, Limits { max: 100 });
+
+    Ok(())
+}
+
+#[test]
+fn default_value_missing_default() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Id {
+        value: core::num::NonZeroU32,
+    }
+
+    let err = default_value(Id::SHAPE).unwrap_err();
+    assert!(matches!(err, ReflectError::MissingCharacteristic { .. }));
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    host: String,
+    #[facet(default = 8080)]
+    port: u16,
+    #[facet(default)]
+    tags: Vec<String>,
+    owner: Option<String>,
+}
+
+#[test]
+fn fill_defaults_fills_missing_fields() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let server = Wip::alloc::<Server>()?
+        .field_named("host")?
+        .put(String::from("localhost"))?
+        .pop()?
+        .field_named("tags")?
+        .begin_pushback()?
+        .push()?
+        .put(String::from("web"))?
+        .pop()?
+        .pop()?
+        .fill_defaults()?
+        .build()?
+        .materialize::<Server>()?;
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080,
+            tags: vec!["web".to_string()],
+            owner: None,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn fill_defaults_leaves_required_fields() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let Err(err) = Wip::alloc::<Server>()?.fill_defaults()?.build() else {
+        panic!("expected `host` to be reported as uninitialized");
+    };
+    assert!(
+        matches!(
+            err,
+            ReflectError::UninitializedField {
+                field_name: "host",
+                ..
+            }
+        ),
+        "{err}"
+    );
+
+    Ok(())
+}
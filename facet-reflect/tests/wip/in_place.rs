@@ -0,0 +1,416 @@
+use facet::Facet;
+use facet_reflect::{ReflectError, Wip};
+
+#[derive(Facet, Debug, PartialEq, Default)]
+struct Config {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    backend: Backend,
+}
+
+#[derive(Facet, Debug, PartialEq, Default)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Backend {
+    #[default]
+    Memory,
+    File {
+        path: String,
+    },
+    Remote(String, u16),
+}
+
+fn config() -> Config {
+    Config {
+        name: "app".to_string(),
+        port: 8080,
+        tags: vec!["a".to_string()],
+        backend: Backend::File {
+            path: "/tmp/app".to_string(),
+        },
+    }
+}
+
+#[test]
+fn overwrite_fields_in_place() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    Wip::from_mut(&mut config)
+        .field_named("name")?
+        .put("renamed".to_string())?
+        .pop()?
+        .field_named("port")?
+        .put(9090u16)?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(
+        config,
+        Config {
+            name: "renamed".to_string(),
+            port: 9090,
+            ..self::config()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reset_fields_in_place() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    Wip::from_mut(&mut config)
+        .field_named("tags")?
+        .put_default()?
+        .pop()?
+        .field_named("backend")?
+        .put_default()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(
+        config,
+        Config {
+            tags: vec![],
+            backend: Backend::Memory,
+            ..self::config()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn push_into_existing_list() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    Wip::from_mut(&mut config)
+        .field_named("tags")?
+        .begin_pushback()?
+        .push()?
+        .put("b".to_string())?
+        .pop()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(config.tags, vec!["a".to_string(), "b".to_string()]);
+
+    Ok(())
+}
+
+#[test]
+fn switch_variant_in_place() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    Wip::from_mut(&mut config)
+        .field_named("backend")?
+        .variant_named("Remote")?
+        .field(0)?
+        .put("example.com".to_string())?
+        .pop()?
+        .field(1)?
+        .put(443u16)?
+        .pop()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(
+        config.backend,
+        Backend::Remote("example.com".to_string(), 443)
+    );
+    assert_eq!(config.name, "app");
+
+    Ok(())
+}
+
+#[test]
+fn finish_half_edited_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    let res = Wip::from_mut(&mut config)
+        .field_named("backend")?
+        .variant_named("Remote")?
+        .field(0)?
+        .put("example.com".to_string())?
+        .pop()?
+        .pop()?
+        .finish();
+    let Err(err) = res else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err, ReflectError::UninitializedField { .. }),
+        "{err}"
+    );
+
+    // what was left half-edited has been reset
+    assert_eq!(config, Config::default());
+
+    Ok(())
+}
+
+#[test]
+fn drop_unchanged_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    drop(Wip::from_mut(&mut config).field_named("tags")?.pop()?);
+    assert_eq!(config, self::config());
+
+    Ok(())
+}
+
+#[test]
+fn build_borrowed_value() {
+    facet_testhelpers::setup();
+
+    let mut port = 8080u16;
+    let Err(err) = Wip::from_mut(&mut port).build() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+    assert_eq!(port, 8080);
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Job {
+    id: u32,
+    backend: Backend,
+}
+
+#[test]
+fn switch_variant_without_default() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut job = Job {
+        id: 7,
+        backend: Backend::Remote("example.com".to_string(), 80),
+    };
+    let Err(err) = Wip::from_mut(&mut job)
+        .field_named("backend")?
+        .variant_named("Memory")
+    else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+
+    // nothing was de-initialized, so the value is left as it was
+    assert_eq!(job.backend, Backend::Remote("example.com".to_string(), 80));
+
+    Ok(())
+}
+
+#[test]
+fn finish_with_frames_left() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut config = config();
+    let Err(err) = Wip::from_mut(&mut config).field_named("port")?.finish() else {
+        panic!("expected an error")
+    };
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+    assert_eq!(config, self::config());
+
+    Ok(())
+}
+
+thread_local! {
+    /// How many `Tracked` values were dropped on this thread
+    static DROPS: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+fn drops() -> usize {
+    DROPS.with(|drops| drops.get())
+}
+
+#[derive(Facet, Debug, PartialEq, Clone, Default)]
+struct Tracked {
+    name: String,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+impl core::str::FromStr for Tracked {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("empty name");
+        }
+        Ok(Tracked {
+            name: s.to_string(),
+        })
+    }
+}
+
+fn tracked(name: &str) -> Tracked {
+    Tracked {
+        name: name.to_string(),
+    }
+}
+
+#[derive(Facet, Debug, PartialEq, Default)]
+struct Holder {
+    one: Tracked,
+    list: Vec<Tracked>,
+    map: std::collections::HashMap<String, Tracked>,
+    at: jiff::Timestamp,
+}
+
+fn holder() -> Holder {
+    Holder {
+        one: tracked("one"),
+        list: vec![tracked("a"), tracked("b")],
+        map: [("k".to_string(), tracked("v"))].into_iter().collect(),
+        at: jiff::Timestamp::UNIX_EPOCH,
+    }
+}
+
+#[test]
+fn put_empty_list_in_place_drops_old_items() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    let before = drops();
+    Wip::from_mut(&mut holder)
+        .field_named("list")?
+        .put_empty_list()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(drops() - before, 2);
+    assert!(holder.list.is_empty());
+    assert_eq!(holder.one, tracked("one"));
+
+    Ok(())
+}
+
+#[test]
+fn put_empty_map_in_place_drops_old_entries() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    let before = drops();
+    Wip::from_mut(&mut holder)
+        .field_named("map")?
+        .put_empty_map()?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(drops() - before, 1);
+    assert!(holder.map.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn put_clone_in_place_drops_old_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    let source = tracked("cloned");
+    let before = drops();
+    Wip::from_mut(&mut holder)
+        .field_named("one")?
+        .put_clone(facet_reflect::Peek::new(&source))?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(drops() - before, 1);
+    assert_eq!(holder.one, source);
+
+    Ok(())
+}
+
+#[test]
+fn parse_in_place_drops_old_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    let before = drops();
+    Wip::from_mut(&mut holder)
+        .field_named("one")?
+        .parse("hello")?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(drops() - before, 1);
+    assert_eq!(holder.one, tracked("hello"));
+
+    Ok(())
+}
+
+#[test]
+fn failed_parse_in_place_resets_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    let before = drops();
+    let res = Wip::from_mut(&mut holder)
+        .field_named("one")?
+        .parse("")
+        .err();
+    assert!(matches!(res, Some(ReflectError::OperationFailed { .. })));
+
+    // the old value was dropped, and what was left half-edited has been reset
+    assert_eq!(drops() - before, 4);
+    assert_eq!(holder, Holder::default());
+
+    Ok(())
+}
+
+#[test]
+fn failed_parse_without_default_keeps_value() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Named {
+        id: u32,
+        one: Tracked,
+    }
+
+    let mut named = Named {
+        id: 1,
+        one: tracked("one"),
+    };
+    let before = drops();
+    let res = Wip::from_mut(&mut named)
+        .field_named("one")?
+        .parse("")
+        .err();
+    assert!(matches!(res, Some(ReflectError::OperationFailed { .. })));
+
+    // nothing was de-initialized, since it couldn't have been reset
+    assert_eq!(drops(), before);
+    assert_eq!(named.one, tracked("one"));
+
+    Ok(())
+}
+
+#[test]
+fn put_unix_nanos_in_place() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let mut holder = holder();
+    Wip::from_mut(&mut holder)
+        .field_named("at")?
+        .put_unix_nanos(1_000_000_000)?
+        .pop()?
+        .finish()?;
+
+    assert_eq!(holder.at, jiff::Timestamp::from_second(1)?);
+    assert_eq!(holder.one, tracked("one"));
+
+    Ok(())
+}
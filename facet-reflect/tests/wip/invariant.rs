@@ -1,5 +1,5 @@
 use facet::Facet;
-use facet_reflect::Wip;
+use facet_reflect::{ReflectError, Wip};
 
 #[test]
 fn build_with_invariants() -> eyre::Result<()> {
@@ -28,7 +28,77 @@ fn build_with_invariants() -> eyre::Result<()> {
         .put(0_u8)?
         .pop()?
         .build();
-    assert!(result.is_err());
+    assert!(
+        matches!(result, Err(ReflectError::InvariantViolated { .. })),
+        "{result:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn build_with_invariant_message() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, PartialEq, Debug)]
+    #[facet(invariants = "Span::ordered")]
+    struct Span {
+        start: u32,
+        end: u32,
+    }
+
+    impl Span {
+        fn ordered(&self) -> Result<(), String> {
+            if self.start <= self.end {
+                Ok(())
+            } else {
+                Err(format!(
+                    "start ({}) is after end ({})",
+                    self.start, self.end
+                ))
+            }
+        }
+    }
+
+    #[derive(Facet, PartialEq, Debug)]
+    struct Selection {
+        spans: Vec<Span>,
+    }
+
+    let build = |start: u32, end: u32| -> Result<Selection, ReflectError> {
+        Wip::alloc::<Selection>()?
+            .field_named("spans")?
+            .begin_pushback()?
+            .push()?
+            .field_named("start")?
+            .put(start)?
+            .pop()?
+            .field_named("end")?
+            .put(end)?
+            .pop()?
+            .pop()?
+            .pop()?
+            .build()?
+            .materialize::<Selection>()
+    };
+
+    assert_eq!(
+        build(1, 2)?,
+        Selection {
+            spans: vec![Span { start: 1, end: 2 }]
+        }
+    );
+
+    let Err(err) = build(2, 1) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err,
+            ReflectError::InvariantViolated { message, .. } if message == "start (2) is after end (1)"
+        ),
+        "{err}"
+    );
 
     Ok(())
 }
@@ -0,0 +1,54 @@
+use facet::Facet;
+use facet_reflect::Wip;
+
+#[derive(Facet, PartialEq, Debug)]
+#[facet(lenses)]
+struct Config {
+    name: String,
+    limits: Limits,
+}
+
+#[derive(Facet, PartialEq, Debug)]
+#[facet(lenses)]
+struct Limits {
+    connections: u32,
+    timeout: f64,
+}
+
+#[test]
+fn wip_through_lenses() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let limits = Config::fields().limits();
+    let connections = limits.then(Limits::fields().connections());
+    let timeout = limits.then(Limits::fields().timeout());
+
+    let config = Wip::alloc::<Config>()?
+        .lens(&Config::fields().name())?
+        .put(String::from("edge"))?
+        .pop()?
+        .lens(&connections)?
+        .put(64u32)?
+        .pop()?
+        .pop()?
+        .lens(&timeout)?
+        .put(2.5f64)?
+        .pop()?
+        .pop()?
+        .build()?
+        .materialize::<Config>()?;
+
+    assert_eq!(
+        config,
+        Config {
+            name: "edge".into(),
+            limits: Limits {
+                connections: 64,
+                timeout: 2.5,
+            },
+        }
+    );
+    assert_eq!(connections.len(), 2);
+
+    Ok(())
+}
@@ -137,6 +137,50 @@ fn wip_unit_enum() -> eyre::Result<()> {
     Ok(())
 }
 
+#[derive(Facet, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Opcode {
+    Nop = 0,
+    #[facet(rename = "load")]
+    Load = 4,
+    Store = 7,
+}
+
+#[test]
+fn wip_enum_variant_by_discriminant() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let wip = Wip::alloc::<Opcode>()?;
+    assert_eq!(wip.variant_count(), Some(3));
+    let names: Vec<_> = wip.variants().unwrap().iter().map(|v| v.name).collect();
+    assert_eq!(names, ["Nop", "load", "Store"]);
+    assert_eq!(wip.find_variant_by_discriminant(7).map(|(i, _)| i), Some(2));
+    assert!(wip.find_variant_by_discriminant(1).is_none());
+    assert_eq!(wip.find_variant("load").map(|(i, _)| i), Some(1));
+    assert!(wip.find_variant("Load").is_none());
+
+    let load = wip
+        .variant_by_discriminant(4)?
+        .build()?
+        .materialize::<Opcode>()?;
+    assert_eq!(load, Opcode::Load);
+
+    let Err(err) = Wip::alloc::<Opcode>()?.variant_by_discriminant(5) else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, ReflectError::NoSuchVariant { .. }));
+
+    let wip = Wip::alloc::<u32>()?;
+    assert_eq!(wip.variant_count(), None);
+    assert!(matches!(
+        wip.variant_by_discriminant(0),
+        Err(ReflectError::WasNotA { .. })
+    ));
+
+    Ok(())
+}
+
 #[derive(Facet, PartialEq, Eq, Debug)]
 #[repr(u8)]
 enum EnumWithData {
@@ -997,3 +1041,59 @@ fn wip_build_option_none_through_default() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn wip_put_128_bit_integers() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    // Wide values narrow down when they fit...
+    let hv = Wip::alloc::<u16>()?.put(300u128)?.build()?;
+    assert_eq!(hv.materialize::<u16>()?, 300);
+    let hv = Wip::alloc::<i8>()?.put(-5i128)?.build()?;
+    assert_eq!(hv.materialize::<i8>()?, -5);
+
+    // ...and narrow values widen
+    let hv = Wip::alloc::<u128>()?.put(u64::MAX)?.build()?;
+    assert_eq!(hv.materialize::<u128>()?, u64::MAX as u128);
+    let hv = Wip::alloc::<i128>()?.put(i64::MIN)?.build()?;
+    assert_eq!(hv.materialize::<i128>()?, i64::MIN as i128);
+
+    assert!(Wip::alloc::<u64>()?.put(u128::MAX).is_err());
+    assert!(Wip::alloc::<u128>()?.put(-1i128).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn wip_put_non_zero() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let hv = Wip::alloc::<core::num::NonZeroI128>()?
+        .put(-7i64)?
+        .build()?;
+    assert_eq!(hv.materialize::<core::num::NonZeroI128>()?.get(), -7);
+    let hv = Wip::alloc::<core::num::NonZeroU128>()?
+        .parse("12")?
+        .build()?;
+    assert_eq!(hv.materialize::<core::num::NonZeroU128>()?.get(), 12);
+    let hv = Wip::alloc::<core::num::NonZeroI32>()?
+        .try_put_f64(-3.0)?
+        .build()?;
+    assert_eq!(hv.materialize::<core::num::NonZeroI32>()?.get(), -3);
+
+    // Zero never makes it in, whichever way it's put
+    assert!(Wip::alloc::<core::num::NonZeroU128>()?.put(0u128).is_err());
+    assert!(Wip::alloc::<core::num::NonZeroU128>()?.parse("0").is_err());
+    assert!(
+        Wip::alloc::<core::num::NonZeroU128>()?
+            .put_default()
+            .is_err()
+    );
+    assert!(
+        Wip::alloc::<core::num::NonZeroI32>()?
+            .try_put_f64(0.0)
+            .is_err()
+    );
+
+    Ok(())
+}
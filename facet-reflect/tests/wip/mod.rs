@@ -9,6 +9,8 @@ mod misc;
 
 mod map;
 
+mod set;
+
 mod list_leak;
 
 mod map_leak;
@@ -26,3 +28,25 @@ mod put_into_tuples;
 mod variance;
 
 mod array_building;
+
+mod default_value;
+
+mod lens;
+
+mod deep_drop;
+
+mod arena;
+
+mod deep_clone;
+
+mod convert;
+
+mod smart_pointer;
+
+mod in_place;
+
+mod typed;
+
+mod validate;
+
+mod build_into;
@@ -33,3 +33,16 @@ fn put_vec_leaktest3() -> eyre::Result<()> {
     assert_eq!(v, vec!["a".to_string()]);
     Ok(())
 }
+
+#[test]
+fn put_vec_leaktest_wrong_shape() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let value = Wip::alloc::<Vec<String>>()?
+        .put(vec!["a".to_string()])?
+        .build()?;
+    // The value can't go into a `u32`: it should be dropped rather than leaked
+    let res = Wip::alloc::<u32>()?.put_heap_value(value);
+    assert!(res.is_err());
+    Ok(())
+}
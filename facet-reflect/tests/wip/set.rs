@@ -0,0 +1,52 @@
+use std::collections::{BTreeSet, HashSet};
+
+use facet_reflect::Wip;
+
+#[test]
+fn wip_set_insert() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let set = Wip::alloc::<HashSet<String>>()?
+        .begin_pushback()?
+        .push()?
+        .put("a".to_string())?
+        .pop()?
+        .push()?
+        .put("b".to_string())?
+        .pop()?
+        .push()?
+        .put("a".to_string())?
+        .pop()?
+        .build()?
+        .materialize::<HashSet<String>>()?;
+    assert_eq!(set, HashSet::from(["a".to_string(), "b".to_string()]));
+
+    Ok(())
+}
+
+#[test]
+fn wip_set_empty() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let set = Wip::alloc::<BTreeSet<u32>>()?
+        .begin_pushback()?
+        .build()?
+        .materialize::<BTreeSet<u32>>()?;
+    assert!(set.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn wip_set_leaktest() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let _ = Wip::alloc::<HashSet<String>>()?
+        .begin_pushback()?
+        .push()?
+        .put("a".to_string())?
+        .pop()?
+        .push()?
+        .put("b".to_string())?;
+    Ok(())
+}
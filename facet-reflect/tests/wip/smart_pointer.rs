@@ -0,0 +1,91 @@
+use facet::Facet;
+use facet_reflect::{ReflectError, Wip};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Shapes {
+    origin: Box<Point>,
+    shared: Option<Rc<Vec<Point>>>,
+}
+
+#[test]
+fn build_pointee_field_by_field() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let point = Wip::alloc::<Arc<Point>>()?
+        .push_pointee()?
+        .field_named("x")?
+        .put(1i32)?
+        .pop()?
+        .field_named("y")?
+        .put(2i32)?
+        .pop()?
+        .pop()?
+        .build()?
+        .materialize::<Arc<Point>>()?;
+    assert_eq!(*point, Point { x: 1, y: 2 });
+
+    Ok(())
+}
+
+#[test]
+fn build_nested_pointees() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let shapes = Wip::alloc::<Shapes>()?
+        .field_named("origin")?
+        .push_pointee()?
+        .field_named("x")?
+        .put(0i32)?
+        .pop()?
+        .field_named("y")?
+        .put(-1i32)?
+        .pop()?
+        .pop()?
+        .pop()?
+        .field_named("shared")?
+        .push_some()?
+        .push_pointee()?
+        .begin_pushback()?
+        .push()?
+        .field_named("x")?
+        .put(3i32)?
+        .pop()?
+        .field_named("y")?
+        .put(4i32)?
+        .pop()?
+        .pop()?
+        .pop()?
+        .pop()?
+        .pop()?
+        .build()?
+        .materialize::<Shapes>()?;
+    assert_eq!(
+        shapes,
+        Shapes {
+            origin: Box::new(Point { x: 0, y: -1 }),
+            shared: Some(Rc::new(vec![Point { x: 3, y: 4 }])),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn push_pointee_needs_a_smart_pointer() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let Err(err) = Wip::alloc::<Point>()?.push_pointee() else {
+        panic!("expected an error");
+    };
+    assert!(matches!(err, ReflectError::WasNotA { .. }), "{err}");
+
+    Ok(())
+}
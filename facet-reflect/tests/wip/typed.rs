@@ -0,0 +1,79 @@
+use facet::Facet;
+use facet_reflect::{Arena, ReflectError, TypedWip, Wip};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    host: String,
+    ports: Vec<u16>,
+}
+
+#[test]
+fn build_typed() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let wip = Wip::alloc_typed::<Server>()?.field_named("host")?;
+    assert_eq!(wip.shape(), String::SHAPE);
+    let server = wip
+        .put("localhost".to_string())?
+        .pop()?
+        .field_named("ports")?
+        .begin_pushback()?
+        .push()?
+        .put(80u16)?
+        .pop()?
+        .pop()?
+        .build()?;
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            ports: vec![80],
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn build_typed_boxed() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let server: Box<Server> = TypedWip::<Server>::alloc()?
+        .field_named("host")?
+        .put("localhost".to_string())?
+        .pop()?
+        .field_named("ports")?
+        .put_default()?
+        .pop()?
+        .build_boxed()?;
+    assert_eq!(server.host, "localhost");
+    assert!(server.ports.is_empty());
+
+    let arena = Arena::new();
+    let port: Box<u16> = TypedWip::<u16>::alloc_in(&arena)?
+        .put(8080u16)?
+        .build_boxed()?;
+    assert_eq!(*port, 8080);
+
+    Ok(())
+}
+
+#[test]
+fn build_typed_uninitialized() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    let Err(err) = Wip::alloc_typed::<Server>()?
+        .field_named("host")?
+        .put("localhost".to_string())?
+        .pop()?
+        .build()
+    else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(err, ReflectError::UninitializedField { .. }),
+        "{err}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use facet::{Facet, Validators};
+use facet_reflect::{ReflectError, Wip};
+
+fn non_empty(s: &str) -> Result<(), &'static str> {
+    if s.is_empty() {
+        Err("must not be empty")
+    } else {
+        Ok(())
+    }
+}
+
+fn valid_port(port: &u16) -> Result<(), String> {
+    if *port == 0 {
+        Err(format!("{port} is not a valid port"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(validate = Range::check)]
+struct Range {
+    #[facet(validate = valid_port)]
+    start: u16,
+    #[facet(validate = "valid_port")]
+    end: u16,
+}
+
+impl Range {
+    fn check(&self) -> Result<(), String> {
+        if self.start > self.end {
+            Err(format!("{} is after {}", self.start, self.end))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn build_range(start: u16, end: u16) -> Result<Range, ReflectError> {
+    Wip::alloc::<Range>()?
+        .field_named("start")?
+        .put(start)?
+        .pop()?
+        .field_named("end")?
+        .put(end)?
+        .pop()?
+        .build()?
+        .materialize()
+}
+
+#[test]
+fn validate_fields_and_container() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        build_range(80, 443)?,
+        Range {
+            start: 80,
+            end: 443
+        }
+    );
+
+    let Err(err) = build_range(0, 443) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err,
+            ReflectError::ValidationFailed {
+                field_name: Some("start"),
+                message,
+                ..
+            } if message == "0 is not a valid port"
+        ),
+        "{err}"
+    );
+
+    let Err(err) = build_range(443, 80) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err,
+            ReflectError::ValidationFailed {
+                field_name: None,
+                message,
+                ..
+            } if message == "443 is after 80"
+        ),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn validate_nested_values() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug)]
+    struct Tag {
+        #[facet(validate = non_empty)]
+        name: String,
+    }
+
+    #[derive(Facet, Debug)]
+    struct Post {
+        tags: Vec<Tag>,
+        main_tag: Option<Box<Tag>>,
+    }
+
+    let tag = |name: &str| -> Result<Tag, ReflectError> {
+        Wip::alloc::<Tag>()?
+            .field_named("name")?
+            .put(name.to_string())?
+            .pop()?
+            .build()?
+            .materialize()
+    };
+    let post = |tags: Vec<Tag>, main_tag: Option<Box<Tag>>| -> Result<Post, ReflectError> {
+        Wip::alloc::<Post>()?
+            .field_named("tags")?
+            .put(tags)?
+            .pop()?
+            .field_named("main_tag")?
+            .put(main_tag)?
+            .pop()?
+            .build()?
+            .materialize()
+    };
+
+    post(vec![tag("rust")?], Some(Box::new(tag("facet")?)))?;
+
+    let in_list = post(
+        vec![Tag {
+            name: String::new(),
+        }],
+        None,
+    );
+    assert!(
+        matches!(in_list, Err(ReflectError::ValidationFailed { .. })),
+        "{in_list:?}"
+    );
+
+    let in_option = post(
+        vec![],
+        Some(Box::new(Tag {
+            name: String::new(),
+        })),
+    );
+    assert!(
+        matches!(in_option, Err(ReflectError::ValidationFailed { .. })),
+        "{in_option:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn validate_enum() -> eyre::Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    #[facet(validate = Figure::check)]
+    enum Figure {
+        Circle { radius: f64 },
+        Square(#[facet(validate = positive)] f64),
+    }
+
+    fn positive(x: &f64) -> Result<(), &'static str> {
+        if *x > 0.0 {
+            Ok(())
+        } else {
+            Err("must be positive")
+        }
+    }
+
+    impl Figure {
+        fn check(&self) -> Result<(), &'static str> {
+            match self {
+                Figure::Circle { radius } if *radius <= 0.0 => Err("radius must be positive"),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    let circle = |radius: f64| -> Result<Figure, ReflectError> {
+        Wip::alloc::<Figure>()?
+            .variant_named("Circle")?
+            .field_named("radius")?
+            .put(radius)?
+            .pop()?
+            .build()?
+            .materialize()
+    };
+    let square = |side: f64| -> Result<Figure, ReflectError> {
+        Wip::alloc::<Figure>()?
+            .variant_named("Square")?
+            .field(0)?
+            .put(side)?
+            .pop()?
+            .build()?
+            .materialize()
+    };
+
+    assert_eq!(circle(1.0)?, Figure::Circle { radius: 1.0 });
+    assert_eq!(square(2.0)?, Figure::Square(2.0));
+
+    let Err(err) = circle(-1.0) else {
+        panic!("expected an error");
+    };
+    assert!(err.to_string().contains("radius must be positive"), "{err}");
+
+    let Err(err) = square(0.0) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(
+            &err,
+            ReflectError::ValidationFailed {
+                field_name: Some("0"),
+                ..
+            }
+        ),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn shapes_know_whether_they_validate() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Plain {
+        name: String,
+        port: Option<u16>,
+    }
+
+    #[derive(Facet)]
+    struct Listed {
+        ranges: Vec<Range>,
+    }
+
+    assert_eq!(Plain::SHAPE.validators, Validators::Absent);
+    assert_eq!(<(Plain, [u8; 4])>::SHAPE.validators, Validators::Absent);
+    assert_eq!(Range::SHAPE.validators, Validators::Present);
+    assert_eq!(<Option<Range>>::SHAPE.validators, Validators::Present);
+    // Only known once the item shape is looked at
+    assert_eq!(Listed::SHAPE.validators, Validators::Unknown);
+}
@@ -1,4 +1,4 @@
-use crate::Serializer;
+use crate::{Serializer, Unsupported};
 
 use alloc::vec::Vec;
 
@@ -10,11 +10,15 @@ struct DebugSerializer<W> {
 #[derive(Debug)]
 enum DebugError {
     Fmt(core::fmt::Error),
+    Unsupported(Unsupported),
 }
 
 impl core::fmt::Display for DebugError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        core::fmt::Debug::fmt(self, f)
+        match self {
+            DebugError::Fmt(err) => core::fmt::Debug::fmt(err, f),
+            DebugError::Unsupported(unsupported) => core::fmt::Display::fmt(unsupported, f),
+        }
     }
 }
 
@@ -26,6 +30,12 @@ impl From<core::fmt::Error> for DebugError {
     }
 }
 
+impl From<Unsupported> for DebugError {
+    fn from(unsupported: Unsupported) -> Self {
+        DebugError::Unsupported(unsupported)
+    }
+}
+
 impl<W> Serializer for DebugSerializer<W>
 where
     W: core::fmt::Write,
@@ -7,28 +7,138 @@
 
 extern crate alloc;
 
-use alloc::string::String;
+use alloc::format;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::time::Duration;
 
 use facet_core::{
-    Def, Facet, Field, PointerType, SequenceType, ShapeAttribute, StructKind, Type, UserType,
+    Def, EnumRepr, EnumTagging, Facet, Field, FieldAttribute, FieldFlags, PointerType,
+    ScalarAffinity, SequenceType, SerializeWith, Shape, ShapeAttribute, SmartPointerFlags,
+    StructKind, Type, UserType, Variant,
+};
+use facet_reflect::{
+    HasFields, HeapValue, Peek, PeekEnum, PeekListLikeIter, PeekMapIter, PeekSetIter, PeekStruct,
+    PeekTuple, ReflectError, ScalarType, ValueId,
 };
-use facet_reflect::{HasFields, Peek, PeekListLike, PeekMap, PeekStruct, PeekTuple, ScalarType};
 use log::{debug, trace};
 
 mod debug_serializer;
 
+// Serializing a proxy hands out peeks into it for as long as it's on the stack
+#[allow(unsafe_code)]
+mod proxy;
+
 fn variant_is_newtype_like(variant: &facet_core::Variant) -> bool {
     variant.data.kind == facet_core::StructKind::Tuple && variant.data.fields.len() == 1
 }
 
+/// What gets serialized for fields marked `#[facet(sensitive)]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SensitiveFields {
+    /// Their values, like for any other field
+    #[default]
+    Include,
+    /// Nothing: named fields are left out, positional ones are redacted
+    Skip,
+    /// The string `[REDACTED]` instead of their values
+    Redact,
+}
+
+/// How a `Duration` is serialized, picked by the serializer or, for a field, with
+/// `#[facet(duration_format = "parts" | "seconds" | "human")]`
+///
+/// Deserializing a `Duration` accepts all of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DurationFormat {
+    /// An object with its whole seconds and the nanoseconds past them, `{"secs":90,"nanos":0}`
+    #[default]
+    Parts,
+    /// A number of seconds, with a fractional part, `90.5`
+    Seconds,
+    /// A string of amounts of units, `"1m30s500ms"`
+    Human,
+}
+
+impl DurationFormat {
+    /// The format picked by a `#[facet(duration_format = "...")]` attribute on a field
+    ///
+    /// # Panics
+    ///
+    /// If the attribute names an unknown format.
+    fn of_field(field: &Field) -> Option<Self> {
+        let name = field.attributes.iter().find_map(|attribute| {
+            let &FieldAttribute::Arbitrary(attribute) = attribute else {
+                return None;
+            };
+            let value = attribute.strip_prefix("duration_format")?.trim_start();
+            let value = value.strip_prefix('=')?.trim();
+            value.strip_prefix('"')?.strip_suffix('"')
+        })?;
+        Some(match name {
+            "parts" => DurationFormat::Parts,
+            "seconds" => DurationFormat::Seconds,
+            "human" => DurationFormat::Human,
+            _ => panic!("unknown duration_format {name:?} on field {}", field.name),
+        })
+    }
+}
+
+/// Why a value can't be serialized, as told to [`Serializer::serialize_unsupported`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Unsupported {
+    /// Its shape is not one that can be serialized
+    Shape {
+        /// The shape of the value
+        shape: &'static Shape,
+        /// Why it can't be serialized
+        reason: &'static str,
+    },
+    /// Its vtable lacks what's needed to serialize it, e.g. a way to borrow a pointee
+    MissingCapability {
+        /// The shape of the value
+        shape: &'static Shape,
+        /// What it can't do, e.g. "borrowing its pointee"
+        capability: &'static str,
+    },
+    /// Its `#[facet(serialize_with = ...)]` or `#[facet(into = ...)]` function failed, with
+    /// a [`ReflectError::ConversionFailed`]
+    ConversionFailed(ReflectError),
+}
+
+impl core::fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Unsupported::Shape { shape, reason } => write!(f, "Can't serialize {shape}: {reason}"),
+            Unsupported::MissingCapability { shape, capability } => {
+                write!(
+                    f,
+                    "Can't serialize {shape}: its vtable has no way of {capability}"
+                )
+            }
+            Unsupported::ConversionFailed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for Unsupported {}
+
+#[cfg(feature = "std")]
+impl From<Unsupported> for std::io::Error {
+    fn from(unsupported: Unsupported) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, unsupported.to_string())
+    }
+}
+
 // --- Serializer Trait Definition ---
 
 /// A trait for implementing format-specific serialization logic.
 /// The core iterative serializer uses this trait to output data.
 pub trait Serializer {
-    /// The error type returned by serialization methods
-    type Error;
+    /// The error type returned by serialization methods, which values that can't be
+    /// serialized are reported as
+    type Error: From<Unsupported>;
 
     /// Serialize an unsigned 64-bit integer.
     fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error>;
@@ -163,6 +273,58 @@ pub trait Serializer {
         self.serialize_f64(value as f64)
     }
 
+    /// Serialize a scalar that is written as its `Display` output, e.g. an IP address.
+    ///
+    /// Formats it into a `String` for [`Serializer::serialize_str`] by default; serializers
+    /// that can escape text as it comes should stream it into their output instead.
+    #[inline(always)]
+    fn serialize_display(&mut self, value: &dyn core::fmt::Display) -> Result<(), Self::Error> {
+        self.serialize_str(&value.to_string())
+    }
+
+    /// Serialize a timestamp or datetime, e.g. a `jiff::Timestamp`. `unix_nanos` is the number
+    /// of nanoseconds since the unix epoch, for values that stand for an instant.
+    ///
+    /// Written as its `Display` output, RFC 3339 for instants, by default.
+    #[inline(always)]
+    fn serialize_time(
+        &mut self,
+        value: &dyn core::fmt::Display,
+        _unix_nanos: Option<i128>,
+    ) -> Result<(), Self::Error> {
+        self.serialize_display(value)
+    }
+
+    /// How `Duration`s are serialized, except in fields with a `duration_format` attribute.
+    /// [`DurationFormat::Parts`] by default.
+    #[inline(always)]
+    fn duration_format(&self) -> DurationFormat {
+        DurationFormat::default()
+    }
+
+    /// Serialize a `Duration` in the given format, see [`DurationFormat`].
+    fn serialize_duration(
+        &mut self,
+        value: Duration,
+        format: DurationFormat,
+    ) -> Result<(), Self::Error> {
+        match format {
+            DurationFormat::Parts => {
+                self.start_object(Some(2))?;
+                self.serialize_field_name("secs")?;
+                self.serialize_u64(value.as_secs())?;
+                self.end_field()?;
+                self.serialize_field_name("nanos")?;
+                self.serialize_u32(value.subsec_nanos())?;
+                self.end_field()?;
+                self.end_object()
+            }
+            DurationFormat::Seconds => self.serialize_f64(value.as_secs_f64()),
+            // Its `Display` output is the human format
+            DurationFormat::Human => self.serialize_display(&Peek::new(&value)),
+        }
+    }
+
     /// Begin serializing a map key value.
     #[inline(always)]
     fn begin_map_key(&mut self) -> Result<(), Self::Error> {
@@ -210,14 +372,78 @@ pub trait Serializer {
     fn end_field(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    /// What to serialize for fields marked `#[facet(sensitive)]`: their values, by default.
+    #[inline(always)]
+    fn sensitive_fields(&self) -> SensitiveFields {
+        SensitiveFields::Include
+    }
+
+    /// Whether values of `shape` are written as they are, with [`Serializer::serialize_raw`].
+    /// None are, by default.
+    #[inline(always)]
+    fn is_raw(&self, _shape: &'static Shape) -> bool {
+        false
+    }
+
+    /// Serialize a value that's already in the output format, e.g. a fragment of JSON, by
+    /// writing its `Display` output verbatim. Only called for shapes [`Serializer::is_raw`]
+    /// accepts; writes it as a string by default.
+    #[inline(always)]
+    fn serialize_raw(&mut self, value: &dyn core::fmt::Display) -> Result<(), Self::Error> {
+        self.serialize_display(value)
+    }
+
+    /// Whether lists, arrays and slices of `u8` go through [`Serializer::serialize_bytes`], rather
+    /// than being serialized as arrays of numbers. Off by default.
+    #[inline(always)]
+    fn byte_lists_as_bytes(&self) -> bool {
+        false
+    }
+
+    /// Whether struct fields are written sorted by name, and the entries of maps and items of
+    /// sets sorted by key and value, rather than in the order they're declared or iterated in.
+    /// Off by default.
+    ///
+    /// Hash maps and sets iterate in an order that changes from one run to the next, so this is
+    /// what makes equal values serialize the same way every time.
+    #[inline(always)]
+    fn sort_keys(&self) -> bool {
+        false
+    }
+
+    /// Whether to keep track of the values being serialized, to catch a value that contains
+    /// itself through references or smart pointers. Off by default, in which case a cycle
+    /// is serialized forever.
+    #[inline(always)]
+    fn detect_cycles(&self) -> bool {
+        false
+    }
+
+    /// Serialize what stands for a value found inside itself, when cycles are detected.
+    ///
+    /// Writes a `<cycle: Type>` string by default; return an error to fail instead.
+    fn serialize_cycle(&mut self, shape: &'static Shape) -> Result<(), Self::Error> {
+        self.serialize_str(&format!("<cycle: {shape}>"))
+    }
+
+    /// Called in place of serializing a value that can't be, e.g. a scalar of unknown type.
+    ///
+    /// Fails with `reason` by default; override it to write something in its place instead.
+    fn serialize_unsupported(&mut self, reason: Unsupported) -> Result<(), Self::Error> {
+        Err(reason.into())
+    }
 }
 
 // --- Iterative Serialization Logic ---
 
 /// Task items for the serialization stack.
-#[derive(Debug)]
+///
+/// Lists and maps are walked with their iterator kept on the stack, one item at a time, so
+/// the stack only grows with the nesting and not with the number of items.
 enum SerializeTask<'mem, 'facet> {
-    Value(Peek<'mem, 'facet>, Option<Field>),
+    /// A value, and whether it is a flattened enum field
+    Value(Peek<'mem, 'facet>, bool),
     // End markers
     EndObject,
     EndArray,
@@ -225,12 +451,26 @@ enum SerializeTask<'mem, 'facet> {
     EndMapKey,
     EndMapValue,
     EndField,
+    /// The placeholder for the value of a sensitive field
+    Redacted,
+    /// Done with the last value entered, when detecting cycles
+    LeaveValue,
+    /// A `Duration` field, in the format of its `duration_format` attribute
+    Duration(Peek<'mem, 'facet>, DurationFormat),
+    /// A field or value to convert with its `serialize_with` or `into` function, and serialize
+    /// the proxy of
+    SerializeWith(Peek<'mem, 'facet>, SerializeWith),
+    /// Done with a proxy: it's dropped along with the task
+    DropProxy(HeapValue<'facet>),
     // Tasks to push sub-elements onto the stack
     ObjectFields(PeekStruct<'mem, 'facet>),
-    ArrayItems(PeekListLike<'mem, 'facet>),
+    ArrayItems(PeekListLikeIter<'mem, 'facet>),
     TupleStructFields(PeekStruct<'mem, 'facet>),
     TupleFields(PeekTuple<'mem, 'facet>),
-    MapEntries(PeekMap<'mem, 'facet>),
+    MapEntries(PeekMapIter<'mem, 'facet>),
+    SortedMapEntries(alloc::vec::IntoIter<(Peek<'mem, 'facet>, Peek<'mem, 'facet>)>),
+    SetItems(PeekSetIter<'mem, 'facet>),
+    SortedSetItems(alloc::vec::IntoIter<Peek<'mem, 'facet>>),
     // Field-related tasks
     SerializeFieldName(&'static str),
     SerializeMapKey(Peek<'mem, 'facet>),
@@ -240,18 +480,49 @@ enum SerializeTask<'mem, 'facet> {
 /// Serializes a `Peek` value using the provided `Serializer`.
 ///
 /// This function uses an iterative approach with a stack to avoid recursion depth limits.
+/// Apart from that stack, which grows with the nesting of the value, nothing is allocated
+/// along the way, except for the fields of flattened structs, the proxies of values with
+/// `#[facet(serialize_with = ...)]` or `#[facet(into = ...)]`, and the fields, entries and items
+/// being sorted when [`Serializer::sort_keys`] is on.
 pub fn serialize_iterative<S>(peek: Peek<'_, '_>, serializer: &mut S) -> Result<(), S::Error>
 where
     S: Serializer,
 {
+    let sensitive = serializer.sensitive_fields();
+    let detect_cycles = serializer.detect_cycles();
+    let byte_lists_as_bytes = serializer.byte_lists_as_bytes();
+    let sort_keys = serializer.sort_keys();
+    // The values being serialized, from the outermost one in
+    let mut path: Vec<ValueId> = Vec::new();
     let mut stack = Vec::new();
-    stack.push(SerializeTask::Value(peek, None));
+    stack.push(SerializeTask::Value(peek, false));
 
     while let Some(task) = stack.pop() {
         match task {
-            SerializeTask::Value(mut cpeek, maybe_field) => {
+            SerializeTask::Value(mut cpeek, flattened) => {
                 debug!("Serializing a value, shape is {}", cpeek.shape(),);
 
+                if serializer.is_raw(cpeek.shape()) {
+                    serializer.serialize_raw(&cpeek)?;
+                    continue;
+                }
+
+                if let Some(into) = cpeek.shape().vtable.serialize_into {
+                    stack.push(SerializeTask::SerializeWith(cpeek, into));
+                    continue;
+                }
+
+                if detect_cycles {
+                    if path.contains(&cpeek.id()) {
+                        debug!("{} contains itself", cpeek.shape());
+                        serializer.serialize_cycle(cpeek.shape())?;
+                        continue;
+                    }
+                    // Under whatever gets pushed for the value's contents
+                    path.push(cpeek.id());
+                    stack.push(SerializeTask::LeaveValue);
+                }
+
                 if cpeek
                     .shape()
                     .attributes
@@ -261,8 +532,16 @@ where
                     let old_shape = cpeek.shape();
 
                     // then serialize the inner shape instead
-                    let ps = cpeek.into_struct().unwrap();
-                    cpeek = ps.field(0).unwrap();
+                    match cpeek.into_struct().ok().and_then(|ps| ps.field(0).ok()) {
+                        Some(inner) => cpeek = inner,
+                        None => {
+                            serializer.serialize_unsupported(Unsupported::Shape {
+                                shape: old_shape,
+                                reason: "transparent shapes must be structs with a field",
+                            })?;
+                            continue;
+                        }
+                    }
 
                     let new_shape = cpeek.shape();
                     debug!(
@@ -340,35 +619,99 @@ where
                             Some(ScalarType::ISize) => {
                                 serializer.serialize_isize(*cpeek.get::<isize>().unwrap())?
                             }
-                            Some(unsupported) => panic!("Unsupported scalar type: {unsupported:?}"),
-                            None => panic!("Unsupported shape: {}", cpeek.shape()),
+                            Some(ScalarType::Duration) => {
+                                let format = serializer.duration_format();
+                                serializer
+                                    .serialize_duration(*cpeek.get::<Duration>().unwrap(), format)?
+                            }
+                            // Timestamps and datetimes, like `chrono::DateTime`
+                            _ if is_time(cpeek.shape()) => {
+                                serializer.serialize_time(&cpeek, cpeek.unix_nanos())?
+                            }
+                            // Anything else that can display itself, like addresses or UUIDs
+                            _ if cpeek.shape().vtable.display.is_some() => {
+                                serializer.serialize_display(&cpeek)?
+                            }
+                            _ => serializer.serialize_unsupported(Unsupported::Shape {
+                                shape: cpeek.shape(),
+                                reason: "scalars of this type can't be serialized",
+                            })?,
                         }
                     }
                     (Def::List(_), _) | (Def::Array(_), _) | (Def::Slice(_), _) => {
                         let peek_list = cpeek.into_list_like().unwrap();
-                        let len = peek_list.len();
-                        serializer.start_array(Some(len))?;
-                        stack.push(SerializeTask::EndArray);
-                        stack.push(SerializeTask::ArrayItems(peek_list));
+                        if byte_lists_as_bytes && peek_list.def().t() == u8::SHAPE {
+                            let bytes: Vec<u8> = peek_list
+                                .iter()
+                                .map(|item| *item.get::<u8>().unwrap())
+                                .collect();
+                            serializer.serialize_bytes(&bytes)?;
+                        } else {
+                            let len = peek_list.len();
+                            serializer.start_array(Some(len))?;
+                            stack.push(SerializeTask::EndArray);
+                            stack.push(SerializeTask::ArrayItems(peek_list.iter()));
+                        }
                     }
                     (Def::Map(_), _) => {
                         let peek_map = cpeek.into_map().unwrap();
                         let len = peek_map.len();
                         serializer.start_map(Some(len))?;
                         stack.push(SerializeTask::EndMap);
-                        stack.push(SerializeTask::MapEntries(peek_map));
+                        if sort_keys {
+                            stack.push(SerializeTask::SortedMapEntries(peek_map.iter_sorted()));
+                        } else {
+                            stack.push(SerializeTask::MapEntries(peek_map.iter()));
+                        }
+                    }
+                    (Def::Set(_), _) => {
+                        // Sets have no counterpart in most formats, arrays come closest
+                        let peek_set = cpeek.into_set().unwrap();
+                        serializer.start_array(Some(peek_set.len()))?;
+                        stack.push(SerializeTask::EndArray);
+                        if sort_keys {
+                            stack.push(SerializeTask::SortedSetItems(peek_set.iter_sorted()));
+                        } else {
+                            stack.push(SerializeTask::SetItems(peek_set.iter()));
+                        }
                     }
                     (Def::Option(_), _) => {
                         let opt = cpeek.into_option().unwrap();
                         if let Some(inner_peek) = opt.value() {
-                            stack.push(SerializeTask::Value(inner_peek, None));
+                            stack.push(SerializeTask::Value(inner_peek, false));
                         } else {
                             serializer.serialize_none()?;
                         }
                     }
+                    (Def::Result(_), _) => {
+                        // Like an externally tagged enum: `{"Ok": value}` or `{"Err": error}`
+                        let (name, inner_peek) = match cpeek.into_result().unwrap().value() {
+                            Ok(value) => ("Ok", value),
+                            Err(error) => ("Err", error),
+                        };
+                        serializer.start_object(Some(1))?;
+                        stack.push(SerializeTask::EndObject);
+                        serializer.serialize_field_name(name)?;
+                        stack.push(SerializeTask::Value(inner_peek, false));
+                    }
                     (Def::SmartPointer(_), _) => {
-                        let _sp = cpeek.into_smart_pointer().unwrap();
-                        panic!("TODO: Implement serialization for smart pointers");
+                        // The pointee stands for the pointer, as if it were transparent
+                        let sp = cpeek.into_smart_pointer().unwrap();
+                        match sp.borrow_inner() {
+                            Some(inner_peek) => {
+                                stack.push(SerializeTask::Value(inner_peek, flattened))
+                            }
+                            // Weak pointers would need upgrading, and may dangle anyway
+                            None if sp.def().flags.contains(SmartPointerFlags::WEAK) => {
+                                serializer.serialize_none()?
+                            }
+                            None => serializer.serialize_unsupported(
+                                Unsupported::MissingCapability {
+                                    shape: cpeek.shape(),
+                                    capability: "borrowing its pointee",
+                                },
+                            )?,
+                        }
                     }
                     (_, Type::User(UserType::Struct(sd))) => {
                         debug!("Serializing struct: shape={}", cpeek.shape(),);
@@ -401,7 +744,10 @@ where
                             StructKind::Struct => {
                                 debug!("  Handling record struct");
                                 let peek_struct = cpeek.into_struct().unwrap();
-                                let fields = peek_struct.fields_for_serialize().count();
+                                let fields = count_named_fields(
+                                    peek_struct.fields_for_serialize(),
+                                    sensitive,
+                                );
                                 debug!("  Serializing {} fields as object", fields);
 
                                 serializer.start_object(Some(fields))?;
@@ -412,9 +758,10 @@ where
                                     fields
                                 );
                             }
-                            _ => {
-                                unreachable!()
-                            }
+                            _ => serializer.serialize_unsupported(Unsupported::Shape {
+                                shape: cpeek.shape(),
+                                reason: "structs of this kind can't be serialized",
+                            })?,
                         }
                     }
                     (_, Type::Sequence(SequenceType::Tuple(_))) => {
@@ -443,7 +790,7 @@ where
                                 let count = peek_list_like.len();
                                 serializer.start_array(Some(count))?;
                                 stack.push(SerializeTask::EndArray);
-                                stack.push(SerializeTask::ArrayItems(peek_list_like));
+                                stack.push(SerializeTask::ArrayItems(peek_list_like.iter()));
                                 trace!("  Pushed ArrayItems to stack for tuple serialization",);
                             } else {
                                 // Final fallback - create an empty array
@@ -458,62 +805,120 @@ where
                     }
                     (_, Type::User(UserType::Enum(_))) => {
                         let peek_enum = cpeek.into_enum().unwrap();
-                        let variant = peek_enum
-                            .active_variant()
-                            .expect("Failed to get active variant");
-                        let variant_index = peek_enum
-                            .variant_index()
-                            .expect("Failed to get variant index");
+                        let (Ok(variant), Ok(variant_index)) =
+                            (peek_enum.active_variant(), peek_enum.variant_index())
+                        else {
+                            serializer.serialize_unsupported(Unsupported::Shape {
+                                shape: cpeek.shape(),
+                                reason: "its active variant can't be told apart",
+                            })?;
+                            continue;
+                        };
                         trace!(
                             "Active variant index is {}, variant is {:?}",
                             variant_index, variant
                         );
-                        let flattened = maybe_field.map(|f| f.flattened).unwrap_or_default();
+                        let has_fields = !variant.data.fields.is_empty();
+                        if !has_fields && cpeek.shape().has_serialize_as_discriminant_attr() {
+                            serialize_discriminant(serializer, peek_enum)?;
+                            continue;
+                        }
+                        match cpeek.shape().enum_tagging() {
+                            EnumTagging::External if !has_fields => {
+                                serializer.serialize_unit_variant(variant_index, variant.name)?;
+                            }
+                            EnumTagging::External => {
+                                if !flattened {
+                                    serializer.start_object(Some(1))?;
+                                    stack.push(SerializeTask::EndObject);
 
-                        if variant.data.fields.is_empty() {
-                            // Unit variant
-                            serializer.serialize_unit_variant(variant_index, variant.name)?;
-                        } else {
-                            if !flattened {
-                                // For now, treat all enum variants with data as objects
-                                serializer.start_object(Some(1))?;
+                                    // Serialize variant name as field name
+                                    serializer.serialize_field_name(variant.name)?;
+                                }
+                                serialize_variant_fields(
+                                    &mut stack, serializer, peek_enum, variant, sensitive,
+                                    sort_keys,
+                                )?;
+                            }
+                            EnumTagging::Internal { tag } => {
+                                // The variant's fields go next to the tag, so they need names:
+                                // a newtype variant lends those of the struct it holds
+                                let newtype_inner = if variant_is_newtype_like(variant) {
+                                    match peek_enum
+                                        .fields_for_serialize()
+                                        .next()
+                                        .map(|(_, field_peek)| field_peek.into_struct())
+                                    {
+                                        Some(Ok(inner))
+                                            if inner.ty().kind == StructKind::Struct =>
+                                        {
+                                            Some(inner)
+                                        }
+                                        _ => {
+                                            serializer.serialize_unsupported(Unsupported::Shape {
+                                                shape: cpeek.shape(),
+                                                reason: "internally tagged newtype variants must hold a struct",
+                                            })?;
+                                            continue;
+                                        }
+                                    }
+                                } else if variant.data.kind == StructKind::Struct || !has_fields {
+                                    None
+                                } else {
+                                    serializer.serialize_unsupported(Unsupported::Shape {
+                                        shape: cpeek.shape(),
+                                        reason: "tuple variants can't be internally tagged",
+                                    })?;
+                                    continue;
+                                };
+                                let fields = match &newtype_inner {
+                                    Some(inner) => {
+                                        count_named_fields(inner.fields_for_serialize(), sensitive)
+                                    }
+                                    None => count_named_fields(
+                                        peek_enum.fields_for_serialize(),
+                                        sensitive,
+                                    ),
+                                };
+                                serializer.start_object(Some(fields + 1))?;
                                 stack.push(SerializeTask::EndObject);
-
-                                // Serialize variant name as field name
-                                serializer.serialize_field_name(variant.name)?;
-                            }
-
-                            if variant_is_newtype_like(variant) {
-                                // Newtype variant - serialize the inner value directly
-                                let fields = peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                let (field, field_peek) = fields[0];
-                                // TODO: error if `skip_serialize` is set?
-                                stack.push(SerializeTask::Value(field_peek, Some(field)));
-                            } else if variant.data.kind == StructKind::Tuple
-                                || variant.data.kind == StructKind::TupleStruct
-                            {
-                                // Tuple variant - serialize as array
-                                let fields = peek_enum.fields_for_serialize().count();
-                                serializer.start_array(Some(fields))?;
-                                stack.push(SerializeTask::EndArray);
-
-                                // Push fields in reverse order for tuple variant
-                                for (field, field_peek) in peek_enum.fields_for_serialize().rev() {
-                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
+                                serializer.serialize_field_name(tag)?;
+                                serializer.serialize_str(variant.name)?;
+                                serializer.end_field()?;
+
+                                match newtype_inner {
+                                    Some(inner) => stack.push(SerializeTask::ObjectFields(inner)),
+                                    None => push_object_fields(
+                                        &mut stack,
+                                        peek_enum.fields_for_serialize(),
+                                        sensitive,
+                                        sort_keys,
+                                    ),
                                 }
-                            } else {
-                                // Struct variant - serialize as object
-                                let fields = peek_enum.fields_for_serialize().count();
-                                serializer.start_object(Some(fields))?;
+                            }
+                            EnumTagging::Adjacent { tag, content } => {
+                                serializer.start_object(Some(if has_fields { 2 } else { 1 }))?;
                                 stack.push(SerializeTask::EndObject);
+                                serializer.serialize_field_name(tag)?;
+                                serializer.serialize_str(variant.name)?;
+                                serializer.end_field()?;
 
-                                // Push fields in reverse order for struct variant
-                                for (field, field_peek) in peek_enum.fields_for_serialize().rev() {
+                                if has_fields {
+                                    serializer.serialize_field_name(content)?;
                                     stack.push(SerializeTask::EndField);
-                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
-                                    stack.push(SerializeTask::SerializeFieldName(field.name));
+                                    serialize_variant_fields(
+                                        &mut stack, serializer, peek_enum, variant, sensitive,
+                                        sort_keys,
+                                    )?;
                                 }
                             }
+                            EnumTagging::Untagged if !has_fields => serializer.serialize_unit()?,
+                            EnumTagging::Untagged => {
+                                serialize_variant_fields(
+                                    &mut stack, serializer, peek_enum, variant, sensitive,
+                                    sort_keys,
+                                )?;
+                            }
                         }
                     }
                     (_, Type::Pointer(pointer_type)) => {
@@ -524,12 +929,14 @@ where
                         } else if let PointerType::Function(_) = pointer_type {
                             // Serialize function pointers as units
                             serializer.serialize_unit()?;
+                        } else if let Some(target) = cpeek.follow_reference() {
+                            stack.push(SerializeTask::Value(target, flattened));
                         } else {
                             // Handle other pointer types with innermost_peek which is safe
                             let innermost = cpeek.innermost_peek();
                             if innermost.shape() != cpeek.shape() {
                                 // We got a different inner value, serialize it
-                                stack.push(SerializeTask::Value(innermost, None));
+                                stack.push(SerializeTask::Value(innermost, false));
                             } else {
                                 // Couldn't access inner value safely, fall back to unit
                                 serializer.serialize_unit()?;
@@ -548,47 +955,72 @@ where
             }
 
             // --- Pushing sub-elements onto the stack ---
-            SerializeTask::ObjectFields(peek_struct) => {
-                // Push fields in reverse order for stack processing
-                for (field, field_peek) in peek_struct.fields_for_serialize().rev() {
-                    stack.push(SerializeTask::EndField);
-                    stack.push(SerializeTask::Value(field_peek, Some(field)));
-                    stack.push(SerializeTask::SerializeFieldName(field.name));
+            SerializeTask::SerializeWith(value_peek, with) => {
+                match value_peek.serialize_with(&with) {
+                    Ok(proxy) => proxy::push_proxy(&mut stack, proxy),
+                    Err(err) => {
+                        serializer.serialize_unsupported(Unsupported::ConversionFailed(err))?
+                    }
                 }
             }
+            SerializeTask::DropProxy(proxy) => drop(proxy),
+            SerializeTask::ObjectFields(peek_struct) => {
+                push_object_fields(
+                    &mut stack,
+                    peek_struct.fields_for_serialize(),
+                    sensitive,
+                    sort_keys,
+                );
+            }
             SerializeTask::TupleStructFields(peek_struct) => {
                 // Push fields in reverse order
                 for (field, field_peek) in peek_struct.fields_for_serialize().rev() {
-                    stack.push(SerializeTask::Value(field_peek, Some(field)));
+                    push_positional_field(&mut stack, field, field_peek, sensitive);
                 }
             }
             SerializeTask::TupleFields(peek_tuple) => {
                 // Push fields in reverse order
                 for (_, field_peek) in peek_tuple.fields().rev() {
-                    // Get the innermost peek value - this is essential for proper serialization
-                    // to unwrap transparent wrappers and get to the actual value
-                    let innermost_peek = field_peek.innermost_peek();
-
-                    // Push the innermost peek to the stack
-                    stack.push(SerializeTask::Value(innermost_peek, None));
+                    // Transparent wrappers are unwrapped when the value is serialized, like
+                    // for any other field: going through them here would trip over `None`
+                    stack.push(SerializeTask::Value(field_peek, false));
                 }
                 trace!("  Pushed {} tuple fields to stack", peek_tuple.len());
             }
-            SerializeTask::ArrayItems(peek_list) => {
-                // Push items in reverse order
-                let items: Vec<_> = peek_list.iter().collect();
-                for item_peek in items.into_iter().rev() {
-                    stack.push(SerializeTask::Value(item_peek, None));
+            SerializeTask::ArrayItems(mut items) => {
+                // Put the rest of the items back under the next one
+                if let Some(item_peek) = items.next() {
+                    stack.push(SerializeTask::ArrayItems(items));
+                    stack.push(SerializeTask::Value(item_peek, false));
+                }
+            }
+            SerializeTask::MapEntries(mut entries) => {
+                // Same for the entries, key first
+                if let Some((key_peek, value_peek)) = entries.next() {
+                    stack.push(SerializeTask::MapEntries(entries));
+                    stack.push(SerializeTask::SerializeMapValue(value_peek));
+                    stack.push(SerializeTask::SerializeMapKey(key_peek));
                 }
             }
-            SerializeTask::MapEntries(peek_map) => {
-                // Push entries in reverse order (key, value pairs)
-                let entries = peek_map.iter().collect::<Vec<_>>();
-                for (key_peek, value_peek) in entries.into_iter().rev() {
+            SerializeTask::SortedMapEntries(mut entries) => {
+                if let Some((key_peek, value_peek)) = entries.next() {
+                    stack.push(SerializeTask::SortedMapEntries(entries));
                     stack.push(SerializeTask::SerializeMapValue(value_peek));
                     stack.push(SerializeTask::SerializeMapKey(key_peek));
                 }
             }
+            SerializeTask::SetItems(mut items) => {
+                if let Some(item_peek) = items.next() {
+                    stack.push(SerializeTask::SetItems(items));
+                    stack.push(SerializeTask::Value(item_peek, false));
+                }
+            }
+            SerializeTask::SortedSetItems(mut items) => {
+                if let Some(item_peek) = items.next() {
+                    stack.push(SerializeTask::SortedSetItems(items));
+                    stack.push(SerializeTask::Value(item_peek, false));
+                }
+            }
 
             // --- Field name and map key/value handling ---
             SerializeTask::SerializeFieldName(name) => {
@@ -596,12 +1028,12 @@ where
             }
             SerializeTask::SerializeMapKey(key_peek) => {
                 stack.push(SerializeTask::EndMapKey);
-                stack.push(SerializeTask::Value(key_peek, None));
+                stack.push(SerializeTask::Value(key_peek, false));
                 serializer.begin_map_key()?;
             }
             SerializeTask::SerializeMapValue(value_peek) => {
                 stack.push(SerializeTask::EndMapValue);
-                stack.push(SerializeTask::Value(value_peek, None));
+                stack.push(SerializeTask::Value(value_peek, false));
                 serializer.begin_map_value()?;
             }
 
@@ -624,6 +1056,15 @@ where
             SerializeTask::EndField => {
                 serializer.end_field()?;
             }
+            SerializeTask::Redacted => {
+                serializer.serialize_str("[REDACTED]")?;
+            }
+            SerializeTask::Duration(cpeek, format) => {
+                serializer.serialize_duration(*cpeek.get::<Duration>().unwrap(), format)?;
+            }
+            SerializeTask::LeaveValue => {
+                path.pop();
+            }
         }
     }
 
@@ -631,6 +1072,147 @@ where
     Ok(())
 }
 
+/// Serializes the fields of an enum variant that has some, without its name: the value
+/// itself for newtype variants, an array for tuple variants and an object for struct variants
+fn serialize_variant_fields<'mem, 'facet, S: Serializer>(
+    stack: &mut Vec<SerializeTask<'mem, 'facet>>,
+    serializer: &mut S,
+    peek_enum: PeekEnum<'mem, 'facet>,
+    variant: &'static Variant,
+    sensitive: SensitiveFields,
+    sort_keys: bool,
+) -> Result<(), S::Error> {
+    if variant_is_newtype_like(variant) {
+        // Newtype variant - serialize the inner value directly
+        let Some((field, field_peek)) = peek_enum.fields_for_serialize().next() else {
+            return serializer.serialize_unsupported(Unsupported::Shape {
+                shape: peek_enum.shape(),
+                reason: "the field of its newtype variant is skipped",
+            });
+        };
+        push_positional_field(stack, field, field_peek, sensitive);
+    } else if variant.data.kind == StructKind::Tuple || variant.data.kind == StructKind::TupleStruct
+    {
+        // Tuple variant - serialize as array
+        let fields = peek_enum.fields_for_serialize().count();
+        serializer.start_array(Some(fields))?;
+        stack.push(SerializeTask::EndArray);
+
+        // Push fields in reverse order for tuple variant
+        for (field, field_peek) in peek_enum.fields_for_serialize().rev() {
+            push_positional_field(stack, field, field_peek, sensitive);
+        }
+    } else {
+        // Struct variant - serialize as object
+        let fields = count_named_fields(peek_enum.fields_for_serialize(), sensitive);
+        serializer.start_object(Some(fields))?;
+        stack.push(SerializeTask::EndObject);
+        push_object_fields(
+            stack,
+            peek_enum.fields_for_serialize(),
+            sensitive,
+            sort_keys,
+        );
+    }
+    Ok(())
+}
+
+/// Serializes the discriminant of a unit variant, for `#[facet(serialize_as = "discriminant")]`
+fn serialize_discriminant<S: Serializer>(
+    serializer: &mut S,
+    peek_enum: PeekEnum<'_, '_>,
+) -> Result<(), S::Error> {
+    let discriminant = peek_enum.discriminant();
+    match peek_enum.enum_repr() {
+        EnumRepr::U8 => serializer.serialize_u8(discriminant as u8),
+        EnumRepr::U16 => serializer.serialize_u16(discriminant as u16),
+        EnumRepr::U32 => serializer.serialize_u32(discriminant as u32),
+        EnumRepr::U64 => serializer.serialize_u64(discriminant as u64),
+        EnumRepr::USize => serializer.serialize_usize(discriminant as usize),
+        EnumRepr::I8 => serializer.serialize_i8(discriminant as i8),
+        EnumRepr::I16 => serializer.serialize_i16(discriminant as i16),
+        EnumRepr::I32 => serializer.serialize_i32(discriminant as i32),
+        EnumRepr::ISize => serializer.serialize_isize(discriminant as isize),
+        _ => serializer.serialize_i64(discriminant),
+    }
+}
+
+/// Pushes the fields of a struct or struct variant, each with its name, sorted by name if
+/// `sort_keys` is on
+fn push_object_fields<'mem, 'facet>(
+    stack: &mut Vec<SerializeTask<'mem, 'facet>>,
+    fields: impl DoubleEndedIterator<Item = (Field, Peek<'mem, 'facet>)>,
+    sensitive: SensitiveFields,
+    sort_keys: bool,
+) {
+    // Push fields in reverse order for stack processing
+    if sort_keys {
+        let mut fields: Vec<_> = fields.collect();
+        fields.sort_by_key(|(field, _)| field.name);
+        for (field, field_peek) in fields.into_iter().rev() {
+            push_named_field(stack, field, field_peek, sensitive);
+        }
+    } else {
+        for (field, field_peek) in fields.rev() {
+            push_named_field(stack, field, field_peek, sensitive);
+        }
+    }
+}
+
+/// Pushes a field along with its name, unless it's sensitive and those are skipped
+fn push_named_field<'mem, 'facet>(
+    stack: &mut Vec<SerializeTask<'mem, 'facet>>,
+    field: Field,
+    field_peek: Peek<'mem, 'facet>,
+    sensitive: SensitiveFields,
+) {
+    if field.flags.contains(FieldFlags::SENSITIVE) && sensitive == SensitiveFields::Skip {
+        return;
+    }
+    stack.push(SerializeTask::EndField);
+    push_positional_field(stack, field, field_peek, sensitive);
+    stack.push(SerializeTask::SerializeFieldName(field.name));
+}
+
+/// Pushes a field's value, or its proxy if it has one, or a placeholder if it's sensitive and
+/// those aren't included
+fn push_positional_field<'mem, 'facet>(
+    stack: &mut Vec<SerializeTask<'mem, 'facet>>,
+    field: Field,
+    field_peek: Peek<'mem, 'facet>,
+    sensitive: SensitiveFields,
+) {
+    if field.flags.contains(FieldFlags::SENSITIVE) && sensitive != SensitiveFields::Include {
+        stack.push(SerializeTask::Redacted);
+    } else if let Some(with) = field.vtable.serialize_with {
+        stack.push(SerializeTask::SerializeWith(field_peek, with));
+    } else if let (Some(format), Ok(_)) = (
+        DurationFormat::of_field(&field),
+        field_peek.get::<Duration>(),
+    ) {
+        stack.push(SerializeTask::Duration(field_peek, format));
+    } else {
+        stack.push(SerializeTask::Value(field_peek, field.flattened));
+    }
+}
+
+/// How many of these fields get serialized along with their names
+fn count_named_fields<'mem, 'facet>(
+    fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+    sensitive: SensitiveFields,
+) -> usize {
+    fields
+        .filter(|(field, _)| {
+            !(field.flags.contains(FieldFlags::SENSITIVE) && sensitive == SensitiveFields::Skip)
+        })
+        .count()
+}
+
+/// Whether values of this shape are timestamps or datetimes
+fn is_time(shape: &'static Shape) -> bool {
+    matches!(shape.def, Def::Scalar(sd) if matches!(sd.affinity, ScalarAffinity::Time(_)))
+}
+
 // --- Helper Trait for Ergonomics ---
 
 /// Extension trait to simplify calling the generic serializer.
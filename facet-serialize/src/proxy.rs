@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+
+use facet_core::PtrConst;
+use facet_reflect::{HeapValue, Peek};
+
+use crate::SerializeTask;
+
+/// Pushes the tasks serializing a proxy, on top of the one dropping it.
+pub(crate) fn push_proxy<'facet>(
+    stack: &mut Vec<SerializeTask<'_, 'facet>>,
+    proxy: HeapValue<'facet>,
+) {
+    let peek = proxy.peek();
+    // SAFETY: the proxy lives on the heap, so moving the `HeapValue` onto the stack doesn't move
+    // it. Tasks are popped in order, so the ones serializing the proxy are all done before the
+    // one dropping it is popped. If serializing fails, the stack is dropped as a whole, and the
+    // remaining peeks are never read again.
+    let peek =
+        unsafe { Peek::unchecked_new(PtrConst::new(peek.data().as_byte_ptr()), peek.shape()) };
+    stack.push(SerializeTask::DropProxy(proxy));
+    stack.push(SerializeTask::Value(peek, false));
+}
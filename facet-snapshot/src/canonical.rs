@@ -0,0 +1,421 @@
+use core::cmp::Ordering;
+use core::fmt::Write;
+
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, Unsupported, serialize_iterative};
+
+/// A value in canonical form: JSON, with map entries sorted by key and floats printed the
+/// shortest way that reads back to the same value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Null,
+    Bool(bool),
+    /// Kept as text, so that numbers of any width compare exactly
+    Number(String),
+    String(String),
+    List(Vec<Node>),
+    /// Struct fields stay in declaration order, map entries are sorted by key
+    Object(Vec<(String, Node)>),
+}
+
+impl Node {
+    pub(crate) fn from_peek(peek: Peek<'_, '_>) -> Result<Node, Unsupported> {
+        let mut builder = Builder::default();
+        serialize_iterative(peek, &mut builder)?;
+        Ok(builder.root.unwrap_or(Node::Null))
+    }
+
+    /// Parses canonical text back, for comparing against what's stored in a snapshot
+    pub(crate) fn parse(text: &str) -> Option<Node> {
+        let mut parser = Parser { text, pos: 0 };
+        let node = parser.value()?;
+        parser.skip_whitespace();
+        (parser.pos == text.len()).then_some(node)
+    }
+
+    /// Renders the node as JSON, indented by two spaces
+    pub(crate) fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out, Some(0));
+        out
+    }
+
+    /// Renders the node as JSON on a single line
+    pub(crate) fn compact(&self) -> String {
+        let mut out = String::new();
+        self.render(&mut out, None);
+        out
+    }
+
+    fn render(&self, out: &mut String, indent: Option<usize>) {
+        match self {
+            Node::Null => out.push_str("null"),
+            Node::Bool(b) => write!(out, "{b}").unwrap(),
+            Node::Number(n) => out.push_str(n),
+            Node::String(s) => write_string(out, s),
+            Node::List(items) if items.is_empty() => out.push_str("[]"),
+            Node::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Node::List(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    separate(out, i, indent);
+                    item.render(out, indent.map(|n| n + 1));
+                }
+                close(out, indent, ']');
+            }
+            Node::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    separate(out, i, indent);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.render(out, indent.map(|n| n + 1));
+                }
+                close(out, indent, '}');
+            }
+        }
+    }
+
+    /// The text a map key renders as, since JSON object keys are always strings
+    fn into_key(self) -> String {
+        match self {
+            Node::String(s) | Node::Number(s) => s,
+            other => other.compact(),
+        }
+    }
+}
+
+/// Writes what goes before the `i`th element of a list or object
+fn separate(out: &mut String, i: usize, indent: Option<usize>) {
+    if i > 0 {
+        out.push(',');
+    }
+    match indent {
+        Some(n) => {
+            out.push('\n');
+            out.push_str(&"  ".repeat(n + 1));
+        }
+        None if i > 0 => out.push(' '),
+        None => {}
+    }
+}
+
+fn close(out: &mut String, indent: Option<usize>, bracket: char) {
+    if let Some(n) = indent {
+        out.push('\n');
+        out.push_str(&"  ".repeat(n));
+    }
+    out.push(bracket);
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Orders map keys: numbers by value, so that `2` comes before `10`, anything else by its text
+fn key_order(a: &Node, b: &Node) -> Ordering {
+    match (a, b) {
+        (Node::Number(x), Node::Number(y)) => match (x.parse::<f64>(), y.parse::<f64>()) {
+            (Ok(m), Ok(n)) => m
+                .partial_cmp(&n)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| x.cmp(y)),
+            _ => x.cmp(y),
+        },
+        _ => a.clone().into_key().cmp(&b.clone().into_key()),
+    }
+}
+
+fn float(text: String, finite: bool) -> Node {
+    // JSON has no infinities or NaN, so those are spelled out as strings
+    match finite {
+        true => Node::Number(text),
+        false => Node::String(text),
+    }
+}
+
+/// Builds a [`Node`] out of the calls `serialize_iterative` makes
+#[derive(Default)]
+struct Builder {
+    stack: Vec<Frame>,
+    root: Option<Node>,
+}
+
+enum Frame {
+    List(Vec<Node>),
+    Object {
+        entries: Vec<(String, Node)>,
+        name: Option<String>,
+    },
+    /// Keys and values arrive alternately, so a pending key means a value comes next
+    Map {
+        entries: Vec<(Node, Node)>,
+        key: Option<Node>,
+    },
+}
+
+impl Builder {
+    fn emit(&mut self, node: Node) -> Result<(), Unsupported> {
+        match self.stack.last_mut() {
+            None => self.root = Some(node),
+            Some(Frame::List(items)) => items.push(node),
+            Some(Frame::Object { entries, name }) => {
+                entries.push((name.take().unwrap_or_default(), node))
+            }
+            Some(Frame::Map { entries, key }) => match key.take() {
+                Some(key) => entries.push((key, node)),
+                None => *key = Some(node),
+            },
+        }
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), Unsupported> {
+        let node = match self.stack.pop() {
+            Some(Frame::List(items)) => Node::List(items),
+            Some(Frame::Object { entries, .. }) => Node::Object(entries),
+            Some(Frame::Map { mut entries, .. }) => {
+                entries.sort_by(|(a, _), (b, _)| key_order(a, b));
+                Node::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key.into_key(), value))
+                        .collect(),
+                )
+            }
+            None => return Ok(()),
+        };
+        self.emit(node)
+    }
+}
+
+impl Serializer for Builder {
+    type Error = Unsupported;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.emit(Node::Number(value.to_string()))
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.emit(Node::Number(value.to_string()))
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.emit(Node::Number(value.to_string()))
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.emit(Node::Number(value.to_string()))
+    }
+
+    // `Debug` prints the shortest text that reads back to the same float — formatted at the
+    // float's own width, so an `f32` of 0.1 stays `0.1` rather than `0.10000000149011612`
+    fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.emit(float(format!("{value:?}"), value.is_finite()))
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.emit(float(format!("{value:?}"), value.is_finite()))
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.emit(Node::Bool(value))
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.emit(Node::String(value.to_string()))
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.emit(Node::String(value.to_string()))
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        let bytes = value.iter().map(|b| Node::Number(b.to_string()));
+        self.emit(Node::List(bytes.collect()))
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.emit(Node::Null)
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.emit(Node::Null)
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.emit(Node::String(variant_name.to_string()))
+    }
+
+    fn start_object(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(Frame::Object {
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            name: None,
+        });
+        Ok(())
+    }
+
+    fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        if let Some(Frame::Object { name: pending, .. }) = self.stack.last_mut() {
+            *pending = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    fn start_array(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        let items = Vec::with_capacity(len.unwrap_or_default());
+        self.stack.push(Frame::List(items));
+        Ok(())
+    }
+
+    fn start_map(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(Frame::Map {
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end()
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end()
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end()
+    }
+}
+
+/// Reads JSON — it only needs to understand what [`Node::pretty`] writes, plus whatever
+/// whitespace an editor might have touched
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        self.skip_whitespace();
+        let first = self.rest().chars().next()?;
+        match first {
+            'n' if self.eat("null") => Some(Node::Null),
+            't' if self.eat("true") => Some(Node::Bool(true)),
+            'f' if self.eat("false") => Some(Node::Bool(false)),
+            '"' => self.string().map(Node::String),
+            '-' | '0'..='9' => {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                Some(Node::Number(rest[..len].to_string()))
+            }
+            '[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.eat("]") {
+                    return Some(Node::List(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    if self.eat("]") {
+                        return Some(Node::List(items));
+                    }
+                    if !self.eat(",") {
+                        return None;
+                    }
+                }
+            }
+            '{' => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                if self.eat("}") {
+                    return Some(Node::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    if !self.eat(":") {
+                        return None;
+                    }
+                    entries.push((key, self.value()?));
+                    if self.eat("}") {
+                        return Some(Node::Object(entries));
+                    }
+                    if !self.eat(",") {
+                        return None;
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let mut chars = self.rest().strip_prefix('"')?.char_indices();
+        let mut out = String::new();
+        loop {
+            let (i, c) = chars.next()?;
+            match c {
+                '"' => {
+                    // One for each quote
+                    self.pos += i + 2;
+                    return Some(out);
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => out.push(c),
+                },
+                c => out.push(c),
+            }
+        }
+    }
+}
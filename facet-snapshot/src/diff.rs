@@ -0,0 +1,117 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::canonical::Node;
+
+/// One way a value differs from its stored snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Where the two differ, as a `facet-query` path like `.users[0].name`
+    pub path: String,
+    /// How they differ
+    pub kind: ChangeKind,
+}
+
+/// How a value differs from its stored snapshot, with each side in canonical form
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// Only the value has something at this path
+    Added {
+        /// What the value has there
+        new: String,
+    },
+    /// Only the snapshot has something at this path
+    Removed {
+        /// What the snapshot has there
+        old: String,
+    },
+    /// Both have something at this path, but not the same thing
+    Changed {
+        /// What the snapshot has there
+        old: String,
+        /// What the value has there
+        new: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ChangeKind::Added { new } => write!(f, "+ {}: {new}", self.path),
+            ChangeKind::Removed { old } => write!(f, "- {}: {old}", self.path),
+            ChangeKind::Changed { old, new } => write!(f, "~ {}: {old} → {new}", self.path),
+        }
+    }
+}
+
+/// Compares two values node by node, recording the outermost paths where they differ
+pub(crate) fn diff(old: &Node, new: &Node, path: &str, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Node::Object(old_entries), Node::Object(new_entries)) => {
+            let old_keys: HashMap<_, _> =
+                old_entries.iter().map(|(k, v)| (k.as_str(), v)).collect();
+            let new_keys: HashMap<_, _> =
+                new_entries.iter().map(|(k, v)| (k.as_str(), v)).collect();
+            for (key, old) in old_entries {
+                let path = child(path, key);
+                match new_keys.get(key.as_str()) {
+                    Some(new) => diff(old, new, &path, changes),
+                    None => changes.push(Change {
+                        path,
+                        kind: ChangeKind::Removed { old: old.compact() },
+                    }),
+                }
+            }
+            for (key, new) in new_entries {
+                if !old_keys.contains_key(key.as_str()) {
+                    changes.push(Change {
+                        path: child(path, key),
+                        kind: ChangeKind::Added { new: new.compact() },
+                    });
+                }
+            }
+        }
+        (Node::List(old_items), Node::List(new_items)) => {
+            for (i, (old, new)) in old_items.iter().zip(new_items).enumerate() {
+                diff(old, new, &index(path, i), changes);
+            }
+            let common = old_items.len().min(new_items.len());
+            for (i, old) in old_items.iter().enumerate().skip(common) {
+                changes.push(Change {
+                    path: index(path, i),
+                    kind: ChangeKind::Removed { old: old.compact() },
+                });
+            }
+            for (i, new) in new_items.iter().enumerate().skip(common) {
+                changes.push(Change {
+                    path: index(path, i),
+                    kind: ChangeKind::Added { new: new.compact() },
+                });
+            }
+        }
+        _ if old == new => {}
+        _ => changes.push(Change {
+            path: path.to_string(),
+            kind: ChangeKind::Changed {
+                old: old.compact(),
+                new: new.compact(),
+            },
+        }),
+    }
+}
+
+fn child(parent: &str, name: &str) -> String {
+    let parent = if parent == "." { "" } else { parent };
+    match !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        true => format!("{parent}.{name}"),
+        false => format!("{parent}.{name:?}"),
+    }
+}
+
+fn index(parent: &str, i: usize) -> String {
+    match parent {
+        "." => format!(".[{i}]"),
+        parent => format!("{parent}[{i}]"),
+    }
+}
@@ -0,0 +1,62 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod canonical;
+mod diff;
+pub use diff::*;
+
+mod snapshot;
+pub use snapshot::{compare, to_canonical_string};
+
+pub use facet_serialize::Unsupported;
+
+#[doc(hidden)]
+pub use snapshot::{Location, assert_snapshot as __assert_snapshot};
+
+/// Asserts that a value matches its stored snapshot, comparing canonical serializations.
+///
+/// Snapshots live in a `snapshots` directory next to the file of the assertion, named after
+/// the module and either the given name or the enclosing function. A missing snapshot is
+/// written and passes; a mismatch fails with a structural diff and writes the new value to a
+/// `.snap.new` file next to the old one. Setting `FACET_SNAPSHOT_UPDATE=always` rewrites
+/// snapshots instead, and `FACET_SNAPSHOT_UPDATE=no` — the default when `CI` is set — never
+/// writes anything.
+///
+/// ```rust,no_run
+/// # use facet::Facet;
+/// #[derive(Facet)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// facet_snapshot::assert_snapshot!(Point { x: 1, y: 2 });
+/// facet_snapshot::assert_snapshot!("origin", Point { x: 0, y: 0 });
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($value:expr $(,)?) => {
+        $crate::__assert_snapshot($crate::__location!(), None, &$value)
+    };
+    ($name:expr, $value:expr $(,)?) => {
+        $crate::__assert_snapshot($crate::__location!(), Some($name), &$value)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __location {
+    () => {
+        &$crate::Location {
+            manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            module_path: module_path!(),
+            file: file!(),
+            line: line!(),
+            function: {
+                fn f() {}
+                ::core::any::type_name_of_val(&f)
+            },
+        }
+    };
+}
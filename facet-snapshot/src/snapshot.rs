@@ -0,0 +1,212 @@
+use core::fmt::Write as _;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_serialize::Unsupported;
+
+use crate::canonical::Node;
+use crate::diff::{Change, ChangeKind, diff};
+
+/// The environment variable that decides when snapshot files get written
+const UPDATE_VAR: &str = "FACET_SNAPSHOT_UPDATE";
+
+/// Serializes `value` in canonical form: pretty-printed JSON, with map entries sorted by key
+/// and floats printed the shortest way that reads back to the same value
+pub fn to_canonical_string<'a, T: Facet<'a>>(value: &T) -> Result<String, Unsupported> {
+    Ok(Node::from_peek(Peek::new(value))?.pretty())
+}
+
+/// Compares `value` against a stored snapshot — either a whole `.snap` file or just its
+/// canonical text — and lists where they differ. Whitespace and formatting don't count, only
+/// structure and values do.
+pub fn compare<'a, T: Facet<'a>>(snapshot: &str, value: &T) -> Result<Vec<Change>, Unsupported> {
+    Ok(compare_node(snapshot, &Node::from_peek(Peek::new(value))?))
+}
+
+fn compare_node(snapshot: &str, new: &Node) -> Vec<Change> {
+    let snapshot = snapshot.replace("\r\n", "\n");
+    let mut changes = Vec::new();
+    match Node::parse(content(&snapshot)) {
+        Some(old) => diff(&old, new, ".", &mut changes),
+        // Not something this crate wrote, so all that can be said is that it differs
+        None => changes.push(Change {
+            path: ".".to_string(),
+            kind: ChangeKind::Changed {
+                old: content(&snapshot).trim().to_string(),
+                new: new.compact(),
+            },
+        }),
+    }
+    changes
+}
+
+/// The canonical text of a `.snap` file, without its header
+fn content(snapshot: &str) -> &str {
+    snapshot
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map_or(snapshot, |(_, content)| content)
+}
+
+/// Where an `assert_snapshot!` is written
+#[doc(hidden)]
+pub struct Location {
+    pub manifest_dir: &'static str,
+    pub module_path: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+    /// The path of a function nested in the one holding the assertion
+    pub function: &'static str,
+}
+
+enum Update {
+    /// Write every snapshot that's missing or doesn't match
+    Always,
+    /// Write missing snapshots, and a `.snap.new` next to those that don't match
+    Auto,
+    /// Never write anything
+    No,
+}
+
+impl Update {
+    fn from_env() -> Self {
+        match std::env::var(UPDATE_VAR).as_deref() {
+            Ok("always" | "1") => Update::Always,
+            Ok("no" | "0") => Update::No,
+            Ok("auto") => Update::Auto,
+            // Snapshots missing in CI were forgotten, not about to be reviewed
+            _ if std::env::var_os("CI").is_some() => Update::No,
+            _ => Update::Auto,
+        }
+    }
+}
+
+/// How many unnamed snapshots each function has asserted so far
+static COUNTERS: Mutex<BTreeMap<&'static str, usize>> = Mutex::new(BTreeMap::new());
+
+/// Names a snapshot after the function it's asserted in, numbering all but the first
+fn auto_name(location: &Location) -> String {
+    let mut function = location
+        .function
+        .strip_suffix("::f")
+        .unwrap_or(location.function);
+    while let Some(outer) = function.strip_suffix("::{{closure}}") {
+        function = outer;
+    }
+    let name = function.rsplit("::").next().unwrap_or(function);
+
+    let mut counters = COUNTERS.lock().unwrap_or_else(|e| e.into_inner());
+    let count = counters.entry(location.function).or_default();
+    *count += 1;
+    match *count {
+        1 => name.to_string(),
+        n => format!("{name}-{n}"),
+    }
+}
+
+#[doc(hidden)]
+pub fn assert_snapshot<'a, T: Facet<'a>>(location: &Location, name: Option<&str>, value: &T) {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => auto_name(location),
+    };
+    let file_name: String = format!("{}__{name}", location.module_path.replace("::", "__"))
+        .chars()
+        .map(
+            |c| match c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect();
+
+    // `file!()` is relative to wherever cargo ran rustc from, which may be the workspace root
+    // rather than the crate, so look for it upwards
+    let manifest_dir = Path::new(location.manifest_dir);
+    let source = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join(location.file))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| manifest_dir.join(location.file));
+    let dir = source.parent().unwrap_or(manifest_dir).join("snapshots");
+    let path = dir.join(format!("{file_name}.snap"));
+    let new_path = dir.join(format!("{file_name}.snap.new"));
+
+    let node = Node::from_peek(Peek::new(value))
+        .unwrap_or_else(|e| panic!("snapshot `{name}` can't be taken: {e}"));
+    let header_source = source.strip_prefix(manifest_dir).unwrap_or(&source);
+    let contents = format!(
+        "---\nsource: {}\ntype: {}\n---\n{}\n",
+        header_source.display(),
+        T::SHAPE,
+        node.pretty()
+    );
+    let at = format!("{}:{}", location.file, location.line);
+
+    let update = Update::from_env();
+    let stored = match fs::read_to_string(&path) {
+        Ok(stored) => stored,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match update {
+            Update::Always | Update::Auto => {
+                write(&path, &contents);
+                eprintln!("created snapshot `{name}` at {}", path.display());
+                return;
+            }
+            Update::No => panic!(
+                "snapshot `{name}` (at {at}) doesn't exist yet, and {UPDATE_VAR} or CI prevents \
+                 creating {}",
+                path.display()
+            ),
+        },
+        Err(e) => panic!("couldn't read snapshot {}: {e}", path.display()),
+    };
+
+    let changes = compare_node(&stored, &node);
+    if changes.is_empty() {
+        // A proposal left over from an earlier failure is stale now
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    let mut message = format!(
+        "snapshot `{name}` (at {at}) doesn't match {}:\n",
+        path.display()
+    );
+    for change in &changes {
+        writeln!(message, "  {change}").unwrap();
+    }
+    match update {
+        Update::Always => {
+            write(&path, &contents);
+            let _ = fs::remove_file(&new_path);
+            eprintln!("{message}updated it");
+            return;
+        }
+        Update::Auto => {
+            write(&new_path, &contents);
+            write!(
+                message,
+                "wrote the new value to {}, rerun with {UPDATE_VAR}=always to accept it",
+                new_path.display()
+            )
+            .unwrap();
+        }
+        Update::No => {}
+    }
+    panic!("{message}");
+}
+
+fn write(path: &Path, contents: &str) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents));
+    if let Err(e) = result {
+        panic!("couldn't write snapshot {}: {e}", path.display());
+    }
+}
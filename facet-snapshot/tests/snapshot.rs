@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_snapshot::{Change, ChangeKind, assert_snapshot, compare, to_canonical_string};
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    ratio: f32,
+    limits: HashMap<String, u32>,
+    mode: Mode,
+    fallback: Option<Mode>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Off,
+    Fixed(u32),
+    Range { low: u32, high: u32 },
+}
+
+fn config() -> Config {
+    Config {
+        name: "edge".into(),
+        ratio: 0.1,
+        limits: HashMap::from([("write".into(), 10), ("read".into(), 100)]),
+        mode: Mode::Range { low: 1, high: 5 },
+        fallback: None,
+    }
+}
+
+const CANONICAL: &str = r#"{
+  "name": "edge",
+  "ratio": 0.1,
+  "limits": {
+    "read": 100,
+    "write": 10
+  },
+  "mode": {
+    "Range": {
+      "low": 1,
+      "high": 5
+    }
+  },
+  "fallback": null
+}"#;
+
+#[test]
+fn canonical_form() {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_canonical_string(&config()).unwrap(), CANONICAL);
+
+    // However the map happens to iterate, its entries come out sorted
+    let mut config = config();
+    config.limits = (0..20).map(|i| (format!("key{i:02}"), i)).collect();
+    let canonical = to_canonical_string(&config).unwrap();
+    let keys: Vec<_> = canonical
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("\"key"))
+        .collect();
+    assert_eq!(keys.len(), 20);
+    assert!(keys.is_sorted());
+}
+
+#[test]
+fn changes_are_structural() {
+    facet_testhelpers::setup();
+
+    // Formatting isn't part of the snapshot, only what it says
+    assert!(
+        compare(&CANONICAL.replace('\n', ""), &config())
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut changed = config();
+    changed.limits.remove("write");
+    changed.limits.insert("delete".into(), 1);
+    changed.mode = Mode::Fixed(3);
+    changed.fallback = Some(Mode::Off);
+
+    let change = |path: &str, kind| Change {
+        path: path.to_string(),
+        kind,
+    };
+    assert_eq!(
+        compare(CANONICAL, &changed).unwrap(),
+        [
+            change(".limits.write", ChangeKind::Removed { old: "10".into() }),
+            change(".limits.delete", ChangeKind::Added { new: "1".into() }),
+            change(
+                ".mode.Range",
+                ChangeKind::Removed {
+                    old: r#"{"low": 1, "high": 5}"#.into()
+                }
+            ),
+            change(".mode.Fixed", ChangeKind::Added { new: "3".into() }),
+            change(
+                ".fallback",
+                ChangeKind::Changed {
+                    old: "null".into(),
+                    new: r#""Off""#.into()
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        compare(CANONICAL, &changed).unwrap()[4].to_string(),
+        r#"~ .fallback: null → "Off""#
+    );
+
+    // Something that isn't canonical JSON can only differ as a whole
+    let changes = compare("Config { name: \"edge\" }", &config()).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].path, ".");
+}
+
+#[test]
+fn stored_snapshots() {
+    facet_testhelpers::setup();
+
+    assert_snapshot!(config());
+    assert_snapshot!("modes", vec![Mode::Off, Mode::Fixed(3)]);
+}
@@ -0,0 +1,163 @@
+//! A shared suite of round-trip tests for format crates, one function per group of types
+//!
+//! [`assert_conformance`] runs all of them; formats that don't support some group yet can
+//! call the others one by one.
+
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+
+use crate::{Format, assert_roundtrip};
+
+#[derive(Facet)]
+struct Integers {
+    u8: u8,
+    u16: u16,
+    u32: u32,
+    u64: u64,
+    usize: usize,
+    i8: i8,
+    i16: i16,
+    i32: i32,
+    i64: i64,
+    isize: isize,
+}
+
+#[derive(Facet)]
+struct WideIntegers {
+    u128: u128,
+    i128: i128,
+}
+
+#[derive(Facet)]
+struct Floats {
+    f32: f32,
+    f64: f64,
+}
+
+#[derive(Facet)]
+struct Strings {
+    bool: bool,
+    string: String,
+    strings: Vec<String>,
+}
+
+#[derive(Facet)]
+struct Chars {
+    char: char,
+    chars: Vec<char>,
+}
+
+#[derive(Facet)]
+struct Options {
+    number: Option<u32>,
+    string: Option<String>,
+    list: Option<Vec<u8>>,
+}
+
+#[derive(Facet)]
+struct Collections {
+    list: Vec<u32>,
+    array: [u16; 3],
+    sorted: BTreeMap<String, i64>,
+    hashed: HashMap<String, Vec<bool>>,
+}
+
+#[derive(Facet)]
+struct Structs {
+    name: String,
+    point: Point,
+    points: Vec<Point>,
+    unit: Unit,
+    pair: Pair,
+}
+
+#[derive(Facet)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Facet)]
+struct Unit;
+
+#[derive(Facet)]
+struct Pair(u8, String);
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Figure {
+    Empty,
+    Circle(u32),
+    Line(Point, Point),
+    Rectangle {
+        width: u32,
+        height: u32,
+        label: Option<String>,
+    },
+}
+
+#[derive(Facet)]
+struct Enums {
+    figure: Figure,
+    figures: Vec<Figure>,
+}
+
+/// Runs every group of the suite
+pub fn assert_conformance<F: Format>() {
+    integers::<F>();
+    wide_integers::<F>();
+    floats::<F>();
+    strings::<F>();
+    chars::<F>();
+    options::<F>();
+    collections::<F>();
+    structs::<F>();
+    enums::<F>();
+}
+
+/// Every integer type up to 64 bits
+pub fn integers<F: Format>() {
+    assert_roundtrip::<Integers, F>();
+}
+
+/// `u128` and `i128`
+pub fn wide_integers<F: Format>() {
+    assert_roundtrip::<WideIntegers, F>();
+}
+
+/// Finite `f32`s and `f64`s, small and huge
+pub fn floats<F: Format>() {
+    assert_roundtrip::<Floats, F>();
+}
+
+/// Strings, with any code point in them, and booleans
+pub fn strings<F: Format>() {
+    assert_roundtrip::<Strings, F>();
+}
+
+/// `char`s
+pub fn chars<F: Format>() {
+    assert_roundtrip::<Chars, F>();
+}
+
+/// Options of scalars and of lists
+pub fn options<F: Format>() {
+    assert_roundtrip::<Options, F>();
+}
+
+/// Lists, arrays and maps with string keys
+pub fn collections<F: Format>() {
+    assert_roundtrip::<Collections, F>();
+}
+
+/// Nested structs, unit structs and tuple structs
+pub fn structs<F: Format>() {
+    assert_roundtrip::<Structs, F>();
+}
+
+/// Enums with unit, tuple and struct variants
+pub fn enums<F: Format>() {
+    assert_roundtrip::<Enums, F>();
+}
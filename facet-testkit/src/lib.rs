@@ -0,0 +1,369 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use facet_arbitrary::{GenerateError, Generator, Source, SplitMix64};
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_snapshot::Change;
+
+pub mod conformance;
+
+mod shrink;
+use shrink::{Tape, shrink};
+
+/// A serialization format to round-trip values through
+///
+/// Implement it on a marker type in your format crate's tests:
+///
+/// ```rust,ignore
+/// struct Json;
+///
+/// impl facet_testkit::Format for Json {
+///     const NAME: &'static str = "JSON";
+///
+///     fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+///         Ok(facet_json::peek_to_string(&peek).into_bytes())
+///     }
+///
+///     fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+///         facet_json::from_slice(input).map_err(|e| e.to_string())
+///     }
+/// }
+/// ```
+pub trait Format {
+    /// Name of the format, for failure messages
+    const NAME: &'static str;
+
+    /// Serializes a value
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String>;
+
+    /// Deserializes a value serialized by [`Format::serialize`]
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String>;
+}
+
+/// Customizes the [`Generator`] every case is generated with
+pub type GeneratorHook = Box<dyn Fn(Generator<&mut dyn Source>) -> Generator<&mut dyn Source>>;
+
+/// How many cases to run, and how to generate and shrink them
+pub struct Config {
+    cases: usize,
+    seed: u64,
+    shrink_budget: usize,
+    generator: Option<GeneratorHook>,
+}
+
+impl Config {
+    /// 256 cases, starting from the seed in `FACET_TESTKIT_SEED` (or 0), with the default
+    /// generator settings and up to 1024 attempts at shrinking a failing case
+    pub fn new() -> Self {
+        let seed = std::env::var("FACET_TESTKIT_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(0);
+        Self {
+            cases: 256,
+            seed,
+            shrink_budget: 1024,
+            generator: None,
+        }
+    }
+
+    /// Sets how many values to round-trip
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Sets the seed of the first case — each one after it uses the next seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets how many smaller values to try, at most, once a case fails
+    pub fn shrink_budget(mut self, attempts: usize) -> Self {
+        self.shrink_budget = attempts;
+        self
+    }
+
+    /// Tunes the generator, for instance to install a text hook or keep values shallow
+    pub fn generator(
+        mut self,
+        hook: impl Fn(Generator<&mut dyn Source>) -> Generator<&mut dyn Source> + 'static,
+    ) -> Self {
+        self.generator = Some(Box::new(hook));
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Config {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Config")
+            .field("cases", &self.cases)
+            .field("seed", &self.seed)
+            .field("shrink_budget", &self.shrink_budget)
+            .field("generator", &self.generator.is_some())
+            .finish()
+    }
+}
+
+/// Round-trips random values of `T` through `F` with the default [`Config`], and panics
+/// with the smallest failing value it finds
+pub fn assert_roundtrip<T, F>()
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    assert_roundtrip_with::<T, F>(&Config::new())
+}
+
+/// Same as [`assert_roundtrip`], with a custom [`Config`]
+pub fn assert_roundtrip_with<T, F>(config: &Config)
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    if let Err(failure) = check_roundtrip::<T, F>(config) {
+        panic!("{failure}");
+    }
+}
+
+/// Round-trips random values of `T` through `F`: each one is serialized, deserialized,
+/// and compared with the original by its canonical form (see [`facet_snapshot`]).
+///
+/// The first value that doesn't make it is shrunk, by generating it again from smaller
+/// and smaller random draws for as long as it keeps failing.
+pub fn check_roundtrip<T, F>(config: &Config) -> Result<(), Failure>
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    for case in 0..config.cases {
+        let seed = config.seed.wrapping_add(case as u64);
+        let mut tape = Tape::new(&[], Some(SplitMix64::new(seed)));
+        let failure = run_case::<T, F>(config, &mut tape).map_err(|e| Failure {
+            format: F::NAME,
+            seed,
+            kind: FailureKind::Generate(e),
+        })?;
+        let Some(kind) = failure else {
+            continue;
+        };
+
+        let draws = shrink(tape.into_draws(), config.shrink_budget, |candidate| {
+            let mut tape = Tape::new(candidate, None);
+            match run_case::<T, F>(config, &mut tape) {
+                Ok(Some(_)) => Some(tape.into_draws()),
+                _ => None,
+            }
+        });
+        let mut tape = Tape::new(&draws, None);
+        // Shrinking only keeps draws that fail, but fall back on the original just in case
+        let kind = match run_case::<T, F>(config, &mut tape) {
+            Ok(Some(shrunk)) => shrunk,
+            _ => kind,
+        };
+        return Err(Failure {
+            format: F::NAME,
+            seed,
+            kind,
+        });
+    }
+    Ok(())
+}
+
+/// Generates one value from `source` and round-trips it
+fn run_case<T, F>(
+    config: &Config,
+    source: &mut dyn Source,
+) -> Result<Option<FailureKind>, GenerateError>
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    let mut generator = Generator::new(source);
+    if let Some(hook) = &config.generator {
+        generator = hook(generator);
+    }
+    let value: T = generator.generate()?;
+    Ok(roundtrip::<T, F>(&value))
+}
+
+/// Round-trips a single value, returning what went wrong, if anything
+pub fn roundtrip<T, F>(value: &T) -> Option<FailureKind>
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    // Without a canonical form there's nothing to compare against
+    let text = match facet_snapshot::to_canonical_string(value) {
+        Ok(text) => text,
+        Err(e) => {
+            return Some(FailureKind::Serialize {
+                value: String::from("(no canonical form)"),
+                message: e.to_string(),
+            });
+        }
+    };
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let output = match F::serialize(Peek::new(value)) {
+            Ok(output) => output,
+            Err(message) => {
+                return Some(FailureKind::Serialize {
+                    value: text.clone(),
+                    message,
+                });
+            }
+        };
+        let decoded = match F::deserialize::<T>(&output) {
+            Ok(decoded) => decoded,
+            Err(message) => {
+                return Some(FailureKind::Deserialize {
+                    value: text.clone(),
+                    output: String::from_utf8_lossy(&output).into_owned(),
+                    message,
+                });
+            }
+        };
+        let changes = match facet_snapshot::compare(&text, &decoded) {
+            Ok(changes) if changes.is_empty() => return None,
+            Ok(changes) => changes,
+            Err(e) => {
+                return Some(FailureKind::Deserialize {
+                    value: text.clone(),
+                    output: String::from_utf8_lossy(&output).into_owned(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        Some(FailureKind::Mismatch {
+            value: text.clone(),
+            output: String::from_utf8_lossy(&output).into_owned(),
+            changes,
+        })
+    }));
+    res.unwrap_or_else(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "(no message)".to_string()
+        };
+        Some(FailureKind::Panic {
+            value: text,
+            message,
+        })
+    })
+}
+
+/// A round trip that didn't work out
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// Name of the format
+    pub format: &'static str,
+    /// Seed of the case that failed first — the value in [`Failure::kind`] has been shrunk
+    /// since, but running that seed alone reproduces the failure
+    pub seed: u64,
+    /// What went wrong
+    pub kind: FailureKind,
+}
+
+/// What went wrong during a round trip
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum FailureKind {
+    /// No value could be generated at all
+    Generate(GenerateError),
+
+    /// Serializing the value failed
+    Serialize {
+        /// Canonical form of the value
+        value: String,
+        /// The format's error
+        message: String,
+    },
+
+    /// Deserializing what was serialized failed
+    Deserialize {
+        /// Canonical form of the value
+        value: String,
+        /// What it was serialized to (lossily decoded as UTF-8)
+        output: String,
+        /// The format's error
+        message: String,
+    },
+
+    /// The value came back different
+    Mismatch {
+        /// Canonical form of the value
+        value: String,
+        /// What it was serialized to (lossily decoded as UTF-8)
+        output: String,
+        /// How the deserialized value differs from the original
+        changes: Vec<Change>,
+    },
+
+    /// Serializing or deserializing panicked
+    Panic {
+        /// Canonical form of the value
+        value: String,
+        /// The panic's message
+        message: String,
+    },
+}
+
+impl core::fmt::Display for Failure {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let format = self.format;
+        match &self.kind {
+            FailureKind::Generate(e) => {
+                return write!(
+                    f,
+                    "couldn't generate values to round-trip through {format}: {e}"
+                );
+            }
+            FailureKind::Serialize { value, message } => {
+                writeln!(f, "serializing to {format} failed: {message}")?;
+                write!(f, "value: {value}")?;
+            }
+            FailureKind::Deserialize {
+                value,
+                output,
+                message,
+            } => {
+                writeln!(f, "deserializing from {format} failed: {message}")?;
+                writeln!(f, "value: {value}")?;
+                write!(f, "serialized: {output}")?;
+            }
+            FailureKind::Mismatch {
+                value,
+                output,
+                changes,
+            } => {
+                writeln!(f, "value changed in a round trip through {format}:")?;
+                for change in changes {
+                    writeln!(f, "  {change}")?;
+                }
+                writeln!(f, "value: {value}")?;
+                write!(f, "serialized: {output}")?;
+            }
+            FailureKind::Panic { value, message } => {
+                writeln!(f, "round trip through {format} panicked: {message}")?;
+                write!(f, "value: {value}")?;
+            }
+        }
+        write!(f, "\n(reproduce with FACET_TESTKIT_SEED={})", self.seed)
+    }
+}
+
+impl core::error::Error for Failure {}
@@ -0,0 +1,136 @@
+use facet_arbitrary::{Source, SplitMix64};
+
+/// A [`Source`] that replays recorded draws, then carries on with a random source if it has
+/// one — or with zeroes, which steer a generator towards small values — and records every
+/// draw it hands out.
+pub(crate) struct Tape<'a> {
+    replay: &'a [u64],
+    fallback: Option<SplitMix64>,
+    draws: Vec<u64>,
+}
+
+impl<'a> Tape<'a> {
+    pub(crate) fn new(replay: &'a [u64], fallback: Option<SplitMix64>) -> Self {
+        Self {
+            replay,
+            fallback,
+            draws: Vec::new(),
+        }
+    }
+
+    /// The draws handed out, minus trailing zeroes: replaying them gives the same value
+    pub(crate) fn into_draws(mut self) -> Vec<u64> {
+        while self.draws.last() == Some(&0) {
+            self.draws.pop();
+        }
+        self.draws
+    }
+}
+
+impl Source for Tape<'_> {
+    fn next_u64(&mut self) -> u64 {
+        let draw = match self.replay.get(self.draws.len()) {
+            Some(&draw) => draw,
+            None => match &mut self.fallback {
+                Some(fallback) => fallback.next_u64(),
+                None => 0,
+            },
+        };
+        self.draws.push(draw);
+        draw
+    }
+}
+
+/// Whether `a` is simpler than `b`: fewer draws, or as many but smaller ones
+fn simpler(a: &[u64], b: &[u64]) -> bool {
+    (a.len(), a) < (b.len(), b)
+}
+
+/// Shrinks the draws of a failing case. `fails` replays candidate draws and returns the
+/// ones actually used if the case still fails; at most `budget` candidates are tried.
+///
+/// Candidates drop chunks of draws (fewer elements, shorter strings…), then lower single
+/// draws: to small values, by halves and by one.
+pub(crate) fn shrink(
+    draws: Vec<u64>,
+    budget: usize,
+    fails: impl FnMut(&[u64]) -> Option<Vec<u64>>,
+) -> Vec<u64> {
+    let mut shrinker = Shrinker {
+        draws,
+        attempts: 0,
+        budget,
+        fails,
+    };
+    let lowerings: [fn(u64) -> u64; 2] = [|draw| draw / 2, |draw| draw - 1];
+
+    loop {
+        let before = shrinker.draws.clone();
+
+        for size in [8, 4, 2, 1] {
+            let mut start = shrinker.draws.len().saturating_sub(size);
+            loop {
+                if start + size <= shrinker.draws.len() {
+                    let mut candidate = shrinker.draws.clone();
+                    candidate.drain(start..start + size);
+                    shrinker.attempt(candidate);
+                }
+                if start == 0 {
+                    break;
+                }
+                start -= 1;
+            }
+        }
+
+        for index in 0..shrinker.draws.len() {
+            // Small values first: draws usually end up modulo something small
+            for small in 0..16 {
+                if index >= shrinker.draws.len() || small >= shrinker.draws[index] {
+                    break;
+                }
+                let mut candidate = shrinker.draws.clone();
+                candidate[index] = small;
+                if shrinker.attempt(candidate) {
+                    break;
+                }
+            }
+            for lower in lowerings {
+                while index < shrinker.draws.len() && shrinker.draws[index] != 0 {
+                    let mut candidate = shrinker.draws.clone();
+                    candidate[index] = lower(candidate[index]);
+                    if !shrinker.attempt(candidate) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if shrinker.draws == before || shrinker.attempts >= shrinker.budget {
+            return shrinker.draws;
+        }
+    }
+}
+
+struct Shrinker<F> {
+    draws: Vec<u64>,
+    attempts: usize,
+    budget: usize,
+    fails: F,
+}
+
+impl<F: FnMut(&[u64]) -> Option<Vec<u64>>> Shrinker<F> {
+    /// Moves on to `candidate` if it still fails and ends up simpler
+    fn attempt(&mut self, candidate: Vec<u64>) -> bool {
+        if self.attempts >= self.budget {
+            return false;
+        }
+        self.attempts += 1;
+        match (self.fails)(&candidate) {
+            Some(used) if simpler(&used, &self.draws) => {
+                self.draws = used;
+                true
+            }
+            _ => false,
+        }
+    }
+}
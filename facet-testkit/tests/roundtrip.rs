@@ -0,0 +1,90 @@
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Config, FailureKind, Format, check_roundtrip, roundtrip};
+
+struct Json;
+
+impl Format for Json {
+    const NAME: &'static str = "JSON";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        Ok(facet_json::peek_to_string(&peek).into_bytes())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        facet_json::from_slice(input).map_err(|e| e.to_string())
+    }
+}
+
+/// JSON that refuses to take more than 16 bytes
+struct Cramped;
+
+impl Format for Cramped {
+    const NAME: &'static str = "cramped JSON";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        let output = Json::serialize(peek)?;
+        if output.len() > 16 {
+            return Err(format!("{} bytes is too many", output.len()));
+        }
+        Ok(output)
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        Json::deserialize(input)
+    }
+}
+
+#[derive(Facet)]
+struct Named {
+    name: String,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn roundtrip_through_json() {
+    facet_testhelpers::setup();
+
+    assert!(roundtrip::<_, Json>(&Point { x: 1, y: -2 }).is_none());
+    check_roundtrip::<Point, Json>(&Config::new().cases(64)).unwrap();
+}
+
+#[test]
+fn failures_are_shrunk() {
+    facet_testhelpers::setup();
+
+    let failure = check_roundtrip::<Named, Cramped>(&Config::new().seed(1)).unwrap_err();
+    let FailureKind::Serialize { value, message } = &failure.kind else {
+        panic!("expected a serialization failure, got {failure}");
+    };
+    // The smallest string that doesn't fit takes a single draw: its length. The rest are
+    // zeroes, and the first character zeroes make is NUL, which JSON escapes to 6 bytes.
+    assert_eq!(value.lines().collect::<String>(), r#"{  "name": "\u0000"}"#);
+    assert_eq!(message, "17 bytes is too many");
+    assert!(failure.to_string().contains("FACET_TESTKIT_SEED="));
+}
+
+#[test]
+fn same_seed_same_failure() {
+    facet_testhelpers::setup();
+
+    let config = Config::new().seed(5).shrink_budget(0);
+    let first = check_roundtrip::<Named, Cramped>(&config).unwrap_err();
+    let second = check_roundtrip::<Named, Cramped>(&config).unwrap_err();
+    assert_eq!(first.seed, second.seed);
+    assert_eq!(first.to_string(), second.to_string());
+}
+
+#[test]
+fn generator_settings_apply() {
+    facet_testhelpers::setup();
+
+    // Empty strings always fit
+    let config = Config::new().generator(|generator| generator.string_len(0..1));
+    check_roundtrip::<Named, Cramped>(&config).unwrap();
+}
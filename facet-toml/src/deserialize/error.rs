@@ -65,6 +65,9 @@ impl<'input> TomlDeError<'input> {
                 format!("Expected type '{expected}', got type '{got}'")
             }
             TomlDeErrorKind::UnrecognizedType(r#type) => format!("Unrecognized type '{type}'"),
+            TomlDeErrorKind::UnsupportedType(shape) => {
+                format!("Can't deserialize type '{shape}' from TOML")
+            }
             TomlDeErrorKind::UnrecognizedScalar(scalar_type) => {
                 format!("Unrecognized Rust scalar type '{scalar_type}'",)
             }
@@ -74,6 +77,9 @@ impl<'input> TomlDeError<'input> {
             TomlDeErrorKind::ExpectedFieldWithName(name) => {
                 format!("Expected field with name '{name}'")
             }
+            TomlDeErrorKind::UnknownField(name) => {
+                format!("Unknown field '{name}'")
+            }
             TomlDeErrorKind::ExpectedAtLeastOneField => {
                 "Expected at least one field, got zero".to_string()
             }
@@ -165,12 +171,16 @@ pub enum TomlDeErrorKind {
     },
     /// Found a TOML type that we don't know how to handle.
     UnrecognizedType(&'static str),
+    /// Found a Rust type that can't be deserialized from TOML.
+    UnsupportedType(&'static Shape),
     /// Found a Rust scalar type that we don't know how to handle.
     UnrecognizedScalar(&'static Shape),
     /// Rust value is not a valid key.
     InvalidKey(&'static Shape),
     /// Expected a TOML field with the specified name, but couldn't find it.
     ExpectedFieldWithName(&'static str),
+    /// Found a TOML field that the struct doesn't have, and it denies unknown fields.
+    UnknownField(String),
     /// Expected at least one field, got zero.
     ExpectedAtLeastOneField,
     /// Expected a single value, got multiple field.
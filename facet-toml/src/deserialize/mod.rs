@@ -105,10 +105,15 @@ fn deserialize_item<'input, 'facet>(
     // Fall back to the def system for other types
     match wip.shape().def {
         Def::Scalar(_) => deserialize_as_scalar(toml, wip, item),
-        Def::List(_) => deserialize_as_list(toml, wip, item),
+        Def::List(_) | Def::Array(_) => deserialize_as_list(toml, wip, item),
         Def::Map(_) => deserialize_as_map(toml, wip, item),
         Def::SmartPointer(_) => deserialize_as_smartpointer(toml, wip, item),
-        _ => todo!(),
+        _ => Err(TomlDeError::new(
+            toml,
+            TomlDeErrorKind::UnsupportedType(wip.shape()),
+            item.span(),
+            wip.path(),
+        )),
     }
 }
 
@@ -124,6 +129,36 @@ fn deserialize_as_struct<'input, 'a>(
         "struct".blue()
     );
 
+    // Tuple structs are written as arrays, except that a single field holding a list can
+    // be written as just that list
+    if let Some(array) = item.as_array() {
+        let is_tuple = def.kind == StructKind::TupleStruct || def.kind == StructKind::Tuple;
+        let is_list_newtype = def.fields.len() == 1
+            && matches!(def.fields[0].shape().def, Def::List(_) | Def::Array(_));
+        if is_tuple && !is_list_newtype {
+            if array.len() > def.fields.len() {
+                return Err(TomlDeError::new(
+                    toml,
+                    TomlDeErrorKind::ExpectedType {
+                        expected: "array with one value per field",
+                        got: "array with more values",
+                    },
+                    array.span(),
+                    wip.path(),
+                ));
+            }
+
+            for (index, value) in array.iter().enumerate() {
+                reflect!(wip, toml, value.span(), field(index));
+                // TODO: remove clone
+                wip = deserialize_item(toml, wip, &Item::Value(value.clone()))?;
+                reflect!(wip, toml, value.span(), pop());
+            }
+
+            return Ok(wip);
+        }
+    }
+
     // Parse as a the inner struct type if item is a single value and the struct is a unit struct
     if item.is_value() && !item.is_inline_table() {
         // Only allow unit structs
@@ -161,6 +196,21 @@ fn deserialize_as_struct<'input, 'a>(
         )
     })?;
 
+    if wip.shape().has_deny_unknown_fields_attr() {
+        if let Some((key, _)) = table.iter().find(|(key, _)| def.field_index(key).is_none()) {
+            let span = table
+                .get_key_value(key)
+                .and_then(|(key, _)| key.span())
+                .or_else(|| item.span());
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::UnknownField(key.to_string()),
+                span,
+                wip.path(),
+            ));
+        }
+    }
+
     for field in def.fields {
         reflect!(wip, toml, item.span(), field_named(field.name));
 
@@ -225,7 +275,17 @@ fn deserialize_as_enum<'input, 'a>(
     );
 
     let wip = match item {
-        Item::None => todo!(),
+        Item::None | Item::ArrayOfTables(_) => {
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::ExpectedType {
+                    expected: "string or table",
+                    got: item.type_name(),
+                },
+                item.span(),
+                wip.path(),
+            ));
+        }
 
         Item::Value(value) => {
             trace!("Entering {}", "value".cyan());
@@ -303,8 +363,6 @@ fn deserialize_as_enum<'input, 'a>(
                 ));
             }
         }
-
-        Item::ArrayOfTables(_array_of_tables) => todo!(),
     };
 
     trace!("Finished deserializing {}", "enum".blue());
@@ -337,8 +395,25 @@ fn build_enum_from_variant_name<'input, 'a>(
     for (index, field) in variant.data.fields.iter().enumerate() {
         reflect!(wip, toml, item.span(), field_named(field.name));
 
-        // Try to get the TOML value as a table to extract the field
-        if let Some(table) = item.as_table_like() {
+        // Tuple variants with several fields are written as arrays
+        if let Some(array) = item
+            .as_array()
+            .filter(|_| is_tuple && variant.data.fields.len() > 1)
+        {
+            match array.get(index) {
+                // TODO: remove clone
+                Some(value) => wip = deserialize_item(toml, wip, &Item::Value(value.clone()))?,
+                None => {
+                    return Err(TomlDeError::new(
+                        toml,
+                        TomlDeErrorKind::ExpectedFieldWithName(field.name),
+                        item.span(),
+                        wip.path(),
+                    ));
+                }
+            }
+        } else if let Some(table) = item.as_table_like() {
+            // Try to get the TOML value as a table to extract the field
             // Base the field name on what type of struct we are
             let field_name = if is_tuple {
                 &index.to_string()
@@ -395,6 +470,33 @@ fn deserialize_as_list<'input, 'a>(
         "list".blue()
     );
 
+    // Fixed-size arrays are filled by pushing too, but can't be put empty
+    let is_list = matches!(wip.shape().def, Def::List(_));
+
+    // Arrays of tables, from `[[name]]` headers
+    if let Item::ArrayOfTables(tables) = item {
+        if tables.is_empty() && is_list {
+            reflect!(wip, toml, item.span(), put_empty_list());
+
+            return Ok(wip);
+        }
+
+        reflect!(wip, toml, item.span(), begin_pushback());
+
+        for table in tables.iter() {
+            reflect!(wip, toml, table.span(), push());
+
+            // TODO: remove clone
+            wip = deserialize_item(toml, wip, &Item::Table(table.clone()))?;
+
+            reflect!(wip, toml, table.span(), pop());
+        }
+
+        trace!("Finished deserializing {}", "array of tables".blue());
+
+        return Ok(wip);
+    }
+
     // Get the TOML item as an array
     let Some(item) = item.as_array() else {
         return Err(TomlDeError::new(
@@ -408,7 +510,7 @@ fn deserialize_as_list<'input, 'a>(
         ));
     };
 
-    if item.is_empty() {
+    if item.is_empty() && is_list {
         // Only put an empty list
         reflect!(wip, toml, item.span(), put_empty_list());
 
@@ -579,8 +681,8 @@ fn deserialize_as_option<'input, 'a>(
 }
 
 fn deserialize_as_smartpointer<'input, 'a>(
-    _toml: &'input str,
-    mut _wip: Wip<'a>,
+    toml: &'input str,
+    mut wip: Wip<'a>,
     item: &Item,
 ) -> Result<Wip<'a>, TomlDeError<'input>> {
     trace!(
@@ -589,9 +691,14 @@ fn deserialize_as_smartpointer<'input, 'a>(
         "smart pointer".blue()
     );
 
+    // The pointee is written as if it weren't behind a pointer
+    reflect!(wip, toml, item.span(), push_pointee());
+    wip = deserialize_item(toml, wip, item)?;
+    reflect!(wip, toml, item.span(), pop());
+
     trace!("Finished deserializing {}", "smart pointer".blue());
 
-    todo!();
+    Ok(wip)
 }
 
 fn deserialize_as_scalar<'input, 'a>(
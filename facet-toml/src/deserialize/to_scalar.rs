@@ -195,12 +195,23 @@ where
 }
 
 /// Try to convert a TOML string to a Rust type that implements `FromStr`.
+///
+/// Datetimes are passed on in their RFC 3339 form, so strings and date types that parse
+/// it can hold them.
 pub(crate) fn put_from_str<'input, 'a>(
     toml: &'input str,
     wip: Wip<'a>,
     item: &Item,
 ) -> Result<Wip<'a>, TomlDeError<'input>> {
-    let string = item.as_str().ok_or_else(|| {
+    let datetime;
+    let string = match item.as_datetime() {
+        Some(value) => {
+            datetime = value.to_string();
+            Some(datetime.as_str())
+        }
+        None => item.as_str(),
+    };
+    let string = string.ok_or_else(|| {
         TomlDeError::new(
             toml,
             TomlDeErrorKind::ExpectedType {
@@ -1,5 +1,7 @@
 //! Errors from parsing TOML documents.
 
+use facet_serialize::Unsupported;
+
 /// Any error from serializing TOML.
 pub enum TomlSerError {
     /// Could not convert number to i64 representation.
@@ -14,6 +16,8 @@ pub enum TomlSerError {
     },
     /// TOML doesn't support byte arrays.
     UnsupportedByteArray,
+    /// The value, or something in it, can't be serialized at all.
+    Unsupported(Unsupported),
 }
 
 impl core::fmt::Display for TomlSerError {
@@ -28,12 +32,19 @@ impl core::fmt::Display for TomlSerError {
             Self::UnsupportedByteArray => {
                 write!(f, "TOML doesn't support byte arrays")
             }
+            Self::Unsupported(unsupported) => write!(f, "{unsupported}"),
         }
     }
 }
 
 impl core::error::Error for TomlSerError {}
 
+impl From<Unsupported> for TomlSerError {
+    fn from(unsupported: Unsupported) -> Self {
+        Self::Unsupported(unsupported)
+    }
+}
+
 impl core::fmt::Debug for TomlSerError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Display::fmt(self, f)
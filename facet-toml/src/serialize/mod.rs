@@ -16,7 +16,7 @@ use core::borrow::Borrow as _;
 pub use error::TomlSerError;
 use facet_serialize::{Serialize, Serializer};
 use log::trace;
-use toml_edit::{DocumentMut, Item, Table, Value};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, Item, Table, Value};
 use yansi::Paint as _;
 
 /// Serializer for TOML values.
@@ -24,7 +24,10 @@ pub struct TomlSerializer {
     /// The TOML document.
     document: DocumentMut,
     /// Current stack of where we are in the tree.
-    key_stack: Vec<Cow<'static, str>>,
+    key_stack: Vec<KeySegment>,
+    /// For each table or array being written, whether it was appended to an array, so
+    /// its index has to be popped from the key stack when it ends.
+    container_stack: Vec<bool>,
     /// What type the current item is.
     current: KeyOrValue,
 }
@@ -35,6 +38,7 @@ impl TomlSerializer {
         Self {
             document: DocumentMut::new(),
             key_stack: Vec::new(),
+            container_stack: Vec::new(),
             current: KeyOrValue::Value,
         }
     }
@@ -56,7 +60,7 @@ impl TomlSerializer {
         match self.current {
             // Write the value
             KeyOrValue::Value => {
-                self.set_current_item(value);
+                self.place(value);
             }
             // Push the value as a new item
             KeyOrValue::Key => {
@@ -73,8 +77,10 @@ impl TomlSerializer {
         Ok(())
     }
 
-    /// Convert the item at the current key to another type.
-    fn set_current_item(&mut self, item: impl Into<Item>) {
+    /// Write an item at the current key, or append it if the current item is an array.
+    ///
+    /// Returns the index it got if it was appended.
+    fn place(&mut self, item: impl Into<Item>) -> Option<usize> {
         let item = item.into();
         trace!(
             "Set item {} to {}",
@@ -82,29 +88,134 @@ impl TomlSerializer {
             item.type_name()
         );
 
-        *self.item_mut() = item;
+        if let Some(KeySegment::Key { written, .. }) = self.key_stack.last_mut() {
+            *written = true;
+        }
+
+        let inline = self.is_inline();
+        let current = self.item_mut();
+        match current {
+            Item::ArrayOfTables(tables) => match item {
+                Item::Table(table) => {
+                    tables.push(table);
+                    Some(tables.len() - 1)
+                }
+                // Something other than a table after all: the array has to be inline
+                item => {
+                    let Item::ArrayOfTables(tables) = core::mem::take(current) else {
+                        unreachable!()
+                    };
+                    *current = Item::Value(Value::Array(tables.into_array()));
+                    self.place(item)
+                }
+            },
+            Item::Value(Value::Array(array)) => {
+                array.push(into_value(item));
+                Some(array.len() - 1)
+            }
+            current => {
+                *current = if inline {
+                    Item::Value(into_value(item))
+                } else {
+                    item
+                };
+                None
+            }
+        }
+    }
+
+    /// Start a table or array at the current key, and make it the current item.
+    fn start_container(&mut self, item: impl Into<Item>) {
+        let index = self.place(item);
+        if let Some(index) = index {
+            self.key_stack.push(KeySegment::Index(index));
+        }
+        self.container_stack.push(index.is_some());
+    }
+
+    /// Finish the current table or array.
+    fn end_container(&mut self) {
+        if self.container_stack.pop() == Some(true) {
+            self.key_stack.pop();
+        }
+    }
+
+    /// Start a table at the current key. Tables in an empty array that isn't inline make
+    /// it an array of tables.
+    fn start_table(&mut self) {
+        if !self.is_inline() {
+            let current = self.item_mut();
+            if let Item::Value(Value::Array(array)) = current {
+                if array.is_empty() {
+                    *current = Item::ArrayOfTables(ArrayOfTables::new());
+                }
+            }
+        }
+
+        let mut table = Table::new();
+        // Also show the table when it's empty
+        table.set_implicit(false);
+
+        self.start_container(table);
+    }
+
+    /// Whether the current item is inside an inline table or array, where tables have
+    /// to be inline too.
+    fn is_inline(&self) -> bool {
+        let mut item = self.document.as_item();
+        for segment in &self.key_stack {
+            if item.is_value() {
+                return true;
+            }
+            let next = match segment {
+                KeySegment::Key { key, .. } => {
+                    let key: &str = key.borrow();
+                    item.get(key)
+                }
+                KeySegment::Index(index) => item.get(*index),
+            };
+            // Keys of tables start out as `Item::None`, which tables don't hand out: nothing
+            // has been written under it yet
+            let Some(next) = next else {
+                return false;
+            };
+            item = next;
+        }
+        false
     }
 
     /// Get the mutable item for the current key.
     fn item_mut(&'_ mut self) -> &'_ mut Item {
         self.key_stack
             .iter()
-            .fold(self.document.as_item_mut(), |item, key| {
-                let key: &str = key.borrow();
-                item.get_mut(key).unwrap()
+            .fold(self.document.as_item_mut(), |item, segment| match segment {
+                KeySegment::Key { key, .. } => {
+                    let key: &str = key.borrow();
+                    item.get_mut(key).unwrap()
+                }
+                KeySegment::Index(index) => item.get_mut(*index).unwrap(),
             })
     }
 
     /// Create a new empty item at the key.
     fn push_key(&mut self, key: Cow<'static, str>, type_name: &'static str) {
-        // Push empty item
-        self.item_mut()
-            .as_table_mut()
-            .unwrap()
-            .insert(key.borrow(), Item::None);
+        // Push empty item, which inline tables can only hold as a value
+        match self.item_mut() {
+            Item::Value(Value::InlineTable(table)) => {
+                table.insert(&*key, Value::from(false));
+            }
+            item => {
+                item.as_table_mut()
+                    .unwrap()
+                    .insert(key.borrow(), Item::None);
+            }
+        }
 
         // Push the key on the stack
-        self.key_stack.push(key);
+        self.key_stack.push(KeySegment::Key {
+            key,
+            written: false,
+        });
 
         trace!("Push {type_name} {}", self.display_full_key());
     }
@@ -113,7 +224,16 @@ impl TomlSerializer {
     fn pop_key(&mut self, type_name: &'static str) {
         trace!("Pop {type_name} {}", self.display_full_key());
 
-        self.key_stack.pop();
+        // Nothing was written for it, e.g. for `None`: leave the key out
+        if let Some(KeySegment::Key {
+            key,
+            written: false,
+        }) = self.key_stack.pop()
+        {
+            if let Some(table) = self.item_mut().as_table_like_mut() {
+                table.remove(key.borrow());
+            }
+        }
     }
 
     /// Print the keys.
@@ -125,12 +245,12 @@ impl TomlSerializer {
         let mut output = "[".to_string();
         let mut first = true;
         for stack_item in &self.key_stack {
-            // Only loop over valid keys
-            output = format!(
-                "{output}{}{}",
-                if first { "" } else { "." },
-                stack_item.cyan()
-            );
+            output = match stack_item {
+                KeySegment::Key { key, .. } => {
+                    format!("{output}{}{}", if first { "" } else { "." }, key.cyan())
+                }
+                KeySegment::Index(index) => format!("{output}[{}]", index.cyan()),
+            };
             first = false;
         }
         format!("{output}]")
@@ -205,33 +325,44 @@ impl Serializer for TomlSerializer {
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &'static str,
+        variant_name: &'static str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        self.write_value(variant_name)
     }
 
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        let mut table = Table::new();
-        // Also show the table when it's empty
-        table.set_implicit(false);
-
-        self.set_current_item(table);
+        self.start_table();
 
         Ok(())
     }
 
     fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_current_item(toml_edit::array());
+        // An inline array, until a table gets pushed to it
+        self.start_container(Value::Array(Array::new()));
 
         Ok(())
     }
 
     fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        let mut table = Table::new();
-        // Also show the table when it's empty
-        table.set_implicit(false);
+        self.start_table();
+
+        Ok(())
+    }
 
-        self.set_current_item(table);
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+
+        Ok(())
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
 
         Ok(())
     }
@@ -267,6 +398,23 @@ impl Serializer for TomlSerializer {
     }
 }
 
+/// One step of the path to the current item.
+enum KeySegment {
+    /// A table key, and whether anything was written for it yet.
+    Key {
+        key: Cow<'static, str>,
+        written: bool,
+    },
+    /// An element of an array or array of tables.
+    Index(usize),
+}
+
+/// Turn a table into an inline table, for places where only values fit.
+fn into_value(item: Item) -> Value {
+    // Only values and tables get placed
+    item.into_value().unwrap()
+}
+
 /// What type the current item is.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum KeyOrValue {
@@ -229,3 +229,30 @@ fn test_enum_root() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_enum_array_of_tables() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: TestEnum,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum TestEnum {
+        Value { inner: i32 },
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("[[value]]\nValue = { inner = 1 }")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::ExpectedType {
+            expected: "string or table",
+            got: "array of tables",
+        }
+    );
+}
@@ -169,3 +169,97 @@ fn test_nested_lists() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_array_of_tables() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        bin: Vec<Target>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Target {
+        name: String,
+        path: Option<String>,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>(
+            r#"
+            [[bin]]
+            name = "first"
+
+            [[bin]]
+            name = "second"
+            path = "src/second.rs"
+            "#
+        )?,
+        Root {
+            bin: vec![
+                Target {
+                    name: "first".to_string(),
+                    path: None,
+                },
+                Target {
+                    name: "second".to_string(),
+                    path: Some("src/second.rs".to_string()),
+                },
+            ],
+        },
+    );
+
+    assert_eq!(
+        facet_toml::from_str::<Root>(r#"bin = [{ name = "inline" }]"#)?,
+        Root {
+            bin: vec![Target {
+                name: "inline".to_string(),
+                path: None,
+            }],
+        },
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fixed_size_array() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: [i32; 3],
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("values = [1, -1, 0]")?,
+        Root { values: [1, -1, 0] },
+    );
+
+    assert!(matches!(
+        facet_toml::from_str::<Root>("values = [1, 2, 3, 4]")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::GenericReflect(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_is_unsupported() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: std::collections::HashSet<i32>,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("values = [1, 2]")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::UnsupportedType(<std::collections::HashSet<i32> as Facet>::SHAPE)
+    );
+}
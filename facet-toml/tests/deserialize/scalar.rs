@@ -557,6 +557,27 @@ fn test_i8() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_datetime() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        released: String,
+        day: String,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("released = 1979-05-27T07:32:00Z\nday = 1979-05-27")?,
+        Root {
+            released: "1979-05-27T07:32:00Z".to_string(),
+            day: "1979-05-27".to_string(),
+        },
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_unit() -> Result<()> {
     facet_testhelpers::setup();
@@ -0,0 +1,32 @@
+//! Tests for TOML values to smart pointers.
+
+use std::sync::Arc;
+
+use eyre::Result;
+use facet::Facet;
+
+#[test]
+fn test_smart_pointers() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        size: Box<u32>,
+        inner: Arc<Inner>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Inner {
+        value: i32,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("size = 3\ninner = { value = 1 }")?,
+        Root {
+            size: Box::new(3),
+            inner: Arc::new(Inner { value: 1 }),
+        },
+    );
+
+    Ok(())
+}
@@ -313,3 +313,29 @@ fn test_default_struct_fields() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_deny_unknown_fields() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct Root {
+        a: i32,
+    }
+
+    assert_eq!(facet_toml::from_str::<Root>("a = 1")?, Root { a: 1 });
+    assert_eq!(
+        facet_toml::from_str::<Root>(
+            r#"
+            a = 1
+            b = true
+            "#
+        )
+        .unwrap_err()
+        .kind,
+        TomlDeErrorKind::UnknownField("b".to_string())
+    );
+
+    Ok(())
+}
@@ -4,6 +4,8 @@ use eyre::Result;
 use facet::Facet;
 use facet_toml::TomlDeErrorKind;
 
+use crate::assert_serialize;
+
 #[test]
 fn test_scalar_list() -> Result<()> {
     facet_testhelpers::setup();
@@ -169,3 +171,125 @@ fn test_nested_lists() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_serialize_lists() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: Vec<i32>,
+        nested: Vec<Vec<i32>>,
+        empty: Vec<Item>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Item(i32);
+
+    let root = Root {
+        values: vec![1, -1],
+        nested: vec![vec![2], vec![]],
+        empty: vec![],
+    };
+    assert_eq!(
+        facet_toml::to_string(&root)?,
+        "values = [1, -1]\nnested = [[2], []]\nempty = []\n"
+    );
+    assert_serialize!(Root, root);
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_array_of_tables() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        name: String,
+        bin: Vec<Target>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Target {
+        name: String,
+        path: Option<String>,
+        features: Vec<Feature>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Feature {
+        name: String,
+    }
+
+    let root = Root {
+        name: "app".to_string(),
+        bin: vec![
+            Target {
+                name: "first".to_string(),
+                path: None,
+                features: vec![],
+            },
+            Target {
+                name: "second".to_string(),
+                path: Some("src/second.rs".to_string()),
+                features: vec![Feature {
+                    name: "fast".to_string(),
+                }],
+            },
+        ],
+    };
+    assert_eq!(
+        facet_toml::to_string(&root)?,
+        r#"name = "app"
+
+[[bin]]
+name = "first"
+features = []
+
+[[bin]]
+name = "second"
+path = "src/second.rs"
+
+[[bin.features]]
+name = "fast"
+"#
+    );
+    assert_serialize!(Root, root);
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_tables_in_inline_arrays() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        groups: Vec<Vec<Point>>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Point {
+        x: i32,
+        label: Option<String>,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            groups: vec![
+                vec![
+                    Point { x: 1, label: None },
+                    Point {
+                        x: 2,
+                        label: Some("two".to_string()),
+                    },
+                ],
+                vec![],
+            ],
+        },
+    );
+
+    Ok(())
+}
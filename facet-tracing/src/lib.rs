@@ -0,0 +1,194 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use std::borrow::Cow;
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+mod text;
+
+#[cfg(feature = "valuable")]
+mod structured;
+
+/// Text recorded in place of sensitive fields
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Wraps a value so it can be recorded by `tracing`
+pub fn record<'mem, 'facet, T: Facet<'facet>>(value: &'mem T) -> Record<'mem, 'facet> {
+    Record::new(Peek::new(value))
+}
+
+/// A value, ready to be recorded by `tracing`
+///
+/// - As a single field, with `%` (its [`Display`](core::fmt::Display) impl): the fields of
+///   a struct, logfmt-style, as in `id=7 name="Ana" address.city="Lyon"`;
+/// - As a single field, with `?` (its [`Debug`](core::fmt::Debug) impl): the value in
+///   Rust-like syntax, as in `User { id: 7, name: "Ana", address: Address { city: "Lyon" } }`;
+/// - As one field per leaf, with [`Record::record_in`], which records into a span's fields;
+/// - With the `valuable` feature, as a fully structured value.
+///
+/// Whichever way it's recorded, fields marked `#[facet(sensitive)]` are replaced
+/// with [`REDACTED`], long collections and strings are truncated, and deep values are cut off,
+/// so a value that's fine to log today doesn't flood the logs tomorrow.
+#[derive(Clone)]
+pub struct Record<'mem, 'facet> {
+    peek: Peek<'mem, 'facet>,
+    prefix: Option<String>,
+    limits: Limits,
+    /// Built on first use by the `Valuable` impl, which has to hand out a reference to it
+    #[cfg(feature = "valuable")]
+    root: std::cell::OnceCell<structured::Node<'mem, 'facet>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Limits {
+    pub(crate) max_depth: usize,
+    pub(crate) max_items: usize,
+    pub(crate) max_string_len: usize,
+    pub(crate) flatten_fields: usize,
+}
+
+impl<'mem, 'facet> Record<'mem, 'facet> {
+    /// Wraps a value, with the default limits: values 4 levels deep, 8 collection items,
+    /// 128 characters per string, and flattening of nested structs with up to 4 fields
+    pub fn new(peek: Peek<'mem, 'facet>) -> Self {
+        Self {
+            peek,
+            prefix: None,
+            limits: Limits {
+                max_depth: 4,
+                max_items: 8,
+                max_string_len: 128,
+                flatten_fields: 4,
+            },
+            #[cfg(feature = "valuable")]
+            root: Default::default(),
+        }
+    }
+
+    /// Prefixes flattened field names: with `user`, fields become `user.id`, `user.name`…
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets how deep values are recorded; anything deeper is recorded as `…`
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.limits.max_depth = depth;
+        #[cfg(feature = "valuable")]
+        self.root.take();
+        self
+    }
+
+    /// Sets how many items of a list or map are recorded; the rest are counted
+    pub fn max_items(mut self, items: usize) -> Self {
+        self.limits.max_items = items;
+        self
+    }
+
+    /// Sets how many characters of a string are recorded
+    pub fn max_string_len(mut self, len: usize) -> Self {
+        self.limits.max_string_len = len;
+        #[cfg(feature = "valuable")]
+        self.root.take();
+        self
+    }
+
+    /// Sets how many fields a nested struct can have and still be flattened into its
+    /// parent (`address.city`, `address.zip`) rather than recorded as one field
+    pub fn flatten_fields(mut self, fields: usize) -> Self {
+        self.limits.flatten_fields = fields;
+        self
+    }
+
+    /// Returns the value's fields as flat name/value pairs, the way [`Record::record_in`]
+    /// records them
+    ///
+    /// Each field of a struct is one pair, and so is each field of a nested struct small
+    /// enough to be flattened; anything else (collections, enums, large structs) is recorded
+    /// as text. Fields that are `None` are left out. A value that isn't a struct at all is a
+    /// single pair, named after the prefix, or `value`.
+    pub fn fields(&self) -> Vec<(String, FieldValue<'mem>)> {
+        let mut fields = Vec::new();
+        text::flatten(self, &mut fields);
+        fields
+    }
+
+    /// Records the value's [`fields`](Record::fields) into `span`
+    ///
+    /// Spans only accept the fields they were created with, so declare them upfront
+    /// (as [`tracing::field::Empty`] if need be); fields the span doesn't know about are
+    /// ignored.
+    pub fn record_in(&self, span: &tracing::Span) {
+        for (name, value) in self.fields() {
+            let name = name.as_str();
+            match value {
+                FieldValue::Bool(b) => span.record(name, b),
+                FieldValue::I64(n) => span.record(name, n),
+                FieldValue::U64(n) => span.record(name, n),
+                FieldValue::F64(n) => span.record(name, n),
+                FieldValue::Str(s) => span.record(name, &*s),
+                FieldValue::Text(s) => span.record(name, tracing::field::display(s)),
+                FieldValue::Redacted => span.record(name, REDACTED),
+            };
+        }
+    }
+}
+
+impl core::fmt::Display for Record<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Values without fields (lists, numbers…) look the same either way
+        if text::named_fields(text::unwrap(self.peek)).is_none() && self.prefix.is_none() {
+            return text::write_value(&self.limits, f, self.peek, 0);
+        }
+        for (i, (name, value)) in self.fields().into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for Record<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        text::write_value(&self.limits, f, self.peek, 0)
+    }
+}
+
+/// One of the flat values of a [`Record`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FieldValue<'mem> {
+    /// A boolean
+    Bool(bool),
+    /// A signed integer
+    I64(i64),
+    /// An unsigned integer
+    U64(u64),
+    /// A floating-point number
+    F64(f64),
+    /// A string (or `char`), truncated if need be
+    Str(Cow<'mem, str>),
+    /// Anything else, as text: other scalars, collections, enums, large structs…
+    Text(String),
+    /// A sensitive field
+    Redacted,
+}
+
+impl core::fmt::Display for FieldValue<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldValue::Bool(b) => write!(f, "{b}"),
+            FieldValue::I64(n) => write!(f, "{n}"),
+            FieldValue::U64(n) => write!(f, "{n}"),
+            FieldValue::F64(n) => write!(f, "{n}"),
+            FieldValue::Str(s) => write!(f, "{s:?}"),
+            FieldValue::Text(s) => f.write_str(s),
+            FieldValue::Redacted => f.write_str(REDACTED),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use std::borrow::Cow;
+
+use facet_core::{Def, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use valuable::{
+    EnumDef, Enumerable, Fields, Listable, Mappable, NamedField, NamedValues, StructDef,
+    Structable, Tuplable, TupleDef, Valuable, Value, Variant, VariantDef, Visit,
+};
+
+use crate::text::{is_sensitive, truncate, unwrap, write_value};
+use crate::{Limits, REDACTED, Record};
+
+impl Valuable for Record<'_, '_> {
+    fn as_value(&self) -> Value<'_> {
+        self.root().as_value()
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.root().visit(visit)
+    }
+}
+
+impl<'mem, 'facet> Record<'mem, 'facet> {
+    fn root(&self) -> &Node<'mem, 'facet> {
+        self.root
+            .get_or_init(|| Node::new(self.limits, self.peek, 0))
+    }
+}
+
+/// A value inside a [`Record`], as `valuable` sees it
+#[derive(Clone)]
+pub(crate) struct Node<'mem, 'facet> {
+    limits: Limits,
+    peek: Peek<'mem, 'facet>,
+    depth: usize,
+    repr: Repr<'mem>,
+}
+
+#[derive(Clone)]
+enum Repr<'mem> {
+    /// `None`
+    Unit,
+    /// Strings, redacted fields, scalars `valuable` has no variant for, and values past the
+    /// maximum depth
+    Text(Cow<'mem, str>),
+    /// Booleans, numbers and chars
+    Primitive,
+    List,
+    Map,
+    Tuple,
+    /// The type name is kept around for [`StructDef`] and [`EnumDef`] to borrow
+    Struct(String),
+    Enum(String),
+}
+
+impl<'mem, 'facet> Node<'mem, 'facet> {
+    fn new(limits: Limits, peek: Peek<'mem, 'facet>, depth: usize) -> Self {
+        let mut peek = unwrap(peek);
+        let repr = loop {
+            if let Ok(option) = peek.into_option() {
+                match option.value() {
+                    Some(value) => {
+                        peek = unwrap(value);
+                        continue;
+                    }
+                    None => break Repr::Unit,
+                }
+            }
+            break Self::repr(&limits, peek, depth);
+        };
+        Self {
+            limits,
+            peek,
+            depth,
+            repr,
+        }
+    }
+
+    fn redacted(limits: Limits, peek: Peek<'mem, 'facet>) -> Self {
+        Self {
+            limits,
+            peek,
+            depth: 0,
+            repr: Repr::Text(Cow::Borrowed(REDACTED)),
+        }
+    }
+
+    fn repr(limits: &Limits, peek: Peek<'mem, 'facet>, depth: usize) -> Repr<'mem> {
+        if let Def::Scalar(_) = peek.shape().def {
+            if let Some(s) = peek.as_str() {
+                return Repr::Text(truncate(s, limits.max_string_len));
+            }
+            return match peek.scalar_type() {
+                Some(
+                    ScalarType::Bool
+                    | ScalarType::Char
+                    | ScalarType::F32
+                    | ScalarType::F64
+                    | ScalarType::U8
+                    | ScalarType::U16
+                    | ScalarType::U32
+                    | ScalarType::U64
+                    | ScalarType::U128
+                    | ScalarType::USize
+                    | ScalarType::I8
+                    | ScalarType::I16
+                    | ScalarType::I32
+                    | ScalarType::I64
+                    | ScalarType::I128
+                    | ScalarType::ISize,
+                ) => Repr::Primitive,
+                _ => Repr::Text(Cow::Owned(text(limits, peek, depth))),
+            };
+        }
+
+        if depth >= limits.max_depth {
+            return Repr::Text(Cow::Borrowed("…"));
+        }
+        if peek.into_list_like().is_ok() {
+            return Repr::List;
+        }
+        if peek.into_map().is_ok() {
+            return Repr::Map;
+        }
+        if peek.into_tuple().is_ok() {
+            return Repr::Tuple;
+        }
+        match peek.shape().ty {
+            Type::User(UserType::Struct(_)) => Repr::Struct(peek.shape().to_string()),
+            Type::User(UserType::Enum(_)) if peek.into_enum().is_ok() => {
+                Repr::Enum(peek.shape().to_string())
+            }
+            _ => Repr::Text(Cow::Owned(text(limits, peek, depth))),
+        }
+    }
+
+    fn child(&self, peek: Peek<'mem, 'facet>) -> Self {
+        Self::new(self.limits, peek, self.depth + 1)
+    }
+
+    /// The fields of a struct or the active enum variant, with sensitive ones redacted
+    fn fields(&self) -> (StructKind, Vec<(&'static str, Self)>) {
+        let (kind, fields): (_, Vec<_>) = match &self.repr {
+            Repr::Struct(_) => match (self.peek.shape().ty, self.peek.into_struct()) {
+                (Type::User(UserType::Struct(st)), Ok(ps)) => {
+                    (st.kind, ps.fields_for_serialize().collect())
+                }
+                _ => return (StructKind::Unit, Vec::new()),
+            },
+            Repr::Enum(_) => {
+                let Ok(pe) = self.peek.into_enum() else {
+                    return (StructKind::Unit, Vec::new());
+                };
+                let Ok(variant) = pe.active_variant() else {
+                    return (StructKind::Unit, Vec::new());
+                };
+                (variant.data.kind, pe.fields_for_serialize().collect())
+            }
+            _ => return (StructKind::Unit, Vec::new()),
+        };
+        let fields = fields
+            .into_iter()
+            .map(|(field, value)| {
+                let node = if is_sensitive(&field) {
+                    Self::redacted(self.limits, value)
+                } else {
+                    self.child(value)
+                };
+                (field.name, node)
+            })
+            .collect();
+        (kind, fields)
+    }
+
+    fn visit_fields(&self, visit: &mut dyn Visit) {
+        let (kind, fields) = self.fields();
+        let values: Vec<Value<'_>> = fields.iter().map(|(_, node)| node.as_value()).collect();
+        match kind {
+            StructKind::Unit => {}
+            StructKind::Struct => {
+                let names: Vec<NamedField<'_>> = fields
+                    .iter()
+                    .map(|(name, _)| NamedField::new(name))
+                    .collect();
+                visit.visit_named_fields(&NamedValues::new(&names, &values));
+            }
+            _ => visit.visit_unnamed_fields(&values),
+        }
+    }
+
+    /// How many items of a collection are visited, the "… N more" marker included
+    fn visited_len(&self, len: usize) -> usize {
+        len.min(self.limits.max_items) + usize::from(len > self.limits.max_items)
+    }
+}
+
+fn text(limits: &Limits, peek: Peek<'_, '_>, depth: usize) -> String {
+    let mut text = String::new();
+    // Writing to a `String` can't fail
+    let _ = write_value(limits, &mut text, peek, depth);
+    text
+}
+
+fn primitive<'a>(peek: Peek<'_, '_>) -> Option<Value<'a>> {
+    Some(match peek.scalar_type()? {
+        ScalarType::Bool => Value::Bool(*peek.get::<bool>().ok()?),
+        ScalarType::Char => Value::Char(*peek.get::<char>().ok()?),
+        ScalarType::F32 => Value::F32(*peek.get::<f32>().ok()?),
+        ScalarType::F64 => Value::F64(*peek.get::<f64>().ok()?),
+        ScalarType::U8 => Value::U8(*peek.get::<u8>().ok()?),
+        ScalarType::U16 => Value::U16(*peek.get::<u16>().ok()?),
+        ScalarType::U32 => Value::U32(*peek.get::<u32>().ok()?),
+        ScalarType::U64 => Value::U64(*peek.get::<u64>().ok()?),
+        ScalarType::U128 => Value::U128(*peek.get::<u128>().ok()?),
+        ScalarType::USize => Value::Usize(*peek.get::<usize>().ok()?),
+        ScalarType::I8 => Value::I8(*peek.get::<i8>().ok()?),
+        ScalarType::I16 => Value::I16(*peek.get::<i16>().ok()?),
+        ScalarType::I32 => Value::I32(*peek.get::<i32>().ok()?),
+        ScalarType::I64 => Value::I64(*peek.get::<i64>().ok()?),
+        ScalarType::I128 => Value::I128(*peek.get::<i128>().ok()?),
+        ScalarType::ISize => Value::Isize(*peek.get::<isize>().ok()?),
+        _ => return None,
+    })
+}
+
+impl Valuable for Node<'_, '_> {
+    fn as_value(&self) -> Value<'_> {
+        match &self.repr {
+            Repr::Unit => Value::Unit,
+            Repr::Text(text) => Value::String(text),
+            Repr::Primitive => primitive(self.peek).unwrap_or(Value::Unit),
+            Repr::List => Value::Listable(self),
+            Repr::Map => Value::Mappable(self),
+            Repr::Tuple => Value::Tuplable(self),
+            Repr::Struct(_) => Value::Structable(self),
+            Repr::Enum(_) => Value::Enumerable(self),
+        }
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        match &self.repr {
+            Repr::List => {
+                let Ok(list) = self.peek.into_list_like() else {
+                    return;
+                };
+                let len = list.len();
+                for item in list.iter().take(self.limits.max_items) {
+                    visit.visit_value(self.child(item).as_value());
+                }
+                if len > self.limits.max_items {
+                    let more = format!("… {} more", len - self.limits.max_items);
+                    visit.visit_value(Value::String(&more));
+                }
+            }
+            Repr::Map => {
+                let Ok(map) = self.peek.into_map() else {
+                    return;
+                };
+                let len = map.len();
+                for (key, value) in map.iter().take(self.limits.max_items) {
+                    visit.visit_entry(self.child(key).as_value(), self.child(value).as_value());
+                }
+                if len > self.limits.max_items {
+                    let more = format!("{} more", len - self.limits.max_items);
+                    visit.visit_entry(Value::String("…"), Value::String(&more));
+                }
+            }
+            Repr::Tuple => {
+                let Ok(tuple) = self.peek.into_tuple() else {
+                    return;
+                };
+                let nodes: Vec<Self> = tuple.fields().map(|(_, item)| self.child(item)).collect();
+                let values: Vec<Value<'_>> = nodes.iter().map(Valuable::as_value).collect();
+                visit.visit_unnamed_fields(&values);
+            }
+            Repr::Struct(_) | Repr::Enum(_) => self.visit_fields(visit),
+            Repr::Unit | Repr::Text(_) | Repr::Primitive => visit.visit_value(self.as_value()),
+        }
+    }
+}
+
+impl Listable for Node<'_, '_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.visited_len(self.peek.into_list_like().map_or(0, |list| list.len()));
+        (len, Some(len))
+    }
+}
+
+impl Mappable for Node<'_, '_> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.visited_len(self.peek.into_map().map_or(0, |map| map.len()));
+        (len, Some(len))
+    }
+}
+
+impl Tuplable for Node<'_, '_> {
+    fn definition(&self) -> TupleDef {
+        TupleDef::new_static(self.peek.into_tuple().map_or(0, |tuple| tuple.len()))
+    }
+}
+
+impl Structable for Node<'_, '_> {
+    fn definition(&self) -> StructDef<'_> {
+        let Repr::Struct(name) = &self.repr else {
+            unreachable!("only structs are structable");
+        };
+        // Fields are only known once visited: unit structs have none, sensitive ones
+        // are still listed, and flattened ones are listed in their parent
+        let fields = match self.peek.shape().ty {
+            Type::User(UserType::Struct(st)) if st.kind != StructKind::Struct => {
+                Fields::Unnamed(st.fields.len())
+            }
+            _ => Fields::Named(&[]),
+        };
+        StructDef::new_dynamic(name, fields)
+    }
+}
+
+impl Enumerable for Node<'_, '_> {
+    fn definition(&self) -> EnumDef<'_> {
+        let Repr::Enum(name) = &self.repr else {
+            unreachable!("only enums are enumerable");
+        };
+        EnumDef::new_dynamic(name, &[])
+    }
+
+    fn variant(&self) -> Variant<'_> {
+        let variant = self
+            .peek
+            .into_enum()
+            .ok()
+            .and_then(|pe| pe.active_variant().ok());
+        match variant {
+            Some(variant) => {
+                let fields = match variant.data.kind {
+                    StructKind::Struct => Fields::Named(&[]),
+                    _ => Fields::Unnamed(variant.data.fields.len()),
+                };
+                Variant::Dynamic(VariantDef::new(variant.name, fields))
+            }
+            None => Variant::Dynamic(VariantDef::new("?", Fields::Unnamed(0))),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use core::fmt::{self, Write};
+use std::borrow::Cow;
+
+use facet_core::{Def, Field, FieldFlags, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+
+use crate::{FieldValue, Limits, REDACTED, Record};
+
+/// Looks through transparent wrappers and smart pointers
+pub(crate) fn unwrap<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Peek<'mem, 'facet> {
+    let mut current = innermost(peek);
+    while let Def::SmartPointer(_) = current.shape().def {
+        match current
+            .into_smart_pointer()
+            .ok()
+            .and_then(|sp| sp.borrow_inner())
+        {
+            Some(inner) => current = innermost(inner),
+            None => break,
+        }
+    }
+    current
+}
+
+/// Like [`Peek::innermost_peek`], but options are kept, as a `None` has nothing to borrow
+fn innermost<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Peek<'mem, 'facet> {
+    match peek.shape().def {
+        Def::Option(_) => peek,
+        _ => peek.innermost_peek(),
+    }
+}
+
+/// The fields of a struct with named fields, sensitive and flattened ones included
+pub(crate) fn named_fields<'mem, 'facet>(
+    peek: Peek<'mem, 'facet>,
+) -> Option<Vec<(Field, Peek<'mem, 'facet>)>> {
+    match peek.shape().ty {
+        Type::User(UserType::Struct(st)) if st.kind == StructKind::Struct => {
+            Some(peek.into_struct().ok()?.fields_for_serialize().collect())
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn is_sensitive(field: &Field) -> bool {
+    field.flags.contains(FieldFlags::SENSITIVE)
+}
+
+pub(crate) fn flatten<'mem>(record: &Record<'mem, '_>, out: &mut Vec<(String, FieldValue<'mem>)>) {
+    let peek = unwrap(record.peek);
+    match named_fields(peek) {
+        Some(fields) => {
+            let prefix = record.prefix.clone().unwrap_or_default();
+            flatten_fields(&record.limits, &prefix, fields, 0, out);
+        }
+        None => {
+            let name = record.prefix.clone().unwrap_or_else(|| "value".to_string());
+            if let Some(value) = leaf(&record.limits, peek, 0) {
+                out.push((name, value));
+            }
+        }
+    }
+}
+
+fn flatten_fields<'mem>(
+    limits: &Limits,
+    prefix: &str,
+    fields: Vec<(Field, Peek<'mem, '_>)>,
+    depth: usize,
+    out: &mut Vec<(String, FieldValue<'mem>)>,
+) {
+    for (field, value) in fields {
+        let name = if prefix.is_empty() {
+            field.name.to_string()
+        } else {
+            format!("{prefix}.{}", field.name)
+        };
+        if is_sensitive(&field) {
+            out.push((name, FieldValue::Redacted));
+            continue;
+        }
+
+        let value = unwrap(value);
+        if depth + 1 < limits.max_depth {
+            if let Some(nested) = named_fields(value) {
+                if nested.len() <= limits.flatten_fields {
+                    flatten_fields(limits, &name, nested, depth + 1, out);
+                    continue;
+                }
+            }
+        }
+        if let Some(value) = leaf(limits, value, depth + 1) {
+            out.push((name, value));
+        }
+    }
+}
+
+/// Converts a value to a flat value, or `None` if it's `None`
+fn leaf<'mem>(limits: &Limits, peek: Peek<'mem, '_>, depth: usize) -> Option<FieldValue<'mem>> {
+    let peek = unwrap(peek);
+    if let Ok(option) = peek.into_option() {
+        return leaf(limits, option.value()?, depth);
+    }
+
+    if let Some(s) = peek.as_str() {
+        return Some(FieldValue::Str(Cow::Owned(
+            truncate(s, limits.max_string_len).into_owned(),
+        )));
+    }
+    let value = match peek.scalar_type() {
+        Some(ScalarType::Bool) => peek.get::<bool>().ok().map(|&b| FieldValue::Bool(b)),
+        Some(ScalarType::Char) => peek
+            .get::<char>()
+            .ok()
+            .map(|c| FieldValue::Str(Cow::Owned(c.to_string()))),
+        Some(ScalarType::F32) => peek.get::<f32>().ok().map(|&n| FieldValue::F64(n as f64)),
+        Some(ScalarType::F64) => peek.get::<f64>().ok().map(|&n| FieldValue::F64(n)),
+        Some(ScalarType::U8) => peek.get::<u8>().ok().map(|&n| FieldValue::U64(n as u64)),
+        Some(ScalarType::U16) => peek.get::<u16>().ok().map(|&n| FieldValue::U64(n as u64)),
+        Some(ScalarType::U32) => peek.get::<u32>().ok().map(|&n| FieldValue::U64(n as u64)),
+        Some(ScalarType::U64) => peek.get::<u64>().ok().map(|&n| FieldValue::U64(n)),
+        Some(ScalarType::USize) => peek.get::<usize>().ok().map(|&n| FieldValue::U64(n as u64)),
+        Some(ScalarType::I8) => peek.get::<i8>().ok().map(|&n| FieldValue::I64(n as i64)),
+        Some(ScalarType::I16) => peek.get::<i16>().ok().map(|&n| FieldValue::I64(n as i64)),
+        Some(ScalarType::I32) => peek.get::<i32>().ok().map(|&n| FieldValue::I64(n as i64)),
+        Some(ScalarType::I64) => peek.get::<i64>().ok().map(|&n| FieldValue::I64(n)),
+        Some(ScalarType::ISize) => peek.get::<isize>().ok().map(|&n| FieldValue::I64(n as i64)),
+        _ => None,
+    };
+    if value.is_some() {
+        return value;
+    }
+
+    let mut text = String::new();
+    write_value(limits, &mut text, peek, depth).ok()?;
+    Some(FieldValue::Text(text))
+}
+
+pub(crate) fn truncate(s: &str, max_len: usize) -> Cow<'_, str> {
+    match s.char_indices().nth(max_len) {
+        Some((end, _)) => Cow::Owned(format!("{}…", &s[..end])),
+        None => Cow::Borrowed(s),
+    }
+}
+
+/// Writes a value in compact, Rust-like syntax
+pub(crate) fn write_value<W: Write>(
+    limits: &Limits,
+    w: &mut W,
+    peek: Peek<'_, '_>,
+    depth: usize,
+) -> fmt::Result {
+    let peek = unwrap(peek);
+
+    if let Ok(option) = peek.into_option() {
+        return match option.value() {
+            Some(value) => write_value(limits, w, value, depth),
+            None => w.write_str("None"),
+        };
+    }
+
+    if let Def::Scalar(_) = peek.shape().def {
+        return match peek.as_str() {
+            Some(s) => write!(w, "{:?}", truncate(s, limits.max_string_len)),
+            None if peek.vtable().display.is_some()
+                && !matches!(peek.scalar_type(), Some(ScalarType::Char)) =>
+            {
+                write!(w, "{peek}")
+            }
+            None => write!(w, "{peek:?}"),
+        };
+    }
+
+    if depth >= limits.max_depth {
+        return w.write_str("…");
+    }
+
+    if let Ok(list) = peek.into_list_like() {
+        let len = list.len();
+        return write_items(
+            limits,
+            w,
+            "[",
+            "]",
+            len,
+            list.iter().map(|item| (None, item)),
+            depth,
+        );
+    }
+
+    if let Ok(map) = peek.into_map() {
+        let len = map.len();
+        return write_items(
+            limits,
+            w,
+            "{",
+            "}",
+            len,
+            map.iter().map(|(k, v)| (Some(k), v)),
+            depth,
+        );
+    }
+
+    if let Ok(tuple) = peek.into_tuple() {
+        // Tuples are short, and truncating them would make them unreadable
+        w.write_str("(")?;
+        for (i, (_, item)) in tuple.fields().enumerate() {
+            if i > 0 {
+                w.write_str(", ")?;
+            }
+            write_value(limits, w, item, depth + 1)?;
+        }
+        return w.write_str(")");
+    }
+
+    match peek.shape().ty {
+        Type::User(UserType::Struct(st)) => {
+            let Ok(ps) = peek.into_struct() else {
+                return write!(w, "{peek:?}");
+            };
+            write!(w, "{}", peek.shape())?;
+            write_fields(limits, w, st.kind, ps.fields_for_serialize(), depth)
+        }
+        Type::User(UserType::Enum(_)) => {
+            let Ok(pe) = peek.into_enum() else {
+                return write!(w, "{peek:?}");
+            };
+            let Ok(variant) = pe.active_variant() else {
+                return write!(w, "{peek:?}");
+            };
+            w.write_str(variant.name)?;
+            write_fields(
+                limits,
+                w,
+                variant.data.kind,
+                pe.fields_for_serialize(),
+                depth,
+            )
+        }
+        _ => write!(w, "{peek:?}"),
+    }
+}
+
+/// Writes the fields of a struct or enum variant, after its name
+fn write_fields<'mem, 'facet, W: Write>(
+    limits: &Limits,
+    w: &mut W,
+    kind: StructKind,
+    fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+    depth: usize,
+) -> fmt::Result {
+    let named = match kind {
+        StructKind::Unit => return Ok(()),
+        StructKind::Struct => true,
+        _ => false,
+    };
+    w.write_str(if named { " {" } else { "(" })?;
+    let mut empty = true;
+    for (i, (field, value)) in fields.enumerate() {
+        empty = false;
+        if i > 0 {
+            w.write_str(",")?;
+        }
+        if named {
+            write!(w, " {}: ", field.name)?;
+        } else if i > 0 {
+            w.write_str(" ")?;
+        }
+        if is_sensitive(&field) {
+            w.write_str(REDACTED)?;
+        } else {
+            write_value(limits, w, value, depth + 1)?;
+        }
+    }
+    match (named, empty) {
+        (true, true) => w.write_str("}"),
+        (true, false) => w.write_str(" }"),
+        (false, _) => w.write_str(")"),
+    }
+}
+
+/// Writes the items of a collection, up to `max_items` of them
+fn write_items<'mem, 'facet, W: Write>(
+    limits: &Limits,
+    w: &mut W,
+    open: &str,
+    close: &str,
+    len: usize,
+    items: impl Iterator<Item = (Option<Peek<'mem, 'facet>>, Peek<'mem, 'facet>)>,
+    depth: usize,
+) -> fmt::Result {
+    w.write_str(open)?;
+    for (i, (key, value)) in items.take(limits.max_items).enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        if let Some(key) = key {
+            write_value(limits, w, key, depth + 1)?;
+            w.write_str(": ")?;
+        }
+        write_value(limits, w, value, depth + 1)?;
+    }
+    if len > limits.max_items {
+        if limits.max_items > 0 {
+            w.write_str(", ")?;
+        }
+        write!(w, "… {} more", len - limits.max_items)?;
+    }
+    w.write_str(close)
+}
@@ -0,0 +1,96 @@
+use facet::Facet;
+use facet_tracing::{FieldValue, record};
+
+#[derive(Facet)]
+struct Signup {
+    id: u64,
+    email: String,
+    #[facet(sensitive)]
+    password: String,
+    address: Address,
+    tags: Vec<String>,
+    referrer: Option<String>,
+}
+
+#[derive(Facet)]
+struct Address {
+    city: String,
+    zip: String,
+}
+
+fn signup() -> Signup {
+    Signup {
+        id: 7,
+        email: "ana@example.com".to_string(),
+        password: "hunter2".to_string(),
+        address: Address {
+            city: "Lyon".to_string(),
+            zip: "69001".to_string(),
+        },
+        tags: ["a", "b", "c", "d", "e"].map(String::from).to_vec(),
+        referrer: None,
+    }
+}
+
+#[test]
+fn display_flattens_fields() {
+    facet_testhelpers::setup();
+
+    let signup = signup();
+    assert_eq!(
+        record(&signup).max_items(2).to_string(),
+        r#"id=7 email="ana@example.com" password=[REDACTED] address.city="Lyon" address.zip="69001" tags=["a", "b", … 3 more]"#
+    );
+
+    // Too many fields to flatten, so `address` is recorded as one
+    assert_eq!(
+        record(&signup).flatten_fields(1).fields()[3],
+        (
+            "address".to_string(),
+            FieldValue::Text(r#"Address { city: "Lyon", zip: "69001" }"#.to_string())
+        )
+    );
+
+    let fields = record(&signup.address)
+        .prefix("user")
+        .max_string_len(3)
+        .fields();
+    assert_eq!(
+        fields,
+        [
+            ("user.city".to_string(), FieldValue::Str("Lyo…".into())),
+            ("user.zip".to_string(), FieldValue::Str("690…".into())),
+        ]
+    );
+}
+
+#[test]
+fn debug_is_rust_like() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        format!("{:?}", record(&signup()).max_items(2)),
+        r#"Signup { id: 7, email: "ana@example.com", password: [REDACTED], address: Address { city: "Lyon", zip: "69001" }, tags: ["a", "b", … 3 more], referrer: None }"#
+    );
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Status {
+        Active,
+        Banned { reason: String },
+    }
+
+    let status = Status::Banned {
+        reason: "spam".to_string(),
+    };
+    assert_eq!(
+        format!("{:?}", record(&status)),
+        r#"Banned { reason: "spam" }"#
+    );
+
+    // Values without fields display the same way
+    let nested = vec![vec![vec![1u8]], vec![]];
+    assert_eq!(record(&nested).max_depth(2).to_string(), "[[…], []]");
+    assert_eq!(record(&(1u8, 'x')).to_string(), "(1, 'x')");
+}
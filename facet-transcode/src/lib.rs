@@ -0,0 +1,44 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_deserialize::DeserError;
+
+mod transcode;
+pub use transcode::*;
+
+/// Something went wrong on one side of a transcoding
+#[derive(Debug)]
+pub enum TranscodeError<'input, E> {
+    /// The input couldn't be read, or didn't fit the shape
+    Read(DeserError<'input>),
+    /// The serializer failed
+    Write(E),
+}
+
+impl<E> TranscodeError<'_, E> {
+    /// Detaches the error from the input it was read from
+    pub fn into_owned(self) -> TranscodeError<'static, E> {
+        match self {
+            TranscodeError::Read(err) => TranscodeError::Read(err.into_owned()),
+            TranscodeError::Write(err) => TranscodeError::Write(err),
+        }
+    }
+}
+
+impl<'input, E> From<DeserError<'input>> for TranscodeError<'input, E> {
+    fn from(err: DeserError<'input>) -> Self {
+        TranscodeError::Read(err)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for TranscodeError<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TranscodeError::Read(err) => write!(f, "{err}"),
+            TranscodeError::Write(err) => write!(f, "couldn't write the output: {err}"),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for TranscodeError<'_, E> {}
@@ -0,0 +1,586 @@
+use facet_core::{
+    Def, Field, FieldFlags, SequenceType, Shape, StructKind, StructType, Type, UserType,
+};
+use facet_deserialize::{
+    DeserErrorKind, EventReader, Expectation, Format, Outcome, Scalar, Span, Spanned,
+};
+use facet_reflect::ScalarType;
+use facet_serialize::Serializer;
+use log::trace;
+
+use crate::TranscodeError;
+
+type Error<'input, S> = TranscodeError<'input, <S as Serializer>::Error>;
+
+/// Calls the serializer, wrapping its error
+macro_rules! emit {
+    ($self:ident.$method:ident($($arg:expr),*)) => {
+        $self
+            .serializer
+            .$method($($arg),*)
+            .map_err(TranscodeError::Write)?
+    };
+}
+
+/// Streams one value from `reader` into `serializer`, event by event.
+///
+/// With a `shape`, the value is checked against it on the way — fields are looked up, and
+/// unknown ones skipped unless the shape denies them, numbers must fit, required fields must
+/// be there — and written the way `facet-serialize` would write a value of that shape:
+/// structs as objects with their field names, integers at their width, and so on. Without
+/// one, everything is passed through as-is.
+///
+/// Some serializers need the length of arrays and objects before their items, so each
+/// container is read twice: once to count its items, skipping over them, then to transcode
+/// them. Input nested `n` levels deep is read up to `n` times over, but nothing is ever
+/// built out of it.
+pub fn transcode<'input, F, S>(
+    reader: EventReader<'input, F>,
+    serializer: &mut S,
+    shape: Option<&'static Shape>,
+) -> Result<(), Error<'input, S>>
+where
+    F: Format,
+    S: Serializer,
+{
+    let mut transcoder = Transcoder {
+        reader,
+        serializer,
+        stack: Vec::new(),
+    };
+    let first = transcoder.reader.next(Expectation::Value)?;
+    transcoder.value(first, shape.map_or(Guide::Any, Guide::Shape))?;
+    transcoder.run()
+}
+
+/// What a value is expected to be
+#[derive(Clone, Copy)]
+enum Guide {
+    /// Anything: it's passed through as-is
+    Any,
+    /// A value of this shape
+    Shape(&'static Shape),
+}
+
+/// What the items of a list are expected to be
+#[derive(Clone, Copy)]
+enum Items {
+    Any,
+    /// All of this shape, and exactly `len` of them for arrays
+    Each {
+        shape: &'static Shape,
+        len: Option<usize>,
+    },
+    /// One per field, for tuples
+    Fields(&'static [Field]),
+}
+
+impl Items {
+    fn guide(self, index: usize) -> Guide {
+        match self {
+            Items::Any => Guide::Any,
+            Items::Each { shape, .. } => Guide::Shape(shape),
+            Items::Fields(fields) => fields
+                .get(index)
+                .map_or(Guide::Any, |field| Guide::Shape(field.shape())),
+        }
+    }
+
+    fn len(self) -> Option<usize> {
+        match self {
+            Items::Any => None,
+            Items::Each { len, .. } => len,
+            Items::Fields(fields) => Some(fields.len()),
+        }
+    }
+}
+
+/// A container being transcoded
+enum Frame {
+    List {
+        items: Items,
+        index: usize,
+    },
+    Map {
+        value: Guide,
+        in_value: bool,
+    },
+    Struct {
+        shape: &'static Shape,
+        ty: StructType,
+        seen: Vec<bool>,
+        in_field: bool,
+    },
+    /// The `{ "Variant": … }` around the fields of an enum variant
+    Variant,
+}
+
+struct Transcoder<'input, 's, F, S> {
+    reader: EventReader<'input, F>,
+    serializer: &'s mut S,
+    stack: Vec<Frame>,
+}
+
+impl<'input, F, S> Transcoder<'input, '_, F, S>
+where
+    F: Format,
+    S: Serializer,
+{
+    /// Transcodes the rest of every open container
+    fn run(&mut self) -> Result<(), Error<'input, S>> {
+        while let Some(frame) = self.stack.last_mut() {
+            match frame {
+                Frame::List { items, index } => {
+                    let guide = items.guide(*index);
+                    *index += 1;
+                    let event = self.reader.next(Expectation::ListItemOrListClose)?;
+                    if let Outcome::ListEnded = event.node {
+                        self.stack.pop();
+                        emit!(self.end_array());
+                    } else {
+                        self.value(event, guide)?;
+                    }
+                }
+                Frame::Map { value, in_value } => {
+                    let value = *value;
+                    if core::mem::replace(in_value, false) {
+                        emit!(self.end_map_value());
+                    }
+                    let event = self.reader.next(Expectation::ObjectKeyOrObjectClose)?;
+                    match &event.node {
+                        Outcome::ObjectEnded => {
+                            self.stack.pop();
+                            emit!(self.end_map());
+                        }
+                        Outcome::Scalar(Scalar::String(key)) => {
+                            *in_value = true;
+                            emit!(self.begin_map_key());
+                            emit!(self.serialize_str(key));
+                            emit!(self.end_map_key());
+                            emit!(self.begin_map_value());
+                            let event = self.reader.next(Expectation::ObjectVal)?;
+                            self.value(event, value)?;
+                        }
+                        _ => return Err(self.mismatch(&event, "an object key")),
+                    }
+                }
+                Frame::Struct {
+                    shape,
+                    ty,
+                    seen,
+                    in_field,
+                } => {
+                    let (shape, fields) = (*shape, ty.fields);
+                    if core::mem::replace(in_field, false) {
+                        emit!(self.end_field());
+                    }
+                    let event = self.reader.next(Expectation::ObjectKeyOrObjectClose)?;
+                    match &event.node {
+                        Outcome::ObjectEnded => {
+                            let missing =
+                                fields.iter().zip(seen.iter()).find_map(|(field, seen)| {
+                                    let required = !field.flags.contains(FieldFlags::DEFAULT)
+                                        && !shape.has_default_attr();
+                                    (required && !*seen).then_some(field.name)
+                                });
+                            if let Some(name) = missing {
+                                let kind = DeserErrorKind::MissingField(name);
+                                return Err(self.error(kind, event.span));
+                            }
+                            self.stack.pop();
+                            emit!(self.end_object());
+                        }
+                        Outcome::Scalar(Scalar::String(key)) => {
+                            let key: &str = key;
+                            match ty.field_index(key) {
+                                Some(index) => {
+                                    seen[index] = true;
+                                    *in_field = true;
+                                    let field = &fields[index];
+                                    emit!(self.serialize_field_name(field.name));
+                                    let event = self.reader.next(Expectation::ObjectVal)?;
+                                    self.value(event, Guide::Shape(field.shape()))?;
+                                }
+                                None if shape.has_deny_unknown_fields_attr() => {
+                                    let kind = DeserErrorKind::UnknownField {
+                                        field_name: key.to_string(),
+                                        shape,
+                                    };
+                                    return Err(self.error(kind, event.span));
+                                }
+                                None => {
+                                    trace!("Skipping unknown field {key} of {shape}");
+                                    self.reader.skip()?;
+                                }
+                            }
+                        }
+                        _ => return Err(self.mismatch(&event, "a field name")),
+                    }
+                }
+                Frame::Variant => {
+                    let event = self.reader.next(Expectation::ObjectKeyOrObjectClose)?;
+                    if let Outcome::ObjectEnded = event.node {
+                        self.stack.pop();
+                        emit!(self.end_object());
+                    } else {
+                        return Err(self.mismatch(&event, "the end of the variant"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Transcodes a value whose first event was just read
+    ///
+    /// Scalars are written right away, while containers are started and left on the stack.
+    fn value(
+        &mut self,
+        event: Spanned<Outcome<'input>>,
+        guide: Guide,
+    ) -> Result<(), Error<'input, S>> {
+        let mut shape = match guide {
+            Guide::Any => return self.any(event),
+            Guide::Shape(shape) => shape,
+        };
+
+        // Options, smart pointers and transparent wrappers are seen through
+        loop {
+            shape = match shape.def {
+                Def::Option(def) => {
+                    if let Outcome::Scalar(Scalar::Null) = event.node {
+                        emit!(self.serialize_none());
+                        return Ok(());
+                    }
+                    def.t
+                }
+                Def::SmartPointer(def) => match def.pointee() {
+                    Some(pointee) => pointee,
+                    None => break,
+                },
+                Def::Scalar(_) if ScalarType::try_from_shape(shape).is_some() => break,
+                _ => match shape.inner {
+                    Some(inner) => inner.get(),
+                    None => break,
+                },
+            };
+        }
+        trace!("Transcoding {} as {shape}", event.node);
+
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            return self.scalar(event, scalar);
+        }
+        match shape.def {
+            Def::List(def) => {
+                let items = Items::Each {
+                    shape: def.t(),
+                    len: None,
+                };
+                return self.list(event, items);
+            }
+            Def::Slice(def) => {
+                let items = Items::Each {
+                    shape: def.t,
+                    len: None,
+                };
+                return self.list(event, items);
+            }
+            Def::Array(def) => {
+                let items = Items::Each {
+                    shape: def.t,
+                    len: Some(def.n),
+                };
+                return self.list(event, items);
+            }
+            Def::Map(def) => return self.map(event, Guide::Shape(def.v())),
+            _ => {}
+        }
+        match shape.ty {
+            Type::User(UserType::Struct(ty)) => self.struct_like(event, shape, ty),
+            Type::User(UserType::Enum(ty)) => {
+                // Unit variants are their name, the others `{ "Variant": fields }`
+                let (name, span) = match &event.node {
+                    Outcome::Scalar(Scalar::String(name)) => (name.clone(), event.span),
+                    Outcome::ObjectStarted => {
+                        let key = self.reader.next(Expectation::ObjectKeyOrObjectClose)?;
+                        match key.node {
+                            Outcome::Scalar(Scalar::String(name)) => (name, key.span),
+                            _ => return Err(self.mismatch(&key, "a variant name")),
+                        }
+                    }
+                    _ => return Err(self.mismatch(&event, "an enum variant")),
+                };
+                let Some((index, variant)) = ty
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, variant)| variant.name == name)
+                else {
+                    let kind = DeserErrorKind::NoSuchVariant {
+                        name: name.into_owned(),
+                        enum_shape: shape,
+                    };
+                    return Err(self.error(kind, span));
+                };
+
+                let data = variant.data;
+                if let Outcome::Scalar(_) = event.node {
+                    if !data.fields.is_empty() {
+                        return Err(self.mismatch(&event, "an object holding the variant"));
+                    }
+                    emit!(self.serialize_unit_variant(index, variant.name));
+                    return Ok(());
+                }
+
+                emit!(self.start_object(Some(1)));
+                emit!(self.serialize_field_name(variant.name));
+                self.stack.push(Frame::Variant);
+                let event = self.reader.next(Expectation::ObjectVal)?;
+                match data.fields {
+                    [field] if data.kind != StructKind::Struct => {
+                        self.value(event, Guide::Shape(field.shape()))
+                    }
+                    _ => self.struct_like(event, shape, data),
+                }
+            }
+            Type::Sequence(SequenceType::Tuple(ty)) => self.list(event, Items::Fields(ty.fields)),
+            // Opaque values and the scalars `facet-reflect` doesn't know are passed through
+            _ => self.any(event),
+        }
+    }
+
+    /// Passes a value through as-is
+    fn any(&mut self, event: Spanned<Outcome<'input>>) -> Result<(), Error<'input, S>> {
+        match &event.node {
+            Outcome::Scalar(scalar) => {
+                match scalar {
+                    Scalar::String(s) => emit!(self.serialize_str(s)),
+                    Scalar::U64(n) => emit!(self.serialize_u64(*n)),
+                    Scalar::I64(n) => emit!(self.serialize_i64(*n)),
+                    Scalar::U128(n) => emit!(self.serialize_u128(*n)),
+                    Scalar::I128(n) => emit!(self.serialize_i128(*n)),
+                    Scalar::F64(n) => emit!(self.serialize_f64(*n)),
+                    Scalar::Bool(b) => emit!(self.serialize_bool(*b)),
+                    Scalar::Null => emit!(self.serialize_none()),
+                }
+                Ok(())
+            }
+            Outcome::ListStarted => self.list(event, Items::Any),
+            Outcome::ObjectStarted => self.map(event, Guide::Any),
+            Outcome::ListEnded | Outcome::ObjectEnded => Err(self.mismatch(&event, "a value")),
+        }
+    }
+
+    fn scalar(
+        &mut self,
+        event: Spanned<Outcome<'input>>,
+        scalar_type: ScalarType,
+    ) -> Result<(), Error<'input, S>> {
+        let Outcome::Scalar(scalar) = &event.node else {
+            return Err(self.mismatch(&event, "a scalar"));
+        };
+
+        macro_rules! int {
+            ($ty:ty, $method:ident) => {{
+                let n = match *scalar {
+                    Scalar::U64(n) => <$ty>::try_from(n).map_err(|_| n as f64),
+                    Scalar::I64(n) => <$ty>::try_from(n).map_err(|_| n as f64),
+                    Scalar::U128(n) => <$ty>::try_from(n).map_err(|_| n as f64),
+                    Scalar::I128(n) => <$ty>::try_from(n).map_err(|_| n as f64),
+                    _ => return Err(self.mismatch(&event, "an integer")),
+                };
+                match n {
+                    Ok(n) => emit!(self.$method(n)),
+                    Err(n) => {
+                        let kind = DeserErrorKind::NumberOutOfRange(n);
+                        return Err(self.error(kind, event.span));
+                    }
+                }
+            }};
+        }
+        macro_rules! float {
+            ($ty:ty, $method:ident) => {{
+                let n = match *scalar {
+                    Scalar::F64(n) => n,
+                    Scalar::U64(n) => n as f64,
+                    Scalar::I64(n) => n as f64,
+                    Scalar::U128(n) => n as f64,
+                    Scalar::I128(n) => n as f64,
+                    _ => return Err(self.mismatch(&event, "a number")),
+                };
+                emit!(self.$method(n as $ty))
+            }};
+        }
+
+        match scalar_type {
+            ScalarType::Unit => match scalar {
+                Scalar::Null => emit!(self.serialize_unit()),
+                _ => return Err(self.mismatch(&event, "null")),
+            },
+            ScalarType::Bool => match scalar {
+                Scalar::Bool(b) => emit!(self.serialize_bool(*b)),
+                _ => return Err(self.mismatch(&event, "a bool")),
+            },
+            ScalarType::Char => {
+                let mut chars = match scalar {
+                    Scalar::String(s) => s.chars(),
+                    _ => return Err(self.mismatch(&event, "a character")),
+                };
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => emit!(self.serialize_char(c)),
+                    _ => return Err(self.mismatch(&event, "a single character")),
+                }
+            }
+            ScalarType::Str | ScalarType::String | ScalarType::CowStr => match scalar {
+                Scalar::String(s) => emit!(self.serialize_str(s)),
+                _ => return Err(self.mismatch(&event, "a string")),
+            },
+            ScalarType::F32 => float!(f32, serialize_f32),
+            ScalarType::F64 => float!(f64, serialize_f64),
+            ScalarType::U8 => int!(u8, serialize_u8),
+            ScalarType::U16 => int!(u16, serialize_u16),
+            ScalarType::U32 => int!(u32, serialize_u32),
+            ScalarType::U64 => int!(u64, serialize_u64),
+            ScalarType::U128 => int!(u128, serialize_u128),
+            ScalarType::USize => int!(usize, serialize_usize),
+            ScalarType::I8 => int!(i8, serialize_i8),
+            ScalarType::I16 => int!(i16, serialize_i16),
+            ScalarType::I32 => int!(i32, serialize_i32),
+            ScalarType::I64 => int!(i64, serialize_i64),
+            ScalarType::I128 => int!(i128, serialize_i128),
+            ScalarType::ISize => int!(isize, serialize_isize),
+            // Addresses, paths, UUIDs… are whatever their format makes of them
+            _ => return self.any(event),
+        }
+        Ok(())
+    }
+
+    fn list(
+        &mut self,
+        event: Spanned<Outcome<'input>>,
+        items: Items,
+    ) -> Result<(), Error<'input, S>> {
+        let Outcome::ListStarted = event.node else {
+            return Err(self.mismatch(&event, "a list"));
+        };
+        let len = self.count(true, None)?;
+        if items.len().is_some_and(|expected| expected != len) {
+            let wanted = match items {
+                Items::Fields(_) => "as many items as the tuple has",
+                _ => "as many items as the array has",
+            };
+            return Err(self.mismatch(&event, wanted));
+        }
+        emit!(self.start_array(Some(len)));
+        self.stack.push(Frame::List { items, index: 0 });
+        Ok(())
+    }
+
+    fn map(
+        &mut self,
+        event: Spanned<Outcome<'input>>,
+        value: Guide,
+    ) -> Result<(), Error<'input, S>> {
+        let Outcome::ObjectStarted = event.node else {
+            return Err(self.mismatch(&event, "an object"));
+        };
+        let len = self.count(false, None)?;
+        emit!(self.start_map(Some(len)));
+        self.stack.push(Frame::Map {
+            value,
+            in_value: false,
+        });
+        Ok(())
+    }
+
+    /// Transcodes a struct, or the fields of an enum variant
+    fn struct_like(
+        &mut self,
+        event: Spanned<Outcome<'input>>,
+        shape: &'static Shape,
+        ty: StructType,
+    ) -> Result<(), Error<'input, S>> {
+        let fields = ty.fields;
+        match ty.kind {
+            StructKind::Unit => match event.node {
+                Outcome::Scalar(Scalar::Null) => {
+                    emit!(self.serialize_unit());
+                    Ok(())
+                }
+                _ => Err(self.mismatch(&event, "null")),
+            },
+            StructKind::Struct => {
+                let Outcome::ObjectStarted = event.node else {
+                    return Err(self.mismatch(&event, "an object"));
+                };
+                if fields
+                    .iter()
+                    .any(|field| field.flags.contains(FieldFlags::FLATTEN))
+                {
+                    let kind = DeserErrorKind::Unimplemented("transcoding flattened fields");
+                    return Err(self.error(kind, event.span));
+                }
+                let len = self.count(false, Some(&ty))?;
+                emit!(self.start_object(Some(len)));
+                self.stack.push(Frame::Struct {
+                    shape,
+                    ty,
+                    seen: vec![false; fields.len()],
+                    in_field: false,
+                });
+                Ok(())
+            }
+            _ => self.list(event, Items::Fields(fields)),
+        }
+    }
+
+    /// Counts the items of the container that was just started, then goes back to them
+    ///
+    /// Only the keys that are fields of `ty` are counted, if given.
+    fn count(&mut self, list: bool, ty: Option<&StructType>) -> Result<usize, Error<'input, S>> {
+        let start = self.reader.offset();
+        let mut count = 0;
+        loop {
+            if list {
+                let event = self.reader.next(Expectation::ListItemOrListClose)?;
+                match event.node {
+                    Outcome::ListEnded => break,
+                    Outcome::ListStarted | Outcome::ObjectStarted => {
+                        self.reader.rewind(event.span.start());
+                        self.reader.skip()?;
+                    }
+                    Outcome::Scalar(_) => {}
+                    Outcome::ObjectEnded => return Err(self.mismatch(&event, "a list item")),
+                }
+                count += 1;
+            } else {
+                let event = self.reader.next(Expectation::ObjectKeyOrObjectClose)?;
+                match &event.node {
+                    Outcome::ObjectEnded => break,
+                    Outcome::Scalar(Scalar::String(key)) => {
+                        let key: &str = key;
+                        if ty.is_none_or(|ty| ty.field_index(key).is_some()) {
+                            count += 1;
+                        }
+                        self.reader.skip()?;
+                    }
+                    _ => return Err(self.mismatch(&event, "an object key")),
+                }
+            }
+        }
+        self.reader.rewind(start);
+        Ok(count)
+    }
+
+    fn mismatch(&self, event: &Spanned<Outcome<'input>>, wanted: &'static str) -> Error<'input, S> {
+        let got = event.node.clone().into_owned();
+        self.error(
+            DeserErrorKind::UnexpectedOutcome { got, wanted },
+            event.span,
+        )
+    }
+
+    fn error(&self, kind: DeserErrorKind, span: Span) -> Error<'input, S> {
+        TranscodeError::Read(self.reader.error_at(kind, span))
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use facet::{Facet, Shape};
+use facet_deserialize::{DeserErrorKind, EventReader};
+use facet_json::{Json, JsonSerializer};
+use facet_msgpack::MessagePackSerializer;
+use facet_transcode::{TranscodeError, transcode};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Upstream {
+    host: String,
+    port: u16,
+    healthy: bool,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Proxy {
+    name: String,
+    upstream: Upstream,
+    retries: u8,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    limits: HashMap<String, u32>,
+    backup: Option<Box<Server>>,
+    mode: Mode,
+    pair: (i8, char),
+    #[facet(default)]
+    weight: f32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Mode {
+    Off,
+    Fixed(u32),
+    Range { low: i64, high: i64 },
+}
+
+#[derive(Facet, Debug)]
+#[facet(deny_unknown_fields)]
+struct Strict {
+    port: u16,
+}
+
+fn to_json(json: &str, shape: Option<&'static Shape>) -> Result<String, DeserErrorKind> {
+    let mut out = Vec::new();
+    let reader = EventReader::new(json.as_bytes(), Json);
+    match transcode(reader, &mut JsonSerializer::new(&mut out), shape) {
+        Ok(()) => Ok(String::from_utf8(out).unwrap()),
+        Err(TranscodeError::Read(err)) => Err(err.kind),
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn json_to_msgpack() {
+    facet_testhelpers::setup();
+
+    let json = br#"{
+        "name": "edge",
+        "color": { "unknown": [1, 2, { "nested": true }] },
+        "upstream": { "port": 8080, "host": "10.0.0.1", "healthy": true },
+        "retries": 3
+    }"#;
+    let mut msgpack = Vec::new();
+    let reader = EventReader::new(json, Json);
+    let mut serializer = MessagePackSerializer::new(&mut msgpack);
+    transcode(reader, &mut serializer, Some(Proxy::SHAPE)).unwrap();
+
+    let proxy: Proxy = facet_msgpack::from_slice(&msgpack).unwrap();
+    assert_eq!(
+        proxy,
+        Proxy {
+            name: "edge".into(),
+            upstream: Upstream {
+                host: "10.0.0.1".into(),
+                port: 8080,
+                healthy: true,
+            },
+            retries: 3,
+        }
+    );
+}
+
+#[test]
+fn json_to_json_with_a_shape() {
+    facet_testhelpers::setup();
+
+    let json = r#"{
+        "name": "primary",
+        "port": 8080,
+        "tags": ["edge", "eu"],
+        "limits": { "read": 100 },
+        "color": "blue",
+        "backup": {
+            "name": "secondary",
+            "port": 8081,
+            "tags": [],
+            "limits": {},
+            "backup": null,
+            "mode": { "Fixed": 3 },
+            "pair": [0, "y"],
+            "weight": 0.5
+        },
+        "mode": { "Range": { "low": -1, "high": 5 } },
+        "pair": [-3, "x"]
+    }"#;
+    let out = to_json(json, Some(Server::SHAPE)).unwrap();
+    assert!(!out.contains("color"), "{out}");
+
+    let server: Server = facet_json::from_str(&out).unwrap();
+    assert_eq!(
+        server,
+        Server {
+            name: "primary".into(),
+            port: 8080,
+            tags: vec!["edge".into(), "eu".into()],
+            limits: HashMap::from([("read".into(), 100)]),
+            backup: Some(Box::new(Server {
+                name: "secondary".into(),
+                port: 8081,
+                tags: vec![],
+                limits: HashMap::new(),
+                backup: None,
+                mode: Mode::Fixed(3),
+                pair: (0, 'y'),
+                weight: 0.5,
+            })),
+            mode: Mode::Range { low: -1, high: 5 },
+            pair: (-3, 'x'),
+            weight: 0.0,
+        }
+    );
+}
+
+#[test]
+fn without_a_shape() {
+    facet_testhelpers::setup();
+
+    let json = r#"{"a":[1,-2,3.5,"four",null],"b":{"c":true}}"#;
+    assert_eq!(to_json(json, None).unwrap(), json);
+}
+
+#[test]
+fn checked_against_the_shape() {
+    facet_testhelpers::setup();
+
+    let strict = Some(Strict::SHAPE);
+    let err = to_json(r#"{ "port": 70000 }"#, strict).unwrap_err();
+    assert!(matches!(err, DeserErrorKind::NumberOutOfRange(n) if n == 70000.0));
+
+    let err = to_json(r#"{ "port": 80, "host": "a" }"#, strict).unwrap_err();
+    assert!(
+        matches!(&err, DeserErrorKind::UnknownField { field_name, .. } if field_name == "host")
+    );
+
+    let err = to_json(r#"{}"#, strict).unwrap_err();
+    assert!(matches!(err, DeserErrorKind::MissingField("port")));
+
+    let off = to_json(r#""Off""#, Some(Mode::SHAPE)).unwrap();
+    assert_eq!(facet_json::from_str::<Mode>(&off).unwrap(), Mode::Off);
+
+    let err = to_json(r#""Sideways""#, Some(Mode::SHAPE)).unwrap_err();
+    assert!(matches!(err, DeserErrorKind::NoSuchVariant { .. }));
+
+    let err = to_json(r#"{ "Fixed": "three" }"#, Some(Mode::SHAPE)).unwrap_err();
+    assert!(matches!(err, DeserErrorKind::UnexpectedOutcome { .. }));
+
+    let err = to_json(r#"[1, "x", 3]"#, Some(<(i8, char)>::SHAPE)).unwrap_err();
+    assert!(matches!(err, DeserErrorKind::UnexpectedOutcome { .. }));
+}
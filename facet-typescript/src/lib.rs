@@ -0,0 +1,280 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::fmt::Write;
+
+use facet_core::{
+    Def, Facet, Field, FieldFlags, PrimitiveType, ScalarAffinity, SequenceType, Shape, StructKind,
+    StructType, Type, UserType,
+};
+
+/// Generates the TypeScript definitions for `T` and every type it refers to
+pub fn to_string<'a, T: Facet<'a>>() -> String {
+    Generator::new().add::<T>().finish()
+}
+
+/// Collects TypeScript definitions for a set of types
+///
+/// Structs become interfaces, enums become unions of their variants, and every named type a
+/// definition refers to gets a definition of its own, once.
+#[derive(Debug, Default)]
+pub struct Generator {
+    declared: Vec<&'static Shape>,
+    declarations: Vec<String>,
+}
+
+impl Generator {
+    /// Creates a generator with no definitions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the definition of `T`, and of every type it refers to
+    pub fn add<'a, T: Facet<'a>>(self) -> Self {
+        self.add_shape(T::SHAPE)
+    }
+
+    /// Adds the definition of `shape`, and of every type it refers to
+    pub fn add_shape(mut self, shape: &'static Shape) -> Self {
+        self.reference(shape);
+        self
+    }
+
+    /// Returns the definitions, in the order types were first encountered
+    pub fn finish(self) -> String {
+        self.declarations.join("\n")
+    }
+
+    /// Returns the TypeScript type for values of `shape`, declaring it if it's a named type
+    fn reference(&mut self, shape: &'static Shape) -> String {
+        match shape.def {
+            Def::Option(od) => return format!("{} | null", self.reference(od.t())),
+            Def::List(ld) => return array(self.reference(ld.t())),
+            Def::Slice(sd) => return array(self.reference(sd.t)),
+            Def::Array(ad) => return array(self.reference(ad.t())),
+            // JSON object keys are always strings
+            Def::Map(md) => return format!("Record<string, {}>", self.reference(md.v())),
+            Def::SmartPointer(sp) => {
+                return match sp.pointee {
+                    Some(pointee) => self.reference(pointee()),
+                    None => "unknown".to_string(),
+                };
+            }
+            Def::Scalar(sd) => return scalar(sd.affinity).to_string(),
+            _ => {}
+        }
+
+        // Transparent wrappers are serialized as what they wrap
+        if let Some(inner) = shape.inner {
+            return self.reference(inner.get());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(st)) if st.kind == StructKind::Unit => "null".to_string(),
+            Type::User(UserType::Struct(_)) | Type::User(UserType::Enum(_)) => self.named(shape),
+            Type::Sequence(SequenceType::Tuple(tt)) => self.tuple(tt.fields),
+            Type::Primitive(PrimitiveType::Boolean) => "boolean".to_string(),
+            Type::Primitive(PrimitiveType::Numeric(_)) => "number".to_string(),
+            Type::Primitive(PrimitiveType::Textual(_)) => "string".to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// Declares a struct or enum, if it isn't already, and returns its name
+    fn named(&mut self, shape: &'static Shape) -> String {
+        let name = type_name(shape);
+        if self.declared.contains(&shape) {
+            return name;
+        }
+
+        // Reserve the slot first, so recursive types refer to themselves instead of looping
+        self.declared.push(shape);
+        let slot = self.declarations.len();
+        self.declarations.push(String::new());
+
+        let mut out = String::new();
+        write_doc(&mut out, shape.doc, "");
+        match shape.ty {
+            Type::User(UserType::Struct(st)) => match st.kind {
+                StructKind::Struct => self.write_struct(&mut out, &name, st),
+                _ => {
+                    let tuple = self.tuple(st.fields);
+                    writeln!(out, "export type {name} = {tuple};").unwrap();
+                }
+            },
+            Type::User(UserType::Enum(et)) => {
+                writeln!(out, "export type {name} =").unwrap();
+                for (i, variant) in et.variants.iter().enumerate() {
+                    write_doc(&mut out, variant.doc, "  ");
+                    let tag = property(variant.name);
+                    let data = &variant.data;
+                    write!(out, "  | ").unwrap();
+                    match data.kind {
+                        _ if data.fields.is_empty() => write!(out, "{}", quote(variant.name)),
+                        StructKind::Struct => {
+                            let fields = self.object(data.fields);
+                            write!(out, "{{ {tag}: {fields} }}")
+                        }
+                        // Newtype variants hold their value directly
+                        _ if data.fields.len() == 1 => {
+                            let value = self.reference(data.fields[0].shape());
+                            write!(out, "{{ {tag}: {value} }}")
+                        }
+                        _ => {
+                            let tuple = self.tuple(data.fields);
+                            write!(out, "{{ {tag}: {tuple} }}")
+                        }
+                    }
+                    .unwrap();
+                    out.push_str(if i + 1 == et.variants.len() {
+                        ";\n"
+                    } else {
+                        "\n"
+                    });
+                }
+                if et.variants.is_empty() {
+                    out.push_str("  never;\n");
+                }
+            }
+            _ => unreachable!("only structs and enums are declared"),
+        }
+
+        self.declarations[slot] = out;
+        name
+    }
+
+    /// Writes a struct as an interface — or, if it has flattened fields, as the intersection
+    /// of its own fields with theirs.
+    fn write_struct(&mut self, out: &mut String, name: &str, st: StructType) {
+        let mut flattened = Vec::new();
+        let mut body = String::new();
+        for field in serialized(st.fields) {
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                flattened.push(self.reference(field.shape()));
+                continue;
+            }
+            write_doc(&mut body, field.doc, "  ");
+            let (optional, ty) = self.field(field);
+            writeln!(body, "  {}{optional}: {ty};", property(field.name)).unwrap();
+        }
+
+        if flattened.is_empty() {
+            writeln!(out, "export interface {name} {{\n{body}}}").unwrap();
+        } else {
+            let flattened: String = flattened.iter().map(|ty| format!(" & ({ty})")).collect();
+            writeln!(out, "export type {name} = {{\n{body}}}{flattened};").unwrap();
+        }
+    }
+
+    /// Returns the fields of a struct variant, as an inline object type
+    fn object(&mut self, fields: &'static [Field]) -> String {
+        let fields: Vec<_> = serialized(fields)
+            .map(|field| {
+                let (optional, ty) = self.field(field);
+                format!("{}{optional}: {ty}", property(field.name))
+            })
+            .collect();
+        format!("{{ {} }}", fields.join("; "))
+    }
+
+    /// Returns the `?` marker for optional fields, and the field's type
+    fn field(&mut self, field: &Field) -> (&'static str, String) {
+        let optional = match field.shape().def {
+            Def::Option(_) => "?",
+            _ => "",
+        };
+        (optional, self.reference(field.shape()))
+    }
+
+    fn tuple(&mut self, fields: &'static [Field]) -> String {
+        let items: Vec<_> = fields.iter().map(|f| self.reference(f.shape())).collect();
+        format!("[{}]", items.join(", "))
+    }
+}
+
+/// Fields that end up in the serialized value
+fn serialized(fields: &'static [Field]) -> impl Iterator<Item = &'static Field> {
+    fields
+        .iter()
+        .filter(|field| !field.flags.contains(FieldFlags::SKIP_SERIALIZING))
+}
+
+fn scalar(affinity: ScalarAffinity) -> &'static str {
+    match affinity {
+        ScalarAffinity::Number(_) => "number",
+        ScalarAffinity::Boolean(_) => "boolean",
+        ScalarAffinity::Empty(_) => "null",
+        // Everything else that's text on the wire
+        ScalarAffinity::String(_)
+        | ScalarAffinity::Char(_)
+        | ScalarAffinity::Path(_)
+        | ScalarAffinity::UUID(_)
+        | ScalarAffinity::ULID(_)
+        | ScalarAffinity::Time(_)
+        | ScalarAffinity::IpAddr(_)
+        | ScalarAffinity::SocketAddr(_) => "string",
+        _ => "unknown",
+    }
+}
+
+fn array(item: String) -> String {
+    if item.contains(' ') && !item.starts_with(['{', '[']) {
+        format!("({item})[]")
+    } else {
+        format!("{item}[]")
+    }
+}
+
+/// `Page<User>` is declared as `PageUser`
+fn type_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    let mut out = String::with_capacity(name.len());
+    for word in name.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        }
+    }
+    out
+}
+
+/// Property names that aren't identifiers are quoted
+fn property(name: &str) -> String {
+    let is_identifier = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_doc(out: &mut String, doc: &[&str], indent: &str) {
+    let lines: Vec<_> = doc
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line).replace("*/", "*\\/"))
+        .collect();
+    match lines.as_slice() {
+        [] => {}
+        [line] => writeln!(out, "{indent}/** {line} */").unwrap(),
+        lines => {
+            writeln!(out, "{indent}/**").unwrap();
+            for line in lines {
+                match line.is_empty() {
+                    true => writeln!(out, "{indent} *").unwrap(),
+                    false => writeln!(out, "{indent} * {line}").unwrap(),
+                }
+            }
+            writeln!(out, "{indent} */").unwrap();
+        }
+    }
+}
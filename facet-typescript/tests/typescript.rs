@@ -0,0 +1,68 @@
+use facet::Facet;
+use facet_typescript::{Generator, to_string};
+
+/// A registered user
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct User {
+    user_id: u64,
+    display_name: String,
+    /// Shown on the profile page
+    avatar_url: Option<String>,
+    roles: Vec<Role>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Role {
+    Admin,
+    Member { team: String },
+    Custom(String),
+}
+
+#[test]
+fn interfaces_and_unions() {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        to_string::<User>(),
+        r#"/** A registered user */
+export interface User {
+  userId: number;
+  displayName: string;
+  /** Shown on the profile page */
+  avatarUrl?: string | null;
+  roles: Role[];
+}
+
+export type Role =
+  | "Admin"
+  | { Member: { team: string } }
+  | { Custom: string };
+"#
+    );
+}
+
+#[derive(Facet)]
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+    span: (u32, u32),
+}
+
+#[test]
+fn types_are_declared_once() {
+    facet_testhelpers::setup();
+
+    let ts = Generator::new().add::<Tree>().add::<Tree>().finish();
+    assert_eq!(
+        ts,
+        "export interface Tree {
+  label: string;
+  children: Tree[];
+  span: [number, number];
+}
+"
+    );
+}
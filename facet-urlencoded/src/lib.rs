@@ -2,10 +2,13 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
-use facet_core::{Def, Facet, Type, UserType};
-use facet_reflect::{HeapValue, Wip};
+use facet_core::{Characteristic, Def, Facet, Field, FieldFlags, Shape, Type, UserType};
+use facet_reflect::{HeapValue, ScalarType, Wip};
 use log::*;
 
+mod serialize;
+pub use serialize::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -183,7 +186,7 @@ fn deserialize_value<'mem>(
 ) -> Result<HeapValue<'mem>, UrlEncodedError> {
     let shape = wip.shape();
     match shape.ty {
-        Type::User(UserType::Struct(_)) => {
+        Type::User(UserType::Struct(sd)) => {
             trace!("Deserializing struct");
 
             let mut wip = wip;
@@ -194,6 +197,8 @@ fn deserialize_value<'mem>(
                     let value = values.get(key).unwrap(); // Safe because we're iterating over keys
                     let field = wip.field(index)?;
                     wip = deserialize_scalar_field(key, value, field)?;
+                } else if shape.has_deny_unknown_fields_attr() {
+                    return Err(UrlEncodedError::UnknownField(key.clone()));
                 } else {
                     trace!("Unknown field: {}", key);
                 }
@@ -205,11 +210,14 @@ fn deserialize_value<'mem>(
                     let nested_values = values.nested.get(key).unwrap(); // Safe because we're iterating over keys
                     let field = wip.field(index)?;
                     wip = deserialize_nested_field(key, nested_values, field)?;
+                } else if shape.has_deny_unknown_fields_attr() {
+                    return Err(UrlEncodedError::UnknownField(key.clone()));
                 } else {
                     trace!("Unknown nested field: {}", key);
                 }
             }
 
+            wip = fill_missing_fields(wip, sd.fields)?;
             trace!("Finished deserializing struct");
             Ok(wip.build()?)
         }
@@ -223,29 +231,37 @@ fn deserialize_value<'mem>(
 }
 
 /// Helper function to deserialize a scalar field
+///
+/// Strings are taken as-is, optional fields hold `Some` of the value, and anything else is
+/// parsed from its text.
 fn deserialize_scalar_field<'mem>(
     key: &str,
     value: &str,
     wip: Wip<'mem>,
 ) -> Result<Wip<'mem>, UrlEncodedError> {
-    match wip.shape().def {
-        Def::Scalar(_sd) => {
-            let wip = if wip.shape().is_type::<String>() {
-                let s = value.to_string();
-                wip.put(s)?
-            } else if wip.shape().is_type::<u64>() {
-                match value.parse::<u64>() {
-                    Ok(num) => wip.put(num)?,
-                    Err(_) => {
+    let shape = wip.shape();
+    match shape.def {
+        Def::Option(_) => {
+            let wip = deserialize_scalar_field(key, value, wip.push_some()?)?;
+            Ok(wip.pop()?)
+        }
+        Def::Scalar(_) => {
+            let wip = if shape.is_type::<String>() {
+                wip.put(value.to_string())?
+            } else {
+                match wip.parse(value) {
+                    Ok(wip) => wip,
+                    Err(_) if is_number(shape) => {
                         return Err(UrlEncodedError::InvalidNumber(
                             key.to_string(),
                             value.to_string(),
                         ));
                     }
+                    Err(err) => {
+                        warn!("Couldn't parse field '{}' as {}", key, shape);
+                        return Err(err.into());
+                    }
                 }
-            } else {
-                warn!("Unsupported scalar type: {}", wip.shape());
-                return Err(UrlEncodedError::UnsupportedType(format!("{}", wip.shape())));
             };
             Ok(wip.pop()?)
         }
@@ -259,6 +275,59 @@ fn deserialize_scalar_field<'mem>(
     }
 }
 
+fn is_number(shape: &'static Shape) -> bool {
+    matches!(
+        ScalarType::try_from_shape(shape),
+        Some(
+            ScalarType::F32
+                | ScalarType::F64
+                | ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize
+        )
+    )
+}
+
+/// Puts defaults in the fields that weren't in the form: `None` for options, and the
+/// default for fields marked `#[facet(default)]`
+fn fill_missing_fields<'mem>(
+    mut wip: Wip<'mem>,
+    fields: &'static [Field],
+) -> Result<Wip<'mem>, UrlEncodedError> {
+    for (index, field) in fields.iter().enumerate() {
+        if wip.is_field_set(index)? {
+            continue;
+        }
+        if field.flags.contains(FieldFlags::DEFAULT) {
+            wip = wip.field(index)?;
+            wip = match field.vtable.default_fn {
+                Some(default_in_place_fn) => wip.put_from_fn(default_in_place_fn)?,
+                None if field.shape().is(Characteristic::Default) => wip.put_default()?,
+                None => {
+                    return Err(UrlEncodedError::UnsupportedShape(format!(
+                        "Field '{}' has a default attribute but its type has no default",
+                        field.name
+                    )));
+                }
+            };
+            wip = wip.pop()?;
+        } else if let Def::Option(_) = field.shape().def {
+            wip = wip.field(index)?.put_default()?.pop()?;
+        }
+        // Anything else is reported as uninitialized when building
+    }
+    Ok(wip)
+}
+
 /// Helper function to deserialize a nested field
 fn deserialize_nested_field<'mem>(
     key: &str,
@@ -267,7 +336,7 @@ fn deserialize_nested_field<'mem>(
 ) -> Result<Wip<'mem>, UrlEncodedError> {
     let shape = wip.shape();
     match shape.ty {
-        Type::User(UserType::Struct(_)) => {
+        Type::User(UserType::Struct(sd)) => {
             trace!("Deserializing nested struct field: {}", key);
 
             let mut current_wip = wip;
@@ -290,6 +359,8 @@ fn deserialize_nested_field<'mem>(
                 }
             }
 
+            current_wip = fill_missing_fields(current_wip, sd.fields)?;
+
             // Return to parent level
             Ok(current_wip.pop()?)
         }
@@ -309,6 +380,8 @@ fn deserialize_nested_field<'mem>(
 pub enum UrlEncodedError {
     /// The field value couldn't be parsed as a number.
     InvalidNumber(String, String),
+    /// The struct has no field with this name, and denies unknown fields.
+    UnknownField(String),
     /// The shape is not supported for deserialization.
     UnsupportedShape(String),
     /// The type is not supported for deserialization.
@@ -329,6 +402,9 @@ impl core::fmt::Display for UrlEncodedError {
             UrlEncodedError::InvalidNumber(field, value) => {
                 write!(f, "Invalid number for field '{}': '{}'", field, value)
             }
+            UrlEncodedError::UnknownField(field) => {
+                write!(f, "Unknown field: {}", field)
+            }
             UrlEncodedError::UnsupportedShape(shape) => {
                 write!(f, "Unsupported shape: {}", shape)
             }
@@ -0,0 +1,106 @@
+use facet_core::{Def, Facet};
+use facet_reflect::{HasFields, Peek, PeekStruct};
+use log::*;
+
+use crate::UrlEncodedError;
+
+/// Serializes a value of type `T` that implements `Facet` into a URL encoded form data string.
+///
+/// Nested structs use the same bracket notation [`from_str`](crate::from_str) reads, so a field
+/// `city` of a field `address` is written as `address[city]` (percent-encoded, as brackets
+/// have to be). Fields that are `None` are left out, and other scalars are written with their
+/// `Display` implementation.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_urlencoded::to_string;
+///
+/// #[derive(Facet)]
+/// struct SearchParams {
+///     query: String,
+///     page: u64,
+/// }
+///
+/// let params = SearchParams { query: "rust programming".to_string(), page: 2 };
+/// assert_eq!(to_string(&params).unwrap(), "query=rust+programming&page=2");
+/// ```
+pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> Result<String, UrlEncodedError> {
+    peek_to_string(Peek::new(value))
+}
+
+/// Serializes a `Peek` instance into a URL encoded form data string, like [`to_string`].
+pub fn peek_to_string(peek: Peek<'_, '_>) -> Result<String, UrlEncodedError> {
+    trace!("Starting URL encoded form data serialization");
+
+    let Some(value) = peek
+        .unwrap_wrappers()
+        .ok()
+        .and_then(|peek| peek.into_struct().ok())
+    else {
+        error!("Unsupported root type");
+        return Err(UrlEncodedError::UnsupportedShape(
+            "Unsupported root type".to_string(),
+        ));
+    };
+
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    serialize_fields(&mut serializer, None, value)?;
+    Ok(serializer.finish())
+}
+
+type FormSerializer = form_urlencoded::Serializer<'static, String>;
+
+/// Writes the fields of a struct, under `prefix` for nested structs
+fn serialize_fields(
+    serializer: &mut FormSerializer,
+    prefix: Option<&str>,
+    value: PeekStruct<'_, '_>,
+) -> Result<(), UrlEncodedError> {
+    for (field, value) in value.fields_for_serialize() {
+        let key = match prefix {
+            Some(prefix) => format!("{}[{}]", prefix, field.name),
+            None => field.name.to_string(),
+        };
+        serialize_value(serializer, &key, value)?;
+    }
+    Ok(())
+}
+
+fn serialize_value(
+    serializer: &mut FormSerializer,
+    key: &str,
+    value: Peek<'_, '_>,
+) -> Result<(), UrlEncodedError> {
+    // `None`s leave the key out
+    let Ok(value) = value.unwrap_wrappers() else {
+        return Ok(());
+    };
+
+    if let Def::Scalar(_) = value.shape().def {
+        if let Some(s) = value.as_str() {
+            serializer.append_pair(key, s);
+        } else if value.vtable().display.is_some() {
+            serializer.append_pair(key, &value.to_string());
+        } else {
+            warn!("Unsupported scalar type: {}", value.shape());
+            return Err(UrlEncodedError::UnsupportedType(format!(
+                "{}",
+                value.shape()
+            )));
+        }
+        return Ok(());
+    }
+
+    match value.into_struct() {
+        Ok(nested) => serialize_fields(serializer, Some(key), nested),
+        Err(_) => {
+            error!("Expected scalar or struct field");
+            Err(UrlEncodedError::UnsupportedShape(format!(
+                "Expected scalar or struct for field '{}'",
+                key
+            )))
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::from_str;
+use crate::{from_str, to_string};
 use eyre::Result;
 use facet::Facet;
 
@@ -117,6 +117,26 @@ fn test_unknown_field() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_deny_unknown_fields() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct StrictParams {
+        query: String,
+        page: u64,
+    }
+
+    let Err(err) = from_str::<StrictParams>("query=rust&page=2&unknown=value") else {
+        panic!("expected an error")
+    };
+    assert!(
+        matches!(&err, crate::UrlEncodedError::UnknownField(field) if field == "unknown"),
+        "{err}"
+    );
+}
+
 #[test]
 fn test_invalid_number() -> Result<()> {
     facet_testhelpers::setup();
@@ -246,3 +266,92 @@ fn test_deep_nesting() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_serialize_flat() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let params = SearchParams {
+        query: "rust & facet!".to_string(),
+        page: 2,
+    };
+    assert_eq!(to_string(&params)?, "query=rust+%26+facet%21&page=2");
+
+    Ok(())
+}
+
+#[test]
+fn test_serialize_nested_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let order = OrderForm {
+        product_id: "ABC123".to_string(),
+        quantity: 2,
+        user: User {
+            name: "John Doe".to_string(),
+            age: 30,
+            address: Address {
+                street: "123 Main St".to_string(),
+                city: "Anytown".to_string(),
+                zip: "12345".to_string(),
+            },
+        },
+    };
+
+    let encoded = to_string(&order)?;
+    assert_eq!(
+        encoded,
+        "product_id=ABC123&quantity=2&user%5Bname%5D=John+Doe&user%5Bage%5D=30\
+         &user%5Baddress%5D%5Bstreet%5D=123+Main+St&user%5Baddress%5D%5Bcity%5D=Anytown\
+         &user%5Baddress%5D%5Bzip%5D=12345"
+    );
+    assert_eq!(from_str::<OrderForm>(&encoded)?, order);
+
+    Ok(())
+}
+
+#[test]
+fn test_other_scalars_and_options() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Filters {
+        min_price: f64,
+        offset: i32,
+        in_stock: bool,
+        category: Option<String>,
+        limit: Option<u8>,
+        #[facet(default)]
+        sort: String,
+    }
+
+    let filters: Filters = from_str("min_price=9.5&offset=-10&in_stock=true&limit=20")?;
+    assert_eq!(
+        filters,
+        Filters {
+            min_price: 9.5,
+            offset: -10,
+            in_stock: true,
+            category: None,
+            limit: Some(20),
+            sort: String::new(),
+        }
+    );
+
+    // `None` is left out
+    assert_eq!(
+        to_string(&filters)?,
+        "min_price=9.5&offset=-10&in_stock=true&limit=20&sort="
+    );
+
+    let result = from_str::<Filters>("min_price=9.5&offset=-10&in_stock=true&limit=300");
+    match result {
+        Err(crate::UrlEncodedError::InvalidNumber(field, value)) => {
+            assert_eq!(field, "limit");
+            assert_eq!(value, "300");
+        }
+        other => panic!("Expected InvalidNumber error, got: {:?}", other),
+    }
+
+    Ok(())
+}
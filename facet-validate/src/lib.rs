@@ -0,0 +1,186 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use std::collections::HashMap;
+
+use facet_core::{Facet, Shape};
+use facet_reflect::Peek;
+
+mod rule;
+mod walk;
+
+/// A function backing a `custom = name` rule: returns an error message if the value is invalid
+pub type CustomFn = Box<dyn Fn(Peek<'_, '_>) -> Result<(), String> + Send + Sync>;
+
+/// Validates values against the rules declared in their `#[facet(validate(...))]` attributes
+///
+/// Only needed to register custom rules — otherwise, use [`validate`].
+#[derive(Default)]
+pub struct Validator {
+    custom: HashMap<String, CustomFn>,
+}
+
+impl Validator {
+    /// Creates a validator with no custom rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the function used by `#[facet(validate(custom = name))]`
+    pub fn custom(
+        mut self,
+        name: impl Into<String>,
+        f: impl Fn(Peek<'_, '_>) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.custom.insert(name.into(), Box::new(f));
+        self
+    }
+
+    /// Validates `value`, returning every violation found
+    pub fn validate<'facet, T: Facet<'facet>>(&self, value: &T) -> Result<(), Vec<Violation>> {
+        self.validate_peek(Peek::new(value))
+    }
+
+    /// Validates a reflected value, returning every violation found
+    pub fn validate_peek(&self, peek: Peek<'_, '_>) -> Result<(), Vec<Violation>> {
+        let violations = walk::Walker::new(self).run(peek);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// Validates `value` against the rules declared in its `#[facet(validate(...))]` attributes,
+/// returning every violation found.
+///
+/// Custom rules need a [`Validator`] to be registered on; here, they're reported as
+/// [`ViolationKind::InvalidRule`].
+pub fn validate<'facet, T: Facet<'facet>>(value: &T) -> Result<(), Vec<Violation>> {
+    Validator::new().validate(value)
+}
+
+/// A rule that didn't hold
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Where the offending value is, as a JSON Pointer (`""` for the root, `/users/0/email`…)
+    ///
+    /// It can be passed to [`Peek::resolve_pointer`] to get back to the value.
+    pub path: String,
+
+    /// The shape of the offending value
+    pub shape: &'static Shape,
+
+    /// What went wrong
+    pub kind: ViolationKind,
+}
+
+impl core::fmt::Display for Violation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+impl core::error::Error for Violation {}
+
+/// The different ways a value can fail validation
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// A number fell outside of `range(min, max)`
+    OutOfRange {
+        /// The offending value
+        value: f64,
+        /// The lower bound, if any
+        min: Option<f64>,
+        /// The upper bound, if any
+        max: Option<f64>,
+    },
+
+    /// A string, list or map's length fell outside of `length(min, max)`
+    InvalidLength {
+        /// The offending length
+        len: usize,
+        /// The lower bound, if any
+        min: Option<usize>,
+        /// The upper bound, if any
+        max: Option<usize>,
+    },
+
+    /// A string didn't match a `regex`
+    PatternMismatch {
+        /// The pattern that didn't match
+        pattern: String,
+    },
+
+    /// A string didn't look like an email address
+    InvalidEmail,
+
+    /// A custom rule returned an error
+    Custom {
+        /// The name the rule was registered under
+        name: String,
+        /// The message it returned
+        message: String,
+    },
+
+    /// A rule was put on a value it can't check, like `range` on a `String`
+    UnsupportedType {
+        /// The rule's name
+        rule: &'static str,
+    },
+
+    /// A rule couldn't be understood: malformed attribute, invalid regex, unregistered
+    /// custom rule…
+    InvalidRule {
+        /// The attribute the rule came from
+        attribute: &'static str,
+        /// What's wrong with it
+        message: String,
+    },
+}
+
+impl core::fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ViolationKind::OutOfRange { value, min, max } => {
+                write!(f, "{value} is out of range")?;
+                write_bounds(f, min, max)
+            }
+            ViolationKind::InvalidLength { len, min, max } => {
+                write!(f, "length {len} is out of range")?;
+                write_bounds(f, min, max)
+            }
+            ViolationKind::PatternMismatch { pattern } => {
+                write!(f, "does not match pattern {pattern:?}")
+            }
+            ViolationKind::InvalidEmail => write!(f, "is not a valid email address"),
+            ViolationKind::Custom { name, message } => write!(f, "{message} ({name})"),
+            ViolationKind::UnsupportedType { rule } => {
+                write!(f, "rule `{rule}` does not apply to this type")
+            }
+            ViolationKind::InvalidRule { attribute, message } => {
+                write!(f, "invalid rule in `{attribute}`: {message}")
+            }
+        }
+    }
+}
+
+fn write_bounds<T: core::fmt::Display>(
+    f: &mut core::fmt::Formatter<'_>,
+    min: &Option<T>,
+    max: &Option<T>,
+) -> core::fmt::Result {
+    match (min, max) {
+        (Some(min), Some(max)) => write!(f, " (expected {min}..={max})"),
+        (Some(min), None) => write!(f, " (expected at least {min})"),
+        (None, Some(max)) => write!(f, " (expected at most {max})"),
+        (None, None) => Ok(()),
+    }
+}
@@ -0,0 +1,350 @@
+//! Parsing of `validate(...)` attributes.
+//!
+//! The derive macro stores unknown attributes as their token stream, stringified — so
+//! `#[facet(validate(range(min = 1, max = 10)))]` shows up as something like
+//! `validate (range (min = 1 , max = 10))`, which is what's parsed here.
+
+/// A single rule out of a `validate(...)` attribute
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Rule {
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    Regex(String),
+    Email,
+    Custom(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Open,
+    Close,
+    Comma,
+    Eq,
+    PathSep,
+    Minus,
+}
+
+/// Parses the rules out of an attribute, or returns `None` if it's not a `validate` attribute
+pub(crate) fn parse(attribute: &str) -> Option<Result<Vec<Rule>, String>> {
+    let tokens = match lex(attribute) {
+        Ok(tokens) => tokens,
+        // Not ours to complain about, unless it was meant for us
+        Err(e) => {
+            return attribute
+                .trim_start()
+                .starts_with("validate")
+                .then_some(Err(e));
+        }
+    };
+    match tokens.as_slice() {
+        [Token::Ident(name), Token::Open, ..] if name == "validate" => {}
+        _ => return None,
+    }
+    let mut parser = Parser { tokens, pos: 2 };
+    Some(parser.rules())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(format!("expected {what}")),
+        }
+    }
+
+    /// rules := rule (',' rule)* ','? ')'
+    fn rules(&mut self) -> Result<Vec<Rule>, String> {
+        let mut rules = Vec::new();
+        loop {
+            if self.peek() == Some(&Token::Close) {
+                self.pos += 1;
+                break;
+            }
+            rules.push(self.rule()?);
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::Close) => break,
+                _ => return Err("expected ',' or ')'".to_string()),
+            }
+        }
+        if self.peek().is_some() {
+            return Err("unexpected input after `validate(...)`".to_string());
+        }
+        Ok(rules)
+    }
+
+    fn rule(&mut self) -> Result<Rule, String> {
+        let Some(Token::Ident(name)) = self.next() else {
+            return Err("expected a rule name".to_string());
+        };
+        match name.as_str() {
+            "range" => {
+                let (min, max) = self.bounds()?;
+                Ok(Rule::Range { min, max })
+            }
+            "length" => {
+                let (min, max) = self.bounds()?;
+                let to_len = |bound: Option<f64>| match bound {
+                    Some(n) if n < 0.0 || n.fract() != 0.0 => {
+                        Err(format!("length bound {n} is not a non-negative integer"))
+                    }
+                    Some(n) => Ok(Some(n as usize)),
+                    None => Ok(None),
+                };
+                Ok(Rule::Length {
+                    min: to_len(min)?,
+                    max: to_len(max)?,
+                })
+            }
+            "regex" => {
+                // Both `regex = "..."` and `regex("...")`
+                let parenthesized = match self.next() {
+                    Some(Token::Eq) => false,
+                    Some(Token::Open) => true,
+                    _ => return Err("expected `regex = \"pattern\"`".to_string()),
+                };
+                let Some(Token::Str(pattern)) = self.next() else {
+                    return Err("expected a string literal pattern".to_string());
+                };
+                if parenthesized {
+                    self.expect(Token::Close, "')'")?;
+                }
+                Ok(Rule::Regex(pattern))
+            }
+            "email" => Ok(Rule::Email),
+            "custom" => {
+                self.expect(Token::Eq, "`custom = name`")?;
+                Ok(Rule::Custom(self.name()?))
+            }
+            other => Err(format!("unknown rule `{other}`")),
+        }
+    }
+
+    /// `(min = a, max = b)`, either one optional
+    fn bounds(&mut self) -> Result<(Option<f64>, Option<f64>), String> {
+        self.expect(Token::Open, "'('")?;
+        let (mut min, mut max) = (None, None);
+        loop {
+            if self.peek() == Some(&Token::Close) {
+                self.pos += 1;
+                break;
+            }
+            let Some(Token::Ident(key)) = self.next() else {
+                return Err("expected `min` or `max`".to_string());
+            };
+            self.expect(Token::Eq, "'='")?;
+            let value = self.number()?;
+            match key.as_str() {
+                "min" => min = Some(value),
+                "max" => max = Some(value),
+                other => return Err(format!("unknown bound `{other}`")),
+            }
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::Close) => break,
+                _ => return Err("expected ',' or ')'".to_string()),
+            }
+        }
+        Ok((min, max))
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            Some(Token::Minus) => match self.next() {
+                Some(Token::Number(n)) => Ok(-n),
+                _ => Err("expected a number".to_string()),
+            },
+            _ => Err("expected a number".to_string()),
+        }
+    }
+
+    /// A custom rule's name: a string literal, or a path like `crate::checks::even`
+    fn name(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Str(name)) => Ok(name),
+            Some(Token::Ident(ident)) => {
+                let mut name = ident;
+                while self.peek() == Some(&Token::PathSep) {
+                    self.pos += 1;
+                    let Some(Token::Ident(ident)) = self.next() else {
+                        return Err("expected an identifier after `::`".to_string());
+                    };
+                    name.push_str("::");
+                    name.push_str(&ident);
+                }
+                Ok(name)
+            }
+            _ => Err("expected a custom rule name".to_string()),
+        }
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '=' | '-' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    ',' => Token::Comma,
+                    '=' => Token::Eq,
+                    _ => Token::Minus,
+                });
+            }
+            ':' => {
+                chars.next();
+                if chars.next().map(|(_, c)| c) != Some(':') {
+                    return Err("unexpected ':'".to_string());
+                }
+                tokens.push(Token::PathSep);
+            }
+            '"' => {
+                chars.next();
+                tokens.push(Token::Str(lex_string(&mut chars)?));
+            }
+            'r' if input[start..].starts_with("r\"") || input[start..].starts_with("r#") => {
+                let hashes = input[start + 1..].chars().take_while(|&c| c == '#').count();
+                let body_start = start + 1 + hashes + 1;
+                let terminator = format!("\"{}", "#".repeat(hashes));
+                let Some(len) = input.get(body_start..).and_then(|s| s.find(&terminator)) else {
+                    return Err("unterminated raw string".to_string());
+                };
+                tokens.push(Token::Str(input[body_start..body_start + len].to_string()));
+                let end = body_start + len + terminator.len();
+                while chars.peek().is_some_and(|&(i, _)| i < end) {
+                    chars.next();
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let mut text = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    if c != '_' {
+                        text.push(c);
+                    }
+                    chars.next();
+                }
+                tokens.push(Token::Number(parse_number(&text)?));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c => return Err(format!("unexpected character {c:?}")),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses a numeric literal, ignoring any type suffix (`10u32`, `0.5f64`)
+fn parse_number(text: &str) -> Result<f64, String> {
+    const SUFFIXES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+    let digits = SUFFIXES
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .unwrap_or(text);
+    digits
+        .parse()
+        .map_err(|_| format!("invalid number `{text}`"))
+}
+
+fn lex_string(
+    chars: &mut core::iter::Peekable<core::str::CharIndices<'_>>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '"' => return Ok(out),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('0') => out.push('\0'),
+                Some(c @ ('\\' | '"' | '\'')) => out.push(c),
+                Some(c) => return Err(format!("unsupported escape `\\{c}`")),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stringified_attributes() {
+        assert_eq!(
+            parse("validate (range (min = - 1 , max = 10u8) , email)"),
+            Some(Ok(vec![
+                Rule::Range {
+                    min: Some(-1.0),
+                    max: Some(10.0)
+                },
+                Rule::Email
+            ]))
+        );
+        assert_eq!(
+            parse(r#"validate (regex = r"^\d+$" , custom = checks :: even)"#),
+            Some(Ok(vec![
+                Rule::Regex(r"^\d+$".to_string()),
+                Rule::Custom("checks::even".to_string())
+            ]))
+        );
+        assert_eq!(
+            parse(r#"validate (regex = "^\\d+$")"#),
+            Some(Ok(vec![Rule::Regex(r"^\d+$".to_string())]))
+        );
+        assert_eq!(parse("sensitive"), None);
+        assert!(matches!(parse("validate (bogus)"), Some(Err(_))));
+        assert!(matches!(
+            parse("validate (length (min = 1.5))"),
+            Some(Err(_))
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use facet_core::{Field, FieldAttribute, FieldFlags, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use regex_lite::Regex;
+
+use crate::rule::{self, Rule};
+use crate::{Validator, Violation, ViolationKind};
+
+pub(crate) struct Walker<'v> {
+    validator: &'v Validator,
+    /// Patterns are compiled once per validation, not once per value checked
+    regexes: HashMap<String, Result<Regex, String>>,
+    /// JSON Pointer segments, already escaped
+    path: Vec<String>,
+    violations: Vec<Violation>,
+}
+
+impl<'v> Walker<'v> {
+    pub(crate) fn new(validator: &'v Validator) -> Self {
+        Self {
+            validator,
+            regexes: HashMap::new(),
+            path: Vec::new(),
+            violations: Vec::new(),
+        }
+    }
+
+    pub(crate) fn run(mut self, peek: Peek<'_, '_>) -> Vec<Violation> {
+        self.walk(peek);
+        self.violations
+    }
+
+    fn walk(&mut self, peek: Peek<'_, '_>) {
+        // Nothing to look at in a `None`
+        let Ok(peek) = peek.unwrap_wrappers() else {
+            return;
+        };
+
+        if let Ok(list) = peek.into_list_like() {
+            for (i, item) in list.iter().enumerate() {
+                self.path.push(i.to_string());
+                self.walk(item);
+                self.path.pop();
+            }
+            return;
+        }
+
+        if let Ok(map) = peek.into_map() {
+            for (key, value) in map.iter() {
+                let key = match key.as_str() {
+                    Some(s) => s.to_string(),
+                    None => key.to_string(),
+                };
+                self.path.push(escape(&key));
+                self.walk(value);
+                self.path.pop();
+            }
+            return;
+        }
+
+        if let Ok(tuple) = peek.into_tuple() {
+            for (i, (_, item)) in tuple.fields().enumerate() {
+                self.path.push(i.to_string());
+                self.walk(item);
+                self.path.pop();
+            }
+            return;
+        }
+
+        match peek.shape().ty {
+            Type::User(UserType::Struct(_)) => {
+                if let Ok(ps) = peek.into_struct() {
+                    self.walk_fields(ps.fields());
+                }
+            }
+            Type::User(UserType::Enum(_)) => {
+                if let Ok(pe) = peek.into_enum() {
+                    self.walk_fields(pe.fields());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn walk_fields<'mem, 'facet>(
+        &mut self,
+        fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+    ) {
+        for (field, value) in fields {
+            // Flattened fields live in their parent, as far as paths go
+            let flatten = field.flags.contains(FieldFlags::FLATTEN);
+            if !flatten {
+                self.path.push(escape(field.name));
+            }
+            for attribute in field.attributes {
+                let &FieldAttribute::Arbitrary(attribute) = attribute else {
+                    continue;
+                };
+                match rule::parse(attribute) {
+                    Some(Ok(rules)) => {
+                        for rule in &rules {
+                            self.check(attribute, rule, value);
+                        }
+                    }
+                    Some(Err(message)) => self.report(value, invalid_rule(attribute, message)),
+                    None => {}
+                }
+            }
+            self.walk(value);
+            if !flatten {
+                self.path.pop();
+            }
+        }
+    }
+
+    fn check(&mut self, attribute: &'static str, rule: &Rule, value: Peek<'_, '_>) {
+        // Rules constrain the value, if there is one
+        let Ok(peek) = value.unwrap_wrappers() else {
+            return;
+        };
+
+        let kind = match rule {
+            Rule::Range { min, max } => match as_f64(peek) {
+                Some(n) if min.is_some_and(|min| n < min) || max.is_some_and(|max| n > max) => {
+                    Some(ViolationKind::OutOfRange {
+                        value: n,
+                        min: *min,
+                        max: *max,
+                    })
+                }
+                Some(_) => None,
+                None => Some(ViolationKind::UnsupportedType { rule: "range" }),
+            },
+            Rule::Length { min, max } => match length(peek) {
+                Some(len)
+                    if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) =>
+                {
+                    Some(ViolationKind::InvalidLength {
+                        len,
+                        min: *min,
+                        max: *max,
+                    })
+                }
+                Some(_) => None,
+                None => Some(ViolationKind::UnsupportedType { rule: "length" }),
+            },
+            Rule::Regex(pattern) => match peek.as_str() {
+                Some(s) => match self.regex(pattern) {
+                    Ok(re) if re.is_match(s) => None,
+                    Ok(_) => Some(ViolationKind::PatternMismatch {
+                        pattern: pattern.clone(),
+                    }),
+                    Err(message) => Some(invalid_rule(attribute, message)),
+                },
+                None => Some(ViolationKind::UnsupportedType { rule: "regex" }),
+            },
+            Rule::Email => match peek.as_str() {
+                Some(s) if is_email(s) => None,
+                Some(_) => Some(ViolationKind::InvalidEmail),
+                None => Some(ViolationKind::UnsupportedType { rule: "email" }),
+            },
+            Rule::Custom(name) => match self.validator.custom.get(name) {
+                Some(f) => f(peek).err().map(|message| ViolationKind::Custom {
+                    name: name.clone(),
+                    message,
+                }),
+                None => Some(invalid_rule(
+                    attribute,
+                    format!("no custom rule named `{name}` was registered"),
+                )),
+            },
+        };
+
+        if let Some(kind) = kind {
+            self.report(value, kind);
+        }
+    }
+
+    fn regex(&mut self, pattern: &str) -> Result<&Regex, String> {
+        if !self.regexes.contains_key(pattern) {
+            let compiled = Regex::new(pattern).map_err(|e| e.to_string());
+            self.regexes.insert(pattern.to_string(), compiled);
+        }
+        self.regexes[pattern].as_ref().map_err(Clone::clone)
+    }
+
+    fn report(&mut self, peek: Peek<'_, '_>, kind: ViolationKind) {
+        let mut path = String::new();
+        for segment in &self.path {
+            path.push('/');
+            path.push_str(segment);
+        }
+        self.violations.push(Violation {
+            path,
+            shape: peek.shape(),
+            kind,
+        });
+    }
+}
+
+fn invalid_rule(attribute: &'static str, message: String) -> ViolationKind {
+    ViolationKind::InvalidRule { attribute, message }
+}
+
+fn length(peek: Peek<'_, '_>) -> Option<usize> {
+    if let Some(s) = peek.as_str() {
+        Some(s.chars().count())
+    } else if let Ok(list) = peek.into_list_like() {
+        Some(list.len())
+    } else if let Ok(map) = peek.into_map() {
+        Some(map.len())
+    } else {
+        None
+    }
+}
+
+fn as_f64(peek: Peek<'_, '_>) -> Option<f64> {
+    macro_rules! num {
+        ($ty:ty) => {
+            peek.get::<$ty>().ok().map(|v| *v as f64)
+        };
+    }
+    match peek.scalar_type()? {
+        ScalarType::U8 => num!(u8),
+        ScalarType::U16 => num!(u16),
+        ScalarType::U32 => num!(u32),
+        ScalarType::U64 => num!(u64),
+        ScalarType::U128 => num!(u128),
+        ScalarType::USize => num!(usize),
+        ScalarType::I8 => num!(i8),
+        ScalarType::I16 => num!(i16),
+        ScalarType::I32 => num!(i32),
+        ScalarType::I64 => num!(i64),
+        ScalarType::I128 => num!(i128),
+        ScalarType::ISize => num!(isize),
+        ScalarType::F32 => num!(f32),
+        ScalarType::F64 => num!(f64),
+        _ => None,
+    }
+}
+
+/// A deliberately loose check: `local@domain.tld`, no whitespace, one `@`
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !s.chars().any(char::is_whitespace)
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+/// Escapes a JSON Pointer segment (`~` → `~0`, `/` → `~1`)
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
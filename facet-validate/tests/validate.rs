@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_validate::{Validator, ViolationKind, validate};
+
+#[derive(Facet)]
+struct Order {
+    #[facet(validate(length(min = 1)))]
+    id: String,
+    #[facet(validate(email))]
+    contact: Option<String>,
+    items: Vec<Item>,
+    #[facet(validate(length(max = 2)))]
+    tags: BTreeMap<String, Tag>,
+}
+
+#[derive(Facet)]
+struct Item {
+    #[facet(validate(regex = "^[A-Z]{3}-[0-9]+$"))]
+    sku: String,
+    #[facet(validate(range(min = 1, max = 99)))]
+    quantity: u32,
+    #[facet(flatten)]
+    price: Price,
+}
+
+#[derive(Facet)]
+struct Price {
+    #[facet(validate(range(min = 0.0)))]
+    amount: f64,
+}
+
+#[derive(Facet)]
+struct Tag {
+    #[facet(validate(custom = lowercase))]
+    label: String,
+}
+
+fn item(sku: &str, quantity: u32, amount: f64) -> Item {
+    Item {
+        sku: sku.to_string(),
+        quantity,
+        price: Price { amount },
+    }
+}
+
+fn order() -> Order {
+    Order {
+        id: "o-1".to_string(),
+        contact: Some("amos@example.com".to_string()),
+        items: vec![item("ABC-1", 2, 9.5), item("XYZ-22", 1, 0.0)],
+        tags: BTreeMap::from([(
+            "a/b".to_string(),
+            Tag {
+                label: "rush".to_string(),
+            },
+        )]),
+    }
+}
+
+fn validator() -> Validator {
+    Validator::new().custom("lowercase", |peek| match peek.as_str() {
+        Some(s) if s.chars().all(|c| !c.is_uppercase()) => Ok(()),
+        _ => Err("must be lowercase".to_string()),
+    })
+}
+
+#[test]
+fn valid_values_pass() {
+    facet_testhelpers::setup();
+
+    assert_eq!(validator().validate(&order()), Ok(()));
+
+    // Rules on `None` are skipped
+    let mut order = order();
+    order.contact = None;
+    assert_eq!(validator().validate(&order), Ok(()));
+}
+
+#[test]
+fn every_violation_is_reported_with_its_path() {
+    facet_testhelpers::setup();
+
+    let mut order = order();
+    order.id.clear();
+    order.contact = Some("not an email".to_string());
+    order.items = vec![item("ABC-1", 0, 1.0), item("abc", 100, -1.0)];
+    order.tags.insert(
+        "b".to_string(),
+        Tag {
+            label: "LOUD".to_string(),
+        },
+    );
+    order.tags.insert(
+        "c".to_string(),
+        Tag {
+            label: "ok".to_string(),
+        },
+    );
+
+    let violations = validator().validate(&order).unwrap_err();
+    let found: Vec<_> = violations
+        .iter()
+        .map(|v| (v.path.as_str(), v.kind.clone()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (
+                "/id",
+                ViolationKind::InvalidLength {
+                    len: 0,
+                    min: Some(1),
+                    max: None
+                }
+            ),
+            ("/contact", ViolationKind::InvalidEmail),
+            (
+                "/items/0/quantity",
+                ViolationKind::OutOfRange {
+                    value: 0.0,
+                    min: Some(1.0),
+                    max: Some(99.0)
+                }
+            ),
+            (
+                "/items/1/sku",
+                ViolationKind::PatternMismatch {
+                    pattern: "^[A-Z]{3}-[0-9]+$".to_string()
+                }
+            ),
+            (
+                "/items/1/quantity",
+                ViolationKind::OutOfRange {
+                    value: 100.0,
+                    min: Some(1.0),
+                    max: Some(99.0)
+                }
+            ),
+            (
+                "/items/1/amount",
+                ViolationKind::OutOfRange {
+                    value: -1.0,
+                    min: Some(0.0),
+                    max: None
+                }
+            ),
+            (
+                "/tags",
+                ViolationKind::InvalidLength {
+                    len: 3,
+                    min: None,
+                    max: Some(2)
+                }
+            ),
+            (
+                "/tags/b/label",
+                ViolationKind::Custom {
+                    name: "lowercase".to_string(),
+                    message: "must be lowercase".to_string()
+                }
+            ),
+        ]
+    );
+
+    // Paths lead back to the offending values
+    let peek = Peek::new(&order);
+    for violation in &violations {
+        let target = peek.resolve_pointer(&violation.path).unwrap();
+        assert_eq!(target.shape(), violation.shape);
+    }
+}
+
+#[test]
+fn misconfigured_rules_are_reported() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Misconfigured {
+        #[facet(validate(range(min = 1)))]
+        name: String,
+        #[facet(validate(regex = "("))]
+        code: String,
+        #[facet(validate(frobnicate))]
+        other: u8,
+    }
+
+    let value = Misconfigured {
+        name: "x".to_string(),
+        code: "y".to_string(),
+        other: 0,
+    };
+    let violations = validate(&value).unwrap_err();
+    assert_eq!(violations.len(), 3);
+    assert_eq!(violations[0].path, "/name");
+    assert_eq!(
+        violations[0].kind,
+        ViolationKind::UnsupportedType { rule: "range" }
+    );
+    for violation in &violations[1..] {
+        assert!(matches!(violation.kind, ViolationKind::InvalidRule { .. }));
+    }
+
+    // Custom rules need to be registered
+    let violations = validate(&order()).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, "/tags/a~1b/label");
+    assert!(matches!(
+        violations[0].kind,
+        ViolationKind::InvalidRule { .. }
+    ));
+}
@@ -0,0 +1,236 @@
+use std::borrow::Cow;
+
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, StructKind, Type, UserType};
+use facet_reflect::{ScalarType, Wip};
+use log::trace;
+
+use crate::dom::{self, Element};
+use crate::{FieldPlacement, XmlError, is_newtype};
+
+/// Deserializes an XML document into a type that implements `Facet`.
+///
+/// The root element's name isn't checked. Attributes and elements no field matches are
+/// ignored, unless the type has `#[facet(deny_unknown_fields)]`.
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Debug, Facet, PartialEq)]
+/// struct Book {
+///     #[facet(xml(attribute))]
+///     isbn: String,
+///     title: String,
+///     authors: Vec<String>,
+/// }
+///
+/// let book: Book = facet_xml::from_str(
+///     r#"<Book isbn="0-7475-3269-9">
+///         <title>Frankenstein</title>
+///         <authors>Mary Shelley</authors>
+///     </Book>"#,
+/// )
+/// .unwrap();
+/// assert_eq!(book.authors, ["Mary Shelley"]);
+/// ```
+pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(
+    input: &'input str,
+) -> Result<T, XmlError> {
+    let root = dom::parse(input)?;
+    let wip = decode_element(Wip::alloc::<T>()?, &root)?;
+    Ok(wip.build()?.materialize::<T>()?)
+}
+
+/// Fills the value a `Wip` is building from an element
+fn decode_element<'facet>(wip: Wip<'facet>, element: &Element) -> Result<Wip<'facet>, XmlError> {
+    let shape = wip.shape();
+    trace!("Reading {} from <{}>", shape, element.name);
+
+    let wip = match (shape.def, shape.ty) {
+        (Def::Scalar(_), _) => decode_text(wip, &element.text)?,
+        (Def::Option(_), _) => decode_element(wip.push_some()?, element)?.pop()?,
+        (Def::SmartPointer(_), _) => decode_element(wip.push_pointee()?, element)?.pop()?,
+        (Def::Map(_), _) => {
+            let mut wip = wip.begin_map_insert()?;
+            for child in &element.children {
+                wip = decode_text(wip.push_map_key()?, &child.name)?;
+                wip = decode_element(wip.push_map_value()?, child)?.pop()?;
+            }
+            wip
+        }
+        (_, Type::User(UserType::Struct(struct_type))) => match struct_type.kind {
+            _ if is_newtype(&struct_type) => decode_element(wip.field(0)?, element)?.pop()?,
+            StructKind::TupleStruct | StructKind::Tuple => {
+                return Err(XmlError::UnsupportedShape(shape));
+            }
+            _ => decode_fields(wip, shape, struct_type.fields, element)?,
+        },
+        // A unit variant as text, or another variant as the only child
+        (_, Type::User(UserType::Enum(_))) => {
+            let [child] = element.children.as_slice() else {
+                return decode_text(wip, &element.text);
+            };
+            let Some((index, variant)) = wip.find_variant(&child.name) else {
+                return Err(XmlError::NoSuchVariant {
+                    shape,
+                    name: child.name.clone(),
+                });
+            };
+            let wip = wip.variant(index)?;
+            match variant.data.kind {
+                StructKind::Unit => wip,
+                _ if is_newtype(&variant.data) => decode_element(wip.field(0)?, child)?.pop()?,
+                StructKind::Struct => decode_fields(wip, shape, variant.data.fields, child)?,
+                _ => return Err(XmlError::UnsupportedShape(shape)),
+            }
+        }
+        _ => return Err(XmlError::UnsupportedShape(shape)),
+    };
+    Ok(wip)
+}
+
+/// Fills the fields of a struct, or of the selected variant, from the attributes, text and
+/// children of an element
+fn decode_fields<'facet>(
+    mut wip: Wip<'facet>,
+    shape: &'static Shape,
+    fields: &'static [Field],
+    element: &Element,
+) -> Result<Wip<'facet>, XmlError> {
+    if shape.has_deny_unknown_fields_attr() {
+        check_unknown(shape, fields, element)?;
+    }
+
+    for (index, field) in fields.iter().enumerate() {
+        if field.flags.contains(FieldFlags::SKIP_DESERIALIZING) {
+            continue;
+        }
+        match FieldPlacement::of(field) {
+            FieldPlacement::Attribute => {
+                if let Some(value) = element.attribute(field.name) {
+                    wip = decode_text(wip.field(index)?, value)?.pop()?;
+                }
+            }
+            FieldPlacement::Text => {
+                // No text is `None`, rather than `Some("")`
+                let is_option = matches!(field.shape().def, Def::Option(_));
+                if !(is_option && element.text.is_empty()) {
+                    wip = decode_text(wip.field(index)?, &element.text)?.pop()?;
+                }
+            }
+            FieldPlacement::Element => {
+                let children: Vec<&Element> = element.children_named(field.name).collect();
+                // Lists are set even without any element, to be empty
+                let is_list = matches!(field.shape().def, Def::List(_) | Def::Array(_));
+                if is_list || !children.is_empty() {
+                    wip = decode_children(wip.field(index)?, &children)?.pop()?;
+                }
+            }
+        }
+    }
+
+    let wip = wip.fill_defaults()?;
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            return Err(XmlError::MissingField {
+                shape,
+                field: field.name,
+            });
+        }
+    }
+    Ok(wip)
+}
+
+/// Fills a field from the elements named after it: all of them for lists, otherwise the first
+fn decode_children<'facet>(
+    wip: Wip<'facet>,
+    children: &[&Element],
+) -> Result<Wip<'facet>, XmlError> {
+    let wip = match wip.shape().def {
+        Def::List(_) | Def::Array(_) => {
+            let mut wip = wip.begin_pushback()?;
+            for child in children {
+                wip = decode_element(wip.push()?, child)?.pop()?;
+            }
+            wip
+        }
+        Def::Option(_) => decode_children(wip.push_some()?, children)?.pop()?,
+        _ => decode_element(wip, children[0])?,
+    };
+    Ok(wip)
+}
+
+/// Fills a scalar, a unit variant, or an option of either, from text
+fn decode_text<'facet>(wip: Wip<'facet>, text: &str) -> Result<Wip<'facet>, XmlError> {
+    let shape = wip.shape();
+    let invalid = || XmlError::InvalidValue {
+        value: text.to_string(),
+        shape,
+    };
+
+    let wip = match (shape.def, shape.ty) {
+        (Def::Option(_), _) => decode_text(wip.push_some()?, text)?.pop()?,
+        (_, Type::User(UserType::Enum(_))) => {
+            let name = text.trim();
+            match wip.find_variant(name) {
+                Some((index, variant)) if variant.data.kind == StructKind::Unit => {
+                    wip.variant(index)?
+                }
+                _ => {
+                    return Err(XmlError::NoSuchVariant {
+                        shape,
+                        name: name.to_string(),
+                    });
+                }
+            }
+        }
+        (Def::Scalar(_), _) => match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::String) => wip.put(text.to_string())?,
+            Some(ScalarType::CowStr) => wip.put(Cow::<str>::Owned(text.to_string()))?,
+            Some(ScalarType::Char) => wip.parse(text).map_err(|_| invalid())?,
+            Some(ScalarType::Bool) => match text.trim() {
+                "true" | "1" => wip.put(true)?,
+                "false" | "0" => wip.put(false)?,
+                _ => return Err(invalid()),
+            },
+            // Nothing to borrow from, the text was unescaped into a new string
+            Some(ScalarType::Str) => return Err(XmlError::UnsupportedShape(shape)),
+            // Only text that stands for a string is kept as-is
+            _ => wip.parse(text.trim()).map_err(|_| invalid())?,
+        },
+        _ => return Err(XmlError::UnsupportedShape(shape)),
+    };
+    Ok(wip)
+}
+
+/// Errors on the first attribute or child that no field matches
+fn check_unknown(
+    shape: &'static Shape,
+    fields: &'static [Field],
+    element: &Element,
+) -> Result<(), XmlError> {
+    let known = |name: &str, placement: FieldPlacement| {
+        fields
+            .iter()
+            .any(|field| field.name == name && FieldPlacement::of(field) == placement)
+    };
+    let unknown = element
+        .attributes
+        .iter()
+        .map(|(name, _)| name)
+        .find(|name| !known(name, FieldPlacement::Attribute))
+        .or_else(|| {
+            element
+                .children
+                .iter()
+                .map(|child| &child.name)
+                .find(|name| !known(name, FieldPlacement::Element))
+        });
+    match unknown {
+        Some(name) => Err(XmlError::UnknownField {
+            shape,
+            name: name.clone(),
+        }),
+        None => Ok(()),
+    }
+}
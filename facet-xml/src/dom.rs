@@ -0,0 +1,107 @@
+//! The elements of a document, read whole before they're mapped to values
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::XmlError;
+
+/// An element, with namespace prefixes left out of its name and of its attributes' names
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    /// Attributes other than namespace declarations
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Element>,
+    /// All the text directly inside the element, unescaped. Whitespace is kept, unless it's
+    /// only the indentation between children.
+    pub(crate) text: String,
+}
+
+impl Element {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Reads the root element of a document. Declarations, comments and processing instructions
+/// are skipped.
+pub(crate) fn parse(input: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader::from_str(input);
+    let syntax = |reader: &Reader<&[u8]>, err: &dyn core::fmt::Display| {
+        XmlError::Syntax(format!("{} at byte {}", err, reader.buffer_position()))
+    };
+
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    loop {
+        let event = reader.read_event().map_err(|err| syntax(&reader, &err))?;
+        let element = match event {
+            Event::Start(start) => {
+                stack.push(element(&start).map_err(|err| syntax(&reader, &err))?);
+                continue;
+            }
+            Event::Empty(start) => element(&start).map_err(|err| syntax(&reader, &err))?,
+            Event::End(_) => {
+                // quick-xml already checked it closes the element on top of the stack
+                let mut element = stack.pop().unwrap();
+                if !element.children.is_empty() && element.text.trim().is_empty() {
+                    element.text.clear();
+                }
+                element
+            }
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|err| syntax(&reader, &err))?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.text.push_str(&text);
+                }
+                continue;
+            }
+            Event::CData(cdata) => {
+                let bytes = cdata.into_inner();
+                let text = core::str::from_utf8(&bytes).map_err(|err| syntax(&reader, &err))?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.text.push_str(text);
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None if root.is_none() => root = Some(element),
+            None => return Err(syntax(&reader, &"more than one root element")),
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(syntax(&reader, &"unclosed element"));
+    }
+    root.ok_or_else(|| syntax(&reader, &"no root element"))
+}
+
+fn element(start: &BytesStart<'_>) -> Result<Element, Box<dyn std::error::Error>> {
+    let name = core::str::from_utf8(start.local_name().as_ref())?.to_string();
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute?;
+        if attribute.key.as_namespace_binding().is_some() {
+            continue;
+        }
+        let key = core::str::from_utf8(attribute.key.local_name().as_ref())?.to_string();
+        attributes.push((key, attribute.unescape_value()?.into_owned()));
+    }
+    Ok(Element {
+        name,
+        attributes,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,81 @@
+use core::fmt;
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors that can occur while reading or writing XML
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum XmlError {
+    /// The input isn't well-formed XML
+    Syntax(String),
+    /// An element or attribute for a field without a default is missing
+    MissingField {
+        /// The struct or variant being read
+        shape: &'static Shape,
+        /// The field that was left unset
+        field: &'static str,
+    },
+    /// An element or attribute that no field matches, in a type with
+    /// `#[facet(deny_unknown_fields)]`
+    UnknownField {
+        /// The struct or variant being read
+        shape: &'static Shape,
+        /// The name of the element or attribute
+        name: String,
+    },
+    /// Text couldn't be parsed as the type it's read into
+    InvalidValue {
+        /// The text, as it was in the input
+        value: String,
+        /// The type it's read into
+        shape: &'static Shape,
+    },
+    /// An enum has no variant by that name
+    NoSuchVariant {
+        /// The enum being read
+        shape: &'static Shape,
+        /// The name that was read
+        name: String,
+    },
+    /// Values of this shape can't be mapped to XML
+    UnsupportedShape(&'static Shape),
+    /// A map key or field name isn't a valid name for an element or attribute
+    InvalidName(String),
+    /// Text has a character that XML documents can't hold, like U+0000
+    ForbiddenChar(char),
+    /// Reflection error
+    ReflectError(ReflectError),
+}
+
+impl From<ReflectError> for XmlError {
+    fn from(err: ReflectError) -> Self {
+        Self::ReflectError(err)
+    }
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XmlError::Syntax(message) => write!(f, "Invalid XML: {}", message),
+            XmlError::MissingField { shape, field } => {
+                write!(f, "Missing `{}` for {}", field, shape)
+            }
+            XmlError::UnknownField { shape, name } => write!(f, "Unknown `{}` in {}", name, shape),
+            XmlError::InvalidValue { value, shape } => {
+                write!(f, "Invalid {}: '{}'", shape, value)
+            }
+            XmlError::NoSuchVariant { shape, name } => {
+                write!(f, "No variant `{}` in enum {}", name, shape)
+            }
+            XmlError::UnsupportedShape(shape) => write!(f, "Unsupported shape for XML: {}", shape),
+            XmlError::InvalidName(name) => write!(f, "Not a valid XML name: '{}'", name),
+            XmlError::ForbiddenChar(c) => {
+                write!(f, "U+{:04X} can't be written in XML", *c as u32)
+            }
+            XmlError::ReflectError(err) => write!(f, "Reflection error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for XmlError {}
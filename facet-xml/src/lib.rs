@@ -0,0 +1,62 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::{Field, FieldAttribute, Shape, StructKind, StructType};
+
+mod dom;
+
+mod errors;
+pub use errors::XmlError;
+
+mod deserialize;
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
+
+/// Where a struct field goes in its element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldPlacement {
+    /// A child element named after the field, the default
+    Element,
+    /// An attribute named after the field, from `#[facet(xml(attribute))]`
+    Attribute,
+    /// The text of the element itself, from `#[facet(xml(text))]`
+    Text,
+}
+
+impl FieldPlacement {
+    fn of(field: &Field) -> Self {
+        for attribute in field.attributes {
+            let FieldAttribute::Arbitrary(attribute) = attribute else {
+                continue;
+            };
+            // `xml(attribute)`, give or take whitespace
+            let attribute: String = attribute.split_whitespace().collect();
+            match attribute.as_str() {
+                "xml(attribute)" => return FieldPlacement::Attribute,
+                "xml(text)" => return FieldPlacement::Text,
+                _ => {}
+            }
+        }
+        FieldPlacement::Element
+    }
+}
+
+/// The root element is named after the type, without its generic parameters
+fn root_name(shape: &'static Shape) -> String {
+    let name = shape.to_string();
+    match name.split_once('<') {
+        Some((name, _)) => name.to_string(),
+        None => name,
+    }
+}
+
+/// Newtypes, and variants with a single unnamed field, stand for what they wrap
+fn is_newtype(struct_type: &StructType) -> bool {
+    matches!(
+        struct_type.kind,
+        StructKind::TupleStruct | StructKind::Tuple
+    ) && struct_type.fields.len() == 1
+}
@@ -0,0 +1,279 @@
+use core::fmt::Write;
+
+use facet_core::{Def, Facet, Field, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+use log::trace;
+
+use crate::{FieldPlacement, XmlError, is_newtype, root_name};
+
+/// Serializes any Facet type to an XML element named after the type
+///
+/// # Example
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Book {
+///     #[facet(xml(attribute))]
+///     isbn: String,
+///     title: String,
+///     authors: Vec<String>,
+/// }
+///
+/// let book = Book {
+///     isbn: "0-7475-3269-9".to_string(),
+///     title: "Frankenstein".to_string(),
+///     authors: vec!["Mary Shelley".to_string()],
+/// };
+/// assert_eq!(
+///     facet_xml::to_string(&book).unwrap(),
+///     r#"<Book isbn="0-7475-3269-9"><title>Frankenstein</title><authors>Mary Shelley</authors></Book>"#
+/// );
+/// ```
+pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> Result<String, XmlError> {
+    peek_to_string(Peek::new(value))
+}
+
+/// Serializes a Peek instance to an XML element named after its type
+pub fn peek_to_string(peek: Peek<'_, '_>) -> Result<String, XmlError> {
+    let mut out = String::new();
+    write_value(&mut out, &root_name(peek.shape()), peek)?;
+    Ok(out)
+}
+
+/// Writes the value of a struct field as an element called `name`, or as many as there are
+/// items for lists
+///
+/// Only fields can be lists: anywhere else, like at the root, in a list or as a map value,
+/// their items couldn't be told apart from their neighbors.
+fn write_field_value(out: &mut String, name: &str, peek: Peek<'_, '_>) -> Result<(), XmlError> {
+    match peek.shape().def {
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => peek
+            .into_list_like()?
+            .iter()
+            .try_for_each(|item| write_value(out, name, item)),
+        Def::Set(_) => peek
+            .into_set()?
+            .iter()
+            .try_for_each(|item| write_value(out, name, item)),
+        Def::Option(_) => match peek.into_option()?.value() {
+            Some(value) => write_field_value(out, name, value),
+            None => Ok(()),
+        },
+        _ => write_value(out, name, peek),
+    }
+}
+
+/// Writes `peek` as an element called `name`, or none for `None`
+fn write_value(out: &mut String, name: &str, peek: Peek<'_, '_>) -> Result<(), XmlError> {
+    let shape = peek.shape();
+    trace!("Writing {} as <{}>", shape, name);
+
+    match (shape.def, shape.ty) {
+        (Def::Scalar(_), _) => write_element(out, name, "", &escape(&text(peek)?, false)?),
+        (Def::Option(_), _) => match peek.into_option()?.value() {
+            Some(value) => write_value(out, name, value),
+            None => Ok(()),
+        },
+        (Def::SmartPointer(_), _) => match peek.into_smart_pointer()?.borrow_inner() {
+            Some(inner) => write_value(out, name, inner),
+            None => Err(XmlError::UnsupportedShape(shape)),
+        },
+        // One child per entry, named after its key
+        (Def::Map(_), _) => {
+            let mut content = String::new();
+            for (key, value) in peek.into_map()?.iter() {
+                write_value(&mut content, &text(key)?, value)?;
+            }
+            write_element(out, name, "", &content)
+        }
+        (_, Type::User(UserType::Struct(struct_type))) => {
+            let fields = peek.into_struct()?;
+            match struct_type.kind {
+                _ if is_newtype(&struct_type) => {
+                    let (_, inner) = fields
+                        .fields()
+                        .next()
+                        .ok_or(XmlError::UnsupportedShape(shape))?;
+                    write_value(out, name, inner)
+                }
+                // Their fields have no names to be elements or attributes
+                StructKind::TupleStruct | StructKind::Tuple => {
+                    Err(XmlError::UnsupportedShape(shape))
+                }
+                _ => write_fields(out, name, fields.fields_for_serialize()),
+            }
+        }
+        // Unit variants are the text of the element, others its only child
+        (_, Type::User(UserType::Enum(_))) => {
+            let peek_enum = peek.into_enum()?;
+            let variant = peek_enum
+                .active_variant()
+                .map_err(|_| XmlError::UnsupportedShape(shape))?;
+            let mut content = String::new();
+            match variant.data.kind {
+                StructKind::Unit => content.push_str(&escape(variant.name, false)?),
+                _ if is_newtype(&variant.data) => {
+                    let (_, inner) = peek_enum
+                        .fields()
+                        .next()
+                        .ok_or(XmlError::UnsupportedShape(shape))?;
+                    write_value(&mut content, variant.name, inner)?;
+                }
+                StructKind::Struct => {
+                    write_fields(&mut content, variant.name, peek_enum.fields_for_serialize())?
+                }
+                _ => return Err(XmlError::UnsupportedShape(shape)),
+            }
+            write_element(out, name, "", &content)
+        }
+        (_, Type::Pointer(_)) => {
+            if let Some(s) = peek.as_str() {
+                write_element(out, name, "", &escape(s, false)?)
+            } else if let Some(target) = peek.follow_reference() {
+                write_value(out, name, target)
+            } else {
+                Err(XmlError::UnsupportedShape(shape))
+            }
+        }
+        _ => Err(XmlError::UnsupportedShape(shape)),
+    }
+}
+
+/// Writes an element with fields as its attributes, text and children
+fn write_fields<'mem, 'facet>(
+    out: &mut String,
+    name: &str,
+    fields: impl Iterator<Item = (Field, Peek<'mem, 'facet>)>,
+) -> Result<(), XmlError> {
+    let mut attributes = String::new();
+    let mut content = String::new();
+    for (field, value) in fields {
+        match FieldPlacement::of(&field) {
+            FieldPlacement::Element => write_field_value(&mut content, field.name, value)?,
+            FieldPlacement::Attribute => {
+                if let Some(value) = optional_text(value)? {
+                    check_name(field.name)?;
+                    write!(attributes, " {}=\"{}\"", field.name, escape(&value, true)?).unwrap();
+                }
+            }
+            FieldPlacement::Text => {
+                if let Some(value) = optional_text(value)? {
+                    content.push_str(&escape(&value, false)?);
+                }
+            }
+        }
+    }
+    write_element(out, name, &attributes, &content)
+}
+
+/// `attributes` start with a space, and `content` is already escaped
+fn write_element(
+    out: &mut String,
+    name: &str,
+    attributes: &str,
+    content: &str,
+) -> Result<(), XmlError> {
+    check_name(name)?;
+    if content.is_empty() {
+        write!(out, "<{}{}/>", name, attributes).unwrap();
+    } else {
+        write!(out, "<{}{}>{}</{}>", name, attributes, content, name).unwrap();
+    }
+    Ok(())
+}
+
+/// Element and attribute names come from field names and map keys, which may not be valid
+/// XML names, like `2nd` or `a b`: writing those would produce XML that can't be read back
+fn check_name(name: &str) -> Result<(), XmlError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char);
+    match valid {
+        true => Ok(()),
+        false => Err(XmlError::InvalidName(name.to_string())),
+    }
+}
+
+/// Escapes text for the content of an element, or for an attribute value in double quotes
+///
+/// Carriage returns, and tabs and newlines in attributes, are written as character references,
+/// or readers would normalize them away. Characters XML can't hold at all, like U+0000, are
+/// an error.
+fn escape(text: &str, in_attribute: bool) -> Result<String, XmlError> {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' | '\n' if in_attribute => write!(escaped, "&#{};", c as u32).unwrap(),
+            _ if !is_char(c) => return Err(XmlError::ForbiddenChar(c)),
+            _ => escaped.push(c),
+        }
+    }
+    Ok(escaped)
+}
+
+/// `Char` from the XML specification, which leaves out most control characters
+fn is_char(c: char) -> bool {
+    matches!(c,
+        '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{10FFFF}'
+    )
+}
+
+/// `NameStartChar` from the XML specification
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z'
+        | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// `NameChar` from the XML specification
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}'
+        )
+}
+
+/// The text of a value that goes in an attribute or as text content, `None` for `None`
+fn optional_text(peek: Peek<'_, '_>) -> Result<Option<String>, XmlError> {
+    if let Def::Option(_) = peek.shape().def {
+        return match peek.into_option()?.value() {
+            Some(value) => optional_text(value),
+            None => Ok(None),
+        };
+    }
+    text(peek).map(Some)
+}
+
+/// Scalars as their `Display` output, and unit variants as their name
+fn text(peek: Peek<'_, '_>) -> Result<String, XmlError> {
+    let peek = peek.innermost_peek();
+    let shape = peek.shape();
+    if let Some(s) = peek.as_str() {
+        return Ok(s.to_string());
+    }
+    if let Type::User(UserType::Enum(_)) = shape.ty {
+        let variant = peek
+            .into_enum()?
+            .active_variant()
+            .map_err(|_| XmlError::UnsupportedShape(shape))?;
+        if variant.data.kind == StructKind::Unit {
+            return Ok(variant.name.to_string());
+        }
+        return Err(XmlError::UnsupportedShape(shape));
+    }
+    match shape.def {
+        Def::Scalar(_) if shape.vtable.display.is_some() => Ok(peek.to_string()),
+        _ => Err(XmlError::UnsupportedShape(shape)),
+    }
+}
@@ -0,0 +1,54 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+// Not covered, since XML can't say them:
+// - options: `Some` of an empty list writes no elements, so it reads back as `None`
+// - collections: map keys become element names, which can't be empty or start with a digit
+// - structs and enums: tuple structs and tuple variants are rejected
+struct Xml;
+
+impl Format for Xml {
+    const NAME: &'static str = "XML";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        facet_xml::peek_to_string(peek)
+            .map(String::into_bytes)
+            .map_err(|e| e.to_string())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        let xml = core::str::from_utf8(input).map_err(|e| e.to_string())?;
+        facet_xml::from_str(xml).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Xml>();
+}
+
+#[test]
+fn wide_integers() {
+    facet_testhelpers::setup();
+    conformance::wide_integers::<Xml>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Xml>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Xml>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Xml>();
+}
@@ -0,0 +1,413 @@
+use std::collections::BTreeMap;
+
+use eyre::Result;
+use facet::Facet;
+use facet_xml::{XmlError, from_str, to_string};
+
+#[derive(Debug, PartialEq, Facet)]
+#[facet(rename_all = "camelCase")]
+struct Invoice {
+    #[facet(xml(attribute))]
+    number: u32,
+    #[facet(xml(attribute))]
+    currency: Option<String>,
+    issued_by: Party,
+    #[facet(rename = "line")]
+    lines: Vec<Line>,
+    status: Status,
+    note: Option<String>,
+    totals: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct Party {
+    #[facet(xml(attribute))]
+    vat_id: String,
+    #[facet(xml(text))]
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+struct Line {
+    #[facet(xml(attribute))]
+    kind: Kind,
+    description: String,
+    amount: f64,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+#[repr(u8)]
+enum Kind {
+    Goods,
+    Service,
+}
+
+#[derive(Debug, PartialEq, Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Draft,
+    Sent { to: String },
+    Paid(f64),
+}
+
+fn invoice() -> Invoice {
+    Invoice {
+        number: 12,
+        currency: Some("EUR".to_string()),
+        issued_by: Party {
+            vat_id: "FR123".to_string(),
+            name: "Bolts <& Nuts>".to_string(),
+        },
+        lines: vec![
+            Line {
+                kind: Kind::Goods,
+                description: "Bolts".to_string(),
+                amount: 12.5,
+            },
+            Line {
+                kind: Kind::Service,
+                description: "Delivery".to_string(),
+                amount: 5.0,
+            },
+        ],
+        status: Status::Sent {
+            to: "billing@example.com".to_string(),
+        },
+        note: None,
+        totals: [("net".to_string(), 17.5)].into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_serialize() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(
+        to_string(&invoice())?,
+        concat!(
+            r#"<Invoice number="12" currency="EUR">"#,
+            r#"<issuedBy vat_id="FR123">Bolts &lt;&amp; Nuts&gt;</issuedBy>"#,
+            r#"<line kind="Goods"><description>Bolts</description><amount>12.5</amount></line>"#,
+            r#"<line kind="Service"><description>Delivery</description><amount>5</amount></line>"#,
+            r#"<status><Sent><to>billing@example.com</to></Sent></status>"#,
+            r#"<totals><net>17.5</net></totals>"#,
+            r#"</Invoice>"#,
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let invoice = invoice();
+    assert_eq!(from_str::<Invoice>(&to_string(&invoice)?)?, invoice);
+
+    let mut paid = self::invoice();
+    paid.status = Status::Paid(17.5);
+    paid.currency = None;
+    paid.lines.clear();
+    paid.note = Some("thanks".to_string());
+    assert_eq!(from_str::<Invoice>(&to_string(&paid)?)?, paid);
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_document() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <!-- exported by the billing system -->
+        <inv:Invoice xmlns:inv="urn:example:invoice" number="12" currency="EUR">
+            <inv:issuedBy vat_id="FR123"><![CDATA[Bolts <& Nuts>]]></inv:issuedBy>
+            <line kind="Goods">
+                <description>Bolts</description>
+                <amount>12.5</amount>
+            </line>
+            <extra>ignored</extra>
+            <line kind="Service">
+                <description>Delivery</description>
+                <amount>5</amount>
+            </line>
+            <status>
+                <Sent><to>billing@example.com</to></Sent>
+            </status>
+            <totals><net>17.5</net></totals>
+        </inv:Invoice>"#;
+    assert_eq!(from_str::<Invoice>(xml)?, invoice());
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Flags {
+        #[facet(xml(attribute))]
+        enabled: bool,
+        status: Status,
+        #[facet(default)]
+        retries: u8,
+    }
+    assert_eq!(
+        from_str::<Flags>(r#"<Flags enabled="1"><status><Draft/></status></Flags>"#)?,
+        Flags {
+            enabled: true,
+            status: Status::Draft,
+            retries: 0,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        #[facet(xml(attribute))]
+        id: u8,
+        name: String,
+    }
+
+    assert!(matches!(
+        from_str::<Strict>(r#"<Strict id="1"></Strict>"#),
+        Err(XmlError::MissingField { field: "name", .. })
+    ));
+    assert!(matches!(
+        from_str::<Strict>(r#"<Strict id="1"><name>a</name><age>3</age></Strict>"#),
+        Err(XmlError::UnknownField { name, .. }) if name == "age"
+    ));
+    assert!(matches!(
+        from_str::<Strict>(r#"<Strict id="1" name="a"><name>a</name></Strict>"#),
+        Err(XmlError::UnknownField { name, .. }) if name == "name"
+    ));
+    assert!(matches!(
+        from_str::<Strict>(r#"<Strict id="300"><name>a</name></Strict>"#),
+        Err(XmlError::InvalidValue { value, .. }) if value == "300"
+    ));
+    assert!(matches!(
+        from_str::<Kind>(r#"<Kind>Refund</Kind>"#),
+        Err(XmlError::NoSuchVariant { name, .. }) if name == "Refund"
+    ));
+    assert!(matches!(
+        from_str::<Strict>(r#"<Strict id="1"><name>a</Strict>"#),
+        Err(XmlError::Syntax(_))
+    ));
+    assert!(matches!(
+        to_string(&vec![1, 2]),
+        Err(XmlError::UnsupportedShape(_))
+    ));
+
+    #[derive(Debug, Facet)]
+    struct Scores {
+        by_player: BTreeMap<String, u32>,
+    }
+
+    let scores = Scores {
+        by_player: BTreeMap::from([("Ada Lovelace".to_string(), 3)]),
+    };
+    assert!(matches!(
+        to_string(&scores),
+        Err(XmlError::InvalidName(name)) if name == "Ada Lovelace"
+    ));
+}
+
+#[test]
+fn test_whitespace_round_trip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Spaced {
+        #[facet(xml(attribute))]
+        label: String,
+        text: String,
+        blank: String,
+        separator: char,
+        count: u8,
+    }
+
+    let spaced = Spaced {
+        label: " a\tb\r\nc ".to_string(),
+        text: "  indented\r\n  lines \n".to_string(),
+        blank: " ".to_string(),
+        separator: ' ',
+        count: 3,
+    };
+    assert_eq!(from_str::<Spaced>(&to_string(&spaced)?)?, spaced);
+
+    // Indentation between children isn't text, and numbers are read without the whitespace
+    // around them
+    let xml = r#"
+        <Spaced label="">
+            <text>a</text>
+            <blank> </blank>
+            <separator>	</separator>
+            <count> 3 </count>
+        </Spaced>"#;
+    assert_eq!(
+        from_str::<Spaced>(xml)?,
+        Spaced {
+            label: String::new(),
+            text: "a".to_string(),
+            blank: " ".to_string(),
+            separator: '\t',
+            count: 3,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_forbidden_chars() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    struct Text {
+        #[facet(xml(attribute))]
+        id: String,
+        body: String,
+    }
+
+    let nul = Text {
+        id: "1".to_string(),
+        body: "a\0b".to_string(),
+    };
+    assert!(matches!(
+        to_string(&nul),
+        Err(XmlError::ForbiddenChar('\0'))
+    ));
+
+    let escape = Text {
+        id: "\u{1b}".to_string(),
+        body: String::new(),
+    };
+    assert!(matches!(
+        to_string(&escape),
+        Err(XmlError::ForbiddenChar('\u{1b}'))
+    ));
+    assert!(matches!(
+        to_string(&'\u{FFFE}'),
+        Err(XmlError::ForbiddenChar(_))
+    ));
+}
+
+#[test]
+fn test_arrays() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Point {
+        #[facet(rename = "c")]
+        coordinates: [i32; 3],
+        tags: Option<Vec<String>>,
+    }
+
+    let point = Point {
+        coordinates: [1, -2, 3],
+        tags: Some(vec!["a".to_string()]),
+    };
+    let xml = to_string(&point)?;
+    assert_eq!(
+        xml,
+        "<Point><c>1</c><c>-2</c><c>3</c><tags>a</tags></Point>"
+    );
+    assert_eq!(from_str::<Point>(&xml)?, point);
+
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_shapes() {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Pair(u8, u8);
+
+    #[derive(Debug, PartialEq, Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Shape {
+        Line(Pair),
+        Rect(u8, u8),
+    }
+
+    #[derive(Debug, Facet)]
+    struct Drawing {
+        pair: Option<Pair>,
+        tuple: Option<(u8, u8)>,
+        shape: Option<Shape>,
+        grid: Vec<[u8; 2]>,
+        named: BTreeMap<String, Vec<u8>>,
+    }
+
+    let empty = || Drawing {
+        pair: None,
+        tuple: None,
+        shape: None,
+        grid: Vec::new(),
+        named: BTreeMap::new(),
+    };
+    let unsupported = |drawing: Drawing, name: &str| match to_string(&drawing) {
+        Err(XmlError::UnsupportedShape(shape)) => assert_eq!(shape.to_string(), name),
+        other => panic!("expected {} to be unsupported, got {:?}", name, other),
+    };
+
+    // Tuple-like fields have no names to be elements
+    unsupported(
+        Drawing {
+            pair: Some(Pair(1, 2)),
+            ..empty()
+        },
+        "Pair",
+    );
+    unsupported(
+        Drawing {
+            tuple: Some((1, 2)),
+            ..empty()
+        },
+        "(u8, u8)",
+    );
+    unsupported(
+        Drawing {
+            shape: Some(Shape::Rect(1, 2)),
+            ..empty()
+        },
+        "Shape",
+    );
+    // Lists of lists, and lists that aren't fields, would run their items together
+    unsupported(
+        Drawing {
+            grid: vec![[1, 2]],
+            ..empty()
+        },
+        "[u8; 2]",
+    );
+    unsupported(
+        Drawing {
+            named: BTreeMap::from([("a".to_string(), vec![1])]),
+            ..empty()
+        },
+        "Vec<u8>",
+    );
+    assert!(matches!(
+        to_string(&[1u8, 2]),
+        Err(XmlError::UnsupportedShape(_))
+    ));
+    assert!(matches!(
+        to_string(&Pair(1, 2)),
+        Err(XmlError::UnsupportedShape(_))
+    ));
+
+    assert!(matches!(
+        from_str::<Pair>("<Pair>1</Pair>"),
+        Err(XmlError::UnsupportedShape(_))
+    ));
+    assert!(matches!(
+        from_str::<Shape>("<Shape><Rect>1</Rect></Shape>"),
+        Err(XmlError::UnsupportedShape(_))
+    ));
+}
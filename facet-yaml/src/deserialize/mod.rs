@@ -5,16 +5,16 @@ compile_error!("feature `alloc` is required");
 
 mod error;
 
-use alloc::{
-    format,
-    string::{String, ToString},
-};
+use alloc::{borrow::Cow, format, string::ToString};
 use error::AnyErr;
-use facet_core::{Def, Facet, Type, UserType};
-use facet_reflect::Wip;
+use facet_core::{Def, Facet, Field, ShapeAttribute, StructKind, Type, UserType};
+use facet_reflect::{ScalarType, Wip};
 use yaml_rust2::{Yaml, YamlLoader};
 
 /// Deserializes a YAML string into a value of type `T` that implements `Facet`.
+///
+/// Anchors and aliases are resolved while loading, so an aliased node deserializes like a
+/// copy of the node it refers to.
 pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(yaml: &'input str) -> Result<T, AnyErr> {
     let wip = Wip::alloc::<T>()?;
     let wip = from_str_value(wip, yaml)?;
@@ -38,17 +38,13 @@ fn yaml_type(ty: &Yaml) -> &'static str {
     }
 }
 
-fn yaml_to_u64(ty: &Yaml) -> Result<u64, AnyErr> {
+/// The text of a scalar, for types that parse themselves from a string
+fn scalar_text(ty: &Yaml) -> Result<Cow<'_, str>, AnyErr> {
     match ty {
-        Yaml::Real(r) => r
-            .parse::<u64>()
-            .map_err(|_| AnyErr("Failed to parse real as u64".into())),
-        Yaml::Integer(i) => Ok(*i as u64),
-        Yaml::String(s) => s
-            .parse::<u64>()
-            .map_err(|_| AnyErr("Failed to parse string as u64".into())),
-        Yaml::Boolean(b) => Ok(if *b { 1 } else { 0 }),
-        _ => Err(AnyErr(format!("Cannot convert {} to u64", yaml_type(ty)))),
+        Yaml::String(s) | Yaml::Real(s) => Ok(Cow::Borrowed(s)),
+        Yaml::Integer(i) => Ok(Cow::Owned(i.to_string())),
+        Yaml::Boolean(b) => Ok(Cow::Owned(b.to_string())),
+        _ => Err(AnyErr(format!("Expected a scalar, got: {}", yaml_type(ty)))),
     }
 }
 
@@ -63,9 +59,214 @@ fn from_str_value<'a>(wip: Wip<'a>, yaml: &str) -> Result<Wip<'a>, AnyErr> {
 fn deserialize_value<'a>(mut wip: Wip<'a>, value: &Yaml) -> Result<Wip<'a>, AnyErr> {
     let shape = wip.shape();
 
-    // First check the type system (Type)
-    if let Type::User(UserType::Struct(_)) = &shape.ty {
-        if let Yaml::Hash(hash) = value {
+    // Options first: `~` and `null` are `None`, anything else is the inner value
+    if let Def::Option(_) = shape.def {
+        if let Yaml::Null = value {
+            return Ok(wip.put_default()?);
+        }
+        wip = wip.push_some()?;
+        wip = deserialize_value(wip, value)?;
+        return Ok(wip.pop()?);
+    }
+
+    // Transparent wrappers are written as their inner value
+    if shape.attributes.contains(&ShapeAttribute::Transparent) {
+        wip = wip.field(0)?;
+        wip = deserialize_value(wip, value)?;
+        return Ok(wip.pop()?);
+    }
+
+    // Then check the type system (Type)
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            return deserialize_fields(wip, sd.kind, sd.fields, value);
+        }
+        Type::User(UserType::Enum(_)) => return deserialize_enum(wip, value),
+        _ => {}
+    }
+
+    // Then check the def system (Def)
+    match shape.def {
+        Def::Scalar(_) => deserialize_scalar(wip, value),
+        Def::List(_) => {
+            let Yaml::Array(items) = value else {
+                return Err(AnyErr(format!(
+                    "Expected a YAML array, got: {}",
+                    yaml_type(value)
+                )));
+            };
+            if items.is_empty() {
+                return Ok(wip.put_empty_list()?);
+            }
+            wip = wip.begin_pushback()?;
+            for item in items {
+                wip = wip.push()?;
+                wip = deserialize_value(wip, item)?;
+                wip = wip.pop()?;
+            }
+            Ok(wip)
+        }
+        Def::Array(array_def) => {
+            let Yaml::Array(items) = value else {
+                return Err(AnyErr(format!(
+                    "Expected a YAML array, got: {}",
+                    yaml_type(value)
+                )));
+            };
+            // Arrays are built one item at a time, so they must get exactly as many
+            if items.len() != array_def.n {
+                return Err(AnyErr(format!(
+                    "Expected {} items for {}, got {}",
+                    array_def.n,
+                    shape,
+                    items.len()
+                )));
+            }
+            wip = wip.begin_pushback()?;
+            for item in items {
+                wip = wip.push()?;
+                wip = deserialize_value(wip, item)?;
+                wip = wip.pop()?;
+            }
+            Ok(wip)
+        }
+        Def::Set(_) => {
+            let Yaml::Array(items) = value else {
+                return Err(AnyErr(format!(
+                    "Expected a YAML array, got: {}",
+                    yaml_type(value)
+                )));
+            };
+            // Starts out with an empty set, even without items
+            wip = wip.begin_pushback()?;
+            for item in items {
+                wip = wip.push()?;
+                wip = deserialize_value(wip, item)?;
+                wip = wip.pop()?;
+            }
+            Ok(wip)
+        }
+        Def::Map(_) => {
+            let Yaml::Hash(hash) = value else {
+                return Err(AnyErr(format!(
+                    "Expected a YAML hash, got: {}",
+                    yaml_type(value)
+                )));
+            };
+            if hash.is_empty() {
+                return Ok(wip.put_empty_map()?);
+            }
+            wip = wip.begin_map_insert()?;
+            for (k, v) in hash {
+                wip = wip.push_map_key()?;
+                wip = deserialize_value(wip, k)?;
+                wip = wip.push_map_value()?;
+                wip = deserialize_value(wip, v)?;
+                wip = wip.pop()?;
+            }
+            Ok(wip)
+        }
+        _ => Err(AnyErr(format!("Unsupported type: {:?}", shape))),
+    }
+}
+
+fn deserialize_scalar<'a>(wip: Wip<'a>, value: &Yaml) -> Result<Wip<'a>, AnyErr> {
+    let shape = wip.shape();
+    let wip = match ScalarType::try_from_shape(shape) {
+        Some(ScalarType::Unit) => wip.put(())?,
+        Some(ScalarType::Bool) => {
+            let b = value
+                .as_bool()
+                .ok_or_else(|| AnyErr(format!("Expected boolean, got: {}", yaml_type(value))))?;
+            wip.put(b)?
+        }
+        Some(ScalarType::String) => wip.put(scalar_text(value)?.into_owned())?,
+        Some(ScalarType::CowStr) => wip.put(Cow::<str>::Owned(scalar_text(value)?.into_owned()))?,
+        Some(ScalarType::F32 | ScalarType::F64) => {
+            // `as_f64` knows about `.inf` and `.nan`, but leaves integers alone
+            let f = match value {
+                Yaml::Integer(i) => Some(*i as f64),
+                _ => value.as_f64(),
+            };
+            let f =
+                f.ok_or_else(|| AnyErr(format!("Expected number, got: {}", yaml_type(value))))?;
+            wip.try_put_f64(f)?
+        }
+        // Integers too: big ones only fit in a YAML real, and `parse` checks the range
+        _ => {
+            let text = scalar_text(value)?;
+            wip.parse(&text)
+                .map_err(|e| AnyErr(format!("Failed to parse {text:?} as {shape}: {e}")))?
+        }
+    };
+    Ok(wip)
+}
+
+fn deserialize_enum<'a>(mut wip: Wip<'a>, value: &Yaml) -> Result<Wip<'a>, AnyErr> {
+    // Unit variants are written as their name, others as a hash with a single entry
+    let (name, fields) = match value {
+        Yaml::String(name) => (name.as_str(), None),
+        Yaml::Hash(hash) if hash.len() == 1 => {
+            let (k, v) = hash.iter().next().unwrap();
+            let name = k
+                .as_str()
+                .ok_or_else(|| AnyErr(format!("Expected variant name, got: {}", yaml_type(k))))?;
+            (name, Some(v))
+        }
+        _ => {
+            return Err(AnyErr(format!(
+                "Expected a variant name or a hash with a single entry, got: {}",
+                yaml_type(value)
+            )));
+        }
+    };
+
+    wip = wip.variant_named(name)?;
+    // Safe to unwrap because the variant got just selected
+    let variant = wip.selected_variant().unwrap();
+    let kind = variant.data.kind;
+    let variant_fields = variant.data.fields;
+
+    match fields {
+        None => Ok(wip.fill_defaults()?),
+        // Newtype variants are written as their only field
+        Some(value)
+            if matches!(kind, StructKind::Tuple | StructKind::TupleStruct)
+                && variant_fields.len() == 1 =>
+        {
+            wip = wip.field(0)?;
+            wip = deserialize_value(wip, value)?;
+            Ok(wip.pop()?)
+        }
+        Some(value) => deserialize_fields(wip, kind, variant_fields, value),
+    }
+}
+
+/// Fills the fields of a struct or of the selected variant: a hash for named fields, an
+/// array for tuple fields
+fn deserialize_fields<'a>(
+    mut wip: Wip<'a>,
+    kind: StructKind,
+    fields: &'static [Field],
+    value: &Yaml,
+) -> Result<Wip<'a>, AnyErr> {
+    match (kind, value) {
+        (StructKind::Unit, _) => {}
+        (StructKind::Tuple | StructKind::TupleStruct, Yaml::Array(items)) => {
+            if items.len() > fields.len() {
+                return Err(AnyErr(format!(
+                    "Expected at most {} elements, got {}",
+                    fields.len(),
+                    items.len()
+                )));
+            }
+            for (index, item) in items.iter().enumerate() {
+                wip = wip.field(index)?;
+                wip = deserialize_value(wip, item)?;
+                wip = wip.pop()?;
+            }
+        }
+        (_, Yaml::Hash(hash)) => {
             for (k, v) in hash {
                 let k = k
                     .as_str()
@@ -79,32 +280,19 @@ fn deserialize_value<'a>(mut wip: Wip<'a>, value: &Yaml) -> Result<Wip<'a>, AnyE
                 wip = deserialize_value(wip, v)?;
                 wip = wip.pop().map_err(|e| AnyErr(e.to_string()))?;
             }
-        } else {
-            return Err(AnyErr(format!("Expected a YAML hash, got: {:?}", value)));
         }
-        return Ok(wip);
-    }
-
-    // Then check the def system (Def)
-    match shape.def {
-        Def::Scalar(_) => {
-            if shape.is_type::<u64>() {
-                let u = yaml_to_u64(value)?;
-                wip = wip.put(u).map_err(|e| AnyErr(e.to_string()))?;
-            } else if shape.is_type::<String>() {
-                let s = value
-                    .as_str()
-                    .ok_or_else(|| AnyErr(format!("Expected string, got: {}", yaml_type(value))))?
-                    .to_string();
-                wip = wip.put(s).map_err(|e| AnyErr(e.to_string()))?;
-            } else {
-                return Err(AnyErr(format!("Unsupported scalar type: {}", shape)));
-            }
+        _ => {
+            return Err(AnyErr(format!(
+                "Expected a YAML {}, got: {}",
+                if kind == StructKind::Struct {
+                    "hash"
+                } else {
+                    "array"
+                },
+                yaml_type(value)
+            )));
         }
-        Def::List(_) => todo!(),
-        Def::Map(_) => todo!(),
-        // Enum has been moved to Type system
-        _ => return Err(AnyErr(format!("Unsupported type: {:?}", shape))),
     }
-    Ok(wip)
+    // Fields that weren't in the document get their default, or `None` for options
+    Ok(wip.fill_defaults()?)
 }
@@ -1 +1,31 @@
-//! Errors from parsing into YAML documents.
+//! Errors from writing YAML documents.
+
+use alloc::string::String;
+
+use facet_serialize::Unsupported;
+
+/// Any error from serializing YAML.
+#[derive(Debug, Clone)]
+pub enum YamlSerError {
+    /// The emitter couldn't write the document.
+    Emit(String),
+    /// The value, or something in it, can't be serialized at all.
+    Unsupported(Unsupported),
+}
+
+impl core::fmt::Display for YamlSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Emit(message) => write!(f, "Error emitting YAML: {message}"),
+            Self::Unsupported(unsupported) => write!(f, "{unsupported}"),
+        }
+    }
+}
+
+impl core::error::Error for YamlSerError {}
+
+impl From<Unsupported> for YamlSerError {
+    fn from(unsupported: Unsupported) -> Self {
+        Self::Unsupported(unsupported)
+    }
+}
@@ -1,9 +1,236 @@
 //! Create and/or write YAML strings from Rust values.
 
+#[cfg(not(feature = "alloc"))]
+compile_error!("feature `alloc` is required");
+
+mod error;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+pub use error::YamlSerError;
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, serialize_iterative};
+use yaml_rust2::{Yaml, YamlEmitter, yaml::Hash};
+
+/// Serializer for YAML values.
+///
+/// Builds a YAML tree as values come in; [`to_string`] then writes it out in block style.
+pub struct YamlSerializer {
+    /// Arrays and hashes that are still being filled, innermost last.
+    stack: Vec<Container>,
+    /// The outermost value, once it's finished.
+    root: Option<Yaml>,
+}
+
+/// An array or hash that is still being filled.
+enum Container {
+    Array(Vec<Yaml>),
+    /// The entries so far, and the key waiting for its value.
+    Hash(Hash, Option<Yaml>),
+}
+
+impl YamlSerializer {
+    /// Create a new serializer.
+    pub fn new() -> Self {
+        Self {
+            stack: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// Get the serialized YAML tree, `~` if nothing was serialized.
+    pub fn into_yaml(self) -> Yaml {
+        self.root.unwrap_or(Yaml::Null)
+    }
+
+    /// Add a finished value to the innermost container: the next element of an array, or
+    /// the key or value of the next hash entry.
+    fn push(&mut self, value: Yaml) {
+        match self.stack.last_mut() {
+            None => self.root = Some(value),
+            Some(Container::Array(items)) => items.push(value),
+            Some(Container::Hash(hash, key)) => match key.take() {
+                Some(key) => {
+                    hash.insert(key, value);
+                }
+                None => *key = Some(value),
+            },
+        }
+    }
+
+    /// Finish the innermost container.
+    fn end(&mut self) {
+        let value = match self.stack.pop() {
+            Some(Container::Array(items)) => Yaml::Array(items),
+            Some(Container::Hash(hash, _)) => Yaml::Hash(hash),
+            None => return,
+        };
+        self.push(value);
+    }
+
+    /// Add an integer. Those outside of `i64` are written as-is: YAML has no limit on
+    /// integers, but `Yaml::Integer` does.
+    fn push_integer<N: TryInto<i64> + ToString + Copy>(&mut self, value: N) {
+        let yaml = match value.try_into() {
+            Ok(value) => Yaml::Integer(value),
+            Err(_) => Yaml::Real(value.to_string()),
+        };
+        self.push(yaml);
+    }
+}
+
+impl Default for YamlSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer for YamlSerializer {
+    type Error = YamlSerError;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.push_integer(value);
+        Ok(())
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.push_integer(value);
+        Ok(())
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.push_integer(value);
+        Ok(())
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.push_integer(value);
+        Ok(())
+    }
+
+    fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        let text = if value.is_nan() {
+            ".nan".to_string()
+        } else if value.is_infinite() {
+            if value > 0.0 { ".inf" } else { "-.inf" }.to_string()
+        } else {
+            // `Debug` keeps the `.0` of whole numbers, so they read back as floats
+            format!("{value:?}")
+        };
+        self.push(Yaml::Real(text));
+        Ok(())
+    }
+
+    fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.push(Yaml::Boolean(value));
+        Ok(())
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.push(Yaml::String(value.to_string()));
+        Ok(())
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.push(Yaml::String(value.to_string()));
+        Ok(())
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        let items = value.iter().map(|b| Yaml::Integer(*b as i64)).collect();
+        self.push(Yaml::Array(items));
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.push(Yaml::Null);
+        Ok(())
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.push(Yaml::Null);
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'static str,
+    ) -> Result<(), Self::Error> {
+        self.push(Yaml::String(variant_name.to_string()));
+        Ok(())
+    }
+
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(Container::Hash(Hash::new(), None));
+        Ok(())
+    }
+
+    fn serialize_field_name(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.push(Yaml::String(name.to_string()));
+        Ok(())
+    }
+
+    fn start_array(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        let items = Vec::with_capacity(len.unwrap_or(0));
+        self.stack.push(Container::Array(items));
+        Ok(())
+    }
+
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.stack.push(Container::Hash(Hash::new(), None));
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end();
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end();
+        Ok(())
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end();
+        Ok(())
+    }
+}
+
 /// Serialize any `Facet` type to a YAML string.
 #[cfg(feature = "alloc")]
-pub fn to_string<'a, T: facet_core::Facet<'a>>(
-    _value: &'a T,
-) -> Result<alloc::string::String, core::convert::Infallible> {
-    todo!()
+pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> Result<String, YamlSerError> {
+    peek_to_string(Peek::new(value))
+}
+
+/// Serialize a `Peek` instance to a YAML string.
+///
+/// The document is written in block style, with strings quoted when they would otherwise
+/// read back as something else (`"true"`, `"1.0"`, `"~"`…) and multi-line strings as
+/// literal blocks.
+#[cfg(feature = "alloc")]
+pub fn peek_to_string(peek: Peek<'_, '_>) -> Result<String, YamlSerError> {
+    let mut serializer = YamlSerializer::new();
+    serialize_iterative(peek, &mut serializer)?;
+    let yaml = serializer.into_yaml();
+
+    let mut output = String::new();
+    let mut emitter = YamlEmitter::new(&mut output);
+    emitter.multiline_strings(true);
+    emitter
+        .dump(&yaml)
+        .map_err(|e| YamlSerError::Emit(e.to_string()))?;
+
+    // The emitter always starts with a `---` document marker, which config files go without
+    let body = output.strip_prefix("---").unwrap_or(&output);
+    let body = body.strip_prefix(['\n', ' ']).unwrap_or(body);
+    let mut body = body.to_string();
+    body.push('\n');
+    Ok(body)
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use eyre::Result;
 use facet::Facet;
 
@@ -25,3 +27,162 @@ fn test_deserialize_person() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_deserialize_nested() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Team {
+        name: String,
+        lead: Option<Person>,
+        members: Vec<Person>,
+        scores: BTreeMap<String, u32>,
+    }
+
+    let yaml = r#"
+            name: Core
+            members:
+              - name: Alice
+                age: 30
+              - name: Bob
+                age: 25
+            scores:
+              alice: 10
+              bob: 7
+        "#;
+
+    let team: Team = facet_yaml::from_str(yaml)?;
+    assert_eq!(
+        team,
+        Team {
+            name: "Core".to_string(),
+            lead: None,
+            members: vec![
+                Person {
+                    name: "Alice".to_string(),
+                    age: 30
+                },
+                Person {
+                    name: "Bob".to_string(),
+                    age: 25
+                },
+            ],
+            scores: [("alice".to_string(), 10), ("bob".to_string(), 7)]
+                .into_iter()
+                .collect(),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_anchors() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Pair {
+        first: Person,
+        second: Person,
+    }
+
+    let yaml = r#"
+            first: &someone
+              name: Alice
+              age: 30
+            second: *someone
+        "#;
+
+    let pair: Pair = facet_yaml::from_str(yaml)?;
+    assert_eq!(pair.first, pair.second);
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_default() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Settings {
+        #[facet(default)]
+        retries: u8,
+        verbose: bool,
+    }
+
+    let settings: Settings = facet_yaml::from_str("verbose: true")?;
+    assert_eq!(
+        settings,
+        Settings {
+            retries: 0,
+            verbose: true
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_unknown_field() {
+    facet_testhelpers::setup();
+
+    let result: Result<Person, _> = facet_yaml::from_str("name: Alice\nage: 30\nheight: 170");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_deserialize_array() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Color {
+        rgb: [u8; 3],
+        palette: Vec<[u8; 3]>,
+    }
+
+    let color: Color = facet_yaml::from_str("rgb: [255, 128, 0]\npalette: [[0, 0, 0], [1, 2, 3]]")?;
+    assert_eq!(
+        color,
+        Color {
+            rgb: [255, 128, 0],
+            palette: vec![[0, 0, 0], [1, 2, 3]],
+        }
+    );
+
+    let err = facet_yaml::from_str::<Color>("rgb: [255, 128]\npalette: []").unwrap_err();
+    assert_eq!(err.to_string(), "Expected 3 items for [u8; 3], got 2");
+    let err = facet_yaml::from_str::<Color>("rgb: [0, 0, 0]\npalette: [[0, 0, 0, 0]]").unwrap_err();
+    assert_eq!(err.to_string(), "Expected 3 items for [u8; 3], got 4");
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_set() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Tags {
+        names: std::collections::HashSet<String>,
+        ids: std::collections::BTreeSet<u32>,
+    }
+
+    let tags: Tags = facet_yaml::from_str(
+        r#"
+            names: [alpha, beta, alpha]
+            ids: []
+        "#,
+    )?;
+    assert_eq!(
+        tags,
+        Tags {
+            names: ["alpha".to_string(), "beta".to_string()].into(),
+            ids: Default::default(),
+        }
+    );
+
+    assert!(facet_yaml::from_str::<Tags>("names: alpha\nids: []").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use facet_core::Facet;
+use facet_reflect::Peek;
+use facet_testkit::{Format, conformance};
+
+struct Yaml;
+
+impl Format for Yaml {
+    const NAME: &'static str = "YAML";
+
+    fn serialize(peek: Peek<'_, '_>) -> Result<Vec<u8>, String> {
+        facet_yaml::peek_to_string(peek)
+            .map(String::into_bytes)
+            .map_err(|e| e.to_string())
+    }
+
+    fn deserialize<'input, T: Facet<'input>>(input: &'input [u8]) -> Result<T, String> {
+        let yaml = core::str::from_utf8(input).map_err(|e| e.to_string())?;
+        facet_yaml::from_str(yaml).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn integers() {
+    facet_testhelpers::setup();
+    conformance::integers::<Yaml>();
+}
+
+#[test]
+fn wide_integers() {
+    facet_testhelpers::setup();
+    conformance::wide_integers::<Yaml>();
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+    conformance::floats::<Yaml>();
+}
+
+#[test]
+fn strings() {
+    facet_testhelpers::setup();
+    conformance::strings::<Yaml>();
+}
+
+#[test]
+fn chars() {
+    facet_testhelpers::setup();
+    conformance::chars::<Yaml>();
+}
+
+#[test]
+fn options() {
+    facet_testhelpers::setup();
+    conformance::options::<Yaml>();
+}
+
+#[test]
+fn collections() {
+    facet_testhelpers::setup();
+    conformance::collections::<Yaml>();
+}
+
+#[test]
+fn structs() {
+    facet_testhelpers::setup();
+    conformance::structs::<Yaml>();
+}
+
+#[test]
+fn enums() {
+    facet_testhelpers::setup();
+    conformance::enums::<Yaml>();
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use eyre::Result;
+use facet::Facet;
+
+/// Assert that the YAML written for a value reads back as the same value.
+macro_rules! assert_roundtrip {
+    ($type:ty, $val:expr $(,)?) => {{
+        let value = $val;
+        let serialized = facet_yaml::to_string(&value)?;
+        let deserialized: $type =
+            facet_yaml::from_str(&serialized).map_err(|err| eyre::eyre!("{err}\n{serialized}"))?;
+        assert_eq!(deserialized, value, "{serialized}");
+    }};
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    server: Server,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn test_block_style() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let config = Config {
+        name: "app".to_string(),
+        server: Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        },
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+
+    assert_eq!(
+        facet_yaml::to_string(&config)?,
+        "name: app\nserver:\n  host: localhost\n  port: 8080\ntags:\n  - a\n  - b\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_quoting() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Strings {
+        values: Vec<String>,
+    }
+
+    // Each of these would read back as something else, or not at all, without quotes
+    assert_roundtrip!(
+        Strings,
+        Strings {
+            values: [
+                "true", "1.0", "42", "~", "null", "", " padded ", "a: b", "- x", "#"
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        },
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_multiline_string() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_roundtrip!(
+        Server,
+        Server {
+            host: "first line\nsecond line".to_string(),
+            port: 1,
+        },
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scalars() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Scalars {
+        boolean: bool,
+        character: char,
+        small: i8,
+        big: u64,
+        huge: i128,
+        float: f64,
+        whole: f32,
+    }
+
+    assert_roundtrip!(
+        Scalars,
+        Scalars {
+            boolean: true,
+            character: 'x',
+            small: -3,
+            big: u64::MAX,
+            huge: i128::MIN,
+            float: 1.5,
+            whole: 2.0,
+        },
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_options_and_maps() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        present: Option<u32>,
+        absent: Option<u32>,
+        env: BTreeMap<String, String>,
+        empty: Vec<u32>,
+    }
+
+    assert_roundtrip!(
+        Root,
+        Root {
+            present: Some(1),
+            absent: None,
+            env: [("HOME", "/root"), ("SHELL", "/bin/sh")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            empty: vec![],
+        },
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_enums() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { width: u32, height: u32 },
+        Line(u32, u32),
+    }
+
+    assert_roundtrip!(
+        Vec<Shape>,
+        vec![
+            Shape::Point,
+            Shape::Circle(1.5),
+            Shape::Rect {
+                width: 2,
+                height: 3
+            },
+            Shape::Line(4, 5),
+        ],
+    );
+
+    Ok(())
+}
@@ -8,6 +8,12 @@
 #![cfg_attr(docsrs, feature(prelude_import))]
 #![cfg_attr(docsrs, allow(internal_features))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Lets the derive's `::facet::` paths resolve within this crate too
+extern crate self as facet;
+
 #[cfg(docsrs)]
 pub mod sample_generated_code;
 
@@ -20,4 +26,9 @@ pub use facet_reflect::*;
 
 pub mod hacking;
 
+#[cfg(feature = "alloc")]
+mod value;
+#[cfg(feature = "alloc")]
+pub use value::*;
+
 pub use static_assertions;
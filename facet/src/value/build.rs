@@ -0,0 +1,367 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_core::{Def, SequenceType, Shape, Type, UserType};
+use facet_reflect::{ReflectError, ScalarType, Wip};
+
+use super::{FieldValue, Value};
+use crate::Facet;
+
+/// Builds a `T` out of a [`Value`]
+pub fn from_value<'facet, T: Facet<'facet>>(value: &Value) -> Result<T, ValueError> {
+    let wip = value.put_into(Wip::alloc::<T>()?)?;
+    Ok(wip.build()?.materialize()?)
+}
+
+impl Value {
+    /// Fills the current frame of `wip` with this value, converted to the frame's shape
+    pub fn put_into<'facet>(&self, wip: Wip<'facet>) -> Result<Wip<'facet>, ValueError> {
+        Builder { path: Vec::new() }.put(wip, self)
+    }
+
+    /// Builds this value into a new [`Wip`] of `shape`, for shapes only known at runtime
+    pub fn into_wip<'facet>(self, shape: &'static Shape) -> Result<Wip<'facet>, ValueError> {
+        self.put_into(Wip::alloc_shape(shape)?)
+    }
+}
+
+/// An error encountered while building a value out of a [`Value`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueError {
+    /// Path to the value that couldn't be built, as a JSON Pointer
+    pub path: String,
+    /// Type of error
+    pub kind: ValueErrorKind,
+}
+
+impl core::fmt::Display for ValueError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            ValueErrorKind::Mismatch { shape, found } => {
+                write!(f, "Can't build a {shape} out of {found}")?
+            }
+            ValueErrorKind::OutOfRange { shape } => write!(f, "Number out of range for a {shape}")?,
+            ValueErrorKind::UnknownField { shape, field } => {
+                write!(f, "{shape} has no field `{field}`")?
+            }
+            ValueErrorKind::UnknownVariant { shape, variant } => {
+                write!(f, "{shape} has no variant `{variant}`")?
+            }
+            ValueErrorKind::Unsupported { shape } => {
+                write!(f, "Don't know how to build a {shape}")?
+            }
+            ValueErrorKind::Reflect(e) => write!(f, "{e}")?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " (at {})", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ValueError {}
+
+impl From<ReflectError> for ValueError {
+    fn from(e: ReflectError) -> Self {
+        Self {
+            path: String::new(),
+            kind: ValueErrorKind::Reflect(e),
+        }
+    }
+}
+
+/// Type of error
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ValueErrorKind {
+    /// The value isn't of a kind the shape can be built out of: a list for a struct, say
+    Mismatch {
+        /// The shape to build
+        shape: &'static Shape,
+        /// What kind of value there was instead
+        found: &'static str,
+    },
+
+    /// An integer doesn't fit in the shape's type
+    OutOfRange {
+        /// The shape to build
+        shape: &'static Shape,
+    },
+
+    /// The value has a field the struct or variant doesn't
+    UnknownField {
+        /// The struct or enum
+        shape: &'static Shape,
+        /// The field's name
+        field: String,
+    },
+
+    /// The value is a variant the enum doesn't have
+    UnknownVariant {
+        /// The enum
+        shape: &'static Shape,
+        /// The variant's name
+        variant: String,
+    },
+
+    /// Values of this shape can't be built at all: borrowed data, opaque types…
+    Unsupported {
+        /// The shape in question
+        shape: &'static Shape,
+    },
+
+    /// Building the value failed
+    Reflect(ReflectError),
+}
+
+struct Builder {
+    /// JSON Pointer segments, already escaped
+    path: Vec<String>,
+}
+
+impl Builder {
+    /// Fills the current frame with `value`
+    fn put<'facet>(&mut self, wip: Wip<'facet>, value: &Value) -> Result<Wip<'facet>, ValueError> {
+        // The innermost failure knows best where it happened
+        self.put_inner(wip, value).map_err(|mut e| {
+            if e.path.is_empty() {
+                e.path = self.path.iter().map(|s| format!("/{s}")).collect();
+            }
+            e
+        })
+    }
+
+    fn put_inner<'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        value: &Value,
+    ) -> Result<Wip<'facet>, ValueError> {
+        let shape = wip.shape();
+        if shape == Value::SHAPE {
+            return Ok(wip.put(value.clone())?);
+        }
+
+        match shape.def {
+            Def::Option(_) => {
+                return match value {
+                    Value::Unit => Ok(wip.put_default()?),
+                    value => Ok(self.put(wip.push_some()?, value)?.pop()?),
+                };
+            }
+            Def::Scalar(_) => return self.put_scalar(wip, value),
+            // Smart pointers and transparent wrappers are built out of their inner value
+            _ if shape.inner.is_some() => return self.put_wrapped(wip, value),
+            Def::List(_) | Def::Array(_) => {
+                return match value {
+                    Value::List(items) => self.items(wip, items.iter().map(Cow::Borrowed)),
+                    Value::Bytes(bytes) => {
+                        let items = bytes.iter().map(|&b| Cow::Owned(Value::U64(b.into())));
+                        self.items(wip, items)
+                    }
+                    _ => Err(mismatch(shape, value)),
+                };
+            }
+            Def::Map(_) => {
+                let Value::Map(entries) = value else {
+                    return Err(mismatch(shape, value));
+                };
+                if entries.is_empty() {
+                    return Ok(wip.put_empty_map()?);
+                }
+                wip = wip.begin_map_insert()?;
+                for entry in entries {
+                    self.path.push(segment(&entry.key));
+                    wip = self.put(wip.push_map_key()?, &entry.key)?;
+                    wip = self.put(wip.push_map_value()?, &entry.value)?.pop()?;
+                    self.path.pop();
+                }
+                return Ok(wip);
+            }
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(ty)) => match value {
+                Value::Unit if ty.fields.is_empty() => Ok(wip),
+                Value::Struct(fields) => self.fields(wip, fields),
+                _ => Err(mismatch(shape, value)),
+            },
+            Type::User(UserType::Enum(_)) => {
+                let Value::Variant { name, fields } = value else {
+                    return Err(mismatch(shape, value));
+                };
+                if wip.find_variant(name).is_none() {
+                    return Err(error(ValueErrorKind::UnknownVariant {
+                        shape,
+                        variant: name.clone(),
+                    }));
+                }
+                let wip = wip.variant_named(name)?;
+                self.fields(wip, fields)
+            }
+            Type::Sequence(SequenceType::Tuple(_)) => match value {
+                Value::List(items) => self.items(wip, items.iter().map(Cow::Borrowed)),
+                _ => Err(mismatch(shape, value)),
+            },
+            _ => Err(error(ValueErrorKind::Unsupported { shape })),
+        }
+    }
+
+    /// Fills a list, an array or a tuple
+    fn items<'a, 'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        items: impl Iterator<Item = Cow<'a, Value>>,
+    ) -> Result<Wip<'facet>, ValueError> {
+        let mut items = items.peekable();
+        if items.peek().is_none() {
+            if let Def::List(_) = wip.shape().def {
+                return Ok(wip.put_empty_list()?);
+            }
+        }
+        wip = wip.begin_pushback()?;
+        for (i, item) in items.enumerate() {
+            self.path.push(i.to_string());
+            wip = self.put(wip.push()?, &item)?.pop()?;
+            self.path.pop();
+        }
+        Ok(wip)
+    }
+
+    /// Fills the fields of a struct, or of the variant just selected
+    fn fields<'facet>(
+        &mut self,
+        mut wip: Wip<'facet>,
+        fields: &[FieldValue],
+    ) -> Result<Wip<'facet>, ValueError> {
+        for field in fields {
+            let Some(index) = wip.field_index(&field.name) else {
+                return Err(error(ValueErrorKind::UnknownField {
+                    shape: wip.shape(),
+                    field: field.name.clone(),
+                }));
+            };
+            self.path
+                .push(field.name.replace('~', "~0").replace('/', "~1"));
+            wip = self.put(wip.field(index)?, &field.value)?.pop()?;
+            self.path.pop();
+        }
+        Ok(wip)
+    }
+
+    /// Builds the inner value of a smart pointer or transparent wrapper, then the wrapper
+    /// out of that
+    fn put_wrapped<'facet>(
+        &mut self,
+        wip: Wip<'facet>,
+        value: &Value,
+    ) -> Result<Wip<'facet>, ValueError> {
+        let shape = wip.shape();
+        let Some(inner) = shape.inner else {
+            return Err(error(ValueErrorKind::Unsupported { shape }));
+        };
+        // A transparent struct can have its one field filled in like any other, unless it's a
+        // scalar with invariants to check, like `NonZero`
+        match (shape.ty, shape.def) {
+            (_, Def::Scalar(_)) => {}
+            (Type::User(UserType::Struct(ty)), _) if ty.fields.len() == 1 => {
+                return Ok(self.put(wip.field(0)?, value)?.pop()?);
+            }
+            _ => {}
+        }
+        let inner = self.put(Wip::alloc_shape(inner.get())?, value)?.build()?;
+        Ok(wip.put_heap_value(inner)?)
+    }
+
+    fn put_scalar<'facet>(
+        &mut self,
+        wip: Wip<'facet>,
+        value: &Value,
+    ) -> Result<Wip<'facet>, ValueError> {
+        let shape = wip.shape();
+
+        macro_rules! int {
+            ($ty:ty) => {{
+                let n = match *value {
+                    Value::U64(n) => <$ty>::try_from(n).ok(),
+                    Value::I64(n) => <$ty>::try_from(n).ok(),
+                    Value::U128(n) => <$ty>::try_from(n).ok(),
+                    Value::I128(n) => <$ty>::try_from(n).ok(),
+                    _ => return Err(mismatch(shape, value)),
+                };
+                wip.put(n.ok_or_else(|| error(ValueErrorKind::OutOfRange { shape }))?)
+            }};
+        }
+
+        let float = || match *value {
+            Value::F64(n) => Ok(n),
+            Value::U64(n) => Ok(n as f64),
+            Value::I64(n) => Ok(n as f64),
+            Value::U128(n) => Ok(n as f64),
+            Value::I128(n) => Ok(n as f64),
+            _ => Err(mismatch(shape, value)),
+        };
+
+        let res = match (ScalarType::try_from_shape(shape), value) {
+            (Some(ScalarType::Unit), Value::Unit) => wip.put(()),
+            (Some(ScalarType::Bool), &Value::Bool(b)) => wip.put(b),
+            (Some(ScalarType::Char), &Value::Char(c)) => wip.put(c),
+            (Some(ScalarType::String), Value::String(s)) => wip.put(s.clone()),
+            (Some(ScalarType::CowStr), Value::String(s)) => wip.put(Cow::<str>::Owned(s.clone())),
+            (Some(ScalarType::U8), _) => int!(u8),
+            (Some(ScalarType::U16), _) => int!(u16),
+            (Some(ScalarType::U32), _) => int!(u32),
+            (Some(ScalarType::U64), _) => int!(u64),
+            (Some(ScalarType::U128), _) => int!(u128),
+            (Some(ScalarType::USize), _) => int!(usize),
+            (Some(ScalarType::I8), _) => int!(i8),
+            (Some(ScalarType::I16), _) => int!(i16),
+            (Some(ScalarType::I32), _) => int!(i32),
+            (Some(ScalarType::I64), _) => int!(i64),
+            (Some(ScalarType::I128), _) => int!(i128),
+            (Some(ScalarType::ISize), _) => int!(isize),
+            (Some(ScalarType::F32), _) => wip.put(float()? as f32),
+            (Some(ScalarType::F64), _) => wip.put(float()?),
+            // Anything else that reads from text (IP addresses, UUIDs, paths…)
+            (_, Value::String(s)) if shape.vtable.parse.is_some() => wip.parse(s),
+            _ if shape.inner.is_some() => return self.put_wrapped(wip, value),
+            (Some(ScalarType::Str), _) => return Err(error(ValueErrorKind::Unsupported { shape })),
+            (None, _) if shape.vtable.parse.is_none() => {
+                return Err(error(ValueErrorKind::Unsupported { shape }));
+            }
+            _ => return Err(mismatch(shape, value)),
+        };
+        Ok(res?)
+    }
+}
+
+fn error(kind: ValueErrorKind) -> ValueError {
+    ValueError {
+        path: String::new(),
+        kind,
+    }
+}
+
+fn mismatch(shape: &'static Shape, value: &Value) -> ValueError {
+    error(ValueErrorKind::Mismatch {
+        shape,
+        found: value.kind(),
+    })
+}
+
+/// The JSON Pointer segment for a map entry
+fn segment(key: &Value) -> String {
+    let key = match key {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::U64(n) => n.to_string(),
+        Value::I64(n) => n.to_string(),
+        Value::U128(n) => n.to_string(),
+        Value::I128(n) => n.to_string(),
+        Value::Char(c) => c.to_string(),
+        key => format!("{key:?}"),
+    };
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,102 @@
+//! An owned value of any shape, for when the shape isn't known until runtime
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Facet;
+
+#[cfg(feature = "reflect")]
+mod build;
+#[cfg(feature = "reflect")]
+pub use build::*;
+
+#[cfg(feature = "reflect")]
+mod peek;
+#[cfg(feature = "reflect")]
+pub use peek::*;
+
+/// An owned value of any shape: its data, without its type
+///
+/// Any `Facet` value can be copied into a `Value` ([`to_value`], or [`PeekExt::to_value`] for
+/// a [`Peek`](crate::Peek)) and built back from it ([`from_value`]) without losing anything —
+/// structs keep the names of their fields and enums the name of their variant, while
+/// transparent wrappers and smart pointers are seen through. A `Value` is itself `Facet`, so it can stand in for the part of a type whose shape
+/// is only known at runtime; it's copied and built as-is rather than as an enum.
+///
+/// `None` is [`Value::Unit`] and `Some` is whatever it holds, so the only values that don't
+/// come back the same are `Some(())` and `Some(None)`, which come back as `None`.
+#[derive(Debug, Clone, PartialEq, Facet)]
+#[repr(u8)]
+pub enum Value {
+    /// `()`, a unit struct, or `None`
+    Unit,
+    /// A `bool`
+    Bool(bool),
+    /// An unsigned integer of up to 64 bits
+    U64(u64),
+    /// A signed integer of up to 64 bits
+    I64(i64),
+    /// A `u128`
+    U128(u128),
+    /// An `i128`
+    I128(i128),
+    /// A floating-point number
+    F64(f64),
+    /// A `char`
+    Char(char),
+    /// A string, or anything else that reads and writes as one: IP addresses, UUIDs…
+    String(String),
+    /// A list, array or slice of bytes
+    Bytes(Vec<u8>),
+    /// A list, array, slice or tuple
+    List(Vec<Value>),
+    /// A map, with its entries in iteration order
+    Map(Vec<MapEntry>),
+    /// A struct; the fields of tuple structs are named `0`, `1`…
+    Struct(Vec<FieldValue>),
+    /// An enum variant
+    Variant {
+        /// The name of the variant
+        name: String,
+        /// Its fields, named like those of structs; empty for unit variants
+        fields: Vec<FieldValue>,
+    },
+}
+
+/// One entry of a [`Value::Map`]
+#[derive(Debug, Clone, PartialEq, Facet)]
+pub struct MapEntry {
+    /// The entry's key
+    pub key: Value,
+    /// The entry's value
+    pub value: Value,
+}
+
+/// One field of a [`Value::Struct`] or [`Value::Variant`]
+#[derive(Debug, Clone, PartialEq, Facet)]
+pub struct FieldValue {
+    /// The field's name
+    pub name: String,
+    /// The field's value
+    pub value: Value,
+}
+
+impl Value {
+    /// Describes what kind of value this is, for error messages
+    #[cfg(feature = "reflect")]
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Value::Unit => "unit",
+            Value::Bool(_) => "a bool",
+            Value::U64(_) | Value::I64(_) | Value::U128(_) | Value::I128(_) => "an integer",
+            Value::F64(_) => "a float",
+            Value::Char(_) => "a char",
+            Value::String(_) => "a string",
+            Value::Bytes(_) => "bytes",
+            Value::List(_) => "a list",
+            Value::Map(_) => "a map",
+            Value::Struct(_) => "a struct",
+            Value::Variant { .. } => "an enum variant",
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use facet_core::{Def, SequenceType, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+
+use super::{FieldValue, MapEntry, Value};
+use crate::Facet;
+
+/// Copies any `Facet` value into a [`Value`]
+pub fn to_value<'facet, T: Facet<'facet>>(value: &T) -> Value {
+    Value::from_peek(Peek::new(value))
+}
+
+/// Adds [`to_value`](PeekExt::to_value) to [`Peek`], which can't have it itself: `Value`
+/// lives in this crate, above `facet-reflect`
+pub trait PeekExt {
+    /// Copies the value into a [`Value`], like [`Value::from_peek`]
+    fn to_value(&self) -> Value;
+}
+
+impl PeekExt for Peek<'_, '_> {
+    fn to_value(&self) -> Value {
+        Value::from_peek(*self)
+    }
+}
+
+impl Value {
+    /// Copies a reflected value into a `Value`
+    ///
+    /// Opaque values are copied as their `Display` text, if they have any, and as
+    /// [`Value::Unit`] otherwise.
+    pub fn from_peek(peek: Peek<'_, '_>) -> Self {
+        if let Ok(value) = peek.get::<Value>() {
+            return value.clone();
+        }
+        if let Some(scalar) = peek.scalar_type() {
+            return from_scalar(peek, scalar).unwrap_or_else(|| text(peek));
+        }
+
+        let shape = peek.shape();
+        match shape.def {
+            Def::Option(_) => {
+                if let Ok(option) = peek.into_option() {
+                    return option.value().map_or(Value::Unit, Value::from_peek);
+                }
+            }
+            Def::Map(_) => {
+                if let Ok(map) = peek.into_map() {
+                    let entries = map.iter().map(|(key, value)| MapEntry {
+                        key: Value::from_peek(key),
+                        value: Value::from_peek(value),
+                    });
+                    return Value::Map(entries.collect());
+                }
+            }
+            Def::SmartPointer(_) => {
+                // A `Weak` whose value is gone has nothing left to copy
+                return peek
+                    .into_smart_pointer()
+                    .ok()
+                    .and_then(|pointer| pointer.borrow_inner())
+                    .map_or(Value::Unit, Value::from_peek);
+            }
+            _ => {}
+        }
+
+        // Lists and arrays, but also slices behind a reference
+        if let Ok(list) = peek.into_list_like() {
+            if list.def().t() == u8::SHAPE {
+                let bytes = list
+                    .iter()
+                    .filter_map(|item| item.get::<u8>().ok().copied());
+                return Value::Bytes(bytes.collect());
+            }
+            return Value::List(list.iter().map(Value::from_peek).collect());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(ty)) => {
+                if ty.kind == StructKind::Unit {
+                    return Value::Unit;
+                }
+                if let Ok(peek_struct) = peek.into_struct() {
+                    // Transparent wrappers (`NonZero`, `OrderedFloat`…) are their one field
+                    if shape.inner.is_some() && ty.fields.len() == 1 {
+                        if let Ok(inner) = peek_struct.field(0) {
+                            return Value::from_peek(inner);
+                        }
+                    }
+                    return Value::Struct(fields(&peek_struct));
+                }
+            }
+            Type::User(UserType::Enum(_)) => {
+                if let Ok(peek_enum) = peek.into_enum() {
+                    if let Ok(variant) = peek_enum.active_variant() {
+                        return Value::Variant {
+                            name: variant.name.to_string(),
+                            fields: fields(&peek_enum),
+                        };
+                    }
+                }
+            }
+            Type::Sequence(SequenceType::Tuple(_)) => {
+                if let Ok(tuple) = peek.into_tuple() {
+                    let items = tuple.fields().map(|(_, item)| Value::from_peek(item));
+                    return Value::List(items.collect());
+                }
+            }
+            _ => {}
+        }
+
+        text(peek)
+    }
+}
+
+/// Copies an opaque value as its `Display` text
+fn text(peek: Peek<'_, '_>) -> Value {
+    match peek.shape().vtable.display {
+        Some(_) => Value::String(peek.to_string()),
+        None => Value::Unit,
+    }
+}
+
+/// Copies every field, including those skipped when serializing
+fn fields<'mem, 'facet>(value: &impl HasFields<'mem, 'facet>) -> Vec<FieldValue> {
+    value
+        .fields()
+        .map(|(field, value)| FieldValue {
+            name: field.name.to_string(),
+            value: Value::from_peek(value),
+        })
+        .collect()
+}
+
+/// Copies the scalars with a `Value` of their own
+fn from_scalar(peek: Peek<'_, '_>, scalar: ScalarType) -> Option<Value> {
+    macro_rules! get {
+        ($ty:ty) => {
+            *peek.get::<$ty>().ok()?
+        };
+    }
+
+    Some(match scalar {
+        ScalarType::Unit => Value::Unit,
+        ScalarType::Bool => Value::Bool(get!(bool)),
+        ScalarType::Char => Value::Char(get!(char)),
+        ScalarType::Str | ScalarType::String | ScalarType::CowStr => {
+            Value::String(peek.as_str()?.to_string())
+        }
+        ScalarType::U8 => Value::U64(get!(u8).into()),
+        ScalarType::U16 => Value::U64(get!(u16).into()),
+        ScalarType::U32 => Value::U64(get!(u32).into()),
+        ScalarType::U64 => Value::U64(get!(u64)),
+        ScalarType::USize => Value::U64(get!(usize) as u64),
+        ScalarType::U128 => Value::U128(get!(u128)),
+        ScalarType::I8 => Value::I64(get!(i8).into()),
+        ScalarType::I16 => Value::I64(get!(i16).into()),
+        ScalarType::I32 => Value::I64(get!(i32).into()),
+        ScalarType::I64 => Value::I64(get!(i64)),
+        ScalarType::ISize => Value::I64(get!(isize) as i64),
+        ScalarType::I128 => Value::I128(get!(i128)),
+        ScalarType::F32 => Value::F64(get!(f32).into()),
+        ScalarType::F64 => Value::F64(get!(f64)),
+        _ => return None,
+    })
+}
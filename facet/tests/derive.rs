@@ -526,3 +526,70 @@ fn core_ops_range() {
     assert_eq!(struct_type.fields[0].name, "start");
     assert_eq!(struct_type.fields[1].name, "end");
 }
+
+#[test]
+fn field_index() {
+    #[derive(Facet)]
+    #[allow(dead_code)]
+    struct Server {
+        port: u16,
+        #[facet(rename = "hostname")]
+        host: String,
+        backup: Option<u16>,
+        tags: Vec<String>,
+    }
+
+    let Type::User(UserType::Struct(struct_type)) = Server::SHAPE.ty else {
+        panic!("expected struct type");
+    };
+    // Derived shapes come with their fields sorted by name
+    assert_eq!(struct_type.fields_by_name, &[2, 1, 0, 3]);
+    assert_eq!(struct_type.field_index("port"), Some(0));
+    assert_eq!(struct_type.field_index("hostname"), Some(1));
+    assert_eq!(struct_type.field_index("tags"), Some(3));
+    assert_eq!(struct_type.field_index("host"), None);
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Figure {
+        Rect { width: u32, height: u32 },
+    }
+
+    let Type::User(UserType::Enum(enum_type)) = Figure::SHAPE.ty else {
+        panic!("expected enum type");
+    };
+    let data = enum_type.variants[0].data;
+    assert_eq!(data.field_index("height"), Some(1));
+    assert_eq!(data.field_index("depth"), None);
+
+    // Shapes without the table fall back to a scan
+    let Type::User(UserType::Struct(range_type)) = core::ops::Range::<usize>::SHAPE.ty else {
+        panic!("expected struct type");
+    };
+    assert!(range_type.fields_by_name.is_empty());
+    assert_eq!(range_type.field_index("end"), Some(1));
+}
+
+#[test]
+fn transparent_inner_shape_is_const() {
+    #[derive(Facet)]
+    #[facet(transparent)]
+    #[allow(dead_code)]
+    struct Meters(u32);
+
+    // The inner shape can be reached without calling anything, so at compile time
+    const INNER: &Shape = match Meters::SHAPE.inner {
+        Some(inner) => match inner.as_static() {
+            Some(inner) => inner,
+            None => panic!("inner shape should be static"),
+        },
+        None => panic!("transparent structs have an inner shape"),
+    };
+    assert_eq!(INNER, u32::SHAPE);
+
+    // Smart pointers may point back at the type holding them, so theirs stays lazy
+    let boxed = Box::<u32>::SHAPE.inner.unwrap();
+    assert!(boxed.as_static().is_none());
+    assert_eq!(boxed.get(), u32::SHAPE);
+}
@@ -8,7 +8,7 @@ fn test_ordered_float() {
     let shape = <OrderedFloat<f64> as Facet>::SHAPE;
     assert!(shape.inner.is_some());
 
-    let inner_shape = (shape.inner.unwrap())();
+    let inner_shape = shape.inner.unwrap().get();
     assert_eq!(inner_shape.id, <f64 as Facet>::SHAPE.id);
 }
 
@@ -17,6 +17,6 @@ fn test_not_nan() {
     let shape = <NotNan<f64> as Facet>::SHAPE;
     assert!(shape.inner.is_some());
 
-    let inner_shape = (shape.inner.unwrap())();
+    let inner_shape = shape.inner.unwrap().get();
     assert_eq!(inner_shape.id, <f64 as Facet>::SHAPE.id);
 }
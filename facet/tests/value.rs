@@ -0,0 +1,186 @@
+#![cfg(feature = "reflect")]
+
+use std::collections::HashMap;
+use std::num::NonZeroU16;
+use std::sync::Arc;
+
+use facet::{Facet, FieldValue, MapEntry, PeekExt, Value, ValueErrorKind, from_value, to_value};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    name: String,
+    port: NonZeroU16,
+    weight: f32,
+    tags: Vec<String>,
+    limits: HashMap<String, u64>,
+    backup: Option<Box<Server>>,
+    shared: Arc<Mode>,
+    checksum: [u8; 4],
+    pair: (i8, char),
+    id: Id,
+    marker: Marker,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Mode {
+    Off,
+    Fixed(u32),
+    Range { low: i64, high: i64 },
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(transparent)]
+struct Id(u64);
+
+#[derive(Facet, Debug, PartialEq)]
+struct Marker;
+
+fn server(name: &str) -> Server {
+    Server {
+        name: name.into(),
+        port: NonZeroU16::new(8080).unwrap(),
+        weight: 0.1,
+        tags: vec!["edge".into(), "eu".into()],
+        limits: HashMap::from([("read".into(), 100)]),
+        backup: None,
+        shared: Arc::new(Mode::Range { low: -1, high: 5 }),
+        checksum: [1, 2, 3, 4],
+        pair: (-3, 'x'),
+        id: Id(7),
+        marker: Marker,
+    }
+}
+
+fn field(name: &str, value: Value) -> FieldValue {
+    FieldValue {
+        name: name.into(),
+        value,
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut original = server("primary");
+    original.backup = Some(Box::new(server("secondary")));
+
+    let value = to_value(&original);
+    assert_eq!(from_value::<Server>(&value).unwrap(), original);
+
+    // Nothing of the type is left over, just its data
+    let Value::Struct(fields) = &value else {
+        panic!("expected a struct, got {value:?}");
+    };
+    let get = |name: &str| &fields.iter().find(|f| f.name == name).unwrap().value;
+    assert_eq!(get("port"), &Value::U64(8080));
+    assert_eq!(get("weight"), &Value::F64(0.1f32.into()));
+    assert_eq!(
+        get("limits"),
+        &Value::Map(vec![MapEntry {
+            key: Value::String("read".into()),
+            value: Value::U64(100),
+        }])
+    );
+    assert_eq!(
+        get("shared"),
+        &Value::Variant {
+            name: "Range".into(),
+            fields: vec![field("low", Value::I64(-1)), field("high", Value::I64(5))],
+        }
+    );
+    assert_eq!(get("checksum"), &Value::Bytes(vec![1, 2, 3, 4]));
+    assert_eq!(
+        get("pair"),
+        &Value::List(vec![Value::I64(-3), Value::Char('x')])
+    );
+    assert_eq!(get("id"), &Value::U64(7));
+    assert_eq!(get("marker"), &Value::Unit);
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Event {
+    kind: String,
+    payload: Value,
+}
+
+#[test]
+fn values_are_facet_too() {
+    // Types can hold themselves through a `Vec`, so `Value` can
+    let tree = Tree {
+        label: "root".into(),
+        children: vec![Tree {
+            label: "leaf".into(),
+            children: vec![],
+        }],
+    };
+    assert_eq!(from_value::<Tree>(&to_value(&tree)).unwrap(), tree);
+
+    // A `Value` inside a type is copied as-is, not as the enum it is
+    let event = Event {
+        kind: "resize".into(),
+        payload: Value::List(vec![Value::U64(640), Value::U64(480)]),
+    };
+    let value = to_value(&event);
+    assert_eq!(
+        value,
+        Value::Struct(vec![
+            field("kind", Value::String("resize".into())),
+            field("payload", event.payload.clone()),
+        ])
+    );
+    assert_eq!(from_value::<Event>(&value).unwrap(), event);
+}
+
+#[test]
+fn errors_say_where() {
+    let with_fields = |edit: &dyn Fn(&mut Vec<FieldValue>)| {
+        let mut value = to_value(&server("primary"));
+        let Value::Struct(fields) = &mut value else {
+            unreachable!()
+        };
+        edit(fields);
+        from_value::<Server>(&value).unwrap_err()
+    };
+
+    let err = with_fields(&|fields| fields[1].value = Value::U64(70000));
+    assert_eq!(err.path, "/port");
+    assert!(matches!(err.kind, ValueErrorKind::OutOfRange { .. }));
+
+    let err = with_fields(&|fields| fields.push(field("color", Value::Unit)));
+    assert!(matches!(err.kind, ValueErrorKind::UnknownField { .. }));
+    assert_eq!(err.to_string(), "Server has no field `color`");
+
+    let mode = Value::Variant {
+        name: "Fixed".into(),
+        fields: vec![field("0", Value::String("3".into()))],
+    };
+    let err = from_value::<Mode>(&mode).unwrap_err();
+    assert_eq!(err.path, "/0");
+    assert!(matches!(
+        err.kind,
+        ValueErrorKind::Mismatch {
+            found: "a string",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn values_build_shapes_known_at_runtime() {
+    let shape = <Server as Facet>::SHAPE;
+    let value = to_value(&server("primary"));
+
+    let heap = value.into_wip(shape).unwrap().build().unwrap();
+    assert_eq!(heap.peek().shape(), shape);
+    assert_eq!(heap.peek().to_value(), to_value(&server("primary")));
+    assert_eq!(heap.materialize::<Server>().unwrap(), server("primary"));
+
+    let err = Value::Bool(true).into_wip(shape).unwrap_err();
+    assert!(matches!(err.kind, ValueErrorKind::Mismatch { .. }), "{err}");
+}